@@ -0,0 +1,282 @@
+//! Memory Access Log
+//!
+//! Every retrieval that surfaces a memory (recall, proactive context,
+//! relevance surfacing, search) is recorded against that memory: when, by
+//! which API key, through which endpoint, at what rank and score. The log is
+//! served per memory at /api/memory/{memory_id}/access_log, for debugging what
+//! gets injected into agents and for auditing who read what in shared
+//! deployments.
+//!
+//! Entries live in the `access_log` column family of the shared DB, keyed
+//! `{user_id}:{memory_id}:{timestamp_nanos}` so a memory's log is one prefix
+//! scan and a user's is removed with the rest of their data on purge. Entries
+//! older than the retention period are dropped on startup and skipped on
+//! read; each memory keeps at most `max_per_memory` of its newest entries.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, IteratorMode, Options, WriteBatch, DB};
+use serde::{Deserialize, Serialize};
+
+/// Column family holding access entries
+pub const CF_ACCESS_LOG: &str = "access_log";
+
+/// A memory's log is trimmed to `max_per_memory` every this many recordings
+const TRIM_INTERVAL: usize = 32;
+
+/// One retrieval that surfaced a memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryAccess {
+    pub accessed_at: DateTime<Utc>,
+    /// Fingerprint of the API key that made the request (see [`crate::auth::key_id`])
+    #[serde(default)]
+    pub key_id: Option<String>,
+    /// Route that surfaced the memory, e.g. "/api/recall"
+    pub endpoint: String,
+    /// Position in the results, starting at 1
+    pub rank: usize,
+    /// Score the endpoint gave the memory, when it scores results
+    #[serde(default)]
+    pub score: Option<f32>,
+}
+
+fn memory_prefix(user_id: &str, memory_id: &str) -> String {
+    format!("{user_id}:{memory_id}:")
+}
+
+fn nanos(at: DateTime<Utc>) -> i64 {
+    at.timestamp_nanos_opt().unwrap_or(0)
+}
+
+/// Persistent per-memory access log
+pub struct AccessLogStore {
+    db: Arc<DB>,
+    retention_days: u64,
+    max_per_memory: usize,
+    recordings: AtomicUsize,
+}
+
+impl AccessLogStore {
+    /// Column family descriptors required by the AccessLogStore.
+    /// The caller must include these (plus `"default"`) when opening the shared DB.
+    pub fn cf_descriptors() -> Vec<ColumnFamilyDescriptor> {
+        let mut cf_opts = Options::default();
+        cf_opts.create_if_missing(true);
+        vec![ColumnFamilyDescriptor::new(CF_ACCESS_LOG, cf_opts)]
+    }
+
+    /// Open the store. `retention_days == 0` turns recording off.
+    pub fn new(db: Arc<DB>, retention_days: u64, max_per_memory: usize) -> Self {
+        Self {
+            db,
+            retention_days,
+            max_per_memory: max_per_memory.max(1),
+            recordings: AtomicUsize::new(0),
+        }
+    }
+
+    fn cf(&self) -> &ColumnFamily {
+        self.db
+            .cf_handle(CF_ACCESS_LOG)
+            .expect("access_log CF must exist")
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.retention_days > 0
+    }
+
+    pub fn max_per_memory(&self) -> usize {
+        self.max_per_memory
+    }
+
+    fn cutoff(&self) -> DateTime<Utc> {
+        Utc::now() - Duration::days(self.retention_days.min(36_500) as i64)
+    }
+
+    /// Record that one request surfaced `hits` (memory id and score, best
+    /// first) for `user_id`
+    pub fn record(
+        &self,
+        user_id: &str,
+        endpoint: &str,
+        key_id: Option<&str>,
+        hits: &[(String, Option<f32>)],
+    ) -> Result<()> {
+        if !self.is_enabled() || hits.is_empty() {
+            return Ok(());
+        }
+        let accessed_at = Utc::now();
+        let mut batch = WriteBatch::default();
+        for (i, (memory_id, score)) in hits.iter().enumerate() {
+            let entry = MemoryAccess {
+                accessed_at,
+                key_id: key_id.map(str::to_string),
+                endpoint: endpoint.to_string(),
+                rank: i + 1,
+                score: *score,
+            };
+            // Entries of one request share a timestamp; the rank keeps keys unique
+            let key = format!(
+                "{}{:020}{:04}",
+                memory_prefix(user_id, memory_id),
+                nanos(accessed_at),
+                i
+            );
+            batch.put_cf(self.cf(), key.as_bytes(), serde_json::to_vec(&entry)?);
+        }
+        self.db.write(batch).context("Failed to write access log")?;
+
+        if self.recordings.fetch_add(1, Ordering::Relaxed) % TRIM_INTERVAL == 0 {
+            for (memory_id, _) in hits {
+                self.trim(user_id, memory_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Keys of a memory's entries, oldest first
+    fn keys(&self, user_id: &str, memory_id: &str) -> Result<Vec<Box<[u8]>>> {
+        let prefix = memory_prefix(user_id, memory_id);
+        let mut keys = Vec::new();
+        for item in self.db.prefix_iterator_cf(self.cf(), prefix.as_bytes()) {
+            let (key, _) = item.context("Failed to read access log")?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            keys.push(key);
+        }
+        Ok(keys)
+    }
+
+    /// Drop all but a memory's newest `max_per_memory` entries
+    fn trim(&self, user_id: &str, memory_id: &str) -> Result<usize> {
+        let keys = self.keys(user_id, memory_id)?;
+        let excess = keys.len().saturating_sub(self.max_per_memory);
+        if excess == 0 {
+            return Ok(0);
+        }
+        let mut batch = WriteBatch::default();
+        for key in &keys[..excess] {
+            batch.delete_cf(self.cf(), key);
+        }
+        self.db.write(batch)?;
+        Ok(excess)
+    }
+
+    /// A memory's accesses within the retention period, newest first
+    pub fn list(&self, user_id: &str, memory_id: &str, limit: usize) -> Result<Vec<MemoryAccess>> {
+        let cutoff = self.cutoff();
+        let prefix = memory_prefix(user_id, memory_id);
+        let mut entries = Vec::new();
+        for item in self.db.prefix_iterator_cf(self.cf(), prefix.as_bytes()) {
+            let (key, value) = item.context("Failed to read access log")?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            match serde_json::from_slice::<MemoryAccess>(&value) {
+                Ok(entry) if entry.accessed_at >= cutoff => entries.push(entry),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Skipping unreadable access log entry: {}", e),
+            }
+        }
+        entries.reverse();
+        entries.truncate(limit);
+        Ok(entries)
+    }
+
+    /// Delete every entry older than the retention period; returns how many
+    pub fn prune_expired(&self) -> Result<usize> {
+        if !self.is_enabled() {
+            return Ok(0);
+        }
+        let cutoff = self.cutoff();
+        let mut batch = WriteBatch::default();
+        let mut pruned = 0;
+        for item in self.db.iterator_cf(self.cf(), IteratorMode::Start) {
+            let (key, value) = item.context("Failed to read access log")?;
+            let expired = serde_json::from_slice::<MemoryAccess>(&value)
+                .map(|entry| entry.accessed_at < cutoff)
+                .unwrap_or(true);
+            if expired {
+                batch.delete_cf(self.cf(), &key);
+                pruned += 1;
+            }
+        }
+        if pruned > 0 {
+            self.db.write(batch)?;
+        }
+        Ok(pruned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_store(path: &std::path::Path, max_per_memory: usize) -> AccessLogStore {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let mut cfs = vec![ColumnFamilyDescriptor::new("default", Options::default())];
+        cfs.extend(AccessLogStore::cf_descriptors());
+        let db = DB::open_cf_descriptors(&opts, path, cfs).unwrap();
+        AccessLogStore::new(Arc::new(db), 30, max_per_memory)
+    }
+
+    #[test]
+    fn test_record_and_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = open_store(dir.path(), 100);
+        let hits = vec![("m1".to_string(), Some(0.9)), ("m2".to_string(), Some(0.4))];
+        store
+            .record("alice", "/api/recall", Some("abcd1234"), &hits)
+            .unwrap();
+        store
+            .record("alice", "/api/proactive_context", None, &hits[1..])
+            .unwrap();
+
+        let m2 = store.list("alice", "m2", 10).unwrap();
+        assert_eq!(m2.len(), 2);
+        assert_eq!(m2[0].endpoint, "/api/proactive_context");
+        assert_eq!(m2[0].rank, 1);
+        assert_eq!(m2[1].endpoint, "/api/recall");
+        assert_eq!(m2[1].rank, 2);
+        assert_eq!(m2[1].key_id.as_deref(), Some("abcd1234"));
+        assert_eq!(store.list("alice", "m1", 10).unwrap().len(), 1);
+        assert!(store.list("bob", "m1", 10).unwrap().is_empty());
+        assert_eq!(store.list("alice", "m2", 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_retention_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = open_store(dir.path(), 3);
+        let hits = vec![("m1".to_string(), None)];
+        for _ in 0..5 {
+            store.record("alice", "/api/recall", None, &hits).unwrap();
+        }
+        store.trim("alice", "m1").unwrap();
+        assert_eq!(store.list("alice", "m1", 100).unwrap().len(), 3);
+
+        let old = MemoryAccess {
+            accessed_at: Utc::now() - Duration::days(31),
+            key_id: None,
+            endpoint: "/api/recall".to_string(),
+            rank: 1,
+            score: None,
+        };
+        store
+            .db
+            .put_cf(
+                store.cf(),
+                format!("alice:m1:{:020}0000", nanos(old.accessed_at)),
+                serde_json::to_vec(&old).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(store.list("alice", "m1", 100).unwrap().len(), 3);
+        assert_eq!(store.prune_expired().unwrap(), 1);
+    }
+}
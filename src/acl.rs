@@ -0,0 +1,271 @@
+//! Namespace Access Control
+//!
+//! Read/write grants binding API keys to memory namespaces. A namespace is a
+//! user's store (`user_id`) or a shared team store (`teams/{team_id}`).
+//!
+//! A namespace without grants stays open to every authenticated key, so
+//! single-key deployments need no setup. Once a namespace has a grant, only
+//! keys granted on it (and admin keys from SHODH_ADMIN_API_KEYS) may use it:
+//! `read` covers retrieval, `write` covers retrieval plus storing, updating
+//! and deleting.
+//!
+//! Team namespaces are the exception once any grant exists: a team's members
+//! are the keys granted on it, so a key can't join a team just by naming it
+//! in `X-Shodh-Team`.
+//!
+//! Keys are referenced by [`crate::auth::key_id`] fingerprints, never stored
+//! in plain text. Grants persist in the `acl` column family of the shared DB.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, IteratorMode, Options, DB};
+use serde::{Deserialize, Serialize};
+
+use crate::handlers::teams::TEAMS_DIR;
+use crate::validation;
+
+/// Column family holding ACL grants ("{namespace}:{key_id}" -> AclGrant)
+pub const CF_ACL: &str = "acl";
+
+/// Access level a grant gives; `Write` includes `Read`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    Read,
+    Write,
+}
+
+impl Permission {
+    /// Whether this grant satisfies `required`
+    pub fn allows(self, required: Permission) -> bool {
+        self >= required
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Write => "write",
+        }
+    }
+}
+
+/// A key's access to one namespace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AclGrant {
+    pub namespace: String,
+    pub key_id: String,
+    pub permission: Permission,
+    /// Operator note, e.g. which component holds the key
+    #[serde(default)]
+    pub label: Option<String>,
+    pub granted_at: DateTime<Utc>,
+}
+
+/// Check that `namespace` is a user id or `teams/{team_id}`
+pub fn validate_namespace(namespace: &str) -> Result<()> {
+    match namespace
+        .strip_prefix(TEAMS_DIR)
+        .and_then(|rest| rest.strip_prefix('/'))
+    {
+        Some(team_id) => validation::validate_user_id(team_id),
+        None => validation::validate_user_id(namespace),
+    }
+}
+
+fn is_team_namespace(namespace: &str) -> bool {
+    namespace
+        .strip_prefix(TEAMS_DIR)
+        .is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn grant_key(namespace: &str, key_id: &str) -> String {
+    format!("{namespace}:{key_id}")
+}
+
+/// Persistent ACL grants with an in-memory index (namespace -> key_id -> grant)
+pub struct AclStore {
+    db: Arc<DB>,
+    grants: RwLock<HashMap<String, HashMap<String, AclGrant>>>,
+}
+
+impl AclStore {
+    /// Column family descriptors required by the AclStore.
+    /// The caller must include these (plus `"default"`) when opening the shared DB.
+    pub fn cf_descriptors() -> Vec<ColumnFamilyDescriptor> {
+        let mut cf_opts = Options::default();
+        cf_opts.create_if_missing(true);
+        vec![ColumnFamilyDescriptor::new(CF_ACL, cf_opts)]
+    }
+
+    /// Open the store and load every grant
+    pub fn new(db: Arc<DB>) -> Result<Self> {
+        let store = Self {
+            db,
+            grants: RwLock::new(HashMap::new()),
+        };
+        let mut grants: HashMap<String, HashMap<String, AclGrant>> = HashMap::new();
+        for item in store.db.iterator_cf(store.cf(), IteratorMode::Start) {
+            let (_, value) = item.context("Failed to read ACL grant")?;
+            match serde_json::from_slice::<AclGrant>(&value) {
+                Ok(grant) => {
+                    grants
+                        .entry(grant.namespace.clone())
+                        .or_default()
+                        .insert(grant.key_id.clone(), grant);
+                }
+                Err(e) => tracing::warn!("Skipping unreadable ACL grant: {}", e),
+            }
+        }
+        *store.grants.write() = grants;
+        Ok(store)
+    }
+
+    fn cf(&self) -> &ColumnFamily {
+        self.db.cf_handle(CF_ACL).expect("acl CF must exist")
+    }
+
+    /// Add or replace a key's grant on a namespace
+    pub fn grant(&self, grant: AclGrant) -> Result<()> {
+        self.db.put_cf(
+            self.cf(),
+            grant_key(&grant.namespace, &grant.key_id),
+            serde_json::to_vec(&grant)?,
+        )?;
+        self.grants
+            .write()
+            .entry(grant.namespace.clone())
+            .or_default()
+            .insert(grant.key_id.clone(), grant);
+        Ok(())
+    }
+
+    /// Remove a key's grant; returns whether one existed. Removing the last
+    /// grant on a namespace opens it again.
+    pub fn revoke(&self, namespace: &str, key_id: &str) -> Result<bool> {
+        let mut grants = self.grants.write();
+        let Some(keys) = grants.get_mut(namespace) else {
+            return Ok(false);
+        };
+        if keys.remove(key_id).is_none() {
+            return Ok(false);
+        }
+        if keys.is_empty() {
+            grants.remove(namespace);
+        }
+        self.db.delete_cf(self.cf(), grant_key(namespace, key_id))?;
+        Ok(true)
+    }
+
+    /// Grants, optionally for one namespace, ordered by namespace then key
+    pub fn list(&self, namespace: Option<&str>) -> Vec<AclGrant> {
+        let grants = self.grants.read();
+        let mut list: Vec<AclGrant> = grants
+            .iter()
+            .filter(|(ns, _)| namespace.is_none_or(|n| n == ns.as_str()))
+            .flat_map(|(_, keys)| keys.values().cloned())
+            .collect();
+        list.sort_by(|a, b| (&a.namespace, &a.key_id).cmp(&(&b.namespace, &b.key_id)));
+        list
+    }
+
+    /// Whether any namespace is restricted
+    pub fn is_empty(&self) -> bool {
+        self.grants.read().is_empty()
+    }
+
+    /// Whether `key_id` may access `namespace` at `required` level
+    /// (`None` for requests that carried no key)
+    pub fn allows(&self, namespace: &str, key_id: Option<&str>, required: Permission) -> bool {
+        let grants = self.grants.read();
+        match grants.get(namespace) {
+            // Teams without members are closed once ACLs are in use
+            None => grants.is_empty() || !is_team_namespace(namespace),
+            Some(keys) => key_id
+                .and_then(|id| keys.get(id))
+                .is_some_and(|g| g.permission.allows(required)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_store(path: &std::path::Path) -> AclStore {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let mut cfs = vec![ColumnFamilyDescriptor::new("default", Options::default())];
+        cfs.extend(AclStore::cf_descriptors());
+        let db = DB::open_cf_descriptors(&opts, path, cfs).unwrap();
+        AclStore::new(Arc::new(db)).unwrap()
+    }
+
+    fn grant(namespace: &str, key_id: &str, permission: Permission) -> AclGrant {
+        AclGrant {
+            namespace: namespace.to_string(),
+            key_id: key_id.to_string(),
+            permission,
+            label: None,
+            granted_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_grants_restrict_namespace() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = open_store(dir.path());
+
+        // Open until the first grant
+        assert!(store.allows("teams/platform", None, Permission::Write));
+
+        store
+            .grant(grant("teams/platform", "reader", Permission::Read))
+            .unwrap();
+        store
+            .grant(grant("teams/platform", "writer", Permission::Write))
+            .unwrap();
+
+        assert!(store.allows("teams/platform", Some("reader"), Permission::Read));
+        assert!(!store.allows("teams/platform", Some("reader"), Permission::Write));
+        assert!(store.allows("teams/platform", Some("writer"), Permission::Write));
+        assert!(!store.allows("teams/platform", Some("other"), Permission::Read));
+        assert!(!store.allows("teams/platform", None, Permission::Read));
+        // Other users stay open; other teams need membership
+        assert!(store.allows("alice", Some("other"), Permission::Write));
+        assert!(!store.allows("teams/infra", Some("writer"), Permission::Read));
+
+        assert!(store.revoke("teams/platform", "reader").unwrap());
+        assert!(!store.revoke("teams/platform", "reader").unwrap());
+        assert!(!store.allows("teams/platform", Some("reader"), Permission::Read));
+        assert!(store.revoke("teams/platform", "writer").unwrap());
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_grants_persist() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let store = open_store(dir.path());
+            store
+                .grant(grant("alice", "k1", Permission::Write))
+                .unwrap();
+        }
+        let store = open_store(dir.path());
+        let grants = store.list(Some("alice"));
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].permission, Permission::Write);
+        assert!(store.list(Some("bob")).is_empty());
+    }
+
+    #[test]
+    fn test_validate_namespace() {
+        assert!(validate_namespace("alice").is_ok());
+        assert!(validate_namespace("teams/platform").is_ok());
+        assert!(validate_namespace("teams/../etc").is_err());
+    }
+}
@@ -0,0 +1,402 @@
+//! Managed API Keys
+//!
+//! Keys created through /api/admin/keys, alongside the ones configured in
+//! SHODH_API_KEYS / SHODH_TENANT_API_KEYS. A managed key is shown once when
+//! it is created or rotated; only its SHA-256 is stored, in the `api_keys`
+//! column family of the shared DB, so keys can be issued and revoked without
+//! editing the environment and restarting.
+//!
+//! Each key carries a label, an optional tenant (see [`crate::tenant`]) and
+//! scopes. A key with scopes may only call routes one of them covers:
+//! `recall` for retrieval, `ingest` for storing, updating and deleting, and
+//! `admin` for everything including /api/admin/*, so a dashboard can hold a
+//! recall-only key and an integration an ingest-only one. A key without
+//! scopes is unrestricted, like the keys from the environment. A key with the
+//! `admin` scope (and no tenant) is an admin key.
+//! Revoked keys are kept, marked with when they were revoked, so the list
+//! still shows who held what. `last_used_at` is updated at most once a
+//! minute per key.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::RwLock;
+use rand::RngCore;
+use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, IteratorMode, Options, DB};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::auth;
+
+/// Column family holding managed keys (key_id -> ApiKeyRecord)
+pub const CF_API_KEYS: &str = "api_keys";
+
+/// Prefix of generated keys, so they are recognisable in config and logs
+const KEY_PREFIX: &str = "sk-shodh-";
+
+/// How stale `last_used_at` may get before a request persists it
+const LAST_USED_RESOLUTION_SECS: i64 = 60;
+
+/// What a key may do (see [`scopes_allow`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyScope {
+    /// Retrieval: recall, search, listing
+    Recall,
+    /// Storing, updating and deleting memories
+    Ingest,
+    /// Admin routes and ACL management
+    Admin,
+}
+
+impl KeyScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Recall => "recall",
+            Self::Ingest => "ingest",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+/// Whether a key with `scopes` may call a route that needs `required`.
+/// No scopes means unrestricted; `admin` covers every route.
+pub fn scopes_allow(scopes: &[KeyScope], required: KeyScope) -> bool {
+    scopes.is_empty() || scopes.contains(&required) || scopes.contains(&KeyScope::Admin)
+}
+
+/// A managed key, as stored (the key itself is never kept)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyRecord {
+    /// Fingerprint, as from [`auth::key_id`]; ACL grants reference it
+    pub key_id: String,
+    /// SHA-256 of the key (hex)
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub hash: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub tenant: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<KeyScope>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// Key this one replaced, when created by a rotation
+    #[serde(default)]
+    pub rotated_from: Option<String>,
+}
+
+impl ApiKeyRecord {
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    /// Copy safe to return from the API (no hash)
+    pub fn redacted(&self) -> Self {
+        Self {
+            hash: String::new(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Settings for a new key
+#[derive(Debug, Clone, Default)]
+pub struct NewKey {
+    pub label: Option<String>,
+    pub tenant: Option<String>,
+    pub scopes: Vec<KeyScope>,
+}
+
+fn key_hash(api_key: &str) -> String {
+    hex::encode(Sha256::digest(api_key.trim().as_bytes()))
+}
+
+fn generate_key() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    format!("{KEY_PREFIX}{}", hex::encode(bytes))
+}
+
+/// Persistent managed keys, indexed in memory by key id
+pub struct ApiKeyStore {
+    db: Arc<DB>,
+    keys: RwLock<HashMap<String, ApiKeyRecord>>,
+}
+
+impl ApiKeyStore {
+    /// Column family descriptors required by the ApiKeyStore.
+    /// The caller must include these (plus `"default"`) when opening the shared DB.
+    pub fn cf_descriptors() -> Vec<ColumnFamilyDescriptor> {
+        let mut cf_opts = Options::default();
+        cf_opts.create_if_missing(true);
+        vec![ColumnFamilyDescriptor::new(CF_API_KEYS, cf_opts)]
+    }
+
+    /// Open the store and load every key
+    pub fn new(db: Arc<DB>) -> Result<Self> {
+        let store = Self {
+            db,
+            keys: RwLock::new(HashMap::new()),
+        };
+        let mut keys = HashMap::new();
+        for item in store.db.iterator_cf(store.cf(), IteratorMode::Start) {
+            let (_, value) = item.context("Failed to read API key")?;
+            match serde_json::from_slice::<ApiKeyRecord>(&value) {
+                Ok(record) => {
+                    keys.insert(record.key_id.clone(), record);
+                }
+                Err(e) => tracing::warn!("Skipping unreadable API key record: {}", e),
+            }
+        }
+        *store.keys.write() = keys;
+        Ok(store)
+    }
+
+    fn cf(&self) -> &ColumnFamily {
+        self.db
+            .cf_handle(CF_API_KEYS)
+            .expect("api_keys CF must exist")
+    }
+
+    fn put(&self, record: &ApiKeyRecord) -> Result<()> {
+        self.db.put_cf(
+            self.cf(),
+            record.key_id.as_bytes(),
+            serde_json::to_vec(record)?,
+        )?;
+        Ok(())
+    }
+
+    /// Issue a new key. Returns the key, which isn't stored, and its record.
+    pub fn create(&self, new: NewKey) -> Result<(String, ApiKeyRecord)> {
+        self.create_with(new, None)
+    }
+
+    fn create_with(
+        &self,
+        new: NewKey,
+        rotated_from: Option<String>,
+    ) -> Result<(String, ApiKeyRecord)> {
+        if let Some(tenant) = &new.tenant {
+            crate::tenant::validate_tenant(tenant)?;
+        }
+        let mut keys = self.keys.write();
+        let (api_key, key_id) = loop {
+            let api_key = generate_key();
+            let key_id = auth::key_id(&api_key);
+            if !keys.contains_key(&key_id) {
+                break (api_key, key_id);
+            }
+        };
+        let mut scopes = new.scopes;
+        scopes.sort();
+        scopes.dedup();
+        let record = ApiKeyRecord {
+            key_id: key_id.clone(),
+            hash: key_hash(&api_key),
+            label: new.label,
+            tenant: new.tenant,
+            scopes,
+            created_at: Utc::now(),
+            last_used_at: None,
+            revoked_at: None,
+            rotated_from,
+        };
+        self.put(&record)?;
+        keys.insert(key_id, record.clone());
+        Ok((api_key, record))
+    }
+
+    /// Change a key's label and/or scopes
+    pub fn update(
+        &self,
+        key_id: &str,
+        label: Option<String>,
+        scopes: Option<Vec<KeyScope>>,
+    ) -> Result<Option<ApiKeyRecord>> {
+        let mut keys = self.keys.write();
+        let Some(record) = keys.get_mut(key_id) else {
+            return Ok(None);
+        };
+        if let Some(label) = label {
+            record.label = Some(label).filter(|l| !l.trim().is_empty());
+        }
+        if let Some(mut scopes) = scopes {
+            scopes.sort();
+            scopes.dedup();
+            record.scopes = scopes;
+        }
+        let record = record.clone();
+        self.put(&record)?;
+        Ok(Some(record))
+    }
+
+    /// Revoke a key; returns its record, or `None` if there is no such key
+    pub fn revoke(&self, key_id: &str) -> Result<Option<ApiKeyRecord>> {
+        let mut keys = self.keys.write();
+        let Some(record) = keys.get_mut(key_id) else {
+            return Ok(None);
+        };
+        if record.revoked_at.is_none() {
+            record.revoked_at = Some(Utc::now());
+        }
+        let record = record.clone();
+        self.put(&record)?;
+        Ok(Some(record))
+    }
+
+    /// Replace a key with a new one holding the same label, tenant and
+    /// scopes, and revoke the old one
+    pub fn rotate(&self, key_id: &str) -> Result<Option<(String, ApiKeyRecord)>> {
+        let Some(old) = self.get(key_id) else {
+            return Ok(None);
+        };
+        if old.is_revoked() {
+            return Err(anyhow!("key {key_id} is revoked"));
+        }
+        let rotated = self.create_with(
+            NewKey {
+                label: old.label,
+                tenant: old.tenant,
+                scopes: old.scopes,
+            },
+            Some(key_id.to_string()),
+        )?;
+        self.revoke(key_id)?;
+        Ok(Some(rotated))
+    }
+
+    pub fn get(&self, key_id: &str) -> Option<ApiKeyRecord> {
+        self.keys.read().get(key_id).cloned()
+    }
+
+    /// Every key, newest first
+    pub fn list(&self) -> Vec<ApiKeyRecord> {
+        let mut list: Vec<ApiKeyRecord> = self.keys.read().values().cloned().collect();
+        list.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        list
+    }
+
+    /// Record for `api_key` if it is a live managed key, noting the use
+    pub fn authenticate(&self, api_key: &str) -> Option<ApiKeyRecord> {
+        let key_id = auth::key_id(api_key);
+        let hash = key_hash(api_key);
+        let record = {
+            let keys = self.keys.read();
+            let record = keys.get(&key_id)?;
+            if !auth::constant_time_compare(&record.hash, &hash) || record.is_revoked() {
+                return None;
+            }
+            record.clone()
+        };
+
+        let now = Utc::now();
+        let stale = record
+            .last_used_at
+            .is_none_or(|t| now - t >= Duration::seconds(LAST_USED_RESOLUTION_SECS));
+        if stale {
+            let mut keys = self.keys.write();
+            if let Some(stored) = keys.get_mut(&key_id) {
+                stored.last_used_at = Some(now);
+                if let Err(e) = self.put(stored) {
+                    tracing::warn!("Failed to record use of API key {key_id}: {e}");
+                }
+            }
+        }
+        Some(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_store(path: &std::path::Path) -> ApiKeyStore {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let mut cfs = vec![ColumnFamilyDescriptor::new("default", Options::default())];
+        cfs.extend(ApiKeyStore::cf_descriptors());
+        let db = DB::open_cf_descriptors(&opts, path, cfs).unwrap();
+        ApiKeyStore::new(Arc::new(db)).unwrap()
+    }
+
+    #[test]
+    fn test_create_authenticate_revoke() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = open_store(dir.path());
+        let (key, record) = store
+            .create(NewKey {
+                label: Some("dashboard".to_string()),
+                scopes: vec![KeyScope::Recall, KeyScope::Recall],
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(key.starts_with(KEY_PREFIX));
+        assert_eq!(record.key_id, auth::key_id(&key));
+        assert_eq!(record.scopes, vec![KeyScope::Recall]);
+        assert!(!serde_json::to_string(&record.redacted())
+            .unwrap()
+            .contains("hash"));
+
+        let authed = store.authenticate(&key).unwrap();
+        assert_eq!(authed.label.as_deref(), Some("dashboard"));
+        assert!(store.get(&record.key_id).unwrap().last_used_at.is_some());
+        assert!(store.authenticate("sk-shodh-guess").is_none());
+
+        store.revoke(&record.key_id).unwrap();
+        assert!(store.authenticate(&key).is_none());
+        assert!(store.rotate(&record.key_id).is_err());
+    }
+
+    #[test]
+    fn test_scopes_allow() {
+        assert!(scopes_allow(&[], KeyScope::Admin));
+        assert!(scopes_allow(&[KeyScope::Recall], KeyScope::Recall));
+        assert!(!scopes_allow(&[KeyScope::Recall], KeyScope::Ingest));
+        assert!(!scopes_allow(&[KeyScope::Ingest], KeyScope::Recall));
+        assert!(!scopes_allow(
+            &[KeyScope::Recall, KeyScope::Ingest],
+            KeyScope::Admin
+        ));
+        assert!(scopes_allow(&[KeyScope::Admin], KeyScope::Ingest));
+    }
+
+    #[test]
+    fn test_rotation_and_persistence() {
+        let dir = tempfile::tempdir().unwrap();
+        let (old_key, new_key, new_id) = {
+            let store = open_store(dir.path());
+            let (old_key, old) = store
+                .create(NewKey {
+                    label: Some("cortex".to_string()),
+                    tenant: Some("acme".to_string()),
+                    scopes: vec![KeyScope::Ingest],
+                })
+                .unwrap();
+            let (new_key, new) = store.rotate(&old.key_id).unwrap().unwrap();
+            assert_eq!(new.rotated_from.as_deref(), Some(old.key_id.as_str()));
+            assert_eq!(new.tenant.as_deref(), Some("acme"));
+            assert_eq!(new.scopes, vec![KeyScope::Ingest]);
+            (old_key, new_key, new.key_id)
+        };
+
+        // Only hashes are stored, and they survive a reopen
+        let store = open_store(dir.path());
+        assert_eq!(store.list().len(), 2);
+        assert!(store.authenticate(&old_key).is_none());
+        assert_eq!(store.authenticate(&new_key).unwrap().key_id, new_id);
+        assert!(store
+            .create(NewKey {
+                tenant: Some("bad~tenant".to_string()),
+                ..Default::default()
+            })
+            .is_err());
+    }
+}
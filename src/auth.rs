@@ -1,12 +1,15 @@
 use axum::{
-    extract::Request,
+    extract::{Request, State},
     http::StatusCode,
     middleware::Next,
     response::{IntoResponse, Response},
     Json,
 };
+use sha2::{Digest, Sha256};
 use std::env;
+use std::sync::{Arc, Mutex};
 
+use crate::api_keys::{ApiKeyStore, KeyScope};
 use crate::errors::ErrorResponse;
 
 /// Default API key for development when no key env vars are configured.
@@ -24,7 +27,8 @@ pub fn is_production_mode() -> bool {
 pub fn log_security_status() {
     let has_api_keys = env::var("SHODH_API_KEYS")
         .map(|k| !k.trim().is_empty())
-        .unwrap_or(false);
+        .unwrap_or(false)
+        || tenants_configured();
     let has_dev_key = env::var("SHODH_DEV_API_KEY")
         .map(|k| !k.trim().is_empty())
         .unwrap_or(false);
@@ -131,7 +135,7 @@ impl IntoResponse for AuthError {
 ///
 /// Compares all bytes of both strings to prevent length-based timing leaks.
 /// The comparison time is constant regardless of where differences occur.
-fn constant_time_compare(a: &str, b: &str) -> bool {
+pub(crate) fn constant_time_compare(a: &str, b: &str) -> bool {
     let a_bytes = a.as_bytes();
     let b_bytes = b.as_bytes();
     let a_len = a_bytes.len();
@@ -156,6 +160,10 @@ fn constant_time_compare(a: &str, b: &str) -> bool {
 
 /// Validate API key against configured keys using constant-time comparison
 pub fn validate_api_key(provided_key: &str) -> Result<(), AuthError> {
+    if tenant_for_key(provided_key).is_some() {
+        return Ok(());
+    }
+
     // Get API keys from environment (comma-separated for multiple keys)
     let valid_keys = match env::var("SHODH_API_KEYS") {
         Ok(keys) if !keys.trim().is_empty() => keys,
@@ -205,8 +213,122 @@ pub fn validate_api_key(provided_key: &str) -> Result<(), AuthError> {
     }
 }
 
+/// Key that authenticated a request, stored in request extensions by `auth_middleware`
+#[derive(Debug, Clone)]
+pub struct AuthenticatedKey {
+    /// Fingerprint of the key (see [`key_id`]), never the key itself
+    pub key_id: String,
+    /// Listed in SHODH_ADMIN_API_KEYS
+    pub admin: bool,
+    /// Tenant the key belongs to (see [`crate::tenant`]); `None` for the default tenant
+    pub tenant: Option<String>,
+    /// Scopes of a managed key; empty for unrestricted keys
+    pub scopes: Vec<KeyScope>,
+}
+
+/// Stable, non-reversible identifier for an API key: the first 16 hex
+/// characters of its SHA-256. ACL grants reference keys by this id.
+pub fn key_id(api_key: &str) -> String {
+    let digest = hex::encode(Sha256::digest(api_key.trim().as_bytes()));
+    digest[..16].to_string()
+}
+
+/// Admin keys from SHODH_ADMIN_API_KEYS (comma-separated, each must also be a valid key)
+fn admin_keys() -> Vec<String> {
+    env::var("SHODH_ADMIN_API_KEYS")
+        .map(|keys| {
+            keys.split(',')
+                .map(|k| k.trim().to_string())
+                .filter(|k| !k.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether any admin keys are configured. Without them every valid key may
+/// use most admin routes (development default); with them only admin keys
+/// may. Changing ACL grants always needs an admin key.
+pub fn admin_keys_configured() -> bool {
+    !admin_keys().is_empty()
+}
+
+/// Whether `provided_key` is listed in SHODH_ADMIN_API_KEYS
+pub fn is_admin_key(provided_key: &str) -> bool {
+    let mut found = false;
+    for key in admin_keys() {
+        if constant_time_compare(&key, provided_key) {
+            found = true;
+        }
+    }
+    found
+}
+
+type TenantKeys = Arc<[(String, String)]>;
+
+/// Parsed SHODH_TENANT_API_KEYS, with the raw value it was parsed from
+static TENANT_KEYS: Mutex<Option<(String, TenantKeys)>> = Mutex::new(None);
+
+/// Tenant keys from SHODH_TENANT_API_KEYS, as (tenant, key) pairs
+///
+/// Format: comma-separated `tenant:key` entries, e.g.
+/// `acme:sk-acme-1,acme:sk-acme-2,globex:sk-globex`. Entries with an invalid
+/// tenant name are skipped with a warning. The value is parsed once and
+/// parsed again only if it changes.
+fn tenant_keys() -> TenantKeys {
+    let raw = env::var("SHODH_TENANT_API_KEYS").unwrap_or_default();
+    let mut cache = TENANT_KEYS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((parsed_from, keys)) = cache.as_ref() {
+        if *parsed_from == raw {
+            return keys.clone();
+        }
+    }
+    let keys: TenantKeys = parse_tenant_keys(&raw).into();
+    *cache = Some((raw, keys.clone()));
+    keys
+}
+
+fn parse_tenant_keys(raw: &str) -> Vec<(String, String)> {
+    raw.split(',')
+        .filter_map(|entry| {
+            let (tenant, key) = entry.trim().split_once(':')?;
+            let (tenant, key) = (tenant.trim(), key.trim());
+            if key.is_empty() {
+                return None;
+            }
+            if let Err(e) = crate::tenant::validate_tenant(tenant) {
+                tracing::warn!("Ignoring SHODH_TENANT_API_KEYS entry: {e}");
+                return None;
+            }
+            Some((tenant.to_string(), key.to_string()))
+        })
+        .collect()
+}
+
+/// Whether any tenant keys are configured
+pub fn tenants_configured() -> bool {
+    !tenant_keys().is_empty()
+}
+
+/// Tenant `provided_key` belongs to, if it is a tenant key
+pub fn tenant_for_key(provided_key: &str) -> Option<String> {
+    let mut found = None;
+    for (tenant, key) in tenant_keys().iter() {
+        if constant_time_compare(key, provided_key) {
+            found = Some(tenant.clone());
+        }
+    }
+    found
+}
+
 /// Authentication middleware
-pub async fn auth_middleware(request: Request, next: Next) -> Response {
+///
+/// Accepts managed keys from `keys` (see [`crate::api_keys`]) as well as the
+/// keys configured in the environment.
+pub async fn auth_middleware(
+    State(keys): State<Arc<ApiKeyStore>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
     let path = request.uri().path();
 
     // Skip auth for health endpoint
@@ -237,10 +359,28 @@ pub async fn auth_middleware(request: Request, next: Next) -> Response {
         None => return AuthError::MissingApiKey.into_response(),
     };
 
+    if let Some(record) = keys.authenticate(&api_key_value) {
+        request.extensions_mut().insert(AuthenticatedKey {
+            admin: record.tenant.is_none() && record.scopes.contains(&KeyScope::Admin),
+            key_id: record.key_id,
+            tenant: record.tenant,
+            scopes: record.scopes,
+        });
+        return next.run(request).await;
+    }
+
     // Validate the cloned key
     if let Err(e) = validate_api_key(&api_key_value) {
         return e.into_response();
     }
+    // Admin keys manage the whole brain, so tenant keys never are
+    let tenant = tenant_for_key(&api_key_value);
+    request.extensions_mut().insert(AuthenticatedKey {
+        key_id: key_id(&api_key_value),
+        admin: tenant.is_none() && is_admin_key(&api_key_value),
+        tenant,
+        scopes: Vec::new(),
+    });
 
     // Now we can move request to next layer
     next.run(request).await
@@ -263,6 +403,8 @@ mod tests {
         env::remove_var("SHODH_API_KEYS");
         env::remove_var("SHODH_DEV_API_KEY");
         env::remove_var("SHODH_ENV");
+        env::remove_var("SHODH_ADMIN_API_KEYS");
+        env::remove_var("SHODH_TENANT_API_KEYS");
     }
 
     // ── constant_time_compare ──
@@ -430,6 +572,49 @@ mod tests {
         clear_auth_env();
     }
 
+    // ── admin keys / key ids ──
+
+    #[test]
+    fn admin_keys_are_opt_in() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_auth_env();
+        assert!(!admin_keys_configured());
+        assert!(!is_admin_key("key1"));
+
+        env::set_var("SHODH_ADMIN_API_KEYS", " key1 ");
+        assert!(admin_keys_configured());
+        assert!(is_admin_key("key1"));
+        assert!(!is_admin_key("key2"));
+        clear_auth_env();
+    }
+
+    #[test]
+    fn tenant_keys_authenticate_and_resolve() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_auth_env();
+        env::set_var("SHODH_ENV", "production");
+        env::set_var(
+            "SHODH_TENANT_API_KEYS",
+            "acme:key-a1, acme:key-a2,globex:key-g,bad~name:key-x,default:key-d",
+        );
+        assert!(tenants_configured());
+        assert_eq!(tenant_for_key("key-a2").as_deref(), Some("acme"));
+        assert_eq!(tenant_for_key("key-g").as_deref(), Some("globex"));
+        assert!(validate_api_key("key-a1").is_ok());
+        // Invalid and reserved tenant names are skipped
+        assert!(tenant_for_key("key-x").is_none());
+        assert!(validate_api_key("key-d").is_err());
+        clear_auth_env();
+    }
+
+    #[test]
+    fn key_id_is_stable_and_opaque() {
+        assert_eq!(key_id("key1"), key_id(" key1 "));
+        assert_ne!(key_id("key1"), key_id("key2"));
+        assert_eq!(key_id("key1").len(), 16);
+        assert!(!key_id("key1").contains("key1"));
+    }
+
     #[test]
     fn validate_empty_dev_key_uses_default() {
         let _guard = ENV_LOCK.lock().unwrap();
@@ -520,15 +520,55 @@ impl ShodhBackupEngine {
     }
 
     /// Verify backup integrity using checksum
+    ///
+    /// RocksDB's own check (every file of the backup present with the
+    /// expected size) runs first, since shared SST files are not covered by
+    /// the stored checksum.
     pub fn verify_backup(&self, user_id: &str, backup_id: u32) -> Result<bool> {
         let metadata = self.load_metadata(user_id, backup_id)?;
         let backup_dir = self.backup_path.join(user_id);
 
+        let backup_opts = BackupEngineOptions::new(&backup_dir)?;
+        let env = Env::new()?;
+        let backup_engine = BackupEngine::open(&backup_opts, &env)?;
+        if let Err(e) = backup_engine.verify_backup(backup_id) {
+            tracing::warn!(backup_id = backup_id, user_id = user_id, error = %e, "RocksDB backup verification failed");
+            return Ok(false);
+        }
+
         let current_checksum = self.calculate_backup_checksum(&backup_dir, backup_id)?;
 
         Ok(current_checksum == metadata.checksum)
     }
 
+    /// Verify the newest backup of every user with backups
+    ///
+    /// Returns `(user_id, backup_id, intact)` per user. Used at startup so a
+    /// corrupted backup is noticed before it is needed for a restore.
+    pub fn verify_latest_backups(&self) -> Result<Vec<(String, u32, bool)>> {
+        let mut results = Vec::new();
+        for entry in fs::read_dir(&self.backup_path)?.filter_map(|e| e.ok()) {
+            if !entry.path().is_dir() {
+                continue;
+            }
+            let user_id = entry.file_name().to_string_lossy().to_string();
+            let latest = match self.list_backups(&user_id) {
+                Ok(backups) => backups.into_iter().max_by_key(|b| b.backup_id),
+                Err(e) => {
+                    tracing::warn!(user_id = %user_id, error = %e, "Failed to list backups");
+                    None
+                }
+            };
+            if let Some(latest) = latest {
+                let intact = self
+                    .verify_backup(&user_id, latest.backup_id)
+                    .unwrap_or(false);
+                results.push((user_id, latest.backup_id, intact));
+            }
+        }
+        Ok(results)
+    }
+
     // ========================================================================
     // Private helper methods
     // ========================================================================
@@ -614,7 +654,7 @@ pub fn copy_dir_recursive_pub(src: &Path, dst: &Path) -> Result<()> {
 }
 
 /// Calculate total size of a directory recursively
-fn dir_size(path: &Path) -> Result<u64> {
+pub fn dir_size(path: &Path) -> Result<u64> {
     let mut total = 0u64;
     if path.is_dir() {
         for entry in fs::read_dir(path)? {
@@ -679,4 +719,19 @@ mod tests {
         assert_eq!(metadata.backup_id, deserialized.backup_id);
         assert_eq!(metadata.user_id, deserialized.user_id);
     }
+
+    #[test]
+    fn test_verify_latest_backups() {
+        let db_dir = TempDir::new().unwrap();
+        let backup_dir = TempDir::new().unwrap();
+        let db = DB::open_default(db_dir.path()).unwrap();
+        db.put(b"key", b"value").unwrap();
+
+        let engine = ShodhBackupEngine::new(backup_dir.path().to_path_buf()).unwrap();
+        engine.create_backup(&db, "alice").unwrap();
+        let second = engine.create_backup(&db, "alice").unwrap();
+
+        let results = engine.verify_latest_backups().unwrap();
+        assert_eq!(results, vec![("alice".to_string(), second.backup_id, true)]);
+    }
 }
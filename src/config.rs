@@ -7,6 +7,9 @@ use std::env;
 use std::path::PathBuf;
 use tracing::info;
 
+use crate::memory::injection::{AgentScope, DiversityConfig, SanitizeMode, TypeInjectionPolicy};
+use crate::memory::secrets::PiiScrubConfig;
+
 /// CORS configuration
 #[derive(Debug, Clone)]
 pub struct CorsConfig {
@@ -219,12 +222,29 @@ pub struct ServerConfig {
     /// Audit log retention days (default: 30)
     pub audit_retention_days: u64,
 
+    /// Memory access log retention days (default: 30, 0 = access log off)
+    pub access_log_retention_days: u64,
+
+    /// Maximum access log entries kept per memory (default: 500)
+    pub access_log_max_entries_per_memory: usize,
+
+    /// Days deleted memories stay in the trash before being purged
+    /// (default: 30, 0 = deletes are permanent)
+    pub trash_retention_days: u64,
+
     /// Rate limit: requests per second (default: 4000 - LLM-friendly)
     pub rate_limit_per_second: u64,
 
     /// Rate limit: burst size (default: 8000 - allows rapid agent bursts)
     pub rate_limit_burst: u32,
 
+    /// Per-key rate limit: requests per second for each API key, shared by
+    /// all keys of a tenant (default: 0 - disabled)
+    pub key_rate_limit_per_second: u64,
+
+    /// Per-key rate limit: burst size (default: 2x the per-key rate)
+    pub key_rate_limit_burst: u32,
+
     /// Maximum concurrent requests (default: 200)
     pub max_concurrent_requests: usize,
 
@@ -258,10 +278,65 @@ pub struct ServerConfig {
     /// Whether backups are enabled (default: true in production, false in dev)
     pub backup_enabled: bool,
 
+    /// Off-box copy of each backup to an S3-compatible bucket (default: none)
+    pub backup_s3: Option<crate::backup_sink::S3Config>,
+
+    /// When heavy maintenance (consolidation, decay, re-embedding) may run
+    /// (default: fixed cadence, no sleep window)
+    pub sleep: crate::sleep::SleepConfig,
+
+    /// Merge near-duplicate memories on heavy maintenance cycles; losers go
+    /// to the trash (default: false)
+    pub merge_duplicates: bool,
+
+    /// Per-memory-type retention, enforced on heavy maintenance cycles
+    /// (default: keep everything)
+    pub retention: crate::memory::retention::RetentionPolicy,
+
+    /// Per-user memory count / byte limits; lowest importance × recency
+    /// memories are evicted when exceeded (default: unlimited)
+    pub quota: crate::memory::quota::QuotaConfig,
+
     /// Maximum entities extracted per memory for graph insertion (default: 10)
     /// Caps the number of NER/tag/regex entities to prevent O(n²) edge explosion
     /// in the knowledge graph. 10 entities → max 45 co-occurrence edges.
     pub max_entities_per_memory: usize,
+
+    /// Redact API keys, tokens and connection strings from memory content
+    /// before it is stored (default: false)
+    pub redact_secrets: bool,
+
+    /// PII categories scrubbed from memory content before it is stored
+    /// (default: none)
+    pub pii_scrub: PiiScrubConfig,
+
+    /// File of user-defined rules excluding interactions from storage, re-read
+    /// when it changes (default: none)
+    pub encoding_filters_path: Option<PathBuf>,
+
+    /// Per-memory-type injection policy for proactive context (default: all types, uncapped)
+    /// Request-level `memory_types` / `memory_type_limits` are layered on top.
+    pub injection_type_policy: TypeInjectionPolicy,
+
+    /// Prompt-injection sanitization applied to surfaced memory content (default: standard)
+    pub injection_sanitize_mode: SanitizeMode,
+
+    /// Whose memories an agent's proactive context may include: own, own_and_parent
+    /// or user (default: user)
+    pub injection_agent_scope: AgentScope,
+
+    /// MMR diversity + recency re-ranking of proactive candidates
+    /// (default: λ=0.7, recency weight 0.1, 72h half-life)
+    pub injection_diversity: DiversityConfig,
+
+    /// Turns a proactively injected memory stays suppressed within a session (default: 5, 0 = off)
+    pub injection_suppression_window: u64,
+
+    /// Per-consecutive-injection priority multiplier while suppressed (default: 0.6)
+    pub injection_suppression_decay: f32,
+
+    /// Maximum tokens of memory content injected per user per day (default: 0 = unlimited)
+    pub injection_daily_token_budget: usize,
 }
 
 impl Default for ServerConfig {
@@ -274,8 +349,13 @@ impl Default for ServerConfig {
             audit_max_entries_per_user: 10_000,
             audit_rotation_check_interval: 100,
             audit_retention_days: 30,
+            access_log_retention_days: 30,
+            access_log_max_entries_per_memory: 500,
+            trash_retention_days: 30,
             rate_limit_per_second: 4000,
             rate_limit_burst: 8000,
+            key_rate_limit_per_second: 0,
+            key_rate_limit_burst: 0,
             max_concurrent_requests: 200,
             request_timeout_secs: 60,
             is_production: false,
@@ -285,7 +365,22 @@ impl Default for ServerConfig {
             backup_interval_secs: 86400,   // 24 hours
             backup_max_count: 7,           // Keep 7 backups (1 week of daily backups)
             backup_enabled: false,         // Disabled by default, auto-enabled in production
-            max_entities_per_memory: 10,   // Cap entities per memory (10 → max 45 edges)
+            backup_s3: None,
+            sleep: crate::sleep::SleepConfig::default(),
+            merge_duplicates: false,
+            retention: crate::memory::retention::RetentionPolicy::default(),
+            quota: crate::memory::quota::QuotaConfig::default(),
+            max_entities_per_memory: 10, // Cap entities per memory (10 → max 45 edges)
+            redact_secrets: false,
+            pii_scrub: PiiScrubConfig::default(),
+            encoding_filters_path: None,
+            injection_type_policy: TypeInjectionPolicy::default(),
+            injection_sanitize_mode: SanitizeMode::Standard,
+            injection_agent_scope: AgentScope::User,
+            injection_diversity: DiversityConfig::default(),
+            injection_suppression_window: 5,
+            injection_suppression_decay: 0.6,
+            injection_daily_token_budget: 0,
         }
     }
 }
@@ -341,6 +436,24 @@ impl ServerConfig {
             }
         }
 
+        if let Ok(val) = env::var("SHODH_ACCESS_LOG_RETENTION_DAYS") {
+            if let Ok(n) = val.parse() {
+                config.access_log_retention_days = n;
+            }
+        }
+
+        if let Ok(val) = env::var("SHODH_ACCESS_LOG_MAX_ENTRIES") {
+            if let Ok(n) = val.parse() {
+                config.access_log_max_entries_per_memory = n;
+            }
+        }
+
+        if let Ok(val) = env::var("SHODH_TRASH_RETENTION_DAYS") {
+            if let Ok(n) = val.parse() {
+                config.trash_retention_days = n;
+            }
+        }
+
         // Rate limiting
         if let Ok(val) = env::var("SHODH_RATE_LIMIT") {
             if let Ok(n) = val.parse() {
@@ -354,6 +467,18 @@ impl ServerConfig {
             }
         }
 
+        if let Ok(val) = env::var("SHODH_KEY_RATE_LIMIT") {
+            if let Ok(n) = val.parse() {
+                config.key_rate_limit_per_second = n;
+            }
+        }
+
+        if let Ok(val) = env::var("SHODH_KEY_RATE_BURST") {
+            if let Ok(n) = val.parse() {
+                config.key_rate_limit_burst = n;
+            }
+        }
+
         // Concurrency
         if let Ok(val) = env::var("SHODH_MAX_CONCURRENT") {
             if let Ok(n) = val.parse() {
@@ -404,6 +529,10 @@ impl ServerConfig {
             // Auto-enable in production
             config.backup_enabled = true;
         }
+        config.backup_s3 = crate::backup_sink::S3Config::from_env();
+        config.sleep = crate::sleep::SleepConfig::from_env();
+        config.retention = crate::memory::retention::RetentionPolicy::from_env();
+        config.quota = crate::memory::quota::QuotaConfig::from_env();
 
         // Entity extraction cap
         if let Ok(val) = env::var("SHODH_MAX_ENTITIES") {
@@ -412,6 +541,90 @@ impl ServerConfig {
             }
         }
 
+        if let Ok(val) = env::var("SHODH_MERGE_DUPLICATES") {
+            config.merge_duplicates = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = env::var("SHODH_REDACT_SECRETS") {
+            config.redact_secrets = val.to_lowercase() == "true" || val == "1";
+        }
+        if let Ok(val) = env::var("SHODH_SCRUB_PII") {
+            config.pii_scrub = PiiScrubConfig::from_list(&val);
+        }
+        if let Ok(val) = env::var("SHODH_ENCODING_FILTERS") {
+            if !val.trim().is_empty() {
+                config.encoding_filters_path = Some(PathBuf::from(val.trim()));
+            }
+        }
+
+        // Per-type injection policy
+        let injected_types: Vec<String> = env::var("SHODH_INJECT_TYPES")
+            .map(|v| v.split(',').map(|s| s.to_string()).collect())
+            .unwrap_or_default();
+        let injected_type_limits = env::var("SHODH_INJECT_TYPE_LIMITS")
+            .map(|v| TypeInjectionPolicy::parse_limits(&v))
+            .unwrap_or_default();
+        config.injection_type_policy =
+            TypeInjectionPolicy::new(injected_types, injected_type_limits);
+
+        if let Ok(val) = env::var("SHODH_SANITIZE_MODE") {
+            match SanitizeMode::from_str_loose(&val) {
+                Some(mode) => config.injection_sanitize_mode = mode,
+                None => tracing::warn!(
+                    "Invalid SHODH_SANITIZE_MODE '{}', expected off|standard|strict",
+                    val
+                ),
+            }
+        }
+
+        if let Ok(val) = env::var("SHODH_AGENT_SCOPE") {
+            match AgentScope::from_str_loose(&val) {
+                Some(scope) => config.injection_agent_scope = scope,
+                None => tracing::warn!(
+                    "Invalid SHODH_AGENT_SCOPE '{}', expected own|own_and_parent|user",
+                    val
+                ),
+            }
+        }
+
+        // Diversity re-ranking (MMR)
+        if let Ok(val) = env::var("SHODH_MMR_LAMBDA") {
+            if let Ok(n) = val.parse::<f32>() {
+                config.injection_diversity.lambda = n.clamp(0.0, 1.0);
+            }
+        }
+
+        if let Ok(val) = env::var("SHODH_MMR_RECENCY_WEIGHT") {
+            if let Ok(n) = val.parse::<f32>() {
+                config.injection_diversity.recency_weight = n.clamp(0.0, 1.0);
+            }
+        }
+
+        if let Ok(val) = env::var("SHODH_MMR_RECENCY_HALF_LIFE") {
+            if let Ok(n) = val.parse::<f32>() {
+                config.injection_diversity.recency_half_life_hours = n.max(1.0);
+            }
+        }
+
+        // Repeat-injection suppression within a session
+        if let Ok(val) = env::var("SHODH_SUPPRESSION_WINDOW") {
+            if let Ok(n) = val.parse() {
+                config.injection_suppression_window = n;
+            }
+        }
+
+        if let Ok(val) = env::var("SHODH_SUPPRESSION_DECAY") {
+            if let Ok(n) = val.parse::<f32>() {
+                config.injection_suppression_decay = n.clamp(0.0, 1.0);
+            }
+        }
+
+        if let Ok(val) = env::var("SHODH_INJECT_DAILY_TOKEN_BUDGET") {
+            if let Ok(n) = val.parse() {
+                config.injection_daily_token_budget = n;
+            }
+        }
+
         config
     }
 
@@ -428,6 +641,24 @@ impl ServerConfig {
         );
         info!("   Port: {}", self.port);
         info!("   Storage: {:?}", self.storage_path);
+        match crate::memory::backend::BackendKind::from_env() {
+            Ok(backend) => info!("   Storage backend: {}", backend.as_str()),
+            Err(e) => tracing::warn!("   Storage backend: {e}"),
+        }
+        match crate::memory::encryption::keyring() {
+            Ok(Some(keyring)) => info!(
+                "   Encryption at rest: XChaCha20-Poly1305 (key {})",
+                keyring.current_key_id()
+            ),
+            Ok(None) => info!("   Encryption at rest: disabled"),
+            Err(e) => tracing::warn!("   Encryption at rest: {e}"),
+        }
+        let compression = crate::memory::blob_compression::settings();
+        if compression.enabled {
+            info!("   Compression: zstd level {}", compression.level);
+        } else {
+            info!("   Compression: disabled");
+        }
         info!("   Max users in memory: {}", self.max_users_in_memory);
         if self.rate_limit_per_second > 0 {
             info!(
@@ -437,9 +668,30 @@ impl ServerConfig {
         } else {
             info!("   Rate limit: disabled");
         }
+        if self.key_rate_limit_per_second > 0 {
+            info!(
+                "   Per-key rate limit: {} req/sec (burst: {})",
+                self.key_rate_limit_per_second,
+                crate::rate_limit::burst_or_default(
+                    self.key_rate_limit_per_second,
+                    self.key_rate_limit_burst
+                )
+            );
+        }
         info!("   Max concurrent: {}", self.max_concurrent_requests);
         info!("   Request timeout: {}s", self.request_timeout_secs);
         info!("   Audit retention: {} days", self.audit_retention_days);
+        if self.access_log_retention_days > 0 {
+            info!(
+                "   Access log retention: {} days, {} entries per memory",
+                self.access_log_retention_days, self.access_log_max_entries_per_memory
+            );
+        }
+        if self.trash_retention_days > 0 {
+            info!("   Trash retention: {} days", self.trash_retention_days);
+        } else {
+            info!("   Trash: off (deletes are permanent)");
+        }
         if self.cors.is_restricted() {
             info!("   CORS origins: {:?}", self.cors.allowed_origins);
         } else {
@@ -455,9 +707,84 @@ impl ServerConfig {
                 "   Backup: enabled (every {}h, keep {})",
                 interval_hours, self.backup_max_count
             );
+            if let Some(s3) = &self.backup_s3 {
+                info!(
+                    "   Backup upload: s3://{}/{} via {} (retention: {}d)",
+                    s3.bucket, s3.prefix, s3.endpoint, s3.retention_days
+                );
+            }
         } else {
             info!("   Backup: disabled");
         }
+        if self.sleep.is_enabled() {
+            info!(
+                "   Sleep-phase consolidation: window {:?}, idle after {}m",
+                self.sleep.window.map(|(s, e)| format!(
+                    "{:02}:{:02}-{:02}:{:02} UTC",
+                    s / 60,
+                    s % 60,
+                    e / 60,
+                    e % 60
+                )),
+                self.sleep.idle_minutes
+            );
+        }
+        if self.merge_duplicates {
+            info!("   Near-duplicate merging: on heavy maintenance cycles");
+        }
+        info!(
+            "   Embeddings: {}",
+            crate::embeddings::ProviderConfig::from_env().describe()
+        );
+        if !self.retention.is_empty() {
+            info!("   Retention policy: {:?}", self.retention.rules());
+        }
+        if !self.quota.is_unlimited() {
+            info!(
+                "   Memory quota: {} memories, {} bytes per user",
+                self.quota
+                    .max_memories
+                    .map_or("unlimited".to_string(), |n| n.to_string()),
+                self.quota
+                    .max_bytes
+                    .map_or("unlimited".to_string(), |n| n.to_string())
+            );
+        }
+        if self.redact_secrets {
+            info!("   Secret redaction: enabled");
+        }
+        if self.pii_scrub.is_enabled() {
+            info!("   PII scrubbing: {:?}", self.pii_scrub.categories);
+        }
+        if let Some(ref path) = self.encoding_filters_path {
+            info!("   Encoding filters: {}", path.display());
+        }
+        if !self.injection_type_policy.is_unrestricted() {
+            info!(
+                "   Injection types: {:?} (limits: {:?})",
+                self.injection_type_policy.allowed_types, self.injection_type_policy.type_limits
+            );
+        }
+        info!(
+            "   Injection re-ranking: MMR λ={:.2}, recency weight {:.2} (half-life {}h)",
+            self.injection_diversity.lambda,
+            self.injection_diversity.recency_weight,
+            self.injection_diversity.recency_half_life_hours
+        );
+        if self.injection_suppression_window > 0 {
+            info!(
+                "   Repeat suppression: {} turns (decay {:.2})",
+                self.injection_suppression_window, self.injection_suppression_decay
+            );
+        } else {
+            info!("   Repeat suppression: disabled");
+        }
+        if self.injection_daily_token_budget > 0 {
+            info!(
+                "   Injection budget: {} tokens/user/day",
+                self.injection_daily_token_budget
+            );
+        }
     }
 }
 
@@ -474,13 +801,21 @@ pub fn print_env_help() {
     println!("  SHODH_MEMORY_PATH      - Storage directory (default: ./shodh_memory_data)");
     println!("  SHODH_API_KEYS         - Comma-separated API keys (required in production)");
     println!("  SHODH_DEV_API_KEY      - Development API key (required in dev if SHODH_API_KEYS not set)");
+    println!("  SHODH_TENANT_API_KEYS  - Comma-separated tenant:key pairs; each tenant's users are isolated");
     println!("  SHODH_MAX_USERS        - Max users in memory LRU (default: 1000)");
     println!("  SHODH_RATE_LIMIT       - Requests per second (default: 4000)");
     println!("  SHODH_RATE_BURST       - Burst size (default: 8000)");
+    println!("  SHODH_KEY_RATE_LIMIT   - Requests per second per API key/tenant (default: 0, off)");
+    println!("  SHODH_KEY_RATE_BURST   - Per-key burst size (default: 2x SHODH_KEY_RATE_LIMIT)");
     println!("  SHODH_MAX_CONCURRENT   - Max concurrent requests (default: 200)");
     println!("  SHODH_REQUEST_TIMEOUT  - Request timeout in seconds (default: 60)");
     println!("  SHODH_AUDIT_MAX_ENTRIES    - Max audit entries per user (default: 10000)");
     println!("  SHODH_AUDIT_RETENTION_DAYS - Audit log retention days (default: 30)");
+    println!("  SHODH_ACCESS_LOG_RETENTION_DAYS - Memory access log retention days (default: 30, 0 = off)");
+    println!(
+        "  SHODH_ACCESS_LOG_MAX_ENTRIES    - Max access log entries per memory (default: 500)"
+    );
+    println!("  SHODH_TRASH_RETENTION_DAYS      - Days deleted memories stay restorable (default: 30, 0 = off)");
     println!();
     println!("Integration APIs:");
     println!("  LINEAR_API_URL         - Linear GraphQL API URL (default: https://api.linear.app/graphql)");
@@ -495,10 +830,99 @@ pub fn print_env_help() {
     println!("  SHODH_CORS_CREDENTIALS - Allow credentials true/false (default: false)");
     println!("  SHODH_CORS_MAX_AGE     - Preflight cache seconds (default: 86400)");
     println!();
+    println!("Storage Backend:");
+    println!("  SHODH_STORAGE_BACKEND    - rocksdb (embedded, per user) or postgres (shared, needs --features postgres) (default: rocksdb)");
+    println!("  SHODH_POSTGRES_URL       - Connection string, e.g. postgres://shodh:secret@db:5432/shodh (required for postgres)");
+    println!("  SHODH_POSTGRES_POOL_SIZE - Max pooled connections per instance (default: 16)");
+    println!("  SHODH_POSTGRES_CA_FILE   - PEM CA certificates trusted for TLS, besides the public roots (default: none)");
+    println!("                             TLS follows the URL's sslmode: prefer (default), require or disable");
+    println!();
+    println!("Encryption at Rest (XChaCha20-Poly1305, re-seal after rotation with POST /api/admin/reencrypt):");
+    println!("  SHODH_ENCRYPTION_KEY           - 32-byte key as hex or base64 (default: none, records stored in plaintext)");
+    println!("  SHODH_ENCRYPTION_KEY_FILE      - File with one key per line, first is current, e.g. a KMS/secret mount");
+    println!(
+        "  SHODH_ENCRYPTION_PREVIOUS_KEYS - Comma-separated old keys still accepted for reading"
+    );
+    println!();
+    println!("Compression (memory records, exports and backup snapshots):");
+    println!("  SHODH_COMPRESSION - zstd or none; existing records stay readable either way (default: zstd)");
+    println!("  SHODH_ZSTD_LEVEL  - zstd level 1-19 (default: 3)");
+    println!();
     println!("Backup Configuration:");
     println!("  SHODH_BACKUP_ENABLED   - Enable automatic backups true/false (default: auto in production)");
     println!("  SHODH_BACKUP_INTERVAL  - Backup interval in seconds (default: 86400 = 24 hours)");
     println!("  SHODH_BACKUP_MAX_COUNT - Max backups to keep per user (default: 7)");
+    println!("  SHODH_BACKUP_S3_BUCKET - Upload each backup to this S3-compatible bucket (default: none)");
+    println!("  SHODH_BACKUP_S3_ENDPOINT - Endpoint URL for MinIO/GCS/etc. (default: AWS S3 for the region)");
+    println!("  SHODH_BACKUP_S3_REGION - Signing region (default: us-east-1)");
+    println!("  SHODH_BACKUP_S3_PREFIX - Object key prefix (default: shodh-backups)");
+    println!("  SHODH_BACKUP_S3_ACCESS_KEY / SHODH_BACKUP_S3_SECRET_KEY - Credentials (default: AWS_ACCESS_KEY_ID / AWS_SECRET_ACCESS_KEY)");
+    println!("  SHODH_BACKUP_S3_PATH_STYLE - Path-style bucket addressing true/false (default: true with a custom endpoint)");
+    println!("  SHODH_BACKUP_S3_RETENTION_DAYS - Expire uploaded backups via a bucket lifecycle rule (merged with existing rules), 0 = unmanaged (default: 30)");
+    println!();
+    println!("Sleep-Phase Consolidation (heavy maintenance deferred to quiet periods):");
+    println!("  SHODH_SLEEP_WINDOW        - Nightly window HH:MM-HH:MM in UTC, may wrap midnight (default: none)");
+    println!("  SHODH_SLEEP_IDLE_MINUTES  - Also sleep after this many minutes without API requests, 0 = off (default: 0)");
+    println!("  SHODH_SLEEP_MAX_GAP_HOURS - Force heavy maintenance if none ran for this long (default: 24)");
+    println!("  SHODH_MERGE_DUPLICATES    - Merge near-duplicates on heavy cycles, duplicates go to the trash (default: false)");
+    println!();
+    println!("Retention (expired memories are deleted on heavy maintenance cycles):");
+    println!(
+        "  SHODH_RETENTION_POLICY - Comma-separated type=rule pairs, rule is <N>d, run or never"
+    );
+    println!("                           e.g. conversation=30d,task=run,decision=never (default: keep all)");
+    println!();
+    println!("Quotas (lowest importance x recency evicted first, pinned memories kept):");
+    println!("  SHODH_QUOTA_MAX_MEMORIES - Max memories per user, 0 = unlimited (default: 0)");
+    println!("  SHODH_QUOTA_MAX_BYTES    - Max stored bytes per user, 0 = unlimited (default: 0)");
+    println!();
+    println!("Vector Index (Vamana ANN graph, applied when a user's index is opened):");
+    println!("  SHODH_VECTOR_MAX_DEGREE - Max neighbors per node, like HNSW M (default: 32)");
+    println!("  SHODH_VECTOR_BUILD_LIST - Candidate list size while inserting, like HNSW ef_construction (default: 100)");
+    println!("  SHODH_VECTOR_SEARCH_EF  - Min candidate list size per query, like HNSW ef_search, 0 = k (default: 0)");
+    println!();
+    println!("Embeddings (existing stores keep their model until POST /api/admin/reembed or `shodh reembed`):");
+    println!(
+        "  SHODH_EMBEDDING_PROVIDER  - local, openai or cohere (default: local MiniLM-L6-v2 ONNX)"
+    );
+    println!("  SHODH_EMBEDDING_MODEL     - Hosted model (default: text-embedding-3-small / embed-english-v3.0)");
+    println!(
+        "  SHODH_EMBEDDING_API_KEY   - Provider API key (default: OPENAI_API_KEY / COHERE_API_KEY)"
+    );
+    println!("  SHODH_EMBEDDING_BASE_URL  - API base URL, e.g. an OpenAI-compatible gateway (default: provider API)");
+    println!("  SHODH_EMBEDDING_DIMENSION - Vector size; required for unlisted models, shortens OpenAI v3 vectors");
+    println!("  SHODH_EMBED_BATCH_SIZE     - Texts per embedding call for import and bulk sync (default: 32)");
+    println!("  SHODH_EMBED_BATCH_WAIT_MS  - Max wait for a batch to fill (default: 20)");
+    println!("  SHODH_EMBED_WORKERS        - Batches embedded concurrently (default: 2)");
+    println!(
+        "  SHODH_EMBED_QUEUE_CAPACITY - Queued texts before bulk ingest waits (default: 1024)"
+    );
+    println!();
+    println!("Hybrid Retrieval (reciprocal-rank fusion of BM25 and vector results):");
+    println!("  SHODH_HYBRID_BM25_WEIGHT   - RRF weight for BM25 keyword matches, 0.0-1.0 (default: 0.35)");
+    println!(
+        "  SHODH_HYBRID_VECTOR_WEIGHT - RRF weight for vector similarity, 0.0-1.0 (default: 0.40)"
+    );
+    println!("  SHODH_HYBRID_RRF_K         - RRF constant, lower favors top ranks (default: 45)");
+    println!();
+    println!("Memory Ingestion:");
+    println!("  SHODH_REDACT_SECRETS   - Redact secrets from memory content before storing true/false (default: false)");
+    println!("  SHODH_SCRUB_PII        - Comma-separated PII to scrub before storing: email,phone,ip,name or all (default: none)");
+    println!("  SHODH_ENCODING_FILTERS - File of content:/tool:/model: rules excluding interactions from storage, reloaded on change (default: none)");
+    println!();
+    println!("Injection Policy:");
+    println!("  SHODH_INJECT_TYPES       - Comma-separated memory types eligible for proactive injection (default: all)");
+    println!("  SHODH_INJECT_TYPE_LIMITS - Per-type caps, e.g. Decision=3,Conversation=0 (0 disables a type)");
+    println!("  SHODH_SANITIZE_MODE      - Prompt-injection sanitization: off, standard, strict (default: standard)");
+    println!("  SHODH_AGENT_SCOPE        - Memories an agent's context may include: own, own_and_parent, user (default: user)");
+    println!("  SHODH_MMR_LAMBDA         - Relevance vs. diversity trade-off, 1.0 disables MMR (default: 0.7)");
+    println!("  SHODH_MMR_RECENCY_WEIGHT - Recency boost applied during re-ranking (default: 0.1)");
+    println!("  SHODH_MMR_RECENCY_HALF_LIFE - Hours until the recency boost halves (default: 72)");
+    println!("  SHODH_SUPPRESSION_WINDOW - Turns a re-injected memory stays suppressed per session, 0 disables (default: 5)");
+    println!(
+        "  SHODH_SUPPRESSION_DECAY  - Priority multiplier per consecutive injection (default: 0.6)"
+    );
+    println!("  SHODH_INJECT_DAILY_TOKEN_BUDGET - Max injected memory tokens per user per day, 0 = unlimited (default: 0)");
     println!();
     println!("  RUST_LOG               - Log level (e.g., info, debug, trace)");
     println!();
@@ -47,6 +47,26 @@ pub const HEBBIAN_DECAY_MISLEADING: f32 = 0.10;
 /// - Allows for context-dependent recovery
 pub const IMPORTANCE_FLOOR: f32 = 0.05;
 
+/// Maximum strength multiplier for a single reinforcement
+///
+/// Callers can grade an outcome (e.g. "Thanks!" vs "PERFECT, exactly right")
+/// with a strength that scales the Hebbian boost/decay above. 1.0 is a normal
+/// signal; the cap keeps one emphatic reaction from outweighing a history of use.
+///
+/// Justification:
+/// - 2x boost (5%) stays within the biological range cited for HEBBIAN_BOOST_HELPFUL
+/// - 2x decay (20%) demotes a clearly wrong memory quickly without zeroing it
+pub const MAX_REINFORCEMENT_STRENGTH: f32 = 2.0;
+
+/// Reinforcement strength when identical content is stored again
+///
+/// A repeated interaction is evidence the memory matters, but a weaker one
+/// than explicit "this helped" feedback; client retries shouldn't inflate it much.
+///
+/// Justification:
+/// - 0.5x boost (1.25%) per repeat: ten repeats ≈ one strong helpful signal
+pub const DUPLICATE_REINFORCEMENT_STRENGTH: f32 = 0.5;
+
 // =============================================================================
 // MEMORY GRAPH EDGE CONSTANTS
 // =============================================================================
@@ -284,6 +304,28 @@ pub const HYBRID_GRAPH_WEIGHT: f32 = 0.35;
 /// - Handles specific entity/keyword queries
 pub const HYBRID_LINGUISTIC_WEIGHT: f32 = 0.15;
 
+/// Weight of graph proximity in related-memory recall (/api/recall/related)
+///
+/// Justification:
+/// - The walk starts from a concrete seed, so the graph path is the primary
+///   evidence of relatedness; relevance (embedding similarity to the seed
+///   memory, or importance for entity seeds) breaks ties between equally
+///   close memories
+pub const RELATED_GRAPH_WEIGHT: f32 = 0.6;
+
+/// Weight of seed relevance in related-memory recall
+pub const RELATED_RELEVANCE_WEIGHT: f32 = 0.4;
+
+/// Maximum hops walked from the seed in related-memory recall
+///
+/// Justification:
+/// - Each hop multiplies candidates by the branching factor; past 3 hops
+///   almost everything in a dense graph is "related"
+pub const RELATED_MAX_DEPTH: usize = 3;
+
+/// Default width of the walk: entities kept per hop and memories per entity
+pub const RELATED_DEFAULT_WIDTH: usize = 10;
+
 // =============================================================================
 // DENSITY-DEPENDENT RETRIEVAL WEIGHTS (SHO-26)
 // Based on GraphRAG Survey (arXiv 2408.08921) - hybrid KG-Vector improves 13.1%
@@ -861,6 +903,45 @@ pub const MEMORY_TIER_GRAPH_MULT_LONGTERM: f32 = 1.0;
 /// - Boost graph weight to surface archived knowledge
 pub const MEMORY_TIER_GRAPH_MULT_ARCHIVE: f32 = 1.2;
 
+/// Retrieval score multiplier for Working tier (working store) memories
+///
+/// Justification:
+/// - 0.9 - unvetted, noisy; recency scoring already favours them
+pub const MEMORY_TIER_RECALL_WEIGHT_WORKING: f32 = 0.9;
+
+/// Retrieval score multiplier for Session tier (episodic store) memories
+pub const MEMORY_TIER_RECALL_WEIGHT_SESSION: f32 = 1.0;
+
+/// Retrieval score multiplier for LongTerm/Archive tier (semantic store) memories
+///
+/// Justification:
+/// - 1.1 - consolidated memories proved their importance to get there
+pub const MEMORY_TIER_RECALL_WEIGHT_LONGTERM: f32 = 1.1;
+
+/// Forgetting-curve time scale for Working tier memories
+///
+/// Days since access are multiplied by this before computing retention
+/// strength, so values > 1.0 forget faster.
+///
+/// Justification:
+/// - 2.0 - working memory is short-lived (Cowan 2001)
+pub const MEMORY_TIER_FORGET_SCALE_WORKING: f64 = 2.0;
+
+/// Forgetting-curve time scale for Session tier memories
+pub const MEMORY_TIER_FORGET_SCALE_SESSION: f64 = 1.5;
+
+/// Forgetting-curve time scale for LongTerm tier memories (the baseline curve)
+pub const MEMORY_TIER_FORGET_SCALE_LONGTERM: f64 = 1.0;
+
+/// Forgetting-curve time scale for Archive tier memories
+///
+/// Justification:
+/// - 0.5 - archived knowledge is consolidated and near-permanent
+pub const MEMORY_TIER_FORGET_SCALE_ARCHIVE: f64 = 0.5;
+
+/// Score multiplier for memories in a tier the caller asked to prefer
+pub const MEMORY_TIER_PREFERENCE_BOOST: f32 = 1.25;
+
 // =============================================================================
 // LONG-TERM POTENTIATION (LTP) CONSTANTS
 // Based on synaptic plasticity and Hebbian learning theory
@@ -1274,6 +1355,153 @@ pub const POWERLAW_BETA_POTENTIATED: f64 = 0.3;
 /// - After 3 days at this rate: ~12.5% retention → power-law takes over
 pub const DECAY_LAMBDA_CONSOLIDATION: f64 = 0.693; // ln(2) / 1.0 day
 
+/// Stability gained per natural-log unit of retrievals (memory forgetting curve)
+///
+/// A memory's decay clock runs `1 + ln(1 + access_count) × this + importance ×
+/// MEMORY_STABILITY_IMPORTANCE` times slower than an untouched one.
+///
+/// Justification:
+/// - Each successful retrieval is a spaced repetition that flattens the curve
+/// - Logarithmic so the 100th access matters less than the 2nd
+///
+/// Reference: Ebbinghaus (1885), Pimsleur (1967) "A Memory Schedule"
+pub const MEMORY_STABILITY_PER_ACCESS: f64 = 1.0;
+
+/// Stability gained from full importance (memory forgetting curve)
+///
+/// Justification:
+/// - An importance-1.0 decision decays ~5x slower than trivia of importance 0
+/// - Keeps critical decisions retrievable for months without any access
+pub const MEMORY_STABILITY_IMPORTANCE: f64 = 4.0;
+
+/// Importance at which a memory uses the potentiated (heavier-tailed) curve
+pub const MEMORY_POTENTIATED_IMPORTANCE: f32 = 0.7;
+
+/// Minimum retention strength of a stored memory
+///
+/// Justification:
+/// - Forgetting lowers rank, it never makes a memory unreachable
+/// - Matches IMPORTANCE_FLOOR (savings effect on relearning)
+pub const MEMORY_STRENGTH_FLOOR: f32 = 0.05;
+
+/// Share of a retrieval score that depends on retention strength
+///
+/// `score × (1 - weight × (1 - strength))`, so a fully forgotten memory keeps
+/// 70% of its relevance score and only loses ties against fresher ones.
+pub const MEMORY_DECAY_SCORE_WEIGHT: f32 = 0.3;
+
+/// Minimum shingle (word 3-gram) Jaccard overlap for near-duplicates
+///
+/// Justification:
+/// - 0.6 tolerates a changed word or two in a short sentence
+/// - Paired with DEDUP_EMBEDDING_THRESHOLD so paraphrases with different
+///   meaning ("tests passed" / "tests failed") aren't merged
+///
+/// Reference: Broder (1997) "On the resemblance and containment of documents"
+pub const DEDUP_SHINGLE_THRESHOLD: f32 = 0.6;
+
+/// Minimum embedding cosine similarity for near-duplicates
+pub const DEDUP_EMBEDDING_THRESHOLD: f32 = 0.95;
+
+/// Shingle overlap required when either memory has no embedding
+pub const DEDUP_SHINGLE_ONLY_THRESHOLD: f32 = 0.9;
+
+/// Shingles shared by more memories than this are ignored for candidate pairs
+///
+/// Boilerplate phrases would otherwise make candidate generation quadratic.
+pub const DEDUP_MAX_SHINGLE_POSTINGS: usize = 200;
+
+/// Maximum importance-score gain from reinforcement (retrievals)
+///
+/// Justification:
+/// - Log-scaled and saturating at IMPORTANCE_SIGNAL_SATURATION, so a handful
+///   of retrievals matters and hundreds don't swamp the content signal
+pub const IMPORTANCE_REINFORCEMENT_WEIGHT: f32 = 0.15;
+
+/// Maximum importance-score gain from citations (responses built on the memory)
+///
+/// Justification:
+/// - Citation is stronger evidence than retrieval: the agent used it
+pub const IMPORTANCE_CITATION_WEIGHT: f32 = 0.2;
+
+/// Importance-score bonus for explicitly remembered memories
+///
+/// Justification:
+/// - A deliberate /remember is a direct "this matters" signal, unlike
+///   auto-captured context
+pub const IMPORTANCE_EXPLICIT_BONUS: f32 = 0.1;
+
+/// Importance-score gain per unit of emotional valence magnitude
+///
+/// Reference: LaBar & Cabeza (2006) "Cognitive neuroscience of emotional memory"
+pub const IMPORTANCE_VALENCE_WEIGHT: f32 = 0.1;
+
+/// Reinforcement/citation count at which their importance gain saturates
+pub const IMPORTANCE_SIGNAL_SATURATION: u32 = 20;
+
+/// Age after which raw episodic memories are folded into a semantic gist
+///
+/// Justification:
+/// - A month covers the active life of most tasks and conversations
+/// - Episodes promoted to long-term storage are never summarized
+///
+/// Reference: Winocur & Moscovitch (2011) "Memory transformation and systems consolidation"
+pub const GIST_MIN_AGE_DAYS: i64 = 30;
+
+/// Smallest episodic cluster worth a gist
+pub const GIST_MIN_CLUSTER_SIZE: usize = 3;
+
+/// Importance multiplier for episodes once their gist exists
+///
+/// Justification:
+/// - Halving lets the gist outrank its raw episodes without deleting history
+pub const GIST_EPISODE_IMPORTANCE_FACTOR: f32 = 0.5;
+
+/// Decisions/problems quoted per gist
+pub const GIST_MAX_HIGHLIGHTS: usize = 5;
+
+/// Minimum activation drop before the maintenance job persists decayed strength
+///
+/// Avoids rewriting every stored memory on each heavy cycle.
+pub const MEMORY_DECAY_PERSIST_DELTA: f32 = 0.05;
+
+// =============================================================================
+// SPACED-REPETITION REVIEW
+// Important memories resurface on an expanding schedule (1, 2, 4, 8... days)
+// Reference: Pimsleur (1967) "A Memory Schedule"
+// =============================================================================
+
+/// First spaced-repetition interval in days (resurfacing schedule)
+///
+/// Justification:
+/// - A day after creation is the steepest part of the forgetting curve
+/// - Matches the 1-day consolidation half-life (DECAY_LAMBDA_CONSOLIDATION)
+///
+/// Reference: Pimsleur (1967) "A Memory Schedule"
+pub const REVIEW_BASE_INTERVAL_DAYS: f64 = 1.0;
+
+/// Growth of the review interval per retrieval
+///
+/// Justification:
+/// - Doubling gives the classic 1, 2, 4, 8, 16... day Leitner schedule
+pub const REVIEW_INTERVAL_MULTIPLIER: f64 = 2.0;
+
+/// Longest gap between reviews of an important memory
+///
+/// Justification:
+/// - Twice a year is enough to keep long-lived decisions retrievable
+pub const REVIEW_MAX_INTERVAL_DAYS: f64 = 180.0;
+
+/// Minimum importance for a memory to be scheduled for review
+///
+/// Justification:
+/// - Only decisions, learnings and similar high-value memories are worth a
+///   context slot; trivia is allowed to fade
+pub const REVIEW_MIN_IMPORTANCE: f32 = 0.6;
+
+/// Due reviews kept in each user's cached queue between heavy maintenance cycles
+pub const REVIEW_QUEUE_CACHE_SIZE: usize = 50;
+
 // =============================================================================
 // INFORMATION CONTENT (IC) WEIGHTS
 // Based on linguistic analysis for query parsing
@@ -1400,6 +1628,19 @@ pub const PREFETCH_RECENCY_PARTIAL_BOOST: f32 = 0.05;
 /// - E.g., if it's 10am, consider memories from 8am-12pm
 pub const PREFETCH_TEMPORAL_WINDOW_HOURS: i32 = 2;
 
+/// Sprint length (days) when resolving "this sprint" / "last sprint" in queries
+///
+/// Justification:
+/// - Two-week sprints are the most common Scrum cadence
+pub const TEMPORAL_SPRINT_DAYS: i64 = 14;
+
+/// Candidate over-fetch factor when a query's temporal expression filters results
+///
+/// Justification:
+/// - Relevance ranking ignores time, so most top candidates may fall outside
+///   the requested range; 4x keeps enough in-range results after filtering
+pub const TEMPORAL_FILTER_OVERFETCH: usize = 4;
+
 // =============================================================================
 // MEMORY REPLAY CONSTANTS (SHO-105)
 // Based on sleep consolidation research: hippocampal replay during rest
@@ -1554,6 +1795,103 @@ pub const INTERFERENCE_VULNERABILITY_HOURS: i64 = 24;
 /// - Limits memory overhead
 pub const INTERFERENCE_MAX_TRACKED: usize = 10;
 
+// =============================================================================
+// CONTRADICTION DETECTION CONSTANTS
+// A newer memory that negates or replaces an older important one marks it stale
+// =============================================================================
+
+/// Minimum embedding similarity for two memories to be about the same thing
+///
+/// Justification:
+/// - Below the interference threshold (0.85): "uses npm" vs "switched to pnpm"
+///   share the topic but differ in the key term, which lowers cosine
+/// - 0.65 still excludes memories that merely share a project name
+pub const CONTRADICTION_SIMILARITY_MIN: f32 = 0.65;
+
+/// Similarity above which a replacement marker is treated as a restatement
+///
+/// Justification:
+/// - Matches INTERFERENCE_SEVERE_THRESHOLD: at 0.95 the memories are near-duplicates
+/// - Negations are still checked above this, since they barely move cosine
+pub const CONTRADICTION_SIMILARITY_MAX: f32 = 0.95;
+
+/// Minimum importance of the older memory for a contradiction to be flagged
+///
+/// Justification:
+/// - Low-importance memories already rank low and decay quickly
+/// - Flagging every trivial observation would flood /api/conflicts
+pub const CONTRADICTION_MIN_IMPORTANCE: f32 = 0.5;
+
+/// Nearest neighbours checked for contradictions when a memory is stored
+pub const CONTRADICTION_CANDIDATES: usize = 5;
+
+/// Recall score multiplier for memories contradicted by a newer one
+///
+/// Justification:
+/// - Halving keeps the stale memory retrievable (it may hold useful history)
+/// - Enough to rank the current memory above it for the same query
+pub const CONTRADICTED_RECALL_PENALTY: f32 = 0.5;
+
+/// Phrases announcing that something replaced what an older memory described
+///
+/// The word following the marker is the replacement ("switched to pnpm").
+pub const CONTRADICTION_CHANGE_MARKERS: &[&str] = &[
+    "switched to",
+    "switching to",
+    "migrated to",
+    "migrating to",
+    "moved to",
+    "replaced with",
+    "replaced by",
+    "in favor of",
+    "now uses",
+    "now use",
+];
+
+// =============================================================================
+// RANKING WEIGHT CONSTANTS
+// Defaults of the per-tenant weights in recall's unified score (memory/ranking.rs)
+// =============================================================================
+
+/// Recency boost at age zero when a query sets no recency weight
+///
+/// Justification:
+/// - About three times a top-ranked RRF relevance score (~1/31), so fresh
+///   memories win close calls without drowning out a better match
+/// - Decays to half within ~70 hours (recall's recency decay rate)
+pub const RANKING_DEFAULT_RECENCY_WEIGHT: f32 = 0.1;
+
+/// Raw recall score that calibrates to 0.5
+///
+/// Calibration maps raw scores through `raw / (raw + pivot)`, so they stay in
+/// 0.0-1.0 and keep their order whatever the user's corpus looks like.
+///
+/// Justification:
+/// - A top-ranked match with a fresh recency boost scores ~0.1-0.15 raw,
+///   calibrating to 0.5-0.6
+/// - A low-ranked stale match (~0.02 raw) calibrates below 0.2, under
+///   typical min-score thresholds
+pub const SCORE_CALIBRATION_PIVOT: f32 = 0.1;
+
+// =============================================================================
+// MEMORY QUOTA CONSTANTS
+// =============================================================================
+
+/// Fraction of a quota a store is evicted down to once it goes over
+///
+/// Justification:
+/// - Evicting to exactly the limit would re-trigger eviction on the next write
+/// - 5% headroom batches evictions while keeping the store close to its quota
+pub const QUOTA_EVICTION_TARGET: f32 = 0.95;
+
+/// Days without access after which a memory's eviction recency factor halves
+///
+/// Justification:
+/// - A month-old unused memory should lose to a fresh one of equal importance
+/// - Importance still dominates: an important memory idle for a month
+///   outranks a trivial one from today
+pub const QUOTA_EVICTION_RECENCY_HALF_LIFE_DAYS: f64 = 30.0;
+
 // =============================================================================
 // PATTERN-TRIGGERED REPLAY CONSTANTS (PIPE-2)
 // Based on hippocampal sharp-wave ripple research (Rasch & Born 2013)
@@ -1840,6 +2178,10 @@ pub const TIER_LTP_THRESHOLD: f32 = 0.8;
 // | HYBRID_SEMANTIC_WEIGHT    | memory/graph_retrieval.rs | spreading_activation_retrieve()   |
 // | HYBRID_GRAPH_WEIGHT       | memory/graph_retrieval.rs | spreading_activation_retrieve()   |
 // | HYBRID_LINGUISTIC_WEIGHT  | memory/graph_retrieval.rs | spreading_activation_retrieve()   |
+// | RELATED_GRAPH_WEIGHT      | handlers/recall.rs        | recall_related()                  |
+// | RELATED_RELEVANCE_WEIGHT  | handlers/recall.rs        | recall_related()                  |
+// | RELATED_MAX_DEPTH         | handlers/recall.rs        | recall_related()                  |
+// | RELATED_DEFAULT_WIDTH     | handlers/recall.rs        | recall_related()                  |
 //
 // ## Semantic Consolidation Constants
 // | Constant                      | File                  | Function/Context                  |
@@ -1855,6 +2197,28 @@ pub const TIER_LTP_THRESHOLD: f32 = 0.8;
 // | FACT_DEDUP_JACCARD_FALLBACK   | memory/facts.rs       | find_similar() fallback mode      |
 // | FACT_NEGATION_MARKERS         | memory/facts.rs       | detect_polarity()                 |
 //
+// ## Contradiction Detection Constants
+// | Constant                      | File                     | Function/Context               |
+// |-------------------------------|--------------------------|--------------------------------|
+// | CONTRADICTION_SIMILARITY_MIN  | memory/contradictions.rs | detect()                       |
+// | CONTRADICTION_SIMILARITY_MAX  | memory/contradictions.rs | detect()                       |
+// | CONTRADICTION_MIN_IMPORTANCE  | memory/contradictions.rs | detect()                       |
+// | CONTRADICTION_CHANGE_MARKERS  | memory/contradictions.rs | replacement_target()           |
+// | CONTRADICTION_CANDIDATES      | memory/mod.rs            | flag_contradictions()          |
+// | CONTRADICTED_RECALL_PENALTY   | memory/mod.rs            | recall() unified score         |
+//
+// ## Ranking Weight Constants
+// | Constant                       | File              | Function/Context               |
+// |--------------------------------|-------------------|--------------------------------|
+// | RANKING_DEFAULT_RECENCY_WEIGHT | memory/ranking.rs | RankingWeights::default()      |
+// | SCORE_CALIBRATION_PIVOT        | memory/ranking.rs | RankingWeights::calibrated()   |
+//
+// ## Memory Quota Constants
+// | Constant                              | File            | Function/Context       |
+// |---------------------------------------|-----------------|------------------------|
+// | QUOTA_EVICTION_TARGET                 | memory/quota.rs | select_evictions()     |
+// | QUOTA_EVICTION_RECENCY_HALF_LIFE_DAYS | memory/quota.rs | retention_score()      |
+//
 // ## Default Configuration Constants
 // | Constant                      | File                | Function/Context                    |
 // |-------------------------------|---------------------|-------------------------------------|
@@ -48,7 +48,9 @@
 //! - Anderson & Schooler (1991) "Reflections of the Environment in Memory"
 
 use crate::constants::{
-    DECAY_CROSSOVER_DAYS, DECAY_LAMBDA_CONSOLIDATION, POWERLAW_BETA, POWERLAW_BETA_POTENTIATED,
+    DECAY_CROSSOVER_DAYS, DECAY_LAMBDA_CONSOLIDATION, MEMORY_POTENTIATED_IMPORTANCE,
+    MEMORY_STABILITY_IMPORTANCE, MEMORY_STABILITY_PER_ACCESS, MEMORY_STRENGTH_FLOOR, POWERLAW_BETA,
+    POWERLAW_BETA_POTENTIATED,
 };
 
 /// Calculates the hybrid decay factor for a given elapsed time.
@@ -146,6 +148,28 @@ pub fn hybrid_decay_factor_custom(
     }
 }
 
+/// Retention strength of a stored memory (Ebbinghaus-style forgetting curve)
+///
+/// Runs the hybrid curve on a clock slowed by the memory's stability, which
+/// grows with reinforcement (retrievals) and importance. Important memories
+/// also use the potentiated heavy tail. Never drops below
+/// `MEMORY_STRENGTH_FLOOR`, so forgetting only lowers rank.
+///
+/// # Arguments
+///
+/// * `days_since_access` - Time since the memory was created or last retrieved
+/// * `access_count` - Number of retrievals (reinforcement history)
+/// * `importance` - Memory importance (0.0-1.0)
+#[inline]
+pub fn memory_strength(days_since_access: f64, access_count: u32, importance: f32) -> f32 {
+    let importance = importance.clamp(0.0, 1.0);
+    let stability = 1.0
+        + (1.0 + access_count as f64).ln() * MEMORY_STABILITY_PER_ACCESS
+        + importance as f64 * MEMORY_STABILITY_IMPORTANCE;
+    let potentiated = importance >= MEMORY_POTENTIATED_IMPORTANCE;
+    hybrid_decay_factor(days_since_access / stability, potentiated).max(MEMORY_STRENGTH_FLOOR)
+}
+
 /// Calculates retention percentage for debugging/visualization.
 ///
 /// Returns a human-readable percentage string showing retention at various time points.
@@ -307,6 +331,25 @@ mod tests {
         assert!(year_retention_potentiated > 0.05);
     }
 
+    #[test]
+    fn test_memory_strength_reinforcement_and_importance() {
+        // Fresh memories are at full strength
+        assert_eq!(memory_strength(0.0, 0, 0.5), 1.0);
+
+        // Older is weaker
+        assert!(memory_strength(30.0, 0, 0.3) < memory_strength(1.0, 0, 0.3));
+
+        // Retrieval history and importance both slow forgetting
+        let one_off = memory_strength(365.0, 0, 0.3);
+        let rehearsed = memory_strength(365.0, 20, 0.3);
+        let critical = memory_strength(365.0, 0, 0.95);
+        assert!(rehearsed > one_off);
+        assert!(critical > one_off);
+
+        // Never below the floor
+        assert!(memory_strength(10_000.0, 0, 0.0) >= MEMORY_STRENGTH_FLOOR);
+    }
+
     #[test]
     fn test_custom_parameters() {
         // Test custom function with aggressive decay
@@ -451,6 +451,11 @@ impl MiniLMEmbedder {
         self.lazy_model.get().is_some()
     }
 
+    /// Whether this embedder uses hash-based embeddings instead of the model
+    pub fn is_simplified(&self) -> bool {
+        self.simplified_mode
+    }
+
     /// Create simplified embedder as fallback when model files are missing
     ///
     /// Uses hash-based embeddings that are fast but less semantic.
@@ -1,7 +1,8 @@
 //! Embedding generation module
 //!
 //! Provides semantic embedding generation for memory retrieval.
-//! Uses ONNX Runtime with MiniLM-L6-v2 for 384-dimensional embeddings.
+//! Uses ONNX Runtime with MiniLM-L6-v2 for 384-dimensional embeddings by
+//! default; OpenAI and Cohere models can be selected instead (see [`provider`]).
 //!
 //! # Features
 //! - **Auto-download**: Model files downloaded on first use to ~/.cache/shodh-memory/
@@ -19,6 +20,8 @@ pub mod downloader;
 pub mod keywords;
 pub mod minilm;
 pub mod ner;
+pub mod pipeline;
+pub mod provider;
 
 // Re-export chunking types
 pub use chunking::{chunk_text, ChunkConfig, ChunkResult};
@@ -38,6 +41,10 @@ pub use ner::{NerConfig, NerEntity, NerEntityType, NeuralNer};
 // Re-export keyword types
 pub use keywords::{Keyword, KeywordConfig, KeywordExtractor};
 
+// Re-export embedding provider and pipeline types
+pub use pipeline::{EmbeddingPipeline, PipelineConfig};
+pub use provider::{EmbeddingInfo, EmbeddingProvider, ProviderConfig, ProviderKind};
+
 // Re-export circuit breaker types
 pub use circuit_breaker::{
     CircuitBreakerConfig, CircuitBreakerMetrics, CircuitState, ResilientEmbedder,
@@ -0,0 +1,255 @@
+//! Batched Embedding Pipeline
+//!
+//! Ingest-heavy paths (bulk import, integration syncs) embed thousands of
+//! texts. Embedding them one call at a time leaves most of the model's (or
+//! the hosted API's) throughput unused, so those paths submit texts to this
+//! pipeline instead:
+//!
+//! - a collector accumulates texts until `max_batch` are queued or
+//!   `max_wait` has passed since the first one, then hands the batch to
+//!   [`Embedder::encode_batch`]
+//! - at most `workers` batches are embedded at once, on the blocking pool
+//! - the queue holds `queue_capacity` texts; when it is full, submitters wait,
+//!   so a large import can't buffer unbounded work ahead of the model
+//!
+//! ```text
+//! SHODH_EMBED_BATCH_SIZE=32
+//! SHODH_EMBED_BATCH_WAIT_MS=20
+//! SHODH_EMBED_WORKERS=2
+//! SHODH_EMBED_QUEUE_CAPACITY=1024
+//! ```
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use tokio::sync::{mpsc, oneshot, Semaphore};
+use tokio::time::Instant;
+
+use super::provider::{EmbeddingInfo, EmbeddingProvider};
+
+/// Batching and concurrency limits for [`EmbeddingPipeline`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PipelineConfig {
+    /// Texts per `encode_batch` call
+    pub max_batch: usize,
+    /// Longest a queued text waits for its batch to fill
+    pub max_wait: Duration,
+    /// Batches embedded concurrently
+    pub workers: usize,
+    /// Texts queued before submitters wait
+    pub queue_capacity: usize,
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self {
+            max_batch: 32,
+            max_wait: Duration::from_millis(20),
+            workers: 2,
+            queue_capacity: 1024,
+        }
+    }
+}
+
+impl PipelineConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let env_usize = |name: &str, default: usize| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|&n| n > 0)
+                .unwrap_or(default)
+        };
+        Self {
+            max_batch: env_usize("SHODH_EMBED_BATCH_SIZE", defaults.max_batch),
+            max_wait: Duration::from_millis(env_usize(
+                "SHODH_EMBED_BATCH_WAIT_MS",
+                defaults.max_wait.as_millis() as usize,
+            ) as u64),
+            workers: env_usize("SHODH_EMBED_WORKERS", defaults.workers),
+            queue_capacity: env_usize("SHODH_EMBED_QUEUE_CAPACITY", defaults.queue_capacity),
+        }
+    }
+}
+
+struct Job {
+    text: String,
+    reply: oneshot::Sender<Result<Vec<f32>, String>>,
+}
+
+/// Shared batching front-end for an embedder
+pub struct EmbeddingPipeline {
+    queue: mpsc::Sender<Job>,
+    info: EmbeddingInfo,
+}
+
+impl EmbeddingPipeline {
+    /// Start the collector task. Must be called from within a Tokio runtime;
+    /// the task stops when the pipeline is dropped.
+    pub fn spawn(embedder: Arc<dyn EmbeddingProvider>, config: PipelineConfig) -> Self {
+        let (queue, jobs) = mpsc::channel(config.queue_capacity.max(1));
+        let info = embedder.info();
+        tokio::spawn(collect(jobs, embedder, config));
+        Self { queue, info }
+    }
+
+    /// Model the pipeline embeds with
+    pub fn info(&self) -> &EmbeddingInfo {
+        &self.info
+    }
+
+    /// Embed one text, batched with whatever else is queued
+    pub async fn embed(&self, text: String) -> Result<Vec<f32>> {
+        let (reply, result) = oneshot::channel();
+        self.queue
+            .send(Job { text, reply })
+            .await
+            .map_err(|_| anyhow!("embedding pipeline stopped"))?;
+        result
+            .await
+            .map_err(|_| anyhow!("embedding pipeline dropped the request"))?
+            .map_err(|e| anyhow!(e))
+    }
+
+    /// Embed many texts, in order. Waits for queue space as it submits.
+    pub async fn embed_many(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let mut pending = Vec::with_capacity(texts.len());
+        for text in texts {
+            let (reply, result) = oneshot::channel();
+            self.queue
+                .send(Job { text, reply })
+                .await
+                .map_err(|_| anyhow!("embedding pipeline stopped"))?;
+            pending.push(result);
+        }
+        let mut embeddings = Vec::with_capacity(pending.len());
+        for result in pending {
+            embeddings.push(
+                result
+                    .await
+                    .map_err(|_| anyhow!("embedding pipeline dropped the request"))?
+                    .map_err(|e| anyhow!(e))?,
+            );
+        }
+        Ok(embeddings)
+    }
+}
+
+/// Pull jobs into batches and embed them on at most `config.workers` blocking threads
+async fn collect(
+    mut jobs: mpsc::Receiver<Job>,
+    embedder: Arc<dyn EmbeddingProvider>,
+    config: PipelineConfig,
+) {
+    let workers = Arc::new(Semaphore::new(config.workers.max(1)));
+    while let Some(first) = jobs.recv().await {
+        let mut batch = vec![first];
+        let deadline = Instant::now() + config.max_wait;
+        while batch.len() < config.max_batch {
+            match tokio::time::timeout_at(deadline, jobs.recv()).await {
+                Ok(Some(job)) => batch.push(job),
+                Ok(None) | Err(_) => break,
+            }
+        }
+
+        // Wait for a free worker before collecting more, so a busy model
+        // backs the queue up to submitters
+        let Ok(permit) = workers.clone().acquire_owned().await else {
+            break;
+        };
+        let embedder = embedder.clone();
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            let texts: Vec<&str> = batch.iter().map(|job| job.text.as_str()).collect();
+            match embedder.encode_batch(&texts) {
+                Ok(embeddings) if embeddings.len() == batch.len() => {
+                    for (job, embedding) in batch.into_iter().zip(embeddings) {
+                        let _ = job.reply.send(Ok(embedding));
+                    }
+                }
+                Ok(embeddings) => {
+                    let reason = format!(
+                        "embedder returned {} embeddings for {} texts",
+                        embeddings.len(),
+                        batch.len()
+                    );
+                    for job in batch {
+                        let _ = job.reply.send(Err(reason.clone()));
+                    }
+                }
+                Err(e) => {
+                    let reason = e.to_string();
+                    for job in batch {
+                        let _ = job.reply.send(Err(reason.clone()));
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embeddings::minilm::{EmbeddingConfig, MiniLMEmbedder};
+    use crate::embeddings::Embedder;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Wraps the hash-based embedder, counting `encode_batch` calls
+    struct CountingEmbedder {
+        inner: MiniLMEmbedder,
+        batches: AtomicUsize,
+    }
+
+    impl Embedder for CountingEmbedder {
+        fn encode(&self, text: &str) -> Result<Vec<f32>> {
+            self.inner.encode(text)
+        }
+
+        fn dimension(&self) -> usize {
+            self.inner.dimension()
+        }
+
+        fn encode_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+            self.batches.fetch_add(1, Ordering::SeqCst);
+            self.inner.encode_batch(texts)
+        }
+    }
+
+    impl EmbeddingProvider for CountingEmbedder {
+        fn info(&self) -> EmbeddingInfo {
+            self.inner.info()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batches_texts_in_order() {
+        let embedder = Arc::new(CountingEmbedder {
+            inner: MiniLMEmbedder::new_simplified(EmbeddingConfig::default()).unwrap(),
+            batches: AtomicUsize::new(0),
+        });
+        let pipeline = EmbeddingPipeline::spawn(
+            embedder.clone(),
+            PipelineConfig {
+                max_batch: 8,
+                max_wait: Duration::from_millis(50),
+                workers: 1,
+                queue_capacity: 4,
+            },
+        );
+
+        let texts: Vec<String> = (0..20).map(|i| format!("memory number {i}")).collect();
+        let embeddings = pipeline.embed_many(texts.clone()).await.unwrap();
+        assert_eq!(embeddings.len(), 20);
+        for (text, embedding) in texts.iter().zip(&embeddings) {
+            assert_eq!(*embedding, embedder.encode(text).unwrap());
+        }
+        // 20 texts through a 4-slot queue still batch well below one call each
+        assert!(embedder.batches.load(Ordering::SeqCst) < 20);
+
+        let single = pipeline.embed("one more".to_string()).await.unwrap();
+        assert_eq!(single, embedder.encode("one more").unwrap());
+    }
+}
@@ -0,0 +1,717 @@
+//! Embedding Providers
+//!
+//! Chooses the model memories are embedded with. The default is the bundled
+//! MiniLM-L6-v2 ONNX model; hosted OpenAI and Cohere models can be used
+//! instead:
+//!
+//! ```text
+//! SHODH_EMBEDDING_PROVIDER=openai        # local (default), openai or cohere
+//! SHODH_EMBEDDING_MODEL=text-embedding-3-small
+//! SHODH_EMBEDDING_API_KEY=sk-...         # default: OPENAI_API_KEY / COHERE_API_KEY
+//! SHODH_EMBEDDING_BASE_URL=...           # OpenAI-compatible gateways, proxies
+//! SHODH_EMBEDDING_DIMENSION=1024         # required for models not in KNOWN_MODELS
+//! ```
+//!
+//! Every memory records the model and dimension its embedding came from
+//! (`embedding_model` / `embedding_dim` metadata), and each user's store is
+//! pinned to the provider it was embedded with (`embedding_provider.json`),
+//! so changing these variables only affects new stores until existing ones
+//! are migrated with `POST /api/admin/reembed`.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::minilm::{EmbeddingConfig, MiniLMEmbedder};
+use super::Embedder;
+
+/// Metadata key recording which model produced a memory's embedding
+pub const EMBEDDING_MODEL_KEY: &str = "embedding_model";
+/// Metadata key recording the dimension of a memory's embedding
+pub const EMBEDDING_DIM_KEY: &str = "embedding_dim";
+
+/// Dimension of the bundled MiniLM-L6-v2 model
+pub const LOCAL_DIMENSION: usize = 384;
+const LOCAL_MODEL: &str = "minilm-l6-v2";
+
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const OPENAI_DEFAULT_MODEL: &str = "text-embedding-3-small";
+/// Inputs per OpenAI embeddings request
+const OPENAI_MAX_BATCH: usize = 2048;
+
+const COHERE_BASE_URL: &str = "https://api.cohere.com/v1";
+const COHERE_DEFAULT_MODEL: &str = "embed-english-v3.0";
+/// Texts per Cohere embed request
+const COHERE_MAX_BATCH: usize = 96;
+
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// File in each user's store naming the provider its vectors came from
+pub const PINNED_PROVIDER_FILE: &str = "embedding_provider.json";
+
+/// Output dimensions of hosted models, used when SHODH_EMBEDDING_DIMENSION is unset
+pub const KNOWN_MODELS: &[(ProviderKind, &str, usize)] = &[
+    (ProviderKind::OpenAi, "text-embedding-3-small", 1536),
+    (ProviderKind::OpenAi, "text-embedding-3-large", 3072),
+    (ProviderKind::OpenAi, "text-embedding-ada-002", 1536),
+    (ProviderKind::Cohere, "embed-english-v3.0", 1024),
+    (ProviderKind::Cohere, "embed-multilingual-v3.0", 1024),
+    (ProviderKind::Cohere, "embed-english-light-v3.0", 384),
+    (ProviderKind::Cohere, "embed-multilingual-light-v3.0", 384),
+];
+
+/// Which backend generates embeddings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderKind {
+    Local,
+    #[serde(rename = "openai")]
+    OpenAi,
+    Cohere,
+}
+
+impl ProviderKind {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "local" | "onnx" | "minilm" => Some(Self::Local),
+            "openai" => Some(Self::OpenAi),
+            "cohere" => Some(Self::Cohere),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Local => "local",
+            Self::OpenAi => "openai",
+            Self::Cohere => "cohere",
+        }
+    }
+}
+
+/// The model behind an embedder, as recorded on each memory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EmbeddingInfo {
+    pub provider: ProviderKind,
+    pub model: String,
+    pub dimension: usize,
+}
+
+impl EmbeddingInfo {
+    /// Stable model identifier, e.g. `openai/text-embedding-3-small`
+    pub fn model_id(&self) -> String {
+        format!("{}/{}", self.provider.as_str(), self.model)
+    }
+
+    /// Record this model on a memory's metadata
+    pub fn write_to(&self, metadata: &mut HashMap<String, String>) {
+        metadata.insert(EMBEDDING_MODEL_KEY.to_string(), self.model_id());
+        metadata.insert(EMBEDDING_DIM_KEY.to_string(), self.dimension.to_string());
+    }
+
+    /// Whether a memory's metadata says it was embedded by this model.
+    /// Memories stored before models were recorded count as local.
+    pub fn matches(&self, metadata: &HashMap<String, String>) -> bool {
+        match metadata.get(EMBEDDING_MODEL_KEY) {
+            Some(model_id) => *model_id == self.model_id(),
+            None => self.provider == ProviderKind::Local,
+        }
+    }
+}
+
+/// An [`Embedder`] that can describe the model it runs
+pub trait EmbeddingProvider: Embedder {
+    fn info(&self) -> EmbeddingInfo;
+}
+
+impl EmbeddingProvider for MiniLMEmbedder {
+    fn info(&self) -> EmbeddingInfo {
+        let model = if self.is_simplified() {
+            "simplified-hash"
+        } else {
+            LOCAL_MODEL
+        };
+        EmbeddingInfo {
+            provider: ProviderKind::Local,
+            model: model.to_string(),
+            dimension: self.dimension(),
+        }
+    }
+}
+
+/// Provider selection from SHODH_EMBEDDING_* variables
+///
+/// Serialized (without the API key) as a store's pinned provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderConfig {
+    pub kind: ProviderKind,
+    pub model: Option<String>,
+    #[serde(skip)]
+    pub api_key: Option<String>,
+    pub base_url: Option<String>,
+    /// Overrides the known dimension; OpenAI v3 models are asked to shorten to it
+    pub dimension: Option<usize>,
+    #[serde(skip, default = "default_timeout")]
+    pub timeout: Duration,
+}
+
+fn default_timeout() -> Duration {
+    Duration::from_secs(DEFAULT_TIMEOUT_SECS)
+}
+
+/// Provider-specific API key variable, used when SHODH_EMBEDDING_API_KEY
+/// belongs to a different provider
+fn provider_key_from_env(kind: ProviderKind) -> Option<String> {
+    let name = match kind {
+        ProviderKind::Local => return None,
+        ProviderKind::OpenAi => "OPENAI_API_KEY",
+        ProviderKind::Cohere => "COHERE_API_KEY",
+    };
+    std::env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        Self {
+            kind: ProviderKind::Local,
+            model: None,
+            api_key: None,
+            base_url: None,
+            dimension: None,
+            timeout: default_timeout(),
+        }
+    }
+}
+
+impl ProviderConfig {
+    pub fn from_env() -> Self {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let kind = match var("SHODH_EMBEDDING_PROVIDER") {
+            Some(value) => ProviderKind::parse(&value).unwrap_or_else(|| {
+                tracing::warn!(
+                    "Unknown SHODH_EMBEDDING_PROVIDER '{}', using local embeddings",
+                    value
+                );
+                ProviderKind::Local
+            }),
+            None => ProviderKind::Local,
+        };
+        let dimension = var("SHODH_EMBEDDING_DIMENSION").and_then(|v| match v.parse::<usize>() {
+            Ok(n) if n > 0 => Some(n),
+            _ => {
+                tracing::warn!("Ignoring invalid SHODH_EMBEDDING_DIMENSION: {}", v);
+                None
+            }
+        });
+        Self {
+            kind,
+            model: var("SHODH_EMBEDDING_MODEL"),
+            api_key: var("SHODH_EMBEDDING_API_KEY").or_else(|| provider_key_from_env(kind)),
+            base_url: var("SHODH_EMBEDDING_BASE_URL"),
+            dimension,
+            timeout: default_timeout(),
+        }
+    }
+
+    /// This config moved to another backend and/or model, e.g. as the target
+    /// of a re-embedding job. A different backend takes its API key from the
+    /// provider-specific variable.
+    pub fn with_model(
+        &self,
+        kind: ProviderKind,
+        model: Option<String>,
+        dimension: Option<usize>,
+    ) -> Self {
+        if kind == self.kind {
+            return Self {
+                model: model.or_else(|| self.model.clone()),
+                dimension: dimension.or(self.dimension),
+                ..self.clone()
+            };
+        }
+        Self {
+            kind,
+            model,
+            api_key: provider_key_from_env(kind),
+            base_url: None,
+            dimension,
+            timeout: self.timeout,
+        }
+    }
+
+    /// Whether both configs produce comparable vectors
+    pub fn same_model(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.model() == other.model()
+            && self.dimension() == other.dimension()
+    }
+
+    /// The provider a store's vectors were embedded with. A store without a
+    /// pin (new, or created before pinning) is pinned to `configured`.
+    pub fn for_store(store_path: &Path, configured: &ProviderConfig) -> Result<Self> {
+        let path = store_path.join(PINNED_PROVIDER_FILE);
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                configured.pin(store_path)?;
+                return Ok(configured.clone());
+            }
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        let mut pinned: Self = serde_json::from_slice(&bytes)
+            .with_context(|| format!("Invalid pinned provider in {}", path.display()))?;
+        pinned.api_key = if pinned.kind == configured.kind {
+            configured.api_key.clone()
+        } else {
+            provider_key_from_env(pinned.kind)
+        };
+        if !pinned.same_model(configured) {
+            tracing::warn!(
+                "Store {} is embedded with {}, not the configured {}; migrate it with POST /api/admin/reembed",
+                store_path.display(),
+                pinned.describe(),
+                configured.describe()
+            );
+        }
+        Ok(pinned)
+    }
+
+    /// Record this provider as the one a store is embedded with
+    pub fn pin(&self, store_path: &Path) -> Result<()> {
+        let path = store_path.join(PINNED_PROVIDER_FILE);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &path)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Model name, falling back to the provider's default
+    pub fn model(&self) -> &str {
+        match (&self.model, self.kind) {
+            (Some(model), _) => model,
+            (None, ProviderKind::Local) => LOCAL_MODEL,
+            (None, ProviderKind::OpenAi) => OPENAI_DEFAULT_MODEL,
+            (None, ProviderKind::Cohere) => COHERE_DEFAULT_MODEL,
+        }
+    }
+
+    /// Embedding dimension, or `None` for an unknown hosted model without
+    /// SHODH_EMBEDDING_DIMENSION
+    pub fn dimension(&self) -> Option<usize> {
+        if self.kind == ProviderKind::Local {
+            return Some(LOCAL_DIMENSION);
+        }
+        self.dimension.or_else(|| {
+            KNOWN_MODELS
+                .iter()
+                .find(|(kind, model, _)| *kind == self.kind && *model == self.model())
+                .map(|(_, _, dim)| *dim)
+        })
+    }
+
+    /// Short description for startup logs
+    pub fn describe(&self) -> String {
+        match self.dimension() {
+            Some(dim) => format!("{}/{} ({} dims)", self.kind.as_str(), self.model(), dim),
+            None => format!("{}/{} (unknown dims)", self.kind.as_str(), self.model()),
+        }
+    }
+}
+
+/// Build the embedder configured by the environment
+pub fn from_env() -> Result<Arc<dyn EmbeddingProvider>> {
+    from_config(&ProviderConfig::from_env())
+}
+
+/// Build the embedder for `config`
+pub fn from_config(config: &ProviderConfig) -> Result<Arc<dyn EmbeddingProvider>> {
+    match config.kind {
+        ProviderKind::Local => Ok(Arc::new(
+            MiniLMEmbedder::new(EmbeddingConfig::default())
+                .context("Failed to initialize MiniLM embedder (ONNX model)")?,
+        )),
+        ProviderKind::OpenAi => Ok(Arc::new(OpenAiEmbedder::new(config)?)),
+        ProviderKind::Cohere => Ok(Arc::new(CohereEmbedder::new(config)?)),
+    }
+}
+
+/// Shared setup for hosted providers
+struct HostedModel {
+    client: reqwest::blocking::Client,
+    api_key: String,
+    base_url: String,
+    info: EmbeddingInfo,
+}
+
+impl HostedModel {
+    fn new(config: &ProviderConfig, default_base_url: &str) -> Result<Self> {
+        let provider = config.kind.as_str();
+        let Some(api_key) = config.api_key.clone() else {
+            bail!("{provider} embeddings need SHODH_EMBEDDING_API_KEY");
+        };
+        let Some(dimension) = config.dimension() else {
+            bail!(
+                "Unknown dimension for {provider} model '{}', set SHODH_EMBEDDING_DIMENSION",
+                config.model()
+            );
+        };
+        let client = reqwest::blocking::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .context("building embedding HTTP client")?;
+        Ok(Self {
+            client,
+            api_key,
+            base_url: config
+                .base_url
+                .as_deref()
+                .unwrap_or(default_base_url)
+                .trim_end_matches('/')
+                .to_string(),
+            info: EmbeddingInfo {
+                provider: config.kind,
+                model: config.model().to_string(),
+                dimension,
+            },
+        })
+    }
+
+    /// POST `body` to `{base_url}/{path}`, recording embedding metrics
+    fn post<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+    ) -> Result<T> {
+        let mode = self.info.provider.as_str();
+        let start = Instant::now();
+        let result = self
+            .client
+            .post(format!("{}/{}", self.base_url, path))
+            .bearer_auth(&self.api_key)
+            .json(body)
+            .send()
+            .with_context(|| format!("{mode} embedding request failed"))
+            .and_then(|response| {
+                let status = response.status();
+                if !status.is_success() {
+                    let text = response.text().unwrap_or_default();
+                    bail!("{mode} embedding request returned {status}: {text}");
+                }
+                response
+                    .json::<T>()
+                    .with_context(|| format!("invalid {mode} embedding response"))
+            });
+        crate::metrics::EMBEDDING_GENERATE_DURATION
+            .with_label_values(&[mode])
+            .observe(start.elapsed().as_secs_f64());
+        crate::metrics::EMBEDDING_GENERATE_TOTAL
+            .with_label_values(&[mode, if result.is_ok() { "success" } else { "failure" }])
+            .inc();
+        result
+    }
+
+    /// Check dimensions and L2-normalize, matching the local model's output
+    fn finish(&self, mut embeddings: Vec<Vec<f32>>, expected: usize) -> Result<Vec<Vec<f32>>> {
+        if embeddings.len() != expected {
+            bail!(
+                "{} returned {} embeddings for {} inputs",
+                self.info.provider.as_str(),
+                embeddings.len(),
+                expected
+            );
+        }
+        for embedding in &mut embeddings {
+            if embedding.len() != self.info.dimension {
+                bail!(
+                    "{} model '{}' returned {} dims, expected {} (check SHODH_EMBEDDING_DIMENSION)",
+                    self.info.provider.as_str(),
+                    self.info.model,
+                    embedding.len(),
+                    self.info.dimension
+                );
+            }
+            normalize(embedding);
+        }
+        Ok(embeddings)
+    }
+}
+
+fn normalize(embedding: &mut [f32]) {
+    let norm = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > f32::EPSILON {
+        embedding.iter_mut().for_each(|x| *x /= norm);
+    }
+}
+
+/// OpenAI (or OpenAI-compatible) `/embeddings` API
+pub struct OpenAiEmbedder {
+    hosted: HostedModel,
+    /// Request shortened vectors (text-embedding-3 models only)
+    request_dimensions: bool,
+}
+
+#[derive(Deserialize)]
+struct OpenAiResponse {
+    data: Vec<OpenAiEmbedding>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiEmbedding {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+impl OpenAiEmbedder {
+    pub fn new(config: &ProviderConfig) -> Result<Self> {
+        Ok(Self {
+            hosted: HostedModel::new(config, OPENAI_BASE_URL)?,
+            request_dimensions: config.dimension.is_some()
+                && config.model().starts_with("text-embedding-3"),
+        })
+    }
+
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let mut body = serde_json::json!({
+            "model": self.hosted.info.model,
+            "input": texts,
+        });
+        if self.request_dimensions {
+            body["dimensions"] = self.hosted.info.dimension.into();
+        }
+        let mut response: OpenAiResponse = self.hosted.post("embeddings", &body)?;
+        response.data.sort_by_key(|e| e.index);
+        let embeddings = response.data.into_iter().map(|e| e.embedding).collect();
+        self.hosted.finish(embeddings, texts.len())
+    }
+}
+
+impl Embedder for OpenAiEmbedder {
+    fn encode(&self, text: &str) -> Result<Vec<f32>> {
+        if text.is_empty() {
+            return Ok(vec![0.0; self.dimension()]);
+        }
+        Ok(self.embed(&[text])?.remove(0))
+    }
+
+    fn dimension(&self) -> usize {
+        self.hosted.info.dimension
+    }
+
+    fn encode_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        encode_chunked(self, texts, OPENAI_MAX_BATCH, |chunk| self.embed(chunk))
+    }
+}
+
+impl EmbeddingProvider for OpenAiEmbedder {
+    fn info(&self) -> EmbeddingInfo {
+        self.hosted.info.clone()
+    }
+}
+
+/// Cohere `/embed` API
+pub struct CohereEmbedder {
+    hosted: HostedModel,
+}
+
+#[derive(Deserialize)]
+struct CohereResponse {
+    embeddings: CohereEmbeddings,
+}
+
+#[derive(Deserialize)]
+struct CohereEmbeddings {
+    float: Vec<Vec<f32>>,
+}
+
+impl CohereEmbedder {
+    pub fn new(config: &ProviderConfig) -> Result<Self> {
+        Ok(Self {
+            hosted: HostedModel::new(config, COHERE_BASE_URL)?,
+        })
+    }
+
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        // Memories and queries share one vector space, so everything is
+        // embedded as a document
+        let body = serde_json::json!({
+            "model": self.hosted.info.model,
+            "texts": texts,
+            "input_type": "search_document",
+            "embedding_types": ["float"],
+        });
+        let response: CohereResponse = self.hosted.post("embed", &body)?;
+        self.hosted.finish(response.embeddings.float, texts.len())
+    }
+}
+
+impl Embedder for CohereEmbedder {
+    fn encode(&self, text: &str) -> Result<Vec<f32>> {
+        if text.is_empty() {
+            return Ok(vec![0.0; self.dimension()]);
+        }
+        Ok(self.embed(&[text])?.remove(0))
+    }
+
+    fn dimension(&self) -> usize {
+        self.hosted.info.dimension
+    }
+
+    fn encode_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        encode_chunked(self, texts, COHERE_MAX_BATCH, |chunk| self.embed(chunk))
+    }
+}
+
+impl EmbeddingProvider for CohereEmbedder {
+    fn info(&self) -> EmbeddingInfo {
+        self.hosted.info.clone()
+    }
+}
+
+/// Embed `texts` in requests of at most `max_batch`, skipping the API for
+/// empty strings (which embed to the zero vector, as with the local model)
+fn encode_chunked(
+    embedder: &dyn Embedder,
+    texts: &[&str],
+    max_batch: usize,
+    embed: impl Fn(&[&str]) -> Result<Vec<Vec<f32>>>,
+) -> Result<Vec<Vec<f32>>> {
+    let mut results = vec![vec![0.0; embedder.dimension()]; texts.len()];
+    let pending: Vec<(usize, &str)> = texts
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| !t.is_empty())
+        .map(|(i, t)| (i, *t))
+        .collect();
+    for chunk in pending.chunks(max_batch) {
+        let inputs: Vec<&str> = chunk.iter().map(|(_, t)| *t).collect();
+        for ((i, _), embedding) in chunk.iter().zip(embed(&inputs)?) {
+            results[*i] = embedding;
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(kind: ProviderKind, model: Option<&str>, dimension: Option<usize>) -> ProviderConfig {
+        ProviderConfig {
+            kind,
+            model: model.map(str::to_string),
+            api_key: Some("test".to_string()),
+            dimension,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dimension_resolution() {
+        assert_eq!(
+            config(ProviderKind::Local, None, Some(99)).dimension(),
+            Some(LOCAL_DIMENSION)
+        );
+        assert_eq!(
+            config(ProviderKind::OpenAi, None, None).dimension(),
+            Some(1536)
+        );
+        assert_eq!(
+            config(ProviderKind::OpenAi, Some("text-embedding-3-large"), None).dimension(),
+            Some(3072)
+        );
+        assert_eq!(
+            config(
+                ProviderKind::OpenAi,
+                Some("text-embedding-3-large"),
+                Some(256)
+            )
+            .dimension(),
+            Some(256)
+        );
+        assert_eq!(
+            config(ProviderKind::Cohere, None, None).dimension(),
+            Some(1024)
+        );
+        assert_eq!(
+            config(ProviderKind::Cohere, Some("my-finetune"), None).dimension(),
+            None
+        );
+        assert!(OpenAiEmbedder::new(&config(ProviderKind::OpenAi, Some("custom"), None)).is_err());
+    }
+
+    #[test]
+    fn test_embedding_info_metadata() {
+        let info = EmbeddingInfo {
+            provider: ProviderKind::OpenAi,
+            model: "text-embedding-3-small".to_string(),
+            dimension: 1536,
+        };
+        let mut metadata = HashMap::new();
+        let local = EmbeddingInfo {
+            provider: ProviderKind::Local,
+            model: LOCAL_MODEL.to_string(),
+            dimension: LOCAL_DIMENSION,
+        };
+        // Unrecorded memories predate providers and were embedded locally
+        assert!(local.matches(&metadata));
+        assert!(!info.matches(&metadata));
+
+        info.write_to(&mut metadata);
+        assert_eq!(
+            metadata.get(EMBEDDING_MODEL_KEY).map(String::as_str),
+            Some("openai/text-embedding-3-small")
+        );
+        assert_eq!(
+            metadata.get(EMBEDDING_DIM_KEY).map(String::as_str),
+            Some("1536")
+        );
+        assert!(info.matches(&metadata));
+        assert!(!local.matches(&metadata));
+    }
+
+    #[test]
+    fn test_store_pinning() {
+        let dir = tempfile::tempdir().unwrap();
+        let openai = config(ProviderKind::OpenAi, None, None);
+        let pinned = ProviderConfig::for_store(dir.path(), &openai).unwrap();
+        assert!(pinned.same_model(&openai));
+        assert!(dir.path().join(PINNED_PROVIDER_FILE).exists());
+
+        // A later config change doesn't move an existing store
+        let cohere = config(ProviderKind::Cohere, None, None);
+        let pinned = ProviderConfig::for_store(dir.path(), &cohere).unwrap();
+        assert_eq!(pinned.kind, ProviderKind::OpenAi);
+        assert!(!pinned.same_model(&cohere));
+
+        cohere.pin(dir.path()).unwrap();
+        let pinned = ProviderConfig::for_store(dir.path(), &cohere).unwrap();
+        assert!(pinned.same_model(&cohere));
+        assert_eq!(pinned.api_key.as_deref(), Some("test"));
+    }
+
+    #[test]
+    fn test_encode_chunked_skips_empty_inputs() {
+        let embedder = MiniLMEmbedder::new_simplified(EmbeddingConfig::default()).unwrap();
+        let calls = std::sync::Mutex::new(Vec::new());
+        let results = encode_chunked(&embedder, &["a", "", "b", "c"], 2, |chunk| {
+            calls.lock().unwrap().push(chunk.len());
+            Ok(chunk.iter().map(|_| vec![1.0; LOCAL_DIMENSION]).collect())
+        })
+        .unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec![2, 1]);
+        assert_eq!(results.len(), 4);
+        assert!(results[1].iter().all(|x| *x == 0.0));
+        assert!(results[2].iter().all(|x| *x == 1.0));
+    }
+}
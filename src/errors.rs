@@ -62,8 +62,12 @@ pub enum AppError {
     TodoNotFound(String),
     ProjectNotFound(String),
 
+    // Access Errors (403)
+    Forbidden(String),
+
     // Conflict Errors (409)
     MemoryAlreadyExists(String),
+    IdempotencyKeyInUse(String),
 
     // Internal Errors (500)
     StorageError(String),
@@ -119,7 +123,9 @@ impl AppError {
             Self::UserNotFound(_) => "USER_NOT_FOUND",
             Self::TodoNotFound(_) => "TODO_NOT_FOUND",
             Self::ProjectNotFound(_) => "PROJECT_NOT_FOUND",
+            Self::Forbidden(_) => "FORBIDDEN",
             Self::MemoryAlreadyExists(_) => "MEMORY_ALREADY_EXISTS",
+            Self::IdempotencyKeyInUse(_) => "IDEMPOTENCY_KEY_IN_USE",
             Self::StorageError(_) => "STORAGE_ERROR",
             Self::DatabaseError(_) => "DATABASE_ERROR",
             Self::SerializationError(_) => "SERIALIZATION_ERROR",
@@ -148,7 +154,9 @@ impl AppError {
             | Self::TodoNotFound(_)
             | Self::ProjectNotFound(_) => StatusCode::NOT_FOUND,
 
-            Self::MemoryAlreadyExists(_) => StatusCode::CONFLICT,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+
+            Self::MemoryAlreadyExists(_) | Self::IdempotencyKeyInUse(_) => StatusCode::CONFLICT,
 
             Self::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
 
@@ -188,7 +196,11 @@ impl AppError {
             Self::UserNotFound(id) => format!("User not found: {id}"),
             Self::TodoNotFound(id) => format!("Todo not found: {id}"),
             Self::ProjectNotFound(id) => format!("Project not found: {id}"),
+            Self::Forbidden(msg) => format!("Forbidden: {msg}"),
             Self::MemoryAlreadyExists(id) => format!("Memory already exists: {id}"),
+            Self::IdempotencyKeyInUse(key) => {
+                format!("A write with idempotency key '{key}' is still in progress")
+            }
             Self::StorageError(msg) => format!("Storage error: {msg}"),
             Self::DatabaseError(msg) => format!("Database error: {msg}"),
             Self::SerializationError(msg) => format!("Serialization error: {msg}"),
@@ -243,6 +255,13 @@ impl From<anyhow::Error> for AppError {
 /// Axum IntoResponse implementation for proper HTTP responses
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        // Stores refuse ids of another tenant deep below the handler, which
+        // usually wraps the refusal as an internal error
+        if let Self::Internal(err) = &self {
+            if let Some(mismatch) = err.downcast_ref::<crate::tenant::TenantMismatch>() {
+                return Self::Forbidden(mismatch.to_string()).into_response();
+            }
+        }
         let status = self.status_code();
         let body = self.to_response();
 
@@ -0,0 +1,783 @@
+//! Outbound Webhooks
+//!
+//! Webhooks registered through /api/webhooks receive the memory events that
+//! /api/events streams (CREATE, UPDATE, REINFORCE, DELETE, ...) as HTTP
+//! POSTs, for server-to-server integrations. A webhook belongs to the tenant
+//! of the key that registered it (see [`crate::tenant`]) and only hears about
+//! that tenant's users. It can be narrowed to one user, some event types and
+//! some memory types, e.g. a Slack channel told about new Decision memories
+//! (`"format": "slack"` posts `{"text": ...}` for Slack incoming webhooks).
+//!
+//! Every delivery is signed with the webhook's secret, which is shown once on
+//! registration:
+//!
+//! ```text
+//! X-Shodh-Signature: sha256=<hex HMAC-SHA256 of "{X-Shodh-Timestamp}.{body}">
+//! X-Shodh-Timestamp: <unix seconds>
+//! X-Shodh-Event:     CREATE
+//! X-Shodh-Delivery:  <uuid, the same across retries>
+//! ```
+//!
+//! Webhooks may only reach public addresses: URLs naming loopback, private
+//! (RFC 1918), link-local, unique-local or other internal addresses are
+//! refused on registration, and host names are resolved again on every
+//! delivery through a resolver that drops such addresses, so a name can't be
+//! re-pointed at an internal service after it was checked. Redirects are not
+//! followed.
+//!
+//! Network errors, 408, 429 and 5xx responses are retried with exponential
+//! backoff; other 4xx are not. After `MAX_CONSECUTIVE_FAILURES` failed
+//! deliveries in a row a webhook is disabled until it is re-enabled.
+//! Webhooks are stored in the `webhooks` column family of the shared DB.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use parking_lot::RwLock;
+use rand::RngCore;
+use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, IteratorMode, Options, DB};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::handlers::types::MemoryEvent;
+use crate::metrics::WEBHOOK_DELIVERIES_TOTAL;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Column family holding webhooks (id -> Webhook)
+pub const CF_WEBHOOKS: &str = "webhooks";
+
+/// Prefix of generated signing secrets
+const SECRET_PREFIX: &str = "whsec_";
+
+/// Attempts per delivery, including the first
+const MAX_ATTEMPTS: u32 = 5;
+
+/// Backoff before the first retry; doubles per attempt up to `MAX_BACKOFF`
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Failed deliveries in a row after which a webhook is disabled
+const MAX_CONSECUTIVE_FAILURES: u32 = 20;
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Body sent to the webhook URL
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadFormat {
+    /// `{"webhook_id": ..., "event": <MemoryEvent>}`
+    #[default]
+    Json,
+    /// `{"text": ...}`, for Slack incoming webhooks
+    Slack,
+}
+
+/// A registered webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: String,
+    /// Owning tenant (`None` = default tenant)
+    #[serde(default)]
+    pub tenant: Option<String>,
+    pub url: String,
+    /// HMAC signing secret
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub secret: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Event types to deliver, e.g. "CREATE" (empty = all)
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    /// Only deliver events of this user
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// Only deliver events about memories of these types, e.g. "Decision"
+    #[serde(default)]
+    pub memory_types: Vec<String>,
+    #[serde(default)]
+    pub format: PayloadFormat,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub last_delivery_at: Option<DateTime<Utc>>,
+    /// "HTTP 200", or the error of the last failed delivery
+    #[serde(default)]
+    pub last_result: Option<String>,
+    #[serde(default)]
+    pub consecutive_failures: u32,
+}
+
+impl Webhook {
+    /// Copy safe to return from the API (no secret)
+    pub fn redacted(&self) -> Self {
+        Self {
+            secret: String::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Whether `event` should be delivered to this webhook
+    pub fn matches(&self, event: &MemoryEvent) -> bool {
+        self.enabled
+            && crate::tenant::owns(self.tenant.as_deref(), &event.user_id)
+            && self.user_id.as_ref().is_none_or(|u| *u == event.user_id)
+            && (self.event_types.is_empty()
+                || self
+                    .event_types
+                    .iter()
+                    .any(|t| t.eq_ignore_ascii_case(&event.event_type)))
+            && (self.memory_types.is_empty()
+                || event
+                    .memory_type
+                    .as_ref()
+                    .is_some_and(|m| self.memory_types.iter().any(|t| t.eq_ignore_ascii_case(m))))
+    }
+}
+
+/// Settings for a new webhook
+#[derive(Debug, Clone, Default)]
+pub struct NewWebhook {
+    pub tenant: Option<String>,
+    pub url: String,
+    pub label: Option<String>,
+    pub event_types: Vec<String>,
+    pub user_id: Option<String>,
+    pub memory_types: Vec<String>,
+    pub format: PayloadFormat,
+}
+
+/// Changes to a webhook; `None` leaves a field as it is
+#[derive(Debug, Clone, Default)]
+pub struct WebhookUpdate {
+    pub url: Option<String>,
+    pub label: Option<String>,
+    pub event_types: Option<Vec<String>>,
+    pub memory_types: Option<Vec<String>>,
+    pub format: Option<PayloadFormat>,
+    /// Re-enabling also clears the failure count
+    pub enabled: Option<bool>,
+}
+
+/// Whether webhooks may be delivered to `ip`: not loopback, private,
+/// link-local, unique-local, shared (CGNAT), multicast or unspecified
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                || v4.is_documentation()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = v6.segments()[0];
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+/// Check a webhook URL: absolute http(s) with a host that isn't an internal
+/// address or a `localhost` name. Names are checked when they resolve (see
+/// [`check_destination`]).
+pub fn validate_url(url: &str) -> Result<()> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| anyhow!("invalid URL: {e}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(anyhow!("URL scheme must be http or https"));
+    }
+    let host = parsed
+        .host_str()
+        .filter(|h| !h.is_empty())
+        .ok_or_else(|| anyhow!("URL has no host"))?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase();
+    if host == "localhost" || host.ends_with(".localhost") {
+        return Err(anyhow!("URL must not point at localhost"));
+    }
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        if !is_public_ip(ip) {
+            return Err(anyhow!("URL must not point at an internal address ({ip})"));
+        }
+    }
+    Ok(())
+}
+
+/// [`validate_url`], then resolve the host and refuse it if any address it
+/// resolves to is internal. A host that doesn't resolve yet is accepted;
+/// deliveries resolve it again and only ever connect to public addresses.
+pub async fn check_destination(url: &str) -> Result<()> {
+    validate_url(url)?;
+    let parsed = reqwest::Url::parse(url)?;
+    let (Some(host), Some(port)) = (parsed.host_str(), parsed.port_or_known_default()) else {
+        return Ok(());
+    };
+    if let Ok(addrs) = tokio::net::lookup_host((host, port)).await {
+        for addr in addrs {
+            if !is_public_ip(addr.ip()) {
+                return Err(anyhow!(
+                    "URL host {host} resolves to an internal address ({})",
+                    addr.ip()
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Resolver for deliveries: keeps only public addresses, so a host name
+/// re-pointed at an internal address after registration can't be reached
+struct PublicResolver;
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(resolve_public(host))
+    }
+}
+
+async fn resolve_public(
+    host: String,
+) -> Result<reqwest::dns::Addrs, Box<dyn std::error::Error + Send + Sync>> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+        .await?
+        .filter(|addr| is_public_ip(addr.ip()))
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("{host} has no public address").into());
+    }
+    Ok(Box::new(addrs.into_iter()))
+}
+
+fn normalize_types(types: Vec<String>) -> Vec<String> {
+    let mut types: Vec<String> = types
+        .into_iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    types.sort();
+    types.dedup();
+    types
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    format!("{SECRET_PREFIX}{}", hex::encode(bytes))
+}
+
+/// Value of the `X-Shodh-Signature` header for `body` sent at `timestamp`
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Request body delivering `event` to `hook`, with tenant qualifiers removed
+fn payload(hook: &Webhook, event: &MemoryEvent) -> Vec<u8> {
+    let body = match hook.format {
+        PayloadFormat::Json => serde_json::json!({
+            "webhook_id": hook.id,
+            "event": event,
+        }),
+        PayloadFormat::Slack => {
+            let user_id = hook
+                .tenant
+                .as_ref()
+                .and_then(|t| {
+                    event
+                        .user_id
+                        .strip_prefix(&format!("{t}{}", crate::tenant::SEPARATOR))
+                })
+                .unwrap_or(&event.user_id);
+            let mut text = format!("*{}* memory event for `{user_id}`", event.event_type);
+            if let Some(memory_type) = &event.memory_type {
+                text.push_str(&format!(" ({memory_type})"));
+            }
+            if let Some(preview) = &event.content_preview {
+                text.push_str(&format!(": {preview}"));
+            }
+            serde_json::json!({ "text": text })
+        }
+    };
+    let bytes = serde_json::to_vec(&body).unwrap_or_default();
+    match &hook.tenant {
+        Some(tenant) => crate::tenant::strip_qualifiers(tenant, &bytes),
+        None => bytes,
+    }
+}
+
+/// Persistent webhooks, indexed in memory by id
+pub struct WebhookStore {
+    db: Arc<DB>,
+    hooks: RwLock<HashMap<String, Webhook>>,
+}
+
+impl WebhookStore {
+    /// Column family descriptors required by the WebhookStore.
+    /// The caller must include these (plus `"default"`) when opening the shared DB.
+    pub fn cf_descriptors() -> Vec<ColumnFamilyDescriptor> {
+        let mut cf_opts = Options::default();
+        cf_opts.create_if_missing(true);
+        vec![ColumnFamilyDescriptor::new(CF_WEBHOOKS, cf_opts)]
+    }
+
+    /// Open the store and load every webhook
+    pub fn new(db: Arc<DB>) -> Result<Self> {
+        let store = Self {
+            db,
+            hooks: RwLock::new(HashMap::new()),
+        };
+        let mut hooks = HashMap::new();
+        for item in store.db.iterator_cf(store.cf(), IteratorMode::Start) {
+            let (_, value) = item.context("Failed to read webhook")?;
+            match serde_json::from_slice::<Webhook>(&value) {
+                Ok(hook) => {
+                    hooks.insert(hook.id.clone(), hook);
+                }
+                Err(e) => tracing::warn!("Skipping unreadable webhook record: {}", e),
+            }
+        }
+        *store.hooks.write() = hooks;
+        Ok(store)
+    }
+
+    fn cf(&self) -> &ColumnFamily {
+        self.db
+            .cf_handle(CF_WEBHOOKS)
+            .expect("webhooks CF must exist")
+    }
+
+    fn put(&self, hook: &Webhook) -> Result<()> {
+        self.db
+            .put_cf(self.cf(), hook.id.as_bytes(), serde_json::to_vec(hook)?)?;
+        Ok(())
+    }
+
+    /// Register a webhook. The returned record includes its secret.
+    pub fn create(&self, new: NewWebhook) -> Result<Webhook> {
+        validate_url(&new.url)?;
+        let hook = Webhook {
+            id: uuid::Uuid::new_v4().to_string(),
+            tenant: new.tenant,
+            url: new.url,
+            secret: generate_secret(),
+            label: new.label,
+            event_types: normalize_types(new.event_types),
+            user_id: new.user_id,
+            memory_types: normalize_types(new.memory_types),
+            format: new.format,
+            enabled: true,
+            created_at: Utc::now(),
+            last_delivery_at: None,
+            last_result: None,
+            consecutive_failures: 0,
+        };
+        self.put(&hook)?;
+        self.hooks.write().insert(hook.id.clone(), hook.clone());
+        Ok(hook)
+    }
+
+    /// Apply `update` to a webhook; `None` if there is no such webhook
+    pub fn update(&self, id: &str, update: WebhookUpdate) -> Result<Option<Webhook>> {
+        if let Some(url) = &update.url {
+            validate_url(url)?;
+        }
+        let mut hooks = self.hooks.write();
+        let Some(hook) = hooks.get_mut(id) else {
+            return Ok(None);
+        };
+        if let Some(url) = update.url {
+            hook.url = url;
+        }
+        if let Some(label) = update.label {
+            hook.label = Some(label).filter(|l| !l.trim().is_empty());
+        }
+        if let Some(types) = update.event_types {
+            hook.event_types = normalize_types(types);
+        }
+        if let Some(types) = update.memory_types {
+            hook.memory_types = normalize_types(types);
+        }
+        if let Some(format) = update.format {
+            hook.format = format;
+        }
+        if let Some(enabled) = update.enabled {
+            if enabled && !hook.enabled {
+                hook.consecutive_failures = 0;
+            }
+            hook.enabled = enabled;
+        }
+        let hook = hook.clone();
+        self.put(&hook)?;
+        Ok(Some(hook))
+    }
+
+    /// Remove a webhook; returns it, or `None` if there is no such webhook
+    pub fn delete(&self, id: &str) -> Result<Option<Webhook>> {
+        let mut hooks = self.hooks.write();
+        if !hooks.contains_key(id) {
+            return Ok(None);
+        }
+        self.db.delete_cf(self.cf(), id.as_bytes())?;
+        Ok(hooks.remove(id))
+    }
+
+    pub fn get(&self, id: &str) -> Option<Webhook> {
+        self.hooks.read().get(id).cloned()
+    }
+
+    /// Webhooks of `tenant` (`None` = default tenant), newest first
+    pub fn list(&self, tenant: Option<&str>) -> Vec<Webhook> {
+        let mut list: Vec<Webhook> = self
+            .hooks
+            .read()
+            .values()
+            .filter(|h| h.tenant.as_deref() == tenant)
+            .cloned()
+            .collect();
+        list.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        list
+    }
+
+    /// Webhooks `event` should be delivered to
+    pub fn matching(&self, event: &MemoryEvent) -> Vec<Webhook> {
+        self.hooks
+            .read()
+            .values()
+            .filter(|h| h.matches(event))
+            .cloned()
+            .collect()
+    }
+
+    /// Note the outcome of a delivery, disabling the webhook after too many
+    /// failures in a row
+    pub fn record_delivery(&self, id: &str, result: std::result::Result<u16, String>) {
+        let mut hooks = self.hooks.write();
+        let Some(hook) = hooks.get_mut(id) else {
+            return;
+        };
+        hook.last_delivery_at = Some(Utc::now());
+        match result {
+            Ok(status) => {
+                hook.last_result = Some(format!("HTTP {status}"));
+                hook.consecutive_failures = 0;
+            }
+            Err(error) => {
+                hook.last_result = Some(error);
+                hook.consecutive_failures += 1;
+                if hook.enabled && hook.consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                    hook.enabled = false;
+                    tracing::warn!(
+                        webhook_id = %id,
+                        failures = hook.consecutive_failures,
+                        "Webhook disabled after repeated delivery failures"
+                    );
+                }
+            }
+        }
+        if let Err(e) = self.put(hook) {
+            tracing::warn!("Failed to record delivery of webhook {id}: {e}");
+        }
+    }
+}
+
+/// Delay before retry number `attempt` (1-based)
+fn backoff(attempt: u32) -> Duration {
+    BASE_BACKOFF
+        .saturating_mul(1 << (attempt - 1).min(16))
+        .min(MAX_BACKOFF)
+}
+
+/// Statuses worth retrying: the receiver may accept the delivery later
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status.is_server_error()
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+/// POST `event` to `hook`, retrying with backoff
+async fn deliver(
+    client: reqwest::Client,
+    store: Arc<WebhookStore>,
+    hook: Webhook,
+    event: MemoryEvent,
+) {
+    let body = payload(&hook, &event);
+    let delivery_id = uuid::Uuid::new_v4().to_string();
+
+    // The URL was checked on registration; checked again in case the rules
+    // tightened since. Host names are checked by the client's resolver.
+    if let Err(e) = validate_url(&hook.url) {
+        store.record_delivery(&hook.id, Err(e.to_string()));
+        return;
+    }
+
+    let mut error = String::new();
+    for attempt in 1..=MAX_ATTEMPTS {
+        let timestamp = Utc::now().timestamp();
+        let response = client
+            .post(&hook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-Shodh-Event", &event.event_type)
+            .header("X-Shodh-Delivery", &delivery_id)
+            .header("X-Shodh-Timestamp", timestamp.to_string())
+            .header("X-Shodh-Signature", sign(&hook.secret, timestamp, &body))
+            .body(body.clone())
+            .send()
+            .await;
+        match response {
+            Ok(response) if response.status().is_success() => {
+                WEBHOOK_DELIVERIES_TOTAL
+                    .with_label_values(&["success"])
+                    .inc();
+                store.record_delivery(&hook.id, Ok(response.status().as_u16()));
+                return;
+            }
+            Ok(response) if !is_retryable(response.status()) => {
+                error = format!("HTTP {}", response.status().as_u16());
+                break;
+            }
+            Ok(response) => error = format!("HTTP {}", response.status().as_u16()),
+            Err(e) => error = e.to_string(),
+        }
+        if attempt < MAX_ATTEMPTS {
+            WEBHOOK_DELIVERIES_TOTAL.with_label_values(&["retry"]).inc();
+            tokio::time::sleep(backoff(attempt)).await;
+        }
+    }
+
+    WEBHOOK_DELIVERIES_TOTAL
+        .with_label_values(&["failed"])
+        .inc();
+    tracing::warn!(
+        webhook_id = %hook.id,
+        delivery_id = %delivery_id,
+        event_type = %event.event_type,
+        "Webhook delivery failed: {error}"
+    );
+    store.record_delivery(&hook.id, Err(error));
+}
+
+/// Deliver every event from `events` to the webhooks it matches, until the
+/// channel closes. Each delivery runs in its own task, so a slow receiver
+/// doesn't hold up the others.
+pub fn spawn_dispatcher(
+    store: Arc<WebhookStore>,
+    mut events: broadcast::Receiver<MemoryEvent>,
+) -> tokio::task::JoinHandle<()> {
+    let client = reqwest::Client::builder()
+        .timeout(DELIVERY_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .dns_resolver(Arc::new(PublicResolver))
+        .build()
+        .expect("webhook HTTP client builds with static settings");
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    for hook in store.matching(&event) {
+                        tokio::spawn(deliver(client.clone(), store.clone(), hook, event.clone()));
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "Webhook dispatcher fell behind; events dropped");
+                }
+                Err(RecvError::Closed) => return,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_store(path: &std::path::Path) -> WebhookStore {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let mut cfs = vec![ColumnFamilyDescriptor::new("default", Options::default())];
+        cfs.extend(WebhookStore::cf_descriptors());
+        let db = DB::open_cf_descriptors(&opts, path, cfs).unwrap();
+        WebhookStore::new(Arc::new(db)).unwrap()
+    }
+
+    fn event(user_id: &str, event_type: &str, memory_type: Option<&str>) -> MemoryEvent {
+        MemoryEvent {
+            event_type: event_type.to_string(),
+            timestamp: Utc::now(),
+            user_id: user_id.to_string(),
+            memory_id: None,
+            content_preview: Some("Chose Postgres over Mongo".to_string()),
+            memory_type: memory_type.map(str::to_string),
+            importance: None,
+            count: None,
+            results: None,
+        }
+    }
+
+    #[test]
+    fn test_matching() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = open_store(dir.path());
+        let hook = store
+            .create(NewWebhook {
+                tenant: Some("acme".to_string()),
+                url: "https://hooks.example.com/shodh".to_string(),
+                event_types: vec!["create".to_string()],
+                memory_types: vec!["Decision".to_string()],
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(hook.secret.starts_with(SECRET_PREFIX));
+
+        assert!(hook.matches(&event("acme~alice", "CREATE", Some("Decision"))));
+        assert!(!hook.matches(&event("acme~alice", "CREATE", Some("Learning"))));
+        assert!(!hook.matches(&event("acme~alice", "DELETE", Some("Decision"))));
+        assert!(!hook.matches(&event("globex~alice", "CREATE", Some("Decision"))));
+        assert!(!hook.matches(&event("alice", "CREATE", Some("Decision"))));
+
+        store
+            .update(
+                &hook.id,
+                WebhookUpdate {
+                    enabled: Some(false),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert!(store
+            .matching(&event("acme~alice", "CREATE", Some("Decision")))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_persistence_and_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let id = {
+            let store = open_store(dir.path());
+            assert!(store
+                .create(NewWebhook {
+                    url: "ftp://example.com".to_string(),
+                    ..Default::default()
+                })
+                .is_err());
+            let hook = store
+                .create(NewWebhook {
+                    url: "http://hooks.example.com:9000/hook".to_string(),
+                    ..Default::default()
+                })
+                .unwrap();
+            for _ in 0..MAX_CONSECUTIVE_FAILURES {
+                store.record_delivery(&hook.id, Err("HTTP 500".to_string()));
+            }
+            hook.id
+        };
+
+        let store = open_store(dir.path());
+        let hook = store.get(&id).unwrap();
+        assert!(!hook.enabled);
+        assert_eq!(hook.last_result.as_deref(), Some("HTTP 500"));
+        assert_eq!(store.list(None).len(), 1);
+        assert!(store.list(Some("acme")).is_empty());
+        assert!(!serde_json::to_string(&hook.redacted())
+            .unwrap()
+            .contains("secret"));
+        assert!(store.delete(&id).unwrap().is_some());
+        assert!(store.get(&id).is_none());
+    }
+
+    #[test]
+    fn test_internal_destinations_are_refused() {
+        for url in [
+            "http://localhost:9000/hook",
+            "http://api.localhost/hook",
+            "http://127.0.0.1:8080/",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://10.0.0.5/hook",
+            "http://172.16.3.4/hook",
+            "http://192.168.1.1/hook",
+            "http://100.64.0.1/hook",
+            "http://0.0.0.0/hook",
+            "http://[::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+        ] {
+            assert!(validate_url(url).is_err(), "{url}");
+        }
+        assert!(validate_url("https://93.184.216.34/hook").is_ok());
+        assert!(validate_url("https://[2606:4700::1111]/hook").is_ok());
+        assert!(validate_url("https://hooks.example.com/hook").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_destinations_resolving_internally_are_refused() {
+        assert!(check_destination("http://[::1]:9000/hook").await.is_err());
+        // Not resolvable yet: accepted, deliveries resolve it again
+        assert!(check_destination("http://hooks.shodh.invalid/hook")
+            .await
+            .is_ok());
+    }
+
+    #[test]
+    fn test_signature_and_payload() {
+        let signature = sign("whsec_test", 1_700_000_000, b"{}");
+        let mut mac = HmacSha256::new_from_slice(b"whsec_test").unwrap();
+        mac.update(b"1700000000.{}");
+        assert_eq!(
+            signature,
+            format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+        );
+
+        let hook = Webhook {
+            id: "hook-1".to_string(),
+            tenant: Some("acme".to_string()),
+            url: "https://hooks.slack.com/services/x".to_string(),
+            secret: String::new(),
+            label: None,
+            event_types: Vec::new(),
+            user_id: None,
+            memory_types: Vec::new(),
+            format: PayloadFormat::Slack,
+            enabled: true,
+            created_at: Utc::now(),
+            last_delivery_at: None,
+            last_result: None,
+            consecutive_failures: 0,
+        };
+        let body: serde_json::Value =
+            serde_json::from_slice(&payload(&hook, &event("acme~alice", "CREATE", None))).unwrap();
+        assert_eq!(
+            body["text"],
+            "*CREATE* memory event for `alice`: Chose Postgres over Mongo"
+        );
+
+        assert_eq!(backoff(1), BASE_BACKOFF);
+        assert_eq!(backoff(3), BASE_BACKOFF * 4);
+        assert_eq!(backoff(30), MAX_BACKOFF);
+    }
+}
@@ -0,0 +1,475 @@
+//! Namespace ACL Handlers
+//!
+//! `enforce_acl` runs on every protected route and checks the caller's API key
+//! against the namespaces a request touches: `user_id` and `team_id` from the
+//! path, query string or JSON body, plus the `X-Shodh-Team` header. Namespaces
+//! without grants are open, so nothing changes until an operator adds one;
+//! from then on a team is open only to the keys granted on it.
+//!
+//! /api/admin/acl lists, adds and revokes grants. Adding and revoking fail
+//! closed: only admin keys (SHODH_ADMIN_API_KEYS or managed keys with the
+//! `admin` scope) may do either, so with no admin key configured grants
+//! can't be changed over the API and a restricted key can't grant itself
+//! access. Admin keys also bypass enforcement.
+
+use std::collections::HashMap;
+
+use axum::{
+    body::Body,
+    extract::{FromRequestParts, MatchedPath, Query, RawPathParams, Request, State},
+    http::{header, request::Parts, Method},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+    Extension,
+};
+use serde::{Deserialize, Serialize};
+
+use super::state::MultiUserMemoryManager;
+use super::teams::{team_namespace, TEAM_HEADER};
+use super::tenants::scope_id;
+use crate::acl::{self, AclGrant, Permission};
+use crate::api_keys::{scopes_allow, KeyScope};
+use crate::auth::{self, AuthenticatedKey};
+use crate::errors::{AppError, ValidationErrorExt};
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// Largest JSON body inspected for namespaces (matches the import limit)
+pub(super) const MAX_SCANNED_BODY_BYTES: usize = super::import::MAX_IMPORT_BYTES;
+
+/// Path segments holding a namespace; ids in the query and body are scoped
+/// to the caller's tenant before this runs (see `tenants::scope_tenant`)
+const SCOPED_PATH_PARAMS: &[&str] = &["user_id", "team_id"];
+
+/// POST routes that only retrieve; every other non-GET route needs write access
+const READ_ONLY_POST_ROUTES: &[&str] = &[
+    "/api/recall",
+    "/api/recall/tracked",
+    "/api/recall/tags",
+    "/api/recall/by-tags",
+    "/api/recall/date",
+    "/api/recall/related",
+    "/api/recall_related",
+    "/api/context_summary",
+    "/api/proactive_context",
+    "/api/context",
+    "/api/relevant",
+    "/api/memories",
+    "/api/search",
+    "/api/search/advanced",
+    "/api/search/keyword",
+    "/api/search/multimodal",
+    "/api/search/robotics",
+    "/api/facts/list",
+    "/api/facts/search",
+    "/api/facts/by-entity",
+    "/api/facts/stats",
+    "/api/lineage/trace",
+    "/api/lineage/edges",
+    "/api/lineage/stats",
+    "/api/lineage/branches",
+    "/api/graph/entity/find",
+    "/api/graph/entities/all",
+    "/api/graph/traverse",
+    "/api/graph/neighborhood",
+    "/api/graph/episode/get",
+    "/api/todos",
+    "/api/todos/list",
+    "/api/todos/due",
+    "/api/todos/stats",
+    "/api/projects/list",
+    "/api/projects/{project_id}/files",
+    "/api/projects/{project_id}/files/search",
+    "/api/reminders",
+    "/api/reminders/due",
+    "/api/reminders/check",
+    "/api/sessions",
+    "/api/export/mif",
+];
+
+/// Access a route needs on the namespaces it touches
+pub(super) fn required_permission(method: &Method, route: &str) -> Permission {
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+        || (*method == Method::POST && READ_ONLY_POST_ROUTES.contains(&route))
+    {
+        Permission::Read
+    } else {
+        Permission::Write
+    }
+}
+
+/// Namespaces named by a request and the access each needs.
+///
+/// Team namespaces are only written by `visibility: "team"` stores; any other
+/// request naming a team just reads it alongside the user's own memories.
+fn requested_namespaces(
+    parts: &Parts,
+    path_params: &[(String, String)],
+    body: Option<&serde_json::Value>,
+) -> Vec<(String, Permission)> {
+    let route = parts
+        .extensions
+        .get::<MatchedPath>()
+        .map(|p| p.as_str())
+        .unwrap_or_else(|| parts.uri.path());
+    let required = required_permission(&parts.method, route);
+    let query: HashMap<String, String> = Query::try_from_uri(&parts.uri)
+        .map(|Query(q)| q)
+        .unwrap_or_default();
+    let field = |name: &str| -> Option<String> {
+        path_params
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.clone())
+            .or_else(|| query.get(name).cloned())
+            .or_else(|| {
+                body.and_then(|b| b.get(name))
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            })
+    };
+
+    let mut namespaces = Vec::new();
+    if let Some(user_id) = field("user_id") {
+        namespaces.push((user_id, required));
+    }
+    let shares_to_team = body
+        .and_then(|b| b.get("visibility"))
+        .and_then(|v| v.as_str())
+        == Some("team");
+    let team = field("team_id").or_else(|| {
+        parts
+            .headers
+            .get(TEAM_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim().to_string())
+    });
+    if let Some(team) = team.filter(|t| !t.is_empty()) {
+        let team_required = if shares_to_team || path_params.iter().any(|(k, _)| k == "team_id") {
+            required
+        } else {
+            Permission::Read
+        };
+        namespaces.push((team_namespace(&team), team_required));
+    }
+    namespaces
+}
+
+/// Whether the body is one axum's `Json` extractor accepts: `application/json`
+/// or any `application/*+json`, compared case-insensitively. Middleware that
+/// inspects bodies must use this, or a body it skips still reaches the handler.
+pub(super) fn is_json(parts: &Parts) -> bool {
+    parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(is_json_content_type)
+}
+
+fn is_json_content_type(content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    match essence.split_once('/') {
+        Some(("application", subtype)) => subtype == "json" || subtype.ends_with("+json"),
+        _ => false,
+    }
+}
+
+/// Middleware: reject requests whose API key lacks access to a restricted namespace
+pub async fn enforce_acl(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let key = request.extensions().get::<AuthenticatedKey>().cloned();
+    if state.acl_store.is_empty() || key.as_ref().is_some_and(|k| k.admin) {
+        return next.run(request).await;
+    }
+
+    let (mut parts, body) = request.into_parts();
+    let path_params: Vec<(String, String)> =
+        match RawPathParams::from_request_parts(&mut parts, &()).await {
+            Ok(params) => {
+                let mut scoped = Vec::new();
+                for (k, v) in params.iter() {
+                    let v = if SCOPED_PATH_PARAMS.contains(&k) {
+                        match scope_id(key.as_ref(), k, v) {
+                            Ok(v) => v,
+                            Err(e) => return e.into_response(),
+                        }
+                    } else {
+                        v.to_string()
+                    };
+                    scoped.push((k.to_string(), v));
+                }
+                scoped
+            }
+            Err(_) => Vec::new(),
+        };
+
+    let (body, json) = if is_json(&parts) {
+        let bytes = match axum::body::to_bytes(body, MAX_SCANNED_BODY_BYTES).await {
+            Ok(bytes) => bytes,
+            Err(_) => {
+                return AppError::ContentTooLarge {
+                    size: MAX_SCANNED_BODY_BYTES + 1,
+                    max: MAX_SCANNED_BODY_BYTES,
+                }
+                .into_response()
+            }
+        };
+        let json = serde_json::from_slice::<serde_json::Value>(&bytes).ok();
+        (Body::from(bytes), json)
+    } else {
+        (body, None)
+    };
+
+    let key_id = key.as_ref().map(|k| k.key_id.as_str());
+    for (namespace, required) in requested_namespaces(&parts, &path_params, json.as_ref()) {
+        if !state.acl_store.allows(&namespace, key_id, required) {
+            tracing::warn!(
+                namespace = %namespace,
+                key_id = key_id.unwrap_or("none"),
+                "ACL denied {} access",
+                required.as_str()
+            );
+            return AppError::Forbidden(format!(
+                "API key has no {} access to '{namespace}'",
+                required.as_str()
+            ))
+            .into_response();
+        }
+    }
+
+    next.run(Request::from_parts(parts, body)).await
+}
+
+/// Only admin keys may perform `action` once SHODH_ADMIN_API_KEYS is set;
+/// tenant keys and scoped keys without the `admin` scope never may
+pub(super) fn require_admin(key: Option<&AuthenticatedKey>, action: &str) -> Result<(), AppError> {
+    let restricted =
+        key.is_some_and(|k| k.tenant.is_some() || !scopes_allow(&k.scopes, KeyScope::Admin));
+    if !restricted && (!auth::admin_keys_configured() || key.is_some_and(|k| k.admin)) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(format!(
+            "{action} requires an admin API key"
+        )))
+    }
+}
+
+/// Only admin keys may perform `action`, whether or not SHODH_ADMIN_API_KEYS
+/// is set. For actions that would let a key lift its own restrictions.
+pub(super) fn require_configured_admin(
+    key: Option<&AuthenticatedKey>,
+    action: &str,
+) -> Result<(), AppError> {
+    if key.is_some_and(|k| k.admin) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(format!(
+            "{action} requires an admin API key (SHODH_ADMIN_API_KEYS)"
+        )))
+    }
+}
+
+/// Key reference in ACL requests: the raw key (hashed here) or its id
+fn resolve_key_id(api_key: Option<&str>, key_id: Option<&str>) -> Result<String, AppError> {
+    match (api_key, key_id) {
+        (Some(api_key), _) if !api_key.trim().is_empty() => Ok(auth::key_id(api_key)),
+        (_, Some(key_id)) if !key_id.trim().is_empty() => Ok(key_id.trim().to_string()),
+        _ => Err(AppError::InvalidInput {
+            field: "key_id".to_string(),
+            reason: "set api_key or key_id".to_string(),
+        }),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AclListQuery {
+    pub namespace: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AclListResponse {
+    pub grants: Vec<AclGrant>,
+    pub count: usize,
+}
+
+/// GET /api/admin/acl?namespace=... - List grants
+pub async fn list_acl(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Query(params): Query<AclListQuery>,
+) -> Result<Json<AclListResponse>, AppError> {
+    require_admin(key.as_deref(), "ACL management")?;
+    let grants = state.acl_store.list(params.namespace.as_deref());
+    Ok(Json(AclListResponse {
+        count: grants.len(),
+        grants,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AclGrantRequest {
+    /// A user id or `teams/{team_id}`
+    pub namespace: String,
+    /// Key to grant (hashed before storage); alternatively `key_id`
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub key_id: Option<String>,
+    pub permission: Permission,
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// POST /api/admin/acl - Grant a key read or write access to a namespace
+pub async fn grant_acl(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Json(req): Json<AclGrantRequest>,
+) -> Result<Json<AclGrant>, AppError> {
+    require_configured_admin(key.as_deref(), "Granting ACL access")?;
+    acl::validate_namespace(&req.namespace).map_validation_err("namespace")?;
+    let grant = AclGrant {
+        namespace: req.namespace,
+        key_id: resolve_key_id(req.api_key.as_deref(), req.key_id.as_deref())?,
+        permission: req.permission,
+        label: req.label,
+        granted_at: chrono::Utc::now(),
+    };
+    state
+        .acl_store
+        .grant(grant.clone())
+        .map_err(AppError::Internal)?;
+    tracing::info!(
+        namespace = %grant.namespace,
+        key_id = %grant.key_id,
+        "ACL granted {}",
+        grant.permission.as_str()
+    );
+    Ok(Json(grant))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AclRevokeQuery {
+    pub namespace: String,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub key_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AclRevokeResponse {
+    pub revoked: bool,
+    pub namespace: String,
+    pub key_id: String,
+}
+
+/// DELETE /api/admin/acl?namespace=...&key_id=... - Revoke a grant
+pub async fn revoke_acl(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Query(params): Query<AclRevokeQuery>,
+) -> Result<Json<AclRevokeResponse>, AppError> {
+    require_configured_admin(key.as_deref(), "Revoking ACL access")?;
+    let key_id = resolve_key_id(params.api_key.as_deref(), params.key_id.as_deref())?;
+    let revoked = state
+        .acl_store
+        .revoke(&params.namespace, &key_id)
+        .map_err(AppError::Internal)?;
+    Ok(Json(AclRevokeResponse {
+        revoked,
+        namespace: params.namespace,
+        key_id,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(method: Method, uri: &str, team: Option<&str>) -> Parts {
+        let mut builder = axum::http::Request::builder().method(method).uri(uri);
+        if let Some(team) = team {
+            builder = builder.header(TEAM_HEADER, team);
+        }
+        builder.body(()).unwrap().into_parts().0
+    }
+
+    #[test]
+    fn test_json_content_types_match_axum() {
+        for ct in [
+            "application/json",
+            "application/json; charset=utf-8",
+            "Application/JSON",
+            "application/cloudevents+json",
+            "APPLICATION/VND.API+JSON;charset=utf-8",
+        ] {
+            assert!(is_json_content_type(ct), "{ct}");
+        }
+        for ct in [
+            "text/json",
+            "application/jsonp",
+            "application/x-www-form-urlencoded",
+        ] {
+            assert!(!is_json_content_type(ct), "{ct}");
+        }
+    }
+
+    #[test]
+    fn test_acl_changes_need_an_admin_key() {
+        let key = |admin| AuthenticatedKey {
+            key_id: "k".to_string(),
+            admin,
+            tenant: None,
+            scopes: Vec::new(),
+        };
+        assert!(require_configured_admin(None, "Granting").is_err());
+        assert!(require_configured_admin(Some(&key(false)), "Granting").is_err());
+        assert!(require_configured_admin(Some(&key(true)), "Granting").is_ok());
+    }
+
+    #[test]
+    fn test_requested_namespaces() {
+        // Recall reads the user and the header team
+        let p = parts(Method::POST, "/api/recall", Some("platform"));
+        let body = serde_json::json!({"user_id": "alice", "query": "db"});
+        assert_eq!(
+            requested_namespaces(&p, &[], Some(&body)),
+            vec![
+                ("alice".to_string(), Permission::Read),
+                ("teams/platform".to_string(), Permission::Read),
+            ]
+        );
+
+        // A private remember only writes the user; a team remember writes the team
+        let p = parts(Method::POST, "/api/remember", Some("platform"));
+        let private = serde_json::json!({"user_id": "alice", "content": "x"});
+        assert_eq!(
+            requested_namespaces(&p, &[], Some(&private))[1],
+            ("teams/platform".to_string(), Permission::Read)
+        );
+        let shared = serde_json::json!({"user_id": "alice", "visibility": "team"});
+        assert_eq!(
+            requested_namespaces(&p, &[], Some(&shared))[1],
+            ("teams/platform".to_string(), Permission::Write)
+        );
+
+        // Path and query parameters
+        let p = parts(Method::DELETE, "/api/users/bob", None);
+        let params = vec![("user_id".to_string(), "bob".to_string())];
+        assert_eq!(
+            requested_namespaces(&p, &params, None),
+            vec![("bob".to_string(), Permission::Write)]
+        );
+        let p = parts(Method::GET, "/api/conflicts?user_id=carol", None);
+        assert_eq!(
+            requested_namespaces(&p, &[], None),
+            vec![("carol".to_string(), Permission::Read)]
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Contradiction Review Handlers
+//!
+//! Lists memories that a newer memory contradicts, so a client can confirm the
+//! newer fact or forget the stale one. Recall already prefers the newer memory;
+//! this only surfaces the pairs.
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::state::MultiUserMemoryManager;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::memory::types::Memory;
+use crate::validation;
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// Largest conflict list returned by GET /api/conflicts
+const MAX_CONFLICTS_LIMIT: usize = 200;
+
+/// Query parameters for GET /api/conflicts
+#[derive(Debug, Deserialize)]
+pub struct ConflictsQuery {
+    pub user_id: String,
+    #[serde(default = "default_conflicts_limit")]
+    pub limit: usize,
+}
+
+fn default_conflicts_limit() -> usize {
+    50
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConflictMemory {
+    pub id: String,
+    pub content: String,
+    pub memory_type: String,
+    pub importance: f32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&Memory> for ConflictMemory {
+    fn from(memory: &Memory) -> Self {
+        Self {
+            id: memory.id.0.to_string(),
+            content: memory.experience.content.clone(),
+            memory_type: format!("{:?}", memory.experience.experience_type),
+            importance: memory.importance(),
+            created_at: memory.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConflictItem {
+    /// Older memory, down-weighted at recall
+    pub stale: ConflictMemory,
+    /// Newer memory that contradicts it
+    pub current: ConflictMemory,
+    /// "negation" or "replacement"
+    pub kind: Option<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConflictsResponse {
+    pub conflicts: Vec<ConflictItem>,
+    pub count: usize,
+}
+
+/// GET /api/conflicts?user_id=...&limit=50 - Contradicted memories, most recent first
+#[tracing::instrument(skip(state), fields(user_id = %params.user_id))]
+pub async fn get_conflicts(
+    State(state): State<AppState>,
+    Query(params): Query<ConflictsQuery>,
+) -> Result<Json<ConflictsResponse>, AppError> {
+    validation::validate_user_id(&params.user_id).map_validation_err("user_id")?;
+
+    let memory = state
+        .get_user_memory(&params.user_id)
+        .map_err(AppError::Internal)?;
+
+    let limit = params.limit.clamp(1, MAX_CONFLICTS_LIMIT);
+    let conflicts = tokio::task::spawn_blocking(move || memory.read().conflicts(limit))
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+        .map_err(AppError::Internal)?;
+
+    let conflicts: Vec<ConflictItem> = conflicts
+        .iter()
+        .map(|c| ConflictItem {
+            stale: ConflictMemory::from(c.stale.as_ref()),
+            current: ConflictMemory::from(c.current.as_ref()),
+            kind: c.kind.map(|k| k.as_str()),
+        })
+        .collect();
+
+    Ok(Json(ConflictsResponse {
+        count: conflicts.len(),
+        conflicts,
+    }))
+}
@@ -3,19 +3,32 @@
 //! Semantic consolidation for fact extraction, vector index maintenance,
 //! and backup/restore operations.
 
-use axum::{extract::State, response::Json};
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{Json, Response},
+    Extension,
+};
+use serde::Serialize;
 
+use super::acl::require_admin;
 use super::state::MultiUserMemoryManager;
 use super::types::{
     BackupResponse, CleanupCorruptedRequest, CleanupCorruptedResponse, ConsolidateRequest,
-    ConsolidateResponse, CreateBackupRequest, ListBackupsRequest, ListBackupsResponse, MemoryEvent,
-    MigrateLegacyRequest, MigrateLegacyResponse, PurgeBackupsRequest, PurgeBackupsResponse,
-    RebuildIndexRequest, RebuildIndexResponse, RepairIndexRequest, RepairIndexResponse,
-    RestoreBackupRequest, RestoreBackupResponse, VerifyBackupRequest, VerifyBackupResponse,
-    VerifyIndexRequest,
+    ConsolidateResponse, CreateBackupRequest, DedupRequest, DedupResponse, DuplicateClusterInfo,
+    ExpiredMemoryInfo, GistInfo, GistRequest, GistResponse, ListBackupsRequest,
+    ListBackupsResponse, MemoryEvent, MigrateLegacyRequest, MigrateLegacyResponse,
+    PurgeBackupsRequest, PurgeBackupsResponse, RebuildIndexRequest, RebuildIndexResponse,
+    ReembedRequest, ReencryptRequest, RepairIndexRequest, RepairIndexResponse,
+    RestoreBackupRequest, RestoreBackupResponse, RetentionRequest, RetentionResponse,
+    VerifyBackupRequest, VerifyBackupResponse, VerifyIndexRequest,
 };
+use crate::auth::AuthenticatedKey;
+use crate::embeddings::{ProviderConfig, ProviderKind};
 use crate::errors::{AppError, ValidationErrorExt};
 use crate::memory;
+use crate::memory::encryption::{self, ReencryptStatus};
+use crate::memory::reembed::{reembed, ReembedStatus};
 use crate::metrics;
 use crate::validation;
 
@@ -320,6 +333,212 @@ pub async fn migrate_legacy(
     }))
 }
 
+/// Merge near-duplicate memories into canonical memories
+///
+/// Duplicates are moved to the trash, so a wrong merge can be restored.
+/// Heavy maintenance cycles do this automatically when SHODH_MERGE_DUPLICATES
+/// is on; this endpoint lets clients preview clusters (`dry_run`) or
+/// consolidate on demand.
+#[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
+pub async fn consolidate_duplicates(
+    State(state): State<AppState>,
+    Json(req): Json<DedupRequest>,
+) -> Result<Json<DedupResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let memory_sys = state
+        .get_user_memory(&req.user_id)
+        .map_err(AppError::Internal)?;
+
+    let dry_run = req.dry_run;
+    let clusters =
+        tokio::task::spawn_blocking(move || memory_sys.read().consolidate_duplicates(dry_run))
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+            .map_err(AppError::Internal)?;
+
+    let memories_merged = if dry_run {
+        0
+    } else {
+        clusters.iter().map(|c| c.duplicates.len()).sum()
+    };
+    if memories_merged > 0 {
+        state.log_event(
+            &req.user_id,
+            "MEMORIES_MERGED",
+            &memories_merged.to_string(),
+            &format!(
+                "Merged {} near-duplicates into {} memories",
+                memories_merged,
+                clusters.len()
+            ),
+        );
+    }
+
+    Ok(Json(DedupResponse {
+        success: true,
+        dry_run,
+        clusters: clusters
+            .iter()
+            .map(|c| DuplicateClusterInfo {
+                canonical_id: c.canonical.id.0.to_string(),
+                canonical_preview: c.canonical.experience.content.chars().take(80).collect(),
+                duplicate_ids: c.duplicates.iter().map(|d| d.id.0.to_string()).collect(),
+                min_similarity: c.min_similarity,
+            })
+            .collect(),
+        memories_merged,
+    }))
+}
+
+/// Summarize aged episodic clusters into semantic gist memories
+///
+/// Also runs automatically on heavy maintenance cycles; `dry_run` previews
+/// which episodes would be folded into which gist.
+#[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
+pub async fn consolidate_gists(
+    State(state): State<AppState>,
+    Json(req): Json<GistRequest>,
+) -> Result<Json<GistResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let memory_sys = state
+        .get_user_memory(&req.user_id)
+        .map_err(AppError::Internal)?;
+
+    let dry_run = req.dry_run;
+    let clusters =
+        tokio::task::spawn_blocking(move || memory_sys.read().consolidate_gists(dry_run))
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+            .map_err(AppError::Internal)?;
+
+    let episodes_summarized = if dry_run {
+        0
+    } else {
+        clusters.iter().map(|(c, _)| c.episodes.len()).sum()
+    };
+    if episodes_summarized > 0 {
+        state.log_event(
+            &req.user_id,
+            "GISTS_CREATED",
+            &clusters.len().to_string(),
+            &format!(
+                "Summarized {} aged episodes into {} gists",
+                episodes_summarized,
+                clusters.len()
+            ),
+        );
+    }
+
+    Ok(Json(GistResponse {
+        success: true,
+        dry_run,
+        clusters: clusters
+            .iter()
+            .map(|(c, gist_id)| GistInfo {
+                label: c.label.clone(),
+                episode_ids: c.episodes.iter().map(|m| m.id.0.to_string()).collect(),
+                first_at: c.episodes[0].created_at,
+                last_at: c.episodes[c.episodes.len() - 1].created_at,
+                gist_id: gist_id.as_ref().map(|id| id.0.to_string()),
+            })
+            .collect(),
+        episodes_summarized,
+    }))
+}
+
+/// Enforce the per-type retention policy (SHODH_RETENTION_POLICY plus
+/// custom memory type overrides)
+///
+/// Dry run by default: reports which memories have expired and why. Expired
+/// memories are also deleted automatically on heavy maintenance cycles.
+#[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
+pub async fn apply_retention(
+    State(state): State<AppState>,
+    Json(req): Json<RetentionRequest>,
+) -> Result<Json<RetentionResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let memory_sys = state
+        .get_user_memory(&req.user_id)
+        .map_err(AppError::Internal)?;
+
+    let dry_run = req.dry_run;
+    let policy = state
+        .memory_types
+        .retention_policy(&state.server_config.retention);
+    let expired =
+        tokio::task::spawn_blocking(move || memory_sys.read().apply_retention(&policy, dry_run))
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+            .map_err(AppError::Internal)?;
+
+    let mut by_type = std::collections::BTreeMap::new();
+    for item in &expired {
+        *by_type
+            .entry(format!("{:?}", item.memory.experience.experience_type))
+            .or_insert(0) += 1;
+    }
+    let deleted = if dry_run { 0 } else { expired.len() };
+    if deleted > 0 {
+        state.log_event(
+            &req.user_id,
+            "RETENTION_EXPIRED",
+            &deleted.to_string(),
+            &format!("Deleted {deleted} expired memories: {by_type:?}"),
+        );
+    }
+
+    Ok(Json(RetentionResponse {
+        success: true,
+        dry_run,
+        expired: expired
+            .iter()
+            .map(|e| ExpiredMemoryInfo {
+                id: e.memory.id.0.to_string(),
+                memory_type: format!("{:?}", e.memory.experience.experience_type),
+                content_preview: e.memory.experience.content.chars().take(80).collect(),
+                created_at: e.memory.created_at,
+                rule: e.rule,
+            })
+            .collect(),
+        by_type,
+        deleted,
+    }))
+}
+
+/// Response for GET /api/admin/consolidation
+#[derive(Debug, Serialize)]
+pub struct ConsolidationStatusResponse {
+    /// Current sleep phase: awake, window, idle
+    pub phase: crate::sleep::SleepPhase,
+    /// Whether heavy work waits for a sleep phase (false = fixed cadence)
+    pub sleep_scheduling: bool,
+    #[serde(flatten)]
+    pub status: crate::sleep::ConsolidationStatus,
+}
+
+/// GET /api/admin/consolidation - Background consolidation progress and sleep phase
+pub async fn consolidation_status(
+    State(state): State<AppState>,
+) -> Json<ConsolidationStatusResponse> {
+    Json(ConsolidationStatusResponse {
+        phase: state.sleep.phase(chrono::Utc::now()),
+        sleep_scheduling: state.sleep.config().is_enabled(),
+        status: state.sleep.status(),
+    })
+}
+
+/// Middleware noting API traffic so idle-triggered sleep phases start only
+/// once clients go quiet (admin polling doesn't count)
+pub async fn track_activity(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    if !req.uri().path().starts_with("/api/admin/") {
+        state.sleep.record_activity();
+    }
+    next.run(req).await
+}
+
 /// Rebuild vector index from storage (removes orphaned index entries)
 pub async fn rebuild_index(
     State(state): State<AppState>,
@@ -343,6 +562,208 @@ pub async fn rebuild_index(
     }))
 }
 
+/// POST /api/admin/reembed - Move stores to a new embedding provider/model
+///
+/// Re-embeds each store into a shadow index in the background and switches
+/// it over once complete; see [`memory::reembed`]. Progress is served by
+/// GET /api/admin/reembed. Only one job runs at a time.
+pub async fn start_reembed(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Json(req): Json<ReembedRequest>,
+) -> Result<Json<ReembedStatus>, AppError> {
+    require_admin(key.as_deref(), "Re-embedding")?;
+    let configured = ProviderConfig::from_env();
+    let kind = match req.provider.as_deref() {
+        Some(name) => ProviderKind::parse(name).ok_or_else(|| AppError::InvalidInput {
+            field: "provider".to_string(),
+            reason: format!("unknown embedding provider '{name}' (local, openai, cohere)"),
+        })?,
+        None => configured.kind,
+    };
+    let provider = configured.with_model(kind, req.model, req.dimension);
+    let force = req.force;
+
+    let users = match req.user_id {
+        Some(user_id) => {
+            validation::validate_user_id(&user_id).map_validation_err("user_id")?;
+            vec![user_id]
+        }
+        None => state.list_users(),
+    };
+
+    let model = format!("{}/{}", provider.kind.as_str(), provider.model());
+    {
+        let mut status = state.reembed_status.write();
+        if status.running {
+            return Err(AppError::ServiceUnavailable(format!(
+                "A re-embedding job to {} is already running",
+                status.model
+            )));
+        }
+        *status = ReembedStatus {
+            running: true,
+            model,
+            started_at: Some(chrono::Utc::now()),
+            users_total: users.len(),
+            ..Default::default()
+        };
+    }
+    tracing::info!(provider = %provider.describe(), users = users.len(), "Re-embedding started");
+
+    let state_clone = state.clone();
+    tokio::task::spawn(async move {
+        let status = state_clone.reembed_status.clone();
+        for user_id in users {
+            let memory = match state_clone.get_user_memory(&user_id) {
+                Ok(m) => m,
+                Err(e) => {
+                    let mut status = status.write();
+                    status.errors.push(format!("{user_id}: {e}"));
+                    status.users_done += 1;
+                    continue;
+                }
+            };
+            let provider = provider.clone();
+            let progress_status = status.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                reembed(&memory, &provider, force, |done, total| {
+                    let mut status = progress_status.write();
+                    status.memories_done = done;
+                    status.memories_total = total;
+                })
+            })
+            .await;
+
+            let mut status = status.write();
+            match result {
+                Ok(Ok(outcome)) if outcome.skipped => status.users_skipped += 1,
+                Ok(Ok(outcome)) => status.reembedded += outcome.reembedded,
+                Ok(Err(e)) => {
+                    tracing::error!(user_id = %user_id, "Re-embedding failed: {e:#}");
+                    status.errors.push(format!("{user_id}: {e:#}"));
+                }
+                Err(e) => {
+                    tracing::error!(user_id = %user_id, "Re-embedding panicked: {e}");
+                    status.errors.push(format!("{user_id}: task panicked"));
+                }
+            }
+            status.users_done += 1;
+        }
+
+        let mut status = status.write();
+        status.running = false;
+        status.finished_at = Some(chrono::Utc::now());
+        tracing::info!(
+            model = %status.model,
+            reembedded = status.reembedded,
+            errors = status.errors.len(),
+            "Re-embedding finished"
+        );
+    });
+
+    Ok(Json(state.reembed_status.read().clone()))
+}
+
+/// GET /api/admin/reembed - Progress of the current or last re-embedding job
+pub async fn reembed_status(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+) -> Result<Json<ReembedStatus>, AppError> {
+    require_admin(key.as_deref(), "Re-embedding")?;
+    Ok(Json(state.reembed_status.read().clone()))
+}
+
+/// POST /api/admin/reencrypt - Re-seal stores with the current encryption key
+///
+/// Run after rotating SHODH_ENCRYPTION_KEY (old key kept in
+/// SHODH_ENCRYPTION_PREVIOUS_KEYS) or after enabling encryption on existing
+/// stores; see [`memory::encryption`]. Progress is served by
+/// GET /api/admin/reencrypt. Only one job runs at a time.
+pub async fn start_reencrypt(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Json(req): Json<ReencryptRequest>,
+) -> Result<Json<ReencryptStatus>, AppError> {
+    require_admin(key.as_deref(), "Re-encryption")?;
+    let keyring = encryption::keyring()
+        .map_err(AppError::Internal)?
+        .ok_or_else(|| {
+            AppError::ServiceUnavailable(
+                "Encryption at rest is not enabled (set SHODH_ENCRYPTION_KEY)".to_string(),
+            )
+        })?;
+
+    let users = match req.user_id {
+        Some(user_id) => {
+            validation::validate_user_id(&user_id).map_validation_err("user_id")?;
+            vec![user_id]
+        }
+        None => state.list_users(),
+    };
+
+    {
+        let mut status = state.reencrypt_status.write();
+        if status.running {
+            return Err(AppError::ServiceUnavailable(
+                "A re-encryption job is already running".to_string(),
+            ));
+        }
+        *status = ReencryptStatus {
+            running: true,
+            key_id: keyring.current_key_id(),
+            started_at: Some(chrono::Utc::now()),
+            users_total: users.len(),
+            ..Default::default()
+        };
+    }
+    tracing::info!(users = users.len(), "Re-encryption started");
+
+    let state_clone = state.clone();
+    tokio::task::spawn(async move {
+        let status = state_clone.reencrypt_status.clone();
+        for user_id in users {
+            let result = match state_clone.get_user_memory(&user_id) {
+                Ok(memory) => tokio::task::spawn_blocking(move || memory.read().reencrypt())
+                    .await
+                    .map_err(|e| anyhow::anyhow!("task panicked: {e}"))
+                    .and_then(|r| r),
+                Err(e) => Err(e),
+            };
+
+            let mut status = status.write();
+            match result {
+                Ok(resealed) => status.resealed += resealed,
+                Err(e) => {
+                    tracing::error!(user_id = %user_id, "Re-encryption failed: {e:#}");
+                    status.errors.push(format!("{user_id}: {e:#}"));
+                }
+            }
+            status.users_done += 1;
+        }
+
+        let mut status = status.write();
+        status.running = false;
+        status.finished_at = Some(chrono::Utc::now());
+        tracing::info!(
+            resealed = status.resealed,
+            errors = status.errors.len(),
+            "Re-encryption finished"
+        );
+    });
+
+    Ok(Json(state.reencrypt_status.read().clone()))
+}
+
+/// GET /api/admin/reencrypt - Progress of the current or last re-encryption job
+pub async fn reencrypt_status(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+) -> Result<Json<ReencryptStatus>, AppError> {
+    require_admin(key.as_deref(), "Re-encryption")?;
+    Ok(Json(state.reencrypt_status.read().clone()))
+}
+
 // =============================================================================
 // BACKUP & RESTORE
 // =============================================================================
@@ -393,6 +814,14 @@ pub async fn create_backup(
                     metadata.size_bytes, secondary_count, metadata.secondary_size_bytes
                 ),
             );
+            if state.backup_sink.is_some() {
+                let state = state.clone();
+                let user_id = req.user_id.clone();
+                let backup_id = metadata.backup_id;
+                tokio::task::spawn_blocking(move || {
+                    state.upload_backup_offsite(&user_id, backup_id)
+                });
+            }
             Ok(Json(BackupResponse {
                 success: true,
                 backup: Some(metadata),
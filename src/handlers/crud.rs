@@ -12,7 +12,10 @@ use std::collections::HashMap;
 use tracing::info;
 
 use super::state::MultiUserMemoryManager;
-use super::types::MemoryEvent;
+use super::tenants::TenantPath;
+use super::types::{
+    MemoryAccessLogResponse, MemoryEvent, MemoryHistoryResponse, MemoryRevisionInfo,
+};
 use crate::errors::{AppError, ValidationErrorExt};
 use crate::memory::{self, ExperienceType, Memory};
 use crate::validation;
@@ -48,13 +51,21 @@ pub struct ListQuery {
     pub memory_type: Option<String>,
     /// Text search query - filters by content or tags (case-insensitive)
     pub query: Option<String>,
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
+    /// Creation-time order: "desc" (newest first, default) or "asc"
+    pub order: Option<String>,
 }
 
 /// List response - simplified memory list
 #[derive(Debug, Serialize)]
 pub struct ListResponse {
     pub memories: Vec<ListMemoryItem>,
+    /// Number of memories matching the filters, across all pages
     pub total: usize,
+    /// Cursor for the next page, absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 /// Request for POST /api/memories - list memories with user_id in body
@@ -65,6 +76,10 @@ pub struct ListMemoriesRequest {
     #[serde(rename = "type")]
     pub memory_type: Option<String>,
     pub query: Option<String>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub order: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -104,17 +119,52 @@ pub struct DeleteMemoryResponse {
     pub success: bool,
     pub id: String,
     pub message: String,
+    /// Moved to the trash (restorable) rather than deleted permanently
+    pub trashed: bool,
 }
 
 // =============================================================================
 // FORGET REQUEST TYPES (local - not in shared types.rs)
 // =============================================================================
 
-/// Forget a single memory by ID (POST body variant)
+/// Forget by ID, text query and/or tags (POST body variant)
+///
+/// `memory_id` deletes exactly that memory. Otherwise memories whose content
+/// contains `query` (case-insensitive) and/or that carry any of `tags` are
+/// deleted; when both are given a memory must match both.
 #[derive(Debug, Deserialize)]
-pub struct ForgetByIdRequest {
+pub struct ForgetRequest {
     pub user_id: String,
-    pub memory_id: String,
+    #[serde(default)]
+    pub memory_id: Option<String>,
+    #[serde(default)]
+    pub query: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Refuse to delete when more memories than this match (default: 100)
+    #[serde(default = "default_forget_limit")]
+    pub limit: usize,
+    /// Delete permanently instead of moving to the trash
+    #[serde(default)]
+    pub permanent: bool,
+}
+
+fn default_forget_limit() -> usize {
+    100
+}
+
+/// Response for POST /api/forget
+#[derive(Debug, Serialize)]
+pub struct ForgetResponse {
+    pub success: bool,
+    /// The deleted memory, for by-ID requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub deleted_ids: Vec<String>,
+    pub deleted_count: usize,
+    pub message: String,
+    /// Moved to the trash (restorable) rather than deleted permanently
+    pub trashed: bool,
 }
 
 /// Forget memories by age
@@ -122,6 +172,9 @@ pub struct ForgetByIdRequest {
 pub struct ForgetByAgeRequest {
     pub user_id: String,
     pub days_old: u32,
+    /// Delete permanently instead of moving to the trash
+    #[serde(default)]
+    pub permanent: bool,
 }
 
 /// Forget memories by importance threshold
@@ -129,6 +182,9 @@ pub struct ForgetByAgeRequest {
 pub struct ForgetByImportanceRequest {
     pub user_id: String,
     pub threshold: f32,
+    /// Delete permanently instead of moving to the trash
+    #[serde(default)]
+    pub permanent: bool,
 }
 
 /// Forget memories matching a pattern
@@ -136,6 +192,9 @@ pub struct ForgetByImportanceRequest {
 pub struct ForgetByPatternRequest {
     pub user_id: String,
     pub pattern: String,
+    /// Delete permanently instead of moving to the trash
+    #[serde(default)]
+    pub permanent: bool,
 }
 
 /// Forget memories by tags
@@ -144,6 +203,9 @@ pub struct ForgetByTagsRequest {
     pub user_id: String,
     /// Tags to match for deletion (deletes memories matching ANY of these tags)
     pub tags: Vec<String>,
+    /// Delete permanently instead of moving to the trash
+    #[serde(default)]
+    pub permanent: bool,
 }
 
 /// Forget memories by date range
@@ -154,6 +216,9 @@ pub struct ForgetByDateRequest {
     pub start: chrono::DateTime<chrono::Utc>,
     /// End of date range (inclusive) - ISO 8601 format
     pub end: chrono::DateTime<chrono::Utc>,
+    /// Delete permanently instead of moving to the trash
+    #[serde(default)]
+    pub permanent: bool,
 }
 
 /// Bulk delete memories by filters
@@ -168,6 +233,9 @@ pub struct BulkDeleteRequest {
     pub created_after: Option<chrono::DateTime<chrono::Utc>>,
     /// Delete memories created before this timestamp
     pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Delete permanently instead of moving to the trash
+    #[serde(default)]
+    pub permanent: bool,
 }
 
 /// Clear ALL memories for a user (GDPR compliance)
@@ -176,18 +244,29 @@ pub struct ClearAllRequest {
     pub user_id: String,
     /// Safety confirmation - must be "CONFIRM" to proceed
     pub confirm: String,
+    /// Erase immediately instead of moving to the trash (right to erasure)
+    #[serde(default)]
+    pub permanent: bool,
 }
 
 /// PATCH endpoint for partial memory updates
 #[derive(Debug, Deserialize)]
 pub struct PatchMemoryRequest {
     pub user_id: String,
-    /// New content (optional)
+    /// New content (optional, re-embedded)
     pub content: Option<String>,
-    /// New/additional tags (optional)
+    /// Additional tags (optional, alias for `add_tags`)
     pub tags: Option<Vec<String>>,
+    /// Tags to add (optional)
+    #[serde(default)]
+    pub add_tags: Vec<String>,
+    /// Tags to remove (optional)
+    #[serde(default)]
+    pub remove_tags: Vec<String>,
     /// New memory type (optional)
     pub memory_type: Option<String>,
+    /// Importance override, 0.0-1.0 (optional)
+    pub importance: Option<f32>,
 }
 
 // =============================================================================
@@ -233,82 +312,125 @@ pub async fn get_memory(
     }))
 }
 
+/// GET /api/memory/{memory_id}/history?user_id=... - Provenance and revision history
+#[tracing::instrument(skip(state, params), fields(memory_id = %memory_id))]
+pub async fn get_memory_history(
+    State(state): State<AppState>,
+    Path(memory_id): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<MemoryHistoryResponse>, AppError> {
+    let user_id = params
+        .get("user_id")
+        .ok_or_else(|| AppError::InvalidInput {
+            field: "user_id".to_string(),
+            reason: "user_id required".to_string(),
+        })?;
+
+    validation::validate_user_id(user_id).map_validation_err("user_id")?;
+
+    let memory = state.get_user_memory(user_id).map_err(AppError::Internal)?;
+    let memory_guard = memory.read();
+    let shared_memory = resolve_memory(&memory_guard, &memory_id)?;
+
+    let revisions: Vec<MemoryRevisionInfo> = shared_memory
+        .get_history()
+        .iter()
+        .enumerate()
+        .map(|(i, r)| MemoryRevisionInfo {
+            revision: i + 1,
+            content: r.previous_content.clone(),
+            changed_at: r.changed_at.to_rfc3339(),
+            change_type: serde_json::to_value(&r.change_type)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            changed_by: r.changed_by.clone(),
+            change_reason: r.change_reason.clone(),
+        })
+        .collect();
+
+    Ok(Json(MemoryHistoryResponse {
+        memory_id: shared_memory.id.0.to_string(),
+        external_id: shared_memory.external_id.clone(),
+        current_content: shared_memory.experience.content.clone(),
+        version: shared_memory.version,
+        created_at: shared_memory.created_at.to_rfc3339(),
+        provenance: shared_memory.provenance(),
+        agent_id: shared_memory.agent_id.clone(),
+        run_id: shared_memory.run_id.clone(),
+        revision_count: revisions.len(),
+        revisions,
+    }))
+}
+
+/// Entries returned by the access log endpoint when no limit is given
+const DEFAULT_ACCESS_LOG_LIMIT: usize = 100;
+
+/// GET /api/memory/{memory_id}/access_log?user_id=...&limit=... - Retrievals that surfaced a memory
+#[tracing::instrument(skip(state, params), fields(memory_id = %memory_id))]
+pub async fn get_memory_access_log(
+    State(state): State<AppState>,
+    Path(memory_id): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<MemoryAccessLogResponse>, AppError> {
+    let user_id = params
+        .get("user_id")
+        .ok_or_else(|| AppError::InvalidInput {
+            field: "user_id".to_string(),
+            reason: "user_id required".to_string(),
+        })?;
+
+    validation::validate_user_id(user_id).map_validation_err("user_id")?;
+    let limit = match params.get("limit") {
+        Some(limit) => limit.parse::<usize>().map_err(|_| AppError::InvalidInput {
+            field: "limit".to_string(),
+            reason: "must be a positive integer".to_string(),
+        })?,
+        None => DEFAULT_ACCESS_LOG_LIMIT,
+    };
+
+    let memory = state.get_user_memory(user_id).map_err(AppError::Internal)?;
+    let memory_id = resolve_memory(&memory.read(), &memory_id)?.id.0.to_string();
+    let access_log = state.access_log.clone();
+    let accesses = {
+        let user_id = user_id.clone();
+        let memory_id = memory_id.clone();
+        tokio::task::spawn_blocking(move || access_log.list(&user_id, &memory_id, limit))
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+            .map_err(AppError::Internal)?
+    };
+
+    Ok(Json(MemoryAccessLogResponse {
+        memory_id,
+        max_entries: state.access_log.max_per_memory(),
+        retention_days: state.server_config.access_log_retention_days,
+        count: accesses.len(),
+        accesses,
+    }))
+}
+
 // =============================================================================
 // LIST MEMORIES HANDLER
 // =============================================================================
 
 /// GET /api/list/{user_id} - List all memories for a user
-/// Query params: ?limit=100&type=Decision
+/// Query params: ?limit=100&type=Decision&cursor=...&order=desc
 #[tracing::instrument(skip(state), fields(user_id = %user_id))]
 pub async fn list_memories(
     State(state): State<AppState>,
-    Path(user_id): Path<String>,
+    TenantPath(user_id): TenantPath,
     Query(query): Query<ListQuery>,
 ) -> Result<Json<ListResponse>, AppError> {
-    validation::validate_user_id(&user_id).map_validation_err("user_id")?;
-
-    let memory = state
-        .get_user_memory(&user_id)
-        .map_err(AppError::Internal)?;
-
-    let all_memories = {
-        let memory = memory.clone();
-        tokio::task::spawn_blocking(move || {
-            let memory_guard = memory.read();
-            memory_guard.get_all_memories()
-        })
-        .await
-        .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
-        .map_err(AppError::Internal)?
-    };
-
-    // Filter by type if specified
-    let mut filtered: Vec<_> = if let Some(ref type_filter) = query.memory_type {
-        let type_lower = type_filter.to_lowercase();
-        all_memories
-            .into_iter()
-            .filter(|m| format!("{:?}", m.experience.experience_type).to_lowercase() == type_lower)
-            .collect()
-    } else {
-        all_memories
+    let req = ListMemoriesRequest {
+        user_id,
+        limit: query.limit,
+        memory_type: query.memory_type,
+        query: query.query,
+        cursor: query.cursor,
+        order: query.order,
     };
-
-    // Filter by text query if specified (search in content and tags)
-    if let Some(ref text_query) = query.query {
-        let query_lower = text_query.to_lowercase();
-        filtered.retain(|m| {
-            // Check content
-            if m.experience.content.to_lowercase().contains(&query_lower) {
-                return true;
-            }
-            // Check tags/entities
-            for tag in &m.experience.entities {
-                if tag.to_lowercase().contains(&query_lower) {
-                    return true;
-                }
-            }
-            false
-        });
-    }
-
-    let total = filtered.len();
-    let limit = query.limit.unwrap_or(100).min(1000);
-
-    let memories: Vec<ListMemoryItem> = filtered
-        .into_iter()
-        .take(limit)
-        .map(|m| ListMemoryItem {
-            id: m.id.0.to_string(),
-            content: m.experience.content.chars().take(500).collect(),
-            memory_type: format!("{:?}", m.experience.experience_type),
-            importance: m.importance(),
-            tags: m.experience.entities.clone(),
-            created_at: m.created_at.to_rfc3339(),
-            tier: format!("{:?}", m.tier),
-        })
-        .collect();
-
-    Ok(Json(ListResponse { memories, total }))
+    list_memories_inner(state, req).await
 }
 
 /// POST /api/memories - List memories (user_id in body)
@@ -329,9 +451,11 @@ pub struct ListMemoriesQuery {
     #[serde(rename = "type")]
     pub memory_type: Option<String>,
     pub query: Option<String>,
+    pub cursor: Option<String>,
+    pub order: Option<String>,
 }
 
-/// GET /api/memories?user_id=...&limit=... - List memories via query params
+/// GET /api/memories?user_id=...&cursor=...&limit=...&order=... - List memories via query params
 /// Cloudflare Worker compatibility alias for POST /api/memories
 #[tracing::instrument(skip(state), fields(user_id = %params.user_id))]
 pub async fn list_memories_get(
@@ -343,6 +467,8 @@ pub async fn list_memories_get(
         limit: params.limit,
         memory_type: params.memory_type,
         query: params.query,
+        cursor: params.cursor,
+        order: params.order,
     };
     list_memories_inner(state, req).await
 }
@@ -398,10 +524,52 @@ async fn list_memories_inner(
         });
     }
 
+    // Order by (created_at, id) so pages stay stable while memories are added
+    let descending = match req.order.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("desc") => true,
+        Some("asc") => false,
+        Some(other) => {
+            return Err(AppError::InvalidInput {
+                field: "order".to_string(),
+                reason: format!("expected 'asc' or 'desc', got '{other}'"),
+            })
+        }
+    };
+    let sort_key = |m: &Memory| (m.created_at.timestamp_micros(), m.id.0);
+    filtered.sort_by_key(sort_key);
+    if descending {
+        filtered.reverse();
+    }
+
     let total = filtered.len();
-    let limit = req.limit.unwrap_or(100).min(1000);
+    let limit = req.limit.unwrap_or(100).clamp(1, 1000);
+
+    let start = match req.cursor.as_deref() {
+        Some(cursor) => {
+            let after = decode_list_cursor(cursor).ok_or_else(|| AppError::InvalidInput {
+                field: "cursor".to_string(),
+                reason: "malformed cursor".to_string(),
+            })?;
+            // Resume after the cursor position even if that memory was since deleted
+            filtered.partition_point(|m| {
+                if descending {
+                    sort_key(m) >= after
+                } else {
+                    sort_key(m) <= after
+                }
+            })
+        }
+        None => 0,
+    };
+
+    let page: Vec<_> = filtered.into_iter().skip(start).take(limit + 1).collect();
+    let next_cursor = if page.len() > limit {
+        page.get(limit - 1).map(|m| encode_list_cursor(sort_key(m)))
+    } else {
+        None
+    };
 
-    let memories: Vec<ListMemoryItem> = filtered
+    let memories: Vec<ListMemoryItem> = page
         .into_iter()
         .take(limit)
         .map(|m| ListMemoryItem {
@@ -415,7 +583,21 @@ async fn list_memories_inner(
         })
         .collect();
 
-    Ok(Json(ListResponse { memories, total }))
+    Ok(Json(ListResponse {
+        memories,
+        total,
+        next_cursor,
+    }))
+}
+
+/// Cursors are `<created_at micros>_<memory uuid>` of the last item on a page
+fn encode_list_cursor((micros, id): (i64, uuid::Uuid)) -> String {
+    format!("{micros}_{id}")
+}
+
+fn decode_list_cursor(cursor: &str) -> Option<(i64, uuid::Uuid)> {
+    let (micros, id) = cursor.split_once('_')?;
+    Some((micros.parse().ok()?, uuid::Uuid::parse_str(id).ok()?))
 }
 
 // =============================================================================
@@ -449,7 +631,12 @@ pub async fn update_memory(
 
     let content_preview: String = req.content.chars().take(50).collect();
 
-    current_memory.experience.content = req.content;
+    current_memory.update_content(
+        req.content,
+        memory::ChangeType::ContentUpdated,
+        Some("api".to_string()),
+        None,
+    );
     if let Some(emb) = req.embeddings {
         current_memory.experience.embeddings = Some(emb);
     } else {
@@ -469,6 +656,18 @@ pub async fn update_memory(
         &format!("Updated memory content: {content_preview}"),
     );
 
+    state.emit_event(MemoryEvent {
+        event_type: "UPDATE".to_string(),
+        timestamp: chrono::Utc::now(),
+        user_id: req.user_id.clone(),
+        memory_id: Some(resolved_id_str.clone()),
+        content_preview: Some(content_preview),
+        memory_type: None,
+        importance: None,
+        count: None,
+        results: None,
+    });
+
     Ok(Json(UpdateMemoryResponse {
         success: true,
         id: resolved_id_str,
@@ -480,7 +679,48 @@ pub async fn update_memory(
 // DELETE MEMORY HANDLER
 // =============================================================================
 
-/// DELETE /api/memories/{memory_id} - Delete specific memory
+/// Delete a memory, into the trash unless `permanent` is set or the trash is
+/// off. Returns whether it went to the trash; `None` if nothing was deleted.
+fn delete_one(
+    state: &AppState,
+    memory_guard: &memory::MemorySystem,
+    memory_id: memory::MemoryId,
+    permanent: bool,
+) -> Result<Option<bool>, AppError> {
+    if permanent || state.server_config.trash_retention_days == 0 {
+        let removed = memory_guard
+            .forget(memory::ForgetCriteria::ById(memory_id))
+            .map_err(AppError::Internal)?;
+        return Ok((removed > 0).then_some(false));
+    }
+    let trashed = memory_guard.trash(&memory_id).map_err(AppError::Internal)?;
+    Ok(trashed.then_some(true))
+}
+
+/// Delete every memory matching `criteria`, into the trash unless `permanent`
+/// is set or the trash is off. Returns how many were deleted and whether they
+/// went to the trash.
+fn delete_matching(
+    state: &AppState,
+    memory_guard: &memory::MemorySystem,
+    criteria: memory::ForgetCriteria,
+    permanent: bool,
+) -> Result<(usize, bool), AppError> {
+    if permanent || state.server_config.trash_retention_days == 0 {
+        let count = memory_guard.forget(criteria).map_err(AppError::Internal)?;
+        return Ok((count, false));
+    }
+    let count = memory_guard
+        .trash_matching(&criteria)
+        .map_err(AppError::Internal)?;
+    Ok((count, true))
+}
+
+/// DELETE /api/memories/{memory_id}?permanent=true - Delete specific memory
+///
+/// Moves the memory to the trash (see /api/memory/{memory_id}/restore)
+/// unless `permanent=true`; trashed memories are purged after
+/// SHODH_TRASH_RETENTION_DAYS.
 #[tracing::instrument(skip(state), fields(memory_id = %memory_id))]
 pub async fn delete_memory(
     State(state): State<AppState>,
@@ -502,12 +742,27 @@ pub async fn delete_memory(
     let shared_memory = resolve_memory(&memory_guard, &memory_id)?;
     let resolved_id = shared_memory.id.clone();
     let resolved_id_str = resolved_id.0.to_string();
+    let permanent = params.get("permanent").is_some_and(|p| p == "true");
+
+    let Some(trashed) = delete_one(&state, &memory_guard, resolved_id, permanent)? else {
+        return Ok(Json(DeleteMemoryResponse {
+            success: true,
+            id: resolved_id_str,
+            message: "Memory is already in the trash".to_string(),
+            trashed: true,
+        }));
+    };
 
-    memory_guard
-        .forget(memory::ForgetCriteria::ById(resolved_id))
-        .map_err(AppError::Internal)?;
-
-    state.log_event(user_id, "DELETE", &resolved_id_str, "Memory deleted");
+    state.log_event(
+        user_id,
+        "DELETE",
+        &resolved_id_str,
+        if trashed {
+            "Memory moved to trash"
+        } else {
+            "Memory deleted"
+        },
+    );
 
     state.emit_event(MemoryEvent {
         event_type: "DELETE".to_string(),
@@ -524,57 +779,256 @@ pub async fn delete_memory(
     Ok(Json(DeleteMemoryResponse {
         success: true,
         id: resolved_id_str,
-        message: "Memory deleted successfully".to_string(),
+        message: if trashed {
+            "Memory moved to trash".to_string()
+        } else {
+            "Memory deleted successfully".to_string()
+        },
+        trashed,
     }))
 }
 
 // =============================================================================
-// FORGET BY ID (POST BODY VARIANT)
+// TRASH AND RESTORE
 // =============================================================================
 
-/// POST /api/forget - Delete a single memory by ID from JSON body
+#[derive(Debug, Deserialize)]
+pub struct RestoreMemoryRequest {
+    pub user_id: String,
+}
+
+/// POST /api/memory/{memory_id}/restore - Take a deleted memory out of the trash
+#[tracing::instrument(skip(state, req), fields(memory_id = %memory_id))]
+pub async fn restore_memory(
+    State(state): State<AppState>,
+    Path(memory_id): Path<String>,
+    Json(req): Json<RestoreMemoryRequest>,
+) -> Result<Json<UpdateMemoryResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let memory = state
+        .get_user_memory(&req.user_id)
+        .map_err(AppError::Internal)?;
+    let memory_guard = memory.read();
+    let resolved_id = resolve_memory(&memory_guard, &memory_id)?.id.clone();
+    let resolved_id_str = resolved_id.0.to_string();
+
+    let restored = memory_guard
+        .restore(&resolved_id)
+        .map_err(AppError::Internal)?
+        .ok_or_else(|| AppError::InvalidInput {
+            field: "memory_id".to_string(),
+            reason: format!("memory {resolved_id_str} is not in the trash"),
+        })?;
+
+    state.log_event(
+        &req.user_id,
+        "RESTORE",
+        &resolved_id_str,
+        "Memory restored from trash",
+    );
+    state.emit_event(MemoryEvent {
+        event_type: "RESTORE".to_string(),
+        timestamp: chrono::Utc::now(),
+        user_id: req.user_id.clone(),
+        memory_id: Some(resolved_id_str.clone()),
+        content_preview: Some(restored.experience.content.chars().take(100).collect()),
+        memory_type: Some(format!("{:?}", restored.experience.experience_type)),
+        importance: Some(restored.importance()),
+        count: None,
+        results: None,
+    });
+
+    Ok(Json(UpdateMemoryResponse {
+        success: true,
+        id: resolved_id_str,
+        message: "Memory restored".to_string(),
+    }))
+}
+
+/// A memory in the trash
+#[derive(Debug, Serialize)]
+pub struct TrashedMemory {
+    pub id: String,
+    pub content: String,
+    pub memory_type: String,
+    pub tags: Vec<String>,
+    pub trashed_at: chrono::DateTime<chrono::Utc>,
+    /// When it will be deleted permanently
+    pub purge_after: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TrashResponse {
+    pub memories: Vec<TrashedMemory>,
+    pub count: usize,
+    pub retention_days: u64,
+}
+
+/// GET /api/trash?user_id=... - Deleted memories that can still be restored
+#[tracing::instrument(skip(state, params))]
+pub async fn list_trash(
+    State(state): State<AppState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<TrashResponse>, AppError> {
+    let user_id = params
+        .get("user_id")
+        .ok_or_else(|| AppError::InvalidInput {
+            field: "user_id".to_string(),
+            reason: "user_id required".to_string(),
+        })?;
+
+    validation::validate_user_id(user_id).map_validation_err("user_id")?;
+
+    let memory = state.get_user_memory(user_id).map_err(AppError::Internal)?;
+    let trashed = tokio::task::spawn_blocking(move || memory.read().trashed_memories())
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+        .map_err(AppError::Internal)?;
+
+    let retention_days = state.server_config.trash_retention_days;
+    let memories: Vec<TrashedMemory> = trashed
+        .into_iter()
+        .filter_map(|m| {
+            let trashed_at = m.trashed_at()?;
+            Some(TrashedMemory {
+                id: m.id.0.to_string(),
+                memory_type: format!("{:?}", m.experience.experience_type),
+                tags: m.experience.tags,
+                content: m.experience.content,
+                trashed_at,
+                purge_after: trashed_at + chrono::Duration::days(retention_days.min(36_500) as i64),
+            })
+        })
+        .collect();
+
+    Ok(Json(TrashResponse {
+        count: memories.len(),
+        memories,
+        retention_days,
+    }))
+}
+
+// =============================================================================
+// FORGET BY ID / QUERY / TAGS (POST BODY VARIANT)
+// =============================================================================
+
+/// POST /api/forget - Delete memories by ID, text query or tags
 ///
 /// Convenience endpoint matching the POST pattern of other forget endpoints
-/// (/api/forget/age, /api/forget/tags, etc.). Delegates to the same logic as
-/// DELETE /api/forget/{memory_id}.
-#[tracing::instrument(skip(state), fields(memory_id = %req.memory_id))]
+/// (/api/forget/age, /api/forget/tags, etc.). With `memory_id` it behaves like
+/// DELETE /api/forget/{memory_id}; with `query` and/or `tags` it deletes every
+/// matching memory. Returns the IDs that were removed. Deleted memories go to
+/// the trash unless `permanent` is set.
+#[tracing::instrument(skip(state, req), fields(user_id = %req.user_id))]
 pub async fn forget_by_id(
     State(state): State<AppState>,
-    Json(req): Json<ForgetByIdRequest>,
-) -> Result<Json<DeleteMemoryResponse>, AppError> {
+    Json(req): Json<ForgetRequest>,
+) -> Result<Json<ForgetResponse>, AppError> {
     validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
 
+    let query = req
+        .query
+        .as_deref()
+        .map(|q| q.trim().to_lowercase())
+        .filter(|q| !q.is_empty());
+    if req.memory_id.is_none() && query.is_none() && req.tags.is_empty() {
+        return Err(AppError::InvalidInput {
+            field: "memory_id".to_string(),
+            reason: "Provide memory_id, query or tags".to_string(),
+        });
+    }
+
     let memory = state
         .get_user_memory(&req.user_id)
         .map_err(AppError::Internal)?;
     let memory_guard = memory.read();
 
-    let shared_memory = resolve_memory(&memory_guard, &req.memory_id)?;
-    let resolved_id = shared_memory.id.clone();
-    let resolved_id_str = resolved_id.0.to_string();
+    let targets: Vec<memory::MemoryId> = match &req.memory_id {
+        Some(memory_id) => vec![resolve_memory(&memory_guard, memory_id)?.id.clone()],
+        None => {
+            let matches: Vec<memory::MemoryId> = memory_guard
+                .get_all_memories()
+                .map_err(AppError::Internal)?
+                .into_iter()
+                .filter(|m| !m.is_forgotten())
+                .filter(|m| {
+                    query
+                        .as_ref()
+                        .is_none_or(|q| m.experience.content.to_lowercase().contains(q.as_str()))
+                        && (req.tags.is_empty()
+                            || m.experience.tags.iter().any(|t| req.tags.contains(t)))
+                })
+                .map(|m| m.id.clone())
+                .collect();
+            if matches.len() > req.limit {
+                return Err(AppError::InvalidInput {
+                    field: "limit".to_string(),
+                    reason: format!(
+                        "{} memories match, more than the limit of {}; narrow the query or raise limit",
+                        matches.len(),
+                        req.limit
+                    ),
+                });
+            }
+            matches
+        }
+    };
 
-    memory_guard
-        .forget(memory::ForgetCriteria::ById(resolved_id))
-        .map_err(AppError::Internal)?;
+    let trash = !req.permanent && state.server_config.trash_retention_days > 0;
+    let mut deleted_ids = Vec::with_capacity(targets.len());
+    for id in targets {
+        let id_str = id.0.to_string();
+        if delete_one(&state, &memory_guard, id, req.permanent)?.is_some() {
+            let details = if trash {
+                "Memory moved to trash"
+            } else {
+                "Memory deleted"
+            };
+            state.log_event(&req.user_id, "DELETE", &id_str, details);
+            deleted_ids.push(id_str);
+        }
+    }
 
-    state.log_event(&req.user_id, "DELETE", &resolved_id_str, "Memory deleted");
+    info!(
+        "Forget: user={}, query={:?}, tags={:?}, deleted={}",
+        req.user_id,
+        query,
+        req.tags,
+        deleted_ids.len()
+    );
 
     state.emit_event(MemoryEvent {
         event_type: "DELETE".to_string(),
         timestamp: chrono::Utc::now(),
         user_id: req.user_id.clone(),
-        memory_id: Some(resolved_id_str.clone()),
+        memory_id: if deleted_ids.len() == 1 {
+            Some(deleted_ids[0].clone())
+        } else {
+            None
+        },
         content_preview: None,
         memory_type: None,
         importance: None,
-        count: None,
+        count: Some(deleted_ids.len()),
         results: None,
     });
 
-    Ok(Json(DeleteMemoryResponse {
+    let id = req
+        .memory_id
+        .as_ref()
+        .and_then(|_| deleted_ids.first().cloned());
+    Ok(Json(ForgetResponse {
         success: true,
-        id: resolved_id_str,
-        message: "Memory deleted successfully".to_string(),
+        id,
+        deleted_count: deleted_ids.len(),
+        message: if trash {
+            format!("Moved {} memories to trash", deleted_ids.len())
+        } else {
+            format!("Deleted {} memories", deleted_ids.len())
+        },
+        deleted_ids,
+        trashed: trash,
     }))
 }
 
@@ -582,7 +1036,11 @@ pub async fn forget_by_id(
 // PATCH MEMORY HANDLER
 // =============================================================================
 
-/// PATCH /api/memories/{memory_id} - Partial memory update
+/// PATCH /api/memory/{memory_id} - Partial memory update
+///
+/// Edits content (re-computing the embedding), adds/removes tags, changes the
+/// type or overrides importance in place, so access and reinforcement history
+/// are kept.
 #[tracing::instrument(skip(state), fields(memory_id = %memory_id))]
 pub async fn patch_memory(
     State(state): State<AppState>,
@@ -602,21 +1060,32 @@ pub async fn patch_memory(
     let resolved_id_str = current_memory.id.0.to_string();
     let mut changes = Vec::new();
 
-    // Update content if provided
+    // Update content if provided (applied after the revision is recorded)
+    let mut updated_content = None;
     if let Some(ref new_content) = req.content {
         validation::validate_content(new_content, false).map_validation_err("content")?;
-        current_memory.experience.content = new_content.clone();
-        current_memory.experience.embeddings = None;
+        let mut content = new_content.clone();
+        state.sanitize_content(&mut content, &mut current_memory.experience.tags);
+        // Re-embed now so storage doesn't keep the stale vector
+        current_memory.experience.embeddings = memory_guard.compute_embedding(&content).ok();
+        updated_content = Some(content);
         changes.push("content");
     }
 
-    // Update tags if provided (add to existing entities)
-    if let Some(ref new_tags) = req.tags {
-        for tag in new_tags {
-            if !current_memory.experience.entities.contains(tag) {
-                current_memory.experience.entities.push(tag.clone());
+    // Add/remove tags (tags and entities are kept in sync, as on remember)
+    let add_tags: Vec<&String> = req.tags.iter().flatten().chain(&req.add_tags).collect();
+    if !add_tags.is_empty() || !req.remove_tags.is_empty() {
+        let experience = &mut current_memory.experience;
+        for tag in add_tags {
+            if !experience.tags.contains(tag) {
+                experience.tags.push(tag.clone());
+            }
+            if !experience.entities.contains(tag) {
+                experience.entities.push(tag.clone());
             }
         }
+        experience.tags.retain(|t| !req.remove_tags.contains(t));
+        experience.entities.retain(|t| !req.remove_tags.contains(t));
         changes.push("tags");
     }
 
@@ -626,6 +1095,18 @@ pub async fn patch_memory(
         changes.push("type");
     }
 
+    // Override importance if provided
+    if let Some(importance) = req.importance {
+        if !(0.0..=1.0).contains(&importance) {
+            return Err(AppError::InvalidInput {
+                field: "importance".to_string(),
+                reason: "must be between 0.0 and 1.0".to_string(),
+            });
+        }
+        current_memory.set_importance(importance);
+        changes.push("importance");
+    }
+
     if changes.is_empty() {
         return Err(AppError::InvalidInput {
             field: "body".to_string(),
@@ -633,6 +1114,21 @@ pub async fn patch_memory(
         });
     }
 
+    // Keep the pre-edit content in version history
+    let change_type = match changes[0] {
+        "tags" => memory::ChangeType::TagsUpdated,
+        "importance" => memory::ChangeType::ImportanceAdjusted,
+        _ => memory::ChangeType::ContentUpdated,
+    };
+    current_memory.record_revision(
+        change_type,
+        Some("api".to_string()),
+        Some(format!("Updated fields: {}", changes.join(", "))),
+    );
+    if let Some(content) = updated_content {
+        current_memory.experience.content = content;
+    }
+
     // Update in-place instead of creating a duplicate via remember()
     memory_guard
         .update_memory(&current_memory)
@@ -645,6 +1141,25 @@ pub async fn patch_memory(
         &format!("Updated fields: {}", changes.join(", ")),
     );
 
+    state.emit_event(MemoryEvent {
+        event_type: "UPDATE".to_string(),
+        timestamp: chrono::Utc::now(),
+        user_id: req.user_id.clone(),
+        memory_id: Some(resolved_id_str.clone()),
+        content_preview: Some(
+            current_memory
+                .experience
+                .content
+                .chars()
+                .take(100)
+                .collect(),
+        ),
+        memory_type: Some(format!("{:?}", current_memory.experience.experience_type)),
+        importance: Some(current_memory.importance()),
+        count: None,
+        results: Some(serde_json::json!({ "updated_fields": changes })),
+    });
+
     Ok(Json(serde_json::json!({
         "success": true,
         "id": resolved_id_str,
@@ -657,6 +1172,8 @@ pub async fn patch_memory(
 // =============================================================================
 
 /// POST /api/forget/age - Forget memories older than N days
+///
+/// Matches go to the trash unless `permanent` is set.
 #[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
 pub async fn forget_by_age(
     State(state): State<AppState>,
@@ -669,9 +1186,12 @@ pub async fn forget_by_age(
         .map_err(AppError::Internal)?;
 
     let memory_guard = memory_sys.read();
-    let count = memory_guard
-        .forget(memory::ForgetCriteria::OlderThan(req.days_old))
-        .map_err(AppError::Internal)?;
+    let (count, trashed) = delete_matching(
+        &state,
+        &memory_guard,
+        memory::ForgetCriteria::OlderThan(req.days_old),
+        req.permanent,
+    )?;
 
     state.log_event(
         &req.user_id,
@@ -683,7 +1203,8 @@ pub async fn forget_by_age(
     Ok(Json(serde_json::json!({
         "success": true,
         "forgotten_count": count,
-        "criteria": format!("older than {} days", req.days_old)
+        "criteria": format!("older than {} days", req.days_old),
+        "trashed": trashed
     })))
 }
 
@@ -692,6 +1213,8 @@ pub async fn forget_by_age(
 // =============================================================================
 
 /// POST /api/forget/importance - Forget memories below importance threshold
+///
+/// Matches go to the trash unless `permanent` is set.
 #[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
 pub async fn forget_by_importance(
     State(state): State<AppState>,
@@ -711,9 +1234,12 @@ pub async fn forget_by_importance(
         .map_err(AppError::Internal)?;
 
     let memory_guard = memory_sys.read();
-    let count = memory_guard
-        .forget(memory::ForgetCriteria::LowImportance(req.threshold))
-        .map_err(AppError::Internal)?;
+    let (count, trashed) = delete_matching(
+        &state,
+        &memory_guard,
+        memory::ForgetCriteria::LowImportance(req.threshold),
+        req.permanent,
+    )?;
 
     state.log_event(
         &req.user_id,
@@ -725,7 +1251,8 @@ pub async fn forget_by_importance(
     Ok(Json(serde_json::json!({
         "success": true,
         "forgotten_count": count,
-        "criteria": format!("importance < {}", req.threshold)
+        "criteria": format!("importance < {}", req.threshold),
+        "trashed": trashed
     })))
 }
 
@@ -734,6 +1261,8 @@ pub async fn forget_by_importance(
 // =============================================================================
 
 /// POST /api/forget/pattern - Forget memories matching a pattern
+///
+/// Matches go to the trash unless `permanent` is set.
 #[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
 pub async fn forget_by_pattern(
     State(state): State<AppState>,
@@ -746,9 +1275,12 @@ pub async fn forget_by_pattern(
         .map_err(AppError::Internal)?;
 
     let memory_guard = memory_sys.read();
-    let count = memory_guard
-        .forget(memory::ForgetCriteria::Pattern(req.pattern.clone()))
-        .map_err(AppError::Internal)?;
+    let (count, trashed) = delete_matching(
+        &state,
+        &memory_guard,
+        memory::ForgetCriteria::Pattern(req.pattern.clone()),
+        req.permanent,
+    )?;
 
     state.log_event(
         &req.user_id,
@@ -760,7 +1292,8 @@ pub async fn forget_by_pattern(
     Ok(Json(serde_json::json!({
         "success": true,
         "forgotten_count": count,
-        "pattern": req.pattern
+        "pattern": req.pattern,
+        "trashed": trashed
     })))
 }
 
@@ -769,6 +1302,8 @@ pub async fn forget_by_pattern(
 // =============================================================================
 
 /// POST /api/forget/tags - Forget memories matching any of the provided tags
+///
+/// Matches go to the trash unless `permanent` is set.
 #[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
 pub async fn forget_by_tags(
     State(state): State<AppState>,
@@ -789,9 +1324,12 @@ pub async fn forget_by_tags(
 
     let memory_guard = memory_sys.read();
 
-    let deleted_count = memory_guard
-        .forget(memory::ForgetCriteria::ByTags(req.tags.clone()))
-        .map_err(AppError::Internal)?;
+    let (deleted_count, trashed) = delete_matching(
+        &state,
+        &memory_guard,
+        memory::ForgetCriteria::ByTags(req.tags.clone()),
+        req.permanent,
+    )?;
 
     info!(
         "🏷️ Forget by tags: user={}, tags={:?}, deleted={}",
@@ -813,7 +1351,8 @@ pub async fn forget_by_tags(
     Ok(Json(serde_json::json!({
         "success": true,
         "deleted_count": deleted_count,
-        "tags": req.tags
+        "tags": req.tags,
+        "trashed": trashed
     })))
 }
 
@@ -822,6 +1361,8 @@ pub async fn forget_by_tags(
 // =============================================================================
 
 /// POST /api/forget/date - Forget memories within a date range
+///
+/// Matches go to the trash unless `permanent` is set.
 #[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
 pub async fn forget_by_date(
     State(state): State<AppState>,
@@ -842,12 +1383,15 @@ pub async fn forget_by_date(
 
     let memory_guard = memory_sys.read();
 
-    let deleted_count = memory_guard
-        .forget(memory::ForgetCriteria::ByDateRange {
+    let (deleted_count, trashed) = delete_matching(
+        &state,
+        &memory_guard,
+        memory::ForgetCriteria::ByDateRange {
             start: req.start,
             end: req.end,
-        })
-        .map_err(AppError::Internal)?;
+        },
+        req.permanent,
+    )?;
 
     info!(
         "📅 Forget by date: user={}, start={}, end={}, deleted={}",
@@ -874,7 +1418,8 @@ pub async fn forget_by_date(
         "success": true,
         "deleted_count": deleted_count,
         "start": req.start.to_rfc3339(),
-        "end": req.end.to_rfc3339()
+        "end": req.end.to_rfc3339(),
+        "trashed": trashed
     })))
 }
 
@@ -883,6 +1428,8 @@ pub async fn forget_by_date(
 // =============================================================================
 
 /// POST /api/bulk_delete - Bulk delete memories by filters
+///
+/// Matches go to the trash unless `permanent` is set.
 #[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
 pub async fn bulk_delete_memories(
     State(state): State<AppState>,
@@ -896,24 +1443,25 @@ pub async fn bulk_delete_memories(
 
     let memory_guard = memory_sys.read();
     let mut total_count = 0;
+    let mut trashed = false;
+    let mut delete = |criteria| -> Result<(), AppError> {
+        let (count, to_trash) = delete_matching(&state, &memory_guard, criteria, req.permanent)?;
+        total_count += count;
+        trashed = to_trash;
+        Ok(())
+    };
 
     // Delete by tags if specified
     if let Some(ref tags) = req.tags {
         if !tags.is_empty() {
-            let count = memory_guard
-                .forget(memory::ForgetCriteria::ByTags(tags.clone()))
-                .map_err(AppError::Internal)?;
-            total_count += count;
+            delete(memory::ForgetCriteria::ByTags(tags.clone()))?;
         }
     }
 
     // Delete by type if specified
     if let Some(ref type_str) = req.memory_type {
         let exp_type = parse_experience_type(type_str)?;
-        let count = memory_guard
-            .forget(memory::ForgetCriteria::ByType(exp_type))
-            .map_err(AppError::Internal)?;
-        total_count += count;
+        delete(memory::ForgetCriteria::ByType(exp_type))?;
     }
 
     // Delete by date range if specified
@@ -922,10 +1470,7 @@ pub async fn bulk_delete_memories(
             .created_after
             .unwrap_or(chrono::DateTime::<chrono::Utc>::MIN_UTC);
         let end = req.created_before.unwrap_or(chrono::Utc::now());
-        let count = memory_guard
-            .forget(memory::ForgetCriteria::ByDateRange { start, end })
-            .map_err(AppError::Internal)?;
-        total_count += count;
+        delete(memory::ForgetCriteria::ByDateRange { start, end })?;
     }
 
     state.log_event(
@@ -937,7 +1482,8 @@ pub async fn bulk_delete_memories(
 
     Ok(Json(serde_json::json!({
         "success": true,
-        "deleted_count": total_count
+        "deleted_count": total_count,
+        "trashed": trashed
     })))
 }
 
@@ -946,6 +1492,8 @@ pub async fn bulk_delete_memories(
 // =============================================================================
 
 /// POST /api/clear_all - Clear ALL memories for a user (GDPR compliance)
+///
+/// Matches go to the trash unless `permanent` is set.
 #[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
 pub async fn clear_all_memories(
     State(state): State<AppState>,
@@ -966,21 +1514,33 @@ pub async fn clear_all_memories(
         .map_err(AppError::Internal)?;
 
     let memory_guard = memory_sys.read();
-    let count = memory_guard
-        .forget(memory::ForgetCriteria::All)
-        .map_err(AppError::Internal)?;
-
+    let (count, trashed) = delete_matching(
+        &state,
+        &memory_guard,
+        memory::ForgetCriteria::All,
+        req.permanent,
+    )?;
+
+    let message = if trashed {
+        format!(
+            "All memories moved to the trash, purged after {} days (set permanent for immediate erasure)",
+            state.server_config.trash_retention_days
+        )
+    } else {
+        "All memories have been permanently deleted (GDPR erasure)".to_string()
+    };
     state.log_event(
         &req.user_id,
         "CLEAR_ALL",
         "GDPR",
-        &format!("GDPR erasure: deleted {count} memories"),
+        &format!("Cleared {count} memories: {message}"),
     );
 
     Ok(Json(serde_json::json!({
         "success": true,
         "deleted_count": count,
-        "message": "All memories have been permanently deleted (GDPR erasure)"
+        "message": message,
+        "trashed": trashed
     })))
 }
 
@@ -992,7 +1552,7 @@ pub async fn clear_all_memories(
 ///
 /// Used by get/update/delete/patch handlers. Validates the input format,
 /// then searches across all memory tiers via prefix matching.
-fn resolve_memory(
+pub(super) fn resolve_memory(
     memory_guard: &memory::MemorySystem,
     memory_id_str: &str,
 ) -> Result<memory::SharedMemory, AppError> {
@@ -1023,7 +1583,7 @@ fn resolve_memory(
 }
 
 /// Parse experience type from string
-fn parse_experience_type(type_str: &str) -> Result<ExperienceType, AppError> {
+pub(crate) fn parse_experience_type(type_str: &str) -> Result<ExperienceType, AppError> {
     match type_str.to_lowercase().as_str() {
         "observation" => Ok(ExperienceType::Observation),
         "decision" => Ok(ExperienceType::Decision),
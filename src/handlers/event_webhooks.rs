@@ -0,0 +1,203 @@
+//! Outbound Webhook Handlers
+//!
+//! /api/webhooks registers, lists, changes and removes the webhooks of the
+//! caller's tenant (see [`crate::event_webhooks`]). Tenant keys manage their
+//! tenant's webhooks; for the default tenant an admin key is needed once
+//! SHODH_ADMIN_API_KEYS is set. The signing secret is returned only on
+//! registration.
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    Extension,
+};
+use serde::{Deserialize, Serialize};
+
+use super::acl::require_admin;
+use super::state::MultiUserMemoryManager;
+use crate::auth::AuthenticatedKey;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::event_webhooks::{NewWebhook, PayloadFormat, Webhook, WebhookUpdate};
+use crate::validation;
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// Tenant whose webhooks the caller manages (`None` = default tenant)
+fn owner(key: Option<&AuthenticatedKey>) -> Result<Option<String>, AppError> {
+    match key.and_then(|k| k.tenant.clone()) {
+        Some(tenant) => Ok(Some(tenant)),
+        None => {
+            require_admin(key, "Webhook management")?;
+            Ok(None)
+        }
+    }
+}
+
+fn unknown_webhook(id: &str) -> AppError {
+    AppError::InvalidInput {
+        field: "webhook_id".to_string(),
+        reason: format!("no webhook with id '{id}'"),
+    }
+}
+
+/// The caller's webhook `id`; other tenants' webhooks look like missing ones
+fn owned_webhook(state: &AppState, tenant: Option<&str>, id: &str) -> Result<Webhook, AppError> {
+    state
+        .webhooks
+        .get(id)
+        .filter(|hook| hook.tenant.as_deref() == tenant)
+        .ok_or_else(|| unknown_webhook(id))
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookListResponse {
+    pub webhooks: Vec<Webhook>,
+    pub count: usize,
+}
+
+/// GET /api/webhooks - List the tenant's webhooks, newest first
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+) -> Result<Json<WebhookListResponse>, AppError> {
+    let tenant = owner(key.as_deref())?;
+    let webhooks: Vec<Webhook> = state
+        .webhooks
+        .list(tenant.as_deref())
+        .iter()
+        .map(Webhook::redacted)
+        .collect();
+    Ok(Json(WebhookListResponse {
+        count: webhooks.len(),
+        webhooks,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Event types to deliver, e.g. ["CREATE", "DELETE"] (default: all)
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    /// Only deliver events of this user
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// Only deliver events about these memory types, e.g. ["Decision"]
+    #[serde(default)]
+    pub memory_types: Vec<String>,
+    #[serde(default)]
+    pub format: PayloadFormat,
+}
+
+/// A newly registered webhook. `secret` can't be shown again.
+#[derive(Debug, Serialize)]
+pub struct RegisteredWebhookResponse {
+    pub secret: String,
+    #[serde(flatten)]
+    pub webhook: Webhook,
+}
+
+/// POST /api/webhooks - Register a webhook
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Json(req): Json<CreateWebhookRequest>,
+) -> Result<Json<RegisteredWebhookResponse>, AppError> {
+    let tenant = owner(key.as_deref())?;
+    crate::event_webhooks::check_destination(&req.url)
+        .await
+        .map_validation_err("url")?;
+    if let Some(user_id) = &req.user_id {
+        validation::validate_user_id(user_id).map_validation_err("user_id")?;
+    }
+    let webhook = state
+        .webhooks
+        .create(NewWebhook {
+            tenant,
+            url: req.url,
+            label: req.label.filter(|l| !l.trim().is_empty()),
+            event_types: req.event_types,
+            user_id: req.user_id,
+            memory_types: req.memory_types,
+            format: req.format,
+        })
+        .map_err(AppError::Internal)?;
+    tracing::info!(
+        webhook_id = %webhook.id,
+        tenant = webhook.tenant.as_deref().unwrap_or(crate::tenant::DEFAULT_TENANT),
+        "Webhook registered"
+    );
+    Ok(Json(RegisteredWebhookResponse {
+        secret: webhook.secret.clone(),
+        webhook: webhook.redacted(),
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateWebhookRequest {
+    #[serde(default)]
+    pub url: Option<String>,
+    /// New label (empty to clear)
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub event_types: Option<Vec<String>>,
+    #[serde(default)]
+    pub memory_types: Option<Vec<String>>,
+    #[serde(default)]
+    pub format: Option<PayloadFormat>,
+    /// Pause or resume deliveries; resuming clears the failure count
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+/// PATCH /api/webhooks/{webhook_id} - Change a webhook
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Path(webhook_id): Path<String>,
+    Json(req): Json<UpdateWebhookRequest>,
+) -> Result<Json<Webhook>, AppError> {
+    let tenant = owner(key.as_deref())?;
+    owned_webhook(&state, tenant.as_deref(), &webhook_id)?;
+    if let Some(url) = &req.url {
+        crate::event_webhooks::check_destination(url)
+            .await
+            .map_validation_err("url")?;
+    }
+    let webhook = state
+        .webhooks
+        .update(
+            &webhook_id,
+            WebhookUpdate {
+                url: req.url,
+                label: req.label,
+                event_types: req.event_types,
+                memory_types: req.memory_types,
+                format: req.format,
+                enabled: req.enabled,
+            },
+        )
+        .map_err(AppError::Internal)?
+        .ok_or_else(|| unknown_webhook(&webhook_id))?;
+    Ok(Json(webhook.redacted()))
+}
+
+/// DELETE /api/webhooks/{webhook_id} - Remove a webhook
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Path(webhook_id): Path<String>,
+) -> Result<Json<Webhook>, AppError> {
+    let tenant = owner(key.as_deref())?;
+    let webhook = owned_webhook(&state, tenant.as_deref(), &webhook_id)?;
+    state
+        .webhooks
+        .delete(&webhook_id)
+        .map_err(AppError::Internal)?;
+    tracing::info!(webhook_id = %webhook_id, "Webhook removed");
+    Ok(Json(webhook.redacted()))
+}
@@ -0,0 +1,274 @@
+//! JSONL Memory Export
+//!
+//! Streams every memory of a user as one JSON object per line, optionally
+//! gzip- or zstd-compressed, so memories can be backed up, inspected with standard
+//! tools, or loaded elsewhere. Each line carries the memory's feedback momentum
+//! (learned helpful/misleading history), which `/api/import` restores. MIF
+//! export (`/api/export/mif`) remains the full-fidelity format including graph,
+//! todos and reminders.
+
+use std::io::Write;
+
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use flate2::{write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use tokio_stream::wrappers::ReceiverStream;
+
+use super::state::MultiUserMemoryManager;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::memory::{FeedbackMomentum, Memory};
+use crate::validation;
+
+type AppState = std::sync::Arc<MultiUserMemoryManager>;
+
+/// Memories serialized per streamed chunk
+const EXPORT_CHUNK_SIZE: usize = 256;
+
+/// Query parameters for GET /api/export
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    pub user_id: String,
+    /// Only "jsonl" is supported
+    #[serde(default = "default_format")]
+    pub format: String,
+    /// Gzip-compress the stream
+    #[serde(default)]
+    pub gzip: bool,
+    /// zstd-compress the stream (smaller and faster than gzip)
+    #[serde(default)]
+    pub zstd: bool,
+    /// Include embedding vectors (large)
+    #[serde(default)]
+    pub include_embeddings: bool,
+}
+
+fn default_format() -> String {
+    "jsonl".to_string()
+}
+
+fn default_memory_type() -> String {
+    "Observation".to_string()
+}
+
+/// One exported memory (one JSONL line)
+///
+/// Also the input format of `/api/import`, where everything but the content
+/// is optional and `memory`/`text` are accepted for content (as in mem0
+/// exports).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryRecord {
+    #[serde(default)]
+    pub id: String,
+    #[serde(alias = "memory", alias = "text")]
+    pub content: String,
+    #[serde(default = "default_memory_type", alias = "type")]
+    pub memory_type: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub entities: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valence: Option<f32>,
+    #[serde(default = "Utc::now")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_accessed: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub importance: Option<f32>,
+    #[serde(default)]
+    pub access_count: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activation: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embeddings: Option<Vec<f32>>,
+    /// Feedback momentum, if the memory has received any feedback
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feedback: Option<FeedbackMomentum>,
+}
+
+impl MemoryRecord {
+    pub fn from_memory(memory: &Memory, include_embeddings: bool) -> Self {
+        let experience = &memory.experience;
+        Self {
+            id: memory.id.0.to_string(),
+            content: experience.content.clone(),
+            memory_type: format!("{:?}", experience.experience_type),
+            tags: experience.tags.clone(),
+            entities: experience.entities.clone(),
+            valence: experience
+                .context
+                .as_ref()
+                .map(|c| c.emotional.valence)
+                .filter(|v| *v != 0.0),
+            created_at: memory.created_at,
+            last_accessed: Some(memory.last_accessed()),
+            importance: Some(memory.importance()),
+            access_count: memory.access_count(),
+            activation: Some(memory.activation()),
+            tier: Some(format!("{:?}", memory.tier)),
+            agent_id: memory.agent_id.clone(),
+            run_id: memory.run_id.clone(),
+            external_id: memory.external_id.clone(),
+            parent_id: memory.parent_id.as_ref().map(|p| p.0.to_string()),
+            embeddings: if include_embeddings {
+                experience.embeddings.clone()
+            } else {
+                None
+            },
+            feedback: None,
+        }
+    }
+}
+
+/// Accumulates JSONL lines, handing out the (possibly compressed) bytes per chunk
+enum ChunkWriter {
+    Plain(Vec<u8>),
+    Gzip(GzEncoder<Vec<u8>>),
+    Zstd(zstd::stream::write::Encoder<'static, Vec<u8>>),
+}
+
+impl ChunkWriter {
+    fn new(gzip: bool, zstd: bool) -> std::io::Result<Self> {
+        Ok(if zstd {
+            let level = crate::memory::blob_compression::settings().level;
+            Self::Zstd(zstd::stream::write::Encoder::new(Vec::new(), level)?)
+        } else if gzip {
+            Self::Gzip(GzEncoder::new(Vec::new(), Compression::default()))
+        } else {
+            Self::Plain(Vec::new())
+        })
+    }
+
+    fn write_record(&mut self, record: &MemoryRecord) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        match self {
+            Self::Plain(buf) => buf.extend_from_slice(&line),
+            Self::Gzip(encoder) => encoder.write_all(&line)?,
+            Self::Zstd(encoder) => encoder.write_all(&line)?,
+        }
+        Ok(())
+    }
+
+    /// Bytes produced so far
+    fn take(&mut self) -> Vec<u8> {
+        match self {
+            Self::Plain(buf) => std::mem::take(buf),
+            Self::Gzip(encoder) => std::mem::take(encoder.get_mut()),
+            Self::Zstd(encoder) => std::mem::take(encoder.get_mut()),
+        }
+    }
+
+    /// Remaining bytes, including the gzip trailer or end of the zstd frame
+    fn finish(self) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Plain(buf) => Ok(buf),
+            Self::Gzip(encoder) => encoder.finish(),
+            Self::Zstd(encoder) => encoder.finish(),
+        }
+    }
+}
+
+/// GET /api/export?user_id=...&format=jsonl&zstd=true - Stream all memories as JSONL
+///
+/// Memories are written oldest first, one JSON object per line.
+#[tracing::instrument(skip(state), fields(user_id = %params.user_id))]
+pub async fn export_jsonl(
+    State(state): State<AppState>,
+    Query(params): Query<ExportQuery>,
+) -> Result<Response, AppError> {
+    validation::validate_user_id(&params.user_id).map_validation_err("user_id")?;
+    if !params.format.eq_ignore_ascii_case("jsonl") {
+        return Err(AppError::InvalidInput {
+            field: "format".to_string(),
+            reason: format!("unsupported format '{}', expected 'jsonl'", params.format),
+        });
+    }
+    if params.gzip && params.zstd {
+        return Err(AppError::InvalidInput {
+            field: "zstd".to_string(),
+            reason: "gzip and zstd are mutually exclusive".to_string(),
+        });
+    }
+
+    let memory = state
+        .get_user_memory(&params.user_id)
+        .map_err(AppError::Internal)?;
+
+    let (gzip, zstd) = (params.gzip, params.zstd);
+    let include_embeddings = params.include_embeddings;
+    let feedback_store = state.feedback_store.clone();
+    let (tx, rx) = tokio::sync::mpsc::channel::<std::io::Result<Vec<u8>>>(4);
+    tokio::task::spawn_blocking(move || {
+        let send_err = |e: std::io::Error| {
+            tracing::warn!("JSONL export failed: {}", e);
+            let _ = tx.blocking_send(Err(e));
+        };
+        let mut memories = match memory.read().get_all_memories() {
+            Ok(memories) => memories,
+            Err(e) => return send_err(std::io::Error::other(e)),
+        };
+        memories.sort_by_key(|m| (m.created_at, m.id.0));
+
+        let mut writer = match ChunkWriter::new(gzip, zstd) {
+            Ok(writer) => writer,
+            Err(e) => return send_err(e),
+        };
+        for chunk in memories.chunks(EXPORT_CHUNK_SIZE) {
+            {
+                let feedback = feedback_store.read();
+                for m in chunk {
+                    let mut record = MemoryRecord::from_memory(m, include_embeddings);
+                    record.feedback = feedback.load_momentum(&m.id);
+                    if let Err(e) = writer.write_record(&record) {
+                        return send_err(e);
+                    }
+                }
+            }
+            let bytes = writer.take();
+            // Receiver gone means the client disconnected
+            if !bytes.is_empty() && tx.blocking_send(Ok(bytes)).is_err() {
+                return;
+            }
+        }
+        match writer.finish() {
+            Ok(bytes) => {
+                let _ = tx.blocking_send(Ok(bytes));
+            }
+            Err(e) => send_err(e),
+        }
+    });
+
+    let (content_type, extension) = if zstd {
+        ("application/zstd", "jsonl.zst")
+    } else if gzip {
+        ("application/gzip", "jsonl.gz")
+    } else {
+        ("application/x-ndjson", "jsonl")
+    };
+    let disposition = format!("attachment; filename=\"shodh-memories.{extension}\"");
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(ReceiverStream::new(rx)),
+    )
+        .into_response())
+}
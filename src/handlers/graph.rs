@@ -3,19 +3,18 @@
 //! Handlers for advanced knowledge graph operations including traversal,
 //! entity management, and memory universe visualization.
 
-use axum::{
-    extract::{Path, State},
-    response::Json,
-};
+use axum::{extract::State, response::Json};
 use serde::Deserialize;
 use tracing::info;
 
 use super::state::MultiUserMemoryManager;
+use super::tenants::TenantPath;
 use super::types::MemoryEvent;
 use crate::errors::{AppError, ValidationErrorExt};
 use crate::graph_memory::{EntityNode, EpisodicNode, GraphStats, GraphTraversal, MemoryUniverse};
 use crate::memory::{Experience, MemoryId};
 use crate::validation;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 type AppState = Arc<MultiUserMemoryManager>;
@@ -23,7 +22,7 @@ type AppState = Arc<MultiUserMemoryManager>;
 /// GET /api/graph/{user_id}/stats - Get graph statistics for a user
 pub async fn get_graph_stats(
     State(state): State<AppState>,
-    Path(user_id): Path<String>,
+    TenantPath(user_id): TenantPath,
 ) -> Result<Json<GraphStats>, AppError> {
     validation::validate_user_id(&user_id).map_validation_err("user_id")?;
 
@@ -96,6 +95,99 @@ pub async fn traverse_graph(
     Ok(Json(traversal))
 }
 
+/// Request for an entity's neighborhood
+#[derive(Debug, Deserialize)]
+pub struct NeighborhoodRequest {
+    pub user_id: String,
+    pub entity_name: String,
+    /// Hops from the entity (default 1)
+    pub max_depth: Option<usize>,
+    /// Max memories returned (default 20)
+    pub limit: Option<usize>,
+}
+
+/// POST /api/graph/neighborhood - Entities and memories around an entity
+///
+/// Returns the entities within `max_depth` hops plus the memories that
+/// mention any of them, closest hop first, then most recent.
+pub async fn get_neighborhood(
+    State(state): State<AppState>,
+    Json(req): Json<NeighborhoodRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let graph = state
+        .get_user_graph(&req.user_id)
+        .map_err(AppError::Internal)?;
+    let graph_guard = graph.read();
+
+    let entity = graph_guard
+        .find_entity_by_name(&req.entity_name)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!(e)))?
+        .ok_or_else(|| {
+            AppError::MemoryNotFound(format!("Entity not found: {}", req.entity_name))
+        })?;
+
+    let max_depth = req.max_depth.unwrap_or(1).min(3);
+    let traversal = graph_guard
+        .traverse_from_entity(&entity.uuid, max_depth)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!(e)))?;
+
+    // Episode UUID == memory ID; keep each memory at its closest hop
+    let mut memories: HashMap<uuid::Uuid, (usize, String, EpisodicNode)> = HashMap::new();
+    for traversed in &traversal.entities {
+        let episodes = graph_guard
+            .get_episodes_by_entity(&traversed.entity.uuid)
+            .map_err(|e| AppError::Internal(anyhow::anyhow!(e)))?;
+        for episode in episodes {
+            let hop = traversed.hop_distance;
+            match memories.get(&episode.uuid) {
+                Some((closest, _, _)) if *closest <= hop => {}
+                _ => {
+                    let via = traversed.entity.name.clone();
+                    memories.insert(episode.uuid, (hop, via, episode));
+                }
+            }
+        }
+    }
+
+    let mut memories: Vec<_> = memories.into_values().collect();
+    memories.sort_by(|a, b| a.0.cmp(&b.0).then(b.2.valid_at.cmp(&a.2.valid_at)));
+    memories.truncate(req.limit.unwrap_or(20));
+
+    let entities: Vec<_> = traversal
+        .entities
+        .iter()
+        .map(|t| {
+            serde_json::json!({
+                "name": t.entity.name,
+                "labels": t.entity.labels,
+                "hop_distance": t.hop_distance,
+                "mention_count": t.entity.mention_count,
+            })
+        })
+        .collect();
+    let memories: Vec<_> = memories
+        .into_iter()
+        .map(|(hop, via, episode)| {
+            serde_json::json!({
+                "memory_id": episode.uuid.to_string(),
+                "content": episode.content,
+                "hop_distance": hop,
+                "via_entity": via,
+                "valid_at": episode.valid_at,
+            })
+        })
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "entity": entity,
+        "entities": entities,
+        "relationships": traversal.relationships,
+        "memories": memories,
+    })))
+}
+
 /// Request to get an episode
 #[derive(Debug, Deserialize)]
 pub struct GetEpisodeRequest {
@@ -165,7 +257,7 @@ pub async fn get_all_entities(
 /// GET /api/graph/{user_id}/universe - Get Memory Universe visualization
 pub async fn get_memory_universe(
     State(state): State<AppState>,
-    Path(user_id): Path<String>,
+    TenantPath(user_id): TenantPath,
 ) -> Result<Json<MemoryUniverse>, AppError> {
     validation::validate_user_id(&user_id).map_validation_err("user_id")?;
 
@@ -182,7 +274,7 @@ pub async fn get_memory_universe(
 /// DELETE /api/graph/{user_id}/clear - Clear all graph data for a user
 pub async fn clear_user_graph(
     State(state): State<AppState>,
-    Path(user_id): Path<String>,
+    TenantPath(user_id): TenantPath,
 ) -> Result<Json<serde_json::Value>, AppError> {
     validation::validate_user_id(&user_id).map_validation_err("user_id")?;
 
@@ -225,7 +317,7 @@ pub async fn clear_user_graph(
 /// POST /api/graph/{user_id}/rebuild - Rebuild graph from all existing memories
 pub async fn rebuild_user_graph(
     State(state): State<AppState>,
-    Path(user_id): Path<String>,
+    TenantPath(user_id): TenantPath,
 ) -> Result<Json<serde_json::Value>, AppError> {
     validation::validate_user_id(&user_id).map_validation_err("user_id")?;
 
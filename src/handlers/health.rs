@@ -11,7 +11,8 @@ use serde::Deserialize;
 use std::collections::HashMap;
 
 use super::state::MultiUserMemoryManager;
-use super::types::{ContextStatus, MemoryEvent};
+use super::types::{ContextStatus, DependencyStatus, MemoryEvent};
+use crate::embeddings::provider::{self, ProviderConfig, ProviderKind};
 use crate::metrics;
 
 /// Application state type alias
@@ -55,17 +56,150 @@ pub async fn health_live() -> (StatusCode, Json<serde_json::Value>) {
     )
 }
 
+/// How long an embedding provider check is reused by the readiness probe
+const EMBEDDING_PROBE_TTL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Longest a readiness dependency check may take before it counts as failed
+const PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Run a dependency check on the blocking pool; panics and timeouts are errors
+async fn run_check<F>(check: F) -> DependencyStatus
+where
+    F: FnOnce() -> (&'static str, Option<String>) + Send + 'static,
+{
+    let start = std::time::Instant::now();
+    let (status, detail) =
+        match tokio::time::timeout(PROBE_TIMEOUT, tokio::task::spawn_blocking(check)).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(e)) => ("error", Some(format!("check panicked: {e}"))),
+            Err(_) => (
+                "error",
+                Some(format!("no answer within {}s", PROBE_TIMEOUT.as_secs())),
+            ),
+        };
+    DependencyStatus {
+        status,
+        detail,
+        latency_ms: start.elapsed().as_millis() as u64,
+    }
+}
+
+/// The shared RocksDB answers reads
+fn check_storage(state: &MultiUserMemoryManager) -> (&'static str, Option<String>) {
+    match state.shared_db.get(b"__readiness_probe") {
+        Ok(_) => ("ok", None),
+        Err(e) => ("error", Some(format!("shared DB read failed: {e}"))),
+    }
+}
+
+/// Vector indexes of the users in memory; a pending rebuild only degrades
+fn check_vector_index(state: &MultiUserMemoryManager) -> (&'static str, Option<String>) {
+    let (mut users, mut vectors, mut needs_rebuild) = (0, 0, 0);
+    for (_, memory) in state.user_memories.iter() {
+        // Skip users busy with a write rather than wait on them
+        let Some(guard) = memory.try_read() else {
+            continue;
+        };
+        let health = guard.index_health();
+        users += 1;
+        vectors += health.total_vectors;
+        if health.needs_rebuild {
+            needs_rebuild += 1;
+        }
+    }
+    let detail = format!("{users} user indexes loaded, {vectors} vectors");
+    if needs_rebuild > 0 {
+        (
+            "degraded",
+            Some(format!("{detail}; {needs_rebuild} need a rebuild")),
+        )
+    } else {
+        ("ok", Some(detail))
+    }
+}
+
+/// The embedding provider produces an embedding. Uses a loaded user's
+/// embedder; with none loaded, hosted providers are called directly and the
+/// local model is left to load with the first user.
+fn check_embedding(state: &MultiUserMemoryManager) -> (&'static str, Option<String>) {
+    let embedder = match state.user_memories.iter().next() {
+        Some((_, memory)) => memory.read().embedder(),
+        None => {
+            let config = ProviderConfig::from_env();
+            if config.kind == ProviderKind::Local {
+                return (
+                    "ok",
+                    Some("local model loads with the first user".to_string()),
+                );
+            }
+            match provider::from_config(&config) {
+                Ok(embedder) => embedder,
+                Err(e) => return ("error", Some(e.to_string())),
+            }
+        }
+    };
+    let info = embedder.info();
+    match embedder.encode("readiness probe") {
+        Ok(_) if info.provider == ProviderKind::Local && info.model == "simplified-hash" => (
+            "degraded",
+            Some("ONNX model unavailable, using hash embeddings".to_string()),
+        ),
+        Ok(_) => ("ok", Some(info.model_id())),
+        Err(e) => ("error", Some(format!("{}: {e}", info.model_id()))),
+    }
+}
+
+/// Embedding check, reused for `EMBEDDING_PROBE_TTL`
+async fn embedding_status(state: &AppState) -> DependencyStatus {
+    let cached = state.embedding_probe.lock().clone();
+    if let Some((checked_at, status)) = cached {
+        if checked_at.elapsed() < EMBEDDING_PROBE_TTL {
+            return status;
+        }
+    }
+    let status = {
+        let state = state.clone();
+        run_check(move || check_embedding(&state)).await
+    };
+    *state.embedding_probe.lock() = Some((std::time::Instant::now(), status.clone()));
+    status
+}
+
 /// Readiness probe - indicates if service can handle traffic
-/// Returns 200 OK if service is ready, 503 if not ready
+///
+/// Checks storage, the loaded vector indexes and the embedding provider and
+/// reports each under `checks`. Returns 503 if any check fails; "degraded"
+/// checks still count as ready. Liveness stays with /health/live, so probes
+/// can tell a broken brain from a busy or impaired one.
 pub async fn health_ready(State(state): State<AppState>) -> (StatusCode, Json<serde_json::Value>) {
-    let users_in_cache = state.users_in_cache();
+    let storage = {
+        let state = state.clone();
+        run_check(move || check_storage(&state)).await
+    };
+    let vector_index = {
+        let state = state.clone();
+        run_check(move || check_vector_index(&state)).await
+    };
+    let embedding = embedding_status(&state).await;
+    let ready = ![&storage, &vector_index, &embedding]
+        .iter()
+        .any(|check| check.is_error());
 
     (
-        StatusCode::OK,
+        if ready {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        },
         Json(serde_json::json!({
-            "status": "ready",
+            "status": if ready { "ready" } else { "not_ready" },
             "version": env!("CARGO_PKG_VERSION"),
-            "users_in_cache": users_in_cache,
+            "users_in_cache": state.users_in_cache(),
+            "checks": {
+                "storage": storage,
+                "vector_index": vector_index,
+                "embedding": embedding,
+            },
             "timestamp": chrono::Utc::now().to_rfc3339()
         })),
     )
@@ -0,0 +1,571 @@
+//! Claude Code Hook Handlers
+//!
+//! Accepts Claude Code lifecycle hook payloads (SessionStart, Stop, SessionEnd,
+//! PostToolUse, plus a custom RunEnd) so the server sees session boundaries
+//! and tool activity that prompt-level context calls never carry.
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+
+use super::state::MultiUserMemoryManager;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::memory::{
+    encoding_filters::FilterInput, is_verification_command, Experience, ExperienceType, MemoryId,
+    Provenance, ProvenanceSource, RetrievalOutcome, SessionEvent,
+};
+use crate::validation;
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// Maximum characters of tool output kept in an encoded memory
+const MAX_TOOL_OUTPUT_CHARS: usize = 300;
+
+/// Reinforcement strength when the first tool call after surfacing fails
+/// (weak: the failure may be unrelated to the injected memories)
+const NEXT_ACTION_FAILURE_STRENGTH: f32 = 0.5;
+
+/// Query parameters for the hooks endpoint
+#[derive(Debug, Deserialize)]
+pub struct HookQuery {
+    pub user_id: Option<String>,
+}
+
+/// Claude Code hook payload (common fields plus the event-specific ones we use)
+#[derive(Debug, Deserialize)]
+pub struct ClaudeHookPayload {
+    pub hook_event_name: String,
+    /// User ID (alternative to the `?user_id=` query parameter)
+    #[serde(default)]
+    pub user_id: Option<String>,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// SessionStart: startup, resume, clear, compact
+    #[serde(default)]
+    pub source: Option<String>,
+    /// SessionEnd: why the session ended
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// RunEnd (or SessionEnd): the run to consolidate into a summary memory
+    #[serde(default)]
+    pub run_id: Option<String>,
+    /// PostToolUse: tool that ran
+    #[serde(default)]
+    pub tool_name: Option<String>,
+    #[serde(default)]
+    pub tool_input: serde_json::Value,
+    #[serde(default)]
+    pub tool_response: serde_json::Value,
+}
+
+/// Response for a processed hook
+#[derive(Debug, Serialize)]
+pub struct HookResponse {
+    pub success: bool,
+    pub event: String,
+    /// What the server did with the hook (e.g. "prewarmed", "summarized", "reinforced", "ignored")
+    pub action: String,
+    /// Memory created from the hook, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_id: Option<String>,
+}
+
+/// POST /api/hooks - Ingest a Claude Code hook payload
+///
+/// - SessionStart: loads the user's memory and graph and warms the embedder
+/// - Stop / SessionEnd: stores a summary memory of the session so far
+///   (SessionEnd also closes the session and, given a run_id, ends that run)
+/// - RunEnd: consolidates the memories stored under `run_id` into one run
+///   summary memory (idle runs are also summarized during session cleanup)
+/// - PostToolUse: encodes file edits and failed commands as memories (unless an
+///   encoding filter excludes them); when a command that failed earlier in the
+///   session succeeds, stores a resolution memory linked under the original
+///   Error memory. Test/build commands also reinforce the memories surfaced
+///   just before them (Helpful on success, Misleading on failure), and a
+///   failing first tool call after surfacing weakly marks them Misleading
+#[tracing::instrument(skip(state, payload), fields(event = %payload.hook_event_name))]
+pub async fn ingest_hook(
+    State(state): State<AppState>,
+    Query(query): Query<HookQuery>,
+    Json(payload): Json<ClaudeHookPayload>,
+) -> Result<Json<HookResponse>, AppError> {
+    let user_id = query
+        .user_id
+        .or_else(|| payload.user_id.clone())
+        .ok_or_else(|| AppError::InvalidInput {
+            field: "user_id".to_string(),
+            reason: "user_id is required (query parameter or body)".to_string(),
+        })?;
+    validation::validate_user_id(&user_id).map_validation_err("user_id")?;
+
+    let (action, memory_id) = match payload.hook_event_name.as_str() {
+        "SessionStart" => {
+            prewarm_user(&state, &user_id, payload.cwd.as_deref()).await?;
+            state.session_store.get_or_create_session(&user_id);
+            ("prewarmed", None)
+        }
+        "Stop" | "SessionEnd" => {
+            let session_id = state.session_store.get_or_create_session(&user_id);
+            let summary = state
+                .session_store
+                .take_unsummarized_events(&session_id)
+                .and_then(|(label, events)| summarize_session_events(&label, &events));
+            let memory_id = match summary {
+                Some(content) => Some(
+                    store_hook_memory(
+                        &state,
+                        &user_id,
+                        content,
+                        ExperienceType::Context,
+                        "session-summary",
+                        None,
+                    )
+                    .await?,
+                ),
+                None => None,
+            };
+            if payload.hook_event_name == "SessionEnd" {
+                let reason = payload.reason.as_deref().unwrap_or("session_end");
+                state.end_session(&session_id, reason);
+                if let Some(run_id) = payload.run_id.as_deref() {
+                    end_run(&state, &user_id, run_id).await?;
+                }
+            }
+            let action = if memory_id.is_some() {
+                "summarized"
+            } else {
+                "nothing_to_summarize"
+            };
+            (action, memory_id)
+        }
+        "RunEnd" => {
+            let run_id = payload
+                .run_id
+                .as_deref()
+                .ok_or_else(|| AppError::InvalidInput {
+                    field: "run_id".to_string(),
+                    reason: "run_id is required for RunEnd".to_string(),
+                })?;
+            match end_run(&state, &user_id, run_id).await? {
+                Some(id) => ("run_summarized", Some(id)),
+                None => ("nothing_to_summarize", None),
+            }
+        }
+        "PostToolUse" => {
+            let session_id = state.session_store.get_or_create_session(&user_id);
+            let outcome = bash_outcome(&payload);
+            let reinforced = match payload.tool_name.as_deref() {
+                Some(tool) => {
+                    let command = outcome.map(|(command, _, _)| command);
+                    apply_tool_outcome(&state, &user_id, tool, command, tool_failed(&payload))
+                        .await?
+                }
+                None => 0,
+            };
+            let resolved = match outcome {
+                Some((command, false, _)) => state
+                    .session_store
+                    .take_open_failure(&session_id, command)
+                    .map(|error_id| (command, error_id)),
+                _ => None,
+            };
+            if let Some((command, error_id)) = resolved {
+                let id = store_hook_memory(
+                    &state,
+                    &user_id,
+                    format!(
+                        "Resolved: `{command}` now succeeds after failing earlier in the session"
+                    ),
+                    ExperienceType::Learning,
+                    "resolution",
+                    Some(&error_id),
+                )
+                .await?;
+                ("resolved", Some(id))
+            } else if let Some((content, experience_type)) = encode_tool_use(&payload) {
+                let filter_input = FilterInput {
+                    content: &content,
+                    tool: payload.tool_name.as_deref(),
+                    model: None,
+                };
+                if let Some(rule) = state.encoding_filters.excludes(&filter_input) {
+                    tracing::debug!(rule = %rule, "PostToolUse excluded by encoding filter");
+                    ("filtered", None)
+                } else {
+                    let id = store_hook_memory(
+                        &state,
+                        &user_id,
+                        content,
+                        experience_type,
+                        "tool-use",
+                        None,
+                    )
+                    .await?;
+                    if let Some((command, true, _)) = outcome {
+                        state
+                            .session_store
+                            .record_open_failure(&session_id, command, &id);
+                    }
+                    ("encoded", Some(id))
+                }
+            } else if reinforced > 0 {
+                ("reinforced", None)
+            } else {
+                ("ignored", None)
+            }
+        }
+        _ => ("ignored", None),
+    };
+
+    if let Some(ref id) = memory_id {
+        state.log_event(
+            &user_id,
+            "HOOK",
+            id,
+            &format!("{} -> {}", payload.hook_event_name, action),
+        );
+    }
+
+    Ok(Json(HookResponse {
+        success: true,
+        event: payload.hook_event_name,
+        action: action.to_string(),
+        memory_id,
+    }))
+}
+
+/// Load the user's memory system and graph and run one embedding so the first
+/// real request of the session doesn't pay cold-start latency
+async fn prewarm_user(state: &AppState, user_id: &str, cwd: Option<&str>) -> Result<(), AppError> {
+    let memory = state.get_user_memory(user_id).map_err(AppError::Internal)?;
+    state.get_user_graph(user_id).map_err(AppError::Internal)?;
+
+    let warmup_text = cwd
+        .and_then(|p| std::path::Path::new(p).file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "session start".to_string());
+    tokio::task::spawn_blocking(move || {
+        let _ = memory.read().compute_embedding(&warmup_text);
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?;
+    Ok(())
+}
+
+/// Store a hook-derived memory, optionally as a child of `parent_id`
+async fn store_hook_memory(
+    state: &AppState,
+    user_id: &str,
+    mut content: String,
+    experience_type: ExperienceType,
+    tag: &str,
+    parent_id: Option<&str>,
+) -> Result<String, AppError> {
+    let memory = state.get_user_memory(user_id).map_err(AppError::Internal)?;
+    let mut tags = vec!["claude-code-hook".to_string(), tag.to_string()];
+    state.sanitize_content(&mut content, &mut tags);
+    let experience = Experience {
+        content,
+        experience_type,
+        tags,
+        metadata: Provenance::new(ProvenanceSource::Cortex).to_metadata(),
+        ..Default::default()
+    };
+    let parent = parent_id
+        .and_then(|p| uuid::Uuid::parse_str(p).ok())
+        .map(MemoryId);
+    let id = tokio::task::spawn_blocking(move || {
+        let guard = memory.read();
+        let id = guard.remember(experience, None)?;
+        if let Some(parent) = parent {
+            if let Err(e) = guard.set_memory_parent(&id, Some(parent)) {
+                tracing::warn!("Failed to link hook memory to parent: {}", e);
+            }
+        }
+        Ok::<_, anyhow::Error>(id)
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+    .map_err(AppError::Internal)?;
+    Ok(id.0.to_string())
+}
+
+/// End a run, returning the ID of its summary memory if one was stored
+async fn end_run(
+    state: &AppState,
+    user_id: &str,
+    run_id: &str,
+) -> Result<Option<String>, AppError> {
+    let state = state.clone();
+    let user_id = user_id.to_string();
+    let run_id = run_id.to_string();
+    let summary_id = tokio::task::spawn_blocking(move || state.end_run(&user_id, &run_id))
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+        .map_err(AppError::Internal)?;
+    Ok(summary_id.map(|id| id.0.to_string()))
+}
+
+/// Apply tool-result feedback to the memories surfaced just before this call.
+///
+/// A test/build command counts as Helpful on success and Misleading on failure;
+/// any other tool call that fails as the first action after surfacing counts as
+/// a weak Misleading signal. Returns how many memories were affected.
+async fn apply_tool_outcome(
+    state: &AppState,
+    user_id: &str,
+    tool: &str,
+    command: Option<&str>,
+    failed: bool,
+) -> Result<usize, AppError> {
+    let memory = state.get_user_memory(user_id).map_err(AppError::Internal)?;
+    let feedback_store = state.feedback_store.clone();
+    let user_id = user_id.to_string();
+    let tool = tool.to_string();
+    let verification = command
+        .filter(|c| is_verification_command(c))
+        .map(String::from);
+    tokio::task::spawn_blocking(move || {
+        let (memory_ids, outcome, strength) = {
+            let mut store = feedback_store.write();
+            let (ids, outcome, strength) = match verification {
+                Some(command) => (
+                    store.record_tool_outcome(&user_id, &command, !failed),
+                    if failed {
+                        RetrievalOutcome::Misleading
+                    } else {
+                        RetrievalOutcome::Helpful
+                    },
+                    1.0,
+                ),
+                None => (
+                    store.record_next_action(&user_id, &tool, failed),
+                    RetrievalOutcome::Misleading,
+                    NEXT_ACTION_FAILURE_STRENGTH,
+                ),
+            };
+            if !ids.is_empty() {
+                if let Err(e) = store.flush() {
+                    tracing::warn!("Failed to flush feedback store: {}", e);
+                }
+            }
+            (ids, outcome, strength)
+        };
+        if !memory_ids.is_empty() {
+            if let Err(e) = memory
+                .read()
+                .reinforce_recall_weighted(&memory_ids, outcome, strength)
+            {
+                tracing::warn!("Failed to apply tool outcome reinforcement: {}", e);
+            }
+        }
+        memory_ids.len()
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))
+}
+
+/// Whether a PostToolUse payload reports a failed tool call
+fn tool_failed(payload: &ClaudeHookPayload) -> bool {
+    match bash_outcome(payload) {
+        Some((_, failed, _)) => failed,
+        None => payload
+            .tool_response
+            .get("is_error")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+    }
+}
+
+/// For a Bash PostToolUse payload: the command, whether it failed, and its stderr
+fn bash_outcome(payload: &ClaudeHookPayload) -> Option<(&str, bool, &str)> {
+    if payload.tool_name.as_deref() != Some("Bash") {
+        return None;
+    }
+    let command = payload.tool_input.get("command").and_then(|v| v.as_str())?;
+    let stderr = payload
+        .tool_response
+        .get("stderr")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .trim();
+    let failed = payload
+        .tool_response
+        .get("is_error")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+        || stderr.to_lowercase().contains("error");
+    Some((command.trim(), failed, stderr))
+}
+
+/// Turn a PostToolUse payload into memory content (edits and failed commands only)
+fn encode_tool_use(payload: &ClaudeHookPayload) -> Option<(String, ExperienceType)> {
+    let tool = payload.tool_name.as_deref()?;
+    let input = &payload.tool_input;
+    match tool {
+        "Edit" | "MultiEdit" | "Write" | "NotebookEdit" => {
+            let path = input
+                .get("file_path")
+                .or_else(|| input.get("notebook_path"))
+                .and_then(|v| v.as_str())?;
+            Some((
+                format!("Edited file {path} with the {tool} tool"),
+                ExperienceType::CodeEdit,
+            ))
+        }
+        "Bash" => {
+            let (command, failed, stderr) = bash_outcome(payload)?;
+            if !failed {
+                return None;
+            }
+            let output: String = stderr.chars().take(MAX_TOOL_OUTPUT_CHARS).collect();
+            Some((
+                format!("Command failed: {command}\n{output}"),
+                ExperienceType::Error,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Build summary memory content from session events, or `None` if nothing
+/// worth remembering happened
+fn summarize_session_events(label: &str, events: &[SessionEvent]) -> Option<String> {
+    let mut created = Vec::new();
+    let mut todos_created = 0usize;
+    let mut todos_completed = 0usize;
+    let mut surfaced = 0usize;
+    let mut used = 0usize;
+
+    for event in events {
+        match event {
+            SessionEvent::MemoryCreated {
+                content_preview, ..
+            } => created.push(content_preview.as_str()),
+            SessionEvent::TodoCreated { .. } => todos_created += 1,
+            SessionEvent::TodoCompleted { .. } => todos_completed += 1,
+            SessionEvent::MemoriesSurfaced { memory_count, .. } => surfaced += memory_count,
+            SessionEvent::MemoryUsed { .. } => used += 1,
+            _ => {}
+        }
+    }
+
+    if created.is_empty() && todos_created == 0 && todos_completed == 0 {
+        return None;
+    }
+
+    let mut summary = format!(
+        "Session summary ({label}): {} memories created, {} todos created, {} todos completed, {} memories surfaced ({} used).",
+        created.len(),
+        todos_created,
+        todos_completed,
+        surfaced,
+        used
+    );
+    if !created.is_empty() {
+        let highlights: Vec<&str> = created.iter().take(5).copied().collect();
+        summary.push_str(" Highlights: ");
+        summary.push_str(&highlights.join("; "));
+    }
+    Some(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(
+        tool: &str,
+        input: serde_json::Value,
+        response: serde_json::Value,
+    ) -> ClaudeHookPayload {
+        ClaudeHookPayload {
+            hook_event_name: "PostToolUse".to_string(),
+            user_id: None,
+            session_id: None,
+            cwd: None,
+            source: None,
+            reason: None,
+            run_id: None,
+            tool_name: Some(tool.to_string()),
+            tool_input: input,
+            tool_response: response,
+        }
+    }
+
+    #[test]
+    fn test_encode_tool_use() {
+        let edit = payload(
+            "Edit",
+            serde_json::json!({"file_path": "src/main.rs"}),
+            serde_json::Value::Null,
+        );
+        let (content, kind) = encode_tool_use(&edit).unwrap();
+        assert!(content.contains("src/main.rs"));
+        assert!(matches!(kind, ExperienceType::CodeEdit));
+
+        let ok = payload(
+            "Bash",
+            serde_json::json!({"command": "ls"}),
+            serde_json::json!({"stdout": "a\nb", "stderr": ""}),
+        );
+        assert!(encode_tool_use(&ok).is_none());
+
+        let failed = payload(
+            "Bash",
+            serde_json::json!({"command": "cargo build"}),
+            serde_json::json!({"stdout": "", "stderr": "error[E0425]: cannot find value"}),
+        );
+        let (content, kind) = encode_tool_use(&failed).unwrap();
+        assert!(content.starts_with("Command failed: cargo build"));
+        assert!(matches!(kind, ExperienceType::Error));
+
+        let (command, failed, _) = bash_outcome(&failed).unwrap();
+        assert_eq!(command, "cargo build");
+        assert!(failed);
+        assert!(bash_outcome(&edit).is_none());
+
+        assert!(encode_tool_use(&payload(
+            "Read",
+            serde_json::json!({}),
+            serde_json::Value::Null
+        ))
+        .is_none());
+    }
+
+    #[test]
+    fn test_summarize_session_events() {
+        let now = chrono::Utc::now();
+        let surfaced_only = vec![SessionEvent::MemoriesSurfaced {
+            timestamp: now,
+            query_preview: "q".to_string(),
+            memory_count: 3,
+            memory_ids: Vec::new(),
+            avg_score: 0.5,
+        }];
+        assert!(summarize_session_events("Today's session", &surfaced_only).is_none());
+
+        let events = vec![
+            SessionEvent::MemoryCreated {
+                timestamp: now,
+                memory_id: "m1".to_string(),
+                memory_type: "Decision".to_string(),
+                content_preview: "Use sqlite for the cache".to_string(),
+                entities: Vec::new(),
+            },
+            SessionEvent::TodoCompleted {
+                timestamp: now,
+                todo_id: "t1".to_string(),
+            },
+        ];
+        let summary = summarize_session_events("Today's session", &events).unwrap();
+        assert!(summary.starts_with("Session summary (Today's session): 1 memories created"));
+        assert!(summary.contains("1 todos completed"));
+        assert!(summary.ends_with("Highlights: Use sqlite for the cache"));
+    }
+}
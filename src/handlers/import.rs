@@ -0,0 +1,562 @@
+//! Bulk Memory Import
+//!
+//! Loads memories from the JSONL export format (`/api/export`), a markdown
+//! outline, or CSV. The request body is the file itself, gzip- or
+//! zstd-compressed or not, so an export can be restored with
+//! `curl --data-binary @shodh-memories.jsonl.zst`.
+
+use std::collections::HashSet;
+use std::io::Read;
+
+use axum::{
+    body::Bytes,
+    extract::{Query, State},
+    response::Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::crud::parse_experience_type;
+use super::export::MemoryRecord;
+use super::state::MultiUserMemoryManager;
+use super::types::MemoryEvent;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::memory::encoding_filters::FilterInput;
+use crate::memory::{Experience, FeedbackMomentum, MemoryId};
+use crate::mif::import::{build_dedup_set, content_hash};
+use crate::validation;
+
+type AppState = std::sync::Arc<MultiUserMemoryManager>;
+
+/// Max request body for imports (bodies are whole files)
+pub const MAX_IMPORT_BYTES: usize = 64 * 1024 * 1024;
+
+/// Records embedded and stored per round; an IMPORT_PROGRESS event follows each
+const IMPORT_BATCH: usize = 100;
+
+/// Query parameters for POST /api/import
+#[derive(Debug, Deserialize)]
+pub struct ImportQuery {
+    pub user_id: String,
+    /// "jsonl" (default), "markdown" or "csv"
+    #[serde(default = "default_format")]
+    pub format: String,
+    /// Parse and dedup without storing anything
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Skip records whose content already exists (or repeats in the file)
+    #[serde(default = "default_true")]
+    pub skip_duplicates: bool,
+}
+
+fn default_format() -> String {
+    "jsonl".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A record that could not be imported
+#[derive(Debug, Serialize)]
+pub struct ImportLineError {
+    /// 1-based line where the record starts
+    pub line: usize,
+    pub reason: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ImportResponse {
+    pub success: bool,
+    pub dry_run: bool,
+    /// Records found in the input
+    pub total: usize,
+    /// Records stored (or, on a dry run, that would be stored)
+    pub imported: usize,
+    pub duplicates: usize,
+    /// Records skipped by the encoding filters
+    pub filtered: usize,
+    pub errors: Vec<ImportLineError>,
+    pub imported_ids: Vec<String>,
+}
+
+type ParsedRecord = (usize, Result<MemoryRecord, String>);
+
+/// A validated record waiting to be embedded and stored
+struct PendingImport {
+    line: usize,
+    id: MemoryId,
+    experience: Experience,
+    created_at: chrono::DateTime<Utc>,
+    importance: Option<f32>,
+    agent_id: Option<String>,
+    run_id: Option<String>,
+    feedback: Option<FeedbackMomentum>,
+}
+
+/// A record with only content and tags, as produced by markdown and CSV input
+fn text_record(content: String, tags: Vec<String>) -> MemoryRecord {
+    MemoryRecord {
+        content,
+        memory_type: "Observation".to_string(),
+        tags,
+        created_at: Utc::now(),
+        ..Default::default()
+    }
+}
+
+fn parse_jsonl(text: &str) -> Vec<ParsedRecord> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            (
+                i + 1,
+                serde_json::from_str::<MemoryRecord>(line).map_err(|e| e.to_string()),
+            )
+        })
+        .collect()
+}
+
+/// Each bullet or paragraph becomes a memory tagged with its nearest heading
+fn parse_markdown(text: &str) -> Vec<ParsedRecord> {
+    let mut records = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut current: Option<(usize, String)> = None;
+
+    let mut flush = |current: &mut Option<(usize, String)>, heading: &Option<String>| {
+        if let Some((line, content)) = current.take() {
+            records.push((
+                line,
+                Ok(text_record(content, heading.iter().cloned().collect())),
+            ));
+        }
+    };
+
+    for (i, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            flush(&mut current, &heading);
+        } else if line.starts_with('#') {
+            flush(&mut current, &heading);
+            let title = line.trim_start_matches('#').trim();
+            heading = (!title.is_empty()).then(|| title.to_string());
+        } else if let Some(item) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|bullet| line.strip_prefix(bullet))
+        {
+            flush(&mut current, &heading);
+            current = Some((i + 1, item.trim().to_string()));
+        } else {
+            match &mut current {
+                Some((_, content)) => {
+                    content.push(' ');
+                    content.push_str(line);
+                }
+                None => current = Some((i + 1, line.to_string())),
+            }
+        }
+    }
+    flush(&mut current, &heading);
+    records
+}
+
+/// Split CSV into rows of fields (RFC 4180 quoting), with each row's start line
+fn split_csv(text: &str) -> Vec<(usize, Vec<String>)> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                if row.iter().any(|f| !f.trim().is_empty()) {
+                    rows.push((row_line, std::mem::take(&mut row)));
+                }
+                row.clear();
+                line += 1;
+                row_line = line;
+            }
+            c => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    row.push(field);
+    if row.iter().any(|f| !f.trim().is_empty()) {
+        rows.push((row_line, row));
+    }
+    rows
+}
+
+/// CSV with a header row: `content` (or `memory`/`text`) is required; `type`,
+/// `tags` (separated by `;` or `,`), `created_at` (RFC 3339) and `importance`
+/// are optional.
+fn parse_csv(text: &str) -> Result<Vec<ParsedRecord>, AppError> {
+    let mut rows = split_csv(text).into_iter();
+    let header: Vec<String> = rows
+        .next()
+        .map(|(_, h)| h.iter().map(|c| c.trim().to_lowercase()).collect())
+        .unwrap_or_default();
+    let column = |names: &[&str]| header.iter().position(|h| names.contains(&h.as_str()));
+    let content_col =
+        column(&["content", "memory", "text"]).ok_or_else(|| AppError::InvalidInput {
+            field: "body".to_string(),
+            reason: "CSV header needs a content column".to_string(),
+        })?;
+    let type_col = column(&["type", "memory_type"]);
+    let tags_col = column(&["tags"]);
+    let created_col = column(&["created_at"]);
+    let importance_col = column(&["importance"]);
+
+    Ok(rows
+        .map(|(line, fields)| {
+            let get = |col: Option<usize>| {
+                col.and_then(|c| fields.get(c))
+                    .map(|v| v.trim())
+                    .filter(|v| !v.is_empty())
+            };
+            let parse = || -> Result<MemoryRecord, String> {
+                let content = get(Some(content_col)).ok_or("empty content")?.to_string();
+                let tags = get(tags_col)
+                    .map(|t| {
+                        t.split([';', ','])
+                            .map(str::trim)
+                            .filter(|t| !t.is_empty())
+                            .map(String::from)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let mut record = text_record(content, tags);
+                if let Some(t) = get(type_col) {
+                    record.memory_type = t.to_string();
+                }
+                if let Some(ts) = get(created_col) {
+                    record.created_at = chrono::DateTime::parse_from_rfc3339(ts)
+                        .map_err(|e| format!("invalid created_at: {e}"))?
+                        .with_timezone(&Utc);
+                }
+                if let Some(i) = get(importance_col) {
+                    record.importance =
+                        Some(i.parse().map_err(|_| format!("invalid importance '{i}'"))?);
+                }
+                Ok(record)
+            };
+            (line, parse())
+        })
+        .collect())
+}
+
+/// Decompress the body if it is gzip or zstd, then decode as UTF-8
+fn decode_body(body: &[u8]) -> Result<String, AppError> {
+    let invalid = |reason: String| AppError::InvalidInput {
+        field: "body".to_string(),
+        reason,
+    };
+    if body.starts_with(&[0x1f, 0x8b]) {
+        let mut text = String::new();
+        flate2::read::GzDecoder::new(body)
+            .read_to_string(&mut text)
+            .map_err(|e| invalid(format!("invalid gzip data: {e}")))?;
+        Ok(text)
+    } else if body.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        let mut text = String::new();
+        zstd::stream::read::Decoder::new(body)
+            .and_then(|mut decoder| decoder.read_to_string(&mut text))
+            .map_err(|e| invalid(format!("invalid zstd data: {e}")))?;
+        Ok(text)
+    } else {
+        String::from_utf8(body.to_vec()).map_err(|e| invalid(format!("not UTF-8: {e}")))
+    }
+}
+
+/// POST /api/import?user_id=...&format=jsonl&dry_run=true - Bulk import memories
+///
+/// Records go through the same checks as /api/remember: content validation,
+/// the encoding filters (excluded records are counted in `filtered`) and
+/// secret redaction / PII scrubbing. They are then deduplicated on a SHA-256
+/// of their content against existing memories and the rest of the file.
+/// Exported ids, timestamps, importance, agent/run attribution and feedback
+/// momentum are kept (a fresh id is used if the original is taken).
+/// Embeddings are recomputed so vectors match the current model,
+/// batched through the shared embedding pipeline.
+/// Progress is broadcast as IMPORT_PROGRESS events on `/api/events`.
+#[tracing::instrument(skip(state, body), fields(user_id = %params.user_id))]
+pub async fn import_memories(
+    State(state): State<AppState>,
+    Query(params): Query<ImportQuery>,
+    body: Bytes,
+) -> Result<Json<ImportResponse>, AppError> {
+    validation::validate_user_id(&params.user_id).map_validation_err("user_id")?;
+
+    let text = decode_body(&body)?;
+    let records = match params.format.to_lowercase().as_str() {
+        "jsonl" | "ndjson" => parse_jsonl(&text),
+        "markdown" | "md" => parse_markdown(&text),
+        "csv" => parse_csv(&text)?,
+        other => {
+            return Err(AppError::InvalidInput {
+                field: "format".to_string(),
+                reason: format!("unsupported format '{other}', expected jsonl, markdown or csv"),
+            })
+        }
+    };
+
+    let memory = state
+        .get_user_memory(&params.user_id)
+        .map_err(AppError::Internal)?;
+
+    // Validate and dedup every record up front, then embed and store the
+    // survivors in rounds so embedding is batched across records
+    let task_state = state.clone();
+    let task_memory = memory.clone();
+    let (mut response, pending) = tokio::task::spawn_blocking(
+        move || -> anyhow::Result<(ImportResponse, Vec<PendingImport>)> {
+            let guard = task_memory.read();
+            let mut seen = if params.skip_duplicates {
+                let existing: Vec<String> = guard
+                    .get_all_memories()?
+                    .iter()
+                    .map(|m| m.experience.content.clone())
+                    .collect();
+                build_dedup_set(&existing)
+            } else {
+                HashSet::new()
+            };
+            let mut claimed_ids = HashSet::new();
+
+            let mut response = ImportResponse {
+                dry_run: params.dry_run,
+                total: records.len(),
+                ..Default::default()
+            };
+            let mut pending = Vec::new();
+            for (line, record) in records {
+                let fail = |reason: String| ImportLineError { line, reason };
+                let mut record = match record {
+                    Ok(r) if r.content.trim().is_empty() => {
+                        response.errors.push(fail("empty content".to_string()));
+                        continue;
+                    }
+                    Ok(r) => r,
+                    Err(reason) => {
+                        response.errors.push(fail(reason));
+                        continue;
+                    }
+                };
+                if let Err(e) = validation::validate_content(&record.content, false) {
+                    response.errors.push(fail(e.to_string()));
+                    continue;
+                }
+                let filter_input = FilterInput {
+                    content: &record.content,
+                    tool: None,
+                    model: None,
+                };
+                if task_state
+                    .encoding_filters
+                    .excludes(&filter_input)
+                    .is_some()
+                {
+                    response.filtered += 1;
+                    continue;
+                }
+                task_state.sanitize_content(&mut record.content, &mut record.tags);
+                let experience_type = match parse_experience_type(&record.memory_type) {
+                    Ok(t) => t,
+                    Err(_) => {
+                        response.errors.push(fail(format!(
+                            "unknown memory type '{}'",
+                            record.memory_type
+                        )));
+                        continue;
+                    }
+                };
+                if params.skip_duplicates && !seen.insert(content_hash(&record.content)) {
+                    response.duplicates += 1;
+                    continue;
+                }
+                if params.dry_run {
+                    response.imported += 1;
+                    continue;
+                }
+
+                let id = uuid::Uuid::parse_str(&record.id)
+                    .ok()
+                    .map(MemoryId)
+                    .filter(|id| !claimed_ids.contains(id) && guard.get_memory(id).is_err())
+                    .unwrap_or_else(|| MemoryId(uuid::Uuid::new_v4()));
+                claimed_ids.insert(id.clone());
+                let entities = if record.entities.is_empty() {
+                    record.tags.clone()
+                } else {
+                    record.entities
+                };
+                pending.push(PendingImport {
+                    line,
+                    id,
+                    experience: Experience {
+                        experience_type,
+                        content: record.content,
+                        tags: record.tags,
+                        entities,
+                        ..Default::default()
+                    },
+                    created_at: record.created_at,
+                    importance: record.importance,
+                    agent_id: record.agent_id,
+                    run_id: record.run_id,
+                    feedback: record.feedback,
+                });
+            }
+            Ok((response, pending))
+        },
+    )
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+    .map_err(AppError::Internal)?;
+
+    let mut processed = response.total - pending.len();
+    let mut pending = pending.into_iter().peekable();
+    while pending.peek().is_some() {
+        let mut round: Vec<PendingImport> = pending.by_ref().take(IMPORT_BATCH).collect();
+        let mut experiences: Vec<Experience> = round
+            .iter_mut()
+            .map(|p| std::mem::take(&mut p.experience))
+            .collect();
+        state.embed_experiences(&memory, &mut experiences).await;
+        for (p, experience) in round.iter_mut().zip(experiences) {
+            p.experience = experience;
+        }
+
+        let round_memory = memory.clone();
+        let feedback_store = state.feedback_store.clone();
+        let (imported_ids, errors) = tokio::task::spawn_blocking(move || {
+            let guard = round_memory.read();
+            let mut imported_ids = Vec::new();
+            let mut errors = Vec::new();
+            for p in round {
+                let experience_type = p.experience.experience_type.clone();
+                match guard.remember_imported(
+                    p.id,
+                    p.experience,
+                    Some(p.created_at),
+                    p.importance,
+                    p.agent_id,
+                    p.run_id,
+                ) {
+                    Ok(id) => {
+                        if let Some(momentum) = p.feedback {
+                            feedback_store.write().restore_momentum(
+                                id.clone(),
+                                experience_type,
+                                momentum,
+                            );
+                        }
+                        imported_ids.push(id.0.to_string());
+                    }
+                    Err(e) => errors.push(ImportLineError {
+                        line: p.line,
+                        reason: e.to_string(),
+                    }),
+                }
+            }
+            (imported_ids, errors)
+        })
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?;
+
+        processed += imported_ids.len() + errors.len();
+        response.imported += imported_ids.len();
+        response.imported_ids.extend(imported_ids);
+        response.errors.extend(errors);
+        state.emit_event(MemoryEvent {
+            event_type: "IMPORT_PROGRESS".to_string(),
+            timestamp: Utc::now(),
+            user_id: params.user_id.clone(),
+            memory_id: None,
+            content_preview: None,
+            memory_type: None,
+            importance: None,
+            count: Some(processed),
+            results: Some(serde_json::json!({
+                "total": response.total,
+                "imported": response.imported,
+                "duplicates": response.duplicates,
+                "filtered": response.filtered,
+                "errors": response.errors.len(),
+            })),
+        });
+    }
+
+    if !response.dry_run && response.imported > 0 {
+        let commit_memory = memory.clone();
+        tokio::task::spawn_blocking(move || commit_memory.read().commit_keyword_index())
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+            .map_err(AppError::Internal)?;
+    }
+    response.success = response.errors.is_empty();
+
+    if !response.dry_run {
+        state.log_event(
+            &params.user_id,
+            "IMPORT",
+            &params.format,
+            &format!(
+                "Imported {} of {} records, skipped {} duplicates and {} filtered, {} errors",
+                response.imported,
+                response.total,
+                response.duplicates,
+                response.filtered,
+                response.errors.len()
+            ),
+        );
+    }
+
+    Ok(Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_markdown_and_csv() {
+        let md = "# Deploys\n- Use blue/green for the API\n  behind the load balancer\n\nCanary first for workers\n";
+        let records = parse_markdown(md);
+        assert_eq!(records.len(), 2);
+        let first = records[0].1.as_ref().unwrap();
+        assert_eq!(
+            first.content,
+            "Use blue/green for the API behind the load balancer"
+        );
+        assert_eq!(first.tags, vec!["Deploys".to_string()]);
+        assert_eq!(records[1].0, 5);
+
+        let csv = "content,type,tags\n\"Chose Postgres, not MySQL\",Decision,db;infra\n\"multi\nline\",,\n";
+        let records = parse_csv(csv).unwrap();
+        assert_eq!(records.len(), 2);
+        let first = records[0].1.as_ref().unwrap();
+        assert_eq!(first.content, "Chose Postgres, not MySQL");
+        assert_eq!(first.memory_type, "Decision");
+        assert_eq!(first.tags, vec!["db".to_string(), "infra".to_string()]);
+        assert_eq!(records[1].1.as_ref().unwrap().content, "multi\nline");
+        assert!(parse_csv("title\nfoo\n").is_err());
+    }
+}
@@ -86,6 +86,7 @@ pub async fn linear_webhook(
         content: content.clone(),
         experience_type: ExperienceType::Task,
         entities: tags.clone(),
+        metadata: memory::Provenance::new(memory::ProvenanceSource::Integration).to_metadata(),
         ..Default::default()
     };
 
@@ -167,22 +168,29 @@ pub async fn linear_sync(
         .get_user_memory(&req.user_id)
         .map_err(AppError::Internal)?;
 
-    for issue in issues {
-        let external_id = match &issue.identifier {
-            Some(id) => format!("linear:{}", id),
-            None => format!("linear:{}", issue.id),
-        };
-
-        let content = LinearWebhook::issue_to_content(&issue);
-        let tags = LinearWebhook::issue_to_tags(&issue);
-
-        let experience = Experience {
-            content,
-            experience_type: ExperienceType::Task,
-            entities: tags,
-            ..Default::default()
-        };
+    let (external_ids, mut experiences): (Vec<String>, Vec<Experience>) = issues
+        .iter()
+        .map(|issue| {
+            let external_id = match &issue.identifier {
+                Some(id) => format!("linear:{}", id),
+                None => format!("linear:{}", issue.id),
+            };
+            let experience = Experience {
+                content: LinearWebhook::issue_to_content(issue),
+                experience_type: ExperienceType::Task,
+                entities: LinearWebhook::issue_to_tags(issue),
+                metadata: memory::Provenance::new(memory::ProvenanceSource::Integration)
+                    .to_metadata(),
+                ..Default::default()
+            };
+            (external_id, experience)
+        })
+        .unzip();
+    state
+        .embed_experiences(&memory_system, &mut experiences)
+        .await;
 
+    for (external_id, experience) in external_ids.into_iter().zip(experiences) {
         let result = {
             let memory = memory_system.clone();
             let ext_id = external_id.clone();
@@ -310,6 +318,7 @@ pub async fn github_webhook(
         content: content.clone(),
         experience_type: ExperienceType::Task,
         entities: tags.clone(),
+        metadata: memory::Provenance::new(memory::ProvenanceSource::Integration).to_metadata(),
         ..Default::default()
     };
 
@@ -396,18 +405,28 @@ pub async fn github_sync(
             .await
             .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to fetch issues: {}", e)))?;
 
-        for issue in issues {
-            let external_id = GitHubWebhook::issue_external_id(&repo_info, issue.number);
-            let content = GitHubWebhook::issue_to_content(&issue, &repo_info);
-            let tags = GitHubWebhook::issue_to_tags(&issue, &repo_info);
-
-            let experience = Experience {
-                content,
-                experience_type: ExperienceType::Task,
-                entities: tags,
-                ..Default::default()
-            };
+        let (external_ids, mut experiences): (Vec<String>, Vec<Experience>) = issues
+            .iter()
+            .map(|issue| {
+                let experience = Experience {
+                    content: GitHubWebhook::issue_to_content(issue, &repo_info),
+                    experience_type: ExperienceType::Task,
+                    entities: GitHubWebhook::issue_to_tags(issue, &repo_info),
+                    metadata: memory::Provenance::new(memory::ProvenanceSource::Integration)
+                        .to_metadata(),
+                    ..Default::default()
+                };
+                (
+                    GitHubWebhook::issue_external_id(&repo_info, issue.number),
+                    experience,
+                )
+            })
+            .unzip();
+        state
+            .embed_experiences(&memory_system, &mut experiences)
+            .await;
 
+        for (external_id, experience) in external_ids.into_iter().zip(experiences) {
             let result = {
                 let memory = memory_system.clone();
                 let ext_id = external_id.clone();
@@ -454,18 +473,28 @@ pub async fn github_sync(
             .await
             .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to fetch PRs: {}", e)))?;
 
-        for pr in prs {
-            let external_id = GitHubWebhook::pr_external_id(&repo_info, pr.number);
-            let content = GitHubWebhook::pr_to_content(&pr, &repo_info);
-            let tags = GitHubWebhook::pr_to_tags(&pr, &repo_info);
-
-            let experience = Experience {
-                content,
-                experience_type: ExperienceType::Task,
-                entities: tags,
-                ..Default::default()
-            };
+        let (external_ids, mut experiences): (Vec<String>, Vec<Experience>) = prs
+            .iter()
+            .map(|pr| {
+                let experience = Experience {
+                    content: GitHubWebhook::pr_to_content(pr, &repo_info),
+                    experience_type: ExperienceType::Task,
+                    entities: GitHubWebhook::pr_to_tags(pr, &repo_info),
+                    metadata: memory::Provenance::new(memory::ProvenanceSource::Integration)
+                        .to_metadata(),
+                    ..Default::default()
+                };
+                (
+                    GitHubWebhook::pr_external_id(&repo_info, pr.number),
+                    experience,
+                )
+            })
+            .unzip();
+        state
+            .embed_experiences(&memory_system, &mut experiences)
+            .await;
 
+        for (external_id, experience) in external_ids.into_iter().zip(experiences) {
             let result = {
                 let memory = memory_system.clone();
                 let ext_id = external_id.clone();
@@ -0,0 +1,332 @@
+//! API Key Management Handlers
+//!
+//! /api/admin/keys creates, labels, rotates and revokes managed API keys (see
+//! [`crate::api_keys`]). The key itself is returned only by create and
+//! rotate; every other response carries the key id, which is also what ACL
+//! grants reference. Rotation moves the old key's ACL grants to the new one.
+//!
+//! No key can issue a key more powerful than itself: only admin keys
+//! (SHODH_ADMIN_API_KEYS, or managed keys created by one with the `admin`
+//! scope) may issue, rotate or grant the `admin` scope, and scoped keys can't
+//! issue scopes they lack. This holds even before SHODH_ADMIN_API_KEYS is set,
+//! when any unrestricted key may otherwise manage keys.
+//!
+//! `enforce_scopes` runs on every protected route and holds scoped keys to
+//! the routes their scopes cover.
+
+use axum::{
+    extract::{MatchedPath, Path, Request, State},
+    http::Method,
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+    Extension,
+};
+use serde::{Deserialize, Serialize};
+
+use super::acl::{require_admin, required_permission};
+use super::state::MultiUserMemoryManager;
+use crate::acl::{AclGrant, Permission};
+use crate::api_keys::{scopes_allow, ApiKeyRecord, KeyScope, NewKey};
+use crate::auth::AuthenticatedKey;
+use crate::errors::{AppError, ValidationErrorExt};
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// GET routes that store rather than retrieve (WebSocket ingestion)
+const INGEST_GET_ROUTES: &[&str] = &["/api/stream"];
+
+/// Scope a route needs from a scoped key
+fn required_scope(method: &Method, route: &str) -> KeyScope {
+    if route.starts_with("/api/admin/") {
+        KeyScope::Admin
+    } else if INGEST_GET_ROUTES.contains(&route) {
+        KeyScope::Ingest
+    } else {
+        match required_permission(method, route) {
+            Permission::Read => KeyScope::Recall,
+            Permission::Write => KeyScope::Ingest,
+        }
+    }
+}
+
+/// Middleware: reject requests outside the scopes of the caller's key
+pub async fn enforce_scopes(request: Request, next: Next) -> Response {
+    let Some(scopes) = request
+        .extensions()
+        .get::<AuthenticatedKey>()
+        .map(|k| k.scopes.clone())
+        .filter(|s| !s.is_empty())
+    else {
+        return next.run(request).await;
+    };
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str())
+        .unwrap_or_else(|| request.uri().path());
+    let required = required_scope(request.method(), route);
+    if !scopes_allow(&scopes, required) {
+        return AppError::Forbidden(format!(
+            "API key lacks the '{}' scope for {route}",
+            required.as_str()
+        ))
+        .into_response();
+    }
+    next.run(request).await
+}
+
+/// Refuse to let the caller hand out `scopes` beyond its own: the `admin`
+/// scope needs an admin key, and no scopes (unrestricted) needs an
+/// unrestricted caller
+fn check_grantable(key: Option<&AuthenticatedKey>, scopes: &[KeyScope]) -> Result<(), AppError> {
+    if key.is_some_and(|k| k.admin) {
+        return Ok(());
+    }
+    if scopes.contains(&KeyScope::Admin) {
+        return Err(AppError::Forbidden(
+            "only an admin API key may issue the 'admin' scope".to_string(),
+        ));
+    }
+    let own = key.map(|k| k.scopes.as_slice()).unwrap_or_default();
+    let broader = if scopes.is_empty() {
+        !own.is_empty()
+    } else {
+        scopes.iter().any(|scope| !scopes_allow(own, *scope))
+    };
+    if broader {
+        return Err(AppError::Forbidden(
+            "an API key can't issue scopes broader than its own".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn unknown_key(key_id: &str) -> AppError {
+    AppError::InvalidInput {
+        field: "key_id".to_string(),
+        reason: format!("no API key with id '{key_id}'"),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiKeyListResponse {
+    pub keys: Vec<ApiKeyRecord>,
+    pub count: usize,
+}
+
+/// GET /api/admin/keys - List managed keys, newest first (revoked included)
+pub async fn list_keys(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+) -> Result<Json<ApiKeyListResponse>, AppError> {
+    require_admin(key.as_deref(), "API key management")?;
+    let keys: Vec<ApiKeyRecord> = state
+        .api_keys
+        .list()
+        .iter()
+        .map(ApiKeyRecord::redacted)
+        .collect();
+    Ok(Json(ApiKeyListResponse {
+        count: keys.len(),
+        keys,
+    }))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CreateKeyRequest {
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Bind the key to a tenant; its users are then isolated from other tenants
+    #[serde(default)]
+    pub tenant: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<KeyScope>,
+}
+
+/// A newly issued key. `api_key` is not stored and can't be shown again.
+#[derive(Debug, Serialize)]
+pub struct IssuedKeyResponse {
+    pub api_key: String,
+    #[serde(flatten)]
+    pub record: ApiKeyRecord,
+}
+
+/// POST /api/admin/keys - Issue a key
+pub async fn create_key(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    body: Option<Json<CreateKeyRequest>>,
+) -> Result<Json<IssuedKeyResponse>, AppError> {
+    require_admin(key.as_deref(), "API key management")?;
+    let req = body.map(|Json(req)| req).unwrap_or_default();
+    check_grantable(key.as_deref(), &req.scopes)?;
+    if let Some(tenant) = &req.tenant {
+        crate::tenant::validate_tenant(tenant).map_validation_err("tenant")?;
+    }
+    let (api_key, record) = state
+        .api_keys
+        .create(NewKey {
+            label: req.label.filter(|l| !l.trim().is_empty()),
+            tenant: req.tenant,
+            scopes: req.scopes,
+        })
+        .map_err(AppError::Internal)?;
+    tracing::info!(
+        key_id = %record.key_id,
+        tenant = record.tenant.as_deref().unwrap_or(crate::tenant::DEFAULT_TENANT),
+        "API key created"
+    );
+    Ok(Json(IssuedKeyResponse {
+        api_key,
+        record: record.redacted(),
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateKeyRequest {
+    /// New label (empty to clear)
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Replaces the key's scopes
+    #[serde(default)]
+    pub scopes: Option<Vec<KeyScope>>,
+}
+
+/// PATCH /api/admin/keys/{key_id} - Relabel a key or change its scopes
+pub async fn update_key(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Path(key_id): Path<String>,
+    Json(req): Json<UpdateKeyRequest>,
+) -> Result<Json<ApiKeyRecord>, AppError> {
+    require_admin(key.as_deref(), "API key management")?;
+    if let Some(scopes) = &req.scopes {
+        check_grantable(key.as_deref(), scopes)?;
+    }
+    let record = state
+        .api_keys
+        .update(&key_id, req.label, req.scopes)
+        .map_err(AppError::Internal)?
+        .ok_or_else(|| unknown_key(&key_id))?;
+    Ok(Json(record.redacted()))
+}
+
+/// POST /api/admin/keys/{key_id}/rotate - Replace a key with a new one
+///
+/// The new key keeps the label, tenant, scopes and ACL grants; the old key
+/// stops working immediately.
+pub async fn rotate_key(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Path(key_id): Path<String>,
+) -> Result<Json<IssuedKeyResponse>, AppError> {
+    require_admin(key.as_deref(), "API key management")?;
+    // Rotation hands out the new key, so it needs the right to issue its scopes
+    let current = state
+        .api_keys
+        .get(&key_id)
+        .ok_or_else(|| unknown_key(&key_id))?;
+    check_grantable(key.as_deref(), &current.scopes)?;
+    let (api_key, record) = state
+        .api_keys
+        .rotate(&key_id)
+        .map_validation_err("key_id")?
+        .ok_or_else(|| unknown_key(&key_id))?;
+
+    for grant in state.acl_store.list(None) {
+        if grant.key_id != key_id {
+            continue;
+        }
+        state
+            .acl_store
+            .grant(AclGrant {
+                key_id: record.key_id.clone(),
+                granted_at: chrono::Utc::now(),
+                ..grant.clone()
+            })
+            .map_err(AppError::Internal)?;
+        state
+            .acl_store
+            .revoke(&grant.namespace, &key_id)
+            .map_err(AppError::Internal)?;
+    }
+
+    tracing::info!(old_key_id = %key_id, key_id = %record.key_id, "API key rotated");
+    Ok(Json(IssuedKeyResponse {
+        api_key,
+        record: record.redacted(),
+    }))
+}
+
+/// DELETE /api/admin/keys/{key_id} - Revoke a key
+pub async fn revoke_key(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Path(key_id): Path<String>,
+) -> Result<Json<ApiKeyRecord>, AppError> {
+    require_admin(key.as_deref(), "API key management")?;
+    let record = state
+        .api_keys
+        .revoke(&key_id)
+        .map_err(AppError::Internal)?
+        .ok_or_else(|| unknown_key(&key_id))?;
+    tracing::info!(key_id = %key_id, "API key revoked");
+    Ok(Json(record.redacted()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_cant_issue_broader_scopes() {
+        let caller = |admin, scopes: &[KeyScope]| AuthenticatedKey {
+            key_id: "k".to_string(),
+            admin,
+            tenant: None,
+            scopes: scopes.to_vec(),
+        };
+        let unrestricted = caller(false, &[]);
+        assert!(check_grantable(Some(&unrestricted), &[KeyScope::Recall]).is_ok());
+        assert!(check_grantable(Some(&unrestricted), &[]).is_ok());
+        assert!(check_grantable(Some(&unrestricted), &[KeyScope::Admin]).is_err());
+        assert!(check_grantable(None, &[KeyScope::Admin]).is_err());
+
+        let recall = caller(false, &[KeyScope::Recall]);
+        assert!(check_grantable(Some(&recall), &[KeyScope::Recall]).is_ok());
+        assert!(check_grantable(Some(&recall), &[KeyScope::Ingest]).is_err());
+        assert!(check_grantable(Some(&recall), &[]).is_err());
+
+        let admin = caller(true, &[KeyScope::Admin]);
+        assert!(check_grantable(Some(&admin), &[KeyScope::Admin]).is_ok());
+    }
+
+    #[test]
+    fn test_required_scope() {
+        assert_eq!(
+            required_scope(&Method::POST, "/api/recall"),
+            KeyScope::Recall
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/memories"),
+            KeyScope::Recall
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/remember"),
+            KeyScope::Ingest
+        );
+        assert_eq!(
+            required_scope(&Method::DELETE, "/api/memory/{memory_id}"),
+            KeyScope::Ingest
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/stream"),
+            KeyScope::Ingest
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/admin/keys"),
+            KeyScope::Admin
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//! Memory Type Registry Handlers
+//!
+//! Lists the built-in memory types alongside deployment-defined custom types,
+//! and lets admins define or remove custom types. See
+//! [`crate::memory::type_registry`] for how custom types are stored.
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    Extension,
+};
+use serde::{Deserialize, Serialize};
+
+use super::acl::require_admin;
+use super::crud::parse_experience_type;
+use super::state::MultiUserMemoryManager;
+use crate::auth::AuthenticatedKey;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::memory::retention::RetentionRule;
+use crate::memory::type_registry::{MemoryTypeDef, ResolvedType, TypeDisplay, BUILTIN_TYPES};
+use crate::memory::ExperienceType;
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// Resolve a request's `memory_type` against the registry (`None` = observation)
+pub fn resolve_memory_type(
+    state: &MultiUserMemoryManager,
+    memory_type: Option<&str>,
+) -> Result<ResolvedType, AppError> {
+    let Some(name) = memory_type.filter(|t| !t.trim().is_empty()) else {
+        return Ok(ResolvedType {
+            base: ExperienceType::Observation,
+            custom: None,
+        });
+    };
+    state
+        .memory_types
+        .resolve(name)
+        .ok_or_else(|| AppError::InvalidInput {
+            field: "memory_type".to_string(),
+            reason: format!("unknown memory type '{name}' (see GET /api/memory_types)"),
+        })
+}
+
+/// A registered type as listed by GET /api/memory_types
+#[derive(Debug, Serialize)]
+pub struct MemoryTypeInfo {
+    pub name: String,
+    /// Built-in type memories are stored as
+    pub base: String,
+    pub builtin: bool,
+    pub description: Option<String>,
+    /// Effective retention rule
+    pub retention: RetentionRule,
+    pub retrieval_weight: f32,
+    pub display: TypeDisplay,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoryTypesResponse {
+    pub types: Vec<MemoryTypeInfo>,
+    pub count: usize,
+}
+
+/// GET /api/memory_types - Built-in and custom memory types
+pub async fn list_memory_types(State(state): State<AppState>) -> Json<MemoryTypesResponse> {
+    let policy = state
+        .memory_types
+        .retention_policy(&state.server_config.retention);
+    let builtin = BUILTIN_TYPES.iter().map(|(name, base)| MemoryTypeInfo {
+        name: name.to_string(),
+        base: format!("{base:?}"),
+        builtin: true,
+        description: None,
+        retention: policy.rule_for(base),
+        retrieval_weight: 1.0,
+        display: TypeDisplay::default(),
+    });
+    let custom = state
+        .memory_types
+        .custom_types()
+        .into_iter()
+        .map(|def| MemoryTypeInfo {
+            retention: def.retention.unwrap_or_else(|| policy.rule_for(&def.base)),
+            base: format!("{:?}", def.base),
+            builtin: false,
+            name: def.name,
+            description: def.description,
+            retrieval_weight: def.retrieval_weight,
+            display: def.display,
+        });
+    let types: Vec<MemoryTypeInfo> = builtin.chain(custom).collect();
+    Json(MemoryTypesResponse {
+        count: types.len(),
+        types,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DefineMemoryTypeRequest {
+    pub name: String,
+    /// Built-in type to store memories as, e.g. "learning"
+    pub base: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Overrides the base type's retention: "never", "run_end" or {"days": N}
+    #[serde(default)]
+    pub retention: Option<RetentionRule>,
+    #[serde(default)]
+    pub retrieval_weight: Option<f32>,
+    #[serde(default)]
+    pub display: TypeDisplay,
+}
+
+/// POST /api/memory_types - Define or replace a custom memory type
+pub async fn define_memory_type(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Json(req): Json<DefineMemoryTypeRequest>,
+) -> Result<Json<MemoryTypeDef>, AppError> {
+    require_admin(key.as_deref(), "Memory type management")?;
+    let base = parse_experience_type(&req.base).map_err(|_| AppError::InvalidInput {
+        field: "base".to_string(),
+        reason: format!("unknown built-in type '{}'", req.base),
+    })?;
+    let def = MemoryTypeDef {
+        name: req.name.trim().to_lowercase(),
+        base,
+        description: req.description,
+        retention: req.retention,
+        retrieval_weight: req.retrieval_weight.unwrap_or(1.0),
+        display: req.display,
+    };
+    state
+        .memory_types
+        .define(def.clone())
+        .map_validation_err("memory_type")?;
+    tracing::info!(name = %def.name, base = ?def.base, "Memory type defined");
+    Ok(Json(def))
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeleteMemoryTypeResponse {
+    pub removed: bool,
+    pub name: String,
+}
+
+/// DELETE /api/memory_types/{name} - Remove a custom memory type
+///
+/// Memories already stored under it keep their base type.
+pub async fn delete_memory_type(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Path(name): Path<String>,
+) -> Result<Json<DeleteMemoryTypeResponse>, AppError> {
+    require_admin(key.as_deref(), "Memory type management")?;
+    let removed = state
+        .memory_types
+        .remove(&name)
+        .map_err(AppError::Internal)?;
+    Ok(Json(DeleteMemoryTypeResponse { removed, name }))
+}
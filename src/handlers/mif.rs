@@ -224,6 +224,9 @@ pub async fn import_mif(
                 Err(e) => result.errors.push(format!("Memory: {e}")),
             }
         }
+        if let Err(e) = guard.commit_keyword_index() {
+            tracing::warn!("Failed to commit BM25 index after MIF import: {}", e);
+        }
     }
 
     // Import projects first (todos may reference them)
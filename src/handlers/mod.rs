@@ -10,18 +10,27 @@ pub mod types;
 
 // Health and utilities
 pub mod health;
+pub mod stats;
 pub mod utils;
 
 // Memory core operations
 pub mod crud;
+pub mod memory_types;
 pub mod recall;
 pub mod remember;
 
 // Advanced memory operations
 pub mod compression;
+pub mod conflicts;
+pub mod export;
 pub mod facts;
+pub mod import;
 pub mod lineage;
+pub mod ranking;
+pub mod review;
 pub mod search;
+pub mod suppress;
+pub mod tags;
 
 // Knowledge graph
 pub mod graph;
@@ -31,6 +40,7 @@ pub mod visualization;
 pub mod todos;
 
 // MCP and webhooks
+pub mod event_webhooks;
 pub mod mif;
 pub mod webhooks;
 
@@ -38,7 +48,12 @@ pub mod webhooks;
 pub mod integrations;
 
 // Session and user management
+pub mod acl;
+pub mod hooks;
+pub mod keys;
 pub mod sessions;
+pub mod teams;
+pub mod tenants;
 pub mod users;
 
 // File and codebase memory
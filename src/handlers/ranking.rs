@@ -0,0 +1,175 @@
+//! Ranking Weight Handlers
+//!
+//! /api/admin/ranking reads and tunes the weights of recall's unified score
+//! per tenant: semantic relevance, recency, importance (retention strength),
+//! reinforcement (feedback momentum), tier weighting and score calibration.
+//! Requests name the tenant with `tenant`; leaving it out (or "default")
+//! addresses the default tenant. Changes apply to loaded users at once and
+//! are persisted.
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+    Extension,
+};
+use serde::{Deserialize, Serialize};
+
+use super::acl::require_admin;
+use super::state::MultiUserMemoryManager;
+use crate::auth::AuthenticatedKey;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::memory::ranking::RankingWeights;
+use crate::tenant::{self, DEFAULT_TENANT};
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// Tenant a request names: `None` for the default tenant
+fn parse_tenant(tenant: Option<&str>) -> Result<Option<String>, AppError> {
+    match tenant.map(str::trim) {
+        None | Some("") | Some(DEFAULT_TENANT) => Ok(None),
+        Some(name) => {
+            tenant::validate_tenant(name).map_validation_err("tenant")?;
+            Ok(Some(name.to_string()))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RankingQuery {
+    /// Only this tenant, with its effective weights
+    pub tenant: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TenantRanking {
+    pub tenant: String,
+    pub weights: RankingWeights,
+    /// Whether the tenant has weights of its own (false = defaults)
+    pub configured: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RankingListResponse {
+    /// Weights of tenants without their own
+    pub defaults: RankingWeights,
+    pub tenants: Vec<TenantRanking>,
+    pub count: usize,
+}
+
+fn tenant_ranking(state: &MultiUserMemoryManager, tenant: Option<&str>) -> TenantRanking {
+    TenantRanking {
+        tenant: tenant.unwrap_or(DEFAULT_TENANT).to_string(),
+        weights: state.ranking.weights(tenant),
+        configured: state.ranking.is_configured(tenant),
+    }
+}
+
+/// GET /api/admin/ranking?tenant=... - Ranking weights per tenant
+pub async fn get_ranking(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Query(query): Query<RankingQuery>,
+) -> Result<Json<RankingListResponse>, AppError> {
+    require_admin(key.as_deref(), "Ranking configuration")?;
+    let tenants = match query.tenant {
+        Some(name) => {
+            let tenant = parse_tenant(Some(&name))?;
+            vec![tenant_ranking(&state, tenant.as_deref())]
+        }
+        None => state
+            .ranking
+            .list()
+            .into_iter()
+            .map(|(tenant, weights)| TenantRanking {
+                tenant,
+                weights,
+                configured: true,
+            })
+            .collect(),
+    };
+    Ok(Json(RankingListResponse {
+        defaults: RankingWeights::default(),
+        count: tenants.len(),
+        tenants,
+    }))
+}
+
+/// Weights to change; fields left out keep their current value
+#[derive(Debug, Deserialize)]
+pub struct SetRankingRequest {
+    #[serde(default)]
+    pub tenant: Option<String>,
+    #[serde(default)]
+    pub semantic: Option<f32>,
+    #[serde(default)]
+    pub recency: Option<f32>,
+    #[serde(default)]
+    pub importance: Option<f32>,
+    #[serde(default)]
+    pub reinforcement: Option<f32>,
+    #[serde(default)]
+    pub calibrate: Option<bool>,
+    #[serde(default)]
+    pub tiers: Option<bool>,
+}
+
+/// PUT /api/admin/ranking - Set a tenant's ranking weights
+pub async fn set_ranking(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Json(req): Json<SetRankingRequest>,
+) -> Result<Json<TenantRanking>, AppError> {
+    require_admin(key.as_deref(), "Ranking configuration")?;
+    let tenant = parse_tenant(req.tenant.as_deref())?;
+    let current = state.ranking.weights(tenant.as_deref());
+    let weights = RankingWeights {
+        semantic: req.semantic.unwrap_or(current.semantic),
+        recency: req.recency.unwrap_or(current.recency),
+        importance: req.importance.unwrap_or(current.importance),
+        reinforcement: req.reinforcement.unwrap_or(current.reinforcement),
+        calibrate: req.calibrate.unwrap_or(current.calibrate),
+        tiers: req.tiers.unwrap_or(current.tiers),
+    };
+    weights.validate().map_validation_err("weights")?;
+    state
+        .ranking
+        .set(tenant.as_deref(), weights)
+        .map_err(AppError::Internal)?;
+    state.apply_ranking(tenant.as_deref());
+    tracing::info!(
+        tenant = tenant.as_deref().unwrap_or(DEFAULT_TENANT),
+        ?weights,
+        "Ranking weights updated"
+    );
+    Ok(Json(tenant_ranking(&state, tenant.as_deref())))
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResetRankingResponse {
+    /// False if the tenant had no weights of its own
+    pub reset: bool,
+    #[serde(flatten)]
+    pub ranking: TenantRanking,
+}
+
+/// DELETE /api/admin/ranking?tenant=... - Return a tenant to the default weights
+pub async fn reset_ranking(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Query(query): Query<RankingQuery>,
+) -> Result<Json<ResetRankingResponse>, AppError> {
+    require_admin(key.as_deref(), "Ranking configuration")?;
+    let tenant = parse_tenant(query.tenant.as_deref())?;
+    let reset = state
+        .ranking
+        .reset(tenant.as_deref())
+        .map_err(AppError::Internal)?;
+    if reset {
+        state.apply_ranking(tenant.as_deref());
+    }
+    Ok(Json(ResetRankingResponse {
+        reset,
+        ranking: tenant_ranking(&state, tenant.as_deref()),
+    }))
+}
@@ -5,26 +5,46 @@
 //! - Hybrid recall (semantic + graph)
 //! - Proactive context surfacing
 //! - Tag-based and date-based recall
+//! - Graph-augmented recall of memories related to a seed
 //! - Tracked retrieval with Hebbian feedback
+//! - Per-memory effectiveness stats from the feedback loop
 
-use axum::{extract::State, response::Json};
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::Json,
+    Extension,
+};
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
 use super::state::MultiUserMemoryManager;
+use super::teams::{resolve_team, TEAM_AUTHOR_KEY};
 use super::types::{
     MemoryEvent, RecallExperience, RecallFact, RecallMemory, RecallRequest, RecallResponse,
     RecallTodo, ReinforceFeedbackRequest, RetrieveResponse, TrackedRetrieveRequest,
     TrackedRetrieveResponse,
 };
 use super::utils::{is_bare_question, is_boilerplate_response, strip_system_noise};
+use crate::auth::AuthenticatedKey;
+use crate::constants::{
+    MAX_REINFORCEMENT_STRENGTH, MEMORY_TIER_PREFERENCE_BOOST, RELATED_DEFAULT_WIDTH,
+    RELATED_GRAPH_WEIGHT, RELATED_MAX_DEPTH, RELATED_RELEVANCE_WEIGHT, TEMPORAL_FILTER_OVERFETCH,
+};
+use crate::embeddings::chunking::estimate_tokens;
 use crate::errors::{AppError, ValidationErrorExt};
+use crate::graph_memory::EntityNode;
 use crate::memory::feedback;
+use crate::memory::injection::{
+    build_overflow_digest, mmr_rerank, sanitize_for_injection, AgentScope, SanitizeMode,
+    TypeInjectionPolicy,
+};
 // Note: compute_relevance removed - using unified 5-layer pipeline scoring instead
+use crate::memory::query_parser::{self, TimeRange};
 use crate::memory::segmentation::{InputSource, SegmentationEngine};
 use crate::memory::sessions::SessionEvent;
 use crate::memory::storage::SearchCriteria;
-use crate::memory::types::MemoryId;
+use crate::memory::types::{MemoryId, Provenance, ProvenanceSource};
 use crate::memory::{Experience, ExperienceType, Query as MemoryQuery, SharedMemory};
 use crate::memory::{ProspectiveTrigger, TodoStatus};
 use crate::metrics;
@@ -97,12 +117,45 @@ pub struct ProactiveContextRequest {
     /// Weight for entity matching in relevance scoring
     #[serde(default = "default_entity_weight")]
     pub entity_match_weight: f32,
-    /// Weight for recency boost
-    #[serde(default = "default_recency_weight")]
-    pub recency_weight: f32,
-    /// Filter to specific memory types
+    /// Weight for recency boost (default 0.2); when set, also replaces the
+    /// server's SHODH_MMR_RECENCY_WEIGHT during re-ranking. 0.0 ranks old and
+    /// new memories alike.
+    #[serde(default)]
+    pub recency_weight: Option<f32>,
+    /// Age in hours at which the recency boost halves (defaults to ~70 hours
+    /// in recall and the server's SHODH_MMR_RECENCY_HALF_LIFE in re-ranking).
+    /// Short for coding sessions, long for research.
+    #[serde(default)]
+    pub recency_half_life: Option<f32>,
+    /// Filter to specific memory types (overrides the server injection policy)
     #[serde(default)]
     pub memory_types: Vec<String>,
+    /// Per-type maximum memories, e.g. `{"Decision": 3, "Conversation": 0}`
+    /// (merged over the server injection policy; 0 disables a type)
+    #[serde(default)]
+    pub memory_type_limits: std::collections::HashMap<String, usize>,
+    /// Prompt-injection sanitization for surfaced content: off, standard, strict
+    /// (defaults to the server's SHODH_SANITIZE_MODE)
+    #[serde(default)]
+    pub sanitize: Option<SanitizeMode>,
+    /// Agent requesting context (auto-ingested context is attributed to it)
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    /// Parent of `agent_id`, for the `own_and_parent` scope
+    #[serde(default)]
+    pub parent_agent_id: Option<String>,
+    /// Whose memories may surface: own, own_and_parent, user
+    /// (defaults to the server's SHODH_AGENT_SCOPE)
+    #[serde(default)]
+    pub agent_scope: Option<AgentScope>,
+    /// Summarize relevant memories that didn't fit `max_results` into a single digest
+    /// (default: false)
+    #[serde(default)]
+    pub overflow_digest: bool,
+    /// MMR relevance vs. diversity trade-off (1.0 = pure relevance;
+    /// defaults to the server's SHODH_MMR_LAMBDA)
+    #[serde(default)]
+    pub mmr_lambda: Option<f32>,
     /// Whether to auto-ingest the context as a Conversation memory
     #[serde(default = "default_true")]
     pub auto_ingest: bool,
@@ -112,8 +165,32 @@ pub struct ProactiveContextRequest {
     /// User's followup message after agent response (for delayed signals)
     #[serde(default)]
     pub user_followup: Option<String>,
+    /// Reserve a slot for an important memory due for spaced-repetition review
+    /// (default: false)
+    #[serde(default)]
+    pub resurface: bool,
+    /// Favor memories from one store: working, episodic or semantic
+    #[serde(default)]
+    pub tier_preference: Option<String>,
+    /// Resolve a temporal expression in the context ("last week", "in March")
+    /// into a date filter on ingest or event time (default: false)
+    #[serde(default)]
+    pub temporal_filter: bool,
+    /// Team whose shared memories may surface too (falls back to the X-Shodh-Team header)
+    #[serde(default)]
+    pub team_id: Option<String>,
 }
 
+/// Longest accepted `recency_half_life` (10 years)
+const MAX_RECENCY_HALF_LIFE_HOURS: f32 = 87_600.0;
+
+/// Character budget for the overflow digest block in proactive_context
+const OVERFLOW_DIGEST_MAX_CHARS: usize = 600;
+
+/// Queued reviews inspected per proactive_context call to find one the
+/// type policy and agent scope allow
+const REVIEW_PROACTIVE_CANDIDATES: usize = 5;
+
 fn default_proactive_max_results() -> usize {
     5
 }
@@ -154,11 +231,48 @@ pub struct ProactiveSurfacedMemory {
     /// Entities from this memory that matched the query context
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub matched_entities: Vec<String>,
+    /// Team member who shared this memory (team memories only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared_by: Option<String>,
+    /// How the score was built (only with `?explain=true`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<RetrievalExplanation>,
     /// Embedding for semantic feedback (not serialized to response)
     #[serde(skip)]
     pub embedding: Vec<f32>,
 }
 
+/// Query flags for proactive context
+#[derive(Debug, Default, Deserialize)]
+pub struct ProactiveContextParams {
+    /// Attach a score breakdown to every surfaced memory
+    #[serde(default)]
+    pub explain: bool,
+}
+
+/// Breakdown of a surfaced memory's score, for auditing why it was injected
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RetrievalExplanation {
+    /// Context words (3+ characters) that also appear in the memory
+    pub matched_terms: Vec<String>,
+    /// Composite recall score (semantic, keyword and graph layers)
+    pub retrieval_score: f32,
+    /// Added for entities/tags shared with the context
+    pub entity_boost: f32,
+    /// Multiplier from repeat suppression (1.0 = not recently injected)
+    pub suppression_factor: f32,
+    /// Multiplier from `tier_preference` (1.0 = not applied)
+    pub tier_boost: f32,
+    /// Retrieval weight of the memory's type (1.0 = neutral)
+    pub type_weight: f32,
+    /// Score after boosts, before normalizing against the top result
+    pub boosted_score: f32,
+    /// Recency bonus added during diversity re-ranking (0.0 when off)
+    pub recency_boost: f32,
+    /// Memory importance at retrieval time
+    pub importance: f32,
+}
+
 /// Entity detected in the query context
 #[derive(Debug, Clone, Serialize)]
 pub struct DetectedEntityInfo {
@@ -240,6 +354,14 @@ pub struct ProactiveContextResponse {
     /// Entities detected in the query context
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub detected_entities: Vec<DetectedEntityInfo>,
+    /// Digest of relevant memories cut by the injection budget ("Summary of additional context")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overflow_digest: Option<String>,
+    /// True when the user's daily injection token budget cut memories from this response
+    pub budget_exhausted: bool,
+    /// Date range resolved from a temporal expression in the context
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_filter: Option<TimeRange>,
 }
 
 // =============================================================================
@@ -255,6 +377,46 @@ pub struct ReinforceFeedbackResponse {
     pub importance_decays: usize,
 }
 
+/// Query for per-memory effectiveness stats
+#[derive(Debug, Deserialize)]
+pub struct MemoryStatsQuery {
+    pub user_id: String,
+    /// Maximum memories listed, most surfaced first (default 50, max 500)
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Feedback history of a single memory
+#[derive(Debug, Serialize)]
+pub struct MemoryEffectiveness {
+    pub id: String,
+    pub content_preview: String,
+    /// Times surfaced by proactive context
+    pub surfaced: u32,
+    /// Feedback signals showing the response built on the memory
+    pub cited: u32,
+    pub positive_signals: u32,
+    pub negative_signals: u32,
+    /// Feedback momentum (-1.0 misleading to 1.0 helpful)
+    pub momentum: f32,
+    pub last_signal_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Per-memory effectiveness stats with user-wide aggregates
+#[derive(Debug, Serialize)]
+pub struct MemoryStatsResponse {
+    pub user_id: String,
+    /// Memories with any feedback history
+    pub tracked_memories: usize,
+    pub total_surfaced: u64,
+    pub total_cited: u64,
+    pub total_positive: u64,
+    pub total_negative: u64,
+    /// Mean momentum across tracked memories
+    pub avg_momentum: f32,
+    pub memories: Vec<MemoryEffectiveness>,
+}
+
 // =============================================================================
 // RECALL BY TAGS/DATE TYPES (local - not in shared types.rs)
 // =============================================================================
@@ -281,6 +443,52 @@ pub struct RecallByDateRequest {
     pub limit: Option<usize>,
 }
 
+// =============================================================================
+// RECALL RELATED TYPES
+// =============================================================================
+
+/// Recall memories connected to a seed memory or entity in the knowledge graph
+///
+/// Exactly one of `memory_id` or `entity` must be set.
+#[derive(Debug, Deserialize)]
+pub struct RecallRelatedRequest {
+    pub user_id: String,
+    /// Seed memory: walk from the entities it mentions
+    pub memory_id: Option<String>,
+    /// Seed entity name
+    pub entity: Option<String>,
+    /// Hops walked from the seed entities (default: 2, max: RELATED_MAX_DEPTH)
+    pub max_depth: Option<usize>,
+    /// Entities kept per hop and memories taken per entity (default: RELATED_DEFAULT_WIDTH)
+    pub max_width: Option<usize>,
+    /// Maximum number of results (default: 10)
+    pub limit: Option<usize>,
+}
+
+/// A memory reached from the seed
+#[derive(Serialize)]
+pub struct RelatedMemory {
+    #[serde(flatten)]
+    pub memory: RecallMemory,
+    /// Hops from the seed to the entity linking this memory (0 = a seed entity)
+    pub hop_distance: usize,
+    /// Entity through which the memory was reached
+    pub via_entity: String,
+    /// Graph proximity (1.0 at a seed entity, decaying per hop)
+    pub graph_score: f32,
+    /// Similarity to the seed memory, or importance for entity seeds
+    pub relevance: f32,
+}
+
+/// Related memories, best combined score first
+#[derive(Serialize)]
+pub struct RecallRelatedResponse {
+    pub memories: Vec<RelatedMemory>,
+    pub count: usize,
+    /// Seed entities the walk started from
+    pub seed_entities: Vec<String>,
+}
+
 // =============================================================================
 // MAIN RECALL HANDLER
 // =============================================================================
@@ -291,9 +499,11 @@ pub struct RecallByDateRequest {
 /// 1. Semantic search via vector similarity
 /// 2. Graph traversal via spreading activation
 /// 3. Hebbian boosting for frequently co-retrieved memories
-#[tracing::instrument(skip(state), fields(user_id = %req.user_id, query = %req.query))]
+#[tracing::instrument(skip(state, key), fields(user_id = %req.user_id, query = %req.query))]
 pub async fn recall(
     State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    headers: HeaderMap,
     Json(req): Json<RecallRequest>,
 ) -> Result<Json<RecallResponse>, AppError> {
     let op_start = std::time::Instant::now();
@@ -304,6 +514,11 @@ pub async fn recall(
         .get_user_memory(&req.user_id)
         .map_err(AppError::Internal)?;
 
+    let team_memory = resolve_team(&headers, req.team_id.as_deref())?
+        .map(|team| state.get_team_memory(&team))
+        .transpose()
+        .map_err(AppError::Internal)?;
+
     let _graph = state
         .get_user_graph(&req.user_id)
         .map_err(AppError::Internal)?;
@@ -318,6 +533,7 @@ pub async fn recall(
     let memory_for_recall = memory.clone();
     let user_id_for_recall = req.user_id.clone();
     let query_for_recall = req.query.clone();
+    let type_weights = state.memory_types.weights();
 
     let (memories, triggered_reminders, _prospective_signals) =
         tokio::task::spawn_blocking(move || {
@@ -395,7 +611,26 @@ pub async fn recall(
                 ..Default::default()
             };
 
-            let memories = memory_guard.recall(&query).unwrap_or_default();
+            let mut memories = memory_guard.recall(&query).unwrap_or_default();
+
+            // Team memories compete with the user's own on score
+            if let Some(team_memory) = team_memory {
+                let team_results = team_memory.read().recall(&query).unwrap_or_default();
+                memories.extend(
+                    team_results
+                        .into_iter()
+                        .filter(|m| !memory_guard.is_suppressed(m)),
+                );
+                memories.sort_by(|a, b| {
+                    b.get_score()
+                        .unwrap_or(0.0)
+                        .total_cmp(&a.get_score().unwrap_or(0.0))
+                });
+                memories.truncate(limit);
+            }
+
+            // Custom memory types rank by their registered retrieval weight
+            type_weights.rank(&mut memories);
 
             (memories, reminders, prospective_signals)
         })
@@ -432,6 +667,7 @@ pub async fn recall(
                 created_at: m.created_at.to_rfc3339(),
                 score,
                 tier: format!("{:?}", m.tier),
+                shared_by: m.experience.metadata.get(TEAM_AUTHOR_KEY).cloned(),
             }
         })
         .collect();
@@ -625,6 +861,15 @@ pub async fn recall(
         count: Some(count),
         results: Some(results_json),
     });
+    state.log_access(
+        key.as_deref(),
+        &req.user_id,
+        "/api/recall",
+        recall_memories
+            .iter()
+            .map(|m| (m.id.clone(), Some(m.score)))
+            .collect(),
+    );
 
     // Track session event
     if count > 0 {
@@ -758,14 +1003,36 @@ pub async fn context_summary(
 // PROACTIVE CONTEXT HANDLER
 // =============================================================================
 
+/// Context words that also appear in `content`, sorted
+fn matched_query_terms(
+    query_words: &std::collections::HashSet<String>,
+    content: &str,
+) -> Vec<String> {
+    let mut terms: Vec<String> = content
+        .split_whitespace()
+        .map(|w| {
+            w.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|w| query_words.contains(w))
+        .collect();
+    terms.sort();
+    terms.dedup();
+    terms
+}
+
 /// POST /api/proactive_context - Combined recall + reminders for AI agents
 ///
 /// Returns relevant memories based on semantic similarity and entity matching,
 /// plus any due or context-triggered reminders. Optionally stores the context
-/// as a Conversation memory for future recall.
-#[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
+/// as a Conversation memory for future recall. With `?explain=true` every
+/// surfaced memory carries a breakdown of how its score was built.
+#[tracing::instrument(skip(state, key), fields(user_id = %req.user_id))]
 pub async fn proactive_context(
     State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    headers: HeaderMap,
+    Query(params): Query<ProactiveContextParams>,
     Json(mut req): Json<ProactiveContextRequest>,
 ) -> Result<Json<ProactiveContextResponse>, AppError> {
     validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
@@ -774,8 +1041,36 @@ pub async fn proactive_context(
         .map_validation_err("semantic_threshold")?;
     validation::validate_weight("entity_match_weight", req.entity_match_weight)
         .map_validation_err("entity_match_weight")?;
-    validation::validate_weight("recency_weight", req.recency_weight)
-        .map_validation_err("recency_weight")?;
+    if let Some(weight) = req.recency_weight {
+        validation::validate_weight("recency_weight", weight)
+            .map_validation_err("recency_weight")?;
+    }
+    if let Some(hours) = req.recency_half_life {
+        if !hours.is_finite() || !(1.0..=MAX_RECENCY_HALF_LIFE_HOURS).contains(&hours) {
+            return Err(AppError::InvalidInput {
+                field: "recency_half_life".to_string(),
+                reason: format!(
+                    "must be between 1 and {MAX_RECENCY_HALF_LIFE_HOURS} hours, got: {hours}"
+                ),
+            });
+        }
+    }
+    if let Some(lambda) = req.mmr_lambda {
+        validation::validate_weight("mmr_lambda", lambda).map_validation_err("mmr_lambda")?;
+    }
+    let preferred_tiers = match req.tier_preference.as_deref() {
+        Some(name) => Some(
+            crate::memory::importance::parse_tier_preference(name).ok_or_else(|| {
+                AppError::InvalidInput {
+                    field: "tier_preference".to_string(),
+                    reason: format!(
+                        "unknown tier '{name}', expected working, episodic or semantic"
+                    ),
+                }
+            })?,
+        ),
+        None => None,
+    };
 
     // Strip system noise BEFORE any processing — <task-notification>, <system-reminder>,
     // <shodh-context>, code blocks, file contents, etc. This ensures embedding, NER, BM25,
@@ -815,6 +1110,9 @@ pub async fn proactive_context(
             relevant_facts: Vec::new(),
             latency_ms: 0.0,
             detected_entities: Vec::new(),
+            overflow_digest: None,
+            budget_exhausted: false,
+            time_filter: None,
         }));
     }
 
@@ -823,6 +1121,10 @@ pub async fn proactive_context(
     let memory_system = state
         .get_user_memory(&req.user_id)
         .map_err(AppError::Internal)?;
+    let team_memory = resolve_team(&headers, req.team_id.as_deref())?
+        .map(|team| state.get_team_memory(&team))
+        .transpose()
+        .map_err(AppError::Internal)?;
 
     let graph_memory = state
         .get_user_graph(&req.user_id)
@@ -898,10 +1200,7 @@ pub async fn proactive_context(
                     let is_misleading = signal.value < -0.3;
 
                     // Get or create momentum for this memory
-                    let momentum = store.get_or_create_momentum(
-                        memory_id.clone(),
-                        crate::memory::types::ExperienceType::Context,
-                    );
+                    let momentum = store.surfaced_momentum(memory_id.clone());
 
                     // Track reinforced/weakened
                     let old_ema = momentum.ema;
@@ -1160,14 +1459,80 @@ pub async fn proactive_context(
     // 2. Retrieve memories using unified 5-layer pipeline
     // The pipeline already applies: RRF fusion + hebbian + recency + feedback (PIPE-9)
     // No double-scoring needed - just use the scores from recall() directly
-    let context_clone = req.context.clone();
+    // "what did we decide last week?" -> date filter + "what did we decide?"
+    let time_filter = req
+        .temporal_filter
+        .then(|| query_parser::parse_time_range(&req.context, chrono::Utc::now()))
+        .flatten();
+    let time_bounds = time_filter.as_ref().map(|r| (r.start, r.end));
+    let context_clone = time_filter
+        .as_ref()
+        .map(|r| r.strip_from(&req.context))
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| req.context.clone());
     let max_results = req.max_results;
     let user_id_for_query = req.user_id.clone();
     let entity_names_for_recall = context_entity_names.clone();
     let entity_match_weight = req.entity_match_weight;
-    let recency_weight = req.recency_weight;
+    let recency_weight = req.recency_weight.unwrap_or_else(default_recency_weight);
+    let recency_half_life = req.recency_half_life;
     let semantic_threshold = req.semantic_threshold;
-    let memories: Vec<ProactiveSurfacedMemory> = {
+    let type_policy = state
+        .server_config
+        .injection_type_policy
+        .merged(&TypeInjectionPolicy::new(
+            req.memory_types.clone(),
+            req.memory_type_limits.clone(),
+        ));
+    let type_weights = state.memory_types.weights();
+    let sanitize_mode = req
+        .sanitize
+        .unwrap_or(state.server_config.injection_sanitize_mode);
+    let agent_scope = req
+        .agent_scope
+        .unwrap_or(state.server_config.injection_agent_scope);
+    let agent_id = req.agent_id.clone();
+    let parent_agent_id = req.parent_agent_id.clone();
+    let want_digest = req.overflow_digest;
+    let resurface = req.resurface;
+    // Memories already injected on recent turns of the user's active session lose priority
+    let session_id = state.session_store.active_session(&req.user_id);
+    let suppression = session_id
+        .as_ref()
+        .map(|id| {
+            state.session_store.injection_priorities(
+                id,
+                state.server_config.injection_suppression_window,
+                state.server_config.injection_suppression_decay,
+            )
+        })
+        .unwrap_or_default();
+    let mut diversity = state.server_config.injection_diversity;
+    if let Some(lambda) = req.mmr_lambda {
+        diversity.lambda = lambda;
+    }
+    if let Some(weight) = req.recency_weight {
+        diversity.recency_weight = weight;
+    }
+    if let Some(hours) = recency_half_life {
+        diversity.recency_half_life_hours = hours;
+    }
+    // Over-fetch when re-ranking so MMR has alternatives to near-duplicates,
+    // and when a date filter will drop out-of-range candidates
+    let mut candidate_pool = if diversity.lambda < 1.0 {
+        max_results * 2
+    } else {
+        max_results
+    };
+    if time_bounds.is_some() {
+        candidate_pool *= TEMPORAL_FILTER_OVERFETCH;
+    }
+    let explain = params.explain;
+    let (memories, overflow_digest, review_id): (
+        Vec<ProactiveSurfacedMemory>,
+        Option<String>,
+        Option<MemoryId>,
+    ) = {
         let memory = memory_system.clone();
         tokio::task::spawn_blocking(move || {
             let memory_guard = memory.read();
@@ -1185,16 +1550,30 @@ pub async fn proactive_context(
             let query = MemoryQuery {
                 user_id: Some(user_id_for_query),
                 query_text: Some(context_clone),
-                max_results,
+                max_results: candidate_pool,
                 recency_weight: Some(recency_weight),
+                recency_half_life_hours: recency_half_life,
                 prospective_signals,
                 ..Default::default()
             };
-            let results = memory_guard.recall(&query).unwrap_or_default();
+            let mut results = memory_guard.recall(&query).unwrap_or_default();
+            // Shared team memories go through the same gates and ranking
+            if let Some(team_memory) = team_memory {
+                let team_results = team_memory.read().recall(&query).unwrap_or_default();
+                results.extend(
+                    team_results
+                        .into_iter()
+                        .filter(|m| !memory_guard.is_suppressed(m)),
+                );
+            }
 
             let candidates: Vec<(SharedMemory, f32)> = results
                 .into_iter()
                 .filter(|m| {
+                    // Temporal filter: ingested or about an event in the range
+                    if time_bounds.is_some_and(|(start, end)| !m.in_time_range(start, end)) {
+                        return false;
+                    }
                     // Quality gate: skip garbage/truncated memories
                     let content = m.experience.content.trim();
                     if content.len() < 30 {
@@ -1235,6 +1614,8 @@ pub async fn proactive_context(
 
             // Compute entity matches and boost scores BEFORE quality gate
             let context_entity_count = entity_names_for_recall.len().max(1);
+            let mut explanations: std::collections::HashMap<MemoryId, RetrievalExplanation> =
+                std::collections::HashMap::new();
             let mut enriched: Vec<(
                 std::sync::Arc<crate::memory::types::Memory>,
                 f32,
@@ -1242,6 +1623,7 @@ pub async fn proactive_context(
             )> = candidates
                 .into_iter()
                 .map(|(m, mut score)| {
+                    let retrieval_score = score;
                     let mut memory_terms: Vec<String> = m
                         .experience
                         .entities
@@ -1279,9 +1661,45 @@ pub async fn proactive_context(
                         .collect();
 
                     // Apply entity match boost: weight * (matched / total context entities)
-                    if !matched.is_empty() {
-                        score += entity_match_weight
-                            * (matched.len() as f32 / context_entity_count as f32);
+                    let entity_boost = if matched.is_empty() {
+                        0.0
+                    } else {
+                        entity_match_weight * (matched.len() as f32 / context_entity_count as f32)
+                    };
+                    score += entity_boost;
+
+                    // Repeat suppression: decay memories injected on consecutive turns
+                    let suppression_factor =
+                        suppression.get(&m.id.0.to_string()).copied().unwrap_or(1.0);
+                    score *= suppression_factor;
+
+                    // Caller's store preference (working/episodic/semantic)
+                    let tier_boost = if preferred_tiers.is_some_and(|tiers| tiers.contains(&m.tier))
+                    {
+                        MEMORY_TIER_PREFERENCE_BOOST
+                    } else {
+                        1.0
+                    };
+                    score *= tier_boost;
+
+                    // Registered retrieval weight of custom memory types
+                    let type_weight = type_weights.weight_for(&m);
+                    score *= type_weight;
+
+                    if explain {
+                        explanations.insert(
+                            m.id.clone(),
+                            RetrievalExplanation {
+                                retrieval_score,
+                                entity_boost,
+                                suppression_factor,
+                                tier_boost,
+                                type_weight,
+                                boosted_score: score,
+                                importance: m.importance(),
+                                ..Default::default()
+                            },
+                        );
                     }
 
                     (m, score, matched)
@@ -1291,6 +1709,23 @@ pub async fn proactive_context(
             // Sort by boosted score (highest first)
             enriched.sort_by(|a, b| b.1.total_cmp(&a.1));
 
+            // Per-type injection policy: drop disallowed types before computing the
+            // relative quality floor so excluded memories can't raise the bar
+            if !type_policy.is_unrestricted() {
+                enriched.retain(|(m, _, _)| {
+                    type_policy.is_allowed(&format!("{:?}", m.experience.experience_type))
+                });
+            }
+
+            // Agent scope: keep other agents' scratch context out of this agent's prompt
+            enriched.retain(|(m, _, _)| {
+                agent_scope.allows(
+                    m.agent_id.as_deref(),
+                    agent_id.as_deref(),
+                    parent_agent_id.as_deref(),
+                )
+            });
+
             // Drop results below minimum absolute score — don't pad with irrelevant filler
             // Also drop results that are < 30% of the top score (too weak relative to best)
             let top_score = enriched.first().map(|(_, s, _)| *s).unwrap_or(0.0);
@@ -1306,10 +1741,37 @@ pub async fn proactive_context(
                 }
             }
 
-            // Return top results with entity overlap annotation
-            enriched
+            // Diversity + recency re-ranking so near-duplicates don't fill the budget
+            let now = chrono::Utc::now();
+            let reranked = enriched.len() >= 2 && diversity.is_enabled();
+            let enriched = mmr_rerank(
+                enriched,
+                |(_, score, _)| *score,
+                |(m, _, _)| m.experience.content.as_str(),
+                |(m, _, _)| (now - m.created_at).num_minutes() as f32 / 60.0,
+                &diversity,
+            );
+
+            // Return top results with entity overlap annotation, honoring per-type caps
+            let (selected, overflow) = type_policy.partition(
+                enriched,
+                |(m, _, _)| format!("{:?}", m.experience.experience_type),
+                max_results,
+            );
+
+            // Relevant memories over budget are summarized rather than silently dropped
+            let digest = if want_digest {
+                let contents: Vec<String> = overflow
+                    .iter()
+                    .map(|(m, _, _)| sanitize_for_injection(&m.experience.content, sanitize_mode))
+                    .collect();
+                build_overflow_digest(&contents, OVERFLOW_DIGEST_MAX_CHARS)
+            } else {
+                None
+            };
+
+            let surfaced: Vec<ProactiveSurfacedMemory> = selected
                 .into_iter()
-                .take(max_results)
                 .map(|(m, score, matched)| {
                     let has_entity_match = !matched.is_empty();
                     let has_semantic_match = score > 0.0;
@@ -1322,9 +1784,18 @@ pub async fn proactive_context(
                     }
                     .to_string();
 
+                    let explanation = explanations.remove(&m.id).map(|mut e| {
+                        e.matched_terms = matched_query_terms(&query_words, &m.experience.content);
+                        if reranked {
+                            let age_hours = (now - m.created_at).num_minutes() as f32 / 60.0;
+                            e.recency_boost = diversity.recency_boost(age_hours);
+                        }
+                        e
+                    });
+
                     ProactiveSurfacedMemory {
                         id: m.id.0.to_string(),
-                        content: m.experience.content.clone(),
+                        content: sanitize_for_injection(&m.experience.content, sanitize_mode),
                         memory_type: format!("{:?}", m.experience.experience_type),
                         score,
                         importance: m.importance(),
@@ -1333,15 +1804,143 @@ pub async fn proactive_context(
                         tier: format!("{:?}", m.tier),
                         relevance_reason,
                         matched_entities: matched,
+                        shared_by: m.experience.metadata.get(TEAM_AUTHOR_KEY).cloned(),
+                        explanation,
                         embedding: m.experience.embeddings.clone().unwrap_or_default(),
                     }
                 })
-                .collect()
+                .collect();
+
+            // Spaced repetition: one slot goes to an important memory that is due
+            // for review, so long-term knowledge keeps getting revisited. It passes
+            // the same gates as recalled memories, and one injected on a recent
+            // turn isn't repeated as a review.
+            let mut surfaced = surfaced;
+            let mut review_id = None;
+            if resurface && max_results > 0 {
+                let exclude: std::collections::HashSet<MemoryId> = surfaced
+                    .iter()
+                    .filter_map(|m| uuid::Uuid::parse_str(&m.id).ok().map(MemoryId))
+                    .collect();
+                let review = memory_guard
+                    .due_reviews(REVIEW_PROACTIVE_CANDIDATES, &exclude)
+                    .into_iter()
+                    .find(|m| {
+                        !suppression.contains_key(&m.id.0.to_string())
+                            && type_policy
+                                .is_allowed(&format!("{:?}", m.experience.experience_type))
+                            && agent_scope.allows(
+                                m.agent_id.as_deref(),
+                                agent_id.as_deref(),
+                                parent_agent_id.as_deref(),
+                            )
+                    });
+                if let Some(m) = review {
+                    if surfaced.len() >= max_results {
+                        surfaced.pop();
+                    }
+                    review_id = Some(m.id.clone());
+                    surfaced.push(ProactiveSurfacedMemory {
+                        id: m.id.0.to_string(),
+                        content: sanitize_for_injection(&m.experience.content, sanitize_mode),
+                        memory_type: format!("{:?}", m.experience.experience_type),
+                        score: 0.0,
+                        importance: m.importance(),
+                        created_at: m.created_at.to_rfc3339(),
+                        tags: m.experience.tags.clone(),
+                        tier: format!("{:?}", m.tier),
+                        relevance_reason: "review".to_string(),
+                        matched_entities: Vec::new(),
+                        shared_by: m.experience.metadata.get(TEAM_AUTHOR_KEY).cloned(),
+                        explanation: explain.then(|| RetrievalExplanation {
+                            suppression_factor: 1.0,
+                            tier_boost: 1.0,
+                            type_weight: 1.0,
+                            importance: m.importance(),
+                            ..Default::default()
+                        }),
+                        embedding: m.experience.embeddings.clone().unwrap_or_default(),
+                    });
+                }
+            }
+            (surfaced, digest, review_id)
         })
         .await
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
     };
 
+    // 2.25. Daily injected-token budget: once a user's allowance is spent, stop injecting
+    let (memories, overflow_digest, budget_exhausted) = {
+        let mut memories = memories;
+        let mut overflow_digest = overflow_digest;
+        let mut budget_exhausted = false;
+
+        let token_budget = state.server_config.injection_daily_token_budget;
+        if token_budget > 0 {
+            let used = state
+                .injection_usage
+                .tokens_today(&req.user_id)
+                .map_err(AppError::Internal)?;
+            let mut remaining = token_budget.saturating_sub(used);
+            let mut keep = 0;
+            for m in &memories {
+                let cost = estimate_tokens(&m.content);
+                if cost > remaining {
+                    break;
+                }
+                remaining -= cost;
+                keep += 1;
+            }
+            let digest_fits = overflow_digest
+                .as_deref()
+                .is_none_or(|d| estimate_tokens(d) <= remaining);
+            if keep < memories.len() || !digest_fits {
+                budget_exhausted = true;
+                memories.truncate(keep);
+                if !digest_fits {
+                    overflow_digest = None;
+                }
+                metrics::INJECTION_BUDGET_EXHAUSTED_TOTAL.inc();
+                tracing::info!(
+                    user_id = %req.user_id,
+                    used,
+                    budget = token_budget,
+                    "proactive_context: daily injection token budget exhausted, trimmed surfaced memories"
+                );
+            }
+        }
+
+        let injected_tokens: usize = memories
+            .iter()
+            .map(|m| estimate_tokens(&m.content))
+            .sum::<usize>()
+            + overflow_digest.as_deref().map(estimate_tokens).unwrap_or(0);
+        if injected_tokens > 0 {
+            if let Err(e) = state.injection_usage.add(&req.user_id, injected_tokens) {
+                tracing::warn!("Failed to record injection usage: {}", e);
+            }
+            metrics::INJECTED_TOKENS_TOTAL.inc_by(injected_tokens as u64);
+        }
+
+        (memories, overflow_digest, budget_exhausted)
+    };
+
+    // A review only counts once the memory is actually injected
+    if let Some(review_id) = review_id.filter(|id| {
+        let id = id.0.to_string();
+        memories.iter().any(|m| m.id == id)
+    }) {
+        let memory = memory_system.clone();
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = memory
+                .read()
+                .mark_reviewed(std::slice::from_ref(&review_id))
+            {
+                tracing::debug!("Failed to record review of {}: {}", review_id.0, e);
+            }
+        });
+    }
+
     // 2.5. Record coactivation - fire-and-forget (doesn't affect response)
     // When memories are retrieved together, their graph edges get stronger (Hebbian learning)
     if memories.len() >= 2 {
@@ -1434,6 +2033,8 @@ pub async fn proactive_context(
                                     "assistant-response".to_string(),
                                     "auto-captured".to_string(),
                                 ],
+                                metadata: Provenance::new(ProvenanceSource::AutoIngest)
+                                    .to_metadata(),
                                 ..Default::default()
                             };
                             let _ = memory_guard.remember(experience, None);
@@ -1481,6 +2082,7 @@ pub async fn proactive_context(
     let ingested_memory_id = if should_ingest {
         let context = clean_context;
         let memory = memory_system.clone();
+        let agent_id = req.agent_id.clone();
         let (tx, rx) = tokio::sync::oneshot::channel();
         tokio::task::spawn(async move {
             let result = tokio::task::spawn_blocking(move || {
@@ -1494,9 +2096,19 @@ pub async fn proactive_context(
                         experience_type: segment.experience_type,
                         entities: segment.entities,
                         tags: vec!["auto-captured".to_string()],
+                        metadata: Provenance::new(ProvenanceSource::AutoIngest).to_metadata(),
                         ..Default::default()
                     };
-                    if let Ok(id) = memory_guard.remember(experience, None) {
+                    let stored = match &agent_id {
+                        Some(agent_id) => memory_guard.remember_with_agent(
+                            experience,
+                            None,
+                            Some(agent_id.clone()),
+                            None,
+                        ),
+                        None => memory_guard.remember(experience, None),
+                    };
+                    if let Ok(id) = stored {
                         if first_id.is_none() {
                             first_id = Some(id);
                         }
@@ -1851,25 +2463,47 @@ pub async fn proactive_context(
         ),
     );
 
-    // Track session event for memories surfaced
-    if memory_count > 0 {
-        let session_id = state.session_store.get_or_create_session(&req.user_id);
-        let memory_ids: Vec<String> = memories.iter().map(|m| m.id.clone()).collect();
-        let avg_score = if !memories.is_empty() {
-            memories.iter().map(|m| m.score).sum::<f32>() / memories.len() as f32
-        } else {
-            0.0
-        };
-        state.session_store.add_event(
-            &session_id,
-            SessionEvent::MemoriesSurfaced {
-                timestamp: chrono::Utc::now(),
-                query_preview: req.context.chars().take(100).collect(),
-                memory_count,
-                memory_ids,
-                avg_score,
-            },
-        );
+    state.log_access(
+        key.as_deref(),
+        &req.user_id,
+        "/api/proactive_context",
+        memories
+            .iter()
+            .map(|m| (m.id.clone(), Some(m.score)))
+            .collect(),
+    );
+
+    // Track session event for memories surfaced (every call counts as an injection turn);
+    // a session is only started once there is something to record
+    let memory_ids: Vec<String> = memories.iter().map(|m| m.id.clone()).collect();
+    let session_id = match session_id {
+        Some(id) => Some(id),
+        None if !memory_ids.is_empty() => {
+            Some(state.session_store.get_or_create_session(&req.user_id))
+        }
+        None => None,
+    };
+    if let Some(session_id) = session_id {
+        state
+            .session_store
+            .record_injection_turn(&session_id, &memory_ids);
+        if memory_count > 0 {
+            let avg_score = if !memories.is_empty() {
+                memories.iter().map(|m| m.score).sum::<f32>() / memories.len() as f32
+            } else {
+                0.0
+            };
+            state.session_store.add_event(
+                &session_id,
+                SessionEvent::MemoriesSurfaced {
+                    timestamp: chrono::Utc::now(),
+                    query_preview: req.context.chars().take(100).collect(),
+                    memory_count,
+                    memory_ids,
+                    avg_score,
+                },
+            );
+        }
     }
 
     let latency_ms = op_start.elapsed().as_secs_f64() * 1000.0;
@@ -1887,6 +2521,9 @@ pub async fn proactive_context(
         relevant_facts,
         latency_ms,
         detected_entities,
+        overflow_digest,
+        budget_exhausted,
+        time_filter,
     }))
 }
 
@@ -1897,9 +2534,10 @@ pub async fn proactive_context(
 /// POST /api/relevant - Proactive memory surfacing
 /// Returns relevant memories based on current context using entity matching
 /// and semantic similarity. Target latency: <30ms
-#[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
+#[tracing::instrument(skip(state, key), fields(user_id = %req.user_id))]
 pub async fn surface_relevant(
     State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
     Json(req): Json<relevance::RelevanceRequest>,
 ) -> Result<Json<relevance::RelevanceResponse>, AppError> {
     validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
@@ -1948,6 +2586,16 @@ pub async fn surface_relevant(
         count: Some(response.memories.len()),
         results: None,
     });
+    state.log_access(
+        key.as_deref(),
+        &req.user_id,
+        "/api/relevant",
+        response
+            .memories
+            .iter()
+            .map(|m| (m.id.clone(), Some(m.relevance_score)))
+            .collect(),
+    );
 
     Ok(Json(response))
 }
@@ -1960,9 +2608,10 @@ pub async fn surface_relevant(
 ///
 /// Use this when you want to provide feedback later on whether memories were helpful.
 /// Returns memory_ids that can be passed to /api/reinforce for Hebbian strengthening.
-#[tracing::instrument(skip(state), fields(user_id = %req.user_id, query = %req.query))]
+#[tracing::instrument(skip(state, key), fields(user_id = %req.user_id, query = %req.query))]
 pub async fn recall_tracked(
     State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
     Json(req): Json<TrackedRetrieveRequest>,
 ) -> Result<Json<TrackedRetrieveResponse>, AppError> {
     let op_start = std::time::Instant::now();
@@ -2020,6 +2669,7 @@ pub async fn recall_tracked(
                 created_at: m.created_at.to_rfc3339(),
                 score,
                 tier: format!("{:?}", m.tier),
+                shared_by: m.experience.metadata.get(TEAM_AUTHOR_KEY).cloned(),
             }
         })
         .collect();
@@ -2037,6 +2687,15 @@ pub async fn recall_tracked(
     metrics::MEMORY_RETRIEVE_RESULTS
         .with_label_values(&["tracked"])
         .observe(count as f64);
+    state.log_access(
+        key.as_deref(),
+        &req.user_id,
+        "/api/recall/tracked",
+        recall_memories
+            .iter()
+            .map(|m| (m.id.clone(), Some(m.score)))
+            .collect(),
+    );
 
     Ok(Json(TrackedRetrieveResponse {
         tracking_id,
@@ -2055,6 +2714,9 @@ pub async fn recall_tracked(
 /// - "helpful": Memories that helped → boost importance, strengthen associations
 /// - "misleading": Memories that misled → reduce importance, don't strengthen
 /// - "neutral": Just record access, mild strengthening
+///
+/// An optional `strength` (0.0-2.0, default 1.0) grades the outcome, so an
+/// emphatic "exactly right" moves importance further than a polite "thanks".
 #[tracing::instrument(skip(state), fields(user_id = %req.user_id, outcome = %req.outcome, count = req.ids.len()))]
 pub async fn reinforce_feedback(
     State(state): State<AppState>,
@@ -2096,6 +2758,14 @@ pub async fn reinforce_feedback(
         });
     }
 
+    let strength = req.strength.unwrap_or(1.0);
+    if !(0.0..=MAX_REINFORCEMENT_STRENGTH).contains(&strength) {
+        return Err(AppError::InvalidInput {
+            field: "strength".to_string(),
+            reason: format!("must be between 0.0 and {MAX_REINFORCEMENT_STRENGTH}"),
+        });
+    }
+
     let memory = state
         .get_user_memory(&req.user_id)
         .map_err(AppError::Internal)?;
@@ -2105,7 +2775,7 @@ pub async fn reinforce_feedback(
         let memory = memory.clone();
         tokio::task::spawn_blocking(move || {
             let memory_guard = memory.read();
-            memory_guard.reinforce_recall(&memory_ids, outcome)
+            memory_guard.reinforce_recall_weighted(&memory_ids, outcome, strength)
         })
         .await
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
@@ -2130,6 +2800,22 @@ pub async fn reinforce_feedback(
         .with_label_values(&[&outcome_label, &String::from("success")])
         .inc();
 
+    state.emit_event(MemoryEvent {
+        event_type: "REINFORCE".to_string(),
+        timestamp: chrono::Utc::now(),
+        user_id: req.user_id.clone(),
+        memory_id: (req.ids.len() == 1).then(|| req.ids[0].clone()),
+        content_preview: None,
+        memory_type: None,
+        importance: None,
+        count: Some(stats.memories_processed),
+        results: Some(serde_json::json!({
+            "outcome": outcome_label,
+            "strength": strength,
+            "ids": req.ids,
+        })),
+    });
+
     Ok(Json(ReinforceFeedbackResponse {
         memories_processed: stats.memories_processed,
         associations_strengthened: stats.associations_strengthened,
@@ -2138,6 +2824,78 @@ pub async fn reinforce_feedback(
     }))
 }
 
+/// GET /api/admin/memory_stats - How each memory fares in the feedback loop
+///
+/// Lists, per memory, how often it was surfaced, cited by the response, and
+/// reinforced positively or negatively, so operators can tell whether
+/// feedback is actually improving retrieval.
+#[tracing::instrument(skip(state), fields(user_id = %query.user_id))]
+pub async fn memory_effectiveness_stats(
+    State(state): State<AppState>,
+    Query(query): Query<MemoryStatsQuery>,
+) -> Result<Json<MemoryStatsResponse>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+
+    let memory = state
+        .get_user_memory(&query.user_id)
+        .map_err(AppError::Internal)?;
+    let feedback_store = state.feedback_store.clone();
+
+    let mut tracked: Vec<MemoryEffectiveness> = tokio::task::spawn_blocking(move || {
+        let memories = memory.read().get_all_memories()?;
+        let store = feedback_store.read();
+        Ok::<_, anyhow::Error>(
+            memories
+                .iter()
+                .filter_map(|m| {
+                    let momentum = store.load_momentum(&m.id)?;
+                    Some(MemoryEffectiveness {
+                        id: m.id.0.to_string(),
+                        content_preview: m.experience.content.chars().take(100).collect(),
+                        surfaced: momentum.surfaced_count,
+                        cited: momentum.cited_count,
+                        positive_signals: momentum.positive_count,
+                        negative_signals: momentum.negative_count,
+                        momentum: momentum.ema_with_decay(),
+                        last_signal_at: momentum.last_signal_at,
+                    })
+                })
+                .collect(),
+        )
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+    .map_err(AppError::Internal)?;
+
+    let tracked_memories = tracked.len();
+    let sum =
+        |f: fn(&MemoryEffectiveness) -> u32| -> u64 { tracked.iter().map(|m| f(m) as u64).sum() };
+    let total_surfaced = sum(|m| m.surfaced);
+    let total_cited = sum(|m| m.cited);
+    let total_positive = sum(|m| m.positive_signals);
+    let total_negative = sum(|m| m.negative_signals);
+    let avg_momentum = if tracked.is_empty() {
+        0.0
+    } else {
+        tracked.iter().map(|m| m.momentum).sum::<f32>() / tracked.len() as f32
+    };
+
+    tracked.sort_by(|a, b| b.surfaced.cmp(&a.surfaced).then(b.cited.cmp(&a.cited)));
+    tracked.truncate(limit);
+
+    Ok(Json(MemoryStatsResponse {
+        user_id: query.user_id,
+        tracked_memories,
+        total_surfaced,
+        total_cited,
+        total_positive,
+        total_negative,
+        avg_momentum,
+        memories: tracked,
+    }))
+}
+
 // =============================================================================
 // RECALL BY TAGS HANDLER
 // =============================================================================
@@ -2145,9 +2903,10 @@ pub async fn reinforce_feedback(
 /// POST /api/recall/tags - Recall memories by tags
 ///
 /// Returns memories matching ANY of the provided tags.
-#[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
+#[tracing::instrument(skip(state, key), fields(user_id = %req.user_id))]
 pub async fn recall_by_tags(
     State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
     Json(req): Json<RecallByTagsRequest>,
 ) -> Result<Json<RetrieveResponse>, AppError> {
     validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
@@ -2175,6 +2934,15 @@ pub async fn recall_by_tags(
         .recall_by_tags(&req.tags, limit)
         .map_err(AppError::Internal)?;
     let count = raw_memories.len();
+    state.log_access(
+        key.as_deref(),
+        &req.user_id,
+        "/api/recall/tags",
+        raw_memories
+            .iter()
+            .map(|m| (m.id.0.to_string(), None))
+            .collect(),
+    );
 
     // Serialize memories to JSON for response
     let memories: Vec<serde_json::Value> = raw_memories
@@ -2210,9 +2978,10 @@ pub async fn recall_by_tags(
 /// POST /api/recall/date - Recall memories by date range
 ///
 /// Returns memories created within the specified date range.
-#[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
+#[tracing::instrument(skip(state, key), fields(user_id = %req.user_id))]
 pub async fn recall_by_date(
     State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
     Json(req): Json<RecallByDateRequest>,
 ) -> Result<Json<RetrieveResponse>, AppError> {
     validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
@@ -2240,6 +3009,15 @@ pub async fn recall_by_date(
         .recall_by_date(req.start, req.end, limit)
         .map_err(AppError::Internal)?;
     let count = raw_memories.len();
+    state.log_access(
+        key.as_deref(),
+        &req.user_id,
+        "/api/recall/date",
+        raw_memories
+            .iter()
+            .map(|m| (m.id.0.to_string(), None))
+            .collect(),
+    );
 
     // Serialize memories to JSON for response
     let memories: Vec<serde_json::Value> = raw_memories
@@ -2271,3 +3049,218 @@ pub async fn recall_by_date(
 
     Ok(Json(RetrieveResponse { memories, count }))
 }
+
+// =============================================================================
+// RECALL RELATED HANDLER
+// =============================================================================
+
+/// POST /api/recall/related - Graph-augmented recall from a seed
+///
+/// Walks the knowledge graph from the seed's entities (bounded by depth and
+/// width) and returns the memories attached to the entities reached, ranked by
+/// graph proximity combined with relevance to the seed. Useful for expanding a
+/// recall result one hop: pass each result's id as `memory_id` with
+/// `max_depth: 0`.
+#[tracing::instrument(skip(state, key), fields(user_id = %req.user_id))]
+pub async fn recall_related(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Json(req): Json<RecallRelatedRequest>,
+) -> Result<Json<RecallRelatedResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let limit = req.limit.unwrap_or(10);
+    validation::validate_max_results(limit).map_validation_err("limit")?;
+
+    let seed_memory = match (&req.memory_id, &req.entity) {
+        (Some(id), None) => {
+            Some(
+                uuid::Uuid::parse_str(id).map_err(|_| AppError::InvalidInput {
+                    field: "memory_id".to_string(),
+                    reason: "Invalid UUID format".to_string(),
+                })?,
+            )
+        }
+        (None, Some(_)) => None,
+        _ => {
+            return Err(AppError::InvalidInput {
+                field: "memory_id".to_string(),
+                reason: "Provide exactly one of memory_id or entity".to_string(),
+            })
+        }
+    };
+    let max_depth = req.max_depth.unwrap_or(2).min(RELATED_MAX_DEPTH);
+    let width = req.max_width.unwrap_or(RELATED_DEFAULT_WIDTH).max(1);
+
+    let memory = state
+        .get_user_memory(&req.user_id)
+        .map_err(AppError::Internal)?;
+    let graph = state
+        .get_user_graph(&req.user_id)
+        .map_err(AppError::Internal)?;
+    let entity_name = req.entity.clone();
+
+    let (memories, seed_entities) = tokio::task::spawn_blocking(move || {
+        let memory_guard = memory.read();
+        let graph_guard = graph.read();
+
+        // 1. Seed entities (and the seed memory's embedding for relevance)
+        let mut seed_embedding: Option<Vec<f32>> = None;
+        let seed_entity_ids: Vec<uuid::Uuid> = if let Some(seed) = seed_memory {
+            let seed_mem = memory_guard
+                .get_memory(&MemoryId(seed))
+                .map_err(|_| AppError::MemoryNotFound(seed.to_string()))?;
+            seed_embedding = seed_mem.experience.embeddings.clone();
+            graph_guard
+                .get_episode(&seed)
+                .map_err(AppError::Internal)?
+                .map(|episode| episode.entity_refs)
+                .unwrap_or_default()
+        } else {
+            let name = entity_name.unwrap_or_default();
+            let entity = graph_guard
+                .find_entity_by_name(&name)
+                .map_err(AppError::Internal)?
+                .ok_or_else(|| AppError::MemoryNotFound(format!("Entity not found: {name}")))?;
+            vec![entity.uuid]
+        };
+
+        // 2. Walk: keep the closest path to each entity, `width` entities per hop
+        let mut reached: std::collections::HashMap<uuid::Uuid, (usize, f32, EntityNode)> =
+            std::collections::HashMap::new();
+        let mut seed_entities = Vec::new();
+        for seed in seed_entity_ids.iter().take(width) {
+            let traversal = graph_guard
+                .traverse_from_entity(seed, max_depth)
+                .map_err(AppError::Internal)?;
+            for t in traversal.entities {
+                if t.hop_distance == 0 {
+                    seed_entities.push(t.entity.name.clone());
+                }
+                match reached.get(&t.entity.uuid) {
+                    Some((_, decay, _)) if *decay >= t.decay_factor => {}
+                    _ => {
+                        reached.insert(t.entity.uuid, (t.hop_distance, t.decay_factor, t.entity));
+                    }
+                }
+            }
+        }
+        let mut by_hop: Vec<_> = reached.into_values().collect();
+        by_hop.sort_by(|a, b| {
+            a.0.cmp(&b.0)
+                .then((b.1 * b.2.salience).total_cmp(&(a.1 * a.2.salience)))
+        });
+        let mut per_hop: std::collections::HashMap<usize, usize> = std::collections::HashMap::new();
+        by_hop.retain(|(hop, _, _)| {
+            let kept = per_hop.entry(*hop).or_default();
+            *kept += 1;
+            *kept <= width
+        });
+
+        // 3. Memories attached to reached entities, best path per memory
+        let mut candidates: std::collections::HashMap<uuid::Uuid, (usize, f32, String)> =
+            std::collections::HashMap::new();
+        for (hop, decay, entity) in &by_hop {
+            let mut episodes = graph_guard
+                .get_episodes_by_entity(&entity.uuid)
+                .map_err(AppError::Internal)?;
+            episodes.sort_by(|a, b| b.valid_at.cmp(&a.valid_at));
+            for episode in episodes.into_iter().take(width) {
+                if Some(episode.uuid) == seed_memory {
+                    continue;
+                }
+                match candidates.get(&episode.uuid) {
+                    Some((_, best, _)) if *best >= *decay => {}
+                    _ => {
+                        candidates.insert(episode.uuid, (*hop, *decay, entity.name.clone()));
+                    }
+                }
+            }
+        }
+
+        // 4. Rank by graph proximity + relevance to the seed
+        let mut related: Vec<(f32, RelatedMemory)> = candidates
+            .into_iter()
+            .filter_map(|(id, (hop, graph_score, via))| {
+                let m = memory_guard.get_memory(&MemoryId(id)).ok()?;
+                if m.is_forgotten() || memory_guard.is_suppressed(&m) {
+                    return None;
+                }
+                let relevance = match (&seed_embedding, &m.experience.embeddings) {
+                    (Some(seed), Some(emb)) => {
+                        crate::similarity::cosine_similarity(seed, emb).clamp(0.0, 1.0)
+                    }
+                    _ => m.importance(),
+                };
+                let score =
+                    RELATED_GRAPH_WEIGHT * graph_score + RELATED_RELEVANCE_WEIGHT * relevance;
+                Some((
+                    score,
+                    RelatedMemory {
+                        memory: RecallMemory {
+                            id: m.id.0.to_string(),
+                            experience: RecallExperience {
+                                content: m.experience.content.clone(),
+                                memory_type: Some(format!("{:?}", m.experience.experience_type)),
+                                tags: m.experience.entities.clone(),
+                            },
+                            importance: m.importance(),
+                            created_at: m.created_at.to_rfc3339(),
+                            score,
+                            tier: format!("{:?}", m.tier),
+                            shared_by: m.experience.metadata.get(TEAM_AUTHOR_KEY).cloned(),
+                        },
+                        hop_distance: hop,
+                        via_entity: via,
+                        graph_score,
+                        relevance,
+                    },
+                ))
+            })
+            .collect();
+        related.sort_by(|a, b| b.0.total_cmp(&a.0));
+        related.truncate(limit);
+
+        Ok::<_, AppError>((
+            related.into_iter().map(|(_, r)| r).collect::<Vec<_>>(),
+            seed_entities,
+        ))
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))??;
+
+    let count = memories.len();
+    info!(
+        "🕸️ Recall related: user={}, seed_entities={}, found={}",
+        req.user_id,
+        seed_entities.len(),
+        count
+    );
+
+    state.emit_event(MemoryEvent {
+        event_type: "RETRIEVE".to_string(),
+        timestamp: chrono::Utc::now(),
+        user_id: req.user_id.clone(),
+        memory_id: req.memory_id.clone(),
+        content_preview: Some(format!("related: {}", seed_entities.join(", "))),
+        memory_type: Some("related".to_string()),
+        importance: None,
+        count: Some(count),
+        results: None,
+    });
+    state.log_access(
+        key.as_deref(),
+        &req.user_id,
+        "/api/recall/related",
+        memories
+            .iter()
+            .map(|m| (m.memory.id.clone(), Some(m.memory.score)))
+            .collect(),
+    );
+
+    Ok(Json(RecallRelatedResponse {
+        memories,
+        count,
+        seed_entities,
+    }))
+}
@@ -2,21 +2,27 @@
 //!
 //! Core handlers for storing memories: remember, batch_remember, upsert.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use axum::{extract::State, response::Json};
+use axum::{extract::State, http::HeaderMap, response::Json, Extension};
 
 use super::health::AppState;
+use super::memory_types::resolve_memory_type;
+use super::teams::{resolve_team, team_namespace, Visibility, TEAM_AUTHOR_KEY, TEAM_HEADER};
 use super::types::MemoryEvent;
 use crate::errors::{AppError, ValidationErrorExt};
+use crate::idempotency::Claim;
 use crate::memory::{
+    encoding_filters::FilterInput,
+    type_registry::ResolvedType,
     types::{
-        ChangeType, ContextId, EmotionalContext, EpisodeContext, NerEntityRecord, RichContext,
-        SourceContext, SourceType,
+        ChangeType, ContextId, EmotionalContext, EpisodeContext, NerEntityRecord, Provenance,
+        ProvenanceSource, RichContext, SourceContext, SourceType, PINNED_KEY,
     },
-    Experience, ExperienceType, SessionEvent,
+    Experience, SessionEvent,
 };
 use crate::metrics;
+use crate::middleware::RequestId;
 use crate::validation;
 
 // =============================================================================
@@ -62,6 +68,32 @@ pub struct RememberRequest {
     /// Use this to create memory trees (e.g., "71-research" -> "algebraic" -> "21×27≡-1")
     #[serde(default)]
     pub parent_id: Option<String>,
+    /// Explicit priority ("critical", "high", "medium") - raises computed importance,
+    /// e.g. for memories the user asked to keep
+    #[serde(default)]
+    pub priority: Option<String>,
+    /// Client-computed key for retry-safe writes: a repeat of the same key for the
+    /// same user returns the original memory instead of storing a duplicate
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    /// Tool that produced this interaction, matched by `tool:` encoding filters
+    #[serde(default)]
+    pub tool_name: Option<String>,
+    /// Model that produced this interaction, matched by `model:` encoding filters
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Where this memory came from ("manual", "cortex", "integration", ...), default manual
+    #[serde(default)]
+    pub source: Option<String>,
+    /// "private" (default) or "team" to share with the caller's team
+    #[serde(default)]
+    pub visibility: Visibility,
+    /// Team for `visibility: "team"` (falls back to the X-Shodh-Team header)
+    #[serde(default)]
+    pub team_id: Option<String>,
+    /// Never evict this memory when the user's quota is exceeded
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 /// Remember response
@@ -69,8 +101,22 @@ pub struct RememberRequest {
 pub struct RememberResponse {
     pub id: String,
     pub success: bool,
+    /// True when an earlier write matched (same idempotency key or identical
+    /// content) and nothing new was stored; `id` is the earlier memory. On a
+    /// content match the request's tags are added to it, but its memory type
+    /// and metadata stay as first stored.
+    pub deduplicated: bool,
+    /// True when an encoding filter excluded the interaction; nothing was
+    /// stored and `id` is empty
+    pub filtered: bool,
+    /// Team namespace the memory was shared to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<String>,
 }
 
+/// Maximum accepted idempotency key length
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 256;
+
 /// Batch remember request
 #[derive(Debug, serde::Deserialize)]
 pub struct BatchRememberRequest {
@@ -122,8 +168,23 @@ pub struct BatchMemoryItem {
     /// Parent memory ID for hierarchical organization
     #[serde(default)]
     pub parent_id: Option<String>,
+    /// Client-supplied key so a re-flushed batch doesn't store this item twice
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    /// Tool that produced this interaction, matched by `tool:` encoding filters
+    #[serde(default)]
+    pub tool_name: Option<String>,
+    /// Model that produced this interaction, matched by `model:` encoding filters
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Where this memory came from ("manual", "cortex", "integration", ...), default manual
+    #[serde(default)]
+    pub source: Option<String>,
 }
 
+/// Batch item ready to store: (request index, experience, created_at)
+type PendingBatchItem = (usize, Experience, Option<chrono::DateTime<chrono::Utc>>);
+
 /// Error detail for batch item
 #[derive(Debug, serde::Serialize)]
 pub struct BatchErrorItem {
@@ -138,6 +199,11 @@ pub struct BatchRememberResponse {
     pub failed: usize,
     pub memory_ids: Vec<String>,
     pub errors: Vec<BatchErrorItem>,
+    /// Items skipped because their idempotency key matched an earlier write
+    /// (their existing IDs are included in `memory_ids`)
+    pub deduplicated: usize,
+    /// Items an encoding filter excluded; not stored and not counted as failed
+    pub filtered: usize,
 }
 
 /// Upsert request - create or update memory
@@ -169,32 +235,46 @@ pub struct UpsertResponse {
     pub success: bool,
     pub was_update: bool,
     pub version: u32,
+    /// True when an encoding filter excluded the content; nothing was
+    /// stored or updated and `id` is empty
+    pub filtered: bool,
 }
 
 // =============================================================================
 // HELPER FUNCTIONS
 // =============================================================================
 
-/// Parse memory type from string
-pub fn parse_experience_type(s: Option<&String>) -> ExperienceType {
-    s.and_then(|s| match s.to_lowercase().as_str() {
-        "observation" => Some(ExperienceType::Observation),
-        "decision" => Some(ExperienceType::Decision),
-        "learning" => Some(ExperienceType::Learning),
-        "error" => Some(ExperienceType::Error),
-        "discovery" => Some(ExperienceType::Discovery),
-        "pattern" => Some(ExperienceType::Pattern),
-        "context" => Some(ExperienceType::Context),
-        "task" => Some(ExperienceType::Task),
-        "codeedit" | "code_edit" => Some(ExperienceType::CodeEdit),
-        "fileaccess" | "file_access" => Some(ExperienceType::FileAccess),
-        "search" => Some(ExperienceType::Search),
-        "command" => Some(ExperienceType::Command),
-        "conversation" => Some(ExperienceType::Conversation),
-        "intention" => Some(ExperienceType::Intention),
-        _ => None,
+/// Whether the user-defined encoding filters exclude an interaction.
+///
+/// Filtered writes are skipped, not rejected: hooks send every interaction
+/// and a filter match is expected, not a client error.
+fn is_filtered(state: &AppState, input: &FilterInput) -> bool {
+    match state.encoding_filters.excludes(input) {
+        Some(rule) => {
+            tracing::debug!(rule = %rule, "write excluded by encoding filter");
+            true
+        }
+        None => false,
+    }
+}
+
+/// Provenance for a client write: its `source`, request ID, model and tool
+pub fn request_provenance(
+    source: Option<&str>,
+    request_id: Option<&RequestId>,
+    model: Option<&String>,
+    tool_name: Option<&String>,
+) -> Result<Provenance, String> {
+    let source = match source {
+        Some(s) => ProvenanceSource::parse(s).ok_or_else(|| format!("unknown source '{s}'"))?,
+        None => ProvenanceSource::Manual,
+    };
+    Ok(Provenance {
+        source,
+        request_id: request_id.map(|r| r.0.clone()),
+        model: model.cloned(),
+        tools: tool_name.cloned().into_iter().collect(),
     })
-    .unwrap_or(ExperienceType::Observation)
 }
 
 /// Parse source type from string
@@ -292,14 +372,121 @@ pub fn build_rich_context(
 #[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
 pub async fn remember(
     State(state): State<AppState>,
-    Json(req): Json<RememberRequest>,
+    request_id: Option<Extension<RequestId>>,
+    headers: HeaderMap,
+    Json(mut req): Json<RememberRequest>,
 ) -> Result<Json<RememberResponse>, AppError> {
     let op_start = std::time::Instant::now();
 
     validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
     validation::validate_content(&req.content, false).map_validation_err("content")?;
+    let provenance = request_provenance(
+        req.source.as_deref(),
+        request_id.as_deref(),
+        req.model.as_ref(),
+        req.tool_name.as_ref(),
+    )
+    .map_err(|reason| AppError::InvalidInput {
+        field: "source".to_string(),
+        reason,
+    })?;
+    let memory_type = resolve_memory_type(&state, req.memory_type.as_deref())?;
+    let team_id = match req.visibility {
+        Visibility::Private => None,
+        Visibility::Team => Some(resolve_team(&headers, req.team_id.as_deref())?.ok_or_else(
+            || AppError::InvalidInput {
+                field: "team_id".to_string(),
+                reason: format!(
+                    "required for team visibility (set team_id or the {TEAM_HEADER} header)"
+                ),
+            },
+        )?),
+    };
+    // Team memories are stored in (and graphed into) the team's namespace
+    let store_id = team_id
+        .as_deref()
+        .map(team_namespace)
+        .unwrap_or_else(|| req.user_id.clone());
+    if is_filtered(
+        &state,
+        &FilterInput {
+            content: &req.content,
+            tool: req.tool_name.as_deref(),
+            model: req.model.as_deref(),
+        },
+    ) {
+        return Ok(Json(RememberResponse {
+            id: String::new(),
+            success: true,
+            deduplicated: false,
+            filtered: true,
+            team_id,
+        }));
+    }
+    state.sanitize_content(&mut req.content, &mut req.tags);
+
+    // Claimed before the write so concurrent retries can't both store
+    let idempotency_claim = match req.idempotency_key.as_deref() {
+        Some(key) if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN => {
+            return Err(AppError::InvalidInput {
+                field: "idempotency_key".to_string(),
+                reason: format!("must be 1-{MAX_IDEMPOTENCY_KEY_LEN} characters"),
+            });
+        }
+        Some(key) => match state
+            .remember_idempotency
+            .claim(&req.user_id, key)
+            .map_err(AppError::Internal)?
+        {
+            Claim::Acquired(claim) => Some(claim),
+            Claim::Existing(existing_id) => {
+                tracing::debug!(memory_id = %existing_id, "remember: idempotency key hit, skipping store");
+                return Ok(Json(RememberResponse {
+                    id: existing_id,
+                    success: true,
+                    deduplicated: true,
+                    filtered: false,
+                    team_id,
+                }));
+            }
+            Claim::InProgress => return Err(AppError::IdempotencyKeyInUse(key.to_string())),
+        },
+        None => None,
+    };
 
-    let experience_type = parse_experience_type(req.memory_type.as_ref());
+    let memory = state
+        .get_user_memory(&store_id)
+        .map_err(AppError::Internal)?;
+
+    // Identical content already stored (retries, repeated interactions):
+    // reinforce the existing memory instead of storing a copy
+    let existing_id = {
+        let memory = memory.clone();
+        let content = req.content.clone();
+        let tags = req.tags.clone();
+        tokio::task::spawn_blocking(move || memory.read().reinforce_duplicate(&content, &tags))
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+            .map_err(AppError::Internal)?
+    };
+    if let Some(existing_id) = existing_id {
+        let existing_id = existing_id.0.to_string();
+        tracing::debug!(memory_id = %existing_id, "remember: duplicate content, reinforced existing memory");
+        if let Some(claim) = idempotency_claim {
+            if let Err(e) = claim.complete(&existing_id) {
+                tracing::warn!("Failed to record idempotency key: {}", e);
+            }
+        }
+        return Ok(Json(RememberResponse {
+            id: existing_id,
+            success: true,
+            deduplicated: true,
+            filtered: false,
+            team_id,
+        }));
+    }
+
+    let experience_type = memory_type.base.clone();
 
     // PERF: Run NER and YAKE extraction in parallel using spawn_blocking
     // Both are CPU-bound and independent - parallelization reduces latency by ~40%
@@ -391,19 +578,32 @@ pub async fn remember(
         req.preceding_memory_id.clone(),
     );
 
-    let experience = Experience {
+    let mut experience = Experience {
         content: req.content.clone(),
         experience_type,
         entities: merged_entities.clone(),
         tags: merged_entities,
         context,
         ner_entities,
+        metadata: req
+            .priority
+            .iter()
+            .map(|p| ("priority".to_string(), p.to_lowercase()))
+            .collect(),
         ..Default::default()
     };
-
-    let memory = state
-        .get_user_memory(&req.user_id)
-        .map_err(AppError::Internal)?;
+    provenance.write_to(&mut experience.metadata);
+    memory_type.write_to(&mut experience.metadata);
+    if req.pinned {
+        experience
+            .metadata
+            .insert(PINNED_KEY.to_string(), "true".to_string());
+    }
+    if team_id.is_some() {
+        experience
+            .metadata
+            .insert(TEAM_AUTHOR_KEY.to_string(), req.user_id.clone());
+    }
 
     let memory_id = {
         let memory = memory.clone();
@@ -425,8 +625,12 @@ pub async fn remember(
         .map_err(AppError::Internal)?
     };
 
+    if let Some(ref run_id) = req.run_id {
+        state.touch_run(&req.user_id, run_id);
+    }
+
     // Build episodic graph: entities + episode + relationships for multi-hop retrieval
-    if let Err(e) = state.process_experience_into_graph(&req.user_id, &experience, &memory_id) {
+    if let Err(e) = state.process_experience_into_graph(&store_id, &experience, &memory_id) {
         tracing::debug!("Graph processing failed (non-fatal): {}", e);
     }
 
@@ -469,7 +673,7 @@ pub async fn remember(
     // E.g., "planning camping next month" → resolves "next month" to absolute date
     {
         let memory = memory.clone();
-        let user_id = req.user_id.clone();
+        let user_id = store_id.clone();
         let content = req.content.clone();
         let entities = experience.entities.clone();
         let created_at = req.created_at.unwrap_or_else(chrono::Utc::now);
@@ -526,9 +730,18 @@ pub async fn remember(
         results: None,
     });
 
+    if let Some(claim) = idempotency_claim {
+        if let Err(e) = claim.complete(&memory_id.0.to_string()) {
+            tracing::warn!("Failed to record idempotency key: {}", e);
+        }
+    }
+
     Ok(Json(RememberResponse {
         id: memory_id.0.to_string(),
         success: true,
+        deduplicated: false,
+        filtered: false,
+        team_id,
     }))
 }
 
@@ -536,6 +749,7 @@ pub async fn remember(
 #[tracing::instrument(skip(state), fields(user_id = %req.user_id, batch_size = req.memories.len()))]
 pub async fn batch_remember(
     State(state): State<AppState>,
+    request_id: Option<Extension<RequestId>>,
     Json(req): Json<BatchRememberRequest>,
 ) -> Result<Json<BatchRememberResponse>, AppError> {
     let op_start = std::time::Instant::now();
@@ -549,6 +763,8 @@ pub async fn batch_remember(
             failed: 0,
             memory_ids: vec![],
             errors: vec![],
+            deduplicated: 0,
+            filtered: 0,
         }));
     }
 
@@ -561,10 +777,13 @@ pub async fn batch_remember(
 
     // Pre-validate all items
     let mut validation_errors: Vec<BatchErrorItem> = Vec::new();
-    let mut valid_items: Vec<(usize, BatchMemoryItem)> = Vec::new();
+    let mut valid_items: Vec<(usize, BatchMemoryItem, Provenance, ResolvedType)> = Vec::new();
+    let mut idempotency_claims = HashMap::new();
+    let mut deduplicated_ids: Vec<String> = Vec::new();
+    let mut filtered = 0;
 
     let mut seen_content: HashSet<u64> = HashSet::new();
-    for (index, item) in req.memories.into_iter().enumerate() {
+    for (index, mut item) in req.memories.into_iter().enumerate() {
         if let Err(e) = validation::validate_content(&item.content, false) {
             validation_errors.push(BatchErrorItem {
                 index,
@@ -572,6 +791,75 @@ pub async fn batch_remember(
             });
             continue;
         }
+        if is_filtered(
+            &state,
+            &FilterInput {
+                content: &item.content,
+                tool: item.tool_name.as_deref(),
+                model: item.model.as_deref(),
+            },
+        ) {
+            filtered += 1;
+            continue;
+        }
+        let provenance = match request_provenance(
+            item.source.as_deref(),
+            request_id.as_deref(),
+            item.model.as_ref(),
+            item.tool_name.as_ref(),
+        ) {
+            Ok(provenance) => provenance,
+            Err(error) => {
+                validation_errors.push(BatchErrorItem { index, error });
+                continue;
+            }
+        };
+        let memory_type = match resolve_memory_type(&state, item.memory_type.as_deref()) {
+            Ok(memory_type) => memory_type,
+            Err(e) => {
+                validation_errors.push(BatchErrorItem {
+                    index,
+                    error: e.to_string(),
+                });
+                continue;
+            }
+        };
+        state.sanitize_content(&mut item.content, &mut item.tags);
+        let idempotency_claim = match item.idempotency_key.as_deref() {
+            Some(key) if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN => {
+                validation_errors.push(BatchErrorItem {
+                    index,
+                    error: format!(
+                        "idempotency_key must be 1-{MAX_IDEMPOTENCY_KEY_LEN} characters"
+                    ),
+                });
+                continue;
+            }
+            Some(key) => match state
+                .remember_idempotency
+                .claim(&req.user_id, key)
+                .map_err(AppError::Internal)?
+            {
+                Claim::Acquired(claim) => Some(claim),
+                Claim::Existing(existing_id) => {
+                    tracing::debug!(
+                        batch_index = index,
+                        memory_id = %existing_id,
+                        "batch_remember: idempotency key hit, skipping store"
+                    );
+                    deduplicated_ids.push(existing_id);
+                    continue;
+                }
+                Claim::InProgress => {
+                    validation_errors.push(BatchErrorItem {
+                        index,
+                        error: AppError::IdempotencyKeyInUse(key.to_string()).message(),
+                    });
+                    continue;
+                }
+            },
+            None => None,
+        };
         // Deduplicate within the batch: skip items with identical content
         let content_hash = {
             use std::hash::{Hash, Hasher};
@@ -590,7 +878,10 @@ pub async fn batch_remember(
             });
             continue;
         }
-        valid_items.push((index, item));
+        if let Some(claim) = idempotency_claim {
+            idempotency_claims.insert(index, claim);
+        }
+        valid_items.push((index, item, provenance, memory_type));
     }
 
     let memory = state
@@ -602,14 +893,10 @@ pub async fn batch_remember(
     let keyword_extractor = state.get_keyword_extractor();
 
     // Build experiences
-    let mut experiences_with_index: Vec<(
-        usize,
-        Experience,
-        Option<chrono::DateTime<chrono::Utc>>,
-    )> = Vec::with_capacity(valid_items.len());
+    let mut experiences_with_index: Vec<PendingBatchItem> = Vec::with_capacity(valid_items.len());
 
-    for (index, item) in valid_items {
-        let experience_type = parse_experience_type(item.memory_type.as_ref());
+    for (index, item, provenance, memory_type) in valid_items {
+        let experience_type = memory_type.base.clone();
 
         let (merged_entities, ner_records) = if extract_entities {
             // NER for named entities (Person, Org, Location, Misc)
@@ -670,7 +957,7 @@ pub async fn batch_remember(
             item.preceding_memory_id.clone(),
         );
 
-        let experience = Experience {
+        let mut experience = Experience {
             content: item.content,
             experience_type,
             entities: merged_entities.clone(),
@@ -679,6 +966,8 @@ pub async fn batch_remember(
             ner_entities: ner_records,
             ..Default::default()
         };
+        provenance.write_to(&mut experience.metadata);
+        memory_type.write_to(&mut experience.metadata);
 
         experiences_with_index.push((index, experience, item.created_at));
     }
@@ -712,8 +1001,20 @@ pub async fn batch_remember(
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
     };
 
-    let memory_ids: Vec<String> = memory_results.iter().map(|(_, id, _)| id.clone()).collect();
+    let mut memory_ids: Vec<String> = memory_results.iter().map(|(_, id, _)| id.clone()).collect();
     let created = memory_ids.len();
+    let deduplicated = deduplicated_ids.len();
+    memory_ids.extend(deduplicated_ids);
+
+    // Claims of items that failed to store are released on drop
+    for (index, id, _) in &memory_results {
+        if let Some(claim) = idempotency_claims.remove(index) {
+            if let Err(e) = claim.complete(id) {
+                tracing::warn!("Failed to record idempotency key: {}", e);
+            }
+        }
+    }
+    drop(idempotency_claims);
 
     let mut all_errors = validation_errors;
     all_errors.extend(storage_errors);
@@ -722,6 +1023,17 @@ pub async fn batch_remember(
 
     // Build episodic graph for each stored memory (enables multi-hop retrieval)
     for (_, id_str, experience) in &memory_results {
+        state.emit_event(MemoryEvent {
+            event_type: "CREATE".to_string(),
+            timestamp: chrono::Utc::now(),
+            user_id: req.user_id.clone(),
+            memory_id: Some(id_str.clone()),
+            content_preview: Some(experience.content.chars().take(100).collect()),
+            memory_type: Some(format!("{:?}", experience.experience_type)),
+            importance: None,
+            count: None,
+            results: None,
+        });
         if let Ok(uuid) = uuid::Uuid::parse_str(id_str) {
             let memory_id = crate::memory::MemoryId(uuid);
             if let Err(e) =
@@ -752,6 +1064,8 @@ pub async fn batch_remember(
         failed,
         memory_ids,
         errors: all_errors,
+        deduplicated,
+        filtered,
     }))
 }
 
@@ -759,12 +1073,28 @@ pub async fn batch_remember(
 #[tracing::instrument(skip(state), fields(user_id = %req.user_id, external_id = %req.external_id))]
 pub async fn upsert_memory(
     State(state): State<AppState>,
-    Json(req): Json<UpsertRequest>,
+    Json(mut req): Json<UpsertRequest>,
 ) -> Result<Json<UpsertResponse>, AppError> {
     let op_start = std::time::Instant::now();
 
     validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
     validation::validate_content(&req.content, false).map_validation_err("content")?;
+    if is_filtered(
+        &state,
+        &FilterInput {
+            content: &req.content,
+            ..Default::default()
+        },
+    ) {
+        return Ok(Json(UpsertResponse {
+            id: String::new(),
+            success: true,
+            was_update: false,
+            version: 0,
+            filtered: true,
+        }));
+    }
+    state.sanitize_content(&mut req.content, &mut req.tags);
 
     if req.external_id.is_empty() {
         return Err(AppError::InvalidInput {
@@ -773,7 +1103,8 @@ pub async fn upsert_memory(
         });
     }
 
-    let experience_type = parse_experience_type(req.memory_type.as_ref());
+    let memory_type = resolve_memory_type(&state, req.memory_type.as_deref())?;
+    let experience_type = memory_type.base.clone();
 
     let change_type = match req.change_type.to_lowercase().as_str() {
         "created" => ChangeType::Created,
@@ -826,7 +1157,7 @@ pub async fn upsert_memory(
         merged_entities.truncate(validation::MAX_ENTITIES_PER_MEMORY);
     }
 
-    let experience = Experience {
+    let mut experience = Experience {
         content: req.content.clone(),
         experience_type,
         entities: merged_entities.clone(),
@@ -834,6 +1165,7 @@ pub async fn upsert_memory(
         ner_entities,
         ..Default::default()
     };
+    memory_type.write_to(&mut experience.metadata);
 
     let memory_system = state
         .get_user_memory(&req.user_id)
@@ -930,5 +1262,6 @@ pub async fn upsert_memory(
         success: true,
         was_update,
         version,
+        filtered: false,
     }))
 }
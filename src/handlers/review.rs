@@ -0,0 +1,147 @@
+//! Spaced-Repetition Review Handlers
+//!
+//! Explicit review queue for clients that want to revisit important memories
+//! on their own schedule instead of (or in addition to) having
+//! proactive_context resurface them.
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::state::MultiUserMemoryManager;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::memory::types::MemoryId;
+use crate::validation;
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// Largest review queue returned by GET /api/review
+const MAX_REVIEW_LIMIT: usize = 200;
+
+/// Query parameters for GET /api/review
+#[derive(Debug, Deserialize)]
+pub struct ReviewQuery {
+    pub user_id: String,
+    #[serde(default = "default_review_limit")]
+    pub limit: usize,
+}
+
+fn default_review_limit() -> usize {
+    20
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReviewQueueItem {
+    pub id: String,
+    pub content: String,
+    pub memory_type: String,
+    pub importance: f32,
+    /// Retention strength on the forgetting curve (lower = closer to forgotten)
+    pub strength: f32,
+    /// Current review interval in days (doubles with each review)
+    pub interval_days: f64,
+    pub due_at: DateTime<Utc>,
+    pub last_accessed: DateTime<Utc>,
+    pub access_count: u32,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReviewQueueResponse {
+    pub items: Vec<ReviewQueueItem>,
+    pub count: usize,
+}
+
+/// Request for POST /api/review/complete
+#[derive(Debug, Deserialize)]
+pub struct CompleteReviewRequest {
+    pub user_id: String,
+    pub memory_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompleteReviewResponse {
+    pub success: bool,
+    /// Memories found and reviewed; unknown or forgotten ids are skipped
+    pub reviewed: usize,
+}
+
+/// GET /api/review?user_id=...&limit=20 - Important memories due for review, most urgent first
+#[tracing::instrument(skip(state), fields(user_id = %params.user_id))]
+pub async fn get_review_queue(
+    State(state): State<AppState>,
+    Query(params): Query<ReviewQuery>,
+) -> Result<Json<ReviewQueueResponse>, AppError> {
+    validation::validate_user_id(&params.user_id).map_validation_err("user_id")?;
+
+    let memory = state
+        .get_user_memory(&params.user_id)
+        .map_err(AppError::Internal)?;
+
+    let limit = params.limit.clamp(1, MAX_REVIEW_LIMIT);
+    let queue = tokio::task::spawn_blocking(move || memory.read().review_queue(limit))
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+        .map_err(AppError::Internal)?;
+
+    let items: Vec<ReviewQueueItem> = queue
+        .into_iter()
+        .map(|r| ReviewQueueItem {
+            id: r.memory.id.0.to_string(),
+            content: r.memory.experience.content.clone(),
+            memory_type: format!("{:?}", r.memory.experience.experience_type),
+            importance: r.memory.importance(),
+            strength: r.strength,
+            interval_days: r.interval_days,
+            due_at: r.due_at,
+            last_accessed: r.memory.last_accessed(),
+            access_count: r.memory.access_count(),
+            tags: r.memory.experience.tags.clone(),
+        })
+        .collect();
+
+    Ok(Json(ReviewQueueResponse {
+        count: items.len(),
+        items,
+    }))
+}
+
+/// POST /api/review/complete - Mark memories as reviewed, expanding their next interval
+#[tracing::instrument(skip(state, req), fields(user_id = %req.user_id))]
+pub async fn complete_review(
+    State(state): State<AppState>,
+    Json(req): Json<CompleteReviewRequest>,
+) -> Result<Json<CompleteReviewResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let ids = req
+        .memory_ids
+        .iter()
+        .map(|id| {
+            uuid::Uuid::parse_str(id)
+                .map(MemoryId)
+                .map_err(|_| AppError::InvalidInput {
+                    field: "memory_ids".to_string(),
+                    reason: format!("invalid memory id '{id}'"),
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let memory = state
+        .get_user_memory(&req.user_id)
+        .map_err(AppError::Internal)?;
+
+    let reviewed = tokio::task::spawn_blocking(move || memory.read().mark_reviewed(&ids))
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+        .map_err(AppError::Internal)?;
+
+    Ok(Json(CompleteReviewResponse {
+        success: true,
+        reviewed,
+    }))
+}
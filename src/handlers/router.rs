@@ -4,15 +4,18 @@
 //! Routes are organized by domain and split into public (no auth) and protected (auth required).
 
 use axum::{
-    routing::{delete, get, post, put},
+    extract::DefaultBodyLimit,
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use std::sync::Arc;
 
 use super::state::MultiUserMemoryManager;
 use super::{
-    ab_testing, compression, consolidation, crud, facts, files, graph, health, integrations,
-    lineage, mif, recall, remember, search, sessions, todos, users, visualization, webhooks,
+    ab_testing, acl, compression, conflicts, consolidation, crud, event_webhooks, export, facts,
+    files, graph, health, hooks, import, integrations, keys, lineage, memory_types, mif, ranking,
+    recall, remember, review, search, sessions, stats, suppress, tags, teams, tenants, todos,
+    users, visualization, webhooks,
 };
 
 /// Application state type alias
@@ -73,6 +76,7 @@ pub fn build_protected_routes(state: AppState) -> Router {
         .route("/api/remember", post(remember::remember))
         .route("/api/remember/batch", post(remember::batch_remember))
         .route("/api/batch_remember", post(remember::batch_remember))
+        .route("/api/remember_batch", post(remember::batch_remember))
         .route("/api/upsert", post(remember::upsert_memory))
         // =================================================================
         // RECALL ENDPOINTS
@@ -82,6 +86,8 @@ pub fn build_protected_routes(state: AppState) -> Router {
         .route("/api/recall/tags", post(recall::recall_by_tags))
         .route("/api/recall/by-tags", post(recall::recall_by_tags)) // OpenAPI alias
         .route("/api/recall/date", post(recall::recall_by_date))
+        .route("/api/recall/related", post(recall::recall_related))
+        .route("/api/recall_related", post(recall::recall_related)) // Alias
         // =================================================================
         // PROACTIVE CONTEXT & RELEVANCE
         // =================================================================
@@ -90,13 +96,32 @@ pub fn build_protected_routes(state: AppState) -> Router {
         .route("/api/context", post(recall::proactive_context)) // OpenAPI alias
         .route("/api/relevant", post(recall::surface_relevant))
         .route("/api/reinforce", post(recall::reinforce_feedback))
+        .route(
+            "/api/admin/memory_stats",
+            get(recall::memory_effectiveness_stats),
+        )
         // =================================================================
         // MEMORY CRUD OPERATIONS
         // =================================================================
         .route("/api/memory/{memory_id}", get(crud::get_memory))
         .route("/api/memories/{memory_id}", get(crud::get_memory)) // Cloudflare compat alias
         .route("/api/memory/{memory_id}", put(crud::update_memory))
+        .route("/api/memory/{memory_id}", patch(crud::patch_memory))
+        .route("/api/memories/{memory_id}", patch(crud::patch_memory))
         .route("/api/memory/{memory_id}", delete(crud::delete_memory))
+        .route(
+            "/api/memory/{memory_id}/history",
+            get(crud::get_memory_history),
+        )
+        .route(
+            "/api/memory/{memory_id}/access_log",
+            get(crud::get_memory_access_log),
+        )
+        .route(
+            "/api/memory/{memory_id}/restore",
+            post(crud::restore_memory),
+        )
+        .route("/api/trash", get(crud::list_trash))
         .route("/api/forget/{memory_id}", delete(crud::delete_memory)) // OpenAPI alias
         .route("/api/list/{user_id}", get(crud::list_memories)) // TUI uses this
         .route("/api/memories", post(crud::list_memories_post)) // POST version
@@ -112,14 +137,28 @@ pub fn build_protected_routes(state: AppState) -> Router {
         .route("/api/forget/pattern", post(crud::forget_by_pattern))
         .route("/api/forget/tags", post(crud::forget_by_tags))
         .route("/api/forget/date", post(crud::forget_by_date))
+        .route(
+            "/api/suppress",
+            post(suppress::suppress).get(suppress::list_suppressions),
+        )
+        .route("/api/suppress/{rule_id}", delete(suppress::unsuppress))
         // =================================================================
         // USER MANAGEMENT
         // =================================================================
         .route("/api/users", get(users::list_users))
         .route("/api/users/{user_id}/stats", get(users::get_user_stats))
+        .route("/api/users/{user_id}/quota", get(users::get_user_quota))
         .route("/api/users/{user_id}", delete(users::delete_user))
+        .route("/api/users/{user_id}/purge", post(users::purge_user))
         .route("/api/stats", get(users::get_stats_query))
         // =================================================================
+        // TEAM NAMESPACES
+        // =================================================================
+        .route(
+            "/api/teams/{team_id}/memories",
+            get(teams::list_team_memories),
+        )
+        // =================================================================
         // COMPRESSION
         // =================================================================
         .route("/api/memory/compress", post(compression::compress_memory))
@@ -131,7 +170,28 @@ pub fn build_protected_routes(state: AppState) -> Router {
         // =================================================================
         // ADVANCED SEARCH
         // =================================================================
+        .route(
+            "/api/search",
+            get(search::search_memories_get).post(search::search_memories),
+        )
         .route("/api/search/advanced", post(search::advanced_search))
+        .route("/api/search/keyword", post(search::keyword_search))
+        // =================================================================
+        // TAG MANAGEMENT
+        // =================================================================
+        .route("/api/tags", get(tags::list_tags))
+        .route("/api/tags/rename", post(tags::rename_tag))
+        .route("/api/tags/merge", post(tags::merge_tags))
+        .route("/api/tags/delete", post(tags::delete_tag))
+        // =================================================================
+        // SPACED-REPETITION REVIEW
+        // =================================================================
+        .route("/api/review", get(review::get_review_queue))
+        .route("/api/review/complete", post(review::complete_review))
+        // =================================================================
+        // CONTRADICTION REVIEW
+        // =================================================================
+        .route("/api/conflicts", get(conflicts::get_conflicts))
         // =================================================================
         // STORAGE & INDEX MANAGEMENT
         // =================================================================
@@ -157,6 +217,18 @@ pub fn build_protected_routes(state: AppState) -> Router {
             "/api/consolidate",
             post(consolidation::consolidate_memories),
         )
+        .route(
+            "/api/consolidate/duplicates",
+            post(consolidation::consolidate_duplicates),
+        )
+        .route(
+            "/api/consolidate/gists",
+            post(consolidation::consolidate_gists),
+        )
+        .route(
+            "/api/consolidate/retention",
+            post(consolidation::apply_retention),
+        )
         .route(
             "/api/consolidation/report",
             post(consolidation::get_consolidation_report),
@@ -172,6 +244,21 @@ pub fn build_protected_routes(state: AppState) -> Router {
         .route("/api/backup/purge", post(consolidation::purge_backups))
         .route("/api/backups/purge", post(consolidation::purge_backups)) // MCP alias
         .route("/api/backup/restore", post(consolidation::restore_backup))
+        .route("/api/admin/backup", post(consolidation::create_backup)) // Admin alias
+        .route("/api/admin/restore", post(consolidation::restore_backup)) // Admin alias
+        .route(
+            "/api/admin/consolidation",
+            get(consolidation::consolidation_status),
+        )
+        .route(
+            "/api/admin/reembed",
+            get(consolidation::reembed_status).post(consolidation::start_reembed),
+        )
+        .route(
+            "/api/admin/reencrypt",
+            get(consolidation::reencrypt_status).post(consolidation::start_reencrypt),
+        )
+        .route("/api/admin/stats", get(stats::admin_stats))
         // =================================================================
         // FACTS
         // =================================================================
@@ -216,6 +303,7 @@ pub fn build_protected_routes(state: AppState) -> Router {
             post(graph::invalidate_relationship),
         )
         .route("/api/graph/traverse", post(graph::traverse_graph))
+        .route("/api/graph/neighborhood", post(graph::get_neighborhood))
         .route("/api/graph/episode/get", post(graph::get_episode))
         // =================================================================
         // KNOWLEDGE GRAPH (BASIC)
@@ -343,6 +431,8 @@ pub fn build_protected_routes(state: AppState) -> Router {
         .route("/api/sessions/stats", get(sessions::get_session_stats))
         .route("/api/sessions/end", post(sessions::end_session))
         .route("/api/sessions/{session_id}", get(sessions::get_session))
+        // Claude Code lifecycle hooks (SessionStart, Stop, SessionEnd, PostToolUse, RunEnd)
+        .route("/api/hooks", post(hooks::ingest_hook))
         // =================================================================
         // A/B TESTING
         // =================================================================
@@ -397,6 +487,15 @@ pub fn build_protected_routes(state: AppState) -> Router {
         .route("/api/context/monitor", get(webhooks::context_monitor_ws))
         .route("/api/events/sse", get(webhooks::memory_events_sse))
         .route("/api/events", get(webhooks::memory_events_sse)) // TUI alias
+        .route("/api/events/ws", get(webhooks::memory_events_ws))
+        .route(
+            "/api/webhooks",
+            get(event_webhooks::list_webhooks).post(event_webhooks::create_webhook),
+        )
+        .route(
+            "/api/webhooks/{webhook_id}",
+            patch(event_webhooks::update_webhook).delete(event_webhooks::delete_webhook),
+        )
         .route("/api/stream", get(webhooks::streaming_memory_ws))
         // =================================================================
         // MULTIMODAL & ROBOTICS SEARCH
@@ -404,12 +503,84 @@ pub fn build_protected_routes(state: AppState) -> Router {
         .route("/api/search/multimodal", post(search::multimodal_search))
         .route("/api/search/robotics", post(search::robotics_search))
         // =================================================================
-        // MIF (Memory Interchange Format) v2
+        // EXPORT: JSONL stream and MIF (Memory Interchange Format) v2
         // =================================================================
+        .route("/api/export", get(export::export_jsonl))
+        .route(
+            "/api/import",
+            post(import::import_memories).layer(DefaultBodyLimit::max(import::MAX_IMPORT_BYTES)),
+        )
         .route("/api/export/mif", post(mif::export_mif))
         .route("/api/import/mif", post(mif::import_mif))
         .route("/api/mif/adapters", get(mif::list_adapters))
         // =================================================================
+        // MEMORY TYPE REGISTRY
+        // =================================================================
+        .route(
+            "/api/memory_types",
+            get(memory_types::list_memory_types).post(memory_types::define_memory_type),
+        )
+        .route(
+            "/api/memory_types/{name}",
+            delete(memory_types::delete_memory_type),
+        )
+        // =================================================================
+        // ACCESS CONTROL (per-namespace grants, enforced on every route above)
+        // =================================================================
+        .route(
+            "/api/admin/acl",
+            get(acl::list_acl)
+                .post(acl::grant_acl)
+                .delete(acl::revoke_acl),
+        )
+        // =================================================================
+        // API KEYS (issued, rotated and revoked without a restart)
+        // =================================================================
+        .route(
+            "/api/admin/keys",
+            get(keys::list_keys).post(keys::create_key),
+        )
+        .route(
+            "/api/admin/keys/{key_id}",
+            patch(keys::update_key).delete(keys::revoke_key),
+        )
+        .route("/api/admin/keys/{key_id}/rotate", post(keys::rotate_key))
+        // =================================================================
+        // TENANTS (ids scoped per tenant key, usage counted per tenant)
+        // =================================================================
+        .route("/api/admin/tenants", get(tenants::list_tenants))
+        // =================================================================
+        // RANKING (per-tenant weights of recall's unified score)
+        // =================================================================
+        .route(
+            "/api/admin/ranking",
+            get(ranking::get_ranking)
+                .put(ranking::set_ranking)
+                .delete(ranking::reset_ranking),
+        )
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            acl::enforce_acl,
+        ))
+        .route_layer(axum::middleware::from_fn(keys::enforce_scopes))
+        // Added after the ACL and scope layers so it runs first: ACL checks
+        // scoped ids, and refused requests still count as tenant usage
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            tenants::scope_tenant,
+        ))
+        // Client traffic delays idle-triggered sleep phases; probes, metrics
+        // and status polling on the public routes don't
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            consolidation::track_activity,
+        ))
+        // Outermost, so throttled requests are turned away before any work
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.rate_limiter.clone(),
+            crate::rate_limit::limit_by_key,
+        ))
+        // =================================================================
         // STATE
         // =================================================================
         .with_state(state)
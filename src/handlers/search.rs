@@ -1,20 +1,404 @@
-//! Search Handlers — Advanced, Multimodal, and Robotics search
+//! Search Handlers — Filtered, Advanced, Multimodal, and Robotics search
 //!
-//! Handlers for advanced memory search with entity filtering, date ranges,
-//! importance thresholds, multi-modal retrieval, and robotics-specific queries.
-
-use axum::{extract::State, response::Json};
+//! Handlers for filtered memory search (free text plus structured filters),
+//! advanced search with entity filtering, date ranges, importance thresholds,
+//! multi-modal retrieval, and robotics-specific queries.
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+    Extension,
+};
 use serde::Deserialize;
 
+use super::crud::parse_experience_type;
 use super::state::MultiUserMemoryManager;
 use super::types::RetrieveResponse;
+use crate::auth::AuthenticatedKey;
+use crate::constants::TEMPORAL_FILTER_OVERFETCH;
 use crate::errors::{AppError, ValidationErrorExt};
-use crate::memory::{self, Memory, Query as MemoryQuery};
+use crate::memory::storage::SearchCriteria;
+use crate::memory::{self, query_parser, Memory, Query as MemoryQuery};
 use crate::validation;
 use std::sync::Arc;
 
 type AppState = Arc<MultiUserMemoryManager>;
 
+/// Maximum memories returned by /api/search
+const MAX_SEARCH_LIMIT: usize = 500;
+
+/// Sort order for /api/search results
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchSort {
+    /// Highest relevance score first (requires `query`)
+    Relevance,
+    Newest,
+    Oldest,
+    Importance,
+}
+
+/// Which time a date filter applies to
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeBasis {
+    /// When the memory was stored (created_at)
+    Ingest,
+    /// Dates the memory is about (mentioned in its content)
+    Event,
+    /// Either
+    Any,
+}
+
+/// Request for filtered search (POST body)
+#[derive(Debug, Deserialize)]
+pub struct SearchRequest {
+    pub user_id: String,
+    /// Free-text query; results are ranked by relevance when present
+    #[serde(default)]
+    pub query: Option<String>,
+    /// Match memories carrying ANY of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub memory_type: Option<String>,
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    #[serde(default)]
+    pub run_id: Option<String>,
+    /// Relevance score range (requires `query`)
+    #[serde(default)]
+    pub min_score: Option<f32>,
+    #[serde(default)]
+    pub max_score: Option<f32>,
+    /// created_at range, RFC3339 (either bound may be omitted). Without
+    /// either bound, a temporal expression in `query` ("last week", "in
+    /// March") is resolved into the range instead.
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub end_date: Option<String>,
+    /// Time the date range applies to; defaults to ingest for explicit dates
+    /// and to either ingest or event time for query expressions
+    #[serde(default)]
+    pub time_basis: Option<TimeBasis>,
+    /// Defaults to relevance with a query, newest otherwise
+    #[serde(default)]
+    pub sort: Option<SearchSort>,
+    #[serde(default = "default_search_limit")]
+    pub limit: usize,
+}
+
+fn default_search_limit() -> usize {
+    20
+}
+
+/// Query parameters for GET /api/search (`tags` is comma-separated)
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    pub user_id: String,
+    pub query: Option<String>,
+    pub tags: Option<String>,
+    pub memory_type: Option<String>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
+    pub min_score: Option<f32>,
+    pub max_score: Option<f32>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub time_basis: Option<TimeBasis>,
+    pub sort: Option<SearchSort>,
+    pub limit: Option<usize>,
+}
+
+impl From<SearchParams> for SearchRequest {
+    fn from(params: SearchParams) -> Self {
+        Self {
+            user_id: params.user_id,
+            query: params.query,
+            tags: params
+                .tags
+                .map(|t| {
+                    t.split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            memory_type: params.memory_type,
+            agent_id: params.agent_id,
+            run_id: params.run_id,
+            min_score: params.min_score,
+            max_score: params.max_score,
+            start_date: params.start_date,
+            end_date: params.end_date,
+            time_basis: params.time_basis,
+            sort: params.sort,
+            limit: params.limit.unwrap_or_else(default_search_limit),
+        }
+    }
+}
+
+fn parse_search_date(
+    field: &str,
+    value: Option<&str>,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>, AppError> {
+    value
+        .map(|v| {
+            chrono::DateTime::parse_from_rfc3339(v)
+                .map(|dt| dt.with_timezone(&chrono::Utc))
+                .map_err(|_| AppError::InvalidInput {
+                    field: field.to_string(),
+                    reason: "Invalid RFC3339 format".to_string(),
+                })
+        })
+        .transpose()
+}
+
+/// GET /api/search - Filtered search with query parameters
+pub async fn search_memories_get(
+    state: State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<RetrieveResponse>, AppError> {
+    search_memories(state, key, Json(params.into())).await
+}
+
+/// POST /api/search - Free-text query plus structured filters
+///
+/// Filters on tags, memory_type, agent_id, run_id, relevance score and
+/// date range, sorted by relevance, newest, oldest or importance.
+pub async fn search_memories(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Json(req): Json<SearchRequest>,
+) -> Result<Json<RetrieveResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let mut query_text = req
+        .query
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .map(String::from);
+    let has_score_filter = req.min_score.is_some() || req.max_score.is_some();
+    if query_text.is_none() && (has_score_filter || req.sort == Some(SearchSort::Relevance)) {
+        return Err(AppError::InvalidInput {
+            field: "query".to_string(),
+            reason: "score filters and relevance sort require a query".to_string(),
+        });
+    }
+    let memory_type = req
+        .memory_type
+        .as_deref()
+        .map(parse_experience_type)
+        .transpose()?;
+    let mut start = parse_search_date("start_date", req.start_date.as_deref())?;
+    let mut end = parse_search_date("end_date", req.end_date.as_deref())?;
+    let mut time_basis = req.time_basis.unwrap_or(TimeBasis::Ingest);
+
+    // "what did we decide last week?" -> date range + "what did we decide?"
+    if start.is_none() && end.is_none() {
+        let parsed = query_text
+            .as_deref()
+            .and_then(|q| query_parser::parse_time_range(q, chrono::Utc::now()).map(|r| (q, r)));
+        if let Some((text, range)) = parsed {
+            tracing::debug!(
+                "Search time filter '{}': {} to {}",
+                range.expression,
+                range.start,
+                range.end
+            );
+            let stripped = range.strip_from(text);
+            start = Some(range.start);
+            end = Some(range.end);
+            time_basis = req.time_basis.unwrap_or(TimeBasis::Any);
+            query_text = Some(stripped).filter(|q| !q.is_empty()).or(query_text);
+        }
+    }
+    let in_range = |m: &Memory| {
+        let lo = start.unwrap_or(chrono::DateTime::<chrono::Utc>::MIN_UTC);
+        let hi = end.unwrap_or(chrono::DateTime::<chrono::Utc>::MAX_UTC);
+        match time_basis {
+            TimeBasis::Ingest => m.created_at >= lo && m.created_at <= hi,
+            TimeBasis::Event => m.event_in_range(lo, hi),
+            TimeBasis::Any => m.in_time_range(lo, hi),
+        }
+    };
+    let limit = req.limit.clamp(1, MAX_SEARCH_LIMIT);
+
+    let memory_sys = state
+        .get_user_memory(&req.user_id)
+        .map_err(AppError::Internal)?;
+    let memory_guard = memory_sys.read();
+
+    let candidates = match &query_text {
+        Some(text) => {
+            let query = MemoryQuery {
+                user_id: Some(req.user_id.clone()),
+                query_text: Some(text.clone()),
+                experience_types: memory_type.clone().map(|t| vec![t]),
+                tags: (!req.tags.is_empty()).then(|| req.tags.clone()),
+                // Over-fetch: agent/run/score/date filters are applied afterwards
+                max_results: (limit * TEMPORAL_FILTER_OVERFETCH).min(MAX_SEARCH_LIMIT),
+                ..Default::default()
+            };
+            memory_guard.recall(&query).map_err(AppError::Internal)?
+        }
+        // Date-only search: read the ingest and/or event date index
+        None if start.is_some() || end.is_some() => {
+            let lo = start.unwrap_or(chrono::DateTime::<chrono::Utc>::MIN_UTC);
+            let hi = end.unwrap_or_else(chrono::Utc::now);
+            let mut criteria = Vec::new();
+            if time_basis != TimeBasis::Event {
+                criteria.push(SearchCriteria::ByDate { start: lo, end: hi });
+            }
+            if time_basis != TimeBasis::Ingest {
+                criteria.push(SearchCriteria::ByEventDate { start: lo, end: hi });
+            }
+            let mut seen = std::collections::HashSet::new();
+            let mut found = Vec::new();
+            for c in criteria {
+                for m in memory_guard
+                    .advanced_search(c)
+                    .map_err(AppError::Internal)?
+                {
+                    if seen.insert(m.id.clone()) {
+                        found.push(Arc::new(m));
+                    }
+                }
+            }
+            found
+        }
+        None => memory_guard
+            .get_all_memories()
+            .map_err(AppError::Internal)?,
+    };
+
+    let mut matches: Vec<_> = candidates
+        .into_iter()
+        .filter(|m| {
+            (req.tags.is_empty() || m.experience.tags.iter().any(|t| req.tags.contains(t)))
+                && memory_type
+                    .as_ref()
+                    .is_none_or(|t| &m.experience.experience_type == t)
+                && req
+                    .agent_id
+                    .as_ref()
+                    .is_none_or(|a| m.agent_id.as_ref() == Some(a))
+                && req
+                    .run_id
+                    .as_ref()
+                    .is_none_or(|r| m.run_id.as_ref() == Some(r))
+                && in_range(m)
+                && req
+                    .min_score
+                    .is_none_or(|min| m.score.unwrap_or(0.0) >= min)
+                && req
+                    .max_score
+                    .is_none_or(|max| m.score.unwrap_or(0.0) <= max)
+                // The date and unfiltered paths read storage directly
+                && !memory_guard.is_suppressed(m)
+        })
+        .collect();
+
+    let sort = req.sort.unwrap_or(if query_text.is_some() {
+        SearchSort::Relevance
+    } else {
+        SearchSort::Newest
+    });
+    match sort {
+        SearchSort::Relevance => {
+            matches.sort_by(|a, b| b.score.unwrap_or(0.0).total_cmp(&a.score.unwrap_or(0.0)))
+        }
+        SearchSort::Newest => matches.sort_by(|a, b| b.created_at.cmp(&a.created_at)),
+        SearchSort::Oldest => matches.sort_by(|a, b| a.created_at.cmp(&b.created_at)),
+        SearchSort::Importance => matches.sort_by(|a, b| b.importance().total_cmp(&a.importance())),
+    }
+    matches.truncate(limit);
+    state.log_access(
+        key.as_deref(),
+        &req.user_id,
+        "/api/search",
+        matches
+            .iter()
+            .map(|m| (m.id.0.to_string(), m.score))
+            .collect(),
+    );
+
+    let count = matches.len();
+    let memories: Vec<serde_json::Value> = matches
+        .iter()
+        .filter_map(|m| serde_json::to_value(m.as_ref()).ok())
+        .collect();
+
+    Ok(Json(RetrieveResponse { memories, count }))
+}
+
+/// Request for full-text keyword search
+#[derive(Debug, Deserialize)]
+pub struct KeywordSearchRequest {
+    pub user_id: String,
+    /// Query in tantivy syntax: `"exact phrase"`, `AND`/`OR`, `+required
+    /// -excluded`, `content:`/`tags:`/`entities:` field scoping
+    pub query: String,
+    #[serde(default = "default_search_limit")]
+    pub limit: usize,
+}
+
+/// POST /api/search/keyword - Full-text search over the BM25 index
+///
+/// Exact identifiers such as function names and error codes, which vector
+/// search handles poorly. Each memory's `score` is its BM25 score.
+pub async fn keyword_search(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Json(req): Json<KeywordSearchRequest>,
+) -> Result<Json<RetrieveResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+    if req.query.trim().is_empty() {
+        return Err(AppError::InvalidInput {
+            field: "query".to_string(),
+            reason: "query must not be empty".to_string(),
+        });
+    }
+
+    let memory_sys = state
+        .get_user_memory(&req.user_id)
+        .map_err(AppError::Internal)?;
+    let hits = memory_sys
+        .read()
+        .keyword_search(&req.query, req.limit.clamp(1, MAX_SEARCH_LIMIT))
+        .map_err(
+            |e| match e.downcast_ref::<tantivy::query::QueryParserError>() {
+                Some(parse_error) => AppError::InvalidInput {
+                    field: "query".to_string(),
+                    reason: parse_error.to_string(),
+                },
+                None => AppError::Internal(e),
+            },
+        )?;
+
+    let count = hits.len();
+    state.log_access(
+        key.as_deref(),
+        &req.user_id,
+        "/api/search/keyword",
+        hits.iter()
+            .map(|(memory, score)| (memory.id.0.to_string(), Some(*score)))
+            .collect(),
+    );
+    let memories: Vec<serde_json::Value> = hits
+        .into_iter()
+        .filter_map(|(mut memory, score)| {
+            memory.score = Some(score);
+            serde_json::to_value(&memory).ok()
+        })
+        .collect();
+
+    Ok(Json(RetrieveResponse { memories, count }))
+}
+
 /// Request for advanced search
 #[derive(Debug, Deserialize)]
 pub struct AdvancedSearchRequest {
@@ -128,7 +128,7 @@ pub async fn end_session(
         .find(|s| matches!(s.status, SessionStatus::Active));
 
     if let Some(summary) = active_session {
-        let session = state.session_store.end_session(&summary.id, &req.reason);
+        let session = state.end_session(&summary.id, &req.reason);
         Ok(Json(EndSessionResponse {
             success: session.is_some(),
             session,
@@ -22,25 +22,98 @@ fn issue_regex() -> &'static regex::Regex {
     RE.get_or_init(|| regex::Regex::new(r"\b([A-Z]{2,10}-\d+)\b").unwrap())
 }
 
+/// Static regex for extracting source file paths (src/main.rs, lib/utils.py, etc.)
+fn file_path_regex() -> &'static regex::Regex {
+    static RE: OnceLock<regex::Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        regex::Regex::new(
+            r"(?:^|[\s`'(\[])((?:[\w.-]+/)*[\w-]+\.(?:rs|py|ts|tsx|js|jsx|go|java|kt|c|h|cpp|hpp|cs|rb|swift|toml|yaml|yml|json|md|sql|sh))\b",
+        )
+        .unwrap()
+    })
+}
+
+/// Static regex for extracting repositories (github.com/owner/repo, etc.)
+fn repo_regex() -> &'static regex::Regex {
+    static RE: OnceLock<regex::Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        regex::Regex::new(r"\b(?:github\.com|gitlab\.com|bitbucket\.org)/([\w.-]+/[\w.-]+)")
+            .unwrap()
+    })
+}
+
+/// Static regex for extracting libraries from install commands (cargo add foo, etc.)
+fn library_regex() -> &'static regex::Regex {
+    static RE: OnceLock<regex::Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        regex::Regex::new(
+            r"\b(?:cargo add|npm (?:install|i)|pip3? install|yarn add|pnpm add|go get)\s+(?:-[\w-]+\s+)*(@?[\w./-]+)",
+        )
+        .unwrap()
+    })
+}
+
+/// Static regex for extracting named services (auth service, billing-svc, etc.)
+fn service_regex() -> &'static regex::Regex {
+    static RE: OnceLock<regex::Regex> = OnceLock::new();
+    RE.get_or_init(|| regex::Regex::new(r"(?i)\b([a-z][\w]*)[ -](?:service|svc)\b").unwrap())
+}
+
+/// Code entities (files, repositories, libraries, services) mentioned in text,
+/// as (name, label) pairs in order of first mention
+fn extract_code_entities(text: &str) -> Vec<(String, &'static str)> {
+    let mut found: Vec<(String, &'static str)> = Vec::new();
+    let mut push = |name: String, label: &'static str| {
+        if !found.iter().any(|(n, _)| n.eq_ignore_ascii_case(&name)) {
+            found.push((name, label));
+        }
+    };
+
+    for caps in repo_regex().captures_iter(text) {
+        let repo = caps[1].trim_end_matches('.');
+        push(repo.trim_end_matches(".git").to_string(), "Repository");
+    }
+    for caps in library_regex().captures_iter(text) {
+        // Version pins (serde@1.0, requests==2.31) end the capture
+        push(caps[1].trim_end_matches('.').to_string(), "Library");
+    }
+    for caps in file_path_regex().captures_iter(text) {
+        push(caps[1].to_string(), "File");
+    }
+    for caps in service_regex().captures_iter(text) {
+        let name = caps[1].to_lowercase();
+        if matches!(
+            name.as_str(),
+            "the" | "a" | "an" | "this" | "that" | "our" | "web"
+        ) {
+            continue;
+        }
+        push(format!("{name} service"), "Service");
+    }
+    found
+}
+
 use crate::ab_testing;
 use crate::backup;
 use crate::config::ServerConfig;
 use crate::embeddings::{
     are_ner_models_downloaded, download_ner_models, get_ner_models_dir, ner::NerEntityType,
-    KeywordExtractor, NerConfig, NeuralNer,
+    EmbeddingPipeline, KeywordExtractor, NerConfig, NeuralNer, PipelineConfig,
 };
 use crate::graph_memory::{
     EdgeTier, EntityLabel, EntityNode, EpisodeSource, EpisodicNode, GraphMemory, GraphStats,
     LtpStatus, RelationType, RelationshipEdge,
 };
 use crate::memory::{
-    query_parser, Experience, FeedbackStore, FileMemoryStore, MemoryConfig, MemoryId, MemoryStats,
-    MemorySystem, ProspectiveStore, SessionStore, TodoStore,
+    encoding_filters::EncodingFilters, encryption::ReencryptStatus, query_parser,
+    reembed::ReembedStatus, secrets, suppression::SuppressionList, Experience, FeedbackStore,
+    FileMemoryStore, MemoryConfig, MemoryId, MemoryStats, MemorySystem, ProspectiveStore, Session,
+    SessionId, SessionStore, TodoStore,
 };
 use crate::relevance::RelevanceEngine;
 use crate::streaming;
 
-use super::types::{AuditEvent, ContextStatus, MemoryEvent};
+use super::types::{AuditEvent, ContextStatus, DependencyStatus, MemoryEvent};
 
 /// Type alias for context sessions map
 pub type ContextSessions = DashMap<String, ContextStatus>;
@@ -162,6 +235,24 @@ impl MultiUserMemoryManagerRotationHelper {
     }
 }
 
+/// How long a remember idempotency key deduplicates retries (24 hours)
+const IDEMPOTENCY_TTL_SECS: u64 = 86_400;
+
+/// A run with no new memories for this long is considered finished and
+/// summarized on the next session cleanup (30 minutes)
+const RUN_IDLE_TIMEOUT_SECS: u64 = 1_800;
+
+/// How long a user purge confirmation token stays valid (5 minutes)
+const PURGE_TOKEN_TTL_SECS: u64 = 300;
+
+/// What a user purge removed
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct UserPurgeReport {
+    pub memories_deleted: usize,
+    pub feedback_entries_deleted: usize,
+    pub sessions_deleted: usize,
+}
+
 /// Multi-user memory manager - central state for the server
 pub struct MultiUserMemoryManager {
     /// Per-user memory systems with LRU eviction
@@ -212,12 +303,42 @@ pub struct MultiUserMemoryManager {
     /// File memory store for codebase integration
     pub file_store: Arc<FileMemoryStore>,
 
+    /// Per-namespace read/write grants for API keys
+    pub acl_store: Arc<crate::acl::AclStore>,
+
+    /// Keys issued through /api/admin/keys (checked by the auth middleware)
+    pub api_keys: Arc<crate::api_keys::ApiKeyStore>,
+
+    /// Outbound webhooks for memory events (see `crate::event_webhooks`)
+    pub webhooks: Arc<crate::event_webhooks::WebhookStore>,
+
+    /// Which retrievals surfaced each memory (see `crate::access_log`)
+    pub access_log: Arc<crate::access_log::AccessLogStore>,
+
+    /// Per-tenant weights of recall's unified score (see `crate::ranking`)
+    pub ranking: Arc<crate::ranking::RankingStore>,
+
+    /// Per-user rules keeping memories out of retrieval (see `crate::suppression`)
+    pub suppressions: Arc<crate::suppression::SuppressionStore>,
+
+    /// Per-user daily injected-token usage (see `crate::injection_usage`)
+    pub injection_usage: Arc<crate::injection_usage::InjectionUsageStore>,
+
+    /// Built-in and deployment-defined memory types
+    pub memory_types: Arc<crate::memory::type_registry::MemoryTypeRegistry>,
+
     /// Implicit feedback store for memory reinforcement
     pub feedback_store: Arc<parking_lot::RwLock<FeedbackStore>>,
 
     /// Backup engine for automated and manual backups
     pub backup_engine: Arc<backup::ShodhBackupEngine>,
 
+    /// Optional S3-compatible bucket receiving a copy of every backup
+    pub backup_sink: Option<Arc<crate::backup_sink::S3BackupSink>>,
+
+    /// Decides when heavy maintenance runs and tracks its progress
+    pub sleep: Arc<crate::sleep::SleepScheduler>,
+
     /// Context status from Claude Code sessions
     pub context_sessions: Arc<ContextSessions>,
 
@@ -233,6 +354,40 @@ pub struct MultiUserMemoryManager {
     /// Shared relevance engine for proactive memory surfacing (entity cache + learned weights persist)
     pub relevance_engine: Arc<RelevanceEngine>,
 
+    /// Idempotency keys seen by /api/remember, so retried writes return the
+    /// original memory instead of storing a duplicate (see `crate::idempotency`)
+    pub remember_idempotency: Arc<crate::idempotency::IdempotencyStore>,
+
+    /// User-defined rules excluding interactions from storage (reloaded on change)
+    pub encoding_filters: Arc<EncodingFilters>,
+
+    /// Runs that stored memories recently ((user_id, run_id) -> last write),
+    /// summarized once they go idle or are ended explicitly
+    pub active_runs: Arc<DashMap<(String, String), std::time::Instant>>,
+
+    /// Outstanding purge confirmation tokens (user_id -> token)
+    pub purge_tokens: moka::sync::Cache<String, String>,
+
+    /// Batched embedding for bulk ingest, one per embedding model id and
+    /// started on first use (stores can be pinned to different models)
+    embedding_pipelines: DashMap<String, Arc<EmbeddingPipeline>>,
+
+    /// Progress of the current or last re-embedding job, served at /api/admin/reembed
+    pub reembed_status: Arc<parking_lot::RwLock<ReembedStatus>>,
+
+    /// Progress of the current or last re-encryption job, served at /api/admin/reencrypt
+    pub reencrypt_status: Arc<parking_lot::RwLock<ReencryptStatus>>,
+
+    /// Per-tenant request counters, served at /api/admin/tenants
+    pub tenants: Arc<crate::tenant::TenantRegistry>,
+
+    /// Token buckets of the per-key rate limit (SHODH_KEY_RATE_LIMIT)
+    pub rate_limiter: Arc<crate::rate_limit::KeyRateLimiter>,
+
+    /// Last embedding provider check of /health/ready, reused for a while so
+    /// probes don't call a hosted provider on every request
+    pub embedding_probe: Arc<parking_lot::Mutex<Option<(std::time::Instant, DependencyStatus)>>>,
+
     /// Maintenance cycle counter: cycles 0..5 are lightweight (in-memory only),
     /// cycle 0 (mod 6) is heavyweight (graph decay, fact extraction, flush).
     /// At 300s intervals, heavy cycles fire every 30 minutes.
@@ -381,6 +536,15 @@ impl MultiUserMemoryManager {
             cfs.extend(TodoStore::cf_descriptors());
             cfs.extend(ProspectiveStore::column_family_descriptors());
             cfs.extend(FileMemoryStore::cf_descriptors());
+            cfs.extend(crate::acl::AclStore::cf_descriptors());
+            cfs.extend(crate::api_keys::ApiKeyStore::cf_descriptors());
+            cfs.extend(crate::event_webhooks::WebhookStore::cf_descriptors());
+            cfs.extend(crate::access_log::AccessLogStore::cf_descriptors());
+            cfs.extend(crate::ranking::RankingStore::cf_descriptors());
+            cfs.extend(crate::suppression::SuppressionStore::cf_descriptors());
+            cfs.extend(crate::injection_usage::InjectionUsageStore::cf_descriptors());
+            cfs.extend(crate::idempotency::IdempotencyStore::cf_descriptors());
+            cfs.extend(crate::memory::type_registry::MemoryTypeRegistry::cf_descriptors());
             // Feedback CF
             cfs.push(ColumnFamilyDescriptor::new(
                 crate::memory::feedback::CF_FEEDBACK,
@@ -420,6 +584,50 @@ impl MultiUserMemoryManager {
         let file_store = Arc::new(FileMemoryStore::new(shared_db.clone(), &base_path)?);
         info!("File memory store initialized");
 
+        let acl_store = Arc::new(crate::acl::AclStore::new(shared_db.clone())?);
+        info!("ACL store initialized");
+
+        let api_keys = Arc::new(crate::api_keys::ApiKeyStore::new(shared_db.clone())?);
+        info!("API key store initialized");
+
+        let webhooks = Arc::new(crate::event_webhooks::WebhookStore::new(shared_db.clone())?);
+        info!("Webhook store initialized");
+
+        let access_log = Arc::new(crate::access_log::AccessLogStore::new(
+            shared_db.clone(),
+            server_config.access_log_retention_days,
+            server_config.access_log_max_entries_per_memory,
+        ));
+        match access_log.prune_expired() {
+            Ok(0) => {}
+            Ok(n) => info!("Pruned {} expired access log entries", n),
+            Err(e) => tracing::warn!("Failed to prune access log: {}", e),
+        }
+
+        let ranking = Arc::new(crate::ranking::RankingStore::new(shared_db.clone())?);
+        info!("Ranking weights store initialized");
+
+        let suppressions = Arc::new(crate::suppression::SuppressionStore::new(shared_db.clone()));
+
+        let injection_usage = Arc::new(crate::injection_usage::InjectionUsageStore::new(
+            shared_db.clone(),
+        ));
+
+        let remember_idempotency = Arc::new(crate::idempotency::IdempotencyStore::new(
+            shared_db.clone(),
+            IDEMPOTENCY_TTL_SECS,
+        ));
+        match remember_idempotency.prune_expired() {
+            Ok(0) => {}
+            Ok(n) => info!("Pruned {} expired idempotency keys", n),
+            Err(e) => tracing::warn!("Failed to prune idempotency keys: {}", e),
+        }
+
+        let memory_types = Arc::new(crate::memory::type_registry::MemoryTypeRegistry::new(
+            shared_db.clone(),
+        )?);
+        info!("Memory type registry initialized");
+
         let feedback_store = Arc::new(parking_lot::RwLock::new(
             FeedbackStore::with_shared_db(shared_db.clone(), &base_path).unwrap_or_else(|e| {
                 tracing::warn!("Failed to load feedback store: {}, using in-memory", e);
@@ -451,9 +659,24 @@ impl MultiUserMemoryManager {
         } else {
             info!("Backup engine initialized (auto-backup disabled)");
         }
+        let sleep = Arc::new(crate::sleep::SleepScheduler::new(
+            server_config.sleep.clone(),
+        ));
+        let backup_sink = match server_config.backup_s3.clone() {
+            Some(s3) => Some(Arc::new(crate::backup_sink::S3BackupSink::new(s3)?)),
+            None => None,
+        };
 
         let broadcast_capacity = (server_config.max_users_in_memory * 4).max(64);
 
+        let encoding_filters = Arc::new(EncodingFilters::new(
+            server_config.encoding_filters_path.clone(),
+        ));
+
+        let rate_limiter = Arc::new(crate::rate_limit::KeyRateLimiter::from_config(
+            &server_config,
+        ));
+
         let manager = Self {
             user_memories,
             audit_logs: Arc::new(DashMap::new()),
@@ -471,8 +694,18 @@ impl MultiUserMemoryManager {
             prospective_store,
             todo_store,
             file_store,
+            acl_store,
+            api_keys,
+            webhooks,
+            access_log,
+            ranking,
+            suppressions,
+            injection_usage,
+            memory_types,
             feedback_store,
             backup_engine,
+            backup_sink,
+            sleep,
             context_sessions: Arc::new(DashMap::new()),
             context_broadcaster: {
                 let (tx, _) = tokio::sync::broadcast::channel(broadcast_capacity);
@@ -481,6 +714,18 @@ impl MultiUserMemoryManager {
             ab_test_manager: Arc::new(ab_testing::ABTestManager::new()),
             session_store: Arc::new(SessionStore::new()),
             relevance_engine,
+            remember_idempotency,
+            encoding_filters,
+            active_runs: Arc::new(DashMap::new()),
+            purge_tokens: moka::sync::Cache::builder()
+                .time_to_live(std::time::Duration::from_secs(PURGE_TOKEN_TTL_SECS))
+                .build(),
+            embedding_pipelines: DashMap::new(),
+            reembed_status: Arc::new(parking_lot::RwLock::new(ReembedStatus::default())),
+            reencrypt_status: Arc::new(parking_lot::RwLock::new(ReencryptStatus::default())),
+            tenants: Arc::new(crate::tenant::TenantRegistry::new()),
+            rate_limiter,
+            embedding_probe: Arc::new(parking_lot::Mutex::new(None)),
             maintenance_cycle: std::sync::atomic::AtomicU64::new(0),
         };
 
@@ -641,6 +886,75 @@ impl MultiUserMemoryManager {
         }
     }
 
+    /// Record that a retrieval through `endpoint` surfaced `hits` (memory id
+    /// and score, best first), non-blocking like [`Self::log_event`]
+    pub fn log_access(
+        &self,
+        key: Option<&crate::auth::AuthenticatedKey>,
+        user_id: &str,
+        endpoint: &'static str,
+        hits: Vec<(String, Option<f32>)>,
+    ) {
+        if hits.is_empty() || !self.access_log.is_enabled() {
+            return;
+        }
+        let access_log = self.access_log.clone();
+        let key_id = key.map(|k| k.key_id.clone());
+        let user_id = user_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = access_log.record(&user_id, endpoint, key_id.as_deref(), &hits) {
+                tracing::warn!("Failed to record memory access for {}: {}", user_id, e);
+            }
+        });
+    }
+
+    /// Embed experiences that have no embedding yet through the shared batch
+    /// pipeline, recording the model on each. If batching fails they are left
+    /// as they are and get embedded one at a time when stored.
+    pub async fn embed_experiences(
+        &self,
+        memory: &Arc<parking_lot::RwLock<MemorySystem>>,
+        experiences: &mut [Experience],
+    ) {
+        let embedder = memory.read().embedder();
+        let pipeline = self
+            .embedding_pipelines
+            .entry(embedder.info().model_id())
+            .or_insert_with(|| {
+                Arc::new(EmbeddingPipeline::spawn(
+                    embedder,
+                    PipelineConfig::from_env(),
+                ))
+            })
+            .clone();
+        let missing: Vec<usize> = experiences
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.embeddings.is_none())
+            .map(|(i, _)| i)
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+        let texts = missing
+            .iter()
+            .map(|&i| experiences[i].content.clone())
+            .collect();
+        match pipeline.embed_many(texts).await {
+            Ok(embeddings) => {
+                for (i, embedding) in missing.into_iter().zip(embeddings) {
+                    let experience = &mut experiences[i];
+                    experience.embeddings = Some(embedding);
+                    pipeline.info().write_to(&mut experience.metadata);
+                }
+            }
+            Err(e) => tracing::warn!(
+                "Batch embedding failed, falling back to per-memory embedding: {}",
+                e
+            ),
+        }
+    }
+
     /// Emit SSE event to all connected dashboard clients
     pub fn emit_event(&self, event: MemoryEvent) {
         let _ = self.event_broadcaster.send(event);
@@ -704,7 +1018,11 @@ impl MultiUserMemoryManager {
     }
 
     /// Get or create memory system for a user
+    ///
+    /// Fails with [`crate::tenant::TenantMismatch`] for another tenant's user
+    /// while serving a tenant-confined request.
     pub fn get_user_memory(&self, user_id: &str) -> Result<Arc<parking_lot::RwLock<MemorySystem>>> {
+        crate::tenant::check_access(user_id)?;
         if let Some(memory) = self.user_memories.get(user_id) {
             return Ok(memory);
         }
@@ -722,6 +1040,10 @@ impl MultiUserMemoryManager {
         memory_system.set_graph_memory(graph);
         // Wire up FeedbackStore for PIPE-9 (feedback momentum in all retrieval paths)
         memory_system.set_feedback_store(self.feedback_store.clone());
+        memory_system.set_quota(self.server_config.quota);
+        memory_system.set_merge_duplicates(self.server_config.merge_duplicates);
+        memory_system.set_ranking(self.ranking.weights_for(user_id));
+        memory_system.set_suppressions(self.suppression_list(user_id));
 
         let memory_arc = Arc::new(parking_lot::RwLock::new(memory_system));
 
@@ -733,6 +1055,45 @@ impl MultiUserMemoryManager {
         Ok(memory_arc)
     }
 
+    /// Push `tenant`'s current ranking weights to its loaded memory systems
+    /// (`None` = default tenant)
+    pub fn apply_ranking(&self, tenant: Option<&str>) {
+        let weights = self.ranking.weights(tenant);
+        for (user_id, memory) in self.user_memories.iter() {
+            if crate::tenant::tenant_of(&user_id) == tenant {
+                memory.write().set_ranking(weights);
+            }
+        }
+    }
+
+    /// Compiled suppression rules of `user_id`
+    fn suppression_list(&self, user_id: &str) -> SuppressionList {
+        match self.suppressions.list(user_id) {
+            Ok(rules) => SuppressionList::new(&rules),
+            Err(e) => {
+                tracing::warn!(user_id, "Failed to load suppression rules: {}", e);
+                SuppressionList::default()
+            }
+        }
+    }
+
+    /// Push `user_id`'s current suppression rules to its memory system, if loaded
+    pub fn apply_suppressions(&self, user_id: &str) {
+        if let Some(memory) = self.user_memories.get(user_id) {
+            let suppressions = self.suppression_list(user_id);
+            memory.write().set_suppressions(suppressions);
+        }
+    }
+
+    /// Get or create the memory system for a team namespace
+    ///
+    /// Team memories live under `<base>/teams/<team_id>`. Neither the cache key
+    /// nor the directory can collide with a user's: user IDs can't contain '/'
+    /// and `teams` is a reserved user ID (see `crate::validation::RESERVED_USER_IDS`).
+    pub fn get_team_memory(&self, team_id: &str) -> Result<Arc<parking_lot::RwLock<MemorySystem>>> {
+        self.get_user_memory(&super::teams::team_namespace(team_id))
+    }
+
     /// Evict a user's memory and graph from in-memory caches (releases DB handles).
     /// Does NOT delete data — used before restore to release file locks.
     pub fn evict_user(&self, user_id: &str) {
@@ -817,6 +1178,55 @@ impl MultiUserMemoryManager {
         Ok(())
     }
 
+    /// Issue a single-use token that must be presented to [`Self::purge_user`]
+    pub fn issue_purge_token(&self, user_id: &str) -> (String, u64) {
+        let token = uuid::Uuid::new_v4().to_string();
+        self.purge_tokens.insert(user_id.to_string(), token.clone());
+        (token, PURGE_TOKEN_TTL_SECS)
+    }
+
+    /// Delete everything held for a user, like [`Self::forget_user`], plus
+    /// feedback momentum, sessions, runs and daily injection usage.
+    ///
+    /// Requires a token from [`Self::issue_purge_token`]; returns `None` when it
+    /// is missing, expired or wrong. The token is taken out of the cache before
+    /// it is compared, so concurrent requests can't both use it (a wrong token
+    /// also spends it). The purge itself is recorded in the audit log after
+    /// the user's previous audit rows are removed.
+    pub fn purge_user(&self, user_id: &str, token: &str) -> Result<Option<UserPurgeReport>> {
+        if self.purge_tokens.remove(user_id).as_deref() != Some(token) {
+            return Ok(None);
+        }
+
+        let memory_ids: Vec<MemoryId> = self
+            .get_user_memory(user_id)?
+            .read()
+            .get_all_memories()?
+            .iter()
+            .map(|m| m.id.clone())
+            .collect();
+        let report = UserPurgeReport {
+            memories_deleted: memory_ids.len(),
+            feedback_entries_deleted: self.feedback_store.write().purge_user(user_id, &memory_ids),
+            sessions_deleted: self.session_store.remove_user(user_id),
+        };
+        self.injection_usage.remove_user(user_id)?;
+        self.active_runs
+            .retain(|(run_user, _), _| run_user != user_id);
+        self.forget_user(user_id)?;
+
+        self.log_event(
+            user_id,
+            "PURGE",
+            "",
+            &format!(
+                "Purged all user data: {} memories, {} feedback entries, {} sessions",
+                report.memories_deleted, report.feedback_entries_deleted, report.sessions_deleted
+            ),
+        );
+        Ok(Some(report))
+    }
+
     /// Prefix-scan and batch-delete all keys starting with `{user_id}:` from a column family
     fn delete_by_prefix(db: &rocksdb::DB, cf: &rocksdb::ColumnFamily, prefix: &[u8]) -> usize {
         let mut batch = rocksdb::WriteBatch::default();
@@ -842,7 +1252,14 @@ impl MultiUserMemoryManager {
         let prefix_bytes = prefix.as_bytes();
 
         // Shared CF names that use `{user_id}:` as key prefix
-        let cf_names = ["todos", "projects", "prospective"];
+        let cf_names = [
+            "todos",
+            "projects",
+            "prospective",
+            "access_log",
+            "suppressions",
+            "remember_idempotency",
+        ];
         for name in &cf_names {
             if let Some(cf) = self.shared_db.cf_handle(name) {
                 let n = Self::delete_by_prefix(&self.shared_db, cf, prefix_bytes);
@@ -971,19 +1388,8 @@ impl MultiUserMemoryManager {
                     if file_type.is_dir() {
                         if let Some(name) = entry.file_name().to_str() {
                             // Filter out system directories
-                            if name != "audit_logs"
-                                && name != "audit_logs.pre_cf_migration"
-                                && name != "backups"
-                                && name != "feedback"
-                                && name != "feedback.pre_cf_migration"
-                                && name != "semantic_facts"
-                                && name != "files"
-                                && name != "files.pre_cf_migration"
-                                && name != "prospective"
-                                && name != "prospective.pre_cf_migration"
-                                && name != "todos"
-                                && name != "todos.pre_cf_migration"
-                                && name != "shared"
+                            if !crate::validation::RESERVED_USER_IDS.contains(&name)
+                                && !name.ends_with(".pre_cf_migration")
                             {
                                 users.push(name.to_string());
                             }
@@ -1143,6 +1549,34 @@ impl MultiUserMemoryManager {
         self.neural_ner.clone()
     }
 
+    /// Apply the configured secret redaction and PII scrubbing to memory
+    /// content before it is stored
+    pub fn sanitize_content(&self, content: &mut String, tags: &mut Vec<String>) {
+        if self.server_config.redact_secrets {
+            secrets::redact_memory(content, tags);
+        }
+        let pii = &self.server_config.pii_scrub;
+        if !pii.is_enabled() {
+            return;
+        }
+        let names: Vec<String> = if pii.scrubs(secrets::PiiCategory::Name) {
+            match self.neural_ner.extract(content) {
+                Ok(entities) => entities
+                    .into_iter()
+                    .filter(|e| e.entity_type == NerEntityType::Person)
+                    .map(|e| e.text)
+                    .collect(),
+                Err(e) => {
+                    tracing::debug!("NER extraction for PII scrubbing failed: {}", e);
+                    Vec::new()
+                }
+            }
+        } else {
+            Vec::new()
+        };
+        secrets::scrub_memory(content, tags, pii, &names);
+    }
+
     /// Get keyword extractor for statistical term extraction
     pub fn get_keyword_extractor(&self) -> Arc<KeywordExtractor> {
         self.keyword_extractor.clone()
@@ -1150,6 +1584,7 @@ impl MultiUserMemoryManager {
 
     /// Get or create graph memory for a user
     pub fn get_user_graph(&self, user_id: &str) -> Result<Arc<parking_lot::RwLock<GraphMemory>>> {
+        crate::tenant::check_access(user_id)?;
         if let Some(graph) = self.graph_memories.get(user_id) {
             return Ok(graph);
         }
@@ -1179,10 +1614,12 @@ impl MultiUserMemoryManager {
             .maintenance_cycle
             .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
-        // Heavy cycle every 6th iteration (6 hours at 3600s intervals).
         // Heavy cycles run replay, entity-entity strengthening, fact extraction (full memory scan),
-        // and flush databases (triggers compaction). Light cycles only touch in-memory data.
-        let is_heavy = cycle % 6 == 0;
+        // duplicate merging, index repair and flush databases (triggers compaction). Light cycles
+        // only touch in-memory data. Without a sleep window/idle threshold heavy cycles run every
+        // 6th iteration; with one they wait for the sleep phase (see crate::sleep).
+        let cycle_start = std::time::Instant::now();
+        let is_heavy = self.sleep.should_run_heavy(cycle, chrono::Utc::now());
 
         if is_heavy {
             tracing::info!(
@@ -1194,6 +1631,9 @@ impl MultiUserMemoryManager {
         }
 
         let decay_factor = self.server_config.activation_decay_factor;
+        let retention = self
+            .memory_types
+            .retention_policy(&self.server_config.retention);
         let mut total_processed = 0;
 
         let user_ids: Vec<String> = self
@@ -1203,6 +1643,7 @@ impl MultiUserMemoryManager {
             .collect();
 
         let user_count = user_ids.len();
+        self.sleep.begin_cycle(is_heavy, user_count);
         let mut edges_decayed = 0;
         let mut edges_strengthened = 0;
         let mut entity_edges_strengthened = 0;
@@ -1375,6 +1816,83 @@ impl MultiUserMemoryManager {
                     }
                 }
             }
+
+            // Heavy cycle: delete memories past their type's retention period
+            if is_heavy && !retention.is_empty() {
+                if let Ok(memory_lock) = self.get_user_memory(&user_id) {
+                    let expired = memory_lock.read().apply_retention(&retention, false);
+                    match expired {
+                        Ok(expired) => {
+                            for item in &expired {
+                                self.log_event(
+                                    &user_id,
+                                    "RETENTION_EXPIRED",
+                                    &item.memory.id.0.to_string(),
+                                    &format!(
+                                        "{:?} memory expired ({:?})",
+                                        item.memory.experience.experience_type, item.rule
+                                    ),
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            tracing::warn!("Retention reaper failed for user {}: {}", user_id, e);
+                        }
+                    }
+                }
+            }
+
+            // Heavy cycle: permanently delete memories that outstayed the trash window
+            if is_heavy && self.server_config.trash_retention_days > 0 {
+                if let Ok(memory_lock) = self.get_user_memory(&user_id) {
+                    let cutoff = chrono::Utc::now()
+                        - chrono::Duration::days(
+                            self.server_config.trash_retention_days.min(36_500) as i64,
+                        );
+                    match memory_lock.read().purge_trash(cutoff) {
+                        Ok(purged) => {
+                            for id in &purged {
+                                self.log_event(
+                                    &user_id,
+                                    "TRASH_PURGED",
+                                    &id.0.to_string(),
+                                    "Deleted permanently after the trash window",
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            tracing::warn!("Trash purge failed for user {}: {}", user_id, e);
+                        }
+                    }
+                }
+            }
+
+            // Heavy cycle: evict over-quota memories (the byte limit is only
+            // checked here, since it needs a storage scan)
+            if is_heavy && !self.server_config.quota.is_unlimited() {
+                if let Ok(memory_lock) = self.get_user_memory(&user_id) {
+                    let evicted = memory_lock.read().enforce_quota(None);
+                    match evicted {
+                        Ok(evicted) => {
+                            for eviction in &evicted {
+                                self.log_event(
+                                    &user_id,
+                                    "QUOTA_EVICTED",
+                                    &eviction.memory.id.0.to_string(),
+                                    &format!(
+                                        "Evicted over quota (importance x recency {:.3})",
+                                        eviction.score
+                                    ),
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            tracing::warn!("Quota enforcement failed for user {}: {}", user_id, e);
+                        }
+                    }
+                }
+            }
+            self.sleep.user_done();
         }
 
         // Heavy cycle: clean up old triggered/dismissed reminders (C4 fix)
@@ -1422,6 +1940,19 @@ impl MultiUserMemoryManager {
             user_count
         );
 
+        self.sleep
+            .finish_cycle(is_heavy.then(|| crate::sleep::HeavyCycleSummary {
+                cycle,
+                users: user_count,
+                memories_processed: total_processed,
+                facts_extracted: total_facts_extracted,
+                facts_reinforced: total_facts_reinforced,
+                edges_strengthened,
+                edges_pruned: edges_decayed,
+                duration_ms: cycle_start.elapsed().as_millis() as u64,
+                completed_at: Some(chrono::Utc::now()),
+            }));
+
         total_processed
     }
 
@@ -1465,6 +1996,95 @@ impl MultiUserMemoryManager {
         &self.session_store
     }
 
+    /// End a session and apply retrospective reinforcement to the memories
+    /// surfaced during it (see [`Session::retrospective`])
+    pub fn end_session(&self, session_id: &SessionId, reason: &str) -> Option<Session> {
+        let session = self.session_store.end_session(session_id, reason)?;
+        self.reinforce_session_retrospective(&session);
+        Some(session)
+    }
+
+    /// End sessions past the session timeout, reinforcing each like [`Self::end_session`].
+    /// Runs idle for longer than `RUN_IDLE_TIMEOUT_SECS` are summarized as well.
+    pub fn cleanup_stale_sessions(&self) -> usize {
+        let ended = self.session_store.cleanup_stale_sessions();
+        for session in &ended {
+            self.reinforce_session_retrospective(session);
+        }
+
+        let idle_timeout = std::time::Duration::from_secs(RUN_IDLE_TIMEOUT_SECS);
+        let idle_runs: Vec<(String, String)> = self
+            .active_runs
+            .iter()
+            .filter(|entry| entry.value().elapsed() >= idle_timeout)
+            .map(|entry| entry.key().clone())
+            .collect();
+        for (user_id, run_id) in idle_runs {
+            if let Err(e) = self.end_run(&user_id, &run_id) {
+                tracing::warn!("Failed to summarize idle run {}: {}", run_id, e);
+            }
+        }
+
+        ended.len()
+    }
+
+    /// Record that a run stored a memory, postponing its idle summary
+    pub fn touch_run(&self, user_id: &str, run_id: &str) {
+        self.active_runs.insert(
+            (user_id.to_string(), run_id.to_string()),
+            std::time::Instant::now(),
+        );
+    }
+
+    /// Stop tracking a run and store its summary memory
+    /// (see [`MemorySystem::summarize_run`])
+    pub fn end_run(&self, user_id: &str, run_id: &str) -> Result<Option<MemoryId>> {
+        self.active_runs
+            .remove(&(user_id.to_string(), run_id.to_string()));
+        let memory = self.get_user_memory(user_id)?;
+        let summary_id = memory.read().summarize_run(run_id)?;
+        if let Some(ref id) = summary_id {
+            tracing::debug!(user_id, run_id, memory_id = %id.0, "Stored run summary");
+        }
+        Ok(summary_id)
+    }
+
+    fn reinforce_session_retrospective(&self, session: &Session) {
+        let Some((ids, outcome, strength)) = session.retrospective() else {
+            return;
+        };
+        let memory_ids: Vec<MemoryId> = ids
+            .iter()
+            .filter_map(|id| uuid::Uuid::parse_str(id).ok())
+            .map(MemoryId)
+            .collect();
+        let memory = match self.get_user_memory(&session.user_id) {
+            Ok(memory) => memory,
+            Err(e) => {
+                tracing::warn!(
+                    "Skipping retrospective reinforcement for {}: {}",
+                    session.user_id,
+                    e
+                );
+                return;
+            }
+        };
+        match memory
+            .read()
+            .reinforce_recall_weighted(&memory_ids, outcome, strength)
+        {
+            Ok(stats) => tracing::debug!(
+                user_id = %session.user_id,
+                session_id = %session.id.0,
+                processed = stats.memories_processed,
+                outcome = ?stats.outcome,
+                strength,
+                "Session retrospective reinforcement applied"
+            ),
+            Err(e) => tracing::warn!("Session retrospective reinforcement failed: {}", e),
+        }
+    }
+
     /// Get context sessions
     pub fn context_sessions(&self) -> &Arc<ContextSessions> {
         &self.context_sessions
@@ -1571,6 +2191,24 @@ impl MultiUserMemoryManager {
         vec![("shared".to_string(), std::sync::Arc::clone(&self.shared_db))]
     }
 
+    /// Copy one of a user's backups to the configured object store, if any
+    ///
+    /// Blocking. Failures are logged; the local backup stays authoritative.
+    pub fn upload_backup_offsite(&self, user_id: &str, backup_id: u32) {
+        let Some(sink) = &self.backup_sink else {
+            return;
+        };
+        let backup_dir = self.backup_engine.backup_path().join(user_id);
+        if let Err(e) = sink.upload_backup(&backup_dir, user_id, backup_id) {
+            tracing::warn!(
+                user_id = user_id,
+                backup_id = backup_id,
+                error = %e,
+                "Failed to upload backup to object store"
+            );
+        }
+    }
+
     /// Run backups for all active users
     pub fn run_backup_all_users(&self, max_backups: usize) -> usize {
         let mut backed_up = 0;
@@ -1617,6 +2255,7 @@ impl MultiUserMemoryManager {
                                 "Backup created successfully"
                             );
                             backed_up += 1;
+                            self.upload_backup_offsite(name, metadata.backup_id);
 
                             if let Err(e) = self.backup_engine.purge_old_backups(name, max_backups)
                             {
@@ -1886,6 +2525,39 @@ impl MultiUserMemoryManager {
             })
             .collect();
 
+        // Extract code entities (repositories, libraries, files, services)
+        let code_entities: Vec<(String, EntityNode)> = extract_code_entities(&experience.content)
+            .into_iter()
+            .filter_map(|(name, label)| {
+                if known_names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+                    return None;
+                }
+                known_names.push(name.clone());
+                let salience = match label {
+                    "Repository" => 0.75,
+                    "Service" => 0.7,
+                    "Library" => 0.65,
+                    _ => 0.6,
+                };
+                Some((
+                    name.clone(),
+                    EntityNode {
+                        uuid: uuid::Uuid::new_v4(),
+                        name,
+                        labels: vec![EntityLabel::Other(label.to_string())],
+                        created_at: now,
+                        last_seen_at: now,
+                        mention_count: 1,
+                        summary: String::new(),
+                        attributes: HashMap::new(),
+                        name_embedding: None,
+                        salience,
+                        is_proper_noun: true,
+                    },
+                ))
+            })
+            .collect();
+
         // Extract verbs for multi-hop reasoning
         let analysis = query_parser::analyze_query(&experience.content);
         let mut verb_entities: Vec<(String, EntityNode)> = Vec::new();
@@ -1940,6 +2612,7 @@ impl MultiUserMemoryManager {
             .chain(tag_entities)
             .chain(allcaps_entities)
             .chain(issue_entities)
+            .chain(code_entities)
             .chain(verb_entities)
             .collect();
         all_entities.sort_by(|a, b| b.1.salience.total_cmp(&a.1.salience));
@@ -2032,3 +2705,27 @@ impl MultiUserMemoryManager {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_code_entities() {
+        let text = "Cloned github.com/acme/widgets.git, ran cargo add serde@1.0 and \
+                    pip install requests==2.31, then fixed `src/auth/jwt.rs` so the \
+                    billing service stops timing out.";
+        let entities = extract_code_entities(text);
+        assert_eq!(
+            entities,
+            vec![
+                ("acme/widgets".to_string(), "Repository"),
+                ("serde".to_string(), "Library"),
+                ("requests".to_string(), "Library"),
+                ("src/auth/jwt.rs".to_string(), "File"),
+                ("billing service".to_string(), "Service"),
+            ]
+        );
+        assert!(extract_code_entities("Restarted the service").is_empty());
+    }
+}
@@ -0,0 +1,322 @@
+//! Brain Statistics Handlers
+//!
+//! GET /api/admin/stats answers "how big is this brain and what's in it":
+//! memory counts per user, memory type, tag and source, ingestion per day,
+//! vector index and graph sizes, and storage bytes. Every user's memories are
+//! scanned, and users not in memory are loaded to do so, so this is meant for
+//! operators rather than dashboards polling it.
+
+use std::collections::{BTreeMap, HashMap};
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+    Extension,
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::acl::require_admin;
+use super::state::MultiUserMemoryManager;
+use crate::auth::AuthenticatedKey;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::memory::{Memory, Provenance, ProvenanceSource};
+use crate::validation;
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+const DEFAULT_DAYS: i64 = 30;
+const MAX_DAYS: i64 = 365;
+const DEFAULT_TOP_TAGS: usize = 50;
+const MAX_TOP_TAGS: usize = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct AdminStatsQuery {
+    /// Only this user (default: every user)
+    pub user_id: Option<String>,
+    /// Days of ingestion history to report (default 30, max 365)
+    pub days: Option<i64>,
+    /// Most used tags to report (default 50, max 500)
+    pub top_tags: Option<usize>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct StatsTotals {
+    pub users: usize,
+    pub memories: usize,
+    /// Serialized size of stored memories
+    pub storage_bytes: usize,
+    /// On-disk size of the storage directory, including indexes and shared data
+    pub disk_bytes: u64,
+    pub vector_index_entries: usize,
+    pub graph_nodes: usize,
+    pub graph_edges: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserStats {
+    pub user_id: String,
+    pub memories: usize,
+    pub storage_bytes: usize,
+    pub disk_bytes: u64,
+    pub vector_index_entries: usize,
+    pub graph_nodes: usize,
+    pub graph_edges: usize,
+    pub last_memory_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IngestionDay {
+    pub date: NaiveDate,
+    pub memories: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IngestionStats {
+    pub days: i64,
+    /// Memories created in the window, per day
+    pub per_day_average: f64,
+    /// Oldest day first; days without memories included
+    pub daily: Vec<IngestionDay>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminStatsResponse {
+    pub totals: StatsTotals,
+    /// Users by memory count, largest first
+    pub users: Vec<UserStats>,
+    pub memory_types: BTreeMap<String, usize>,
+    pub sources: BTreeMap<String, usize>,
+    /// Most used tags, most used first
+    pub tags: Vec<TagCount>,
+    pub ingestion: IngestionStats,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Where a memory came from: its provenance, with integrations named by
+/// their external id prefix (e.g. "integration:linear")
+fn source_of(memory: &Memory) -> String {
+    match Provenance::read_from(&memory.experience.metadata).map(|p| p.source) {
+        Some(ProvenanceSource::Integration) => match memory
+            .external_id
+            .as_deref()
+            .and_then(|id| id.split_once(':'))
+        {
+            Some((integration, _)) => format!("integration:{integration}"),
+            None => ProvenanceSource::Integration.as_str().to_string(),
+        },
+        Some(source) => source.as_str().to_string(),
+        None => "unknown".to_string(),
+    }
+}
+
+/// Counts across every scanned memory
+struct Tally {
+    since: DateTime<Utc>,
+    memory_types: BTreeMap<String, usize>,
+    sources: BTreeMap<String, usize>,
+    tags: HashMap<String, usize>,
+    daily: BTreeMap<NaiveDate, usize>,
+}
+
+impl Tally {
+    fn new(since: DateTime<Utc>) -> Self {
+        Self {
+            since,
+            memory_types: BTreeMap::new(),
+            sources: BTreeMap::new(),
+            tags: HashMap::new(),
+            daily: BTreeMap::new(),
+        }
+    }
+
+    fn add(&mut self, memory: &Memory) {
+        let memory_type = memory
+            .custom_type()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{:?}", memory.experience.experience_type));
+        *self.memory_types.entry(memory_type).or_default() += 1;
+        *self.sources.entry(source_of(memory)).or_default() += 1;
+        for tag in &memory.experience.tags {
+            *self.tags.entry(tag.clone()).or_default() += 1;
+        }
+        if memory.created_at >= self.since {
+            *self
+                .daily
+                .entry(memory.created_at.date_naive())
+                .or_default() += 1;
+        }
+    }
+}
+
+/// Scan `user_ids` and build the report (blocking: full scans and disk walks)
+fn collect_stats(
+    state: &MultiUserMemoryManager,
+    user_ids: Vec<String>,
+    days: i64,
+    top_tags: usize,
+) -> anyhow::Result<AdminStatsResponse> {
+    let now = Utc::now();
+    let first_day = (now - Duration::days(days - 1)).date_naive();
+    let since = first_day
+        .and_hms_opt(0, 0, 0)
+        .map(|t| t.and_utc())
+        .unwrap_or(now);
+    let mut tally = Tally::new(since);
+    let mut totals = StatsTotals::default();
+    let mut users = Vec::with_capacity(user_ids.len());
+
+    for user_id in user_ids {
+        let stats = state.get_stats(&user_id)?;
+        let memory = state.get_user_memory(&user_id)?;
+        let (storage_bytes, last_memory_at, memories) = {
+            let guard = memory.read();
+            let memories = guard.get_all_memories()?;
+            let mut count = 0;
+            let mut last_memory_at: Option<DateTime<Utc>> = None;
+            for memory in memories.iter().filter(|m| !m.is_forgotten()) {
+                tally.add(memory);
+                count += 1;
+                last_memory_at = last_memory_at.max(Some(memory.created_at));
+            }
+            (guard.quota_status()?.storage_bytes, last_memory_at, count)
+        };
+        let disk_bytes = crate::backup::dir_size(&state.base_path.join(&user_id)).unwrap_or(0);
+
+        totals.memories += memories;
+        totals.storage_bytes += storage_bytes;
+        totals.vector_index_entries += stats.vector_index_count;
+        totals.graph_nodes += stats.graph_nodes;
+        totals.graph_edges += stats.graph_edges;
+        users.push(UserStats {
+            user_id,
+            memories,
+            storage_bytes,
+            disk_bytes,
+            vector_index_entries: stats.vector_index_count,
+            graph_nodes: stats.graph_nodes,
+            graph_edges: stats.graph_edges,
+            last_memory_at,
+        });
+    }
+    totals.users = users.len();
+    totals.disk_bytes = crate::backup::dir_size(&state.base_path).unwrap_or(0);
+    users.sort_by(|a, b| {
+        b.memories
+            .cmp(&a.memories)
+            .then_with(|| a.user_id.cmp(&b.user_id))
+    });
+
+    let mut tags: Vec<TagCount> = tally
+        .tags
+        .into_iter()
+        .map(|(tag, count)| TagCount { tag, count })
+        .collect();
+    tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+    tags.truncate(top_tags);
+
+    let daily: Vec<IngestionDay> = first_day
+        .iter_days()
+        .take(days as usize)
+        .map(|date| IngestionDay {
+            date,
+            memories: tally.daily.get(&date).copied().unwrap_or(0),
+        })
+        .collect();
+    let in_window: usize = daily.iter().map(|d| d.memories).sum();
+
+    Ok(AdminStatsResponse {
+        totals,
+        users,
+        memory_types: tally.memory_types,
+        sources: tally.sources,
+        tags,
+        ingestion: IngestionStats {
+            days,
+            per_day_average: in_window as f64 / days as f64,
+            daily,
+        },
+        generated_at: now,
+    })
+}
+
+/// GET /api/admin/stats - What the brain holds, by user, type, tag and source
+pub async fn admin_stats(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Query(query): Query<AdminStatsQuery>,
+) -> Result<Json<AdminStatsResponse>, AppError> {
+    require_admin(key.as_deref(), "Brain statistics")?;
+    let days = query.days.unwrap_or(DEFAULT_DAYS);
+    if !(1..=MAX_DAYS).contains(&days) {
+        return Err(AppError::InvalidInput {
+            field: "days".to_string(),
+            reason: format!("must be between 1 and {MAX_DAYS}"),
+        });
+    }
+    let top_tags = query.top_tags.unwrap_or(DEFAULT_TOP_TAGS).min(MAX_TOP_TAGS);
+    let user_ids = match query.user_id {
+        Some(user_id) => {
+            validation::validate_user_id(&user_id).map_validation_err("user_id")?;
+            vec![user_id]
+        }
+        None => state.list_users(),
+    };
+
+    let response =
+        tokio::task::spawn_blocking(move || collect_stats(&state, user_ids, days, top_tags))
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+            .map_err(AppError::Internal)?;
+    Ok(Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{Experience, MemoryId};
+
+    fn memory(metadata: HashMap<String, String>, external_id: Option<&str>) -> Memory {
+        let mut memory = Memory::new(
+            MemoryId(uuid::Uuid::new_v4()),
+            Experience {
+                content: "Ship the migration on Tuesday".to_string(),
+                tags: vec!["release".to_string()],
+                metadata,
+                ..Default::default()
+            },
+            0.5,
+            None,
+            None,
+            None,
+            None,
+        );
+        memory.external_id = external_id.map(str::to_string);
+        memory
+    }
+
+    #[test]
+    fn test_source_of() {
+        let integration = Provenance::new(ProvenanceSource::Integration).to_metadata();
+        assert_eq!(
+            source_of(&memory(integration.clone(), Some("linear:ENG-42"))),
+            "integration:linear"
+        );
+        assert_eq!(source_of(&memory(integration, None)), "integration");
+        assert_eq!(
+            source_of(&memory(
+                Provenance::new(ProvenanceSource::Cortex).to_metadata(),
+                None
+            )),
+            "cortex"
+        );
+        assert_eq!(source_of(&memory(HashMap::new(), None)), "unknown");
+    }
+}
@@ -0,0 +1,145 @@
+//! Suppression Handlers
+//!
+//! /api/suppress keeps memories from surfacing for a user without deleting
+//! them: a rule names one memory (`memory_id`) or a content regex
+//! (`pattern`). Matching memories are skipped by recall, search, proactive
+//! context and the review queue from the next request on, but stay stored,
+//! listed and exportable. Deleting the rule lets them surface again.
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+
+use super::crud::resolve_memory;
+use super::state::MultiUserMemoryManager;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::memory::suppression::SuppressionRule;
+use crate::validation;
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// Request for POST /api/suppress: exactly one of `memory_id` and `pattern`
+#[derive(Debug, Deserialize)]
+pub struct SuppressRequest {
+    pub user_id: String,
+    /// Memory to suppress (full UUID or 8+ char prefix)
+    #[serde(default)]
+    pub memory_id: Option<String>,
+    /// Suppress memories whose content matches this regex
+    #[serde(default)]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// POST /api/suppress - Stop a memory, or memories matching a pattern, from surfacing
+#[tracing::instrument(skip(state, req), fields(user_id = %req.user_id))]
+pub async fn suppress(
+    State(state): State<AppState>,
+    Json(req): Json<SuppressRequest>,
+) -> Result<Json<SuppressionRule>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let (memory_id, pattern) = match (req.memory_id.as_deref(), req.pattern.as_deref()) {
+        (Some(memory_id), None) => {
+            let memory = state
+                .get_user_memory(&req.user_id)
+                .map_err(AppError::Internal)?;
+            let resolved = resolve_memory(&memory.read(), memory_id)?.id.clone();
+            (Some(resolved), None)
+        }
+        (None, Some(pattern)) => {
+            validation::validate_and_compile_pattern(pattern).map_validation_err("pattern")?;
+            (None, Some(pattern.to_string()))
+        }
+        _ => {
+            return Err(AppError::InvalidInput {
+                field: "memory_id".to_string(),
+                reason: "exactly one of memory_id and pattern is required".to_string(),
+            })
+        }
+    };
+
+    let rule = SuppressionRule {
+        id: uuid::Uuid::new_v4().to_string(),
+        memory_id,
+        pattern,
+        reason: req.reason,
+        created_at: chrono::Utc::now(),
+    };
+    state
+        .suppressions
+        .add(&req.user_id, &rule)
+        .map_err(AppError::Internal)?;
+    state.apply_suppressions(&req.user_id);
+
+    let target = match (&rule.memory_id, &rule.pattern) {
+        (Some(id), _) => id.0.to_string(),
+        (None, Some(pattern)) => pattern.clone(),
+        (None, None) => String::new(),
+    };
+    state.log_event(&req.user_id, "SUPPRESS", &target, "Suppression rule added");
+
+    Ok(Json(rule))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuppressionsQuery {
+    pub user_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SuppressionListResponse {
+    pub rules: Vec<SuppressionRule>,
+    pub count: usize,
+}
+
+/// GET /api/suppress?user_id=... - A user's suppression rules, oldest first
+pub async fn list_suppressions(
+    State(state): State<AppState>,
+    Query(query): Query<SuppressionsQuery>,
+) -> Result<Json<SuppressionListResponse>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+    let rules = state
+        .suppressions
+        .list(&query.user_id)
+        .map_err(AppError::Internal)?;
+    Ok(Json(SuppressionListResponse {
+        count: rules.len(),
+        rules,
+    }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnsuppressResponse {
+    /// False if the user had no such rule
+    pub removed: bool,
+    pub rule_id: String,
+}
+
+/// DELETE /api/suppress/{rule_id}?user_id=... - Remove a rule so its memories surface again
+#[tracing::instrument(skip(state, query), fields(user_id = %query.user_id))]
+pub async fn unsuppress(
+    State(state): State<AppState>,
+    Path(rule_id): Path<String>,
+    Query(query): Query<SuppressionsQuery>,
+) -> Result<Json<UnsuppressResponse>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+    let removed = state
+        .suppressions
+        .remove(&query.user_id, &rule_id)
+        .map_err(AppError::Internal)?;
+    if removed {
+        state.apply_suppressions(&query.user_id);
+        state.log_event(
+            &query.user_id,
+            "UNSUPPRESS",
+            &rule_id,
+            "Suppression rule removed",
+        );
+    }
+    Ok(Json(UnsuppressResponse { removed, rule_id }))
+}
@@ -0,0 +1,234 @@
+//! Tag Management Handlers
+//!
+//! List tags with usage counts and curate them across all of a user's
+//! memories: rename, merge several into one, or delete.
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+
+use super::state::MultiUserMemoryManager;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::validation;
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// Query parameters for GET /api/tags
+#[derive(Debug, Deserialize)]
+pub struct ListTagsQuery {
+    pub user_id: String,
+    /// Only tags starting with this prefix (e.g. "tool:")
+    pub prefix: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListTagsResponse {
+    pub tags: Vec<TagCount>,
+    pub total: usize,
+}
+
+/// Request for POST /api/tags/rename
+#[derive(Debug, Deserialize)]
+pub struct RenameTagRequest {
+    pub user_id: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// Request for POST /api/tags/merge
+#[derive(Debug, Deserialize)]
+pub struct MergeTagsRequest {
+    pub user_id: String,
+    /// Tags folded into `target`
+    pub sources: Vec<String>,
+    pub target: String,
+}
+
+/// Request for POST /api/tags/delete
+#[derive(Debug, Deserialize)]
+pub struct DeleteTagRequest {
+    pub user_id: String,
+    pub tag: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagChangeResponse {
+    pub success: bool,
+    pub memories_updated: usize,
+    pub message: String,
+}
+
+fn require_tag(field: &str, tag: &str) -> Result<(), AppError> {
+    if tag.trim().is_empty() {
+        return Err(AppError::InvalidInput {
+            field: field.to_string(),
+            reason: "tag must not be empty".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// GET /api/tags?user_id=...&prefix=... - Tags with memory counts, most used first
+#[tracing::instrument(skip(state), fields(user_id = %params.user_id))]
+pub async fn list_tags(
+    State(state): State<AppState>,
+    Query(params): Query<ListTagsQuery>,
+) -> Result<Json<ListTagsResponse>, AppError> {
+    validation::validate_user_id(&params.user_id).map_validation_err("user_id")?;
+
+    let memory = state
+        .get_user_memory(&params.user_id)
+        .map_err(AppError::Internal)?;
+
+    let counts = tokio::task::spawn_blocking(move || memory.read().tag_counts())
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+        .map_err(AppError::Internal)?;
+
+    let tags: Vec<TagCount> = counts
+        .into_iter()
+        .filter(|(tag, _)| {
+            params
+                .prefix
+                .as_deref()
+                .is_none_or(|prefix| tag.starts_with(prefix))
+        })
+        .map(|(tag, count)| TagCount { tag, count })
+        .collect();
+
+    Ok(Json(ListTagsResponse {
+        total: tags.len(),
+        tags,
+    }))
+}
+
+/// Apply a tag replacement off the async runtime and record it in the event log
+async fn replace_tags(
+    state: AppState,
+    user_id: &str,
+    from: Vec<String>,
+    to: Option<String>,
+    action: &str,
+) -> Result<usize, AppError> {
+    let memory = state.get_user_memory(user_id).map_err(AppError::Internal)?;
+
+    let target = to.clone();
+    let sources = from.clone();
+    let updated = tokio::task::spawn_blocking(move || {
+        memory.read().replace_tags(&sources, target.as_deref())
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+    .map_err(AppError::Internal)?;
+
+    let description = match &to {
+        Some(to) => format!("{} -> {}", from.join(", "), to),
+        None => from.join(", "),
+    };
+    state.log_event(
+        user_id,
+        action,
+        &description,
+        &format!("Updated {} memories", updated.len()),
+    );
+
+    Ok(updated.len())
+}
+
+/// POST /api/tags/rename - Rename a tag across all memories
+#[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
+pub async fn rename_tag(
+    State(state): State<AppState>,
+    Json(req): Json<RenameTagRequest>,
+) -> Result<Json<TagChangeResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+    require_tag("from", &req.from)?;
+    require_tag("to", &req.to)?;
+
+    let count = replace_tags(
+        state,
+        &req.user_id,
+        vec![req.from.clone()],
+        Some(req.to.clone()),
+        "TAG_RENAME",
+    )
+    .await?;
+
+    Ok(Json(TagChangeResponse {
+        success: true,
+        memories_updated: count,
+        message: format!("Renamed '{}' to '{}' on {count} memories", req.from, req.to),
+    }))
+}
+
+/// POST /api/tags/merge - Fold several tags into one
+#[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
+pub async fn merge_tags(
+    State(state): State<AppState>,
+    Json(req): Json<MergeTagsRequest>,
+) -> Result<Json<TagChangeResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+    require_tag("target", &req.target)?;
+    if req.sources.is_empty() {
+        return Err(AppError::InvalidInput {
+            field: "sources".to_string(),
+            reason: "at least one source tag is required".to_string(),
+        });
+    }
+    for tag in &req.sources {
+        require_tag("sources", tag)?;
+    }
+
+    let count = replace_tags(
+        state,
+        &req.user_id,
+        req.sources.clone(),
+        Some(req.target.clone()),
+        "TAG_MERGE",
+    )
+    .await?;
+
+    Ok(Json(TagChangeResponse {
+        success: true,
+        memories_updated: count,
+        message: format!(
+            "Merged {} tag(s) into '{}' on {count} memories",
+            req.sources.len(),
+            req.target
+        ),
+    }))
+}
+
+/// POST /api/tags/delete - Remove a tag from all memories (the memories are kept)
+#[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
+pub async fn delete_tag(
+    State(state): State<AppState>,
+    Json(req): Json<DeleteTagRequest>,
+) -> Result<Json<TagChangeResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+    require_tag("tag", &req.tag)?;
+
+    let count = replace_tags(
+        state,
+        &req.user_id,
+        vec![req.tag.clone()],
+        None,
+        "TAG_DELETE",
+    )
+    .await?;
+
+    Ok(Json(TagChangeResponse {
+        success: true,
+        memories_updated: count,
+        message: format!("Removed '{}' from {count} memories", req.tag),
+    }))
+}
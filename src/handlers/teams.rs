@@ -0,0 +1,168 @@
+//! Team Namespace Handlers
+//!
+//! Memories are private to the user who stored them unless stored with
+//! `visibility: "team"`, which writes them to a shared team namespace instead.
+//! Recall and proactive_context read the caller's team namespace alongside
+//! their own memories, so decisions and preferences learned in one member's
+//! sessions reach the whole team.
+//!
+//! The team comes from a `team_id` request field or the `X-Shodh-Team` header
+//! (sent by the MCP server and hooks when `SHODH_TEAM` is set). Once ACL
+//! grants are in use, only keys granted on `teams/{team_id}` are members and
+//! may read or share to it (see `crate::acl`).
+
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+
+use super::state::MultiUserMemoryManager;
+use super::tenants::TenantPath;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::validation;
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// Header carrying the caller's team
+pub const TEAM_HEADER: &str = "X-Shodh-Team";
+
+/// Directory under the storage root holding team namespaces (a reserved user ID)
+pub const TEAMS_DIR: &str = "teams";
+
+/// Metadata key on team memories naming the member who stored them
+pub const TEAM_AUTHOR_KEY: &str = "team_author";
+
+/// Largest listing returned by GET /api/teams/{team_id}/memories
+const MAX_TEAM_MEMORIES_LIMIT: usize = 500;
+
+/// Who can see a stored memory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    /// Only the storing user (default)
+    #[default]
+    Private,
+    /// Every member of the caller's team
+    Team,
+}
+
+/// Storage key for a team namespace
+pub fn team_namespace(team_id: &str) -> String {
+    format!("{TEAMS_DIR}/{team_id}")
+}
+
+/// Team for a request: an explicit `team_id` field wins over the header
+pub fn resolve_team(
+    headers: &HeaderMap,
+    team_id: Option<&str>,
+) -> Result<Option<String>, AppError> {
+    let team = team_id
+        .map(str::to_string)
+        .or_else(|| {
+            headers
+                .get(TEAM_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_string())
+        })
+        .filter(|t| !t.is_empty());
+    if let Some(ref team) = team {
+        validation::validate_user_id(team).map_validation_err("team_id")?;
+    }
+    Ok(team)
+}
+
+/// Query parameters for GET /api/teams/{team_id}/memories
+#[derive(Debug, Deserialize)]
+pub struct TeamMemoriesQuery {
+    #[serde(default = "default_team_memories_limit")]
+    pub limit: usize,
+}
+
+fn default_team_memories_limit() -> usize {
+    50
+}
+
+#[derive(Debug, Serialize)]
+pub struct TeamMemoryItem {
+    pub id: String,
+    pub content: String,
+    pub memory_type: String,
+    pub importance: f32,
+    pub created_at: String,
+    /// Member who shared this memory
+    pub shared_by: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TeamMemoriesResponse {
+    pub team_id: String,
+    pub memories: Vec<TeamMemoryItem>,
+    pub count: usize,
+}
+
+/// GET /api/teams/{team_id}/memories?limit=50 - Team-visible memories, newest first
+#[tracing::instrument(skip(state), fields(team_id = %team_id))]
+pub async fn list_team_memories(
+    State(state): State<AppState>,
+    TenantPath(team_id): TenantPath,
+    Query(params): Query<TeamMemoriesQuery>,
+) -> Result<Json<TeamMemoriesResponse>, AppError> {
+    validation::validate_user_id(&team_id).map_validation_err("team_id")?;
+
+    let memory = state
+        .get_team_memory(&team_id)
+        .map_err(AppError::Internal)?;
+
+    let limit = params.limit.clamp(1, MAX_TEAM_MEMORIES_LIMIT);
+    let mut memories = tokio::task::spawn_blocking(move || memory.read().get_all_memories())
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+        .map_err(AppError::Internal)?;
+    memories.retain(|m| !m.is_forgotten());
+    memories.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    memories.truncate(limit);
+
+    let memories: Vec<TeamMemoryItem> = memories
+        .iter()
+        .map(|m| TeamMemoryItem {
+            id: m.id.0.to_string(),
+            content: m.experience.content.clone(),
+            memory_type: format!("{:?}", m.experience.experience_type),
+            importance: m.importance(),
+            created_at: m.created_at.to_rfc3339(),
+            shared_by: m.experience.metadata.get(TEAM_AUTHOR_KEY).cloned(),
+        })
+        .collect();
+
+    Ok(Json(TeamMemoriesResponse {
+        team_id,
+        count: memories.len(),
+        memories,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_resolve_team_prefers_field_over_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(resolve_team(&headers, None).unwrap(), None);
+
+        headers.insert(TEAM_HEADER, HeaderValue::from_static("platform"));
+        assert_eq!(
+            resolve_team(&headers, None).unwrap().as_deref(),
+            Some("platform")
+        );
+        assert_eq!(
+            resolve_team(&headers, Some("infra")).unwrap().as_deref(),
+            Some("infra")
+        );
+        assert!(resolve_team(&headers, Some("../etc")).is_err());
+    }
+}
@@ -0,0 +1,420 @@
+//! Tenant Scoping Handlers
+//!
+//! `scope_tenant` runs on every protected route, ahead of `enforce_acl`. For
+//! a tenant key it qualifies the `user_id` and `team_id` the request names
+//! (query string, top-level JSON body fields and the `X-Shodh-Team` header)
+//! with the key's tenant, and strips the qualifier from JSON, text and SSE
+//! responses, so clients never see it. Path segments are qualified by the
+//! [`TenantPath`] extractor. Every body axum would parse as JSON is scoped,
+//! whatever `application/*+json` type it is sent as, and query keys are
+//! matched after percent-decoding. Ids nested deeper in a JSON body are not
+//! rewritten; they can't reach another tenant's data either, since the
+//! request is [`tenant::confine`]d to the caller's tenant.
+//!
+//! Tenant keys can't reach /api/admin/* or the WebSocket routes, whose user
+//! ids arrive in messages rather than the request. Other keys may not name a
+//! tenant-qualified id directly; admin keys may.
+//!
+//! Every request is counted against its tenant; GET /api/admin/tenants
+//! reports the counters along with each tenant's user count.
+
+use std::collections::BTreeMap;
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::{FromRequestParts, MatchedPath, RawPathParams, Request, State},
+    http::{header, request::Parts, HeaderValue, Uri},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+    Extension,
+};
+use futures::StreamExt;
+use serde::Serialize;
+
+use super::acl::{is_json, require_admin, required_permission, MAX_SCANNED_BODY_BYTES};
+use super::state::MultiUserMemoryManager;
+use super::teams::TEAM_HEADER;
+use crate::acl::Permission;
+use crate::auth::{self, AuthenticatedKey};
+use crate::errors::AppError;
+use crate::tenant::{self, RequestRecord, TenantUsageSnapshot, SEPARATOR};
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// Request fields holding a user or team id
+const SCOPED_FIELDS: &[&str] = &["user_id", "team_id"];
+
+/// Routes whose user ids don't come from the request (WebSocket messages)
+const UNSCOPED_ROUTES: &[&str] = &["/api/stream", "/api/context/monitor"];
+
+/// Id as stored for the caller: qualified with a tenant key's tenant, as sent
+/// otherwise. Only admin keys may send a qualified id themselves.
+pub(super) fn scope_id(
+    key: Option<&AuthenticatedKey>,
+    field: &str,
+    id: &str,
+) -> Result<String, AppError> {
+    if key.is_some_and(|k| k.admin) {
+        return Ok(id.to_string());
+    }
+    let tenant = key.and_then(|k| k.tenant.as_deref());
+    if (tenant.is_some() || auth::tenants_configured())
+        && (id.contains(SEPARATOR) || id.to_ascii_lowercase().contains("%7e"))
+    {
+        return Err(AppError::InvalidInput {
+            field: field.to_string(),
+            reason: format!("must not contain '{SEPARATOR}'"),
+        });
+    }
+    Ok(match tenant {
+        Some(tenant) => tenant::qualify(tenant, id),
+        None => id.to_string(),
+    })
+}
+
+/// Whether a request's ids need checking or rewriting
+fn needs_scoping(key: Option<&AuthenticatedKey>) -> bool {
+    match key {
+        Some(k) if k.admin => false,
+        Some(k) if k.tenant.is_some() => true,
+        _ => auth::tenants_configured(),
+    }
+}
+
+/// Query key as `Query` sees it: `+` as space, `%XX` escapes decoded
+fn decode_query_key(name: &str) -> String {
+    let hex = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    let bytes = name.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes.get(i..i + 3) {
+            Some([b'%', hi, lo]) => hex(*hi).zip(hex(*lo)).map(|(hi, lo)| (hi << 4) | lo),
+            _ => None,
+        };
+        match (escaped, bytes[i]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (None, b'+') => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (None, byte) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Scope `user_id` / `team_id` pairs of a raw query string. Keys are
+/// compared decoded, so `user%5Fid` is scoped like `user_id`.
+fn scope_query(key: Option<&AuthenticatedKey>, query: &str) -> Result<String, AppError> {
+    query
+        .split('&')
+        .map(|pair| {
+            let Some((name, value)) = pair.split_once('=') else {
+                return Ok(pair.to_string());
+            };
+            let name = decode_query_key(name);
+            match SCOPED_FIELDS.iter().find(|field| **field == name) {
+                Some(field) => Ok(format!("{field}={}", scope_id(key, field, value)?)),
+                None => Ok(pair.to_string()),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|pairs| pairs.join("&"))
+}
+
+/// Scope top-level `user_id` / `team_id` string fields of a JSON body.
+/// Bodies that aren't a JSON object are returned unchanged.
+fn scope_body(key: Option<&AuthenticatedKey>, bytes: Bytes) -> Result<Bytes, AppError> {
+    let Ok(serde_json::Value::Object(mut body)) = serde_json::from_slice(&bytes) else {
+        return Ok(bytes);
+    };
+    let mut changed = false;
+    for field in SCOPED_FIELDS {
+        if let Some(serde_json::Value::String(id)) = body.get_mut(*field) {
+            let scoped = scope_id(key, field, id)?;
+            changed |= scoped != *id;
+            *id = scoped;
+        }
+    }
+    if !changed {
+        return Ok(bytes);
+    }
+    serde_json::to_vec(&body)
+        .map(Bytes::from)
+        .map_err(|e| AppError::Internal(e.into()))
+}
+
+/// Rewrite the ids a request names for the caller's tenant
+async fn scope_request(
+    key: Option<&AuthenticatedKey>,
+    route: &str,
+    request: Request,
+) -> Result<Request, AppError> {
+    if key.is_some_and(|k| k.tenant.is_some())
+        && (route.starts_with("/api/admin/") || UNSCOPED_ROUTES.contains(&route))
+    {
+        return Err(AppError::Forbidden(format!(
+            "{route} is not available to tenant API keys"
+        )));
+    }
+
+    let (mut parts, body) = request.into_parts();
+    if let Some(query) = parts.uri.query() {
+        let scoped = scope_query(key, query)?;
+        if scoped != query {
+            let path_and_query = format!("{}?{scoped}", parts.uri.path());
+            let mut uri = parts.uri.clone().into_parts();
+            uri.path_and_query =
+                Some(path_and_query.parse().map_err(|_| AppError::InvalidInput {
+                    field: "query".to_string(),
+                    reason: "invalid query string".to_string(),
+                })?);
+            parts.uri = Uri::from_parts(uri).map_err(|e| AppError::Internal(e.into()))?;
+        }
+    }
+
+    if let Some(team) = parts
+        .headers
+        .get(TEAM_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+    {
+        let scoped = scope_id(key, "team_id", team)?;
+        let value = HeaderValue::from_str(&scoped).map_err(|e| AppError::Internal(e.into()))?;
+        parts.headers.insert(TEAM_HEADER, value);
+    }
+
+    let body = if is_json(&parts) {
+        let bytes = axum::body::to_bytes(body, MAX_SCANNED_BODY_BYTES)
+            .await
+            .map_err(|_| AppError::ContentTooLarge {
+                size: MAX_SCANNED_BODY_BYTES + 1,
+                max: MAX_SCANNED_BODY_BYTES,
+            })?;
+        let bytes = scope_body(key, bytes)?;
+        parts
+            .headers
+            .insert(header::CONTENT_LENGTH, bytes.len().into());
+        Body::from(bytes)
+    } else {
+        body
+    };
+    Ok(Request::from_parts(parts, body))
+}
+
+/// Strip `tenant`'s qualifier from ids in a JSON, text or event-stream response
+async fn unqualify_response(tenant: &str, response: Response) -> Response {
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let (mut parts, body) = response.into_parts();
+    if content_type.starts_with("text/event-stream")
+        || content_type.starts_with("application/x-ndjson")
+    {
+        // Streamed: rewrite each chunk as it goes (events are one chunk each)
+        let tenant = tenant.to_string();
+        let stream = body
+            .into_data_stream()
+            .map(move |chunk| chunk.map(|b| Bytes::from(tenant::strip_qualifiers(&tenant, &b))));
+        return Response::from_parts(parts, Body::from_stream(stream));
+    }
+    if !content_type.starts_with("application/json") && !content_type.starts_with("text/") {
+        return Response::from_parts(parts, body);
+    }
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => return AppError::Internal(anyhow::anyhow!("{e}")).into_response(),
+    };
+    let stripped = tenant::strip_qualifiers(tenant, &bytes);
+    parts
+        .headers
+        .insert(header::CONTENT_LENGTH, stripped.len().into());
+    Response::from_parts(parts, Body::from(stripped))
+}
+
+/// Middleware: scope request ids to the caller's tenant and count the request
+pub async fn scope_tenant(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let key = request.extensions().get::<AuthenticatedKey>().cloned();
+    let tenant = key.as_ref().and_then(|k| k.tenant.clone());
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let write = required_permission(request.method(), &route) == Permission::Write;
+    let bytes_in = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+
+    let response = if needs_scoping(key.as_ref()) {
+        match scope_request(key.as_ref(), &route, request).await {
+            Ok(request) => tenant::confine(tenant.clone(), next.run(request)).await,
+            Err(e) => e.into_response(),
+        }
+    } else {
+        next.run(request).await
+    };
+    let response = match tenant.as_deref() {
+        Some(tenant) => unqualify_response(tenant, response).await,
+        None => response,
+    };
+
+    state.tenants.record(
+        tenant.as_deref(),
+        RequestRecord {
+            write,
+            error: response.status().is_client_error() || response.status().is_server_error(),
+            bytes_in,
+            // Streamed responses have no known size and aren't counted
+            bytes_out: response.body().size_hint().exact().unwrap_or(0),
+        },
+    );
+    response
+}
+
+/// Path `{user_id}` or `{team_id}`, scoped to the caller's tenant
+pub struct TenantPath(pub String);
+
+impl<S: Send + Sync> FromRequestParts<S> for TenantPath {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let params = RawPathParams::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        let (field, id) = params
+            .iter()
+            .find(|(name, _)| SCOPED_FIELDS.contains(name))
+            .ok_or_else(|| {
+                AppError::Internal(anyhow::anyhow!("route has no user_id or team_id segment"))
+                    .into_response()
+            })?;
+        let key = parts.extensions.get::<AuthenticatedKey>();
+        scope_id(key, field, id)
+            .map(TenantPath)
+            .map_err(IntoResponse::into_response)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TenantSummary {
+    #[serde(flatten)]
+    pub usage: TenantUsageSnapshot,
+    /// Users with stored data
+    pub users: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TenantListResponse {
+    pub tenants: Vec<TenantSummary>,
+    pub count: usize,
+}
+
+/// GET /api/admin/tenants - Usage since startup and user count per tenant
+pub async fn list_tenants(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+) -> Result<Json<TenantListResponse>, AppError> {
+    require_admin(key.as_deref(), "Tenant usage")?;
+
+    let mut tenants: BTreeMap<String, TenantSummary> = state
+        .tenants
+        .snapshot()
+        .into_iter()
+        .map(|usage| (usage.tenant.clone(), TenantSummary { usage, users: 0 }))
+        .collect();
+    for user_id in state.list_users() {
+        let name = user_id
+            .split_once(SEPARATOR)
+            .map_or(tenant::DEFAULT_TENANT, |(tenant, _)| tenant);
+        tenants
+            .entry(name.to_string())
+            .or_insert_with(|| TenantSummary {
+                usage: TenantUsageSnapshot {
+                    tenant: name.to_string(),
+                    requests: 0,
+                    reads: 0,
+                    writes: 0,
+                    errors: 0,
+                    bytes_in: 0,
+                    bytes_out: 0,
+                    last_request_at: None,
+                },
+                users: 0,
+            })
+            .users += 1;
+    }
+
+    let tenants: Vec<_> = tenants.into_values().collect();
+    Ok(Json(TenantListResponse {
+        count: tenants.len(),
+        tenants,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tenant_key(tenant: Option<&str>, admin: bool) -> AuthenticatedKey {
+        AuthenticatedKey {
+            key_id: "k".to_string(),
+            admin,
+            tenant: tenant.map(str::to_string),
+            scopes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_scope_ids_for_tenant_keys() {
+        let acme = tenant_key(Some("acme"), false);
+        assert_eq!(
+            scope_id(Some(&acme), "user_id", "alice").unwrap(),
+            "acme~alice"
+        );
+        assert!(scope_id(Some(&acme), "user_id", "globex~alice").is_err());
+        assert!(scope_id(Some(&acme), "user_id", "globex%7Ealice").is_err());
+        assert_eq!(
+            scope_query(Some(&acme), "user_id=alice&limit=5&team_id=eng").unwrap(),
+            "user_id=acme~alice&limit=5&team_id=acme~eng"
+        );
+        assert_eq!(
+            scope_query(Some(&acme), "user%5Fid=alice&%75ser_id=bob").unwrap(),
+            "user_id=acme~alice&user_id=acme~bob"
+        );
+        assert!(scope_query(Some(&acme), "user%5fid=globex~alice").is_err());
+
+        let body = scope_body(
+            Some(&acme),
+            Bytes::from_static(br#"{"user_id":"alice","tags":["user_id"]}"#),
+        )
+        .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["user_id"], "acme~alice");
+        assert_eq!(body["tags"][0], "user_id");
+
+        // Admin keys address stored ids as they are
+        let admin = tenant_key(None, true);
+        assert_eq!(
+            scope_id(Some(&admin), "user_id", "acme~alice").unwrap(),
+            "acme~alice"
+        );
+        assert!(!needs_scoping(Some(&admin)));
+        assert!(needs_scoping(Some(&acme)));
+    }
+}
@@ -69,8 +69,12 @@ impl TestHarness {
     /// Mirrors `main.rs`: auth middleware wraps only the protected routes.
     pub fn router(&self) -> Router {
         let public = build_public_routes(self.manager.clone());
-        let protected = build_protected_routes(self.manager.clone())
-            .layer(axum::middleware::from_fn(crate::auth::auth_middleware));
+        let protected = build_protected_routes(self.manager.clone()).layer(
+            axum::middleware::from_fn_with_state(
+                self.manager.api_keys.clone(),
+                crate::auth::auth_middleware,
+            ),
+        );
         Router::new().merge(public).merge(protected)
     }
 }
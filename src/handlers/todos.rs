@@ -701,7 +701,7 @@ pub async fn check_context_reminders(
         let memory_guard = memory_for_embedding.read();
         memory_guard
             .compute_embedding(&context_for_embed)
-            .unwrap_or_else(|_| vec![0.0; 384])
+            .unwrap_or_else(|_| vec![0.0; memory_guard.embedding_info().dimension])
     })
     .await
     .map_err(|e| AppError::Internal(anyhow::anyhow!("Embedding task panicked: {e}")))?;
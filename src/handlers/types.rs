@@ -18,6 +18,22 @@ pub struct HealthResponse {
     pub active_users: usize,
 }
 
+/// Outcome of one dependency check in the readiness probe
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyStatus {
+    /// "ok", "degraded" (serving, but impaired) or "error" (not ready)
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub latency_ms: u64,
+}
+
+impl DependencyStatus {
+    pub fn is_error(&self) -> bool {
+        self.status == "error"
+    }
+}
+
 // =============================================================================
 // AUDIT & EVENTS
 // =============================================================================
@@ -140,6 +156,9 @@ pub struct RecallRequest {
     /// Retrieval mode: "semantic", "associative", or "hybrid" (default)
     #[serde(default = "default_recall_mode")]
     pub mode: String,
+    /// Team whose shared memories are recalled too (falls back to the X-Shodh-Team header)
+    #[serde(default)]
+    pub team_id: Option<String>,
 }
 
 pub fn default_recall_limit() -> usize {
@@ -223,6 +242,9 @@ pub struct RecallMemory {
     pub created_at: String,
     pub score: f32,
     pub tier: String,
+    /// Team member who shared this memory (team memories only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared_by: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -362,6 +384,13 @@ pub struct MemoryHistoryResponse {
     pub memory_id: String,
     pub external_id: Option<String>,
     pub current_content: String,
+    /// Current version (1 = never changed)
+    pub version: u32,
+    pub created_at: String,
+    /// Where the memory came from; `None` for memories stored before provenance tracking
+    pub provenance: Option<crate::memory::Provenance>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
     pub revision_count: usize,
     pub revisions: Vec<MemoryRevisionInfo>,
 }
@@ -369,10 +398,24 @@ pub struct MemoryHistoryResponse {
 #[derive(Serialize)]
 pub struct MemoryRevisionInfo {
     pub revision: usize,
+    /// Content before this change
     pub content: String,
     pub changed_at: String,
     pub change_type: String,
     pub changed_by: Option<String>,
+    pub change_reason: Option<String>,
+}
+
+/// Response with the retrievals that surfaced a memory
+#[derive(Serialize)]
+pub struct MemoryAccessLogResponse {
+    pub memory_id: String,
+    /// Entries kept per memory; older ones are dropped
+    pub max_entries: usize,
+    pub retention_days: u64,
+    pub count: usize,
+    /// Newest first
+    pub accesses: Vec<crate::access_log::MemoryAccess>,
 }
 
 // =============================================================================
@@ -415,6 +458,10 @@ pub struct ReinforceFeedbackRequest {
     pub ids: Vec<String>,
     /// "helpful", "misleading", or "neutral"
     pub outcome: String,
+    /// How strongly the outcome was signalled, scaling the importance change
+    /// (0.0-2.0, default 1.0)
+    #[serde(default)]
+    pub strength: Option<f32>,
 }
 
 // =============================================================================
@@ -456,6 +503,95 @@ pub struct ConsolidateResponse {
     pub warnings: Vec<String>,
 }
 
+/// Request to merge near-duplicate memories
+#[derive(Debug, Deserialize)]
+pub struct DedupRequest {
+    pub user_id: String,
+    /// Report clusters without merging
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// A near-duplicate cluster found (and merged unless dry_run)
+#[derive(Debug, Serialize)]
+pub struct DuplicateClusterInfo {
+    pub canonical_id: String,
+    pub canonical_preview: String,
+    pub duplicate_ids: Vec<String>,
+    pub min_similarity: f32,
+}
+
+/// Response from near-duplicate consolidation
+#[derive(Debug, Serialize)]
+pub struct DedupResponse {
+    pub success: bool,
+    pub dry_run: bool,
+    pub clusters: Vec<DuplicateClusterInfo>,
+    /// Memories folded into a canonical memory (0 on dry runs)
+    pub memories_merged: usize,
+}
+
+/// Request to summarize aged episodes into semantic gists
+#[derive(Debug, Deserialize)]
+pub struct GistRequest {
+    pub user_id: String,
+    /// Report clusters without writing gists
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// An aged episodic cluster (and its gist unless dry_run)
+#[derive(Debug, Serialize)]
+pub struct GistInfo {
+    pub label: String,
+    pub episode_ids: Vec<String>,
+    pub first_at: chrono::DateTime<chrono::Utc>,
+    pub last_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gist_id: Option<String>,
+}
+
+/// Response from gist consolidation
+#[derive(Debug, Serialize)]
+pub struct GistResponse {
+    pub success: bool,
+    pub dry_run: bool,
+    pub clusters: Vec<GistInfo>,
+    /// Episodes demoted under a new gist (0 on dry runs)
+    pub episodes_summarized: usize,
+}
+
+/// Request to enforce the retention policy (SHODH_RETENTION_POLICY)
+#[derive(Debug, Deserialize)]
+pub struct RetentionRequest {
+    pub user_id: String,
+    /// Report expired memories without deleting them (default: true)
+    #[serde(default = "default_true")]
+    pub dry_run: bool,
+}
+
+/// A memory past its retention period
+#[derive(Debug, Serialize)]
+pub struct ExpiredMemoryInfo {
+    pub id: String,
+    pub memory_type: String,
+    pub content_preview: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub rule: crate::memory::retention::RetentionRule,
+}
+
+/// Response from a retention pass
+#[derive(Debug, Serialize)]
+pub struct RetentionResponse {
+    pub success: bool,
+    pub dry_run: bool,
+    pub expired: Vec<ExpiredMemoryInfo>,
+    /// Expired memories per type
+    pub by_type: std::collections::BTreeMap<String, usize>,
+    /// Memories deleted (0 on dry runs)
+    pub deleted: usize,
+}
+
 // =============================================================================
 // INDEX MAINTENANCE
 // =============================================================================
@@ -517,6 +653,30 @@ pub struct RebuildIndexResponse {
     pub is_healthy: bool,
 }
 
+#[derive(Deserialize)]
+pub struct ReembedRequest {
+    /// Only this user's store (default: every user)
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// Target provider: "local", "openai" or "cohere" (default: SHODH_EMBEDDING_PROVIDER)
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub dimension: Option<usize>,
+    /// Re-embed stores already on the target model
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Deserialize)]
+pub struct ReencryptRequest {
+    /// Only this user's store (default: every user)
+    #[serde(default)]
+    pub user_id: Option<String>,
+}
+
 // =============================================================================
 // BACKUP & RESTORE
 // =============================================================================
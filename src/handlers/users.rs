@@ -3,14 +3,20 @@
 //! Handlers for user-related operations including stats, deletion (GDPR), and listing.
 
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Query, State},
     response::Json,
+    Extension,
 };
 use serde::{Deserialize, Serialize};
 
-use super::state::MultiUserMemoryManager;
-use crate::errors::AppError;
+use super::state::{MultiUserMemoryManager, UserPurgeReport};
+use super::tenants::TenantPath;
+use crate::auth::AuthenticatedKey;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::memory::quota::QuotaStatus;
 use crate::memory::MemoryStats;
+use crate::tenant;
+use crate::validation;
 use std::sync::Arc;
 
 type AppState = Arc<MultiUserMemoryManager>;
@@ -18,12 +24,38 @@ type AppState = Arc<MultiUserMemoryManager>;
 /// GET /api/users/{user_id}/stats - Get user statistics
 pub async fn get_user_stats(
     State(state): State<AppState>,
-    Path(user_id): Path<String>,
+    TenantPath(user_id): TenantPath,
 ) -> Result<Json<MemoryStats>, AppError> {
     let stats = state.get_stats(&user_id).map_err(AppError::Internal)?;
     Ok(Json(stats))
 }
 
+/// Response for quota status
+#[derive(Debug, Serialize)]
+pub struct UserQuotaResponse {
+    pub user_id: String,
+    #[serde(flatten)]
+    pub status: QuotaStatus,
+}
+
+/// GET /api/users/{user_id}/quota - Memory count / storage usage against the user's quota
+pub async fn get_user_quota(
+    State(state): State<AppState>,
+    TenantPath(user_id): TenantPath,
+) -> Result<Json<UserQuotaResponse>, AppError> {
+    validation::validate_user_id(&user_id).map_validation_err("user_id")?;
+
+    let memory = state
+        .get_user_memory(&user_id)
+        .map_err(AppError::Internal)?;
+    let status = tokio::task::spawn_blocking(move || memory.read().quota_status())
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+        .map_err(AppError::Internal)?;
+
+    Ok(Json(UserQuotaResponse { user_id, status }))
+}
+
 /// Query parameters for stats endpoint
 #[derive(Debug, Deserialize)]
 pub struct StatsQuery {
@@ -52,7 +84,7 @@ pub struct DeleteUserResponse {
 /// DELETE /api/users/{user_id} - Delete user data (GDPR compliance)
 pub async fn delete_user(
     State(state): State<AppState>,
-    Path(user_id): Path<String>,
+    TenantPath(user_id): TenantPath,
 ) -> Result<Json<DeleteUserResponse>, AppError> {
     state.forget_user(&user_id).map_err(AppError::Internal)?;
 
@@ -63,7 +95,90 @@ pub async fn delete_user(
     }))
 }
 
-/// GET /api/users - List all users
-pub async fn list_users(State(state): State<AppState>) -> Json<Vec<String>> {
-    Json(state.list_users())
+/// Request body for a user purge (empty to request a confirmation token)
+#[derive(Debug, Default, Deserialize)]
+pub struct PurgeUserRequest {
+    #[serde(default)]
+    pub confirmation_token: Option<String>,
+}
+
+/// Response for a user purge
+#[derive(Debug, Serialize)]
+pub struct PurgeUserResponse {
+    pub success: bool,
+    pub user_id: String,
+    /// Whether data was deleted (false when only a token was issued)
+    pub purged: bool,
+    /// Token to send back to confirm the purge
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_in_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<UserPurgeReport>,
+    pub message: String,
+}
+
+/// POST /api/users/{user_id}/purge - Delete everything held for a user (GDPR erasure)
+///
+/// Two-step: a request without `confirmation_token` returns a short-lived
+/// token; repeating the request with that token deletes all memories,
+/// embeddings, feedback, sessions and audit rows, and records the purge in
+/// the audit log.
+pub async fn purge_user(
+    State(state): State<AppState>,
+    TenantPath(user_id): TenantPath,
+    body: Option<Json<PurgeUserRequest>>,
+) -> Result<Json<PurgeUserResponse>, AppError> {
+    validation::validate_user_id(&user_id).map_validation_err("user_id")?;
+    let req = body.map(|Json(req)| req).unwrap_or_default();
+
+    let Some(token) = req.confirmation_token else {
+        let (token, ttl) = state.issue_purge_token(&user_id);
+        return Ok(Json(PurgeUserResponse {
+            success: true,
+            user_id,
+            purged: false,
+            confirmation_token: Some(token),
+            expires_in_secs: Some(ttl),
+            report: None,
+            message: "Repeat the request with this confirmation_token to purge all user data"
+                .to_string(),
+        }));
+    };
+
+    let purge_state = state.clone();
+    let purge_user_id = user_id.clone();
+    let report =
+        tokio::task::spawn_blocking(move || purge_state.purge_user(&purge_user_id, &token))
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+            .map_err(AppError::Internal)?
+            .ok_or_else(|| AppError::InvalidInput {
+                field: "confirmation_token".to_string(),
+                reason: "Invalid or expired confirmation token".to_string(),
+            })?;
+
+    Ok(Json(PurgeUserResponse {
+        success: true,
+        user_id,
+        purged: true,
+        confirmation_token: None,
+        expires_in_secs: None,
+        report: Some(report),
+        message: "All user data purged".to_string(),
+    }))
+}
+
+/// GET /api/users - List users of the caller's tenant (every user for admin keys)
+pub async fn list_users(
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+) -> Json<Vec<String>> {
+    let mut users = state.list_users();
+    if !key.as_ref().is_some_and(|k| k.admin) {
+        let tenant = key.as_ref().and_then(|k| k.tenant.as_deref());
+        users.retain(|user_id| tenant::owns(tenant, user_id));
+    }
+    Json(users)
 }
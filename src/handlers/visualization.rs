@@ -4,12 +4,13 @@
 //! Includes live browser-based graph visualization with SSE updates.
 
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Query, State},
     response::{Html, Json},
 };
 use serde::{Deserialize, Serialize};
 
 use super::state::MultiUserMemoryManager;
+use super::tenants::TenantPath;
 use crate::errors::{AppError, ValidationErrorExt};
 use crate::memory::GraphStats as VisualizationStats;
 use crate::validation;
@@ -52,7 +53,7 @@ pub struct BrainStats {
 /// GET /api/brain/{user_id} - Get brain state visualization
 pub async fn get_brain_state(
     State(state): State<AppState>,
-    Path(user_id): Path<String>,
+    TenantPath(user_id): TenantPath,
 ) -> Result<Json<BrainStateResponse>, AppError> {
     validation::validate_user_id(&user_id).map_validation_err("user_id")?;
 
@@ -146,7 +147,7 @@ pub async fn get_brain_state(
 /// GET /api/visualization/{user_id}/stats - Get visualization statistics
 pub async fn get_visualization_stats(
     State(state): State<AppState>,
-    Path(user_id): Path<String>,
+    TenantPath(user_id): TenantPath,
 ) -> Result<Json<VisualizationStats>, AppError> {
     validation::validate_user_id(&user_id).map_validation_err("user_id")?;
 
@@ -163,7 +164,7 @@ pub async fn get_visualization_stats(
 /// GET /api/visualization/{user_id}/dot - Export graph as DOT format
 pub async fn get_visualization_dot(
     State(state): State<AppState>,
-    Path(user_id): Path<String>,
+    TenantPath(user_id): TenantPath,
 ) -> Result<String, AppError> {
     validation::validate_user_id(&user_id).map_validation_err("user_id")?;
 
@@ -256,7 +257,7 @@ pub async fn graph_view(Query(params): Query<GraphViewParams>) -> Html<String> {
 /// GET /api/graph/data/{user_id} - Get graph data as JSON for d3.js
 pub async fn get_graph_data(
     State(state): State<AppState>,
-    Path(user_id): Path<String>,
+    TenantPath(user_id): TenantPath,
 ) -> Result<Json<GraphDataResponse>, AppError> {
     validation::validate_user_id(&user_id).map_validation_err("user_id")?;
 
@@ -12,12 +12,18 @@ use axum::{
         sse::{Event, KeepAlive, Sse},
         IntoResponse,
     },
+    Extension,
 };
 use futures::{SinkExt, StreamExt};
 use std::convert::Infallible;
+use tokio::sync::broadcast::error::RecvError;
 use tokio_stream::wrappers::BroadcastStream;
 
+use super::acl::require_configured_admin;
 use super::state::MultiUserMemoryManager;
+use super::types::MemoryEvent;
+use crate::auth::AuthenticatedKey;
+use crate::errors::AppError;
 use crate::relevance;
 use crate::streaming;
 use crate::validation;
@@ -26,6 +32,11 @@ use crate::validation;
 #[derive(Debug, serde::Deserialize)]
 pub struct SseQuery {
     pub user_id: Option<String>,
+    /// Without `user_id`: stream every user of the caller's tenant (admin keys only)
+    #[serde(default)]
+    pub all_users: bool,
+    /// Comma-separated event types to keep (e.g. "CREATE,UPDATE"); all if unset
+    pub types: Option<String>,
 }
 
 /// Application state type alias
@@ -61,46 +72,161 @@ pub async fn context_status_sse(
     )
 }
 
+/// Which memory events a subscriber of /api/events receives
+#[derive(Clone)]
+struct EventFilter {
+    state: AppState,
+    key: Option<AuthenticatedKey>,
+    user_id: Option<String>,
+    all_users: bool,
+    types: Option<Vec<String>>,
+}
+
+impl EventFilter {
+    /// Filter for a subscription; following every user requires an admin key
+    fn new(
+        state: AppState,
+        key: Option<AuthenticatedKey>,
+        params: SseQuery,
+    ) -> Result<Self, AppError> {
+        if params.all_users && params.user_id.is_none() {
+            require_configured_admin(key.as_ref(), "Streaming every user's events")?;
+        }
+        let types = params.types.map(|types| {
+            types
+                .split(',')
+                .map(|t| t.trim().to_ascii_uppercase())
+                .filter(|t| !t.is_empty())
+                .collect()
+        });
+        Ok(Self {
+            state,
+            key,
+            user_id: params.user_id,
+            all_users: params.all_users,
+            types,
+        })
+    }
+
+    fn matches(&self, event: &MemoryEvent) -> bool {
+        if let Some(types) = &self.types {
+            if !types
+                .iter()
+                .any(|t| event.event_type.eq_ignore_ascii_case(t))
+            {
+                return false;
+            }
+        }
+        match &self.user_id {
+            Some(uid) => event.user_id == *uid,
+            // Every user of the (admin) key's tenant
+            None if self.all_users => crate::tenant::owns(
+                self.key.as_ref().and_then(|k| k.tenant.as_deref()),
+                &event.user_id,
+            ),
+            // No user_id specified — drop event (secure by default)
+            None => false,
+        }
+    }
+
+    /// Event as sent to the client, with tenant qualifiers removed
+    fn render(&self, event: &MemoryEvent) -> Option<String> {
+        let json = serde_json::to_string(event).ok()?;
+        match self.key.as_ref().and_then(|k| k.tenant.as_deref()) {
+            Some(tenant) => {
+                String::from_utf8(crate::tenant::strip_qualifiers(tenant, json.as_bytes())).ok()
+            }
+            None => Some(json),
+        }
+    }
+}
+
 /// SSE endpoint for real-time memory events
 ///
-/// Streams CREATE, RETRIEVE, DELETE events to connected dashboard clients.
-/// Accepts optional `?user_id=X` query parameter to filter events.
-/// Without user_id, no events are sent (secure by default).
+/// Streams CREATE, UPDATE, REINFORCE, RETRIEVE, DELETE (and todo/graph)
+/// events to connected dashboard clients. Accepts `?user_id=X` to follow one
+/// user, or `?all_users=true` (admin keys only) for every user, plus an
+/// optional `?types=CREATE,DELETE` filter.
+/// Without user_id or all_users, no events are sent (secure by default).
 pub async fn memory_events_sse(
     State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
     Query(params): Query<SseQuery>,
-) -> Sse<impl futures::Stream<Item = Result<Event, Infallible>>> {
+) -> Result<Sse<impl futures::Stream<Item = Result<Event, Infallible>>>, AppError> {
+    let filter = EventFilter::new(state.clone(), key.map(|Extension(k)| k), params)?;
     let receiver = state.subscribe_events();
     let stream = BroadcastStream::new(receiver);
-    let filter_user_id = params.user_id;
 
     let event_stream = stream.filter_map(move |result| {
-        let filter_uid = filter_user_id.clone();
+        let filter = filter.clone();
         async move {
             match result {
-                Ok(event) => {
-                    // Filter by user_id: only send events belonging to the subscribed user
-                    if let Some(ref uid) = filter_uid {
-                        if event.user_id != *uid {
-                            return None;
-                        }
-                    } else {
-                        // No user_id specified — drop event (secure by default)
-                        return None;
-                    }
+                Ok(event) if filter.matches(&event) => {
+                    // Tenant qualifiers are stripped by the tenant middleware
                     let json = serde_json::to_string(&event).ok()?;
                     Some(Ok(Event::default().event(&event.event_type).data(json)))
                 }
-                Err(_) => None,
+                _ => None,
             }
         }
     });
 
-    Sse::new(event_stream).keep_alive(
+    Ok(Sse::new(event_stream).keep_alive(
         KeepAlive::new()
             .interval(std::time::Duration::from_secs(15))
             .text("heartbeat"),
-    )
+    ))
+}
+
+/// WebSocket variant of /api/events, for clients without SSE support
+///
+/// Takes the same query parameters and sends each event as a JSON text frame.
+pub async fn memory_events_ws(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    key: Option<Extension<AuthenticatedKey>>,
+    Query(params): Query<SseQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let filter = EventFilter::new(state, key.map(|Extension(k)| k), params)?;
+    Ok(ws.on_upgrade(|socket| handle_events_socket(socket, filter)))
+}
+
+/// Forward matching memory events until the client disconnects
+async fn handle_events_socket(socket: WebSocket, filter: EventFilter) {
+    let (mut sender, mut receiver) = socket.split();
+    let mut events = filter.state.subscribe_events();
+
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => {
+                    if !filter.matches(&event) {
+                        continue;
+                    }
+                    let Some(json) = filter.render(&event) else {
+                        continue;
+                    };
+                    if sender.send(Message::Text(json.into())).await.is_err() {
+                        return;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "Memory event WebSocket lagged");
+                }
+                Err(RecvError::Closed) => return,
+            },
+            msg = receiver.next() => match msg {
+                Some(Ok(Message::Ping(data))) => {
+                    let _ = sender.send(Message::Pong(data)).await;
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+                    tracing::debug!("Memory event WebSocket closed");
+                    return;
+                }
+                Some(Ok(_)) => {}
+            },
+        }
+    }
 }
 
 // =============================================================================
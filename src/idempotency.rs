@@ -0,0 +1,251 @@
+//! Remember Idempotency Keys
+//!
+//! A client may send an `idempotency_key` with /api/remember (and per batch
+//! item) so a retried write returns the original memory instead of storing a
+//! duplicate. A key is claimed before the write and recorded with the stored
+//! memory's ID once it succeeds; a concurrent request with a key that is still
+//! being written is turned away instead of storing a second copy, and a failed
+//! write releases its claim so the client can retry.
+//!
+//! Recorded keys live in the `remember_idempotency` column family of the shared
+//! DB, keyed `{user_id}:{key}`, so they survive restarts and are removed with
+//! the rest of a user's data on purge. A key deduplicates for `ttl` after its
+//! write; expired entries are ignored on read and dropped on startup.
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use dashmap::DashSet;
+use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, IteratorMode, Options, WriteBatch, DB};
+use serde::{Deserialize, Serialize};
+
+/// Column family holding recorded idempotency keys
+pub const CF_REMEMBER_IDEMPOTENCY: &str = "remember_idempotency";
+
+/// A completed write recorded under an idempotency key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IdempotencyEntry {
+    memory_id: String,
+    created_at: DateTime<Utc>,
+}
+
+/// Outcome of claiming an idempotency key
+pub enum Claim<'a> {
+    /// The key is new; the caller performs the write and completes the claim
+    Acquired(IdempotencyClaim<'a>),
+    /// An earlier write with this key stored the given memory
+    Existing(String),
+    /// A write with this key is still in progress
+    InProgress,
+}
+
+/// A claimed key, released on drop unless completed
+pub struct IdempotencyClaim<'a> {
+    store: &'a IdempotencyStore,
+    key: String,
+    completed: bool,
+}
+
+impl IdempotencyClaim<'_> {
+    /// Record the memory the write stored (or deduplicated to) under the key
+    pub fn complete(mut self, memory_id: &str) -> Result<()> {
+        self.completed = true;
+        let result = self.store.record(&self.key, memory_id);
+        self.store.in_flight.remove(&self.key);
+        result
+    }
+}
+
+impl Drop for IdempotencyClaim<'_> {
+    fn drop(&mut self) {
+        if !self.completed {
+            self.store.in_flight.remove(&self.key);
+        }
+    }
+}
+
+/// Persistent remember idempotency keys
+pub struct IdempotencyStore {
+    db: Arc<DB>,
+    ttl: Duration,
+    /// Claimed keys whose write hasn't completed yet
+    in_flight: DashSet<String>,
+}
+
+impl IdempotencyStore {
+    /// Column family descriptors required by the IdempotencyStore.
+    /// The caller must include these (plus `"default"`) when opening the shared DB.
+    pub fn cf_descriptors() -> Vec<ColumnFamilyDescriptor> {
+        let mut cf_opts = Options::default();
+        cf_opts.create_if_missing(true);
+        vec![ColumnFamilyDescriptor::new(
+            CF_REMEMBER_IDEMPOTENCY,
+            cf_opts,
+        )]
+    }
+
+    pub fn new(db: Arc<DB>, ttl_secs: u64) -> Self {
+        Self {
+            db,
+            ttl: Duration::seconds(ttl_secs as i64),
+            in_flight: DashSet::new(),
+        }
+    }
+
+    fn cf(&self) -> &ColumnFamily {
+        self.db
+            .cf_handle(CF_REMEMBER_IDEMPOTENCY)
+            .expect("remember_idempotency CF must exist")
+    }
+
+    fn entry_key(user_id: &str, key: &str) -> String {
+        format!("{user_id}:{key}")
+    }
+
+    /// Memory recorded under a key, if it hasn't expired
+    fn lookup(&self, entry_key: &str) -> Result<Option<String>> {
+        let Some(value) = self
+            .db
+            .get_cf(self.cf(), entry_key.as_bytes())
+            .context("Failed to read idempotency key")?
+        else {
+            return Ok(None);
+        };
+        Ok(serde_json::from_slice::<IdempotencyEntry>(&value)
+            .ok()
+            .filter(|entry| entry.created_at >= Utc::now() - self.ttl)
+            .map(|entry| entry.memory_id))
+    }
+
+    fn record(&self, entry_key: &str, memory_id: &str) -> Result<()> {
+        let entry = IdempotencyEntry {
+            memory_id: memory_id.to_string(),
+            created_at: Utc::now(),
+        };
+        self.db
+            .put_cf(self.cf(), entry_key.as_bytes(), serde_json::to_vec(&entry)?)
+            .context("Failed to store idempotency key")
+    }
+
+    /// Claim a user's idempotency key before writing
+    ///
+    /// At most one caller acquires a key at a time; everyone else sees the
+    /// recorded memory or that the write is still in progress.
+    pub fn claim(&self, user_id: &str, key: &str) -> Result<Claim<'_>> {
+        let entry_key = Self::entry_key(user_id, key);
+        if !self.in_flight.insert(entry_key.clone()) {
+            return Ok(Claim::InProgress);
+        }
+        let claim = IdempotencyClaim {
+            store: self,
+            key: entry_key,
+            completed: false,
+        };
+        // Checked while holding the claim, so a write completing concurrently
+        // is either seen here or still in flight above
+        match self.lookup(&claim.key)? {
+            Some(memory_id) => Ok(Claim::Existing(memory_id)),
+            None => Ok(Claim::Acquired(claim)),
+        }
+    }
+
+    /// Drop entries older than the TTL, returning how many were removed
+    pub fn prune_expired(&self) -> Result<usize> {
+        let cutoff = Utc::now() - self.ttl;
+        let mut batch = WriteBatch::default();
+        let mut pruned = 0;
+        for item in self.db.iterator_cf(self.cf(), IteratorMode::Start) {
+            let (key, value) = item.context("Failed to read idempotency keys")?;
+            let expired = serde_json::from_slice::<IdempotencyEntry>(&value)
+                .map(|entry| entry.created_at < cutoff)
+                .unwrap_or(true);
+            if expired {
+                batch.delete_cf(self.cf(), &key);
+                pruned += 1;
+            }
+        }
+        if pruned > 0 {
+            self.db.write(batch)?;
+        }
+        Ok(pruned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_db(path: &std::path::Path) -> Arc<DB> {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let mut cfs = vec![ColumnFamilyDescriptor::new("default", Options::default())];
+        cfs.extend(IdempotencyStore::cf_descriptors());
+        Arc::new(DB::open_cf_descriptors(&opts, path, cfs).unwrap())
+    }
+
+    #[test]
+    fn test_claim_is_exclusive_until_completed() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = IdempotencyStore::new(open_db(dir.path()), 3600);
+
+        let Claim::Acquired(claim) = store.claim("alice", "retry-1").unwrap() else {
+            panic!("first claim should be acquired");
+        };
+        assert!(matches!(
+            store.claim("alice", "retry-1").unwrap(),
+            Claim::InProgress
+        ));
+        // Keys are per user
+        assert!(matches!(
+            store.claim("bob", "retry-1").unwrap(),
+            Claim::Acquired(_)
+        ));
+
+        claim.complete("mem-1").unwrap();
+        match store.claim("alice", "retry-1").unwrap() {
+            Claim::Existing(id) => assert_eq!(id, "mem-1"),
+            _ => panic!("completed key should return the stored memory"),
+        }
+    }
+
+    #[test]
+    fn test_dropped_claim_is_released() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = IdempotencyStore::new(open_db(dir.path()), 3600);
+
+        let claim = store.claim("alice", "retry-1").unwrap();
+        drop(claim);
+        assert!(matches!(
+            store.claim("alice", "retry-1").unwrap(),
+            Claim::Acquired(_)
+        ));
+    }
+
+    #[test]
+    fn test_keys_survive_reopen_and_expire() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let store = IdempotencyStore::new(open_db(dir.path()), 3600);
+            let Claim::Acquired(claim) = store.claim("alice", "retry-1").unwrap() else {
+                panic!("first claim should be acquired");
+            };
+            claim.complete("mem-1").unwrap();
+        }
+        let store = IdempotencyStore::new(open_db(dir.path()), 3600);
+        assert!(matches!(
+            store.claim("alice", "retry-1").unwrap(),
+            Claim::Existing(_)
+        ));
+        assert_eq!(store.prune_expired().unwrap(), 0);
+
+        let expired = IdempotencyStore::new(store.db.clone(), 0);
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert!(matches!(
+            expired.claim("alice", "retry-1").unwrap(),
+            Claim::Acquired(_)
+        ));
+        assert_eq!(expired.prune_expired().unwrap(), 1);
+    }
+}
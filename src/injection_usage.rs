@@ -0,0 +1,166 @@
+//! Daily Injection Usage
+//!
+//! Tokens of memory content injected by proactive context count against
+//! `SHODH_INJECT_DAILY_TOKEN_BUDGET` per user and UTC day, across sessions.
+//! Usage lives in the `injection_usage` column family of the shared DB, keyed
+//! by user id, so a restart doesn't hand out a fresh allowance. A user has one
+//! entry, overwritten when the day rolls over, and it is removed on purge.
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
+use parking_lot::Mutex;
+use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, Options, DB};
+use serde::{Deserialize, Serialize};
+
+/// Column family holding injection usage (user_id -> DailyInjectionUsage)
+pub const CF_INJECTION_USAGE: &str = "injection_usage";
+
+/// Tokens of memory content injected for a user on one (UTC) day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyInjectionUsage {
+    pub day: NaiveDate,
+    pub tokens: usize,
+}
+
+/// Persistent per-user daily injection usage
+pub struct InjectionUsageStore {
+    db: Arc<DB>,
+    /// Serializes read-modify-write updates
+    write_lock: Mutex<()>,
+}
+
+impl InjectionUsageStore {
+    /// Column family descriptors required by the InjectionUsageStore.
+    /// The caller must include these (plus `"default"`) when opening the shared DB.
+    pub fn cf_descriptors() -> Vec<ColumnFamilyDescriptor> {
+        let mut cf_opts = Options::default();
+        cf_opts.create_if_missing(true);
+        vec![ColumnFamilyDescriptor::new(CF_INJECTION_USAGE, cf_opts)]
+    }
+
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    fn cf(&self) -> &ColumnFamily {
+        self.db
+            .cf_handle(CF_INJECTION_USAGE)
+            .expect("injection_usage CF must exist")
+    }
+
+    fn get(&self, user_id: &str) -> Result<Option<DailyInjectionUsage>> {
+        let Some(value) = self
+            .db
+            .get_cf(self.cf(), user_id.as_bytes())
+            .context("Failed to read injection usage")?
+        else {
+            return Ok(None);
+        };
+        match serde_json::from_slice(&value) {
+            Ok(usage) => Ok(Some(usage)),
+            Err(e) => {
+                tracing::warn!("Ignoring unreadable injection usage: {}", e);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Tokens of memory content injected for a user so far today
+    pub fn tokens_today(&self, user_id: &str) -> Result<usize> {
+        let today = Utc::now().date_naive();
+        Ok(self
+            .get(user_id)?
+            .filter(|usage| usage.day == today)
+            .map(|usage| usage.tokens)
+            .unwrap_or(0))
+    }
+
+    /// Add injected tokens to a user's daily usage, returning today's new total
+    ///
+    /// Usage resets at UTC midnight.
+    pub fn add(&self, user_id: &str, tokens: usize) -> Result<usize> {
+        let today = Utc::now().date_naive();
+        let _guard = self.write_lock.lock();
+        let mut usage = self
+            .get(user_id)?
+            .filter(|usage| usage.day == today)
+            .unwrap_or(DailyInjectionUsage {
+                day: today,
+                tokens: 0,
+            });
+        usage.tokens += tokens;
+        self.db
+            .put_cf(self.cf(), user_id.as_bytes(), serde_json::to_vec(&usage)?)
+            .context("Failed to store injection usage")?;
+        Ok(usage.tokens)
+    }
+
+    /// Drop a user's usage
+    pub fn remove_user(&self, user_id: &str) -> Result<()> {
+        self.db
+            .delete_cf(self.cf(), user_id.as_bytes())
+            .context("Failed to delete injection usage")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn open_db(path: &std::path::Path) -> Arc<DB> {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let mut cfs = vec![ColumnFamilyDescriptor::new("default", Options::default())];
+        cfs.extend(InjectionUsageStore::cf_descriptors());
+        Arc::new(DB::open_cf_descriptors(&opts, path, cfs).unwrap())
+    }
+
+    #[test]
+    fn test_daily_injection_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = InjectionUsageStore::new(open_db(dir.path()));
+        assert_eq!(store.tokens_today("user-1").unwrap(), 0);
+
+        assert_eq!(store.add("user-1", 120).unwrap(), 120);
+        assert_eq!(store.add("user-1", 30).unwrap(), 150);
+        assert_eq!(store.tokens_today("user-1").unwrap(), 150);
+        assert_eq!(store.tokens_today("user-2").unwrap(), 0);
+
+        // Usage from a previous day doesn't count against today
+        let yesterday = DailyInjectionUsage {
+            day: Utc::now().date_naive() - Duration::days(1),
+            tokens: 10_000,
+        };
+        store
+            .db
+            .put_cf(
+                store.cf(),
+                b"user-2",
+                serde_json::to_vec(&yesterday).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(store.tokens_today("user-2").unwrap(), 0);
+        assert_eq!(store.add("user-2", 5).unwrap(), 5);
+
+        store.remove_user("user-1").unwrap();
+        assert_eq!(store.tokens_today("user-1").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_usage_survives_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let store = InjectionUsageStore::new(open_db(dir.path()));
+            store.add("user-1", 400).unwrap();
+        }
+        let store = InjectionUsageStore::new(open_db(dir.path()));
+        assert_eq!(store.tokens_today("user-1").unwrap(), 400);
+    }
+}
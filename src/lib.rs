@@ -16,24 +16,36 @@
 //! - Full offline operation
 
 pub mod ab_testing;
+pub mod access_log;
+pub mod acl;
+pub mod api_keys;
 pub mod auth;
 pub mod backup;
+pub mod backup_sink;
 pub mod config;
 pub mod constants;
 pub mod decay;
 pub mod embeddings;
 pub mod errors;
+pub mod event_webhooks;
 pub mod graph_memory;
 pub mod handlers;
+pub mod idempotency;
+pub mod injection_usage;
 pub mod integrations;
 pub mod memory;
 pub mod metrics;
 pub mod middleware;
 pub mod mif;
 pub mod query_parsing;
+pub mod ranking;
+pub mod rate_limit;
 pub mod relevance;
 pub mod similarity;
+pub mod sleep;
 pub mod streaming;
+pub mod suppression;
+pub mod tenant;
 pub mod tracing_setup;
 pub mod validation;
 pub mod vector_db;
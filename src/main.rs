@@ -220,6 +220,13 @@ async fn async_main() -> Result<()> {
     // Start active reminder scheduler (checks every 60s for due reminders)
     start_reminder_scheduler(Arc::clone(&manager));
 
+    // Deliver memory events to registered outbound webhooks
+    shodh_memory::event_webhooks::spawn_dispatcher(
+        manager.webhooks.clone(),
+        manager.subscribe_events(),
+    );
+    info!("Outbound webhook dispatcher started");
+
     // Start backup scheduler if enabled
     if server_config.backup_enabled && server_config.backup_interval_secs > 0 {
         start_backup_scheduler(
@@ -227,6 +234,7 @@ async fn async_main() -> Result<()> {
             server_config.backup_interval_secs,
             server_config.backup_max_count,
         );
+        verify_backups_on_startup(Arc::clone(&manager));
     }
 
     // Configure rate limiting (0 = disabled, for localhost/embedded use)
@@ -278,11 +286,15 @@ async fn async_main() -> Result<()> {
 
     let protected_routes = if let Some(governor) = governor_layer {
         handlers::build_protected_routes(Arc::clone(&manager))
-            .layer(axum::middleware::from_fn(auth::auth_middleware))
+            .layer(axum::middleware::from_fn_with_state(
+                manager.api_keys.clone(),
+                auth::auth_middleware,
+            ))
             .layer(governor)
     } else {
-        handlers::build_protected_routes(Arc::clone(&manager))
-            .layer(axum::middleware::from_fn(auth::auth_middleware))
+        handlers::build_protected_routes(Arc::clone(&manager)).layer(
+            axum::middleware::from_fn_with_state(manager.api_keys.clone(), auth::auth_middleware),
+        )
     };
 
     // Combine routes with global middleware
@@ -386,8 +398,12 @@ fn start_maintenance_scheduler(manager: AppState, interval_secs: u64) {
                 tracing::debug!("Cleaned {} stale streaming sessions", cleaned);
             }
 
-            // Cleanup stale user sessions
-            let session_cleaned = manager.session_store().cleanup_stale_sessions();
+            // Cleanup stale user sessions (applies retrospective reinforcement)
+            let session_manager = Arc::clone(&manager);
+            let session_cleaned =
+                tokio::task::spawn_blocking(move || session_manager.cleanup_stale_sessions())
+                    .await
+                    .unwrap_or(0);
             if session_cleaned > 0 {
                 tracing::debug!("Ended {} stale user sessions", session_cleaned);
             }
@@ -408,6 +424,35 @@ fn start_maintenance_scheduler(manager: AppState, interval_secs: u64) {
     );
 }
 
+/// Check the newest backup of each user in the background so corruption is
+/// reported at startup rather than discovered during a restore
+fn verify_backups_on_startup(manager: AppState) {
+    tokio::spawn(async move {
+        let results =
+            tokio::task::spawn_blocking(move || manager.backup_engine().verify_latest_backups())
+                .await;
+        match results {
+            Ok(Ok(results)) => {
+                let corrupted: Vec<_> = results.iter().filter(|(_, _, ok)| !ok).collect();
+                for (user_id, backup_id, _) in &corrupted {
+                    error!(
+                        user_id = %user_id,
+                        backup_id = backup_id,
+                        "Backup failed integrity verification"
+                    );
+                }
+                info!(
+                    "Backup verification: {} of {} latest backups intact",
+                    results.len() - corrupted.len(),
+                    results.len()
+                );
+            }
+            Ok(Err(e)) => tracing::warn!("Backup verification failed: {}", e),
+            Err(e) => tracing::warn!("Backup verification task panicked: {}", e),
+        }
+    });
+}
+
 fn start_backup_scheduler(manager: AppState, interval_secs: u64, max_backups: usize) {
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
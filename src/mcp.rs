@@ -4,6 +4,8 @@
 //!   shodh serve              - Run as MCP server (stdio transport)
 //!   shodh hook session-start - Output session start hook JSON
 //!   shodh hook prompt <msg>  - Output prompt submit hook JSON
+//!                              (`/remember`, `/recall`, `/forget` are handled locally)
+//!   shodh reembed            - Move stores to a new embedding provider/model (admin)
 //!
 //! Both modes use the same core memory functionality, ready for future MCP push.
 
@@ -55,6 +57,10 @@ enum Commands {
         /// User ID for memory operations
         #[arg(long, env = "SHODH_USER_ID", default_value = "claude-code")]
         user_id: String,
+
+        /// Team whose shared memories are recalled alongside the user's
+        #[arg(long, env = "SHODH_TEAM")]
+        team: Option<String>,
     },
 
     /// Output Claude Code hook JSON
@@ -63,6 +69,45 @@ enum Commands {
         hook_type: HookType,
     },
 
+    /// Re-embed stored memories with a new embedding provider/model (admin key)
+    Reembed {
+        /// API URL for the memory server
+        #[arg(long, env = "SHODH_API_URL", default_value = "http://127.0.0.1:3030")]
+        api_url: String,
+
+        /// Admin API key
+        #[arg(
+            long,
+            env = "SHODH_API_KEY",
+            default_value = "sk-shodh-dev-local-testing-key"
+        )]
+        api_key: String,
+
+        /// Only re-embed this user's store (default: every user)
+        #[arg(long)]
+        user_id: Option<String>,
+
+        /// Target provider: local, openai or cohere (default: the server's SHODH_EMBEDDING_PROVIDER)
+        #[arg(long)]
+        provider: Option<String>,
+
+        /// Target model (default: the provider's default)
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Target embedding dimension
+        #[arg(long)]
+        dimension: Option<usize>,
+
+        /// Re-embed stores already on the target model
+        #[arg(long)]
+        force: bool,
+
+        /// Wait for the job to finish, printing progress
+        #[arg(long)]
+        wait: bool,
+    },
+
     /// Launch Claude Code with Shodh Cortex proxy (transparent memory injection)
     Claude {
         /// Port for the shodh-memory server
@@ -95,6 +140,10 @@ enum HookType {
         #[arg(long, env = "SHODH_USER_ID", default_value = "claude-code")]
         user_id: String,
 
+        /// Team whose shared memories are recalled alongside the user's
+        #[arg(long, env = "SHODH_TEAM")]
+        team: Option<String>,
+
         /// Project directory (from CLAUDE_PROJECT_DIR)
         #[arg(long, env = "CLAUDE_PROJECT_DIR")]
         project_dir: Option<String>,
@@ -120,6 +169,14 @@ enum HookType {
         /// User ID for memory operations
         #[arg(long, env = "SHODH_USER_ID", default_value = "claude-code")]
         user_id: String,
+
+        /// Team whose shared memories are recalled alongside the user's
+        #[arg(long, env = "SHODH_TEAM")]
+        team: Option<String>,
+
+        /// Let `/remember` prompts continue to the model instead of answering locally
+        #[arg(long, env = "SHODH_REMEMBER_PASSTHROUGH")]
+        remember_passthrough: bool,
     },
 }
 
@@ -127,6 +184,9 @@ enum HookType {
 // API CLIENT
 // =============================================================================
 
+/// Header naming the caller's team (mirrors the server's `X-Shodh-Team`)
+const TEAM_HEADER: &str = "X-Shodh-Team";
+
 /// HTTP client for the shodh-memory API (async version for MCP tools)
 #[derive(Clone, Debug)]
 struct AsyncApiClient {
@@ -134,15 +194,18 @@ struct AsyncApiClient {
     base_url: String,
     api_key: String,
     user_id: String,
+    /// Sent as X-Shodh-Team so the server includes team memories
+    team: Option<String>,
 }
 
 impl AsyncApiClient {
-    fn new(base_url: String, api_key: String, user_id: String) -> Self {
+    fn new(base_url: String, api_key: String, user_id: String, team: Option<String>) -> Self {
         Self {
             client: reqwest::Client::new(),
             base_url,
             api_key,
             user_id,
+            team,
         }
     }
 
@@ -152,12 +215,31 @@ impl AsyncApiClient {
         body: &T,
     ) -> Result<R> {
         let url = format!("{}{endpoint}", self.base_url);
-        let resp = self
+        let mut req = self
             .client
             .post(&url)
             .header("Content-Type", "application/json")
+            .header("X-API-Key", &self.api_key);
+        if let Some(team) = &self.team {
+            req = req.header(TEAM_HEADER, team);
+        }
+        let resp = req.json(body).send().await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            anyhow::bail!("API error {status}: {text}");
+        }
+
+        Ok(resp.json().await?)
+    }
+
+    async fn get<R: for<'de> Deserialize<'de>>(&self, endpoint: &str) -> Result<R> {
+        let url = format!("{}{endpoint}", self.base_url);
+        let resp = self
+            .client
+            .get(&url)
             .header("X-API-Key", &self.api_key)
-            .json(body)
             .send()
             .await?;
 
@@ -177,14 +259,17 @@ struct BlockingApiClient {
     client: reqwest::blocking::Client,
     base_url: String,
     api_key: String,
+    /// Sent as X-Shodh-Team so the server includes team memories
+    team: Option<String>,
 }
 
 impl BlockingApiClient {
-    fn new(base_url: String, api_key: String) -> Self {
+    fn new(base_url: String, api_key: String, team: Option<String>) -> Self {
         Self {
             client: reqwest::blocking::Client::new(),
             base_url,
             api_key,
+            team,
         }
     }
 
@@ -194,13 +279,15 @@ impl BlockingApiClient {
         body: &T,
     ) -> Result<R> {
         let url = format!("{}{endpoint}", self.base_url);
-        let resp = self
+        let mut req = self
             .client
             .post(&url)
             .header("Content-Type", "application/json")
-            .header("X-API-Key", &self.api_key)
-            .json(body)
-            .send()?;
+            .header("X-API-Key", &self.api_key);
+        if let Some(team) = &self.team {
+            req = req.header(TEAM_HEADER, team);
+        }
+        let resp = req.json(body).send()?;
 
         if !resp.status().is_success() {
             let status = resp.status();
@@ -216,17 +303,42 @@ impl BlockingApiClient {
 // API REQUEST/RESPONSE TYPES
 // =============================================================================
 
+#[derive(Serialize)]
+struct ReembedRequest {
+    user_id: Option<String>,
+    provider: Option<String>,
+    model: Option<String>,
+    dimension: Option<usize>,
+    force: bool,
+}
+
+#[derive(Deserialize)]
+struct ReembedStatus {
+    running: bool,
+    model: String,
+    users_total: usize,
+    users_done: usize,
+    memories_done: usize,
+    memories_total: usize,
+    reembedded: usize,
+    users_skipped: usize,
+    errors: Vec<String>,
+}
+
 #[derive(Serialize)]
 struct ProactiveContextRequest {
     user_id: String,
     context: String,
     max_results: u32,
     auto_ingest: bool,
+    overflow_digest: bool,
 }
 
 #[derive(Deserialize)]
 struct ProactiveContextResponse {
     memories: Vec<SurfacedMemory>,
+    #[serde(default)]
+    overflow_digest: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -265,11 +377,14 @@ struct RememberRequest {
     content: String,
     memory_type: Option<String>,
     tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<String>,
 }
 
 #[derive(Deserialize)]
 struct RememberResponse {
     id: String,
+    #[serde(default)]
     message: String,
 }
 
@@ -296,6 +411,42 @@ struct RecalledMemory {
     tags: Vec<String>,
 }
 
+#[derive(Serialize)]
+struct RecallByTagsRequest {
+    user_id: String,
+    tags: Vec<String>,
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct StoredMemoriesResponse {
+    memories: Vec<StoredMemory>,
+}
+
+#[derive(Deserialize)]
+struct StoredMemory {
+    id: String,
+    experience: StoredExperience,
+}
+
+#[derive(Deserialize)]
+struct StoredExperience {
+    content: String,
+    #[serde(default)]
+    experience_type: String,
+}
+
+#[derive(Serialize)]
+struct ForgetRequest {
+    user_id: String,
+    memory_id: String,
+}
+
+#[derive(Deserialize)]
+struct ForgetResponse {
+    id: String,
+}
+
 // =============================================================================
 // HOOK OUTPUT
 // =============================================================================
@@ -314,6 +465,40 @@ struct HookSpecificOutput {
     additional_context: String,
 }
 
+/// Hook output that stops the prompt from reaching the model (reason is shown to the user)
+#[derive(Serialize)]
+struct HookBlockOutput {
+    decision: &'static str,
+    reason: String,
+}
+
+/// Render memory content as a block quote so it reads as reference data, not instructions
+fn quote_memory(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| format!("  > {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Argument of a leading `/<name> <arg>` memory command, if the prompt is one
+fn parse_slash_command<'a>(message: &'a str, name: &str) -> Option<&'a str> {
+    let rest = message.trim_start().strip_prefix('/')?.strip_prefix(name)?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let text = rest.trim();
+    (!text.is_empty()).then_some(text)
+}
+
+fn output_hook_block(reason: &str) {
+    let output = HookBlockOutput {
+        decision: "block",
+        reason: reason.to_string(),
+    };
+    println!("{}", serde_json::to_string(&output).unwrap());
+}
+
 fn output_hook(event_name: &str, context: &str) {
     let output = HookOutput {
         hook_specific_output: HookSpecificOutput {
@@ -328,8 +513,14 @@ fn output_hook(event_name: &str, context: &str) {
 // HOOK HANDLERS
 // =============================================================================
 
-fn handle_session_start(api_url: &str, api_key: &str, user_id: &str, project_dir: Option<&str>) {
-    let client = BlockingApiClient::new(api_url.to_string(), api_key.to_string());
+fn handle_session_start(
+    api_url: &str,
+    api_key: &str,
+    user_id: &str,
+    team: Option<String>,
+    project_dir: Option<&str>,
+) {
+    let client = BlockingApiClient::new(api_url.to_string(), api_key.to_string(), team);
 
     let dir_name = project_dir
         .and_then(|p| std::path::Path::new(p).file_name())
@@ -344,6 +535,7 @@ fn handle_session_start(api_url: &str, api_key: &str, user_id: &str, project_dir
             context: format!("Starting session in {dir_name}"),
             max_results: 3,
             auto_ingest: false,
+            overflow_digest: false,
         },
     );
 
@@ -364,10 +556,10 @@ fn handle_session_start(api_url: &str, api_key: &str, user_id: &str, project_dir
             context_parts.push("### Relevant Memories:".to_string());
             for mem in ctx.memories.iter().take(3) {
                 context_parts.push(format!(
-                    "- [{}] {}: {}",
+                    "- [{}] {}:\n{}",
                     mem.memory_type,
                     &mem.id[..8.min(mem.id.len())],
-                    mem.content.chars().take(200).collect::<String>()
+                    quote_memory(&mem.content.chars().take(200).collect::<String>())
                 ));
             }
             context_parts.push(String::new());
@@ -411,8 +603,142 @@ fn handle_session_start(api_url: &str, api_key: &str, user_id: &str, project_dir
     output_hook("SessionStart", &context_parts.join("\n"));
 }
 
-fn handle_prompt_submit(api_url: &str, api_key: &str, user_id: &str, message: &str) {
-    let client = BlockingApiClient::new(api_url.to_string(), api_key.to_string());
+/// Minimum similarity for `/forget <query>` to delete the best match without an ID
+const FORGET_MIN_SIMILARITY: f32 = 0.6;
+
+fn recall_memories(
+    client: &BlockingApiClient,
+    user_id: &str,
+    query: &str,
+    limit: u32,
+) -> Result<Vec<RecalledMemory>> {
+    let resp: RecallResponse = client.post(
+        "/api/recall",
+        &RecallRequest {
+            user_id: user_id.to_string(),
+            query: query.to_string(),
+            limit: Some(limit),
+            mode: None,
+        },
+    )?;
+    Ok(resp.memories)
+}
+
+fn format_recalled(mem: &RecalledMemory) -> String {
+    format!(
+        "- [{}] {} ({:.0}%): {}",
+        &mem.id[..8.min(mem.id.len())],
+        mem.memory_type,
+        mem.similarity * 100.0,
+        mem.content.chars().take(200).collect::<String>()
+    )
+}
+
+fn recall_command(client: &BlockingApiClient, user_id: &str, query: &str) -> Result<String> {
+    let memories = recall_memories(client, user_id, query, 5)?;
+    if memories.is_empty() {
+        return Ok(format!("No memories found for \"{query}\"."));
+    }
+    let mut lines = vec![format!("Memories for \"{query}\":")];
+    lines.extend(memories.iter().map(format_recalled));
+    Ok(lines.join("\n"))
+}
+
+/// Whether a `/forget` argument looks like a memory ID (full UUID or 8+ char prefix)
+fn looks_like_memory_id(target: &str) -> bool {
+    target.len() >= 8 && target.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+}
+
+fn forget_command(client: &BlockingApiClient, user_id: &str, target: &str) -> Result<String> {
+    let memory_id = if looks_like_memory_id(target) {
+        target.to_string()
+    } else {
+        // Query form: only delete a confident best match, otherwise list candidates
+        let candidates = recall_memories(client, user_id, target, 3)?;
+        match candidates.first() {
+            Some(best) if best.similarity >= FORGET_MIN_SIMILARITY => best.id.clone(),
+            Some(_) => {
+                let mut lines = vec![format!(
+                    "No confident match for \"{target}\". Use /forget <id> with one of:"
+                )];
+                lines.extend(candidates.iter().map(format_recalled));
+                return Ok(lines.join("\n"));
+            }
+            None => return Ok(format!("No memories found for \"{target}\".")),
+        }
+    };
+
+    let resp: ForgetResponse = client.post(
+        "/api/forget",
+        &ForgetRequest {
+            user_id: user_id.to_string(),
+            memory_id,
+        },
+    )?;
+    Ok(format!("Forgot memory {}.", resp.id))
+}
+
+fn handle_prompt_submit(
+    api_url: &str,
+    api_key: &str,
+    user_id: &str,
+    team: Option<String>,
+    message: &str,
+    remember_passthrough: bool,
+) {
+    let client = BlockingApiClient::new(api_url.to_string(), api_key.to_string(), team);
+
+    // Explicit `/remember <text>`: store it with high priority instead of asking the model
+    if let Some(text) = parse_slash_command(message, "remember") {
+        let stored: Result<RememberResponse> = client.post(
+            "/api/remember",
+            &RememberRequest {
+                user_id: user_id.to_string(),
+                content: text.to_string(),
+                memory_type: None,
+                tags: Some(vec!["explicit".to_string()]),
+                priority: Some("high".to_string()),
+            },
+        );
+        match stored {
+            Ok(resp) if !remember_passthrough => {
+                output_hook_block(&format!("Remembered ({}): {}", resp.id, text));
+                return;
+            }
+            Ok(resp) => {
+                output_hook(
+                    "UserPromptSubmit",
+                    &format!(
+                        "The user asked to remember the following; it has been stored in memory ({}):\n{}",
+                        resp.id,
+                        quote_memory(text)
+                    ),
+                );
+                return;
+            }
+            Err(e) => eprintln!("shodh: /remember failed: {e}"),
+        }
+    }
+
+    // `/recall <query>` and `/forget <query|id>` are answered locally, never sent upstream
+    if let Some(query) = parse_slash_command(message, "recall") {
+        match recall_command(&client, user_id, query) {
+            Ok(reply) => {
+                output_hook_block(&reply);
+                return;
+            }
+            Err(e) => eprintln!("shodh: /recall failed: {e}"),
+        }
+    }
+    if let Some(target) = parse_slash_command(message, "forget") {
+        match forget_command(&client, user_id, target) {
+            Ok(reply) => {
+                output_hook_block(&reply);
+                return;
+            }
+            Err(e) => eprintln!("shodh: /forget failed: {e}"),
+        }
+    }
 
     // Get proactive context based on user message
     let context_result: Result<ProactiveContextResponse> = client.post(
@@ -422,6 +748,7 @@ fn handle_prompt_submit(api_url: &str, api_key: &str, user_id: &str, message: &s
             context: message.to_string(),
             max_results: 5,
             auto_ingest: true, // Store the context for implicit feedback
+            overflow_digest: true,
         },
     );
 
@@ -433,13 +760,16 @@ fn handle_prompt_submit(api_url: &str, api_key: &str, user_id: &str, message: &s
             for mem in ctx.memories.iter() {
                 let relevance = (mem.relevance_score * 100.0) as u32;
                 context_parts.push(format!(
-                    "- [{}%] **{}**: {}",
+                    "- [{}%] **{}**:\n{}",
                     relevance,
                     mem.memory_type,
-                    mem.content.chars().take(300).collect::<String>()
+                    quote_memory(&mem.content.chars().take(300).collect::<String>())
                 ));
             }
         }
+        if let Some(digest) = ctx.overflow_digest {
+            context_parts.push(format!("\n{}", quote_memory(&digest)));
+        }
     }
 
     // Only output if we have relevant context
@@ -476,6 +806,20 @@ struct RecallParams {
     mode: Option<String>,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct SearchParams {
+    /// Tags to match (memories with ANY of these tags are returned)
+    tags: Vec<String>,
+    /// Maximum number of results (default: 20)
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct ForgetParams {
+    /// Memory ID to delete (full UUID or 8+ character prefix)
+    memory_id: String,
+}
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 struct ProactiveContextParams {
     /// Current conversation context
@@ -621,9 +965,9 @@ struct ShodhMcpServer {
 
 #[tool_router]
 impl ShodhMcpServer {
-    fn new(api_url: String, api_key: String, user_id: String) -> Self {
+    fn new(api_url: String, api_key: String, user_id: String, team: Option<String>) -> Self {
         Self {
-            client: Arc::new(AsyncApiClient::new(api_url, api_key, user_id)),
+            client: Arc::new(AsyncApiClient::new(api_url, api_key, user_id, team)),
             tool_router: Self::tool_router(),
         }
     }
@@ -644,6 +988,7 @@ impl ShodhMcpServer {
                     content: params.content,
                     memory_type: params.memory_type,
                     tags: params.tags,
+                    priority: None,
                 },
             )
             .await;
@@ -703,6 +1048,77 @@ impl ShodhMcpServer {
         }
     }
 
+    #[tool(
+        description = "List memories by tag. Use this for exact lookups (e.g. all memories tagged 'auth') rather than semantic search."
+    )]
+    async fn search(
+        &self,
+        Parameters(params): Parameters<SearchParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let result: Result<StoredMemoriesResponse> = self
+            .client
+            .post(
+                "/api/recall/tags",
+                &RecallByTagsRequest {
+                    user_id: self.client.user_id.clone(),
+                    tags: params.tags,
+                    limit: Some(params.limit.unwrap_or(20)),
+                },
+            )
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let mut output = format!("Found {} memories:\n\n", resp.memories.len());
+                for mem in resp.memories {
+                    output.push_str(&format!(
+                        "**[{}]** {}\n{}\n\n",
+                        mem.experience.experience_type,
+                        &mem.id[..8.min(mem.id.len())],
+                        mem.experience.content
+                    ));
+                }
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Err(McpError {
+                code: ErrorCode::INTERNAL_ERROR,
+                message: Cow::from(e.to_string()),
+                data: None,
+            }),
+        }
+    }
+
+    #[tool(
+        description = "Delete a memory by ID. Use when a memory is wrong, outdated, or the user asks to forget it."
+    )]
+    async fn forget(
+        &self,
+        Parameters(params): Parameters<ForgetParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let result: Result<ForgetResponse> = self
+            .client
+            .post(
+                "/api/forget",
+                &ForgetRequest {
+                    user_id: self.client.user_id.clone(),
+                    memory_id: params.memory_id,
+                },
+            )
+            .await;
+
+        match result {
+            Ok(resp) => Ok(CallToolResult::success(vec![Content::text(format!(
+                "Forgot memory: {}",
+                resp.id
+            ))])),
+            Err(e) => Err(McpError {
+                code: ErrorCode::INTERNAL_ERROR,
+                message: Cow::from(e.to_string()),
+                data: None,
+            }),
+        }
+    }
+
     #[tool(
         description = "REQUIRED: Call this to surface relevant memories based on current context. Enables automatic memory surfacing and implicit feedback learning."
     )]
@@ -719,6 +1135,7 @@ impl ShodhMcpServer {
                     context: params.context,
                     max_results: params.max_results.unwrap_or(5),
                     auto_ingest: params.auto_ingest.unwrap_or(true),
+                    overflow_digest: true,
                 },
             )
             .await;
@@ -734,6 +1151,9 @@ impl ShodhMcpServer {
                         mem.content.chars().take(200).collect::<String>()
                     ));
                 }
+                if let Some(digest) = resp.overflow_digest {
+                    output.push_str(&format!("\n{digest}\n"));
+                }
                 Ok(CallToolResult::success(vec![Content::text(output)]))
             }
             Err(e) => Err(McpError {
@@ -968,7 +1388,8 @@ impl ServerHandler for ShodhMcpServer {
                 "Shodh Memory - persistent cognitive memory with causal reasoning. \
                  Use proactive_context at session start to surface relevant memories. \
                  Use remember to store decisions, learnings, errors. \
-                 Use recall to search memories. \
+                 Use recall to search memories, search to list them by tag. \
+                 Use forget to delete a wrong or outdated memory. \
                  Use lineage_trace to understand 'why' - trace causal chains backward/forward. \
                  Use lineage_link to explicitly connect cause→effect memories. \
                  Use lineage_confirm/reject to improve inference accuracy."
@@ -991,12 +1412,16 @@ async fn main() -> Result<()> {
             api_url,
             api_key,
             user_id,
+            team,
         } => {
             eprintln!("Starting shodh MCP server...");
             eprintln!("  API URL: {}", api_url);
             eprintln!("  User ID: {}", user_id);
+            if let Some(team) = &team {
+                eprintln!("  Team: {}", team);
+            }
 
-            let server = ShodhMcpServer::new(api_url, api_key, user_id);
+            let server = ShodhMcpServer::new(api_url, api_key, user_id, team);
             let service = server.serve(rmcp::transport::stdio()).await?;
             service.waiting().await?;
         }
@@ -1006,9 +1431,10 @@ async fn main() -> Result<()> {
                 api_url,
                 api_key,
                 user_id,
+                team,
                 project_dir,
             } => {
-                handle_session_start(&api_url, &api_key, &user_id, project_dir.as_deref());
+                handle_session_start(&api_url, &api_key, &user_id, team, project_dir.as_deref());
             }
 
             HookType::Prompt {
@@ -1016,11 +1442,40 @@ async fn main() -> Result<()> {
                 api_url,
                 api_key,
                 user_id,
+                team,
+                remember_passthrough,
             } => {
-                handle_prompt_submit(&api_url, &api_key, &user_id, &message);
+                handle_prompt_submit(
+                    &api_url,
+                    &api_key,
+                    &user_id,
+                    team,
+                    &message,
+                    remember_passthrough,
+                );
             }
         },
 
+        Commands::Reembed {
+            api_url,
+            api_key,
+            user_id,
+            provider,
+            model,
+            dimension,
+            force,
+            wait,
+        } => {
+            let request = ReembedRequest {
+                user_id,
+                provider,
+                model,
+                dimension,
+                force,
+            };
+            handle_reembed(api_url, api_key, request, wait).await?;
+        }
+
         Commands::Claude { port, args } => {
             handle_claude_launch(port, args).await?;
         }
@@ -1029,6 +1484,47 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Start a re-embedding job and optionally follow it to completion
+async fn handle_reembed(
+    api_url: String,
+    api_key: String,
+    request: ReembedRequest,
+    wait: bool,
+) -> Result<()> {
+    // Admin endpoints aren't scoped to a user
+    let client = AsyncApiClient::new(api_url, api_key, String::new(), None);
+    let mut status: ReembedStatus = client.post("/api/admin/reembed", &request).await?;
+    eprintln!(
+        "Re-embedding {} store(s) with {}",
+        status.users_total, status.model
+    );
+    if !wait {
+        eprintln!("Follow progress with GET /api/admin/reembed or `shodh reembed --wait`");
+        return Ok(());
+    }
+
+    while status.running {
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        status = client.get("/api/admin/reembed").await?;
+        eprintln!(
+            "  users {}/{} | current store {}/{} memories",
+            status.users_done, status.users_total, status.memories_done, status.memories_total
+        );
+    }
+
+    eprintln!(
+        "Done: {} memories re-embedded, {} store(s) already on {}",
+        status.reembedded, status.users_skipped, status.model
+    );
+    for error in &status.errors {
+        eprintln!("  failed: {error}");
+    }
+    if !status.errors.is_empty() {
+        anyhow::bail!("{} store(s) stayed on their old model", status.errors.len());
+    }
+    Ok(())
+}
+
 /// Launch Claude Code with Shodh Cortex proxy
 async fn handle_claude_launch(port: u16, args: Vec<String>) -> Result<()> {
     let server_url = format!("http://127.0.0.1:{port}");
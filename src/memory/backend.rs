@@ -0,0 +1,442 @@
+//! Storage Backends
+//!
+//! Long-term memories are persisted through the [`Storage`] trait, so the
+//! brain can run on an embedded store or a shared database:
+//!
+//! - `rocksdb` (default): [`MemoryStorage`], one embedded database per user
+//!   under the storage path
+//! - `postgres` (build with `--features postgres`): one shared Postgres
+//!   database with pgvector, so several server instances can serve the same
+//!   users behind a load balancer
+//!
+//! ```text
+//! SHODH_STORAGE_BACKEND=postgres
+//! SHODH_POSTGRES_URL=postgres://shodh:secret@db:5432/shodh
+//! SHODH_POSTGRES_POOL_SIZE=16
+//! ```
+//!
+//! With Postgres, memory records and their bookkeeping live in the database
+//! and vector search runs in pgvector, so every instance sees every memory.
+//! Derived stores (facts, lineage, learning history, temporal facts), the
+//! BM25 index and the in-process vector index stay in each instance's local
+//! storage directory and only cover memories written through that instance.
+//!
+//! Backends share one key-value area ("meta") for small bookkeeping records;
+//! the default methods below build interference history, watermarks and
+//! counters on top of it.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use rocksdb::DB;
+
+use super::replay::InterferenceRecord;
+use super::storage::{MemoryStorage, SearchCriteria, StorageStats, VectorMappingEntry};
+use super::types::{Memory, MemoryId};
+
+const RETRIEVAL_COUNT_KEY: &str = "stats:total_retrievals";
+const INTERFERENCE_PREFIX: &str = "interference:";
+const INTERFERENCE_TOTAL_KEY: &str = "interference_meta:total";
+const FACT_WATERMARK_PREFIX: &str = "_watermark:fact_extraction:";
+
+/// Persistence for one user's long-term memories
+pub trait Storage: Send + Sync {
+    /// Local directory for this store's derived data and caches
+    fn path(&self) -> &Path;
+
+    /// Insert or replace a memory
+    fn store(&self, memory: &Memory) -> Result<()>;
+
+    /// Fetch a memory, including forgotten ones
+    fn get(&self, id: &MemoryId) -> Result<Memory>;
+
+    /// Replace an existing memory
+    fn update(&self, memory: &Memory) -> Result<()> {
+        self.store(memory)
+    }
+
+    /// Remove a memory and its vector mapping
+    fn delete(&self, id: &MemoryId) -> Result<()>;
+
+    /// Ids of every stored memory, including forgotten ones
+    fn get_all_ids(&self) -> Result<Vec<MemoryId>>;
+
+    /// Every live (not forgotten) memory
+    fn get_all(&self) -> Result<Vec<Memory>>;
+
+    /// Live memories matching `criteria`
+    fn search(&self, criteria: SearchCriteria) -> Result<Vec<Memory>>;
+
+    fn get_stats(&self) -> Result<StorageStats>;
+
+    /// Make buffered writes durable
+    fn flush(&self) -> Result<()>;
+
+    // =========================================================================
+    // META RECORDS
+    // =========================================================================
+
+    fn get_meta(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    fn put_meta(&self, key: &str, value: &[u8]) -> Result<()>;
+
+    fn delete_meta(&self, key: &str) -> Result<()>;
+
+    /// Meta records whose key starts with `prefix`
+    fn scan_meta(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>>;
+
+    /// Delete meta records whose key starts with `prefix`, returning how many
+    fn delete_meta_prefix(&self, prefix: &str) -> Result<usize> {
+        let records = self.scan_meta(prefix)?;
+        for (key, _) in &records {
+            self.delete_meta(key)?;
+        }
+        Ok(records.len())
+    }
+
+    // =========================================================================
+    // VECTOR MAPPINGS
+    // =========================================================================
+
+    /// Vector index ids per memory, used to rebuild the in-process index
+    fn get_all_vector_mappings(&self) -> Result<Vec<(MemoryId, VectorMappingEntry)>>;
+
+    fn update_vector_mapping(&self, memory_id: &MemoryId, vector_ids: Vec<u32>) -> Result<()>;
+
+    fn delete_vector_mapping(&self, memory_id: &MemoryId) -> Result<()>;
+
+    /// Nearest memories to `embedding` by cosine similarity, for backends
+    /// with their own vector index. `None` means the in-process index is used.
+    fn vector_search(
+        &self,
+        _embedding: &[f32],
+        _limit: usize,
+    ) -> Result<Option<Vec<(MemoryId, f32)>>> {
+        Ok(None)
+    }
+
+    // =========================================================================
+    // LOOKUPS
+    // =========================================================================
+
+    /// Memory synced from an external system (e.g. "linear:SHO-39")
+    fn find_by_external_id(&self, external_id: &str) -> Result<Option<Memory>> {
+        Ok(self
+            .get_all()?
+            .into_iter()
+            .find(|m| m.external_id.as_deref() == Some(external_id)))
+    }
+
+    /// Live memory whose content has this [`super::types::content_hash`]
+    fn find_by_content_hash(&self, hash: &str) -> Result<Option<Memory>> {
+        Ok(self
+            .get_all()?
+            .into_iter()
+            .find(|m| super::types::content_hash(&m.experience.content) == hash))
+    }
+
+    fn get_children(&self, parent_id: &MemoryId) -> Result<Vec<Memory>> {
+        self.search(SearchCriteria::ByParent(parent_id.clone()))
+    }
+
+    /// Parent chain of a memory, nearest first
+    fn get_ancestors(&self, memory_id: &MemoryId) -> Result<Vec<Memory>> {
+        let mut ancestors = Vec::new();
+        let mut current_id = memory_id.clone();
+
+        // Walk up the parent chain (max 100 to prevent infinite loops)
+        for _ in 0..100 {
+            let memory = self.get(&current_id)?;
+            if let Some(parent_id) = &memory.parent_id {
+                let parent = self.get(parent_id)?;
+                ancestors.push(parent.clone());
+                current_id = parent_id.clone();
+            } else {
+                break; // Reached root
+            }
+        }
+
+        Ok(ancestors)
+    }
+
+    /// (ancestors, memory, children)
+    fn get_hierarchy_context(
+        &self,
+        memory_id: &MemoryId,
+    ) -> Result<(Vec<Memory>, Memory, Vec<Memory>)> {
+        let memory = self.get(memory_id)?;
+        let ancestors = self.get_ancestors(memory_id)?;
+        let children = self.get_children(memory_id)?;
+        Ok((ancestors, memory, children))
+    }
+
+    fn get_uncompressed_older_than(&self, cutoff: DateTime<Utc>) -> Result<Vec<Memory>> {
+        Ok(self
+            .get_all()?
+            .into_iter()
+            .filter(|m| !m.compressed && m.created_at < cutoff)
+            .collect())
+    }
+
+    // =========================================================================
+    // MAINTENANCE
+    // =========================================================================
+
+    /// Flag memories created before `cutoff` as forgotten, returning their ids
+    fn mark_forgotten_by_age(&self, cutoff: DateTime<Utc>) -> Result<Vec<MemoryId>> {
+        mark_forgotten(self, |m| m.created_at < cutoff)
+    }
+
+    /// Flag memories below `threshold` importance as forgotten, returning their ids
+    fn mark_forgotten_by_importance(&self, threshold: f32) -> Result<Vec<MemoryId>> {
+        mark_forgotten(self, |m| m.importance() < threshold)
+    }
+
+    fn update_access(&self, id: &MemoryId) -> Result<()> {
+        if let Ok(memory) = self.get(id) {
+            memory.update_access();
+            self.update(&memory)?;
+        }
+        Ok(())
+    }
+
+    /// Remove entries that can't be read; backends that never store legacy
+    /// formats have nothing to clean up
+    fn cleanup_corrupted(&self) -> Result<usize> {
+        Ok(0)
+    }
+
+    /// Re-write legacy-format memories, returning (migrated, current, failed)
+    fn migrate_legacy(&self) -> Result<(usize, usize, usize)> {
+        Ok((0, self.get_all_ids()?.len(), 0))
+    }
+
+    /// Re-seal records with the current encryption key, returning how many
+    /// were rewritten; backends that don't encrypt have nothing to do
+    fn reencrypt(&self) -> Result<usize> {
+        Ok(0)
+    }
+
+    /// Train a compression dictionary on the store's records once there are
+    /// enough of them, returning how many records were recompressed
+    fn train_compression(&self) -> Result<usize> {
+        Ok(0)
+    }
+
+    // =========================================================================
+    // COUNTERS AND WATERMARKS
+    // =========================================================================
+
+    fn get_retrieval_count(&self) -> Result<usize> {
+        Ok(self
+            .get_meta(RETRIEVAL_COUNT_KEY)?
+            .and_then(|v| {
+                v.get(..8)
+                    .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+            })
+            .unwrap_or(0) as usize)
+    }
+
+    /// Increment and persist the retrieval counter, returning the new value
+    fn increment_retrieval_count(&self) -> Result<usize> {
+        let count = self.get_retrieval_count()? + 1;
+        self.put_meta(RETRIEVAL_COUNT_KEY, &(count as u64).to_le_bytes())?;
+        Ok(count)
+    }
+
+    /// Fact extraction watermark for a user (unix millis)
+    fn get_fact_watermark(&self, user_id: &str) -> Option<i64> {
+        let key = format!("{FACT_WATERMARK_PREFIX}{user_id}");
+        match self.get_meta(&key) {
+            Ok(Some(bytes)) if bytes.len() == 8 => {
+                Some(i64::from_le_bytes(bytes[..8].try_into().unwrap()))
+            }
+            _ => None,
+        }
+    }
+
+    fn set_fact_watermark(&self, user_id: &str, timestamp_millis: i64) {
+        let key = format!("{FACT_WATERMARK_PREFIX}{user_id}");
+        if let Err(e) = self.put_meta(&key, &timestamp_millis.to_le_bytes()) {
+            tracing::warn!("Failed to persist fact extraction watermark: {e}");
+        }
+    }
+
+    // =========================================================================
+    // INTERFERENCE PERSISTENCE (SHO-106 RIF)
+    // =========================================================================
+
+    /// Persist interference records for a single memory (JSON)
+    fn save_interference_records(
+        &self,
+        memory_id: &str,
+        records: &[InterferenceRecord],
+    ) -> Result<()> {
+        let value =
+            serde_json::to_vec(records).context("Failed to serialize interference records")?;
+        self.put_meta(&format!("{INTERFERENCE_PREFIX}{memory_id}"), &value)
+            .context("Failed to persist interference records")
+    }
+
+    /// All interference history and the total event count, for startup
+    fn load_all_interference_records(
+        &self,
+    ) -> Result<(HashMap<String, Vec<InterferenceRecord>>, usize)> {
+        let mut history = HashMap::new();
+        let mut total_events = 0;
+        for (key, value) in self.scan_meta(INTERFERENCE_PREFIX)? {
+            let Some(memory_id) = key.strip_prefix(INTERFERENCE_PREFIX) else {
+                continue;
+            };
+            match serde_json::from_slice::<Vec<InterferenceRecord>>(&value) {
+                Ok(records) => {
+                    total_events += records.len();
+                    history.insert(memory_id.to_string(), records);
+                }
+                Err(e) => tracing::warn!(
+                    key = %key,
+                    error = %e,
+                    "Failed to deserialize interference records, skipping"
+                ),
+            }
+        }
+
+        // The persisted total may exceed the records kept after eviction
+        let persisted_total = self
+            .get_meta(INTERFERENCE_TOTAL_KEY)
+            .ok()
+            .flatten()
+            .filter(|v| v.len() == 8)
+            .map(|v| u64::from_le_bytes(v[..8].try_into().unwrap()) as usize)
+            .unwrap_or(total_events);
+
+        Ok((history, persisted_total.max(total_events)))
+    }
+
+    fn delete_interference_records(&self, memory_id: &str) -> Result<()> {
+        self.delete_meta(&format!("{INTERFERENCE_PREFIX}{memory_id}"))
+            .context("Failed to delete interference records")
+    }
+
+    fn save_interference_event_count(&self, count: usize) -> Result<()> {
+        self.put_meta(INTERFERENCE_TOTAL_KEY, &(count as u64).to_le_bytes())
+            .context("Failed to persist interference event count")
+    }
+
+    /// Delete all interference history (GDPR forget_all)
+    fn clear_all_interference_records(&self) -> Result<usize> {
+        self.delete_meta_prefix("interference")
+    }
+}
+
+/// Flag live memories matching `predicate` as forgotten
+fn mark_forgotten<S: Storage + ?Sized>(
+    storage: &S,
+    predicate: impl Fn(&Memory) -> bool,
+) -> Result<Vec<MemoryId>> {
+    let now = Utc::now().to_rfc3339();
+    let mut flagged = Vec::new();
+    for mut memory in storage.get_all()? {
+        if !predicate(&memory) {
+            continue;
+        }
+        let metadata = &mut memory.experience.metadata;
+        metadata.insert("forgotten".to_string(), "true".to_string());
+        metadata.insert("forgotten_at".to_string(), now.clone());
+        storage.update(&memory)?;
+        flagged.push(memory.id);
+    }
+    Ok(flagged)
+}
+
+/// Where long-term memories are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendKind {
+    RocksDb,
+    Postgres,
+}
+
+impl BackendKind {
+    /// Read SHODH_STORAGE_BACKEND (default: rocksdb)
+    pub fn from_env() -> Result<Self> {
+        match std::env::var("SHODH_STORAGE_BACKEND") {
+            Err(_) => Ok(Self::RocksDb),
+            Ok(v) => match v.trim().to_lowercase().as_str() {
+                "" | "rocksdb" | "embedded" => Ok(Self::RocksDb),
+                "postgres" | "postgresql" | "pg" => Ok(Self::Postgres),
+                other => bail!("Unknown SHODH_STORAGE_BACKEND '{other}' (rocksdb, postgres)"),
+            },
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RocksDb => "rocksdb",
+            Self::Postgres => "postgres",
+        }
+    }
+}
+
+/// A user's memory store plus the local RocksDB holding derived stores
+pub struct OpenedStorage {
+    pub memories: Arc<dyn Storage>,
+    /// Facts, lineage, learning history and temporal facts
+    pub local_db: Arc<DB>,
+}
+
+/// Open the store at `path` with the configured backend
+pub fn open(path: &Path) -> Result<OpenedStorage> {
+    let local = Arc::new(
+        MemoryStorage::new(path)
+            .with_context(|| format!("Failed to open storage at {:?}", path))?,
+    );
+    let local_db = local.db();
+    let memories: Arc<dyn Storage> = match BackendKind::from_env()? {
+        BackendKind::RocksDb => local,
+        #[cfg(feature = "postgres")]
+        BackendKind::Postgres => Arc::new(super::pg_storage::PostgresStorage::open(path, local)?),
+        #[cfg(not(feature = "postgres"))]
+        BackendKind::Postgres => {
+            bail!("SHODH_STORAGE_BACKEND=postgres requires building with --features postgres")
+        }
+    };
+    Ok(OpenedStorage { memories, local_db })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meta_defaults_read_embedded_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let embedded = MemoryStorage::new(dir.path()).unwrap();
+        embedded.set_fact_watermark("alice", 1_700_000_000_000);
+        embedded.save_interference_event_count(3).unwrap();
+        embedded.increment_retrieval_count().unwrap();
+
+        // Records written by the embedded store read back through the
+        // meta-based defaults other backends rely on
+        let storage: &dyn Storage = &embedded;
+        assert_eq!(storage.get_fact_watermark("alice"), Some(1_700_000_000_000));
+        assert_eq!(storage.get_fact_watermark("bob"), None);
+        assert_eq!(storage.get_retrieval_count().unwrap(), 1);
+        assert_eq!(storage.increment_retrieval_count().unwrap(), 2);
+        assert_eq!(embedded.get_retrieval_count().unwrap(), 2);
+
+        let (history, total) = storage.load_all_interference_records().unwrap();
+        assert!(history.is_empty());
+        assert_eq!(total, 3);
+
+        storage.put_meta("interference:m1", b"[]").unwrap();
+        assert_eq!(storage.scan_meta("interference:").unwrap().len(), 1);
+        assert_eq!(storage.delete_meta_prefix("interference").unwrap(), 2);
+        assert!(storage
+            .get_meta("interference_meta:total")
+            .unwrap()
+            .is_none());
+    }
+}
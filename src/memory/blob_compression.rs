@@ -0,0 +1,295 @@
+//! zstd Compression of Memory Records
+//!
+//! Memory records are compressed with zstd before they are written (and
+//! before they are sealed, when encryption at rest is on). Cortex-encoded
+//! interactions repeat the same scaffolding in every record ("User: ...
+//! Tools: ... Assistant: ..."), so a dictionary trained on the store's own
+//! records does much better than compressing each record on its own.
+//!
+//! ```text
+//! SHODH_COMPRESSION=zstd|none   # default zstd
+//! SHODH_ZSTD_LEVEL=3            # 1-19
+//! ```
+//!
+//! Records are written without a dictionary until the store holds enough of
+//! them to train one; heavy maintenance then trains it once and recompresses
+//! existing records. Dictionaries are kept in the store and never replaced,
+//! since records compressed with them can't be read without them.
+//!
+//! Decompression is transparent: values without the header are returned as
+//! they are, so stores written before compression (or with it turned off)
+//! keep working. Records that don't shrink are stored uncompressed.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{anyhow, bail, Context, Result};
+use parking_lot::RwLock;
+use zstd::dict::{DecoderDictionary, EncoderDictionary};
+
+/// Compressed record: magic, version, dictionary id (0 = none), plain length, zstd frame
+const COMPRESSED_MAGIC: &[u8; 3] = b"SHZ";
+const COMPRESSED_VERSION: u8 = 1;
+const HEADER_LEN: usize = COMPRESSED_MAGIC.len() + 1 + 4 + 4;
+
+/// Refuse to inflate records claiming more than this
+const MAX_PLAIN_LEN: usize = 256 * 1024 * 1024;
+
+/// Size of a trained dictionary
+pub const DICTIONARY_SIZE: usize = 32 * 1024;
+
+/// Records needed before a dictionary is trained, and the most sampled
+pub const MIN_TRAINING_SAMPLES: usize = 256;
+pub const MAX_TRAINING_SAMPLES: usize = 4096;
+
+/// Compression settings from the environment
+#[derive(Debug, Clone, Copy)]
+pub struct Settings {
+    pub enabled: bool,
+    pub level: i32,
+}
+
+impl Settings {
+    fn from_env() -> Self {
+        let enabled = !matches!(
+            std::env::var("SHODH_COMPRESSION")
+                .unwrap_or_default()
+                .to_lowercase()
+                .as_str(),
+            "none" | "off" | "false" | "0"
+        );
+        let level = std::env::var("SHODH_ZSTD_LEVEL")
+            .ok()
+            .and_then(|v| v.parse::<i32>().ok())
+            .map(|l| l.clamp(1, 19))
+            .unwrap_or(zstd::DEFAULT_COMPRESSION_LEVEL);
+        Self { enabled, level }
+    }
+}
+
+/// Process-wide compression settings, read once
+pub fn settings() -> Settings {
+    static SETTINGS: OnceLock<Settings> = OnceLock::new();
+    *SETTINGS.get_or_init(Settings::from_env)
+}
+
+/// Whether a stored value is a compressed record
+pub fn is_compressed(value: &[u8]) -> bool {
+    value.len() > HEADER_LEN && &value[..3] == COMPRESSED_MAGIC && value[3] == COMPRESSED_VERSION
+}
+
+struct Dictionary {
+    id: u32,
+    encoder: EncoderDictionary<'static>,
+    decoder: DecoderDictionary<'static>,
+}
+
+#[derive(Default)]
+struct Dictionaries {
+    current: Option<Arc<Dictionary>>,
+    by_id: HashMap<u32, Arc<Dictionary>>,
+}
+
+/// Compresses and decompresses one store's records
+pub struct Codec {
+    settings: Settings,
+    dictionaries: RwLock<Dictionaries>,
+}
+
+impl Codec {
+    pub fn new(settings: Settings) -> Self {
+        Self {
+            settings,
+            dictionaries: RwLock::new(Dictionaries::default()),
+        }
+    }
+
+    /// Codec using the process-wide settings
+    pub fn from_env() -> Self {
+        Self::new(settings())
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.settings.enabled
+    }
+
+    /// Register a dictionary. The highest id becomes the one new records use.
+    pub fn add_dictionary(&self, id: u32, raw: &[u8]) {
+        let dictionary = Arc::new(Dictionary {
+            id,
+            encoder: EncoderDictionary::copy(raw, self.settings.level),
+            decoder: DecoderDictionary::copy(raw),
+        });
+        let mut dictionaries = self.dictionaries.write();
+        if dictionaries.current.as_ref().is_none_or(|d| d.id < id) {
+            dictionaries.current = Some(dictionary.clone());
+        }
+        dictionaries.by_id.insert(id, dictionary);
+    }
+
+    /// Id of the dictionary new records are compressed with (0 = none)
+    pub fn current_dictionary(&self) -> u32 {
+        self.dictionaries
+            .read()
+            .current
+            .as_ref()
+            .map_or(0, |d| d.id)
+    }
+
+    /// Compress `plain` with the current dictionary. Returns it unchanged when
+    /// compression is off or doesn't make it smaller.
+    pub fn compress(&self, plain: Vec<u8>) -> Result<Vec<u8>> {
+        if !self.settings.enabled || plain.len() > MAX_PLAIN_LEN {
+            return Ok(plain);
+        }
+        let current = self.dictionaries.read().current.clone();
+        let (dictionary_id, frame) = match &current {
+            Some(dictionary) => (
+                dictionary.id,
+                zstd::bulk::Compressor::with_prepared_dictionary(&dictionary.encoder)?
+                    .compress(&plain)?,
+            ),
+            None => (0, zstd::bulk::compress(&plain, self.settings.level)?),
+        };
+        if HEADER_LEN + frame.len() >= plain.len() {
+            return Ok(plain);
+        }
+        let mut compressed = Vec::with_capacity(HEADER_LEN + frame.len());
+        compressed.extend_from_slice(COMPRESSED_MAGIC);
+        compressed.push(COMPRESSED_VERSION);
+        compressed.extend_from_slice(&dictionary_id.to_le_bytes());
+        compressed.extend_from_slice(&(plain.len() as u32).to_le_bytes());
+        compressed.extend_from_slice(&frame);
+        Ok(compressed)
+    }
+
+    /// Decompress a stored value, which may be compressed or not. Works with
+    /// compression turned off, so stores can be switched back.
+    pub fn decompress<'a>(&self, value: &'a [u8]) -> Result<Cow<'a, [u8]>> {
+        if !is_compressed(value) {
+            return Ok(Cow::Borrowed(value));
+        }
+        let dictionary_id = u32::from_le_bytes(value[4..8].try_into().unwrap());
+        let plain_len = u32::from_le_bytes(value[8..12].try_into().unwrap()) as usize;
+        if plain_len > MAX_PLAIN_LEN {
+            bail!("compressed record claims {plain_len} bytes");
+        }
+        let frame = &value[HEADER_LEN..];
+        let plain = if dictionary_id == 0 {
+            zstd::bulk::decompress(frame, plain_len)?
+        } else {
+            let dictionary = self
+                .dictionaries
+                .read()
+                .by_id
+                .get(&dictionary_id)
+                .cloned()
+                .ok_or_else(|| {
+                    anyhow!("record compressed with unknown dictionary {dictionary_id}")
+                })?;
+            zstd::bulk::Decompressor::with_prepared_dictionary(&dictionary.decoder)?
+                .decompress(frame, plain_len)?
+        };
+        if plain.len() != plain_len {
+            bail!(
+                "compressed record inflated to {} bytes, expected {plain_len}",
+                plain.len()
+            );
+        }
+        Ok(Cow::Owned(plain))
+    }
+
+    /// Whether a stored value would be written differently now: uncompressed,
+    /// or compressed with an older dictionary
+    pub fn is_stale(&self, value: &[u8]) -> bool {
+        if !self.settings.enabled {
+            return false;
+        }
+        if !is_compressed(value) {
+            return true;
+        }
+        let dictionary_id = u32::from_le_bytes(value[4..8].try_into().unwrap());
+        dictionary_id != self.current_dictionary()
+    }
+}
+
+/// Train a dictionary on sample records (plain, before compression)
+pub fn train_dictionary<S: AsRef<[u8]>>(samples: &[S]) -> Result<Vec<u8>> {
+    if samples.len() < MIN_TRAINING_SAMPLES {
+        bail!(
+            "need at least {MIN_TRAINING_SAMPLES} records to train a dictionary, got {}",
+            samples.len()
+        );
+    }
+    zstd::dict::from_samples(samples, DICTIONARY_SIZE).context("zstd dictionary training failed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ON: Settings = Settings {
+        enabled: true,
+        level: 3,
+    };
+
+    fn interaction(i: usize) -> Vec<u8> {
+        format!(
+            "User: how do I configure the retry policy for job {i}?\n\
+             Tools: read_file(src/jobs/{i}.rs), grep(\"retry\")\n\
+             Assistant: Job {i} retries up to {} times with exponential backoff; \
+             set max_retries in the job config to change it.\n",
+            i % 7
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn test_round_trip_without_dictionary() {
+        let codec = Codec::new(ON);
+        let plain = interaction(1).repeat(4);
+        let compressed = codec.compress(plain.clone()).unwrap();
+        assert!(is_compressed(&compressed));
+        assert!(compressed.len() < plain.len());
+        assert_eq!(&*codec.decompress(&compressed).unwrap(), &plain[..]);
+
+        // Values without the header pass through
+        assert_eq!(&*codec.decompress(b"raw bincode").unwrap(), b"raw bincode");
+        // Too small to shrink: stored as is
+        assert_eq!(codec.compress(b"tiny".to_vec()).unwrap(), b"tiny");
+    }
+
+    #[test]
+    fn test_dictionary_improves_small_records() {
+        let samples: Vec<Vec<u8>> = (0..1000).map(interaction).collect();
+        let raw = train_dictionary(&samples).unwrap();
+
+        let plain = interaction(5000);
+        let without = Codec::new(ON).compress(plain.clone()).unwrap().len();
+        let codec = Codec::new(ON);
+        codec.add_dictionary(1, &raw);
+        let with = codec.compress(plain.clone()).unwrap();
+        assert!(with.len() < without);
+        assert_eq!(&*codec.decompress(&with).unwrap(), &plain[..]);
+
+        // Unreadable without the dictionary
+        assert!(Codec::new(ON).decompress(&with).is_err());
+        assert!(train_dictionary(&samples[..10]).is_err());
+    }
+
+    #[test]
+    fn test_disabled_still_reads() {
+        let compressed = Codec::new(ON).compress(interaction(1).repeat(4)).unwrap();
+        let off = Codec::new(Settings {
+            enabled: false,
+            level: 3,
+        });
+        assert_eq!(off.compress(b"raw".to_vec()).unwrap(), b"raw");
+        assert_eq!(
+            &*off.decompress(&compressed).unwrap(),
+            &interaction(1).repeat(4)[..]
+        );
+        assert!(!off.is_stale(b"raw"));
+    }
+}
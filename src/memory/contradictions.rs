@@ -0,0 +1,198 @@
+//! Contradiction Detection
+//!
+//! Facts change: a project that "uses npm" later "switched to pnpm". Both
+//! memories stay semantically close to the same queries, so without help the
+//! stale one keeps getting injected next to (or instead of) the current one.
+//!
+//! When a new memory is stored it is compared against its nearest neighbours.
+//! An older, important neighbour is contradicted when the new memory:
+//!
+//! - flips its polarity ("the cache is enabled" vs "the cache is not enabled"), or
+//! - announces a replacement ("switched to", "migrated to", "no longer", ...)
+//!   naming something the old memory never mentioned
+//!
+//! Both memories are flagged in metadata. Retrieval down-weights the stale one
+//! and GET /api/conflicts lists the pairs for review.
+
+use std::collections::HashMap;
+
+use crate::constants::{
+    CONTRADICTION_CHANGE_MARKERS, CONTRADICTION_MIN_IMPORTANCE, CONTRADICTION_SIMILARITY_MAX,
+    CONTRADICTION_SIMILARITY_MIN, FACT_NEGATION_MARKERS,
+};
+use crate::memory::types::{Memory, SharedMemory};
+
+/// Metadata key on a stale memory naming the memory that contradicts it
+pub const CONTRADICTED_BY_KEY: &str = "contradicted_by";
+
+/// Metadata key on a stale memory with the `ContradictionKind`
+pub const CONTRADICTION_KIND_KEY: &str = "contradiction_kind";
+
+/// Metadata key on a new memory listing the memories it contradicts (comma-separated)
+pub const CONTRADICTS_KEY: &str = "contradicts";
+
+/// How a newer memory contradicts an older one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContradictionKind {
+    /// Same statement with opposite polarity
+    Negation,
+    /// The newer memory announces a replacement
+    Replacement,
+}
+
+impl ContradictionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Negation => "negation",
+            Self::Replacement => "replacement",
+        }
+    }
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|w| {
+            w.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+                .to_lowercase()
+        })
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Even number of negation markers (including none)
+fn is_positive(words: &[String]) -> bool {
+    words
+        .iter()
+        .filter(|w| FACT_NEGATION_MARKERS.contains(&w.as_str()))
+        .count()
+        % 2
+        == 0
+}
+
+/// Word following a change marker ("switched to pnpm" -> "pnpm")
+fn replacement_target(text: &str) -> Option<String> {
+    let lower = text.to_lowercase();
+    CONTRADICTION_CHANGE_MARKERS.iter().find_map(|marker| {
+        let at = lower.find(marker)?;
+        words(&lower[at + marker.len()..])
+            .into_iter()
+            .find(|w| w.len() >= 2 && !matches!(w.as_str(), "the" | "a" | "an" | "to" | "use"))
+    })
+}
+
+/// Whether `newer` contradicts `older`, given their embedding similarity
+pub fn detect(older: &Memory, newer: &Memory, similarity: f32) -> Option<ContradictionKind> {
+    if older.id == newer.id
+        || older.is_forgotten()
+        || older.created_at > newer.created_at
+        || older.importance() < CONTRADICTION_MIN_IMPORTANCE
+        || similarity < CONTRADICTION_SIMILARITY_MIN
+    {
+        return None;
+    }
+
+    let old_words = words(&older.experience.content);
+    let new_words = words(&newer.experience.content);
+    if is_positive(&old_words) != is_positive(&new_words) {
+        return Some(ContradictionKind::Negation);
+    }
+
+    // Near-duplicates restate the old memory rather than replace it
+    if similarity >= CONTRADICTION_SIMILARITY_MAX {
+        return None;
+    }
+    let target = replacement_target(&newer.experience.content)?;
+    (!old_words.contains(&target)).then_some(ContradictionKind::Replacement)
+}
+
+/// A stale memory and the newer memory that contradicts it
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    pub stale: SharedMemory,
+    pub current: SharedMemory,
+    pub kind: Option<ContradictionKind>,
+}
+
+/// Flagged conflicts among `memories`, most recently contradicted first
+pub fn find_conflicts(memories: &[SharedMemory]) -> Vec<Conflict> {
+    let by_id: HashMap<String, &SharedMemory> =
+        memories.iter().map(|m| (m.id.0.to_string(), m)).collect();
+
+    let mut conflicts: Vec<Conflict> = memories
+        .iter()
+        .filter(|m| !m.is_forgotten())
+        .filter_map(|stale| {
+            let current_id = stale.experience.metadata.get(CONTRADICTED_BY_KEY)?;
+            let current = by_id.get(current_id)?;
+            let kind = match stale
+                .experience
+                .metadata
+                .get(CONTRADICTION_KIND_KEY)
+                .map(String::as_str)
+            {
+                Some("negation") => Some(ContradictionKind::Negation),
+                Some("replacement") => Some(ContradictionKind::Replacement),
+                _ => None,
+            };
+            Some(Conflict {
+                stale: stale.clone(),
+                current: (*current).clone(),
+                kind,
+            })
+        })
+        .collect();
+    conflicts.sort_by(|a, b| b.current.created_at.cmp(&a.current.created_at));
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::types::{Experience, MemoryId};
+    use chrono::{Duration, Utc};
+
+    fn memory(content: &str, importance: f32, age_days: i64) -> Memory {
+        let experience = Experience {
+            content: content.to_string(),
+            ..Default::default()
+        };
+        Memory::new(
+            MemoryId(uuid::Uuid::new_v4()),
+            experience,
+            importance,
+            None,
+            None,
+            None,
+            Some(Utc::now() - Duration::days(age_days)),
+        )
+    }
+
+    #[test]
+    fn test_replacement_detected() {
+        let old = memory("The frontend uses npm for package management", 0.8, 30);
+        let new = memory("Switched to pnpm for frontend package management", 0.5, 0);
+        assert_eq!(
+            detect(&old, &new, 0.8),
+            Some(ContradictionKind::Replacement)
+        );
+
+        // Restating the replacement isn't a new contradiction
+        let again = memory("We switched to pnpm last month", 0.5, 0);
+        let noted = memory("Switched to pnpm, lockfile is pnpm-lock.yaml", 0.8, 5);
+        assert_eq!(detect(&noted, &again, 0.8), None);
+    }
+
+    #[test]
+    fn test_negation_detected() {
+        let old = memory("Response caching is enabled in production", 0.7, 10);
+        let new = memory("Response caching is not enabled in production", 0.5, 0);
+        assert_eq!(detect(&old, &new, 0.97), Some(ContradictionKind::Negation));
+
+        // Unimportant or unrelated memories aren't flagged
+        let trivial = memory("Response caching is enabled in production", 0.2, 10);
+        assert_eq!(detect(&trivial, &new, 0.97), None);
+        assert_eq!(detect(&old, &new, 0.3), None);
+        // Only newer memories contradict older ones
+        assert_eq!(detect(&new, &old, 0.97), None);
+    }
+}
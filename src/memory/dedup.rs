@@ -0,0 +1,225 @@
+//! Near-Duplicate Consolidation
+//!
+//! Agents encode the same interaction many times ("Ran cargo test", "Ran cargo
+//! test again"), and the copies crowd distinct memories out of retrieval
+//! slots. This module clusters near-duplicates so the memory system can fold
+//! each cluster into one canonical memory.
+//!
+//! Two memories are duplicates when their word 3-gram shingles overlap
+//! (Jaccard) AND their embeddings agree (cosine). Memories without embeddings
+//! need a much higher shingle overlap. Candidate pairs come from an inverted
+//! shingle index rather than all-pairs comparison, so a pass stays near-linear
+//! in practice.
+//!
+//! Reference: Broder (1997) "On the resemblance and containment of documents"
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use crate::constants::{
+    DEDUP_EMBEDDING_THRESHOLD, DEDUP_MAX_SHINGLE_POSTINGS, DEDUP_SHINGLE_ONLY_THRESHOLD,
+    DEDUP_SHINGLE_THRESHOLD,
+};
+use crate::memory::types::SharedMemory;
+use crate::similarity::cosine_similarity;
+
+/// Words per shingle
+const SHINGLE_SIZE: usize = 3;
+
+/// Hashed word 3-grams of normalized text (single words for very short text)
+pub fn shingles(text: &str) -> HashSet<u64> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|w| {
+            w.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let hash = |parts: &[String]| {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        parts.hash(&mut hasher);
+        hasher.finish()
+    };
+    if words.len() < SHINGLE_SIZE {
+        return words.chunks(1).map(hash).collect();
+    }
+    words.windows(SHINGLE_SIZE).map(hash).collect()
+}
+
+/// Jaccard similarity of two shingle sets
+pub fn jaccard(a: &HashSet<u64>, b: &HashSet<u64>) -> f32 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let intersection = a.intersection(b).count();
+    let union = a.len() + b.len() - intersection;
+    intersection as f32 / union as f32
+}
+
+/// A group of near-duplicate memories
+#[derive(Debug, Clone)]
+pub struct DuplicateCluster {
+    /// Memory that survives: most important, then most accessed, then oldest
+    pub canonical: SharedMemory,
+    /// Memories to fold into the canonical one
+    pub duplicates: Vec<SharedMemory>,
+    /// Lowest pairwise shingle similarity that joined this cluster
+    pub min_similarity: f32,
+}
+
+/// Whether two memories are near-duplicates, with their shingle similarity
+fn near_duplicate(
+    a: &SharedMemory,
+    b: &SharedMemory,
+    sa: &HashSet<u64>,
+    sb: &HashSet<u64>,
+) -> Option<f32> {
+    if a.experience.experience_type != b.experience.experience_type {
+        return None;
+    }
+    let overlap = jaccard(sa, sb);
+    let similar = match (&a.experience.embeddings, &b.experience.embeddings) {
+        (Some(ea), Some(eb)) if ea.len() == eb.len() && !ea.is_empty() => {
+            overlap >= DEDUP_SHINGLE_THRESHOLD
+                && cosine_similarity(ea, eb) >= DEDUP_EMBEDDING_THRESHOLD
+        }
+        _ => overlap >= DEDUP_SHINGLE_ONLY_THRESHOLD,
+    };
+    similar.then_some(overlap)
+}
+
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// Cluster near-duplicate memories; memories without a duplicate are omitted
+///
+/// Forgotten (soft-deleted) memories are ignored.
+pub fn find_duplicate_clusters(memories: &[SharedMemory]) -> Vec<DuplicateCluster> {
+    let live: Vec<&SharedMemory> = memories.iter().filter(|m| !m.is_forgotten()).collect();
+    let shingle_sets: Vec<HashSet<u64>> = live
+        .iter()
+        .map(|m| shingles(&m.experience.content))
+        .collect();
+
+    let mut postings: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (i, set) in shingle_sets.iter().enumerate() {
+        for s in set {
+            postings.entry(*s).or_default().push(i);
+        }
+    }
+
+    let mut parent: Vec<usize> = (0..live.len()).collect();
+    let mut min_similarity: HashMap<usize, f32> = HashMap::new();
+    let mut compared: HashSet<(usize, usize)> = HashSet::new();
+    for ids in postings.values() {
+        // Boilerplate shingles shared by everything carry no signal
+        if ids.len() < 2 || ids.len() > DEDUP_MAX_SHINGLE_POSTINGS {
+            continue;
+        }
+        for (n, &i) in ids.iter().enumerate() {
+            for &j in &ids[n + 1..] {
+                if !compared.insert((i, j)) {
+                    continue;
+                }
+                if let Some(sim) =
+                    near_duplicate(live[i], live[j], &shingle_sets[i], &shingle_sets[j])
+                {
+                    let (ri, rj) = (find(&mut parent, i), find(&mut parent, j));
+                    if ri != rj {
+                        parent[rj] = ri;
+                        let merged = min_similarity
+                            .remove(&rj)
+                            .unwrap_or(1.0)
+                            .min(min_similarity.get(&ri).copied().unwrap_or(1.0))
+                            .min(sim);
+                        min_similarity.insert(ri, merged);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<&SharedMemory>> = HashMap::new();
+    for i in 0..live.len() {
+        let root = find(&mut parent, i);
+        groups.entry(root).or_default().push(live[i]);
+    }
+
+    let mut clusters: Vec<DuplicateCluster> = groups
+        .into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(root, mut members)| {
+            members.sort_by(|a, b| {
+                b.importance()
+                    .total_cmp(&a.importance())
+                    .then_with(|| b.access_count().cmp(&a.access_count()))
+                    .then_with(|| a.created_at.cmp(&b.created_at))
+            });
+            DuplicateCluster {
+                canonical: members[0].clone(),
+                duplicates: members[1..].iter().map(|m| (*m).clone()).collect(),
+                min_similarity: min_similarity.get(&root).copied().unwrap_or(1.0),
+            }
+        })
+        .collect();
+    clusters.sort_by_key(|c| std::cmp::Reverse(c.duplicates.len()));
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::types::{Experience, ExperienceType, Memory, MemoryId};
+    use std::sync::Arc;
+
+    fn memory(content: &str, importance: f32) -> SharedMemory {
+        let experience = Experience {
+            content: content.to_string(),
+            experience_type: ExperienceType::Observation,
+            ..Default::default()
+        };
+        Arc::new(Memory::new(
+            MemoryId(uuid::Uuid::new_v4()),
+            experience,
+            importance,
+            None,
+            None,
+            None,
+            None,
+        ))
+    }
+
+    #[test]
+    fn test_clusters_near_duplicates_only() {
+        let memories = vec![
+            memory("Ran cargo test on the workspace and all tests passed", 0.3),
+            memory("Ran cargo test on the workspace and all tests passed!", 0.6),
+            memory("Ran cargo test on the workspace and all tests passed.", 0.4),
+            memory("Deployed the API gateway to the staging cluster", 0.5),
+        ];
+
+        let clusters = find_duplicate_clusters(&memories);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].duplicates.len(), 2);
+        // Most important member survives
+        assert_eq!(clusters[0].canonical.id, memories[1].id);
+    }
+
+    #[test]
+    fn test_jaccard() {
+        let a = shingles("the quick brown fox jumps");
+        let b = shingles("the quick brown fox sleeps");
+        let sim = jaccard(&a, &b);
+        assert!(sim > 0.3 && sim < 1.0);
+        assert_eq!(jaccard(&a, &a), 1.0);
+    }
+}
@@ -0,0 +1,218 @@
+//! User-defined Encoding Filters
+//!
+//! Rules that keep matching interactions out of memory entirely, e.g. anything
+//! containing "DO NOT STORE" or anything produced by a given model. Rules live
+//! in a plain-text file that is re-read whenever it changes on disk, so they
+//! can be edited without restarting the server.
+//!
+//! One rule per line, `<field>:<pattern>`, where field is `content`, `tool` or
+//! `model`. Patterns are globs (`model:claude-3-5-haiku*`); wrap a pattern in
+//! slashes for a regex (`content:/DO NOT STORE/`). Blank lines and `#`
+//! comments are ignored.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use anyhow::{anyhow, Context, Result};
+use parking_lot::RwLock;
+
+/// Which part of an interaction a rule matches against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterField {
+    Content,
+    Tool,
+    Model,
+}
+
+#[derive(Debug, Clone)]
+enum FilterPattern {
+    Glob(glob::Pattern),
+    Regex(regex::Regex),
+}
+
+impl FilterPattern {
+    fn matches(&self, value: &str) -> bool {
+        match self {
+            Self::Glob(pattern) => pattern.matches(value),
+            Self::Regex(regex) => regex.is_match(value),
+        }
+    }
+}
+
+/// A single exclusion rule
+#[derive(Debug, Clone)]
+pub struct FilterRule {
+    pub field: FilterField,
+    /// The rule as written, for logging which rule dropped an interaction
+    pub source: String,
+    pattern: FilterPattern,
+}
+
+impl FilterRule {
+    /// Parse a `<field>:<pattern>` rule
+    pub fn parse(line: &str) -> Result<Self> {
+        let (field, pattern) = line
+            .split_once(':')
+            .ok_or_else(|| anyhow!("expected <field>:<pattern>"))?;
+        let field = match field.trim().to_lowercase().as_str() {
+            "content" => FilterField::Content,
+            "tool" => FilterField::Tool,
+            "model" => FilterField::Model,
+            other => return Err(anyhow!("unknown field '{other}'")),
+        };
+        let pattern = pattern.trim();
+        let pattern = match pattern.strip_prefix('/').and_then(|p| p.strip_suffix('/')) {
+            Some(regex) if !regex.is_empty() => FilterPattern::Regex(regex::Regex::new(regex)?),
+            _ if pattern.is_empty() => return Err(anyhow!("empty pattern")),
+            _ => FilterPattern::Glob(glob::Pattern::new(pattern)?),
+        };
+        Ok(Self {
+            field,
+            source: line.trim().to_string(),
+            pattern,
+        })
+    }
+}
+
+/// What is known about an interaction when deciding whether to store it
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FilterInput<'a> {
+    pub content: &'a str,
+    pub tool: Option<&'a str>,
+    pub model: Option<&'a str>,
+}
+
+/// A parsed set of exclusion rules
+#[derive(Debug, Clone, Default)]
+pub struct EncodingFilterSet {
+    pub rules: Vec<FilterRule>,
+}
+
+impl EncodingFilterSet {
+    /// Parse a rules file, skipping (and warning about) invalid lines
+    pub fn parse(text: &str) -> Self {
+        let mut rules = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match FilterRule::parse(line) {
+                Ok(rule) => rules.push(rule),
+                Err(e) => tracing::warn!("Ignoring encoding filter on line {}: {}", index + 1, e),
+            }
+        }
+        Self { rules }
+    }
+
+    /// The first rule excluding this interaction, if any
+    pub fn excludes(&self, input: &FilterInput) -> Option<&FilterRule> {
+        self.rules.iter().find(|rule| {
+            let value = match rule.field {
+                FilterField::Content => Some(input.content),
+                FilterField::Tool => input.tool,
+                FilterField::Model => input.model,
+            };
+            value.is_some_and(|v| rule.pattern.matches(v))
+        })
+    }
+}
+
+/// Encoding filters backed by a file, reloaded when its modification time changes
+pub struct EncodingFilters {
+    path: Option<PathBuf>,
+    loaded: RwLock<(Option<SystemTime>, Arc<EncodingFilterSet>)>,
+}
+
+impl EncodingFilters {
+    /// Filters read from `path`, or no filters when `path` is `None`
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let filters = Self {
+            path,
+            loaded: RwLock::new((None, Arc::new(EncodingFilterSet::default()))),
+        };
+        if let Some(path) = &filters.path {
+            if let Err(e) = filters.reload(path) {
+                tracing::warn!("Failed to load encoding filters: {:#}", e);
+            }
+        }
+        filters
+    }
+
+    /// Current rules, re-reading the file first if it changed since the last load
+    pub fn current(&self) -> Arc<EncodingFilterSet> {
+        if let Some(path) = &self.path {
+            let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+            if modified != self.loaded.read().0 {
+                if let Err(e) = self.reload(path) {
+                    tracing::warn!("Failed to reload encoding filters: {:#}", e);
+                }
+            }
+        }
+        self.loaded.read().1.clone()
+    }
+
+    /// The rule excluding this interaction, if any
+    pub fn excludes(&self, input: &FilterInput) -> Option<String> {
+        self.current()
+            .excludes(input)
+            .map(|rule| rule.source.clone())
+    }
+
+    fn reload(&self, path: &Path) -> Result<()> {
+        let mut loaded = self.loaded.write();
+        let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        // A missing file means no filters
+        let set = match modified {
+            Some(_) => {
+                let text = std::fs::read_to_string(path)
+                    .with_context(|| format!("reading {}", path.display()))?;
+                EncodingFilterSet::parse(&text)
+            }
+            None => EncodingFilterSet::default(),
+        };
+        tracing::info!(
+            "Loaded {} encoding filter(s) from {}",
+            set.rules.len(),
+            path.display()
+        );
+        *loaded = (modified, Arc::new(set));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_rules() {
+        let set = EncodingFilterSet::parse(
+            "# never store these\n\
+             content:/DO NOT STORE/\n\
+             model:claude-3-5-haiku*\n\
+             tool:mcp__secret_*\n\
+             bogus rule\n",
+        );
+        assert_eq!(set.rules.len(), 3);
+
+        let input = |content, tool, model| FilterInput {
+            content,
+            tool,
+            model,
+        };
+        assert!(set
+            .excludes(&input("please DO NOT STORE this", None, None))
+            .is_some());
+        assert!(set
+            .excludes(&input("hi", None, Some("claude-3-5-haiku-20241022")))
+            .is_some());
+        assert!(set
+            .excludes(&input("hi", Some("mcp__secret_vault"), None))
+            .is_some());
+        assert!(set
+            .excludes(&input("hi", Some("Bash"), Some("claude-sonnet-4")))
+            .is_none());
+    }
+}
@@ -0,0 +1,351 @@
+//! Encryption at Rest
+//!
+//! When a key is configured, every memory record (content, embeddings and
+//! metadata) is sealed with XChaCha20-Poly1305 before it is written and only
+//! opened in memory when read. Records are bound to their memory id, so a
+//! sealed record can't be swapped in for another memory.
+//!
+//! ```text
+//! SHODH_ENCRYPTION_KEY=<32 bytes, hex or base64>
+//! SHODH_ENCRYPTION_KEY_FILE=/run/secrets/shodh-key   # one key per line, first is current
+//! SHODH_ENCRYPTION_PREVIOUS_KEYS=<old key>,<older key>
+//! ```
+//!
+//! Key rotation: make the new key current and list the old one as previous.
+//! Records sealed with an old key still open and are re-sealed when next
+//! written; POST /api/admin/reencrypt re-seals everything, after which the
+//! old key can be dropped. The same job encrypts stores written before a key
+//! was configured.
+//!
+//! The content index used for ingest dedup is keyed with an HMAC derived from
+//! the current key rather than a bare SHA-256, so identical content can't be
+//! confirmed by hashing guesses. After a rotation, dedup only matches
+//! memories written since until the re-encryption job rebuilds that index.
+//!
+//! Not covered: secondary index keys (entities, tags, dates) and the BM25
+//! keyword index hold terms in plaintext, and derived stores (facts, lineage,
+//! todos) are unencrypted. The Vamana index file isn't written while
+//! encryption is on; the index is rebuilt from the store on startup.
+
+use std::borrow::Cow;
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Sealed record: magic, version, key id, nonce, ciphertext + tag
+const SEALED_MAGIC: &[u8; 3] = b"SHE";
+const SEALED_VERSION: u8 = 1;
+const KEY_ID_LEN: usize = 4;
+const NONCE_LEN: usize = 24;
+const HEADER_LEN: usize = SEALED_MAGIC.len() + 1 + KEY_ID_LEN + NONCE_LEN;
+
+/// Context string for deriving the content index MAC key
+const CONTENT_MAC_CONTEXT: &[u8] = b"shodh-memory content index v1";
+
+struct Key {
+    id: [u8; KEY_ID_LEN],
+    cipher: XChaCha20Poly1305,
+    /// Separate key for content index digests, never used for sealing
+    content_mac: [u8; 32],
+}
+
+impl Key {
+    fn parse(encoded: &str) -> Result<Self> {
+        let encoded = encoded.trim();
+        let bytes = if encoded.len() == 64 && encoded.chars().all(|c| c.is_ascii_hexdigit()) {
+            hex::decode(encoded)?
+        } else {
+            general_purpose::STANDARD
+                .decode(encoded)
+                .context("encryption key must be 32 bytes as hex or base64")?
+        };
+        if bytes.len() != 32 {
+            bail!("encryption key must be 32 bytes, got {}", bytes.len());
+        }
+        let digest = Sha256::digest(&bytes);
+        let mut id = [0u8; KEY_ID_LEN];
+        id.copy_from_slice(&digest[..KEY_ID_LEN]);
+        let mut mac =
+            <HmacSha256 as Mac>::new_from_slice(&bytes).expect("HMAC accepts any key length");
+        mac.update(CONTENT_MAC_CONTEXT);
+        let mut content_mac = [0u8; 32];
+        content_mac.copy_from_slice(&mac.finalize().into_bytes());
+        Ok(Self {
+            id,
+            content_mac,
+            cipher: XChaCha20Poly1305::new_from_slice(&bytes)
+                .map_err(|_| anyhow!("invalid encryption key"))?,
+        })
+    }
+}
+
+/// Current key plus previous keys still accepted for reading
+pub struct Keyring {
+    current: Key,
+    previous: Vec<Key>,
+}
+
+impl Keyring {
+    /// Current key first, then previous keys
+    pub fn new<S: AsRef<str>>(keys: &[S]) -> Result<Self> {
+        let mut keys = keys
+            .iter()
+            .map(|k| Key::parse(k.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        if keys.is_empty() {
+            bail!("no encryption key given");
+        }
+        let current = keys.remove(0);
+        Ok(Self {
+            current,
+            previous: keys,
+        })
+    }
+
+    /// Read SHODH_ENCRYPTION_KEY / SHODH_ENCRYPTION_KEY_FILE, plus
+    /// SHODH_ENCRYPTION_PREVIOUS_KEYS. `None` when no key is configured.
+    pub fn from_env() -> Result<Option<Self>> {
+        let mut keys: Vec<String> = Vec::new();
+        if let Ok(key) = std::env::var("SHODH_ENCRYPTION_KEY") {
+            if !key.trim().is_empty() {
+                keys.push(key);
+            }
+        }
+        if let Ok(path) = std::env::var("SHODH_ENCRYPTION_KEY_FILE") {
+            let contents = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read SHODH_ENCRYPTION_KEY_FILE {path}"))?;
+            keys.extend(
+                contents
+                    .lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                    .map(String::from),
+            );
+        }
+        if keys.is_empty() {
+            return Ok(None);
+        }
+        if let Ok(previous) = std::env::var("SHODH_ENCRYPTION_PREVIOUS_KEYS") {
+            keys.extend(
+                previous
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|k| !k.is_empty())
+                    .map(String::from),
+            );
+        }
+        Self::new(&keys).map(Some)
+    }
+
+    /// Id of the key new records are sealed with (hex)
+    pub fn current_key_id(&self) -> String {
+        hex::encode(self.current.id)
+    }
+
+    /// Keyed digest of a [`super::types::content_hash`] for the content index
+    /// (hex HMAC-SHA256 under a key derived from the current key)
+    pub fn content_digest(&self, content_hash: &str) -> String {
+        let mut mac = <HmacSha256 as Mac>::new_from_slice(&self.current.content_mac)
+            .expect("HMAC accepts any key length");
+        mac.update(content_hash.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    /// Seal `plaintext` with the current key, bound to `aad` (the record key)
+    pub fn seal(&self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .current
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad,
+                },
+            )
+            .map_err(|_| anyhow!("encryption failed"))?;
+        let mut sealed = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+        sealed.extend_from_slice(SEALED_MAGIC);
+        sealed.push(SEALED_VERSION);
+        sealed.extend_from_slice(&self.current.id);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    fn open_sealed(&self, aad: &[u8], sealed: &[u8]) -> Result<(Vec<u8>, bool)> {
+        let key_id = &sealed[4..4 + KEY_ID_LEN];
+        let (key, stale) = if key_id == self.current.id {
+            (&self.current, false)
+        } else {
+            let key = self
+                .previous
+                .iter()
+                .find(|k| k.id == key_id)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "record sealed with unknown key {}; add it to SHODH_ENCRYPTION_PREVIOUS_KEYS",
+                        hex::encode(key_id)
+                    )
+                })?;
+            (key, true)
+        };
+        let nonce = XNonce::from_slice(&sealed[4 + KEY_ID_LEN..HEADER_LEN]);
+        let plaintext = key
+            .cipher
+            .decrypt(
+                nonce,
+                Payload {
+                    msg: &sealed[HEADER_LEN..],
+                    aad,
+                },
+            )
+            .map_err(|_| anyhow!("decryption failed: wrong key or tampered record"))?;
+        Ok((plaintext, stale))
+    }
+}
+
+/// Whether a stored value is a sealed record
+pub fn is_sealed(value: &[u8]) -> bool {
+    value.len() > HEADER_LEN && &value[..3] == SEALED_MAGIC && value[3] == SEALED_VERSION
+}
+
+/// Content index hash for a [`super::types::content_hash`]: keyed when
+/// encryption is on, so the index can't confirm guessed content
+pub fn content_index_hash(keyring: Option<&Keyring>, content_hash: &str) -> String {
+    match keyring {
+        Some(keyring) => keyring.content_digest(content_hash),
+        None => content_hash.to_string(),
+    }
+}
+
+/// Open a stored value, which may be sealed or plaintext. The flag is true
+/// when the value should be rewritten: plaintext while encryption is on, or
+/// sealed with a previous key.
+pub fn open<'a>(
+    keyring: Option<&Keyring>,
+    aad: &[u8],
+    value: &'a [u8],
+) -> Result<(Cow<'a, [u8]>, bool)> {
+    match keyring {
+        Some(keyring) if is_sealed(value) => {
+            let (plaintext, stale) = keyring.open_sealed(aad, value)?;
+            Ok((Cow::Owned(plaintext), stale))
+        }
+        Some(_) => Ok((Cow::Borrowed(value), true)),
+        None if is_sealed(value) => {
+            bail!("record is encrypted but no SHODH_ENCRYPTION_KEY is configured")
+        }
+        None => Ok((Cow::Borrowed(value), false)),
+    }
+}
+
+/// Process-wide keyring from the environment, read once
+pub fn keyring() -> Result<Option<Arc<Keyring>>> {
+    static KEYRING: OnceLock<std::result::Result<Option<Arc<Keyring>>, String>> = OnceLock::new();
+    KEYRING
+        .get_or_init(|| {
+            Keyring::from_env()
+                .map(|k| k.map(Arc::new))
+                .map_err(|e| format!("{e:#}"))
+        })
+        .clone()
+        .map_err(|e| anyhow!("Invalid encryption configuration: {e}"))
+}
+
+/// Whether memory records are encrypted at rest
+pub fn enabled() -> bool {
+    matches!(keyring(), Ok(Some(_)))
+}
+
+/// Progress of a re-encryption job across users' stores
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReencryptStatus {
+    pub running: bool,
+    /// Key records are being sealed with
+    pub key_id: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub users_total: usize,
+    pub users_done: usize,
+    /// Records re-sealed so far, across users
+    pub resealed: usize,
+    /// "user_id: reason" for stores that failed
+    pub errors: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+    const NEW_KEY: &str = "ICEiIyQlJicoKSorLC0uLzAxMjM0NTY3ODk6Ozw9Pj8=";
+
+    #[test]
+    fn test_seal_and_open() {
+        let keyring = Keyring::new(&[OLD_KEY]).unwrap();
+        let sealed = keyring.seal(b"id-1", b"api_key=hunter2").unwrap();
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.windows(7).any(|w| w == b"hunter2"));
+
+        let (plaintext, rewrite) = open(Some(&keyring), b"id-1", &sealed).unwrap();
+        assert_eq!(&*plaintext, b"api_key=hunter2");
+        assert!(!rewrite);
+
+        // Bound to the record key
+        assert!(open(Some(&keyring), b"id-2", &sealed).is_err());
+        // Unreadable without a key
+        assert!(open(None, b"id-1", &sealed).is_err());
+        // Plaintext passes through, flagged for sealing
+        let (plaintext, rewrite) = open(Some(&keyring), b"id-1", b"plain").unwrap();
+        assert_eq!(&*plaintext, b"plain");
+        assert!(rewrite);
+    }
+
+    #[test]
+    fn test_content_digest_is_keyed() {
+        let old = Keyring::new(&[OLD_KEY]).unwrap();
+        let new = Keyring::new(&[NEW_KEY]).unwrap();
+        let hash = crate::memory::types::content_hash("Deployed the gateway");
+        assert_eq!(old.content_digest(&hash), old.content_digest(&hash));
+        assert_ne!(old.content_digest(&hash), hash);
+        assert_ne!(old.content_digest(&hash), new.content_digest(&hash));
+    }
+
+    #[test]
+    fn test_rotation() {
+        let old = Keyring::new(&[OLD_KEY]).unwrap();
+        let sealed = old.seal(b"id", b"secret").unwrap();
+
+        let rotated = Keyring::new(&[NEW_KEY, OLD_KEY]).unwrap();
+        let (plaintext, stale) = open(Some(&rotated), b"id", &sealed).unwrap();
+        assert_eq!(&*plaintext, b"secret");
+        assert!(stale);
+
+        let resealed = rotated.seal(b"id", &plaintext).unwrap();
+        let new_only = Keyring::new(&[NEW_KEY]).unwrap();
+        assert!(!open(Some(&new_only), b"id", &resealed).unwrap().1);
+        assert!(open(Some(&new_only), b"id", &sealed).is_err());
+    }
+
+    #[test]
+    fn test_key_parsing() {
+        assert!(Keyring::new(&["too-short"]).is_err());
+        assert!(Keyring::new(&[&OLD_KEY[..62]]).is_err());
+        assert!(Keyring::new::<&str>(&[]).is_err());
+        assert_ne!(
+            Keyring::new(&[OLD_KEY]).unwrap().current_key_id(),
+            Keyring::new(&[NEW_KEY]).unwrap().current_key_id()
+        );
+    }
+}
@@ -48,6 +48,15 @@ const SIGNAL_REPETITION_PENALTY: f32 = -0.4; // User asked again = memories fail
 const SIGNAL_TOPIC_CHANGE_BOOST: f32 = 0.2; // User moved on = task might be complete
 const SIGNAL_IGNORED_PENALTY: f32 = -0.2; // Memory shown but completely unused
 
+/// Outcome-based signals: test/build result of the first verification command
+/// run after memories were surfaced
+const SIGNAL_TOOL_PASS_BOOST: f32 = 0.6;
+const SIGNAL_TOOL_FAIL_PENALTY: f32 = -0.4;
+
+/// Any tool call failing right after memories were surfaced (weak: the
+/// failure may well be unrelated to what was injected)
+const SIGNAL_NEXT_ACTION_FAIL_PENALTY: f32 = -0.2;
+
 /// Weights for combining entity and semantic signals
 const ENTITY_WEIGHT: f32 = 0.4;
 const SEMANTIC_WEIGHT: f32 = 0.6;
@@ -103,6 +112,39 @@ const NEGATIVE_KEYWORDS: &[&str] = &[
     "don't show",
 ];
 
+/// Command prefixes that verify work (tests, builds, type checks)
+/// Matched against each `&&` / `;` / `|` segment of a shell command
+const VERIFICATION_COMMANDS: &[&str] = &[
+    "cargo test",
+    "cargo build",
+    "cargo check",
+    "cargo clippy",
+    "cargo nextest",
+    "npm test",
+    "npm run test",
+    "npm run build",
+    "yarn test",
+    "yarn build",
+    "pnpm test",
+    "pnpm build",
+    "npx jest",
+    "npx vitest",
+    "npx tsc",
+    "tsc",
+    "pytest",
+    "python -m pytest",
+    "go test",
+    "go build",
+    "go vet",
+    "mvn test",
+    "mvn package",
+    "gradle test",
+    "./gradlew test",
+    "dotnet test",
+    "dotnet build",
+    "make",
+];
+
 // =============================================================================
 // SIGNAL TYPES
 // =============================================================================
@@ -141,6 +183,14 @@ pub enum SignalTrigger {
         memory_entities_used: usize,
         response_entities_total: usize,
     },
+
+    /// Test/build command run after the memories were surfaced
+    /// Action: passed → memories likely helped; failed → they may have misled
+    ToolOutcome { command: String, passed: bool },
+
+    /// First tool call after the memories were surfaced returned an error
+    /// Action: the memories may have pointed the model the wrong way
+    NextActionFailed { tool: String },
 }
 
 /// A single feedback signal
@@ -194,6 +244,51 @@ impl SignalRecord {
             SignalTrigger::NegativeKeywords { keywords },
         )
     }
+
+    /// Create signal from the result of a test/build command
+    pub fn from_tool_outcome(command: &str, passed: bool) -> Self {
+        let (value, confidence) = if passed {
+            (SIGNAL_TOOL_PASS_BOOST, 0.7)
+        } else {
+            (SIGNAL_TOOL_FAIL_PENALTY, 0.6) // Failures often have unrelated causes
+        };
+        Self::new(
+            value,
+            confidence,
+            SignalTrigger::ToolOutcome {
+                command: command.to_string(),
+                passed,
+            },
+        )
+    }
+
+    /// Whether this signal shows the response building on the memory
+    pub fn is_citation(&self) -> bool {
+        match &self.trigger {
+            SignalTrigger::EntityOverlap { overlap_ratio } => {
+                *overlap_ratio >= OVERLAP_STRONG_THRESHOLD
+            }
+            SignalTrigger::SemanticSimilarity { similarity } => {
+                *similarity >= SEMANTIC_STRONG_THRESHOLD
+            }
+            SignalTrigger::EntityFlow {
+                memory_entities_used,
+                ..
+            } => *memory_entities_used > 0,
+            _ => false,
+        }
+    }
+
+    /// Create signal from a failed tool call right after surfacing
+    pub fn from_next_action_failure(tool: &str) -> Self {
+        Self::new(
+            SIGNAL_NEXT_ACTION_FAIL_PENALTY,
+            0.4,
+            SignalTrigger::NextActionFailed {
+                tool: tool.to_string(),
+            },
+        )
+    }
 }
 
 // =============================================================================
@@ -366,6 +461,20 @@ pub struct FeedbackMomentum {
 
     /// Contexts where this memory was misleading
     pub misleading_contexts: Vec<ContextFingerprint>,
+
+    /// Times this memory was surfaced by proactive context
+    #[serde(default)]
+    pub surfaced_count: u32,
+
+    /// Signals showing the response built on this memory
+    #[serde(default)]
+    pub cited_count: u32,
+
+    /// Signals with a positive / negative value
+    #[serde(default)]
+    pub positive_count: u32,
+    #[serde(default)]
+    pub negative_count: u32,
 }
 
 impl FeedbackMomentum {
@@ -381,6 +490,10 @@ impl FeedbackMomentum {
             recent_signals: VecDeque::with_capacity(MAX_RECENT_SIGNALS),
             helpful_contexts: Vec::new(),
             misleading_contexts: Vec::new(),
+            surfaced_count: 0,
+            cited_count: 0,
+            positive_count: 0,
+            negative_count: 0,
         }
     }
 
@@ -511,6 +624,15 @@ impl FeedbackMomentum {
                 (self.stability - STABILITY_DECREMENT_MULTIPLIER * contradiction_strength).max(0.0);
         }
 
+        if signal.value > 0.0 {
+            self.positive_count += 1;
+        } else if signal.value < 0.0 {
+            self.negative_count += 1;
+        }
+        if signal.is_citation() {
+            self.cited_count += 1;
+        }
+
         // Record signal
         self.recent_signals.push_back(signal);
         if self.recent_signals.len() > MAX_RECENT_SIGNALS {
@@ -526,6 +648,48 @@ impl FeedbackMomentum {
         Trend::from_signals(&self.recent_signals)
     }
 
+    /// Fold another memory's momentum into this one (merged duplicates)
+    ///
+    /// Counts add up, EMA and stability become signal-weighted averages, and
+    /// recent signals and contexts are combined, keeping the newest.
+    pub fn absorb(&mut self, other: &FeedbackMomentum) {
+        let total = self.signal_count + other.signal_count;
+        if total > 0 {
+            let weight = |n: u32| n as f32 / total as f32;
+            self.ema =
+                self.ema * weight(self.signal_count) + other.ema * weight(other.signal_count);
+            self.stability = self.stability * weight(self.signal_count)
+                + other.stability * weight(other.signal_count);
+        }
+        self.signal_count = total;
+        self.surfaced_count += other.surfaced_count;
+        self.cited_count += other.cited_count;
+        self.positive_count += other.positive_count;
+        self.negative_count += other.negative_count;
+        self.first_signal_at = match (self.first_signal_at, other.first_signal_at) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.last_signal_at = self.last_signal_at.max(other.last_signal_at);
+
+        let mut signals: Vec<SignalRecord> = self
+            .recent_signals
+            .drain(..)
+            .chain(other.recent_signals.iter().cloned())
+            .collect();
+        signals.sort_by_key(|s| s.timestamp);
+        let skip = signals.len().saturating_sub(MAX_RECENT_SIGNALS);
+        self.recent_signals = signals.into_iter().skip(skip).collect();
+
+        for fingerprint in other
+            .helpful_contexts
+            .iter()
+            .chain(&other.misleading_contexts)
+        {
+            self.add_context(fingerprint.clone());
+        }
+    }
+
     /// Add context fingerprint
     pub fn add_context(&mut self, fingerprint: ContextFingerprint) {
         let target = if fingerprint.was_helpful {
@@ -608,6 +772,12 @@ pub struct PendingFeedback {
     pub surfaced_memories: Vec<SurfacedMemoryInfo>,
     pub context: String,
     pub context_embedding: Vec<f32>,
+    /// Whether a test/build outcome was already applied to these memories
+    #[serde(default)]
+    pub outcome_recorded: bool,
+    /// Whether the first tool call after surfacing has been seen
+    #[serde(default)]
+    pub next_action_seen: bool,
 }
 
 impl PendingFeedback {
@@ -623,6 +793,8 @@ impl PendingFeedback {
             surfaced_memories: memories,
             context,
             context_embedding,
+            outcome_recorded: false,
+            next_action_seen: false,
         }
     }
 
@@ -697,6 +869,21 @@ pub fn detect_negative_keywords(text: &str) -> Vec<String> {
         .collect()
 }
 
+/// Whether a shell command runs tests, a build, or a type check
+pub fn is_verification_command(command: &str) -> bool {
+    command
+        .split(['&', ';', '|'])
+        .map(str::trim)
+        .any(|segment| {
+            VERIFICATION_COMMANDS.iter().any(|prefix| {
+                segment == *prefix
+                    || segment
+                        .strip_prefix(prefix)
+                        .is_some_and(|rest| rest.starts_with(' '))
+            })
+        })
+}
+
 /// FBK-8: Calculate entity flow between memory and response
 ///
 /// Tracks how the response builds on memory entities:
@@ -1252,6 +1439,49 @@ impl FeedbackStore {
         })
     }
 
+    /// Momentum for a memory from the cache or disk, without creating it
+    pub fn load_momentum(&self, memory_id: &MemoryId) -> Option<FeedbackMomentum> {
+        if let Some(momentum) = self.momentum.get(memory_id) {
+            return Some(momentum.clone());
+        }
+        let (db, cf) = (self.db.as_ref()?, self.feedback_cf()?);
+        let key = format!("momentum:{}", memory_id.0);
+        let data = db.get_cf(cf, key.as_bytes()).ok()??;
+        serde_json::from_slice(&data).ok()
+    }
+
+    /// Fold `from`'s momentum into `into`'s, when `from` was merged into
+    /// `into` as a near-duplicate. `from`'s own entry is kept so restoring it
+    /// from the trash brings its history back. False if `from` had none.
+    pub fn merge_momentum(
+        &mut self,
+        into: &MemoryId,
+        into_type: ExperienceType,
+        from: &MemoryId,
+    ) -> bool {
+        let Some(other) = self.load_momentum(from) else {
+            return false;
+        };
+        self.get_or_create_momentum(into.clone(), into_type)
+            .absorb(&other);
+        self.mark_dirty(into);
+        true
+    }
+
+    /// Install momentum carried over from an export as `memory_id`'s,
+    /// replacing any it had
+    pub fn restore_momentum(
+        &mut self,
+        memory_id: MemoryId,
+        memory_type: ExperienceType,
+        mut momentum: FeedbackMomentum,
+    ) {
+        momentum.memory_id = memory_id.clone();
+        momentum.memory_type = memory_type;
+        self.momentum.insert(memory_id.clone(), momentum);
+        self.dirty.insert(memory_id);
+    }
+
     /// Get momentum for a memory (if exists)
     pub fn get_momentum(&self, memory_id: &MemoryId) -> Option<&FeedbackMomentum> {
         self.momentum.get(memory_id)
@@ -1262,8 +1492,30 @@ impl FeedbackStore {
         self.dirty.insert(memory_id.clone());
     }
 
+    /// Momentum for a surfaced memory that is receiving feedback
+    ///
+    /// A memory's first feedback creates its momentum and counts the surfacing
+    /// it came from; `set_pending` only counts surfacings of memories that
+    /// already have momentum.
+    pub fn surfaced_momentum(&mut self, memory_id: MemoryId) -> &mut FeedbackMomentum {
+        let momentum = self.get_or_create_momentum(memory_id, ExperienceType::Context);
+        momentum.surfaced_count = momentum.surfaced_count.max(1);
+        momentum
+    }
+
     /// Set pending feedback for a user (also persists to disk)
+    ///
+    /// Each call is one surfacing of the pending memories, counted in the
+    /// `surfaced_count` of those that already have momentum. No momentum is
+    /// created here; see `surfaced_momentum`.
     pub fn set_pending(&mut self, pending: PendingFeedback) {
+        for memory in &pending.surfaced_memories {
+            if let Some(mut momentum) = self.load_momentum(&memory.id) {
+                momentum.surfaced_count += 1;
+                self.momentum.insert(memory.id.clone(), momentum);
+                self.mark_dirty(&memory.id);
+            }
+        }
         let user_id = pending.user_id.clone();
         self.pending.insert(user_id.clone(), pending.clone());
 
@@ -1291,11 +1543,106 @@ impl FeedbackStore {
         result
     }
 
+    /// Remove everything held for a user: pending feedback, previous context and
+    /// the momentum of `memory_ids` (in memory and on disk). Returns how many
+    /// momentum entries were removed.
+    pub fn purge_user(&mut self, user_id: &str, memory_ids: &[MemoryId]) -> usize {
+        self.take_pending(user_id);
+        self.previous_context.remove(user_id);
+        if let (Some(db), Some(cf)) = (&self.db, self.feedback_cf()) {
+            let key = format!("prev_ctx:{}", user_id);
+            let _ = db.delete_cf(cf, key.as_bytes());
+        }
+
+        let mut removed = 0;
+        for memory_id in memory_ids {
+            let cached = self.momentum.remove(memory_id).is_some();
+            self.dirty.remove(memory_id);
+            let mut stored = false;
+            if let (Some(db), Some(cf)) = (&self.db, self.feedback_cf()) {
+                let key = format!("momentum:{}", memory_id.0);
+                stored = matches!(db.get_cf(cf, key.as_bytes()), Ok(Some(_)));
+                if stored {
+                    if let Err(e) = db.delete_cf(cf, key.as_bytes()) {
+                        tracing::warn!("Failed to delete feedback momentum: {}", e);
+                    }
+                }
+            }
+            if cached || stored {
+                removed += 1;
+            }
+        }
+        removed
+    }
+
     /// Get pending feedback for a user (without removing)
     pub fn get_pending(&self, user_id: &str) -> Option<&PendingFeedback> {
         self.pending.get(user_id)
     }
 
+    /// Apply a test/build outcome to the memories surfaced for a user.
+    ///
+    /// Only the first verification command after memories were surfaced counts;
+    /// later runs in the same turn mostly reflect the model's own fixes.
+    /// Returns the memory IDs that received the signal.
+    pub fn record_tool_outcome(
+        &mut self,
+        user_id: &str,
+        command: &str,
+        passed: bool,
+    ) -> Vec<MemoryId> {
+        let memory_ids: Vec<MemoryId> = match self.pending.get_mut(user_id) {
+            Some(pending) if !pending.outcome_recorded && !pending.is_expired() => {
+                pending.outcome_recorded = true;
+                pending.next_action_seen = true;
+                pending
+                    .surfaced_memories
+                    .iter()
+                    .map(|m| m.id.clone())
+                    .collect()
+            }
+            _ => return Vec::new(),
+        };
+
+        let signal = SignalRecord::from_tool_outcome(command, passed);
+        for memory_id in &memory_ids {
+            self.surfaced_momentum(memory_id.clone())
+                .update(signal.clone());
+            self.mark_dirty(memory_id);
+        }
+        memory_ids
+    }
+
+    /// Weakly penalize the memories surfaced for a user when the very next
+    /// tool call after surfacing failed.
+    ///
+    /// Only the first tool call counts. Returns the memory IDs that received
+    /// the signal (empty when the call succeeded or was not the first).
+    pub fn record_next_action(&mut self, user_id: &str, tool: &str, failed: bool) -> Vec<MemoryId> {
+        let memory_ids: Vec<MemoryId> = match self.pending.get_mut(user_id) {
+            Some(pending) if !pending.next_action_seen && !pending.is_expired() => {
+                pending.next_action_seen = true;
+                if !failed {
+                    return Vec::new();
+                }
+                pending
+                    .surfaced_memories
+                    .iter()
+                    .map(|m| m.id.clone())
+                    .collect()
+            }
+            _ => return Vec::new(),
+        };
+
+        let signal = SignalRecord::from_next_action_failure(tool);
+        for memory_id in &memory_ids {
+            self.surfaced_momentum(memory_id.clone())
+                .update(signal.clone());
+            self.mark_dirty(memory_id);
+        }
+        memory_ids
+    }
+
     /// Clean up expired pending feedback
     pub fn cleanup_expired(&mut self) {
         self.pending.retain(|_, p| !p.is_expired());
@@ -1504,6 +1851,34 @@ mod tests {
         assert!(momentum.stability > 0.7);
     }
 
+    #[test]
+    fn test_absorb_combines_momentum() {
+        let signal = |value: f32| {
+            SignalRecord::new(
+                value,
+                1.0,
+                SignalTrigger::EntityOverlap { overlap_ratio: 1.0 },
+            )
+        };
+        let mut canonical =
+            FeedbackMomentum::new(MemoryId(Uuid::new_v4()), ExperienceType::Learning);
+        let mut duplicate =
+            FeedbackMomentum::new(MemoryId(Uuid::new_v4()), ExperienceType::Learning);
+        canonical.update(signal(1.0));
+        for _ in 0..3 {
+            duplicate.update(signal(1.0));
+        }
+        duplicate.surfaced_count = 4;
+
+        let duplicate_ema = duplicate.ema;
+        canonical.absorb(&duplicate);
+
+        assert_eq!(canonical.signal_count, 4);
+        assert_eq!(canonical.surfaced_count, 4);
+        assert_eq!(canonical.recent_signals.len(), 4);
+        assert!(canonical.ema > 0.0 && canonical.ema <= duplicate_ema);
+    }
+
     #[test]
     fn test_trend_detection() {
         let mut signals = VecDeque::new();
@@ -1577,6 +1952,7 @@ mod tests {
     fn test_feedback_store_pending() {
         let mut store = FeedbackStore::new();
         let user_id = "test-user";
+        let memory_id = MemoryId(Uuid::new_v4());
 
         // Initially no pending
         assert!(store.get_pending(user_id).is_none());
@@ -1587,14 +1963,14 @@ mod tests {
             "test context".to_string(),
             vec![0.1; 384],
             vec![SurfacedMemoryInfo {
-                id: MemoryId(Uuid::new_v4()),
+                id: memory_id.clone(),
                 entities: ["rust", "memory"].iter().map(|s| s.to_string()).collect(),
                 content_preview: "Test memory".to_string(),
                 score: 0.8,
                 embedding: Vec::new(),
             }],
         );
-        store.set_pending(pending);
+        store.set_pending(pending.clone());
 
         // Should have pending now
         assert!(store.get_pending(user_id).is_some());
@@ -1603,6 +1979,14 @@ mod tests {
             1
         );
 
+        // Surfacing alone creates no momentum
+        assert!(store.get_momentum(&memory_id).is_none());
+
+        // Once the memory has feedback, later surfacings are counted
+        assert_eq!(store.surfaced_momentum(memory_id.clone()).surfaced_count, 1);
+        store.set_pending(pending);
+        assert_eq!(store.get_momentum(&memory_id).unwrap().surfaced_count, 2);
+
         // Take should remove it
         let taken = store.take_pending(user_id);
         assert!(taken.is_some());
@@ -1937,4 +2321,71 @@ mod tests {
             _ => panic!("Expected EntityFlow trigger"),
         }
     }
+
+    #[test]
+    fn test_record_tool_outcome() {
+        assert!(is_verification_command("cargo test --workspace"));
+        assert!(is_verification_command("cd web && npm run build"));
+        assert!(!is_verification_command("cargo testify"));
+        assert!(!is_verification_command("ls -la"));
+
+        let mut store = FeedbackStore::new();
+        let memory_id = MemoryId(Uuid::new_v4());
+        store.set_pending(PendingFeedback::new(
+            "user1".to_string(),
+            "fix the failing test".to_string(),
+            Vec::new(),
+            vec![SurfacedMemoryInfo {
+                id: memory_id.clone(),
+                entities: HashSet::new(),
+                content_preview: "Run tests with --test-threads=1".to_string(),
+                score: 0.8,
+                embedding: Vec::new(),
+            }],
+        ));
+
+        let ids = store.record_tool_outcome("user1", "cargo test", true);
+        assert_eq!(ids, vec![memory_id.clone()]);
+        let momentum = store.get_momentum(&memory_id).unwrap();
+        assert!(momentum.ema > 0.0);
+        assert_eq!(momentum.surfaced_count, 1);
+        assert_eq!(momentum.positive_count, 1);
+
+        // Only the first verification run after surfacing counts
+        assert!(store
+            .record_tool_outcome("user1", "cargo test", false)
+            .is_empty());
+        assert!(store
+            .record_tool_outcome("other-user", "cargo test", true)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_record_next_action() {
+        let memory_id = MemoryId(Uuid::new_v4());
+        let pending = PendingFeedback::new(
+            "user1".to_string(),
+            "rename the config field".to_string(),
+            Vec::new(),
+            vec![SurfacedMemoryInfo {
+                id: memory_id.clone(),
+                entities: HashSet::new(),
+                content_preview: "Config lives in settings.toml".to_string(),
+                score: 0.8,
+                embedding: Vec::new(),
+            }],
+        );
+
+        let mut store = FeedbackStore::new();
+        store.set_pending(pending.clone());
+        assert!(store.record_next_action("user1", "Read", false).is_empty());
+        // A later failure is not the next action anymore
+        assert!(store.record_next_action("user1", "Edit", true).is_empty());
+
+        let mut store = FeedbackStore::new();
+        store.set_pending(pending);
+        let ids = store.record_next_action("user1", "Read", true);
+        assert_eq!(ids, vec![memory_id.clone()]);
+        assert!(store.get_momentum(&memory_id).unwrap().ema < 0.0);
+    }
 }
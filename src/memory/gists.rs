@@ -0,0 +1,274 @@
+//! Semantic Gists of Aged Episodes
+//!
+//! Raw episodic memories (chat turns, commands, file edits) stay useful for a
+//! few weeks. After that they mostly compete with each other for retrieval
+//! slots. Like systems consolidation in the brain, where the hippocampus hands
+//! the gist of an experience to the neocortex, aged episodes are folded into
+//! one compact summary memory, for example "In October 2026 (auth): migrated
+//! auth to JWT. Key decisions: ...". The raw episodes are kept but demoted:
+//! their importance drops and they point at their gist.
+//!
+//! An episodic cluster is the memories of one episode, else of one run, else
+//! of one calendar month sharing a leading entity.
+//!
+//! Reference: Winocur & Moscovitch (2011) "Memory transformation and systems
+//! consolidation"
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Duration, Utc};
+
+use crate::constants::{GIST_MAX_HIGHLIGHTS, GIST_MIN_AGE_DAYS, GIST_MIN_CLUSTER_SIZE};
+use crate::memory::types::{Experience, ExperienceType, MemoryTier, SharedMemory};
+
+/// Tag on gist memories
+pub const GIST_TAG: &str = "gist";
+
+/// Metadata key on a demoted episode naming the gist that summarizes it
+pub const GIST_ID_KEY: &str = "gist_id";
+
+/// Characters kept from each summarized memory
+const PREVIEW_CHARS: usize = 120;
+
+/// Aged episodic memories to fold into one gist
+#[derive(Debug, Clone)]
+pub struct EpisodeCluster {
+    /// What the cluster is about (most common entity, else its episode/run)
+    pub label: String,
+    /// Episodes, oldest first
+    pub episodes: Vec<SharedMemory>,
+}
+
+/// Whether a memory is a raw episode old enough to be summarized
+fn is_aged_episode(memory: &SharedMemory, now: DateTime<Utc>) -> bool {
+    matches!(memory.tier, MemoryTier::Working | MemoryTier::Session)
+        && !memory.is_forgotten()
+        && memory.experience.experience_type != ExperienceType::Intention
+        && now - memory.created_at >= Duration::days(GIST_MIN_AGE_DAYS)
+        && !memory.experience.metadata.contains_key(GIST_ID_KEY)
+        && !memory
+            .experience
+            .tags
+            .iter()
+            .any(|t| t == GIST_TAG || t == "run-summary")
+}
+
+/// Cluster key: episode, else run, else month + leading entity
+fn cluster_key(memory: &SharedMemory) -> Option<String> {
+    if let Some(episode) = memory
+        .experience
+        .context
+        .as_ref()
+        .and_then(|c| c.episode.episode_id.as_deref())
+    {
+        return Some(format!("episode:{episode}"));
+    }
+    if let Some(run) = memory.run_id.as_deref() {
+        return Some(format!("run:{run}"));
+    }
+    let entity = memory.experience.entities.first()?;
+    Some(format!(
+        "month:{}-{:02}:{}",
+        memory.created_at.year(),
+        memory.created_at.month(),
+        entity.to_lowercase()
+    ))
+}
+
+/// Clusters of aged episodes with at least `GIST_MIN_CLUSTER_SIZE` members
+pub fn find_aged_clusters(memories: &[SharedMemory], now: DateTime<Utc>) -> Vec<EpisodeCluster> {
+    let mut groups: HashMap<String, Vec<SharedMemory>> = HashMap::new();
+    for memory in memories.iter().filter(|m| is_aged_episode(m, now)) {
+        if let Some(key) = cluster_key(memory) {
+            groups.entry(key).or_default().push(memory.clone());
+        }
+    }
+
+    let mut clusters: Vec<EpisodeCluster> = groups
+        .into_iter()
+        .filter(|(_, episodes)| episodes.len() >= GIST_MIN_CLUSTER_SIZE)
+        .map(|(key, mut episodes)| {
+            episodes.sort_by_key(|m| m.created_at);
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for entity in episodes.iter().flat_map(|m| &m.experience.entities) {
+                *counts.entry(entity.to_lowercase()).or_default() += 1;
+            }
+            let label = counts
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+                .map(|(entity, _)| entity)
+                .unwrap_or_else(|| key.split_once(':').map_or(key.clone(), |(_, k)| k.into()));
+            EpisodeCluster { label, episodes }
+        })
+        .collect();
+    clusters.sort_by_key(|c| c.episodes[0].created_at);
+    clusters
+}
+
+fn preview(memory: &SharedMemory) -> String {
+    let first_line = memory
+        .experience
+        .content
+        .lines()
+        .next()
+        .unwrap_or("")
+        .trim();
+    let mut text: String = first_line.chars().take(PREVIEW_CHARS).collect();
+    if first_line.chars().count() > PREVIEW_CHARS {
+        text.push_str("...");
+    }
+    text
+}
+
+/// "October 2026", "October-November 2026" or "December 2025-January 2026"
+fn month_span(first: DateTime<Utc>, last: DateTime<Utc>) -> String {
+    let (a, b) = (first.format("%B %Y"), last.format("%B %Y"));
+    if first.year() != last.year() {
+        format!("{a}-{b}")
+    } else if first.month() != last.month() {
+        format!("{}-{}", first.format("%B"), b)
+    } else {
+        a.to_string()
+    }
+}
+
+/// Summary memory for a cluster (extractive: first/last episode, decisions, errors)
+pub fn compose_gist(cluster: &EpisodeCluster) -> Experience {
+    let episodes = &cluster.episodes;
+    let first = &episodes[0];
+    let last = &episodes[episodes.len() - 1];
+
+    let mut decisions = Vec::new();
+    let mut problems = Vec::new();
+    for memory in episodes {
+        let list = match memory.experience.experience_type {
+            ExperienceType::Decision => &mut decisions,
+            ExperienceType::Error => &mut problems,
+            _ => continue,
+        };
+        let text = preview(memory);
+        if list.len() < GIST_MAX_HIGHLIGHTS && !list.contains(&text) {
+            list.push(text);
+        }
+    }
+
+    let mut content = format!(
+        "In {} ({}): {}",
+        month_span(first.created_at, last.created_at),
+        cluster.label,
+        preview(first)
+    );
+    if !decisions.is_empty() {
+        content.push_str(&format!(". Key decisions: {}", decisions.join("; ")));
+    }
+    if !problems.is_empty() {
+        content.push_str(&format!(". Problems: {}", problems.join("; ")));
+    }
+    if episodes.len() > 1 {
+        content.push_str(&format!(". Last: {}", preview(last)));
+    }
+    content.push_str(&format!(" [{} episodes]", episodes.len()));
+
+    let mut entities: Vec<String> = Vec::new();
+    for entity in episodes.iter().flat_map(|m| &m.experience.entities) {
+        if !entities.iter().any(|e| e.eq_ignore_ascii_case(entity)) {
+            entities.push(entity.clone());
+        }
+    }
+
+    let mut metadata = HashMap::new();
+    metadata.insert("gist_of".to_string(), episodes.len().to_string());
+    metadata.insert(
+        "gist_span".to_string(),
+        format!(
+            "{}..{}",
+            first.created_at.to_rfc3339(),
+            last.created_at.to_rfc3339()
+        ),
+    );
+
+    Experience {
+        content,
+        experience_type: ExperienceType::Context,
+        entities,
+        tags: vec![GIST_TAG.to_string()],
+        related_memories: episodes.iter().map(|m| m.id.clone()).collect(),
+        metadata,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::types::{Memory, MemoryId};
+    use chrono::TimeZone;
+    use std::sync::Arc;
+
+    fn episode(content: &str, kind: ExperienceType, at: DateTime<Utc>) -> SharedMemory {
+        let experience = Experience {
+            content: content.to_string(),
+            experience_type: kind,
+            entities: vec!["JWT".to_string()],
+            ..Default::default()
+        };
+        let mut memory = Memory::new(
+            MemoryId(uuid::Uuid::new_v4()),
+            experience,
+            0.3,
+            None,
+            None,
+            None,
+            Some(at),
+        );
+        memory.tier = MemoryTier::Session;
+        Arc::new(memory)
+    }
+
+    #[test]
+    fn test_aged_cluster_becomes_gist() {
+        let day = |d| Utc.with_ymd_and_hms(2026, 10, d, 12, 0, 0).unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 12, 20, 0, 0, 0).unwrap();
+        let memories = vec![
+            episode(
+                "Started migrating auth to JWT",
+                ExperienceType::Task,
+                day(2),
+            ),
+            episode("Use RS256 signed tokens", ExperienceType::Decision, day(3)),
+            episode(
+                "Token refresh failed in tests",
+                ExperienceType::Error,
+                day(4),
+            ),
+            episode("JWT migration merged", ExperienceType::Task, day(6)),
+            // Too recent to summarize
+            episode("Rotated JWT keys", ExperienceType::Task, now),
+        ];
+
+        let clusters = find_aged_clusters(&memories, now);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].episodes.len(), 4);
+        assert_eq!(clusters[0].label, "jwt");
+
+        let gist = compose_gist(&clusters[0]);
+        assert!(gist
+            .content
+            .starts_with("In October 2026 (jwt): Started migrating"));
+        assert!(gist
+            .content
+            .contains("Key decisions: Use RS256 signed tokens"));
+        assert!(gist.content.contains("Problems: Token refresh failed"));
+        assert_eq!(gist.related_memories.len(), 4);
+    }
+
+    #[test]
+    fn test_month_span() {
+        let oct = Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap();
+        let nov = Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap();
+        let jan = Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(month_span(oct, oct), "October 2026");
+        assert_eq!(month_span(oct, nov), "October-November 2026");
+        assert_eq!(month_span(nov, jan), "November 2026-January 2027");
+    }
+}
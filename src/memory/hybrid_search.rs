@@ -25,11 +25,10 @@ use tantivy::collector::TopDocs;
 use tantivy::query::QueryParser;
 use tantivy::schema::{Field, Schema, Value, STORED, STRING, TEXT};
 use tantivy::{Index, IndexReader, IndexWriter, TantivyDocument};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use super::types::MemoryId;
-use crate::embeddings::minilm::MiniLMEmbedder;
-use crate::embeddings::Embedder;
+use crate::embeddings::EmbeddingProvider;
 
 /// Configuration for hybrid search
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -116,6 +115,38 @@ impl Default for HybridSearchConfig {
     }
 }
 
+impl HybridSearchConfig {
+    /// Defaults with fusion weights overridden by SHODH_HYBRID_BM25_WEIGHT,
+    /// SHODH_HYBRID_VECTOR_WEIGHT and SHODH_HYBRID_RRF_K
+    pub fn from_env() -> Self {
+        Self::default().with_overrides(|name| std::env::var(name).ok())
+    }
+
+    /// Apply fusion overrides from `lookup`, ignoring values out of range
+    fn with_overrides(mut self, lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let parse = |name: &str| -> Option<f32> {
+            let value = lookup(name)?;
+            match value.trim().parse::<f32>() {
+                Ok(v) if v.is_finite() && v >= 0.0 => Some(v),
+                _ => {
+                    tracing::warn!("Ignoring invalid {}={}", name, value);
+                    None
+                }
+            }
+        };
+        if let Some(w) = parse("SHODH_HYBRID_BM25_WEIGHT") {
+            self.bm25_weight = w.min(1.0);
+        }
+        if let Some(w) = parse("SHODH_HYBRID_VECTOR_WEIGHT") {
+            self.vector_weight = w.min(1.0);
+        }
+        if let Some(k) = parse("SHODH_HYBRID_RRF_K").filter(|&k| k > 0.0) {
+            self.rrf_k = k;
+        }
+        self
+    }
+}
+
 /// Result from hybrid search with component scores
 #[derive(Debug, Clone)]
 pub struct HybridSearchResult {
@@ -150,6 +181,14 @@ pub struct HybridSearchResult {
     pub graph_rank: Option<usize>,
 }
 
+/// Whether an index keeps a stored copy of memory content (older schema)
+fn stores_content(index: &Index) -> bool {
+    let schema = index.schema();
+    schema
+        .get_field("content")
+        .is_ok_and(|field| schema.get_field_entry(field).is_stored())
+}
+
 /// BM25 Index using Tantivy
 pub struct BM25Index {
     index: Index,
@@ -169,8 +208,9 @@ impl BM25Index {
         // Memory ID (stored, not tokenized)
         let id_field = schema_builder.add_text_field("id", STRING | STORED);
 
-        // Main content (tokenized for BM25)
-        let content_field = schema_builder.add_text_field("content", TEXT | STORED);
+        // Main content (tokenized for BM25, not stored: only ids are read back,
+        // and a stored copy would keep content in plaintext at rest)
+        let content_field = schema_builder.add_text_field("content", TEXT);
 
         // Tags (tokenized)
         let tags_field = schema_builder.add_text_field("tags", TEXT);
@@ -186,7 +226,22 @@ impl BM25Index {
             .context("Failed to open tantivy directory")?;
 
         let index = if Index::exists(&dir)? {
-            Index::open(dir).context("Failed to open existing BM25 index")?
+            let existing = Index::open(dir).context("Failed to open existing BM25 index")?;
+            if stores_content(&existing) {
+                // Indexes from before content stopped being stored keep a
+                // plaintext copy of every memory. Start over; the memory system
+                // backfills an empty index from its records on startup.
+                warn!(
+                    "BM25 index at {:?} stores memory content, rebuilding it",
+                    path
+                );
+                drop(existing);
+                std::fs::remove_dir_all(path).context("Failed to remove old BM25 index")?;
+                std::fs::create_dir_all(path)?;
+                Index::create_in_dir(path, schema.clone()).context("Failed to create BM25 index")?
+            } else {
+                existing
+            }
         } else {
             Index::create_in_dir(path, schema.clone()).context("Failed to create BM25 index")?
         };
@@ -369,8 +424,40 @@ impl BM25Index {
             }
         };
 
+        self.collect_top_docs(&searcher, &*parsed_query, limit)
+    }
+
+    /// Search with the user's query passed through tantivy's query syntax
+    ///
+    /// Unlike [`Self::search`], operators are kept: `"exact phrase"`,
+    /// `AND`/`OR`, `+required -excluded`, and field scoping with
+    /// `content:`, `tags:` or `entities:`. Terms are ANDed by default, so
+    /// exact identifiers (function names, error codes) narrow the results.
+    /// Returns a [`tantivy::query::QueryParserError`] for malformed queries.
+    pub fn search_query(&self, query: &str, limit: usize) -> Result<Vec<(MemoryId, f32)>> {
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let searcher = self.reader.searcher();
+        let mut query_parser = QueryParser::for_index(
+            &self.index,
+            vec![self.content_field, self.tags_field, self.entities_field],
+        );
+        query_parser.set_conjunction_by_default();
+        let parsed_query = query_parser.parse_query(query)?;
+
+        self.collect_top_docs(&searcher, &*parsed_query, limit)
+    }
+
+    fn collect_top_docs(
+        &self,
+        searcher: &tantivy::Searcher,
+        query: &dyn tantivy::query::Query,
+        limit: usize,
+    ) -> Result<Vec<(MemoryId, f32)>> {
         let top_docs = searcher
-            .search(&parsed_query, &TopDocs::with_limit(limit))
+            .search(query, &TopDocs::with_limit(limit))
             .context("BM25 search failed")?;
 
         let mut results = Vec::with_capacity(top_docs.len());
@@ -484,12 +571,12 @@ impl RRFusion {
 ///
 /// Future: Replace with actual cross-encoder model for better accuracy.
 pub struct CrossEncoderReranker {
-    embedder: Arc<MiniLMEmbedder>,
+    embedder: Arc<dyn EmbeddingProvider>,
 }
 
 impl CrossEncoderReranker {
     /// Create reranker with shared embedder
-    pub fn new(embedder: Arc<MiniLMEmbedder>) -> Self {
+    pub fn new(embedder: Arc<dyn EmbeddingProvider>) -> Self {
         Self { embedder }
     }
 
@@ -561,7 +648,7 @@ impl HybridSearchEngine {
     /// Create hybrid search engine
     pub fn new(
         bm25_path: &Path,
-        embedder: Arc<MiniLMEmbedder>,
+        embedder: Arc<dyn EmbeddingProvider>,
         config: HybridSearchConfig,
     ) -> Result<Self> {
         let bm25_index = BM25Index::new(bm25_path)?;
@@ -1045,6 +1132,22 @@ mod tests {
         assert_eq!(config.min_graph_score, 0.01); // Graph score threshold (SHO-D4)
     }
 
+    #[test]
+    fn test_hybrid_config_overrides() {
+        let env: HashMap<&str, &str> = [
+            ("SHODH_HYBRID_BM25_WEIGHT", "0.6"),
+            ("SHODH_HYBRID_VECTOR_WEIGHT", "-1"),
+            ("SHODH_HYBRID_RRF_K", "60"),
+        ]
+        .into_iter()
+        .collect();
+        let config = HybridSearchConfig::default()
+            .with_overrides(|name| env.get(name).map(|v| v.to_string()));
+        assert_eq!(config.bm25_weight, 0.6);
+        assert_eq!(config.vector_weight, 0.40); // Negative weight ignored
+        assert_eq!(config.rrf_k, 60.0);
+    }
+
     #[test]
     fn test_bm25_index_and_search() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -1109,6 +1212,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bm25_query_syntax() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = BM25Index::new(temp_dir.path()).unwrap();
+
+        let id1 = MemoryId(uuid::Uuid::new_v4());
+        let id2 = MemoryId(uuid::Uuid::new_v4());
+        index
+            .upsert(
+                &id1,
+                "parse_config returned error E0432 after the refactor",
+                &["build".to_string()],
+                &[],
+            )
+            .unwrap();
+        index
+            .upsert(
+                &id2,
+                "The refactor of the config parser is done",
+                &["deploy".to_string()],
+                &[],
+            )
+            .unwrap();
+        index.commit().unwrap();
+        index.reload().unwrap();
+
+        let ids = |query: &str| -> Vec<MemoryId> {
+            index
+                .search_query(query, 10)
+                .unwrap()
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect()
+        };
+
+        assert_eq!(ids("E0432"), vec![id1.clone()]);
+        assert_eq!(ids("\"config parser\""), vec![id2.clone()]);
+        assert_eq!(ids("refactor -E0432"), vec![id2.clone()]);
+        assert_eq!(ids("tags:build"), vec![id1.clone()]);
+        assert!(ids("content:deploy").is_empty());
+        assert!(index.search_query("content:(unclosed", 10).is_err());
+    }
+
+    #[test]
+    fn test_bm25_rebuilds_index_with_stored_content() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        {
+            let mut schema_builder = Schema::builder();
+            let id_field = schema_builder.add_text_field("id", STRING | STORED);
+            let content_field = schema_builder.add_text_field("content", TEXT | STORED);
+            schema_builder.add_text_field("tags", TEXT);
+            schema_builder.add_text_field("entities", TEXT);
+            let index = Index::create_in_dir(temp_dir.path(), schema_builder.build()).unwrap();
+            let mut writer: IndexWriter = index.writer(15_000_000).unwrap();
+            let mut doc = TantivyDocument::default();
+            doc.add_text(id_field, uuid::Uuid::new_v4().to_string());
+            doc.add_text(content_field, "database password is hunter2");
+            writer.add_document(doc).unwrap();
+            writer.commit().unwrap();
+        }
+
+        let index = BM25Index::new(temp_dir.path()).unwrap();
+        assert!(!stores_content(&index.index));
+        assert_eq!(index.len(), 0);
+    }
+
     #[test]
     fn test_bm25_keyword_vs_semantic_gap() {
         // This test demonstrates why BM25 is needed alongside vector search
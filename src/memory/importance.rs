@@ -0,0 +1,221 @@
+//! Importance Scoring and Memory Tiers
+//!
+//! Stored importance is assigned at encoding (type, content, context) and
+//! nudged by feedback. The importance *score* adds the evidence that piles up
+//! afterwards:
+//!
+//! ```text
+//! score = importance
+//!       + IMPORTANCE_REINFORCEMENT_WEIGHT × sat(access_count)
+//!       + IMPORTANCE_CITATION_WEIGHT      × sat(cited_count)
+//!       + IMPORTANCE_EXPLICIT_BONUS       (stored on purpose, not auto-captured)
+//!       + IMPORTANCE_VALENCE_WEIGHT       × |valence|
+//!
+//! sat(n) = min(1, ln(1 + n) / ln(1 + IMPORTANCE_SIGNAL_SATURATION))
+//! ```
+//!
+//! The score decides which store a memory belongs to. The stores map onto the
+//! existing tiers: working = `Working`, episodic = `Session`, semantic =
+//! `LongTerm` (and the compressed `Archive`). Each store has its own retrieval
+//! weight and forgetting-curve time scale.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::constants::{
+    IMPORTANCE_CITATION_WEIGHT, IMPORTANCE_EXPLICIT_BONUS, IMPORTANCE_REINFORCEMENT_WEIGHT,
+    IMPORTANCE_SIGNAL_SATURATION, IMPORTANCE_VALENCE_WEIGHT, MEMORY_TIER_FORGET_SCALE_ARCHIVE,
+    MEMORY_TIER_FORGET_SCALE_LONGTERM, MEMORY_TIER_FORGET_SCALE_SESSION,
+    MEMORY_TIER_FORGET_SCALE_WORKING, MEMORY_TIER_RECALL_WEIGHT_LONGTERM,
+    MEMORY_TIER_RECALL_WEIGHT_SESSION, MEMORY_TIER_RECALL_WEIGHT_WORKING,
+    TIER_PROMOTION_SESSION_AGE_SECS, TIER_PROMOTION_SESSION_IMPORTANCE,
+    TIER_PROMOTION_WORKING_AGE_SECS, TIER_PROMOTION_WORKING_IMPORTANCE,
+};
+use crate::memory::types::{Memory, MemoryTier};
+
+/// Tags of memories the system captured on its own (not explicitly remembered)
+const AUTO_CAPTURE_TAGS: &[&str] = &["auto-captured", "claude-code-hook", "run-summary", "gist"];
+
+/// Evidence of a memory's importance gathered after encoding
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImportanceSignals {
+    /// Times the memory was retrieved
+    pub reinforcement: u32,
+    /// Times a response was built on the memory
+    pub citations: u32,
+    /// Stored deliberately (/remember) rather than auto-captured
+    pub explicit: bool,
+    /// Emotional valence at encoding (-1.0 to 1.0)
+    pub valence: f32,
+}
+
+impl ImportanceSignals {
+    /// Signals of `memory`; `citations` come from the feedback store
+    pub fn of(memory: &Memory, citations: u32) -> Self {
+        Self {
+            reinforcement: memory.access_count(),
+            citations,
+            explicit: !memory
+                .experience
+                .tags
+                .iter()
+                .any(|t| AUTO_CAPTURE_TAGS.contains(&t.as_str())),
+            valence: memory
+                .experience
+                .context
+                .as_ref()
+                .map(|c| c.emotional.valence)
+                .unwrap_or(0.0),
+        }
+    }
+}
+
+fn saturate(count: u32) -> f32 {
+    ((1.0 + count as f32).ln() / (1.0 + IMPORTANCE_SIGNAL_SATURATION as f32).ln()).min(1.0)
+}
+
+/// Importance score in 0.0-1.0 from stored importance plus later evidence
+pub fn importance_score(importance: f32, signals: &ImportanceSignals) -> f32 {
+    let mut score = importance
+        + IMPORTANCE_REINFORCEMENT_WEIGHT * saturate(signals.reinforcement)
+        + IMPORTANCE_CITATION_WEIGHT * saturate(signals.citations)
+        + IMPORTANCE_VALENCE_WEIGHT * signals.valence.abs().min(1.0);
+    if signals.explicit {
+        score += IMPORTANCE_EXPLICIT_BONUS;
+    }
+    score.clamp(0.0, 1.0)
+}
+
+/// Tier a memory should be in at `now`
+///
+/// Uses the same rules as the in-memory promotions: episodic once older than
+/// TIER_PROMOTION_WORKING_AGE_SECS with a score of at least
+/// TIER_PROMOTION_WORKING_IMPORTANCE, semantic once older than
+/// TIER_PROMOTION_SESSION_AGE_SECS with at least
+/// TIER_PROMOTION_SESSION_IMPORTANCE. `threshold` adjusts those minimums for
+/// the memory (the graph-adjusted threshold). Memories only move up, and
+/// archived memories stay archived.
+pub fn target_tier(
+    memory: &Memory,
+    score: f32,
+    threshold: impl Fn(f32) -> f32,
+    now: DateTime<Utc>,
+) -> MemoryTier {
+    let age = now - memory.created_at;
+    let earned = if age >= Duration::seconds(TIER_PROMOTION_SESSION_AGE_SECS)
+        && score >= threshold(TIER_PROMOTION_SESSION_IMPORTANCE)
+    {
+        MemoryTier::LongTerm
+    } else if age >= Duration::seconds(TIER_PROMOTION_WORKING_AGE_SECS)
+        && score >= threshold(TIER_PROMOTION_WORKING_IMPORTANCE)
+    {
+        MemoryTier::Session
+    } else {
+        MemoryTier::Working
+    };
+    if tier_rank(earned) > tier_rank(memory.tier) {
+        earned
+    } else {
+        memory.tier
+    }
+}
+
+fn tier_rank(tier: MemoryTier) -> u8 {
+    match tier {
+        MemoryTier::Working => 0,
+        MemoryTier::Session => 1,
+        MemoryTier::LongTerm => 2,
+        MemoryTier::Archive => 3,
+    }
+}
+
+/// Retrieval score multiplier for a tier
+pub fn tier_recall_weight(tier: MemoryTier) -> f32 {
+    match tier {
+        MemoryTier::Working => MEMORY_TIER_RECALL_WEIGHT_WORKING,
+        MemoryTier::Session => MEMORY_TIER_RECALL_WEIGHT_SESSION,
+        MemoryTier::LongTerm | MemoryTier::Archive => MEMORY_TIER_RECALL_WEIGHT_LONGTERM,
+    }
+}
+
+/// Forgetting-curve time scale for a tier (> 1.0 forgets faster)
+pub fn tier_forget_scale(tier: MemoryTier) -> f64 {
+    match tier {
+        MemoryTier::Working => MEMORY_TIER_FORGET_SCALE_WORKING,
+        MemoryTier::Session => MEMORY_TIER_FORGET_SCALE_SESSION,
+        MemoryTier::LongTerm => MEMORY_TIER_FORGET_SCALE_LONGTERM,
+        MemoryTier::Archive => MEMORY_TIER_FORGET_SCALE_ARCHIVE,
+    }
+}
+
+/// Tiers named by a store preference: working, episodic, semantic
+/// (the tier names working, session, longterm and archive are accepted too)
+pub fn parse_tier_preference(name: &str) -> Option<&'static [MemoryTier]> {
+    match name.to_lowercase().as_str() {
+        "working" => Some(&[MemoryTier::Working]),
+        "episodic" | "session" => Some(&[MemoryTier::Session]),
+        "semantic" => Some(&[MemoryTier::LongTerm, MemoryTier::Archive]),
+        "longterm" | "long_term" => Some(&[MemoryTier::LongTerm]),
+        "archive" => Some(&[MemoryTier::Archive]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::types::{Experience, MemoryId};
+
+    fn memory(age_hours: i64, tags: &[&str]) -> Memory {
+        let experience = Experience {
+            content: "tiering test".to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        };
+        Memory::new(
+            MemoryId(uuid::Uuid::new_v4()),
+            experience,
+            0.4,
+            None,
+            None,
+            None,
+            Some(Utc::now() - Duration::hours(age_hours)),
+        )
+    }
+
+    #[test]
+    fn test_importance_score_signals() {
+        let base = ImportanceSignals::default();
+        assert_eq!(importance_score(0.4, &base), 0.4);
+
+        let cited = ImportanceSignals {
+            citations: IMPORTANCE_SIGNAL_SATURATION * 5,
+            ..base
+        };
+        let score = importance_score(0.4, &cited);
+        assert!((score - (0.4 + IMPORTANCE_CITATION_WEIGHT)).abs() < 1e-6);
+
+        let explicit = ImportanceSignals::of(&memory(0, &[]), 0);
+        let captured = ImportanceSignals::of(&memory(0, &["auto-captured"]), 0);
+        assert!(importance_score(0.4, &explicit) > importance_score(0.4, &captured));
+        assert_eq!(importance_score(0.95, &explicit), 1.0);
+    }
+
+    #[test]
+    fn test_target_tier_only_promotes() {
+        let now = Utc::now();
+        let tier = |memory: &Memory, score: f32| target_tier(memory, score, |t| t, now);
+        assert_eq!(tier(&memory(0, &[]), 0.9), MemoryTier::Working);
+        assert_eq!(tier(&memory(2, &[]), 0.9), MemoryTier::Session);
+        assert_eq!(tier(&memory(48, &[]), 0.2), MemoryTier::Working);
+        assert_eq!(tier(&memory(48, &[]), 0.4), MemoryTier::Session);
+        assert_eq!(tier(&memory(48, &[]), 0.9), MemoryTier::LongTerm);
+
+        // Same graph-adjusted minimums as the in-memory promotions
+        let stricter = target_tier(&memory(48, &[]), 0.4, |t| t + 0.1, now);
+        assert_eq!(stricter, MemoryTier::Working);
+
+        let mut archived = memory(48, &[]);
+        archived.tier = MemoryTier::Archive;
+        assert_eq!(tier(&archived, 0.0), MemoryTier::Archive);
+    }
+}
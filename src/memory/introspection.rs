@@ -288,6 +288,41 @@ pub enum ConsolidationEvent {
         orphaned_entities: usize,
         timestamp: DateTime<Utc>,
     },
+
+    /// Near-duplicate memories folded into a canonical memory
+    MemoriesMerged {
+        canonical_id: String,
+        merged_ids: Vec<String>,
+        content_preview: String,
+        min_similarity: f32,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Aged episodes summarized into a semantic gist memory
+    GistCreated {
+        gist_id: String,
+        episode_ids: Vec<String>,
+        label: String,
+        content_preview: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// A newer memory contradicts an older important one
+    ContradictionDetected {
+        stale_memory_id: String,
+        current_memory_id: String,
+        kind: String,
+        content_preview: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Lowest importance × recency memories evicted to keep a store under quota
+    MemoriesEvicted {
+        memory_ids: Vec<String>,
+        max_memories: Option<usize>,
+        max_bytes: Option<usize>,
+        timestamp: DateTime<Utc>,
+    },
 }
 
 /// Types of memory interference (SHO-106)
@@ -898,6 +933,10 @@ impl ConsolidationEventBuffer {
                 ConsolidationEvent::GraphOrphanDetected { .. } => {}
                 ConsolidationEvent::GraphAdjustedPromotion { .. } => {}
                 ConsolidationEvent::GraphDecayConsolidated { .. } => {}
+                ConsolidationEvent::MemoriesMerged { .. } => {}
+                ConsolidationEvent::GistCreated { .. } => {}
+                ConsolidationEvent::ContradictionDetected { .. } => {}
+                ConsolidationEvent::MemoriesEvicted { .. } => {}
             }
         }
 
@@ -1232,6 +1271,10 @@ impl ConsolidationEventBuffer {
                 ConsolidationEvent::GraphOrphanDetected { .. } => {}
                 ConsolidationEvent::GraphAdjustedPromotion { .. } => {}
                 ConsolidationEvent::GraphDecayConsolidated { .. } => {}
+                ConsolidationEvent::MemoriesMerged { .. } => {}
+                ConsolidationEvent::GistCreated { .. } => {}
+                ConsolidationEvent::ContradictionDetected { .. } => {}
+                ConsolidationEvent::MemoriesEvicted { .. } => {}
             }
         }
 
@@ -1275,6 +1318,10 @@ impl ConsolidationEvent {
             ConsolidationEvent::GraphOrphanDetected { timestamp, .. } => *timestamp,
             ConsolidationEvent::GraphAdjustedPromotion { timestamp, .. } => *timestamp,
             ConsolidationEvent::GraphDecayConsolidated { timestamp, .. } => *timestamp,
+            ConsolidationEvent::MemoriesMerged { timestamp, .. } => *timestamp,
+            ConsolidationEvent::GistCreated { timestamp, .. } => *timestamp,
+            ConsolidationEvent::ContradictionDetected { timestamp, .. } => *timestamp,
+            ConsolidationEvent::MemoriesEvicted { timestamp, .. } => *timestamp,
         }
     }
 
@@ -1323,6 +1370,10 @@ impl ConsolidationEvent {
                 | ConsolidationEvent::GraphOrphanDetected { .. }
                 | ConsolidationEvent::GraphAdjustedPromotion { .. }
                 | ConsolidationEvent::GraphDecayConsolidated { .. }
+                | ConsolidationEvent::MemoriesMerged { .. }
+                | ConsolidationEvent::GistCreated { .. }
+                | ConsolidationEvent::ContradictionDetected { .. }
+                | ConsolidationEvent::MemoriesEvicted { .. }
         )
     }
 }
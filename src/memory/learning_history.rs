@@ -346,6 +346,34 @@ impl LearningHistoryStore {
                 None,
                 None,
             ),
+            ConsolidationEvent::MemoriesMerged { canonical_id, .. } => (
+                LearningEventType::MaintenanceCycleCompleted,
+                Some(canonical_id.clone()),
+                None,
+                None,
+            ),
+            ConsolidationEvent::GistCreated { gist_id, .. } => (
+                LearningEventType::MaintenanceCycleCompleted,
+                Some(gist_id.clone()),
+                None,
+                None,
+            ),
+            ConsolidationEvent::ContradictionDetected {
+                stale_memory_id,
+                current_memory_id,
+                ..
+            } => (
+                LearningEventType::InterferenceDetected,
+                Some(current_memory_id.clone()),
+                Some(stale_memory_id.clone()),
+                None,
+            ),
+            ConsolidationEvent::MemoriesEvicted { .. } => (
+                LearningEventType::MaintenanceCycleCompleted,
+                None,
+                None,
+                None,
+            ),
         }
     }
 
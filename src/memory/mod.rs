@@ -6,28 +6,46 @@
 //! - Multi-modal retrieval (similarity, temporal, causal)
 //! - Automatic memory consolidation
 
+pub mod backend;
+pub mod blob_compression;
 pub mod compression;
 pub mod context;
+pub mod contradictions;
+pub mod dedup;
+pub mod encoding_filters;
+pub mod encryption;
 pub mod facts;
 pub mod feedback;
 pub mod files;
+pub mod gists;
 pub mod graph_retrieval;
 pub mod hybrid_search;
+pub mod importance;
 pub mod injection;
 pub mod introspection;
 pub mod learning_history;
 pub mod lineage;
 pub mod pattern_detection;
+#[cfg(feature = "postgres")]
+pub mod pg_storage;
 pub mod prospective;
 pub mod query_parser;
+pub mod quota;
+pub mod ranking;
+pub mod reembed;
 pub mod replay;
+pub mod retention;
 pub mod retrieval;
+pub mod secrets;
 pub mod segmentation;
 pub mod sessions;
+pub mod spaced_repetition;
 pub mod storage;
+pub mod suppression;
 pub mod temporal_facts;
 pub mod todo_formatter;
 pub mod todos;
+pub mod type_registry;
 pub mod types;
 pub mod visualization;
 
@@ -47,18 +65,21 @@ use crate::metrics::{
 };
 
 use crate::constants::{
-    DEFAULT_COMPRESSION_AGE_DAYS, DEFAULT_IMPORTANCE_THRESHOLD, DEFAULT_MAX_HEAP_PER_USER_MB,
-    DEFAULT_SESSION_MEMORY_SIZE_MB, DEFAULT_WORKING_MEMORY_SIZE, EDGE_SEMANTIC_WEIGHT_FLOOR,
-    ESTIMATED_BYTES_PER_MEMORY, HEBBIAN_BOOST_HELPFUL, HEBBIAN_DECAY_MISLEADING,
-    POTENTIATION_ACCESS_THRESHOLD, POTENTIATION_MAINTENANCE_BOOST, TIER_PROMOTION_SESSION_AGE_SECS,
-    TIER_PROMOTION_SESSION_IMPORTANCE, TIER_PROMOTION_WORKING_AGE_SECS,
-    TIER_PROMOTION_WORKING_IMPORTANCE,
+    CONTRADICTED_RECALL_PENALTY, CONTRADICTION_CANDIDATES, DEFAULT_COMPRESSION_AGE_DAYS,
+    DEFAULT_IMPORTANCE_THRESHOLD, DEFAULT_MAX_HEAP_PER_USER_MB, DEFAULT_SESSION_MEMORY_SIZE_MB,
+    DEFAULT_WORKING_MEMORY_SIZE, DUPLICATE_REINFORCEMENT_STRENGTH, EDGE_SEMANTIC_WEIGHT_FLOOR,
+    ESTIMATED_BYTES_PER_MEMORY, GIST_EPISODE_IMPORTANCE_FACTOR, HEBBIAN_BOOST_HELPFUL,
+    HEBBIAN_DECAY_MISLEADING, MAX_REINFORCEMENT_STRENGTH, MEMORY_DECAY_PERSIST_DELTA,
+    POTENTIATION_ACCESS_THRESHOLD, POTENTIATION_MAINTENANCE_BOOST, REVIEW_QUEUE_CACHE_SIZE,
+    TIER_PROMOTION_SESSION_AGE_SECS, TIER_PROMOTION_SESSION_IMPORTANCE,
+    TIER_PROMOTION_WORKING_AGE_SECS, TIER_PROMOTION_WORKING_IMPORTANCE,
 };
 
-use crate::memory::storage::{MemoryStorage, SearchCriteria};
+use crate::memory::backend::Storage;
+use crate::memory::storage::SearchCriteria;
 pub use crate::memory::types::*;
 // pub use crate::memory::vector_storage::{VectorIndexedMemoryStorage, StorageStats};  // Disabled
-use crate::embeddings::Embedder;
+use crate::embeddings::{EmbeddingInfo, EmbeddingProvider, ProviderConfig};
 use crate::memory::compression::CompressionPipeline;
 pub use crate::memory::compression::{
     ConsolidationResult, FactType, SemanticConsolidator, SemanticFact,
@@ -66,10 +87,10 @@ pub use crate::memory::compression::{
 pub use crate::memory::facts::{FactQueryResponse, FactStats, SemanticFactStore};
 pub use crate::memory::feedback::{
     apply_context_pattern_signals, calculate_entity_flow, calculate_entity_overlap,
-    detect_negative_keywords, extract_entities_simple, process_implicit_feedback,
-    process_implicit_feedback_with_semantics, signal_from_entity_flow, ContextFingerprint,
-    FeedbackMomentum, FeedbackStore, FeedbackStoreStats, PendingFeedback, PreviousContext,
-    SignalRecord, SignalTrigger, SurfacedMemoryInfo, Trend,
+    detect_negative_keywords, extract_entities_simple, is_verification_command,
+    process_implicit_feedback, process_implicit_feedback_with_semantics, signal_from_entity_flow,
+    ContextFingerprint, FeedbackMomentum, FeedbackStore, FeedbackStoreStats, PendingFeedback,
+    PreviousContext, SignalRecord, SignalTrigger, SurfacedMemoryInfo, Trend,
 };
 pub use crate::memory::files::{FileMemoryStats, FileMemoryStore, IndexingResult};
 pub use crate::memory::graph_retrieval::{
@@ -158,7 +179,11 @@ pub struct MemorySystem {
     /// Three-tier memory hierarchy
     working_memory: Arc<RwLock<WorkingMemory>>,
     session_memory: Arc<RwLock<SessionMemory>>,
-    long_term_memory: Arc<MemoryStorage>,
+    long_term_memory: Arc<dyn Storage>,
+
+    /// Node-local RocksDB for facts, lineage, learning history and temporal
+    /// facts (the same database as `long_term_memory` on the embedded backend)
+    local_db: Arc<rocksdb::DB>,
 
     /// Compression pipeline
     compressor: CompressionPipeline,
@@ -167,7 +192,10 @@ pub struct MemorySystem {
     retriever: RetrievalEngine,
 
     /// Embedder for semantic search
-    embedder: Arc<crate::embeddings::minilm::MiniLMEmbedder>,
+    embedder: Arc<dyn EmbeddingProvider>,
+
+    /// Provider this store is pinned to (see `embeddings::provider`)
+    embedding_provider: ProviderConfig,
 
     /// Query embedding cache - SHA256(query_text) → embedding
     /// Uses SHA256 for stable hashing across restarts (unlike DefaultHasher)
@@ -246,6 +274,22 @@ pub struct MemorySystem {
     /// re-process the entire memory store. Initialized from the latest fact's
     /// created_at or 0 if no facts exist.
     fact_extraction_watermark: std::sync::atomic::AtomicI64,
+
+    /// Memories due for spaced-repetition review, most urgent first.
+    /// Rebuilt on heavy maintenance cycles; drained as proactive_context resurfaces them.
+    review_queue: RwLock<Vec<MemoryId>>,
+
+    /// Per-user limits on memory count / stored bytes (unlimited by default)
+    quota: quota::QuotaConfig,
+
+    /// Weights of recall's unified score (the owning tenant's, see `crate::ranking`)
+    ranking: ranking::RankingWeights,
+
+    /// Memories the user asked never to surface again (see `crate::suppression`)
+    suppressions: suppression::SuppressionList,
+
+    /// Merge near-duplicates in heavy maintenance cycles (SHODH_MERGE_DUPLICATES)
+    merge_duplicates: bool,
 }
 
 /// Resolve an entity name to a graph label and salience using pre-extracted NER data.
@@ -282,18 +326,18 @@ impl MemorySystem {
     /// Create a new memory system
     pub fn new(config: MemoryConfig) -> Result<Self> {
         let storage_path = config.storage_path.clone();
-        let storage = Arc::new(
-            MemoryStorage::new(&storage_path)
-                .with_context(|| format!("Failed to open storage at {:?}", storage_path))?,
-        );
+        let backend::OpenedStorage {
+            memories: storage,
+            local_db,
+        } = backend::open(&storage_path)?;
 
         // CRITICAL: Initialize embedder ONCE and share between MemorySystem and RetrievalEngine
         // This prevents loading the ONNX model multiple times (50-200ms overhead per load)
-        let embedding_config = crate::embeddings::minilm::EmbeddingConfig::default();
-        let embedder = Arc::new(
-            crate::embeddings::minilm::MiniLMEmbedder::new(embedding_config)
-                .context("Failed to initialize MiniLM embedder (ONNX model)")?,
-        );
+        // The store keeps the provider its vectors came from until re-embedded
+        let embedding_provider =
+            ProviderConfig::for_store(&storage_path, &ProviderConfig::from_env())?;
+        let embedder = crate::embeddings::provider::from_config(&embedding_provider)
+            .context("Failed to initialize embedding provider")?;
 
         // Create consolidation event buffer first so we can share it with retriever
         let consolidation_events = Arc::new(RwLock::new(ConsolidationEventBuffer::new()));
@@ -416,15 +460,16 @@ impl MemorySystem {
 
         // SHO-f0e7: Create semantic fact store using the same DB as long-term memory
         // Facts use "facts:" prefix to avoid key collisions with episodic memories
-        let fact_store = Arc::new(facts::SemanticFactStore::new(storage.db()));
+        let fact_store = Arc::new(facts::SemanticFactStore::new(local_db.clone()));
 
         // SHO-118: Create lineage graph for causal memory tracking
         // Lineage uses "lineage:" prefix for edges and branches
-        let lineage_graph = Arc::new(lineage::LineageGraph::new(storage.db()));
+        let lineage_graph = Arc::new(lineage::LineageGraph::new(local_db.clone()));
 
         // Initialize hybrid search engine (BM25 + Vector + RRF + Reranking)
+        // Fusion weights can be tuned with SHODH_HYBRID_* env vars
         let bm25_path = storage_path.join("bm25_index");
-        let hybrid_search_config = hybrid_search::HybridSearchConfig::default();
+        let hybrid_search_config = hybrid_search::HybridSearchConfig::from_env();
         let hybrid_search_engine = hybrid_search::HybridSearchEngine::new(
             &bm25_path,
             embedder.clone(),
@@ -465,11 +510,14 @@ impl MemorySystem {
 
         // Initialize learning history store for persistent significant events
         // Uses the same DB as long-term memory with "learning:" prefix
-        let learning_history = Arc::new(learning_history::LearningHistoryStore::new(storage.db()));
+        let learning_history = Arc::new(learning_history::LearningHistoryStore::new(
+            local_db.clone(),
+        ));
 
         // Initialize temporal fact store for multi-hop temporal reasoning
         // Uses the same DB with "temporal_facts:", "temporal_by_entity:", "temporal_by_event:" prefixes
-        let temporal_fact_store = Arc::new(temporal_facts::TemporalFactStore::new(storage.db()));
+        let temporal_fact_store =
+            Arc::new(temporal_facts::TemporalFactStore::new(local_db.clone()));
 
         // SHO-106: Load persisted interference history from RocksDB
         let interference_detector = {
@@ -497,9 +545,11 @@ impl MemorySystem {
                 config.session_memory_size_mb,
             ))),
             long_term_memory: storage,
+            local_db,
             compressor: CompressionPipeline::new(),
             retriever,
             embedder,
+            embedding_provider,
             // LRU embedding caches: max 2,000 entries each (~3MB for 384-dim embeddings)
             query_cache: moka::sync::Cache::builder().max_capacity(2_000).build(),
             content_cache: moka::sync::Cache::builder().max_capacity(2_000).build(),
@@ -531,6 +581,11 @@ impl MemorySystem {
             // Watermark for incremental fact extraction — initialized to 0 (sentinel).
             // On first maintenance call, loaded from RocksDB or derived from latest fact timestamp.
             fact_extraction_watermark: std::sync::atomic::AtomicI64::new(0),
+            review_queue: RwLock::new(Vec::new()),
+            quota: quota::QuotaConfig::default(),
+            ranking: ranking::RankingWeights::default(),
+            suppressions: suppression::SuppressionList::default(),
+            merge_duplicates: false,
         })
     }
 
@@ -567,6 +622,42 @@ impl MemorySystem {
         self.feedback_store = Some(feedback);
     }
 
+    /// Whether heavy maintenance cycles merge near-duplicates (off by default;
+    /// POST /api/consolidate/duplicates works either way)
+    pub fn set_merge_duplicates(&mut self, enabled: bool) {
+        self.merge_duplicates = enabled;
+    }
+
+    /// Set the per-user quota enforced on writes and maintenance cycles
+    pub fn set_quota(&mut self, quota: quota::QuotaConfig) {
+        self.quota = quota;
+    }
+
+    /// Get the configured quota
+    pub fn quota(&self) -> &quota::QuotaConfig {
+        &self.quota
+    }
+
+    /// Set the weights of recall's unified score
+    pub fn set_ranking(&mut self, ranking: ranking::RankingWeights) {
+        self.ranking = ranking;
+    }
+
+    /// Get the ranking weights in effect
+    pub fn ranking(&self) -> ranking::RankingWeights {
+        self.ranking
+    }
+
+    /// Set the rules keeping memories out of retrieval
+    pub fn set_suppressions(&mut self, suppressions: suppression::SuppressionList) {
+        self.suppressions = suppressions;
+    }
+
+    /// Whether a suppression rule keeps `memory` out of retrieval
+    pub fn is_suppressed(&self, memory: &Memory) -> bool {
+        self.suppressions.matches(memory)
+    }
+
     /// Get reference to the optional feedback store
     pub fn feedback_store(&self) -> Option<&Arc<parking_lot::RwLock<FeedbackStore>>> {
         self.feedback_store.as_ref()
@@ -578,13 +669,32 @@ impl MemorySystem {
     /// embedding generation and vector indexing, but skips graph entity extraction
     /// (imported memories already have their entity relationships established).
     pub fn remember_with_id(
+        &self,
+        memory_id: MemoryId,
+        experience: Experience,
+        created_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<MemoryId> {
+        self.remember_imported(memory_id, experience, created_at, None, None, None)
+    }
+
+    /// Store an imported memory, keeping its original id, importance and attribution.
+    ///
+    /// Like [`Self::remember_with_id`], but `importance` overrides the computed
+    /// value when given. The BM25 document is added without committing; call
+    /// [`Self::commit_keyword_index`] once the batch is done.
+    pub fn remember_imported(
         &self,
         memory_id: MemoryId,
         mut experience: Experience,
         created_at: Option<chrono::DateTime<chrono::Utc>>,
+        importance: Option<f32>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
     ) -> Result<MemoryId> {
         self.check_resource_limits()?;
-        let importance = self.calculate_importance(&experience);
+        let importance = importance
+            .map(|i| i.clamp(0.0, 1.0))
+            .unwrap_or_else(|| self.calculate_importance(&experience));
 
         // Generate embedding if not provided
         if experience.embeddings.is_none() {
@@ -595,14 +705,17 @@ impl MemorySystem {
                 self.content_cache.insert(content_hash, embedding.clone());
                 experience.embeddings = Some(embedding);
             }
+            if experience.embeddings.is_some() {
+                self.record_embedding_model(&mut experience.metadata);
+            }
         }
 
         let memory = Arc::new(Memory::new(
             memory_id.clone(),
             experience,
             importance,
-            None,
-            None,
+            agent_id,
+            run_id,
             None,
             created_at,
         ));
@@ -617,10 +730,27 @@ impl MemorySystem {
         if let Err(e) = self.retriever.index_memory(&memory) {
             tracing::warn!("Failed to index imported memory {}: {}", memory.id.0, e);
         }
+        if let Err(e) = self.hybrid_search.index_memory(
+            &memory.id,
+            &memory.experience.content,
+            &memory.experience.tags,
+            &memory.experience.entities,
+        ) {
+            tracing::warn!(
+                "Failed to index imported memory {} in BM25: {}",
+                memory.id.0,
+                e
+            );
+        }
 
         Ok(memory_id)
     }
 
+    /// Commit pending BM25 documents so they become searchable
+    pub fn commit_keyword_index(&self) -> Result<()> {
+        self.hybrid_search.commit_and_reload()
+    }
+
     /// Store a new memory (takes ownership to avoid clones)
     /// Thread-safe: uses interior mutability for all internal state
     /// If `created_at` is None, uses current time (Utc::now())
@@ -631,6 +761,7 @@ impl MemorySystem {
     ) -> Result<MemoryId> {
         // CRITICAL: Check resource limits before recording to prevent OOM
         self.check_resource_limits()?;
+        Self::ensure_provenance(&mut experience);
 
         let memory_id = MemoryId(Uuid::new_v4());
 
@@ -665,6 +796,9 @@ impl MemorySystem {
                     }
                 }
             }
+            if experience.embeddings.is_some() {
+                self.record_embedding_model(&mut experience.metadata);
+            }
         }
 
         // TEMPORAL EXTRACTION: Extract dates from content for temporal filtering
@@ -921,6 +1055,10 @@ impl MemorySystem {
             false
         };
 
+        // Flag older memories this one contradicts (after caching, so the
+        // metadata update reaches the working/session copies too)
+        self.flag_contradictions(&memory);
+
         // Update stats - track all tier counts accurately
         {
             let mut stats = self.stats.write();
@@ -935,6 +1073,8 @@ impl MemorySystem {
             }
         }
 
+        self.enforce_count_quota(&memory_id);
+
         // Trigger background consolidation if needed
         self.consolidate_if_needed()?;
 
@@ -965,6 +1105,7 @@ impl MemorySystem {
     ) -> Result<MemoryId> {
         // CRITICAL: Check resource limits before recording to prevent OOM
         self.check_resource_limits()?;
+        Self::ensure_provenance(&mut experience);
 
         let memory_id = MemoryId(Uuid::new_v4());
 
@@ -985,6 +1126,9 @@ impl MemorySystem {
                     experience.embeddings = Some(embedding);
                 }
             }
+            if experience.embeddings.is_some() {
+                self.record_embedding_model(&mut experience.metadata);
+            }
         }
 
         // TEMPORAL EXTRACTION: Extract dates from content for temporal filtering
@@ -1162,6 +1306,8 @@ impl MemorySystem {
                 .add_shared(Arc::clone(&memory))?;
         }
 
+        self.flag_contradictions(&memory);
+
         // Update stats
         {
             let mut stats = self.stats.write();
@@ -1170,6 +1316,8 @@ impl MemorySystem {
             stats.working_memory_count += 1;
         }
 
+        self.enforce_count_quota(&memory_id);
+
         self.consolidate_if_needed()?;
 
         // Commit and reload BM25 index changes
@@ -1184,6 +1332,98 @@ impl MemorySystem {
         Ok(memory_id)
     }
 
+    /// Consolidate a finished run into a single summary memory
+    ///
+    /// Gathers the run's memories created since its last summary and stores one
+    /// Context memory capturing the goal (first memory), decisions, files
+    /// touched and outcome (last memory), tagged `run-summary` and `run:<id>`
+    /// and carrying the run_id. Returns `None` when fewer than
+    /// `MIN_RUN_SUMMARY_MEMORIES` new memories exist for the run.
+    pub fn summarize_run(&self, run_id: &str) -> Result<Option<MemoryId>> {
+        const MIN_RUN_SUMMARY_MEMORIES: usize = 2;
+        const PREVIEW_CHARS: usize = 160;
+        const MAX_LISTED: usize = 5;
+
+        let preview = |memory: &Memory| -> String {
+            let first_line = memory
+                .experience
+                .content
+                .lines()
+                .next()
+                .unwrap_or("")
+                .trim();
+            let mut text: String = first_line.chars().take(PREVIEW_CHARS).collect();
+            if first_line.chars().count() > PREVIEW_CHARS {
+                text.push_str("...");
+            }
+            text
+        };
+
+        let mut run_memories: Vec<SharedMemory> = self
+            .get_all_memories()?
+            .into_iter()
+            .filter(|m| m.run_id.as_deref() == Some(run_id))
+            .collect();
+        run_memories.sort_by_key(|m| m.created_at);
+
+        let last_summary = run_memories
+            .iter()
+            .filter(|m| m.experience.tags.iter().any(|t| t == "run-summary"))
+            .map(|m| m.created_at)
+            .max();
+        let fragments: Vec<&SharedMemory> = run_memories
+            .iter()
+            .filter(|m| last_summary.is_none_or(|at| m.created_at > at))
+            .collect();
+        if fragments.len() < MIN_RUN_SUMMARY_MEMORIES {
+            return Ok(None);
+        }
+
+        let mut decisions = Vec::new();
+        let mut files = Vec::new();
+        for memory in &fragments {
+            match memory.experience.experience_type {
+                ExperienceType::Decision if decisions.len() < MAX_LISTED => {
+                    decisions.push(preview(memory))
+                }
+                ExperienceType::CodeEdit | ExperienceType::FileAccess => {
+                    let file = preview(memory);
+                    if files.len() < MAX_LISTED && !files.contains(&file) {
+                        files.push(file);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut content = format!(
+            "Run summary ({run_id}): {} memories. Goal: {}",
+            fragments.len(),
+            preview(fragments[0])
+        );
+        if !decisions.is_empty() {
+            content.push_str(&format!(" Decisions: {}.", decisions.join("; ")));
+        }
+        if !files.is_empty() {
+            content.push_str(&format!(" Files touched: {}.", files.join("; ")));
+        }
+        content.push_str(&format!(
+            " Outcome: {}",
+            preview(fragments[fragments.len() - 1])
+        ));
+
+        let agent_id = fragments.iter().rev().find_map(|m| m.agent_id.clone());
+        let experience = Experience {
+            content,
+            experience_type: ExperienceType::Context,
+            tags: vec!["run-summary".to_string(), format!("run:{run_id}")],
+            related_memories: fragments.iter().map(|m| m.id.clone()).collect(),
+            ..Default::default()
+        };
+        let id = self.remember_with_agent(experience, None, agent_id, Some(run_id.to_string()))?;
+        Ok(Some(id))
+    }
+
     /// Search and retrieve relevant memories (zero-copy with Arc<Memory>)
     ///
     /// PRODUCTION IMPLEMENTATION:
@@ -1243,6 +1483,7 @@ impl MemorySystem {
         // Expand with hierarchy context (parent chain + children)
         // Related memories in hierarchy get a decayed score boost
         self.expand_with_hierarchy(&mut memories, &mut seen_ids);
+        memories.retain(|m| !self.is_suppressed(m));
 
         // Rank by importance * temporal relevance
         let now = chrono::Utc::now();
@@ -1332,6 +1573,7 @@ impl MemorySystem {
     pub fn recall_by_tags(&self, tags: &[String], limit: usize) -> Result<Vec<Memory>> {
         let criteria = storage::SearchCriteria::ByTags(tags.to_vec());
         let mut memories = self.advanced_search(criteria)?;
+        memories.retain(|m| !self.is_suppressed(m));
         memories.truncate(limit);
         if let Ok(count) = self.long_term_memory.increment_retrieval_count() {
             self.stats.write().total_retrievals = count;
@@ -1350,6 +1592,7 @@ impl MemorySystem {
     ) -> Result<Vec<Memory>> {
         let criteria = storage::SearchCriteria::ByDate { start, end };
         let mut memories = self.advanced_search(criteria)?;
+        memories.retain(|m| !self.is_suppressed(m));
         memories.truncate(limit);
         if let Ok(count) = self.long_term_memory.increment_retrieval_count() {
             self.stats.write().total_retrievals = count;
@@ -1849,6 +2092,7 @@ impl MemorySystem {
             episode_id: query.episode_id.clone(),
             prospective_signals: query.prospective_signals.clone(),
             recency_weight: query.recency_weight,
+            recency_half_life_hours: query.recency_half_life_hours,
         };
 
         // ===========================================================================
@@ -2162,24 +2406,30 @@ impl MemorySystem {
         // Layer 5: Unified scoring with hebbian + recency + emotional + feedback signals
         // Recency decay: recent memories get boost, old memories decay
         // λ = 0.01 means ~50% at 70 hours, ~25% at 140 hours
+        // A query's recency half-life h replaces it with λ = ln 2 / h
         const RECENCY_DECAY_RATE: f32 = 0.01;
+        let recency_decay_rate = query
+            .recency_half_life_hours
+            .map(|hours| std::f32::consts::LN_2 / hours.max(1.0))
+            .unwrap_or(RECENCY_DECAY_RATE);
         let now = chrono::Utc::now();
 
         // PIPE-9: Get feedback store guard for momentum-based scoring
         // Acquire once outside the loop to avoid repeated locking
         let feedback_guard = self.feedback_store.as_ref().map(|fs| fs.read());
+        let ranking = self.ranking;
 
         for (memory_id, score) in memory_ids {
             // Hebbian boost from learned graph weights (10% contribution)
             let hebbian_boost = hebbian_scores.get(&memory_id).copied().unwrap_or(0.0);
-            let base_score = score + hebbian_boost * 0.1;
+            let base_score = (score + hebbian_boost * 0.1) * ranking.semantic;
 
             // Helper to apply unified scoring (recency + arousal + credibility + temporal)
-            let recency_scale = query.recency_weight.unwrap_or(0.1);
+            let recency_scale = query.recency_weight.unwrap_or(ranking.recency);
             let with_unified_score = |mem: &SharedMemory, base: f32| -> SharedMemory {
                 // Recency decay: exponential decay based on age
                 let hours_old = (now - mem.created_at).num_hours().max(0) as f32;
-                let recency_boost = (-RECENCY_DECAY_RATE * hours_old).exp() * recency_scale;
+                let recency_boost = (-recency_decay_rate * hours_old).exp() * recency_scale;
 
                 // Emotional arousal boost: high arousal = more salient (5% contribution)
                 // Research: LaBar & Cabeza (2006) - emotionally arousing events better remembered
@@ -2235,19 +2485,13 @@ impl MemorySystem {
 
                 // FEEDBACK MOMENTUM (PIPE-9)
                 // Apply momentum from past feedback to consistently boost/suppress memories
-                // - Positive momentum (proven helpful) → boost score
+                // - Positive momentum (proven helpful) → boost score (up to 10%)
                 // - Negative momentum (frequently ignored) → suppress up to 20%
+                // Both scaled by the reinforcement weight
                 // This ensures consistent feedback integration across ALL retrieval paths
                 let feedback_multiplier = if let Some(ref guard) = feedback_guard {
                     if let Some(fm) = guard.get_momentum(&mem.id) {
-                        let momentum = fm.ema_with_decay();
-                        if momentum < 0.0 {
-                            // Suppress: up to 20% penalty for highly negative momentum
-                            1.0 + (momentum * 0.2).max(-0.2)
-                        } else {
-                            // Boost: up to 10% bonus for positive momentum
-                            1.0 + (momentum * 0.1).min(0.1)
-                        }
+                        ranking.momentum_multiplier(fm.ema_with_decay())
                     } else {
                         1.0 // No feedback history
                     }
@@ -2255,9 +2499,33 @@ impl MemorySystem {
                     1.0 // No feedback store configured
                 };
 
-                let final_score =
+                // FORGETTING CURVE: one-off memories nobody revisits lose rank over
+                // time; reinforced and important ones hold it (at most the
+                // importance weight, 30% by default)
+                let retention_multiplier =
+                    ranking.retention_multiplier(mem.retention_strength(now));
+
+                // TIER WEIGHT: consolidated (semantic) memories outrank unvetted working ones
+                let tier_multiplier = ranking.tier_multiplier(mem.tier);
+
+                // CONTRADICTION: a newer memory superseded this one, prefer the newer
+                let contradiction_multiplier = if mem
+                    .experience
+                    .metadata
+                    .contains_key(contradictions::CONTRADICTED_BY_KEY)
+                {
+                    CONTRADICTED_RECALL_PENALTY
+                } else {
+                    1.0
+                };
+
+                let final_score = ranking.calibrated(
                     (base + recency_boost + arousal_boost + credibility_boost + temporal_boost)
-                        * feedback_multiplier;
+                        * feedback_multiplier
+                        * retention_multiplier
+                        * tier_multiplier
+                        * contradiction_multiplier,
+                );
 
                 let mut cloned: Memory = mem.as_ref().clone();
                 cloned.set_score(final_score);
@@ -2267,7 +2535,9 @@ impl MemorySystem {
             // Try working memory first (hot cache)
             if let Some(memory) = self.working_memory.read().get(&memory_id) {
                 // CRITICAL FIX: Apply filters before adding to results
-                if self.retriever.matches_filters(&memory, &vector_query) {
+                if self.retriever.matches_filters(&memory, &vector_query)
+                    && !self.is_suppressed(&memory)
+                {
                     memories.push(with_unified_score(&memory, base_score));
                     if !sources.contains(&"working") {
                         sources.push("working");
@@ -2282,7 +2552,9 @@ impl MemorySystem {
             // Try session memory second (warm cache)
             if let Some(memory) = self.session_memory.read().get(&memory_id) {
                 // CRITICAL FIX: Apply filters before adding to results
-                if self.retriever.matches_filters(&memory, &vector_query) {
+                if self.retriever.matches_filters(&memory, &vector_query)
+                    && !self.is_suppressed(&memory)
+                {
                     memories.push(with_unified_score(&memory, base_score));
                     if !sources.contains(&"session") {
                         sources.push("session");
@@ -2298,7 +2570,9 @@ impl MemorySystem {
             match self.retriever.get_from_storage(&memory_id) {
                 Ok(memory) => {
                     // CRITICAL FIX: Apply filters before adding to results
-                    if self.retriever.matches_filters(&memory, &vector_query) {
+                    if self.retriever.matches_filters(&memory, &vector_query)
+                        && !self.is_suppressed(&memory)
+                    {
                         // Reuse unified scoring (includes feedback_multiplier)
                         let shared = Arc::new(memory);
                         memories.push(with_unified_score(&shared, base_score));
@@ -2903,10 +3177,30 @@ impl MemorySystem {
     }
 
     /// Get reference to embedder for graph-aware retrieval
-    pub fn get_embedder(&self) -> &dyn Embedder {
+    pub fn get_embedder(&self) -> &dyn EmbeddingProvider {
         self.embedder.as_ref()
     }
 
+    /// Shared handle to the embedder, e.g. for batched embedding
+    pub fn embedder(&self) -> Arc<dyn EmbeddingProvider> {
+        self.embedder.clone()
+    }
+
+    /// Model and dimension new embeddings are generated with
+    pub fn embedding_info(&self) -> EmbeddingInfo {
+        self.embedder.info()
+    }
+
+    /// Provider this store is pinned to
+    pub fn embedding_provider(&self) -> &ProviderConfig {
+        &self.embedding_provider
+    }
+
+    /// Record the model that generated a memory's embedding on its metadata
+    fn record_embedding_model(&self, metadata: &mut std::collections::HashMap<String, String>) {
+        self.embedder.info().write_to(metadata);
+    }
+
     /// Compute embedding for arbitrary text (for external use like prospective memory)
     pub fn compute_embedding(&self, text: &str) -> Result<Vec<f32>> {
         self.embedder.encode(text)
@@ -3281,9 +3575,23 @@ impl MemorySystem {
         Ok(())
     }
 
+    /// Importance score of a memory: stored importance plus reinforcement,
+    /// citations, explicitness and valence (see [`importance::importance_score`])
+    pub fn importance_score(&self, memory: &Memory) -> f32 {
+        let citations = self
+            .feedback_store
+            .as_ref()
+            .and_then(|fs| fs.read().get_momentum(&memory.id).map(|m| m.cited_count))
+            .unwrap_or(0);
+        importance::importance_score(
+            memory.importance(),
+            &importance::ImportanceSignals::of(memory, citations),
+        )
+    }
+
     /// Move memories from working to session memory (Cowan's model)
     ///
-    /// Promotion criteria: importance >= TIER_PROMOTION_WORKING_IMPORTANCE
+    /// Promotion criteria: importance score >= TIER_PROMOTION_WORKING_IMPORTANCE
     /// AND age >= TIER_PROMOTION_WORKING_AGE_SECS
     fn promote_working_to_session(&self) -> Result<()> {
         let now = chrono::Utc::now();
@@ -3297,7 +3605,7 @@ impl MemorySystem {
                 .into_iter()
                 .filter(|m| {
                     let age = now - m.created_at;
-                    let importance = m.importance();
+                    let importance = self.importance_score(m);
                     let threshold =
                         self.graph_adjusted_threshold(m, TIER_PROMOTION_WORKING_IMPORTANCE);
                     importance >= threshold && age >= min_age
@@ -3343,7 +3651,7 @@ impl MemorySystem {
 
     /// Move memories from session to long-term storage (Cowan's model)
     ///
-    /// Promotion criteria: importance >= TIER_PROMOTION_SESSION_IMPORTANCE
+    /// Promotion criteria: importance score >= TIER_PROMOTION_SESSION_IMPORTANCE
     /// AND age >= TIER_PROMOTION_SESSION_AGE_SECS
     fn promote_session_to_longterm(&self) -> Result<()> {
         let now = chrono::Utc::now();
@@ -3357,7 +3665,7 @@ impl MemorySystem {
                 .into_iter()
                 .filter(|m| {
                     let age = now - m.created_at;
-                    let importance = m.importance();
+                    let importance = self.importance_score(m);
                     let threshold =
                         self.graph_adjusted_threshold(m, TIER_PROMOTION_SESSION_IMPORTANCE);
                     importance >= threshold && age >= min_age
@@ -4172,7 +4480,7 @@ impl MemorySystem {
 
         // Step 6: Clear semantic facts (GDPR — knowledge derived from memories)
         {
-            let db = self.long_term_memory.db();
+            let db = self.local_db.clone();
             let mut batch = rocksdb::WriteBatch::default();
             let mut facts_deleted = 0usize;
             for prefix in &[
@@ -4267,7 +4575,7 @@ impl MemorySystem {
     /// This provides direct access to the database. Use with caution.
     /// Primarily intended for backup/restore operations.
     pub fn get_db(&self) -> std::sync::Arc<rocksdb::DB> {
-        self.long_term_memory.db()
+        self.local_db.clone()
     }
 
     /// Advanced search using storage criteria
@@ -4280,6 +4588,613 @@ impl MemorySystem {
         self.long_term_memory.get(id)
     }
 
+    /// Important memories due for spaced-repetition review, computed fresh
+    ///
+    /// Full scan; see [`spaced_repetition::build_review_queue`].
+    pub fn review_queue(&self, limit: usize) -> Result<Vec<spaced_repetition::ReviewItem>> {
+        let memories = self.get_all_memories()?;
+        Ok(spaced_repetition::build_review_queue(
+            &memories,
+            chrono::Utc::now(),
+            limit,
+        ))
+    }
+
+    /// Up to `limit` due reviews from the cached queue (no storage scan),
+    /// skipping `exclude`
+    ///
+    /// The queue is filled by heavy maintenance cycles, so it is empty until
+    /// the first one has run.
+    pub fn due_reviews(&self, limit: usize, exclude: &HashSet<MemoryId>) -> Vec<Memory> {
+        let queue = self.review_queue.read();
+        queue
+            .iter()
+            .filter(|id| !exclude.contains(*id))
+            .filter_map(|id| self.long_term_memory.get(id).ok())
+            .filter(|m| !self.is_suppressed(m))
+            .take(limit)
+            .collect()
+    }
+
+    /// Record a review: counts as an access, which expands the next interval
+    ///
+    /// Ids that don't name a stored, unforgotten memory are skipped. Returns
+    /// how many memories were reviewed.
+    pub fn mark_reviewed(&self, ids: &[MemoryId]) -> Result<usize> {
+        let mut found: Vec<MemoryId> = Vec::with_capacity(ids.len());
+        for id in ids {
+            let exists = self
+                .long_term_memory
+                .get(id)
+                .is_ok_and(|m| !m.is_forgotten());
+            if exists && !found.contains(id) {
+                found.push(id.clone());
+            }
+        }
+        self.review_queue.write().retain(|id| !ids.contains(id));
+        if !found.is_empty() {
+            self.reinforce_recall(&found, RetrievalOutcome::Neutral)?;
+        }
+        Ok(found.len())
+    }
+
+    /// Full-text search with tantivy query syntax (phrases, boolean operators,
+    /// `content:`/`tags:`/`entities:` scoping), returning memories with their
+    /// BM25 scores. See [`hybrid_search::BM25Index::search_query`].
+    pub fn keyword_search(&self, query: &str, limit: usize) -> Result<Vec<(Memory, f32)>> {
+        let hits = self.hybrid_search.bm25_index().search_query(query, limit)?;
+        Ok(hits
+            .into_iter()
+            .filter_map(|(id, score)| self.long_term_memory.get(&id).ok().map(|m| (m, score)))
+            .filter(|(m, _)| !self.is_suppressed(m))
+            .collect())
+    }
+
+    /// Tags in use with the number of memories carrying each, most used first
+    pub fn tag_counts(&self) -> Result<Vec<(String, usize)>> {
+        let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        for memory in self.get_all_memories()? {
+            for tag in &memory.experience.tags {
+                *counts.entry(tag.clone()).or_default() += 1;
+            }
+        }
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(counts)
+    }
+
+    /// Replace tags across all memories
+    ///
+    /// Every tag in `from` (case-insensitive) is removed from tags and
+    /// entities and, when `to` is given, replaced by it. This covers
+    /// renaming (one source), merging (several) and deleting (`to = None`).
+    /// Returns the ids of the memories that changed.
+    pub fn replace_tags(&self, from: &[String], to: Option<&str>) -> Result<Vec<MemoryId>> {
+        let from: HashSet<String> = from.iter().map(|t| t.to_lowercase()).collect();
+        let matches = |t: &String| from.contains(&t.to_lowercase());
+
+        let mut updated = Vec::new();
+        for memory in self.get_all_memories()? {
+            if !memory.experience.tags.iter().any(matches)
+                && !memory.experience.entities.iter().any(matches)
+            {
+                continue;
+            }
+            let mut memory = (*memory).clone();
+            for list in [&mut memory.experience.tags, &mut memory.experience.entities] {
+                let had = list.iter().any(matches);
+                list.retain(|t| !matches(t));
+                if let Some(to) = to.filter(|_| had) {
+                    if !list.iter().any(|t| t == to) {
+                        list.push(to.to_string());
+                    }
+                }
+            }
+            self.update_memory(&memory)?;
+            updated.push(memory.id);
+        }
+        Ok(updated)
+    }
+
+    /// Find near-duplicate clusters and, unless `dry_run`, merge each into its
+    /// canonical memory
+    ///
+    /// Full scan; see [`dedup::find_duplicate_clusters`] for the similarity rules.
+    pub fn consolidate_duplicates(&self, dry_run: bool) -> Result<Vec<dedup::DuplicateCluster>> {
+        let clusters = dedup::find_duplicate_clusters(&self.get_all_memories()?);
+        if !dry_run {
+            self.merge_duplicate_clusters(&clusters)?;
+        }
+        Ok(clusters)
+    }
+
+    /// Fold each cluster's duplicates into its canonical memory
+    ///
+    /// The canonical memory gains the union of tags and entities and the
+    /// duplicates' reinforcement history and feedback momentum. Provenance is
+    /// kept in its `merged_from` metadata, a `Merged` history revision, and a
+    /// `MemoriesMerged` consolidation event. Duplicates are then moved to the
+    /// trash, so a bad merge can be undone. Returns how many were trashed.
+    pub fn merge_duplicate_clusters(&self, clusters: &[dedup::DuplicateCluster]) -> Result<usize> {
+        let now = chrono::Utc::now();
+        let mut removed = 0;
+        for cluster in clusters {
+            let mut canonical = (*cluster.canonical).clone();
+            let merged_ids: Vec<String> = cluster
+                .duplicates
+                .iter()
+                .map(|d| d.id.0.to_string())
+                .collect();
+
+            for duplicate in &cluster.duplicates {
+                canonical.absorb_reinforcement(duplicate);
+                for (list, extra) in [
+                    (&mut canonical.experience.tags, &duplicate.experience.tags),
+                    (
+                        &mut canonical.experience.entities,
+                        &duplicate.experience.entities,
+                    ),
+                ] {
+                    for item in extra {
+                        if !list.iter().any(|t| t.eq_ignore_ascii_case(item)) {
+                            list.push(item.clone());
+                        }
+                    }
+                }
+            }
+
+            let mut provenance: Vec<String> = canonical
+                .experience
+                .metadata
+                .get("merged_from")
+                .map(|v| v.split(',').map(str::to_string).collect())
+                .unwrap_or_default();
+            provenance.extend(merged_ids.iter().cloned());
+            canonical
+                .experience
+                .metadata
+                .insert("merged_from".to_string(), provenance.join(","));
+            canonical.record_revision(
+                ChangeType::Merged,
+                Some("consolidation".to_string()),
+                Some(format!(
+                    "merged {} near-duplicate(s): {}",
+                    merged_ids.len(),
+                    merged_ids.join(", ")
+                )),
+            );
+            self.update_memory(&canonical)?;
+
+            if let Some(feedback) = &self.feedback_store {
+                let mut feedback = feedback.write();
+                for duplicate in &cluster.duplicates {
+                    feedback.merge_momentum(
+                        &canonical.id,
+                        canonical.experience.experience_type.clone(),
+                        &duplicate.id,
+                    );
+                }
+            }
+
+            for duplicate in &cluster.duplicates {
+                if self.trash(&duplicate.id)? {
+                    removed += 1;
+                }
+            }
+
+            self.record_consolidation_event(ConsolidationEvent::MemoriesMerged {
+                canonical_id: canonical.id.0.to_string(),
+                merged_ids,
+                content_preview: canonical.experience.content.chars().take(50).collect(),
+                min_similarity: cluster.min_similarity,
+                timestamp: now,
+            });
+        }
+        if removed > 0 {
+            if let Err(e) = self.hybrid_search.commit_and_reload() {
+                tracing::warn!(error = %e, "Failed to commit BM25 after merging duplicates");
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Delete memories past their type's retention period; with `dry_run`
+    /// only report them
+    ///
+    /// Full scan; see [`retention::find_expired`] for the rules.
+    pub fn apply_retention(
+        &self,
+        policy: &retention::RetentionPolicy,
+        dry_run: bool,
+    ) -> Result<Vec<retention::ExpiredMemory>> {
+        if policy.is_empty() {
+            return Ok(Vec::new());
+        }
+        let expired =
+            retention::find_expired(&self.get_all_memories()?, policy, chrono::Utc::now());
+        if !dry_run {
+            for item in &expired {
+                self.forget(ForgetCriteria::ById(item.memory.id.clone()))?;
+            }
+        }
+        Ok(expired)
+    }
+
+    /// Move a memory to the trash: flag it forgotten and drop it from the
+    /// vector and BM25 indices and the working/session tiers, so retrieval no
+    /// longer sees it. Its graph episode is kept, so [`Self::restore`] brings
+    /// it back whole. Returns false if it is already deleted.
+    pub fn trash(&self, memory_id: &MemoryId) -> Result<bool> {
+        let mut memory = self.long_term_memory.get(memory_id)?;
+        if memory.is_forgotten() {
+            return Ok(false);
+        }
+        let now = chrono::Utc::now().to_rfc3339();
+        let metadata = &mut memory.experience.metadata;
+        metadata.insert("forgotten".to_string(), "true".to_string());
+        metadata.insert("forgotten_at".to_string(), now.clone());
+        metadata.insert(TRASHED_AT_KEY.to_string(), now);
+        self.long_term_memory.update(&memory)?;
+
+        let was_in_working = self.working_memory.write().remove(memory_id).is_ok();
+        let was_in_session = self.session_memory.write().remove(memory_id).is_ok();
+        let was_indexed = self.retriever.remove_memory(memory_id);
+        if let Err(e) = self.hybrid_search.remove_memory(memory_id) {
+            tracing::warn!(
+                memory_id = %memory_id.0,
+                error = %e,
+                "Failed to clean BM25 index for trashed memory"
+            );
+        }
+
+        let mut stats = self.stats.write();
+        stats.total_memories = stats.total_memories.saturating_sub(1);
+        stats.long_term_memory_count = stats.long_term_memory_count.saturating_sub(1);
+        if was_in_working {
+            stats.working_memory_count = stats.working_memory_count.saturating_sub(1);
+        }
+        if was_in_session {
+            stats.session_memory_count = stats.session_memory_count.saturating_sub(1);
+        }
+        if was_indexed {
+            stats.vector_index_count = stats.vector_index_count.saturating_sub(1);
+        }
+        Ok(true)
+    }
+
+    /// Move every memory matched by `criteria` to the trash; returns how many
+    ///
+    /// The trash counterpart of [`Self::forget`]: same criteria, but matches
+    /// stay restorable until purged.
+    pub fn trash_matching(&self, criteria: &ForgetCriteria) -> Result<usize> {
+        let pattern = match criteria {
+            ForgetCriteria::Pattern(pattern) => Some(
+                crate::validation::validate_and_compile_pattern(pattern)
+                    .map_err(|e| anyhow::anyhow!("Invalid pattern: {e}"))?,
+            ),
+            _ => None,
+        };
+        let now = chrono::Utc::now();
+        let matches = |m: &Memory| match criteria {
+            ForgetCriteria::ById(id) => &m.id == id,
+            ForgetCriteria::OlderThan(days) => {
+                m.created_at < now - chrono::Duration::days(*days as i64)
+            }
+            ForgetCriteria::LowImportance(threshold) => m.importance() < *threshold,
+            ForgetCriteria::Pattern(_) => pattern
+                .as_ref()
+                .is_some_and(|re| re.is_match(&m.experience.content)),
+            ForgetCriteria::ByTags(tags) => m.experience.tags.iter().any(|t| tags.contains(t)),
+            ForgetCriteria::ByDateRange { start, end } => {
+                m.created_at >= *start && m.created_at <= *end
+            }
+            ForgetCriteria::ByType(exp_type) => m.experience.experience_type == *exp_type,
+            ForgetCriteria::All => true,
+        };
+
+        let ids: Vec<MemoryId> = self
+            .long_term_memory
+            .get_all()?
+            .into_iter()
+            .filter(|m| !m.is_forgotten() && matches(m))
+            .map(|m| m.id)
+            .collect();
+        let mut trashed = 0;
+        for id in &ids {
+            if self.trash(id)? {
+                trashed += 1;
+            }
+        }
+        if trashed > 0 {
+            if let Err(e) = self.hybrid_search.commit_and_reload() {
+                tracing::warn!(error = %e, "Failed to commit BM25 after trashing");
+            }
+        }
+        Ok(trashed)
+    }
+
+    /// Bring back a trashed (or otherwise forgotten) memory and re-index it.
+    /// Returns `None` if it isn't deleted.
+    pub fn restore(&self, memory_id: &MemoryId) -> Result<Option<Memory>> {
+        let mut memory = self.long_term_memory.get(memory_id)?;
+        if !memory.is_forgotten() {
+            return Ok(None);
+        }
+        let metadata = &mut memory.experience.metadata;
+        metadata.remove("forgotten");
+        metadata.remove("forgotten_at");
+        metadata.remove(TRASHED_AT_KEY);
+        self.update_memory(&memory)?;
+
+        let mut stats = self.stats.write();
+        stats.total_memories += 1;
+        stats.long_term_memory_count += 1;
+        if memory.experience.embeddings.is_some() {
+            stats.vector_index_count += 1;
+        }
+        Ok(Some(memory))
+    }
+
+    /// Memories in the trash, most recently deleted first
+    pub fn trashed_memories(&self) -> Result<Vec<Memory>> {
+        let mut trashed: Vec<Memory> = self
+            .long_term_memory
+            .get_all()?
+            .into_iter()
+            .filter(|m| m.trashed_at().is_some())
+            .collect();
+        trashed.sort_by_key(|m| std::cmp::Reverse(m.trashed_at()));
+        Ok(trashed)
+    }
+
+    /// Permanently delete memories trashed before `cutoff`; returns their IDs
+    ///
+    /// Trashing already took them out of the indices, tiers and stats, so
+    /// only storage, graph episodes and interference records are left.
+    pub fn purge_trash(&self, cutoff: chrono::DateTime<chrono::Utc>) -> Result<Vec<MemoryId>> {
+        let mut purged = Vec::new();
+        for memory in self.trashed_memories()? {
+            if memory.trashed_at().is_some_and(|t| t < cutoff) {
+                self.long_term_memory.delete(&memory.id)?;
+                purged.push(memory.id);
+            }
+        }
+        self.cleanup_graph_for_ids(&purged);
+        self.cleanup_interference_for_ids(&purged);
+        Ok(purged)
+    }
+
+    /// Usage of this store against its quota
+    ///
+    /// Full scan (stored bytes and pinned memories).
+    pub fn quota_status(&self) -> Result<quota::QuotaStatus> {
+        let stats = self.long_term_memory.get_stats()?;
+        let pinned = self
+            .get_all_memories()?
+            .iter()
+            .filter(|m| m.is_pinned() && !m.is_forgotten())
+            .count();
+        Ok(quota::QuotaStatus::new(
+            &self.quota,
+            stats.total_count,
+            stats.total_size_bytes,
+            pinned,
+        ))
+    }
+
+    /// Evict the lowest importance × recency memories until the store is back
+    /// under quota. Pinned memories and `protected` are never evicted.
+    ///
+    /// Full scan; see [`quota::select_evictions`] for the ordering.
+    pub fn enforce_quota(&self, protected: Option<&MemoryId>) -> Result<Vec<quota::Eviction>> {
+        if self.quota.is_unlimited() {
+            return Ok(Vec::new());
+        }
+        let storage_bytes = if self.quota.max_bytes.is_some() {
+            self.long_term_memory.get_stats()?.total_size_bytes
+        } else {
+            0
+        };
+        let evictions = quota::select_evictions(
+            &self.get_all_memories()?,
+            storage_bytes,
+            &self.quota,
+            protected,
+            chrono::Utc::now(),
+        );
+        if evictions.is_empty() {
+            return Ok(evictions);
+        }
+        for eviction in &evictions {
+            self.forget(ForgetCriteria::ById(eviction.memory.id.clone()))?;
+        }
+        self.record_consolidation_event(ConsolidationEvent::MemoriesEvicted {
+            memory_ids: evictions
+                .iter()
+                .map(|e| e.memory.id.0.to_string())
+                .collect(),
+            max_memories: self.quota.max_memories,
+            max_bytes: self.quota.max_bytes,
+            timestamp: chrono::Utc::now(),
+        });
+        tracing::info!(
+            evicted = evictions.len(),
+            max_memories = ?self.quota.max_memories,
+            max_bytes = ?self.quota.max_bytes,
+            "Evicted memories over quota"
+        );
+        Ok(evictions)
+    }
+
+    /// Write-path quota check: the memory count is tracked in stats, so only
+    /// scan when a new memory pushed the store over its count limit
+    fn enforce_count_quota(&self, just_stored: &MemoryId) {
+        if !self.quota.count_exceeded(self.stats.read().total_memories) {
+            return;
+        }
+        if let Err(e) = self.enforce_quota(Some(just_stored)) {
+            tracing::warn!("Failed to enforce memory quota: {}", e);
+        }
+    }
+
+    /// Find aged episodic clusters and, unless `dry_run`, summarize each into
+    /// a semantic gist memory
+    ///
+    /// Full scan; see [`gists::find_aged_clusters`] for which episodes qualify.
+    /// Returns the clusters with their gist ids (`None` on dry runs).
+    pub fn consolidate_gists(
+        &self,
+        dry_run: bool,
+    ) -> Result<Vec<(gists::EpisodeCluster, Option<MemoryId>)>> {
+        let clusters = gists::find_aged_clusters(&self.get_all_memories()?, chrono::Utc::now());
+        if dry_run {
+            return Ok(clusters.into_iter().map(|c| (c, None)).collect());
+        }
+        let ids = self.write_gists(&clusters)?;
+        Ok(clusters
+            .into_iter()
+            .zip(ids.into_iter().map(Some))
+            .collect())
+    }
+
+    /// Store a gist memory for each cluster and demote its episodes
+    ///
+    /// Episodes keep their content; their importance is scaled by
+    /// `GIST_EPISODE_IMPORTANCE_FACTOR` and their `gist_id` metadata points at
+    /// the gist, which also lists them in `related_memories`. A `GistCreated`
+    /// consolidation event records each gist.
+    pub fn write_gists(&self, clusters: &[gists::EpisodeCluster]) -> Result<Vec<MemoryId>> {
+        let now = chrono::Utc::now();
+        let mut gist_ids = Vec::with_capacity(clusters.len());
+        for cluster in clusters {
+            let mut experience = gists::compose_gist(cluster);
+            Provenance::new(ProvenanceSource::Consolidation).write_to(&mut experience.metadata);
+            let content_preview: String = experience.content.chars().take(50).collect();
+            let agent_id = cluster
+                .episodes
+                .iter()
+                .rev()
+                .find_map(|m| m.agent_id.clone());
+            let gist_id = self.remember_with_agent(experience, None, agent_id, None)?;
+
+            for episode in &cluster.episodes {
+                let mut demoted = (**episode).clone();
+                demoted.set_importance(episode.importance() * GIST_EPISODE_IMPORTANCE_FACTOR);
+                demoted
+                    .experience
+                    .metadata
+                    .insert(gists::GIST_ID_KEY.to_string(), gist_id.0.to_string());
+                demoted.record_revision(
+                    ChangeType::ImportanceAdjusted,
+                    Some("consolidation".to_string()),
+                    Some(format!("summarized into gist {}", gist_id.0)),
+                );
+                self.update_memory(&demoted)?;
+            }
+
+            self.record_consolidation_event(ConsolidationEvent::GistCreated {
+                gist_id: gist_id.0.to_string(),
+                episode_ids: cluster
+                    .episodes
+                    .iter()
+                    .map(|m| m.id.0.to_string())
+                    .collect(),
+                label: cluster.label.clone(),
+                content_preview,
+                timestamp: now,
+            });
+            gist_ids.push(gist_id);
+        }
+        Ok(gist_ids)
+    }
+
+    /// Mark memories stored without provenance as manual
+    ///
+    /// Entry points that know better (hooks, integrations, consolidation, ...)
+    /// write their own `Provenance` before calling `remember`/`upsert`.
+    fn ensure_provenance(experience: &mut Experience) {
+        if !experience.metadata.contains_key(PROVENANCE_SOURCE_KEY) {
+            Provenance::default().write_to(&mut experience.metadata);
+        }
+    }
+
+    /// Flag older important memories that a newly stored memory contradicts
+    ///
+    /// Checks the nearest `CONTRADICTION_CANDIDATES` neighbours by embedding.
+    /// Each stale memory gets `contradicted_by` metadata pointing at the new
+    /// one, the new memory lists them under `contradicts`, and a
+    /// `ContradictionDetected` consolidation event is recorded per pair.
+    fn flag_contradictions(&self, memory: &Memory) {
+        let Some(embedding) = &memory.experience.embeddings else {
+            return;
+        };
+        let Ok(similar) = self.retriever.search_by_embedding(
+            embedding,
+            CONTRADICTION_CANDIDATES,
+            Some(&memory.id),
+        ) else {
+            return;
+        };
+
+        let mut stale_ids = Vec::new();
+        for (id, similarity) in similar {
+            let Ok(older) = self.long_term_memory.get(&id) else {
+                continue;
+            };
+            let Some(kind) = contradictions::detect(&older, memory, similarity) else {
+                continue;
+            };
+
+            let mut stale = older;
+            let metadata = &mut stale.experience.metadata;
+            metadata.insert(
+                contradictions::CONTRADICTED_BY_KEY.to_string(),
+                memory.id.0.to_string(),
+            );
+            metadata.insert(
+                contradictions::CONTRADICTION_KIND_KEY.to_string(),
+                kind.as_str().to_string(),
+            );
+            if let Err(e) = self.update_memory(&stale) {
+                tracing::debug!("Failed to flag contradicted memory {}: {e}", id.0);
+                continue;
+            }
+
+            self.record_consolidation_event(ConsolidationEvent::ContradictionDetected {
+                stale_memory_id: id.0.to_string(),
+                current_memory_id: memory.id.0.to_string(),
+                kind: kind.as_str().to_string(),
+                content_preview: stale.experience.content.chars().take(50).collect(),
+                timestamp: chrono::Utc::now(),
+            });
+            stale_ids.push(id.0.to_string());
+        }
+
+        if stale_ids.is_empty() {
+            return;
+        }
+        tracing::info!(
+            memory_id = %memory.id.0,
+            contradicted = stale_ids.len(),
+            "New memory contradicts older memories"
+        );
+        let mut current = memory.clone();
+        current.experience.metadata.insert(
+            contradictions::CONTRADICTS_KEY.to_string(),
+            stale_ids.join(","),
+        );
+        if let Err(e) = self.update_memory(&current) {
+            tracing::debug!("Failed to flag contradicting memory {}: {e}", memory.id.0);
+        }
+    }
+
+    /// Contradicted memories paired with the newer memory that supersedes them
+    pub fn conflicts(&self, limit: usize) -> Result<Vec<contradictions::Conflict>> {
+        let mut conflicts = contradictions::find_conflicts(&self.get_all_memories()?);
+        conflicts.truncate(limit);
+        Ok(conflicts)
+    }
+
     /// Update a memory in storage with full re-indexing
     ///
     /// This properly updates the memory by:
@@ -4525,6 +5440,12 @@ impl MemorySystem {
         self.long_term_memory.migrate_legacy()
     }
 
+    /// Re-seal stored memories with the current encryption key (see `encryption`)
+    /// Returns the number of records rewritten
+    pub fn reencrypt(&self) -> Result<usize> {
+        self.long_term_memory.reencrypt()
+    }
+
     /// Rebuild vector index from scratch using only valid memories in storage
     /// This removes orphaned index entries and rebuilds with proper ID mappings
     /// Returns (total_memories, total_indexed)
@@ -4673,7 +5594,8 @@ impl MemorySystem {
     /// memory_system.reinforce_recall(&tracked.memory_ids(), RetrievalOutcome::Helpful)?;
     /// ```
     pub fn recall_tracked(&self, query: &Query) -> Result<TrackedRetrieval> {
-        let result = self.retriever.search_tracked(query, query.max_results)?;
+        let mut result = self.retriever.search_tracked(query, query.max_results)?;
+        result.memories.retain(|m| !self.is_suppressed(m));
         if let Ok(count) = self.long_term_memory.increment_retrieval_count() {
             self.stats.write().total_retrievals = count;
         }
@@ -4702,6 +5624,59 @@ impl MemorySystem {
         memory_ids: &[MemoryId],
         outcome: RetrievalOutcome,
     ) -> Result<ReinforcementStats> {
+        self.reinforce_recall_weighted(memory_ids, outcome, 1.0)
+    }
+
+    /// Reinforce the stored memory with the same normalized content instead of
+    /// storing a duplicate; returns its ID, or `None` if the content is new
+    ///
+    /// Repeats count as a mild helpful signal and refresh recency
+    /// (see [`DUPLICATE_REINFORCEMENT_STRENGTH`]). Tags the repeat adds are
+    /// merged into the existing memory; its type and metadata are kept.
+    pub fn reinforce_duplicate(&self, content: &str, tags: &[String]) -> Result<Option<MemoryId>> {
+        let Some(existing) = self
+            .long_term_memory
+            .find_by_content_hash(&types::content_hash(content))?
+        else {
+            return Ok(None);
+        };
+        self.reinforce_recall_weighted(
+            std::slice::from_ref(&existing.id),
+            RetrievalOutcome::Helpful,
+            DUPLICATE_REINFORCEMENT_STRENGTH,
+        )?;
+        if tags.iter().any(|t| !existing.experience.tags.contains(t)) {
+            // Re-read so the reinforcement just recorded isn't overwritten
+            let mut memory = self.long_term_memory.get(&existing.id)?;
+            for tag in tags {
+                if !memory.experience.tags.contains(tag) {
+                    memory.experience.tags.push(tag.clone());
+                }
+            }
+            self.update_memory(&memory)?;
+        }
+        Ok(Some(existing.id))
+    }
+
+    /// Reinforce memories with a graded outcome strength
+    ///
+    /// Same as [`Self::reinforce_recall`], but the importance boost/decay is
+    /// scaled by `strength` (clamped to `0.0..=MAX_REINFORCEMENT_STRENGTH`,
+    /// 1.0 = normal signal). Access is recorded regardless of strength.
+    pub fn reinforce_recall_weighted(
+        &self,
+        memory_ids: &[MemoryId],
+        outcome: RetrievalOutcome,
+        strength: f32,
+    ) -> Result<ReinforcementStats> {
+        let strength = if strength.is_finite() {
+            strength.clamp(0.0, MAX_REINFORCEMENT_STRENGTH)
+        } else {
+            1.0
+        };
+        let boost = HEBBIAN_BOOST_HELPFUL * strength;
+        let decay = HEBBIAN_DECAY_MISLEADING * strength;
+
         if memory_ids.is_empty() {
             return Ok(ReinforcementStats::default());
         }
@@ -4763,11 +5738,11 @@ impl MemorySystem {
                 memory.record_access();
                 match &outcome {
                     RetrievalOutcome::Helpful => {
-                        memory.boost_importance(HEBBIAN_BOOST_HELPFUL);
+                        memory.boost_importance(boost);
                         stats.importance_boosts += 1;
                     }
                     RetrievalOutcome::Misleading => {
-                        memory.decay_importance(HEBBIAN_DECAY_MISLEADING);
+                        memory.decay_importance(decay);
                         stats.importance_decays += 1;
                     }
                     RetrievalOutcome::Neutral => {
@@ -4791,11 +5766,11 @@ impl MemorySystem {
                         memory.record_access();
                         match &outcome {
                             RetrievalOutcome::Helpful => {
-                                memory.boost_importance(HEBBIAN_BOOST_HELPFUL);
+                                memory.boost_importance(boost);
                                 stats.importance_boosts += 1;
                             }
                             RetrievalOutcome::Misleading => {
-                                memory.decay_importance(HEBBIAN_DECAY_MISLEADING);
+                                memory.decay_importance(decay);
                                 stats.importance_decays += 1;
                             }
                             RetrievalOutcome::Neutral => {
@@ -5050,6 +6025,7 @@ impl MemorySystem {
     ) -> Result<(MemoryId, bool)> {
         // Check resource limits
         self.check_resource_limits()?;
+        Self::ensure_provenance(&mut experience);
 
         // Try to find existing memory with this external_id
         if let Some(mut existing) = self.long_term_memory.find_by_external_id(&external_id)? {
@@ -5074,9 +6050,12 @@ impl MemorySystem {
                 existing.experience.tags = experience.tags;
             }
 
-            // Regenerate embeddings for new content
+            // Regenerate embeddings for new content, unless the caller
+            // already embedded it (e.g. batched bulk sync)
             let content_hash = Self::sha256_hash(&existing.experience.content);
-            if let Some(cached_embedding) = self.content_cache.get(&content_hash) {
+            if let Some(embedding) = experience.embeddings.take() {
+                existing.experience.embeddings = Some(embedding);
+            } else if let Some(cached_embedding) = self.content_cache.get(&content_hash) {
                 existing.experience.embeddings = Some(cached_embedding.clone());
             } else {
                 match self.embedder.encode(&existing.experience.content) {
@@ -5089,6 +6068,9 @@ impl MemorySystem {
                     }
                 }
             }
+            if existing.experience.embeddings.is_some() {
+                self.record_embedding_model(&mut existing.experience.metadata);
+            }
 
             // TEMPORAL EXTRACTION: Re-extract dates when content changes
             let temporal =
@@ -5171,6 +6153,9 @@ impl MemorySystem {
                         }
                     }
                 }
+                if experience.embeddings.is_some() {
+                    self.record_embedding_model(&mut experience.metadata);
+                }
             }
 
             // TEMPORAL EXTRACTION: Extract dates from content for temporal filtering
@@ -5370,8 +6355,8 @@ impl MemorySystem {
 
     /// Get the history of a memory (audit trail of changes)
     ///
-    /// Returns the full revision history for memories with external linking.
-    /// Returns empty vec for regular (non-mutable) memories.
+    /// Returns every revision recorded by upserts, API edits and consolidation.
+    /// Returns empty vec for memories never changed after creation.
     pub fn get_memory_history(&self, memory_id: &MemoryId) -> Result<Vec<MemoryRevision>> {
         let memory = self.long_term_memory.get(memory_id)?;
         Ok(memory.history.clone())
@@ -5546,12 +6531,85 @@ impl MemorySystem {
 
         // 3.7. Heavy cycle: load all memories once for both fact extraction and replay.
         // This avoids two separate RocksDB full scans on the same cycle.
-        let all_memories_for_heavy: Vec<SharedMemory> = if is_heavy {
+        let mut all_memories_for_heavy: Vec<SharedMemory> = if is_heavy {
             self.get_all_memories().unwrap_or_default()
         } else {
             Vec::new()
         };
 
+        // 3.74. Tiering: move stored memories into the working/episodic/semantic
+        // store their age and importance score have earned (heavy only)
+        if is_heavy {
+            let mut retiered = 0;
+            for memory in all_memories_for_heavy.iter_mut() {
+                let tier = importance::target_tier(
+                    memory,
+                    self.importance_score(memory),
+                    |base| self.graph_adjusted_threshold(memory, base),
+                    now,
+                );
+                if tier == memory.tier {
+                    continue;
+                }
+                let mut updated = (**memory).clone();
+                updated.tier = tier;
+                if let Err(e) = self.long_term_memory.update(&updated) {
+                    tracing::debug!("Failed to persist tier of memory {}: {}", memory.id.0, e);
+                    continue;
+                }
+                *memory = Arc::new(updated);
+                retiered += 1;
+            }
+            if retiered > 0 {
+                tracing::debug!("Tiering: moved {} stored memories up a tier", retiered);
+            }
+        }
+
+        // 3.75. Forgetting curve: persist decayed strength of stored memories (heavy only)
+        // Activation of long-term memories tracks their retention strength, so
+        // memories nobody revisits fade while reinforced/important ones persist.
+        if is_heavy {
+            let mut forgotten_count = 0;
+            for memory in &all_memories_for_heavy {
+                let strength = memory.retention_strength(now);
+                if memory.activation() - strength > MEMORY_DECAY_PERSIST_DELTA {
+                    memory.set_activation(strength);
+                    if let Err(e) = self.long_term_memory.update(memory) {
+                        tracing::debug!("Failed to persist decayed memory {}: {}", memory.id.0, e);
+                        continue;
+                    }
+                    forgotten_count += 1;
+                }
+            }
+            if forgotten_count > 0 {
+                tracing::debug!(
+                    "Forgetting curve: decayed {} stored memories",
+                    forgotten_count
+                );
+            }
+            decayed_count += forgotten_count;
+        }
+
+        // 3.76. Spaced repetition: refresh the review queue (heavy only)
+        if is_heavy {
+            let due = spaced_repetition::build_review_queue(
+                &all_memories_for_heavy,
+                now,
+                REVIEW_QUEUE_CACHE_SIZE,
+            );
+            *self.review_queue.write() = due.into_iter().map(|r| r.memory.id.clone()).collect();
+        }
+
+        // 3.77. Compression: train the store's zstd dictionary once it holds
+        // enough records, recompressing what's there (heavy only, one-off)
+        if is_heavy {
+            match self.long_term_memory.train_compression() {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("Recompressed {} memories with trained dictionary", n),
+                Err(e) => tracing::debug!("Compression dictionary training skipped: {}", e),
+            }
+        }
+
         // 3.8. Fact extraction: consolidate episodic memories into semantic facts
         // HEAVY ONLY: requires ONNX inference for embedding new facts.
         // The dirty flag (fact_extraction_needed) is only checked on heavy cycles;
@@ -5905,6 +6963,49 @@ impl MemorySystem {
         // Removes patterns older than 24 hours
         self.pattern_detector.write().cleanup();
 
+        // 4.6. Near-duplicate consolidation (heavy only, opt-in). Runs after fact
+        // extraction and replay so those steps never see memories trashed here.
+        let mut merged_away: HashSet<MemoryId> = HashSet::new();
+        if is_heavy && self.merge_duplicates && !all_memories_for_heavy.is_empty() {
+            let clusters = dedup::find_duplicate_clusters(&all_memories_for_heavy);
+            if !clusters.is_empty() {
+                match self.merge_duplicate_clusters(&clusters) {
+                    Ok(removed) => tracing::debug!(
+                        "Duplicate consolidation: merged {} memories into {} canonical memories",
+                        removed,
+                        clusters.len()
+                    ),
+                    Err(e) => tracing::warn!("Duplicate consolidation failed: {}", e),
+                }
+                // Canonical memories changed too; leave whole clusters for the next cycle
+                merged_away.extend(clusters.iter().flat_map(|c| {
+                    std::iter::once(c.canonical.id.clone())
+                        .chain(c.duplicates.iter().map(|d| d.id.clone()))
+                }));
+            }
+        }
+
+        // 4.7. Semantic gists: summarize aged episodic clusters and demote the
+        // raw episodes (heavy only). Skips memories touched by 4.6.
+        if is_heavy && !all_memories_for_heavy.is_empty() {
+            let remaining: Vec<SharedMemory> = all_memories_for_heavy
+                .iter()
+                .filter(|m| !merged_away.contains(&m.id))
+                .cloned()
+                .collect();
+            let clusters = gists::find_aged_clusters(&remaining, now);
+            if !clusters.is_empty() {
+                match self.write_gists(&clusters) {
+                    Ok(ids) => tracing::debug!(
+                        "Gist consolidation: summarized {} episodes into {} gists",
+                        clusters.iter().map(|c| c.episodes.len()).sum::<usize>(),
+                        ids.len()
+                    ),
+                    Err(e) => tracing::warn!("Gist consolidation failed: {}", e),
+                }
+            }
+        }
+
         // 5. Auto-repair index integrity and compact if needed (heavy only)
         // repair_vector_index() does a full RocksDB scan + ONNX inference per orphan
         if is_heavy {
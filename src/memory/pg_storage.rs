@@ -0,0 +1,568 @@
+//! Postgres + pgvector Storage Backend
+//!
+//! Stores every user's memories in one shared Postgres database, so several
+//! server instances can serve the same users. Rows are keyed by
+//! `(store, id)`, where `store` is the user's storage directory name (the
+//! user id). The full memory is kept as bincode in `data`; the columns next
+//! to it exist for lookups and for the pgvector cosine search.
+//!
+//! Requires the `vector` extension (created on first connect when the role
+//! is allowed to). The embedding column is untyped so stores on different
+//! models can share the table; the configured embedding dimension gets an
+//! HNSW index on `embedding::vector(N)` (pgvector 0.5+, up to 2000 dims),
+//! which vector search casts to. Other dimensions fall back to an exact scan.
+//! HNSW filters by user after the index scan, so on pgvector 0.8+ consider
+//! `hnsw.iterative_scan` for large shared tables.
+//!
+//! Connections use TLS as the URL's `sslmode` asks: `prefer` (the default)
+//! falls back to plaintext, `require` refuses it. Server certificates are
+//! checked against the public webpki roots and SHODH_POSTGRES_CA_FILE.
+//!
+//! Vector mappings for the in-process index stay in the instance's local
+//! RocksDB, since their ids are only meaningful to that instance's index.
+//!
+//! With encryption at rest on (see [`super::encryption`]), `data` is sealed
+//! and the embedding column is left empty, so vector search falls back to
+//! each instance's in-process index.
+//!
+//! `data` is zstd-compressed (see [`super::blob_compression`]) without a
+//! trained dictionary, since instances don't share one.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use pgvector::Vector;
+use postgres::Client;
+use r2d2_postgres::PostgresConnectionManager;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::CertificateDer;
+use tokio_postgres_rustls::MakeRustlsConnect;
+
+use super::backend::Storage;
+use super::blob_compression::Codec;
+use super::encryption::{self, Keyring};
+use super::storage::{MemoryStorage, SearchCriteria, StorageStats, VectorMappingEntry};
+use super::types::{content_hash, Memory, MemoryId};
+
+type Pool = r2d2::Pool<PostgresConnectionManager<MakeRustlsConnect>>;
+
+const DEFAULT_POOL_SIZE: u32 = 16;
+
+/// Largest dimension pgvector can build an HNSW index for
+const MAX_HNSW_DIMENSIONS: usize = 2000;
+
+const SCHEMA: &str = "
+CREATE EXTENSION IF NOT EXISTS vector;
+CREATE TABLE IF NOT EXISTS shodh_memories (
+    store        TEXT        NOT NULL,
+    id           UUID        NOT NULL,
+    data         BYTEA       NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL,
+    external_id  TEXT,
+    content_hash TEXT        NOT NULL,
+    parent_id    UUID,
+    forgotten    BOOLEAN     NOT NULL DEFAULT FALSE,
+    compressed   BOOLEAN     NOT NULL DEFAULT FALSE,
+    embedding    vector,
+    PRIMARY KEY (store, id)
+);
+CREATE INDEX IF NOT EXISTS shodh_memories_external
+    ON shodh_memories (store, external_id) WHERE external_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS shodh_memories_content ON shodh_memories (store, content_hash);
+CREATE INDEX IF NOT EXISTS shodh_memories_parent
+    ON shodh_memories (store, parent_id) WHERE parent_id IS NOT NULL;
+CREATE TABLE IF NOT EXISTS shodh_meta (
+    store TEXT  NOT NULL,
+    key   TEXT  NOT NULL,
+    value BYTEA NOT NULL,
+    PRIMARY KEY (store, key)
+);
+";
+
+/// Connection pool shared by every user's store
+static POOL: OnceLock<Pool> = OnceLock::new();
+
+fn pool() -> Result<Pool> {
+    if let Some(pool) = POOL.get() {
+        return Ok(pool.clone());
+    }
+
+    let url = std::env::var("SHODH_POSTGRES_URL")
+        .context("SHODH_STORAGE_BACKEND=postgres requires SHODH_POSTGRES_URL")?;
+    let size = std::env::var("SHODH_POSTGRES_POOL_SIZE")
+        .ok()
+        .and_then(|v| v.parse::<u32>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_POOL_SIZE);
+    let manager = PostgresConnectionManager::new(
+        url.parse().context("Invalid SHODH_POSTGRES_URL")?,
+        tls_connector()?,
+    );
+
+    let pool = blocking(|| -> Result<Pool> {
+        let pool = r2d2::Pool::builder()
+            .max_size(size)
+            .build(manager)
+            .context("Failed to connect to Postgres")?;
+        let mut client = pool.get()?;
+        client
+            .batch_execute(SCHEMA)
+            .context("Failed to create Postgres schema")?;
+        if let Some(dims) = crate::embeddings::ProviderConfig::from_env().dimension() {
+            create_vector_index(&mut client, dims);
+        }
+        Ok(pool)
+    })?;
+    tracing::info!(pool_size = size, "Connected to Postgres storage backend");
+    // Another store may have connected first; keep whichever pool won
+    Ok(POOL.get_or_init(|| pool).clone())
+}
+
+/// TLS for pooled connections; whether it's used is up to the URL's sslmode
+fn tls_connector() -> Result<MakeRustlsConnect> {
+    let mut roots = rustls::RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    if let Ok(path) = std::env::var("SHODH_POSTGRES_CA_FILE") {
+        let certs = CertificateDer::pem_file_iter(&path)
+            .map_err(|e| anyhow!("Failed to read SHODH_POSTGRES_CA_FILE {path}: {e:?}"))?;
+        for cert in certs {
+            let cert = cert.map_err(|e| anyhow!("Invalid certificate in {path}: {e:?}"))?;
+            roots
+                .add(cert)
+                .with_context(|| format!("Invalid CA certificate in {path}"))?;
+        }
+    }
+    let config = rustls::ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok(MakeRustlsConnect::new(config))
+}
+
+/// HNSW index over embeddings of one dimension. Best effort: without it
+/// vector search is an exact scan.
+fn create_vector_index(client: &mut Client, dims: usize) {
+    if dims > MAX_HNSW_DIMENSIONS {
+        tracing::info!(
+            dims,
+            "Embeddings too wide for an HNSW index, using exact vector search"
+        );
+        return;
+    }
+    let sql = format!(
+        "CREATE INDEX IF NOT EXISTS shodh_memories_embedding_{dims} ON shodh_memories
+             USING hnsw ((embedding::vector({dims})) vector_cosine_ops)
+             WHERE vector_dims(embedding) = {dims}"
+    );
+    if let Err(e) = client.batch_execute(&sql) {
+        tracing::warn!(
+            dims,
+            "Failed to create pgvector HNSW index, using exact vector search: {e}"
+        );
+    }
+}
+
+/// Run synchronous Postgres I/O without stalling async worker threads
+fn blocking<T>(f: impl FnOnce() -> T) -> T {
+    use tokio::runtime::{Handle, RuntimeFlavor};
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(f)
+        }
+        _ => f(),
+    }
+}
+
+/// Open, decompress and deserialize a `data` column. The flag is true when
+/// the record should be re-sealed with the current key.
+fn decode(
+    keyring: Option<&Keyring>,
+    codec: &Codec,
+    id: &uuid::Uuid,
+    data: &[u8],
+) -> Result<(Memory, bool)> {
+    let (plaintext, reseal) = encryption::open(keyring, id.as_bytes(), data)?;
+    let plaintext = codec.decompress(&plaintext)?;
+    let (memory, _) = bincode::serde::decode_from_slice(&plaintext, bincode::config::standard())
+        .context("Failed to deserialize memory from Postgres")?;
+    Ok((memory, reseal))
+}
+
+/// One user's memories in the shared Postgres database
+pub struct PostgresStorage {
+    pool: Pool,
+    /// Row key: the storage directory name
+    store: String,
+    path: PathBuf,
+    /// Instance-local vector mappings
+    local: Arc<MemoryStorage>,
+    keyring: Option<Arc<Keyring>>,
+    codec: Codec,
+}
+
+impl PostgresStorage {
+    pub fn open(path: &Path, local: Arc<MemoryStorage>) -> Result<Self> {
+        let store = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .ok_or_else(|| anyhow!("Storage path {:?} has no directory name", path))?;
+        Ok(Self {
+            pool: pool()?,
+            store,
+            path: path.to_path_buf(),
+            local,
+            keyring: encryption::keyring()?,
+            codec: Codec::from_env(),
+        })
+    }
+
+    fn with_client<T>(&self, f: impl FnOnce(&mut Client) -> Result<T>) -> Result<T> {
+        blocking(|| {
+            let mut client = self
+                .pool
+                .get()
+                .context("Failed to get Postgres connection")?;
+            f(&mut client)
+        })
+    }
+
+    /// Memories from a `SELECT id, data ...` query
+    fn query_memories(
+        &self,
+        sql: &str,
+        params: &[&(dyn postgres::types::ToSql + Sync)],
+    ) -> Result<Vec<Memory>> {
+        let rows = self.with_client(|client| Ok(client.query(sql, params)?))?;
+        rows.iter()
+            .map(|row| {
+                decode(
+                    self.keyring.as_deref(),
+                    &self.codec,
+                    &row.get(0),
+                    row.get(1),
+                )
+                .map(|(m, _)| m)
+            })
+            .collect()
+    }
+
+    /// `content_hash` column value, keyed when encryption is on
+    fn content_index_hash(&self, memory: &Memory) -> String {
+        encryption::content_index_hash(
+            self.keyring.as_deref(),
+            &content_hash(&memory.experience.content),
+        )
+    }
+}
+
+impl Storage for PostgresStorage {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn store(&self, memory: &Memory) -> Result<()> {
+        let data = bincode::serde::encode_to_vec(memory, bincode::config::standard())
+            .with_context(|| format!("Failed to serialize memory {}", memory.id.0))?;
+        let mut data = self.codec.compress(data)?;
+        // Encrypted stores keep embeddings only inside the sealed record
+        let embedding = match &self.keyring {
+            Some(keyring) => {
+                data = keyring.seal(memory.id.0.as_bytes(), &data)?;
+                None
+            }
+            None => memory
+                .experience
+                .embeddings
+                .clone()
+                .filter(|e| !e.is_empty())
+                .map(Vector::from),
+        };
+        let parent_id = memory.parent_id.as_ref().map(|p| p.0);
+        let hash = self.content_index_hash(memory);
+        self.with_client(|client| {
+            client.execute(
+                "INSERT INTO shodh_memories
+                     (store, id, data, created_at, external_id, content_hash, parent_id,
+                      forgotten, compressed, embedding)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                 ON CONFLICT (store, id) DO UPDATE SET
+                     data = EXCLUDED.data,
+                     created_at = EXCLUDED.created_at,
+                     external_id = EXCLUDED.external_id,
+                     content_hash = EXCLUDED.content_hash,
+                     parent_id = EXCLUDED.parent_id,
+                     forgotten = EXCLUDED.forgotten,
+                     compressed = EXCLUDED.compressed,
+                     embedding = EXCLUDED.embedding",
+                &[
+                    &self.store,
+                    &memory.id.0,
+                    &data,
+                    &memory.created_at,
+                    &memory.external_id,
+                    &hash,
+                    &parent_id,
+                    &memory.is_forgotten(),
+                    &memory.compressed,
+                    &embedding,
+                ],
+            )?;
+            Ok(())
+        })
+        .with_context(|| format!("Failed to store memory {} in Postgres", memory.id.0))
+    }
+
+    fn get(&self, id: &MemoryId) -> Result<Memory> {
+        self.query_memories(
+            "SELECT id, data FROM shodh_memories WHERE store = $1 AND id = $2",
+            &[&self.store, &id.0],
+        )?
+        .pop()
+        .ok_or_else(|| anyhow!("Memory not found: {id:?}"))
+    }
+
+    fn delete(&self, id: &MemoryId) -> Result<()> {
+        self.with_client(|client| {
+            client.execute(
+                "DELETE FROM shodh_memories WHERE store = $1 AND id = $2",
+                &[&self.store, &id.0],
+            )?;
+            Ok(())
+        })?;
+        self.local.delete_vector_mapping(id)
+    }
+
+    fn get_all_ids(&self) -> Result<Vec<MemoryId>> {
+        let rows = self.with_client(|client| {
+            Ok(client.query(
+                "SELECT id FROM shodh_memories WHERE store = $1",
+                &[&self.store],
+            )?)
+        })?;
+        Ok(rows.iter().map(|row| MemoryId(row.get(0))).collect())
+    }
+
+    fn get_all(&self) -> Result<Vec<Memory>> {
+        self.query_memories(
+            "SELECT id, data FROM shodh_memories WHERE store = $1 AND NOT forgotten",
+            &[&self.store],
+        )
+    }
+
+    fn search(&self, criteria: SearchCriteria) -> Result<Vec<Memory>> {
+        let mut memories = match &criteria {
+            SearchCriteria::ByParent(parent_id) => self.query_memories(
+                "SELECT id, data FROM shodh_memories
+                 WHERE store = $1 AND parent_id = $2 AND NOT forgotten",
+                &[&self.store, &parent_id.0],
+            )?,
+            _ => self
+                .get_all()?
+                .into_iter()
+                .filter(|m| criteria.matches(m))
+                .collect(),
+        };
+        if let SearchCriteria::ByEpisodeSequence { .. } = criteria {
+            // Temporal order within the episode, as the index scan returns it
+            memories.sort_by_key(|m| {
+                m.experience
+                    .context
+                    .as_ref()
+                    .and_then(|ctx| ctx.episode.sequence_number)
+            });
+        }
+        Ok(memories)
+    }
+
+    fn get_stats(&self) -> Result<StorageStats> {
+        let rows = self.with_client(|client| {
+            Ok(client.query(
+                "SELECT id, data FROM shodh_memories WHERE store = $1 AND NOT forgotten",
+                &[&self.store],
+            )?)
+        })?;
+        let mut stats = StorageStats::default();
+        for row in &rows {
+            let data: &[u8] = row.get(1);
+            let (memory, _) = decode(self.keyring.as_deref(), &self.codec, &row.get(0), data)?;
+            stats.total_count += 1;
+            stats.total_size_bytes += data.len();
+            if memory.compressed {
+                stats.compressed_count += 1;
+            }
+            stats.importance_sum += memory.importance();
+        }
+        if stats.total_count > 0 {
+            stats.average_importance = stats.importance_sum / stats.total_count as f32;
+        }
+        stats.total_retrievals = self.get_retrieval_count().unwrap_or(0);
+        Ok(stats)
+    }
+
+    fn flush(&self) -> Result<()> {
+        // Postgres commits are durable; only the local mappings need flushing
+        self.local.flush()
+    }
+
+    fn get_meta(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let row = self.with_client(|client| {
+            Ok(client.query_opt(
+                "SELECT value FROM shodh_meta WHERE store = $1 AND key = $2",
+                &[&self.store, &key],
+            )?)
+        })?;
+        Ok(row.map(|row| row.get(0)))
+    }
+
+    fn put_meta(&self, key: &str, value: &[u8]) -> Result<()> {
+        self.with_client(|client| {
+            client.execute(
+                "INSERT INTO shodh_meta (store, key, value) VALUES ($1, $2, $3)
+                 ON CONFLICT (store, key) DO UPDATE SET value = EXCLUDED.value",
+                &[&self.store, &key, &value],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn delete_meta(&self, key: &str) -> Result<()> {
+        self.with_client(|client| {
+            client.execute(
+                "DELETE FROM shodh_meta WHERE store = $1 AND key = $2",
+                &[&self.store, &key],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn scan_meta(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        let rows = self.with_client(|client| {
+            Ok(client.query(
+                "SELECT key, value FROM shodh_meta
+                 WHERE store = $1 AND starts_with(key, $2) ORDER BY key",
+                &[&self.store, &prefix],
+            )?)
+        })?;
+        Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+
+    fn delete_meta_prefix(&self, prefix: &str) -> Result<usize> {
+        let deleted = self.with_client(|client| {
+            Ok(client.execute(
+                "DELETE FROM shodh_meta WHERE store = $1 AND starts_with(key, $2)",
+                &[&self.store, &prefix],
+            )?)
+        })?;
+        Ok(deleted as usize)
+    }
+
+    fn get_all_vector_mappings(&self) -> Result<Vec<(MemoryId, VectorMappingEntry)>> {
+        self.local.get_all_vector_mappings()
+    }
+
+    fn update_vector_mapping(&self, memory_id: &MemoryId, vector_ids: Vec<u32>) -> Result<()> {
+        self.local.update_vector_mapping(memory_id, vector_ids)
+    }
+
+    fn delete_vector_mapping(&self, memory_id: &MemoryId) -> Result<()> {
+        self.local.delete_vector_mapping(memory_id)
+    }
+
+    fn vector_search(
+        &self,
+        embedding: &[f32],
+        limit: usize,
+    ) -> Result<Option<Vec<(MemoryId, f32)>>> {
+        if self.keyring.is_some() {
+            // No plaintext embeddings to search; use the in-process index
+            return Ok(None);
+        }
+        // Dimension inlined (not a parameter) so the planner can match the
+        // per-dimension HNSW index and its partial predicate
+        let dims = embedding.len();
+        let query = Vector::from(embedding.to_vec());
+        let sql = format!(
+            "SELECT id, 1 - (embedding::vector({dims}) <=> $2) FROM shodh_memories
+             WHERE store = $1 AND NOT forgotten AND vector_dims(embedding) = {dims}
+             ORDER BY embedding::vector({dims}) <=> $2 LIMIT $3"
+        );
+        let rows = self.with_client(|client| {
+            Ok(client.query(sql.as_str(), &[&self.store, &query, &(limit as i64)])?)
+        })?;
+        Ok(Some(
+            rows.iter()
+                .map(|row| (MemoryId(row.get(0)), row.get::<_, f64>(1) as f32))
+                .collect(),
+        ))
+    }
+
+    fn find_by_external_id(&self, external_id: &str) -> Result<Option<Memory>> {
+        Ok(self
+            .query_memories(
+                "SELECT id, data FROM shodh_memories WHERE store = $1 AND external_id = $2 LIMIT 1",
+                &[&self.store, &external_id],
+            )?
+            .pop())
+    }
+
+    fn find_by_content_hash(&self, hash: &str) -> Result<Option<Memory>> {
+        Ok(self
+            .query_memories(
+                "SELECT id, data FROM shodh_memories
+                 WHERE store = $1 AND content_hash = $2 AND NOT forgotten
+                 ORDER BY created_at LIMIT 1",
+                &[
+                    &self.store,
+                    &encryption::content_index_hash(self.keyring.as_deref(), hash),
+                ],
+            )?
+            .pop())
+    }
+
+    fn get_uncompressed_older_than(&self, cutoff: DateTime<Utc>) -> Result<Vec<Memory>> {
+        self.query_memories(
+            "SELECT id, data FROM shodh_memories
+             WHERE store = $1 AND NOT compressed AND NOT forgotten AND created_at < $2",
+            &[&self.store, &cutoff],
+        )
+    }
+
+    fn reencrypt(&self) -> Result<usize> {
+        if self.keyring.is_none() {
+            return Ok(0);
+        }
+        let rows = self.with_client(|client| {
+            Ok(client.query(
+                "SELECT id, data FROM shodh_memories WHERE store = $1",
+                &[&self.store],
+            )?)
+        })?;
+        let mut resealed = 0;
+        for row in &rows {
+            let id: uuid::Uuid = row.get(0);
+            match decode(self.keyring.as_deref(), &self.codec, &id, row.get(1)) {
+                Ok((memory, true)) => {
+                    self.store(&memory)?;
+                    resealed += 1;
+                }
+                // Content hashes are keyed with the current key; rewrite them
+                // so dedup keeps matching records sealed before a rotation
+                Ok((memory, false)) => {
+                    let hash = self.content_index_hash(&memory);
+                    self.with_client(|client| {
+                        client.execute(
+                            "UPDATE shodh_memories SET content_hash = $3 WHERE store = $1 AND id = $2",
+                            &[&self.store, &id, &hash],
+                        )?;
+                        Ok(())
+                    })?;
+                }
+                Err(e) => {
+                    tracing::warn!("Skipping unreadable record {id} during re-encryption: {e}")
+                }
+            }
+        }
+        Ok(resealed)
+    }
+}
@@ -503,6 +503,231 @@ fn update_bounds(
     }
 }
 
+// ============================================================================
+// TIME RANGE RESOLUTION
+// ============================================================================
+// Resolves the temporal expression of a query ("yesterday", "last sprint",
+// "in March") to a concrete range used as a retrieval filter.
+
+/// The time range a query's temporal expression refers to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TimeRange {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// The expression as written in the query
+    pub expression: String,
+    /// Byte span of the expression in the query
+    #[serde(skip)]
+    span: (usize, usize),
+}
+
+impl TimeRange {
+    /// `text` without the temporal expression, so it doesn't skew semantic matching
+    pub fn strip_from(&self, text: &str) -> String {
+        let (start, end) = self.span;
+        match (text.get(..start), text.get(end..)) {
+            (Some(before), Some(after)) => format!("{before} {after}")
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+            _ => text.to_string(),
+        }
+    }
+}
+
+fn time_expression_regex() -> &'static regex::Regex {
+    static RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    RE.get_or_init(|| {
+        regex::Regex::new(
+            r"(?ix)\b(?:
+                (?P<day>today|yesterday)
+              | (?P<rel>this|last|past|previous)\s+(?P<rel_unit>week|month|year|sprint)
+              | (?:last|past)\s+(?P<n>\d{1,3})\s+(?P<n_unit>day|week|month)s?
+              | (?P<ago>\d{1,3})\s+(?P<ago_unit>day|week|month)s?\s+ago
+              | (?:(?P<month_prep>in|during|since|last)\s+)?
+                (?P<month>january|february|march|april|may|june|july|august|september|october|november|december)
+                (?:\s+(?P<month_year>\d{4}))?
+              | (?:on|last)\s+(?P<weekday>monday|tuesday|wednesday|thursday|friday|saturday|sunday)
+              | (?:in|during)\s+(?P<year>(?:19|20)\d{2})
+            )\b",
+        )
+        .unwrap()
+    })
+}
+
+/// Resolve the first temporal expression in `text` relative to `now`
+///
+/// Handles:
+/// - Days: "today", "yesterday", "3 days ago", "on Monday", "last Tuesday"
+/// - Calendar periods: "this week", "last month", "last year"
+/// - Rolling windows: "past week", "last 10 days"
+/// - Sprints: "this sprint", "last sprint" (TEMPORAL_SPRINT_DAYS long)
+/// - Months and years: "in March", "since October", "March 2025", "in 2024"
+///
+/// A bare month name needs a preposition or year ("may" is usually a verb).
+pub fn parse_time_range(text: &str, now: DateTime<Utc>) -> Option<TimeRange> {
+    time_expression_regex()
+        .captures_iter(text)
+        .find_map(|caps| {
+            let whole = caps.get(0)?;
+            let (start, end) = resolve_time_expression(&caps, now)?;
+            Some(TimeRange {
+                start,
+                end,
+                expression: whole.as_str().to_string(),
+                span: (whole.start(), whole.end()),
+            })
+        })
+}
+
+fn day_start(date: NaiveDate) -> Option<DateTime<Utc>> {
+    date.and_hms_opt(0, 0, 0).map(|t| t.and_utc())
+}
+
+fn day_end(date: NaiveDate) -> Option<DateTime<Utc>> {
+    date.and_hms_opt(23, 59, 59).map(|t| t.and_utc())
+}
+
+/// First and last day of a calendar month
+fn month_bounds(year: i32, month: u32) -> Option<(NaiveDate, NaiveDate)> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)?
+    };
+    Some((first, next.pred_opt()?))
+}
+
+fn unit_days(unit: &str) -> i64 {
+    match unit {
+        "day" => 1,
+        "week" => 7,
+        "sprint" => crate::constants::TEMPORAL_SPRINT_DAYS,
+        "year" => 365,
+        _ => 30,
+    }
+}
+
+fn resolve_time_expression(
+    caps: &regex::Captures,
+    now: DateTime<Utc>,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let today = now.date_naive();
+    let group = |name: &str| caps.name(name).map(|m| m.as_str().to_lowercase());
+    let days = chrono::Duration::days;
+
+    if let Some(day) = group("day") {
+        let date = if day == "today" {
+            today
+        } else {
+            today.pred_opt()?
+        };
+        return Some((day_start(date)?, day_end(date)?));
+    }
+
+    if let Some(rel) = group("rel") {
+        let unit = group("rel_unit")?;
+        let len = unit_days(&unit);
+        let previous = rel == "last" || rel == "previous";
+        if rel == "past" || unit == "sprint" {
+            // Rolling window, or the one before it
+            let offset = if previous { len } else { 0 };
+            return Some((now - days(offset + len), now - days(offset)));
+        }
+        let (first, last) = match unit.as_str() {
+            "week" => {
+                let monday = today - days(today.weekday().num_days_from_monday() as i64);
+                if previous {
+                    (monday - days(7), monday - days(1))
+                } else {
+                    (monday, today)
+                }
+            }
+            "month" => {
+                let (year, month) = if !previous {
+                    (today.year(), today.month())
+                } else if today.month() == 1 {
+                    (today.year() - 1, 12)
+                } else {
+                    (today.year(), today.month() - 1)
+                };
+                let (first, last) = month_bounds(year, month)?;
+                (first, if previous { last } else { today })
+            }
+            _ => {
+                let year = today.year() - previous as i32;
+                let first = NaiveDate::from_ymd_opt(year, 1, 1)?;
+                let last = if previous {
+                    NaiveDate::from_ymd_opt(year, 12, 31)?
+                } else {
+                    today
+                };
+                (first, last)
+            }
+        };
+        return Some((day_start(first)?, day_end(last)?));
+    }
+
+    if let Some(n) = group("n") {
+        let n: i64 = n.parse().ok()?;
+        let len = unit_days(&group("n_unit")?);
+        return Some((now - days(n * len), now));
+    }
+
+    if let Some(n) = group("ago") {
+        let n: i64 = n.parse().ok()?;
+        let unit = group("ago_unit")?;
+        if unit == "day" {
+            let date = today - days(n);
+            return Some((day_start(date)?, day_end(date)?));
+        }
+        // A one-unit window centered on the point in time
+        let len = unit_days(&unit);
+        let center = now - days(n * len);
+        return Some((center - days(len / 2), center + days(len - len / 2)));
+    }
+
+    if let Some(month_name) = group("month") {
+        let prep = group("month_prep");
+        let explicit_year = group("month_year").and_then(|y| y.parse::<i32>().ok());
+        if prep.is_none() && explicit_year.is_none() {
+            return None;
+        }
+        let month = month_to_num(&month_name);
+        // Without a year, the most recent such month that has started
+        let year = explicit_year.unwrap_or(if month > today.month() {
+            today.year() - 1
+        } else {
+            today.year()
+        });
+        let (first, last) = month_bounds(year, month)?;
+        if prep.as_deref() == Some("since") {
+            return Some((day_start(first)?, now));
+        }
+        return Some((day_start(first)?, day_end(last)?));
+    }
+
+    if let Some(weekday) = group("weekday") {
+        let weekday: chrono::Weekday = weekday.parse().ok()?;
+        // Most recent such day before today
+        let back =
+            (today.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+        let date = today - days(if back == 0 { 7 } else { back as i64 });
+        return Some((day_start(date)?, day_end(date)?));
+    }
+
+    if let Some(year) = group("year") {
+        let year: i32 = year.parse().ok()?;
+        return Some((
+            day_start(NaiveDate::from_ymd_opt(year, 1, 1)?)?,
+            day_end(NaiveDate::from_ymd_opt(year, 12, 31)?)?,
+        ));
+    }
+
+    None
+}
+
 // ============================================================================
 // TEMPORAL QUERY DETECTION
 // ============================================================================
@@ -3396,6 +3621,49 @@ fn is_stop_word(word: &str) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_time_range() {
+        use chrono::TimeZone;
+        // Friday
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 15, 0, 0).unwrap();
+        let day = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        let range = |text: &str| {
+            let r = parse_time_range(text, now).expect(text);
+            (r.start.date_naive(), r.end.date_naive())
+        };
+
+        let yesterday = parse_time_range("what did we decide yesterday?", now).unwrap();
+        assert_eq!(yesterday.expression, "yesterday");
+        assert_eq!(
+            yesterday.strip_from("what did we decide yesterday?"),
+            "what did we decide ?"
+        );
+        assert_eq!(
+            range("what did we decide yesterday"),
+            (day(10, 15), day(10, 15))
+        );
+        assert_eq!(
+            range("what did we decide last week"),
+            (day(10, 5), day(10, 11))
+        );
+        assert_eq!(range("bugs this week"), (day(10, 12), day(10, 16)));
+        assert_eq!(range("auth work in March"), (day(3, 1), day(3, 31)));
+        assert_eq!(range("deploys since September"), (day(9, 1), day(10, 16)));
+        assert_eq!(range("3 days ago"), (day(10, 13), day(10, 13)));
+        assert_eq!(range("on Monday"), (day(10, 12), day(10, 12)));
+        assert_eq!(range("last sprint"), (day(9, 18), day(10, 2)));
+        assert_eq!(
+            range("in December"),
+            (
+                NaiveDate::from_ymd_opt(2025, 12, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()
+            )
+        );
+
+        assert!(parse_time_range("this may break the build", now).is_none());
+        assert!(parse_time_range("refactor the parser", now).is_none());
+    }
+
     #[test]
     fn test_noun_detection() {
         let query = "robot detected obstacle at coordinates";
@@ -0,0 +1,268 @@
+//! Per-User Memory Quotas
+//!
+//! Auto-encoding every interaction grows a user's store without bound, which
+//! eventually hurts both disk usage and retrieval quality. A quota caps the
+//! number of memories and/or stored bytes per user:
+//!
+//! ```text
+//! SHODH_QUOTA_MAX_MEMORIES=50000
+//! SHODH_QUOTA_MAX_BYTES=500000000
+//! ```
+//!
+//! When a store goes over quota the memories with the lowest
+//! importance × recency are evicted until it is back under
+//! `QUOTA_EVICTION_TARGET` of the limit, so eviction doesn't run on every
+//! write. Pinned memories are never evicted. The memory count is checked on
+//! every write; stored bytes need a storage scan and are checked by the heavy
+//! maintenance cycle.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{QUOTA_EVICTION_RECENCY_HALF_LIFE_DAYS, QUOTA_EVICTION_TARGET};
+use crate::memory::types::{Memory, MemoryId, SharedMemory};
+
+/// Limits on one user's store; `None` is unlimited
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaConfig {
+    pub max_memories: Option<usize>,
+    pub max_bytes: Option<usize>,
+}
+
+impl QuotaConfig {
+    /// Read SHODH_QUOTA_MAX_MEMORIES / SHODH_QUOTA_MAX_BYTES (unset or 0 = unlimited)
+    pub fn from_env() -> Self {
+        let limit = |name: &str| {
+            let val = std::env::var(name).ok()?;
+            match val.trim().parse::<usize>() {
+                Ok(0) => None,
+                Ok(n) => Some(n),
+                Err(_) => {
+                    tracing::warn!("Ignoring invalid {}: {}", name, val);
+                    None
+                }
+            }
+        };
+        Self {
+            max_memories: limit("SHODH_QUOTA_MAX_MEMORIES"),
+            max_bytes: limit("SHODH_QUOTA_MAX_BYTES"),
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.max_memories.is_none() && self.max_bytes.is_none()
+    }
+
+    /// Whether `count` memories exceed the memory-count limit
+    pub fn count_exceeded(&self, count: usize) -> bool {
+        self.max_memories.is_some_and(|max| count > max)
+    }
+
+    /// Whether `bytes` of stored memories exceed the byte limit
+    pub fn bytes_exceeded(&self, bytes: usize) -> bool {
+        self.max_bytes.is_some_and(|max| bytes > max)
+    }
+}
+
+/// Current usage of a user's store against its quota
+#[derive(Debug, Clone, Serialize)]
+pub struct QuotaStatus {
+    pub memory_count: usize,
+    pub storage_bytes: usize,
+    pub pinned_count: usize,
+    pub max_memories: Option<usize>,
+    pub max_bytes: Option<usize>,
+    /// Highest usage ratio across the configured limits (`None` when unlimited)
+    pub usage: Option<f32>,
+    pub over_quota: bool,
+}
+
+impl QuotaStatus {
+    pub fn new(
+        quota: &QuotaConfig,
+        memory_count: usize,
+        storage_bytes: usize,
+        pinned_count: usize,
+    ) -> Self {
+        let ratio = |used: usize, max: usize| used as f32 / max.max(1) as f32;
+        let usage = [
+            quota.max_memories.map(|max| ratio(memory_count, max)),
+            quota.max_bytes.map(|max| ratio(storage_bytes, max)),
+        ]
+        .into_iter()
+        .flatten()
+        .reduce(f32::max);
+        Self {
+            memory_count,
+            storage_bytes,
+            pinned_count,
+            max_memories: quota.max_memories,
+            max_bytes: quota.max_bytes,
+            usage,
+            over_quota: quota.count_exceeded(memory_count) || quota.bytes_exceeded(storage_bytes),
+        }
+    }
+}
+
+/// A memory chosen for eviction
+#[derive(Debug, Clone)]
+pub struct Eviction {
+    pub memory: SharedMemory,
+    /// Importance × recency at selection time
+    pub score: f32,
+}
+
+/// Importance × recency, recency halving every `QUOTA_EVICTION_RECENCY_HALF_LIFE_DAYS`
+/// since the memory was last accessed
+pub fn retention_score(memory: &Memory, now: DateTime<Utc>) -> f32 {
+    let idle_days = (now - memory.last_accessed()).num_seconds().max(0) as f64 / 86_400.0;
+    let recency = 0.5f64.powf(idle_days / QUOTA_EVICTION_RECENCY_HALF_LIFE_DAYS);
+    memory.importance() * recency as f32
+}
+
+/// Rough stored size of a memory (content plus embedding)
+pub fn estimated_bytes(memory: &Memory) -> usize {
+    memory.experience.content.len()
+        + memory
+            .experience
+            .embeddings
+            .as_ref()
+            .map_or(0, |e| e.len() * std::mem::size_of::<f32>())
+}
+
+/// Memories to evict to bring `memories` (totalling `storage_bytes`) back
+/// under `QUOTA_EVICTION_TARGET` of the quota, lowest retention score first.
+///
+/// Pinned, forgotten and `protected` memories are never selected.
+pub fn select_evictions(
+    memories: &[SharedMemory],
+    storage_bytes: usize,
+    quota: &QuotaConfig,
+    protected: Option<&MemoryId>,
+    now: DateTime<Utc>,
+) -> Vec<Eviction> {
+    let live: Vec<&SharedMemory> = memories.iter().filter(|m| !m.is_forgotten()).collect();
+    let mut count = live.len();
+    let mut bytes = storage_bytes;
+    if !quota.count_exceeded(count) && !quota.bytes_exceeded(bytes) {
+        return Vec::new();
+    }
+    let target = |max: usize| (max as f32 * QUOTA_EVICTION_TARGET) as usize;
+    let count_target = quota.max_memories.map(target);
+    let bytes_target = quota.max_bytes.map(target);
+
+    let mut candidates: Vec<Eviction> = live
+        .into_iter()
+        .filter(|m| !m.is_pinned() && protected != Some(&m.id))
+        .map(|m| Eviction {
+            memory: m.clone(),
+            score: retention_score(m, now),
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.score.total_cmp(&b.score));
+
+    let mut evictions = Vec::new();
+    for candidate in candidates {
+        let count_ok = count_target.is_none_or(|t| count <= t);
+        let bytes_ok = bytes_target.is_none_or(|t| bytes <= t);
+        if count_ok && bytes_ok {
+            break;
+        }
+        count -= 1;
+        bytes = bytes.saturating_sub(estimated_bytes(&candidate.memory));
+        evictions.push(candidate);
+    }
+    evictions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::types::{Experience, PINNED_KEY};
+    use chrono::Duration;
+    use std::sync::Arc;
+
+    fn memory(content: &str, importance: f32, idle_days: i64, pinned: bool) -> SharedMemory {
+        let mut experience = Experience {
+            content: content.to_string(),
+            ..Default::default()
+        };
+        if pinned {
+            experience
+                .metadata
+                .insert(PINNED_KEY.to_string(), "true".to_string());
+        }
+        // last_accessed starts at created_at
+        Arc::new(Memory::new(
+            MemoryId(uuid::Uuid::new_v4()),
+            experience,
+            importance,
+            None,
+            None,
+            None,
+            Some(Utc::now() - Duration::days(idle_days)),
+        ))
+    }
+
+    #[test]
+    fn test_evicts_lowest_importance_recency_never_pinned() {
+        let memories = vec![
+            memory("important and fresh", 0.9, 0, false),
+            memory("trivial but pinned", 0.1, 90, true),
+            memory("trivial and stale", 0.2, 60, false),
+            memory("middling", 0.5, 5, false),
+            memory("trivial and fresh", 0.2, 0, false),
+        ];
+        let quota = QuotaConfig {
+            max_memories: Some(3),
+            max_bytes: None,
+        };
+        let evicted = select_evictions(&memories, 0, &quota, None, Utc::now());
+        let contents: Vec<&str> = evicted
+            .iter()
+            .map(|e| e.memory.experience.content.as_str())
+            .collect();
+        // 5 memories, target 95% of 3 = 2: three go, pinned stays
+        assert_eq!(
+            contents,
+            vec!["trivial and stale", "trivial and fresh", "middling"]
+        );
+
+        // Under quota: nothing to do
+        let roomy = QuotaConfig {
+            max_memories: Some(10),
+            max_bytes: None,
+        };
+        assert!(select_evictions(&memories, 0, &roomy, None, Utc::now()).is_empty());
+    }
+
+    #[test]
+    fn test_protected_memory_is_kept() {
+        let memories = vec![
+            memory("just stored", 0.1, 0, false),
+            memory("older", 0.8, 1, false),
+        ];
+        let quota = QuotaConfig {
+            max_memories: Some(1),
+            max_bytes: None,
+        };
+        let evicted = select_evictions(&memories, 0, &quota, Some(&memories[0].id), Utc::now());
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].memory.experience.content, "older");
+    }
+
+    #[test]
+    fn test_status_usage() {
+        let quota = QuotaConfig {
+            max_memories: Some(100),
+            max_bytes: Some(1000),
+        };
+        let status = QuotaStatus::new(&quota, 50, 1500, 2);
+        assert!(status.over_quota);
+        assert_eq!(status.usage, Some(1.5));
+        assert_eq!(
+            QuotaStatus::new(&QuotaConfig::default(), 5, 5, 0).usage,
+            None
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Ranking Weights
+//!
+//! Recall's unified score (Layer 5) combines fused relevance with a recency
+//! boost, the forgetting curve and feedback momentum:
+//!
+//! ```text
+//! raw = (semantic × relevance + recency_boost + ...)
+//!       × momentum_multiplier × retention_multiplier × ...
+//! ```
+//!
+//! How much each part counts is set per tenant through /api/admin/ranking
+//! (see `crate::ranking`). The default weights match the fixed ones recall
+//! used before they were configurable, except that tier weighting is on:
+//! working memories score ×0.9 and long-term or archived ones ×1.1. Set
+//! `tiers: false` through /api/admin/ranking to rank all tiers alike.
+//!
+//! Raw scores are only comparable within one query: a top match may score
+//! 0.05 for one user and 0.3 for another. With `calibrate` on, raw scores are
+//! mapped through `raw / (raw + SCORE_CALIBRATION_PIVOT)` into 0.0-1.0, so a
+//! client's min-score threshold means the same thing for every user.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::importance::tier_recall_weight;
+use super::types::MemoryTier;
+use crate::constants::{
+    MEMORY_DECAY_SCORE_WEIGHT, RANKING_DEFAULT_RECENCY_WEIGHT, SCORE_CALIBRATION_PIVOT,
+};
+
+/// Upper bound for the multiplier weights (`semantic`, `reinforcement`)
+pub const MAX_MULTIPLIER_WEIGHT: f32 = 5.0;
+
+/// Largest boost and penalty feedback momentum applies at reinforcement 1.0
+const MOMENTUM_MAX_BOOST: f32 = 0.1;
+const MOMENTUM_MAX_PENALTY: f32 = 0.2;
+
+/// Weights of recall's unified score
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RankingWeights {
+    /// Multiplier on fused relevance (vector, BM25 and graph layers)
+    pub semantic: f32,
+    /// Recency boost at age zero, for queries that don't set their own
+    pub recency: f32,
+    /// Share of the score that depends on retention strength, which grows
+    /// with importance and use (0.0-1.0)
+    pub importance: f32,
+    /// Scale of the feedback momentum boost/penalty (0.0 = ignore feedback)
+    pub reinforcement: f32,
+    /// Map scores into 0.0-1.0 so thresholds hold across users
+    pub calibrate: bool,
+    /// Weight scores by memory tier, favouring semantic over working memories
+    /// (see `importance::tier_recall_weight`)
+    pub tiers: bool,
+}
+
+impl Default for RankingWeights {
+    fn default() -> Self {
+        Self {
+            semantic: 1.0,
+            recency: RANKING_DEFAULT_RECENCY_WEIGHT,
+            importance: MEMORY_DECAY_SCORE_WEIGHT,
+            reinforcement: 1.0,
+            calibrate: false,
+            tiers: true,
+        }
+    }
+}
+
+impl RankingWeights {
+    pub fn validate(&self) -> Result<()> {
+        for (name, value, max) in [
+            ("semantic", self.semantic, MAX_MULTIPLIER_WEIGHT),
+            ("recency", self.recency, 1.0),
+            ("importance", self.importance, 1.0),
+            ("reinforcement", self.reinforcement, MAX_MULTIPLIER_WEIGHT),
+        ] {
+            if !value.is_finite() || !(0.0..=max).contains(&value) {
+                return Err(anyhow!(
+                    "{name} must be between 0.0 and {max}, got: {value}"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Score multiplier for feedback momentum (EMA in -1.0..1.0)
+    pub fn momentum_multiplier(&self, momentum: f32) -> f32 {
+        let adjustment = if momentum < 0.0 {
+            (momentum * MOMENTUM_MAX_PENALTY).max(-MOMENTUM_MAX_PENALTY)
+        } else {
+            (momentum * MOMENTUM_MAX_BOOST).min(MOMENTUM_MAX_BOOST)
+        };
+        (1.0 + adjustment * self.reinforcement).max(0.0)
+    }
+
+    /// Score multiplier for retention strength on the forgetting curve
+    pub fn retention_multiplier(&self, strength: f32) -> f32 {
+        1.0 - self.importance * (1.0 - strength.clamp(0.0, 1.0))
+    }
+
+    /// Score multiplier for a memory's tier (1.0 when `tiers` is off)
+    pub fn tier_multiplier(&self, tier: MemoryTier) -> f32 {
+        if self.tiers {
+            tier_recall_weight(tier)
+        } else {
+            1.0
+        }
+    }
+
+    /// Final score: calibrated into 0.0-1.0 when `calibrate` is on
+    pub fn calibrated(&self, raw: f32) -> f32 {
+        if !self.calibrate {
+            return raw;
+        }
+        let raw = raw.max(0.0);
+        raw / (raw + SCORE_CALIBRATION_PIVOT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_match_fixed_weights() {
+        let weights = RankingWeights::default();
+        assert!(weights.validate().is_ok());
+        assert_eq!(weights.momentum_multiplier(0.0), 1.0);
+        assert!((weights.momentum_multiplier(1.0) - 1.1).abs() < 1e-6);
+        assert!((weights.momentum_multiplier(-1.0) - 0.8).abs() < 1e-6);
+        assert!((weights.retention_multiplier(0.0) - 0.7).abs() < 1e-6);
+        assert_eq!(weights.calibrated(0.37), 0.37);
+
+        let ignore_feedback = RankingWeights {
+            reinforcement: 0.0,
+            importance: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(ignore_feedback.momentum_multiplier(-1.0), 1.0);
+        assert_eq!(ignore_feedback.retention_multiplier(0.0), 1.0);
+
+        assert!(weights.tier_multiplier(MemoryTier::LongTerm) > 1.0);
+        let flat_tiers = RankingWeights {
+            tiers: false,
+            ..Default::default()
+        };
+        assert_eq!(flat_tiers.tier_multiplier(MemoryTier::Working), 1.0);
+    }
+
+    #[test]
+    fn test_calibration_is_bounded_and_monotonic() {
+        let weights = RankingWeights {
+            calibrate: true,
+            ..Default::default()
+        };
+        let scores: Vec<f32> = [0.0, 0.01, 0.1, 0.5, 3.0]
+            .iter()
+            .map(|raw| weights.calibrated(*raw))
+            .collect();
+        assert_eq!(scores[0], 0.0);
+        assert!((scores[2] - 0.5).abs() < 1e-6);
+        assert!(scores.windows(2).all(|w| w[0] < w[1]));
+        assert!(scores.iter().all(|s| (0.0..1.0).contains(s)));
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range() {
+        let bad = RankingWeights {
+            importance: 1.5,
+            ..Default::default()
+        };
+        assert!(bad
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("importance"));
+        let bad = RankingWeights {
+            semantic: f32::NAN,
+            ..Default::default()
+        };
+        assert!(bad.validate().is_err());
+    }
+}
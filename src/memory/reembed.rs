@@ -0,0 +1,180 @@
+//! Re-embedding a Store with a New Provider
+//!
+//! Vectors from different models aren't comparable, so moving a store to a
+//! new embedding provider or model means re-computing every memory's
+//! embedding. The job runs in two phases:
+//!
+//! 1. every memory is embedded with the new model into a [`ShadowIndex`], in
+//!    batches and without holding the memory system lock, so the store keeps
+//!    serving reads and writes with the old model meanwhile
+//! 2. under the write lock, memories written or edited during phase 1 are
+//!    caught up, the new embeddings are stored, the shadow index replaces the
+//!    live one and the store is pinned to the new provider
+//!
+//! Nothing is written before phase 2, so a failed job leaves the store on its
+//! old model. Fact, entity and todo embeddings are not rewritten.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::Serialize;
+
+use super::retrieval::ShadowIndex;
+use super::types::{Memory, MemoryId};
+use super::MemorySystem;
+use crate::embeddings::{provider, ProviderConfig};
+
+/// Memories embedded per `encode_batch` call
+const REEMBED_BATCH: usize = 64;
+
+/// New content hash and embedding per memory, from phase 1
+type Embedded = HashMap<MemoryId, ([u8; 32], Vec<f32>)>;
+
+/// Result of re-embedding one store
+#[derive(Debug, Clone, Serialize)]
+pub struct ReembedOutcome {
+    /// Model id the store is now embedded with
+    pub model: String,
+    pub reembedded: usize,
+    /// The store was already on this model
+    pub skipped: bool,
+}
+
+/// Progress of a re-embedding job across users' stores
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReembedStatus {
+    pub running: bool,
+    /// Model id stores are moving to
+    pub model: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub users_total: usize,
+    pub users_done: usize,
+    /// Progress through the current user's store
+    pub memories_done: usize,
+    pub memories_total: usize,
+    /// Memories re-embedded so far, across users
+    pub reembedded: usize,
+    /// Users whose store was already on the model
+    pub users_skipped: usize,
+    /// "user_id: reason" for stores that failed and stay on their old model
+    pub errors: Vec<String>,
+}
+
+/// Re-embed every memory in a store with `provider` and switch the store to
+/// it. `progress` is called with (embedded, total) after each batch.
+pub fn reembed(
+    memory: &RwLock<MemorySystem>,
+    provider: &ProviderConfig,
+    force: bool,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<ReembedOutcome> {
+    let (storage, ids) = {
+        let guard = memory.read();
+        if !force && guard.embedding_provider.same_model(provider) {
+            return Ok(ReembedOutcome {
+                model: guard.embedding_info().model_id(),
+                reembedded: 0,
+                skipped: true,
+            });
+        }
+        let storage = guard.long_term_memory.clone();
+        let ids = storage.get_all_ids()?;
+        (storage, ids)
+    };
+
+    let embedder =
+        provider::from_config(provider).context("Failed to initialize embedding provider")?;
+    let model = embedder.info().model_id();
+    let mut shadow = ShadowIndex::new(embedder)?;
+    let mut embedded = Embedded::with_capacity(ids.len());
+    for batch in ids.chunks(REEMBED_BATCH) {
+        // Memories deleted since the snapshot are skipped
+        let memories: Vec<Memory> = batch.iter().filter_map(|id| storage.get(id).ok()).collect();
+        let embeddings = shadow.add_batch(&memories)?;
+        for (memory, embedding) in memories.iter().zip(embeddings) {
+            let hash = MemorySystem::sha256_hash(&memory.experience.content);
+            embedded.insert(memory.id.clone(), (hash, embedding));
+        }
+        progress(embedded.len(), ids.len());
+    }
+
+    let reembedded = memory
+        .write()
+        .switch_embeddings(shadow, embedded, provider.clone())?;
+    tracing::info!(model = %model, reembedded, "Store switched to new embedding model");
+    Ok(ReembedOutcome {
+        model,
+        reembedded,
+        skipped: false,
+    })
+}
+
+impl MemorySystem {
+    /// Phase 2: catch up on changes made during phase 1, store the new
+    /// embeddings and swap the shadow index in
+    fn switch_embeddings(
+        &mut self,
+        mut shadow: ShadowIndex,
+        mut embedded: Embedded,
+        provider: ProviderConfig,
+    ) -> Result<usize> {
+        let info = shadow.embedder().info();
+        let mut count = 0;
+        for id in self.long_term_memory.get_all_ids()? {
+            let Ok(mut memory) = self.long_term_memory.get(&id) else {
+                continue;
+            };
+            let hash = Self::sha256_hash(&memory.experience.content);
+            let embedding = match embedded.remove(&id) {
+                Some((embedded_hash, embedding)) if embedded_hash == hash => embedding,
+                stale => {
+                    // Written or edited while phase 1 ran
+                    if stale.is_some() {
+                        shadow.remove(&id);
+                    }
+                    shadow
+                        .add_batch(std::slice::from_ref(&memory))?
+                        .pop()
+                        .context("Embedder returned no embedding")?
+                }
+            };
+            memory.experience.embeddings = Some(embedding);
+            info.write_to(&mut memory.experience.metadata);
+            self.long_term_memory.update(&memory)?;
+            self.refresh_cached_tiers(&memory);
+            count += 1;
+        }
+        // Whatever is left was deleted while phase 1 ran
+        for id in embedded.into_keys() {
+            shadow.remove(&id);
+        }
+
+        let embedder = shadow.embedder();
+        self.retriever.swap_in(shadow)?;
+        self.embedder = embedder;
+        self.query_cache.invalidate_all();
+        self.content_cache.invalidate_all();
+        provider.pin(&self.config.storage_path)?;
+        self.embedding_provider = provider;
+        Ok(count)
+    }
+
+    /// Replace a memory's copy in working/session memory, if it is cached there
+    fn refresh_cached_tiers(&self, memory: &Memory) {
+        {
+            let mut working = self.working_memory.write();
+            if working.contains(&memory.id) {
+                let _ = working.remove(&memory.id);
+                let _ = working.add_shared(std::sync::Arc::new(memory.clone()));
+            }
+        }
+        let mut session = self.session_memory.write();
+        if session.contains(&memory.id) {
+            let _ = session.remove(&memory.id);
+            let _ = session.add_shared(std::sync::Arc::new(memory.clone()));
+        }
+    }
+}
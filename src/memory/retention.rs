@@ -0,0 +1,256 @@
+//! Per-Type Retention Policies
+//!
+//! Memory types age very differently: a chat turn is stale in weeks, a
+//! decision stays relevant for the life of the project, and task chatter is
+//! noise once its run has been summarized. A retention policy maps memory
+//! types to a rule, and the heavy maintenance cycle deletes what has expired.
+//!
+//! Policies are configured with `SHODH_RETENTION_POLICY`:
+//!
+//! ```text
+//! conversation=30d,task=run,decision=never
+//! ```
+//!
+//! - `<N>d` - expire N days after creation
+//! - `run`  - expire once the memory's run has ended (a run summary exists)
+//! - `never` - keep forever (also the default for unlisted types)
+//!
+//! Custom memory types (see [`crate::memory::type_registry`]) can override
+//! the rule of the built-in type they are stored as.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::handlers::crud::parse_experience_type;
+use crate::memory::types::{ExperienceType, Memory, SharedMemory};
+
+/// Tag carried by run summary memories (see `MemorySystem::summarize_run`)
+const RUN_SUMMARY_TAG: &str = "run-summary";
+
+/// How long memories of one type are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionRule {
+    /// Keep forever
+    Never,
+    /// Expire this many days after creation
+    Days(u32),
+    /// Expire once the memory's run has been summarized
+    RunEnd,
+}
+
+/// Retention rules by memory type; unlisted types are kept forever
+#[derive(Debug, Clone, Default)]
+pub struct RetentionPolicy {
+    rules: Vec<(ExperienceType, RetentionRule)>,
+    /// Custom type name -> rule, taking precedence over the base type's rule
+    custom: HashMap<String, RetentionRule>,
+}
+
+impl RetentionPolicy {
+    /// Parse `type=rule` pairs separated by commas, e.g. `conversation=30d,task=run`
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut policy = Self::default();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (type_name, rule) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected type=rule, got '{entry}'"))?;
+            let memory_type = parse_experience_type(type_name.trim())
+                .map_err(|_| format!("unknown memory type '{}'", type_name.trim()))?;
+            let rule = match rule.trim().to_lowercase().as_str() {
+                "never" | "keep" => RetentionRule::Never,
+                "run" => RetentionRule::RunEnd,
+                days => days
+                    .strip_suffix('d')
+                    .and_then(|n| n.parse::<u32>().ok())
+                    .filter(|n| *n > 0)
+                    .map(RetentionRule::Days)
+                    .ok_or_else(|| format!("invalid rule '{}' for {type_name}", rule.trim()))?,
+            };
+            policy.set(memory_type, rule);
+        }
+        Ok(policy)
+    }
+
+    /// Read SHODH_RETENTION_POLICY (invalid specs are logged and ignored)
+    pub fn from_env() -> Self {
+        let Ok(spec) = std::env::var("SHODH_RETENTION_POLICY") else {
+            return Self::default();
+        };
+        Self::parse(&spec).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring invalid SHODH_RETENTION_POLICY: {}", e);
+            Self::default()
+        })
+    }
+
+    /// Set the rule for one memory type
+    pub fn set(&mut self, memory_type: ExperienceType, rule: RetentionRule) {
+        self.rules.retain(|(t, _)| *t != memory_type);
+        if rule != RetentionRule::Never {
+            self.rules.push((memory_type, rule));
+        }
+    }
+
+    /// Set the rule for a custom memory type (`Never` still overrides its base type)
+    pub fn set_custom(&mut self, custom_type: &str, rule: RetentionRule) {
+        self.custom.insert(custom_type.to_string(), rule);
+    }
+
+    /// Rule for a memory, preferring its custom type's rule
+    pub fn rule_for_memory(&self, memory: &Memory) -> RetentionRule {
+        memory
+            .custom_type()
+            .and_then(|name| self.custom.get(name))
+            .copied()
+            .unwrap_or_else(|| self.rule_for(&memory.experience.experience_type))
+    }
+
+    /// Rule for a memory type
+    pub fn rule_for(&self, memory_type: &ExperienceType) -> RetentionRule {
+        self.rules
+            .iter()
+            .find(|(t, _)| t == memory_type)
+            .map(|(_, rule)| *rule)
+            .unwrap_or(RetentionRule::Never)
+    }
+
+    /// Whether any type can expire
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty() && self.custom.values().all(|r| *r == RetentionRule::Never)
+    }
+
+    /// Configured rules, for display
+    pub fn rules(&self) -> &[(ExperienceType, RetentionRule)] {
+        &self.rules
+    }
+}
+
+/// A memory past its retention period
+#[derive(Debug, Clone)]
+pub struct ExpiredMemory {
+    pub memory: SharedMemory,
+    pub rule: RetentionRule,
+}
+
+/// Memories that have expired under `policy` at `now`
+///
+/// Run summaries themselves never expire through the `run` rule, and only
+/// memories created before their run's latest summary count as ended.
+pub fn find_expired(
+    memories: &[SharedMemory],
+    policy: &RetentionPolicy,
+    now: DateTime<Utc>,
+) -> Vec<ExpiredMemory> {
+    if policy.is_empty() {
+        return Vec::new();
+    }
+
+    let mut run_ended: HashMap<&str, DateTime<Utc>> = HashMap::new();
+    for memory in memories {
+        if !memory.experience.tags.iter().any(|t| t == RUN_SUMMARY_TAG) {
+            continue;
+        }
+        if let Some(run_id) = memory.run_id.as_deref() {
+            let ended = run_ended.entry(run_id).or_insert(memory.created_at);
+            *ended = (*ended).max(memory.created_at);
+        }
+    }
+
+    memories
+        .iter()
+        .filter(|m| !m.is_forgotten())
+        .filter_map(|m| {
+            let rule = policy.rule_for_memory(m);
+            let expired = match rule {
+                RetentionRule::Never => false,
+                RetentionRule::Days(days) => now - m.created_at >= Duration::days(days as i64),
+                RetentionRule::RunEnd => {
+                    !m.experience.tags.iter().any(|t| t == RUN_SUMMARY_TAG)
+                        && m.run_id
+                            .as_deref()
+                            .and_then(|run| run_ended.get(run))
+                            .is_some_and(|ended| m.created_at <= *ended)
+                }
+            };
+            expired.then(|| ExpiredMemory {
+                memory: m.clone(),
+                rule,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::types::{Experience, Memory, MemoryId};
+    use std::sync::Arc;
+
+    fn memory(
+        experience_type: ExperienceType,
+        age_days: i64,
+        run_id: Option<&str>,
+        tags: &[&str],
+    ) -> SharedMemory {
+        let experience = Experience {
+            content: "retention test".to_string(),
+            experience_type,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        };
+        Arc::new(Memory::new(
+            MemoryId(uuid::Uuid::new_v4()),
+            experience,
+            0.5,
+            None,
+            run_id.map(str::to_string),
+            None,
+            Some(Utc::now() - Duration::days(age_days)),
+        ))
+    }
+
+    #[test]
+    fn test_parse_policy() {
+        let policy = RetentionPolicy::parse("conversation=30d, task=run,decision=never").unwrap();
+        assert_eq!(
+            policy.rule_for(&ExperienceType::Conversation),
+            RetentionRule::Days(30)
+        );
+        assert_eq!(
+            policy.rule_for(&ExperienceType::Task),
+            RetentionRule::RunEnd
+        );
+        assert_eq!(
+            policy.rule_for(&ExperienceType::Decision),
+            RetentionRule::Never
+        );
+        assert_eq!(policy.rules().len(), 2);
+
+        assert!(RetentionPolicy::parse("conversation=soon").is_err());
+        assert!(RetentionPolicy::parse("gossip=30d").is_err());
+    }
+
+    #[test]
+    fn test_find_expired() {
+        let policy = RetentionPolicy::parse("conversation=30d,task=run").unwrap();
+        let memories = vec![
+            memory(ExperienceType::Conversation, 45, None, &[]),
+            memory(ExperienceType::Conversation, 5, None, &[]),
+            memory(ExperienceType::Decision, 400, None, &[]),
+            memory(ExperienceType::Task, 2, Some("run-a"), &[]),
+            memory(ExperienceType::Task, 1, Some("run-a"), &[RUN_SUMMARY_TAG]),
+            memory(ExperienceType::Task, 2, Some("run-b"), &[]),
+        ];
+
+        let expired: Vec<MemoryId> = find_expired(&memories, &policy, Utc::now())
+            .into_iter()
+            .map(|e| e.memory.id.clone())
+            .collect();
+        assert_eq!(
+            expired,
+            vec![memories[0].id.clone(), memories[3].id.clone()]
+        );
+    }
+}
@@ -15,15 +15,16 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::{info, warn};
 
+use super::backend::Storage;
 use super::introspection::ConsolidationEventBuffer;
-use super::storage::{MemoryStorage, SearchCriteria};
+use super::storage::SearchCriteria;
 use super::types::*;
 use crate::constants::{
     PREFETCH_RECENCY_FULL_BOOST, PREFETCH_RECENCY_FULL_HOURS, PREFETCH_RECENCY_PARTIAL_BOOST,
     PREFETCH_RECENCY_PARTIAL_HOURS, PREFETCH_TEMPORAL_WINDOW_HOURS,
     VECTOR_SEARCH_CANDIDATE_MULTIPLIER,
 };
-use crate::embeddings::{minilm::MiniLMEmbedder, Embedder};
+use crate::embeddings::EmbeddingProvider;
 use crate::vector_db::vamana::{VamanaConfig, VamanaIndex};
 
 /// Filename for persisted Vamana index (instant startup)
@@ -47,8 +48,8 @@ const VAMANA_INDEX_FILE: &str = "vamana.idx";
 /// **Note:** Memory graph (Hebbian learning) has been consolidated into GraphMemory
 /// which is managed at the API layer (MultiUserMemoryManager.graph_memories)
 pub struct RetrievalEngine {
-    storage: Arc<MemoryStorage>,
-    embedder: Arc<MiniLMEmbedder>,
+    storage: Arc<dyn Storage>,
+    embedder: Arc<dyn EmbeddingProvider>,
     /// Lock order: 1 - Acquire first
     vector_index: Arc<RwLock<VamanaIndex>>,
     /// Lock order: 2
@@ -129,6 +130,114 @@ impl IdMapping {
     }
 }
 
+/// Vamana parameters for a memory index of `dimension`-sized vectors
+///
+/// Optimized for 10M+ memories per user. Graph parameters can be tuned with
+/// SHODH_VECTOR_MAX_DEGREE, SHODH_VECTOR_BUILD_LIST and SHODH_VECTOR_SEARCH_EF.
+fn vamana_config(dimension: usize) -> VamanaConfig {
+    let env_usize = |name: &str, default: usize| {
+        std::env::var(name)
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(default)
+    };
+    VamanaConfig {
+        dimension,
+        // Increased for better recall at scale
+        max_degree: env_usize("SHODH_VECTOR_MAX_DEGREE", 32),
+        // 2x for better accuracy with 10M vectors
+        search_list_size: env_usize("SHODH_VECTOR_BUILD_LIST", 100),
+        search_ef: std::env::var("SHODH_VECTOR_SEARCH_EF")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0),
+        alpha: 1.2,
+        use_mmap: false, // Keep in memory for low-latency robotics
+        ..Default::default()
+    }
+}
+
+/// A vector index for a new embedding model, built beside the live index by a
+/// re-embedding job and swapped in by [`RetrievalEngine::swap_in`]
+pub struct ShadowIndex {
+    embedder: Arc<dyn EmbeddingProvider>,
+    vector_index: VamanaIndex,
+    id_mapping: IdMapping,
+}
+
+impl ShadowIndex {
+    pub fn new(embedder: Arc<dyn EmbeddingProvider>) -> Result<Self> {
+        let vector_index = VamanaIndex::new(vamana_config(embedder.dimension()))
+            .context("Failed to initialize shadow vector index")?;
+        Ok(Self {
+            embedder,
+            vector_index,
+            id_mapping: IdMapping::new(),
+        })
+    }
+
+    pub fn embedder(&self) -> Arc<dyn EmbeddingProvider> {
+        self.embedder.clone()
+    }
+
+    /// Embed memories with the new model and index the live ones, chunking
+    /// long content like [`RetrievalEngine::index_memory`]. Returns each
+    /// memory's new content embedding, in order.
+    pub fn add_batch(&mut self, memories: &[Memory]) -> Result<Vec<Vec<f32>>> {
+        use crate::embeddings::chunking::{chunk_text, ChunkConfig};
+
+        let contents: Vec<&str> = memories
+            .iter()
+            .map(|m| m.experience.content.as_str())
+            .collect();
+        let embeddings = self
+            .embedder
+            .encode_batch(&contents)
+            .context("Failed to embed memories")?;
+
+        let chunk_config = ChunkConfig::default();
+        for (memory, embedding) in memories.iter().zip(&embeddings) {
+            if memory.is_forgotten() {
+                continue;
+            }
+            let text = RetrievalEngine::extract_searchable_text(memory);
+            let chunk_result = chunk_text(&text, &chunk_config);
+            let vector_ids = if chunk_result.was_chunked {
+                let chunks: Vec<&str> = chunk_result.chunks.iter().map(String::as_str).collect();
+                let chunk_embeddings = self
+                    .embedder
+                    .encode_batch(&chunks)
+                    .context("Failed to embed chunks")?;
+                chunk_embeddings
+                    .into_iter()
+                    .map(|e| self.vector_index.add_vector(e))
+                    .collect::<Result<Vec<_>>>()?
+            } else {
+                vec![self.vector_index.add_vector(embedding.clone())?]
+            };
+            self.id_mapping.insert_chunks(memory.id.clone(), vector_ids);
+        }
+        Ok(embeddings)
+    }
+
+    /// Drop a memory's vectors, e.g. before re-adding it after an edit
+    pub fn remove(&mut self, memory_id: &MemoryId) {
+        for vector_id in self.id_mapping.remove_all(memory_id) {
+            self.vector_index.mark_deleted(vector_id);
+        }
+    }
+
+    /// Number of memories indexed
+    pub fn len(&self) -> usize {
+        self.id_mapping.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.id_mapping.len() == 0
+    }
+}
+
 impl RetrievalEngine {
     /// Create new retrieval engine with shared embedder (CRITICAL: embedder loaded only once)
     ///
@@ -136,7 +245,7 @@ impl RetrievalEngine {
     /// - Vector mappings are stored atomically with memories in RocksDB
     /// - Vamana index is rebuilt from RocksDB on startup (pure in-memory cache)
     /// - No more file-based IdMapping = no more orphaned memories
-    pub fn new(storage: Arc<MemoryStorage>, embedder: Arc<MiniLMEmbedder>) -> Result<Self> {
+    pub fn new(storage: Arc<dyn Storage>, embedder: Arc<dyn EmbeddingProvider>) -> Result<Self> {
         Self::with_event_buffer(storage, embedder, None)
     }
 
@@ -150,24 +259,14 @@ impl RetrievalEngine {
     ///
     /// ATOMIC STARTUP: Rebuilds Vamana from RocksDB mappings for crash safety.
     pub fn with_event_buffer(
-        storage: Arc<MemoryStorage>,
-        embedder: Arc<MiniLMEmbedder>,
+        storage: Arc<dyn Storage>,
+        embedder: Arc<dyn EmbeddingProvider>,
         consolidation_events: Option<Arc<RwLock<ConsolidationEventBuffer>>>,
     ) -> Result<Self> {
         let storage_path = storage.path().to_path_buf();
 
-        // Initialize Vamana index optimized for 10M+ memories per user
-        let vamana_config = VamanaConfig {
-            dimension: 384,        // MiniLM dimension
-            max_degree: 32,        // Increased for better recall at scale
-            search_list_size: 100, // 2x for better accuracy with 10M vectors
-            alpha: 1.2,
-            use_mmap: false, // Keep in memory for low-latency robotics
-            ..Default::default()
-        };
-
-        let vector_index =
-            VamanaIndex::new(vamana_config).context("Failed to initialize Vamana vector index")?;
+        let vector_index = VamanaIndex::new(vamana_config(embedder.dimension()))
+            .context("Failed to initialize Vamana vector index")?;
         let id_mapping = IdMapping::new();
 
         // NOTE: Memory graph (Hebbian associations) has been consolidated into GraphMemory
@@ -205,7 +304,7 @@ impl RetrievalEngine {
             .storage_path
             .join("vector_index")
             .join(VAMANA_INDEX_FILE);
-        if vamana_path.exists() {
+        if vamana_path.exists() && !super::encryption::enabled() {
             if let Ok(loaded) = self.try_load_persisted_vamana(&vamana_path) {
                 if loaded {
                     info!(
@@ -374,7 +473,8 @@ impl RetrievalEngine {
         }
 
         // Load the persisted index
-        let loaded_index = match VamanaIndex::load_from_file(vamana_path) {
+        let runtime = vamana_config(self.embedder.dimension());
+        let loaded_index = match VamanaIndex::load_from_file(vamana_path, &runtime) {
             Ok(idx) => idx,
             Err(e) => {
                 warn!("Failed to load Vamana file: {}, will rebuild", e);
@@ -453,12 +553,20 @@ impl RetrievalEngine {
     /// - .vamana file: Persisted graph for instant startup (skip rebuild)
     ///
     /// On next startup, if .vamana exists and is valid, we load it directly.
-    /// Otherwise, we fall back to rebuilding from RocksDB.
+    /// Otherwise, we fall back to rebuilding from RocksDB. With encryption at
+    /// rest on, no file is kept and startup always rebuilds.
     pub fn save(&self) -> Result<()> {
         let index_path = self.storage_path.join("vector_index");
         fs::create_dir_all(&index_path)?;
 
         let vamana_path = index_path.join(VAMANA_INDEX_FILE);
+        if super::encryption::enabled() {
+            // The index file holds embeddings in plaintext
+            if vamana_path.exists() {
+                fs::remove_file(&vamana_path)?;
+            }
+            return Ok(());
+        }
         let id_mapping = self.id_mapping.read();
         let vector_count = id_mapping.len();
 
@@ -487,6 +595,30 @@ impl RetrievalEngine {
         Ok(())
     }
 
+    /// Replace the live index and embedder with a finished shadow index,
+    /// persisting its vector mappings and the index file
+    pub fn swap_in(&mut self, shadow: ShadowIndex) -> Result<()> {
+        {
+            let mut vector_index = self.vector_index.write();
+            let mut id_mapping = self.id_mapping.write();
+            *vector_index = shadow.vector_index;
+            *id_mapping = shadow.id_mapping;
+            // Mappings of memories the shadow didn't index point into the old index
+            for (memory_id, _) in self.storage.get_all_vector_mappings()? {
+                if !id_mapping.memory_to_vectors.contains_key(&memory_id) {
+                    self.storage.delete_vector_mapping(&memory_id)?;
+                }
+            }
+            for (memory_id, vector_ids) in &id_mapping.memory_to_vectors {
+                self.storage
+                    .update_vector_mapping(memory_id, vector_ids.clone())
+                    .context("Failed to persist vector mapping to RocksDB")?;
+            }
+        }
+        self.embedder = shadow.embedder;
+        self.save()
+    }
+
     /// Get number of vectors in the index
     pub fn len(&self) -> usize {
         self.id_mapping.read().len()
@@ -767,54 +899,15 @@ impl RetrievalEngine {
                 None
             };
 
-        // Search vector index - fetch more candidates for chunk deduplication
-        let index = self.vector_index.read();
-        let results = index
-            .search(
-                &query_embedding,
-                limit * VECTOR_SEARCH_CANDIDATE_MULTIPLIER * 2,
-            )
-            .context("Vector search failed")?;
+        let mut memory_ids = self.nearest(
+            &query_embedding,
+            limit * VECTOR_SEARCH_CANDIDATE_MULTIPLIER * 2,
+        )?;
 
-        // Map vector IDs to memory IDs, deduplicating by MemoryId (keep highest similarity)
-        //
-        // CRITICAL FIX: Vamana returns DISTANCE, not similarity.
-        // For NormalizedDotProduct: distance = -dot(a,b)
-        // - Similar vectors have dot ≈ 1.0, so distance ≈ -1.0
-        // - Orthogonal vectors have dot ≈ 0.0, so distance ≈ 0.0
-        // Convert: similarity = -distance (so similarity = dot product = cosine similarity)
-        let id_mapping = self.id_mapping.read();
-        let mut best_scores: std::collections::HashMap<MemoryId, f32> =
-            std::collections::HashMap::new();
-
-        for (vector_id, distance) in results {
-            // Convert distance to similarity: similarity = -distance
-            // For NormalizedDotProduct, this gives us the actual dot product/cosine similarity
-            let similarity = -distance;
-
-            if let Some(memory_id) = id_mapping.get_memory_id(vector_id) {
-                // TEMPORAL FILTER: If episode pre-filter is active, skip memories outside episode
-                if let Some(ref candidates) = episode_candidates {
-                    if !candidates.contains(memory_id) {
-                        continue; // Skip - not in target episode
-                    }
-                }
-
-                // Keep the highest similarity for each memory (best matching chunk)
-                best_scores
-                    .entry(memory_id.clone())
-                    .and_modify(|score| {
-                        if similarity > *score {
-                            *score = similarity;
-                        }
-                    })
-                    .or_insert(similarity);
-            }
+        // TEMPORAL FILTER: If episode pre-filter is active, skip memories outside episode
+        if let Some(ref candidates) = episode_candidates {
+            memory_ids.retain(|(memory_id, _)| candidates.contains(memory_id));
         }
-
-        // Convert to vec and sort by similarity descending (highest first)
-        let mut memory_ids: Vec<(MemoryId, f32)> = best_scores.into_iter().collect();
-        memory_ids.sort_by(|a, b| b.1.total_cmp(&a.1));
         memory_ids.truncate(limit);
 
         Ok(memory_ids)
@@ -840,32 +933,47 @@ impl RetrievalEngine {
         limit: usize,
         exclude_id: Option<&MemoryId>,
     ) -> Result<Vec<(MemoryId, f32)>> {
-        // Search vector index - fetch more candidates to account for chunk deduplication
+        let mut memory_ids =
+            self.nearest(embedding, limit * VECTOR_SEARCH_CANDIDATE_MULTIPLIER * 2)?;
+        if let Some(exclude) = exclude_id {
+            memory_ids.retain(|(memory_id, _)| memory_id != exclude);
+        }
+        memory_ids.truncate(limit);
+
+        Ok(memory_ids)
+    }
+
+    /// Nearest memories to `embedding`, best chunk per memory, highest
+    /// similarity first
+    ///
+    /// Backends with their own vector index (pgvector) answer directly, so
+    /// memories written by other instances are found; otherwise the Vamana
+    /// index is searched.
+    fn nearest(&self, embedding: &[f32], candidates: usize) -> Result<Vec<(MemoryId, f32)>> {
+        if let Some(results) = self.storage.vector_search(embedding, candidates)? {
+            return Ok(results);
+        }
+
         let index = self.vector_index.read();
         let results = index
-            .search(embedding, limit * VECTOR_SEARCH_CANDIDATE_MULTIPLIER * 2)
-            .context("Vector search by embedding failed")?;
+            .search(embedding, candidates)
+            .context("Vector search failed")?;
 
         // Map vector IDs to memory IDs, deduplicating by MemoryId (keep highest similarity)
         //
         // CRITICAL FIX: Vamana returns DISTANCE, not similarity.
-        // Convert: similarity = -distance (for NormalizedDotProduct)
+        // For NormalizedDotProduct: distance = -dot(a,b)
+        // - Similar vectors have dot ≈ 1.0, so distance ≈ -1.0
+        // - Orthogonal vectors have dot ≈ 0.0, so distance ≈ 0.0
+        // Convert: similarity = -distance (so similarity = dot product = cosine similarity)
         let id_mapping = self.id_mapping.read();
         let mut best_scores: std::collections::HashMap<MemoryId, f32> =
             std::collections::HashMap::new();
 
         for (vector_id, distance) in results {
-            // Convert distance to similarity
             let similarity = -distance;
 
             if let Some(memory_id) = id_mapping.get_memory_id(vector_id) {
-                // Skip excluded ID
-                if let Some(exclude) = exclude_id {
-                    if memory_id == exclude {
-                        continue;
-                    }
-                }
-
                 // Keep the highest similarity for each memory (best matching chunk)
                 best_scores
                     .entry(memory_id.clone())
@@ -881,7 +989,6 @@ impl RetrievalEngine {
         // Convert to vec and sort by similarity descending (highest first)
         let mut memory_ids: Vec<(MemoryId, f32)> = best_scores.into_iter().collect();
         memory_ids.sort_by(|a, b| b.1.total_cmp(&a.1));
-        memory_ids.truncate(limit);
 
         Ok(memory_ids)
     }
@@ -904,7 +1011,7 @@ impl RetrievalEngine {
         Ok(results)
     }
 
-    /// PRODUCTION: Similarity search using Vamana graph-based ANN (sub-millisecond, zero-copy)
+    /// PRODUCTION: Similarity search using graph-based ANN (sub-millisecond, zero-copy)
     fn similarity_search(&self, query: &Query, limit: usize) -> Result<Vec<SharedMemory>> {
         // BUG-006 FIX: Log warning for empty queries
         let query_embedding = if let Some(embedding) = &query.query_embedding {
@@ -920,25 +1027,17 @@ impl RetrievalEngine {
             return Ok(Vec::new());
         };
 
-        // Search Vamana index with candidate multiplier for filtering headroom
-        let index = self.vector_index.read();
-        let results = index
-            .search(&query_embedding, limit * VECTOR_SEARCH_CANDIDATE_MULTIPLIER)
-            .context("Vector search failed")?;
+        // Search with candidate multiplier for filtering headroom
+        let results = self.nearest(&query_embedding, limit * VECTOR_SEARCH_CANDIDATE_MULTIPLIER)?;
 
-        // Map vector IDs to memory IDs and fetch memories
-        let id_mapping = self.id_mapping.read();
         let mut memories = Vec::new();
-
-        for (vector_id, _distance) in results {
-            if let Some(memory_id) = id_mapping.get_memory_id(vector_id) {
-                if let Ok(memory) = self.storage.get(memory_id) {
-                    let shared_memory = Arc::new(memory);
-                    if self.matches_filters(&shared_memory, query) {
-                        memories.push(shared_memory);
-                        if memories.len() >= limit {
-                            break;
-                        }
+        for (memory_id, _similarity) in results {
+            if let Ok(memory) = self.storage.get(&memory_id) {
+                let shared_memory = Arc::new(memory);
+                if self.matches_filters(&shared_memory, query) {
+                    memories.push(shared_memory);
+                    if memories.len() >= limit {
+                        break;
                     }
                 }
             }
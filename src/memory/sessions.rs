@@ -9,6 +9,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::memory::RetrievalOutcome;
+
 /// Time of day classification
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -352,6 +354,32 @@ impl SessionStats {
     }
 }
 
+/// Session metadata key: timeline length already covered by a summary memory
+const SUMMARIZED_UPTO_KEY: &str = "summarized_upto";
+
+/// Session metadata key: failed commands awaiting a fix (command -> Error memory ID)
+const OPEN_FAILURES_KEY: &str = "open_failures";
+
+/// Session metadata key: failed commands later fixed within the session
+const RESOLVED_FAILURES_KEY: &str = "resolved_failures";
+
+/// Retrospective reinforcement strength for a session's surfaced memories.
+/// Kept well below a direct signal since it is spread over every memory
+/// surfaced in the session, used or not.
+const RETROSPECTIVE_BASE_STRENGTH: f32 = 0.25;
+const RETROSPECTIVE_MAX_STRENGTH: f32 = 0.75;
+
+/// How often a memory has been proactively injected within a session
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InjectionHistory {
+    /// Consecutive injection turns ending at `last_turn`
+    pub streak: u32,
+    /// Total injections this session
+    pub total: u32,
+    /// Injection turn of the most recent injection
+    pub last_turn: u64,
+}
+
 /// A user session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
@@ -377,6 +405,12 @@ pub struct Session {
     pub label: Option<String>,
     /// Metadata
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Proactive-injection turns seen this session
+    #[serde(default)]
+    pub injection_turn: u64,
+    /// Injection history by memory ID (drives repeat suppression)
+    #[serde(default)]
+    pub injected: HashMap<String, InjectionHistory>,
 }
 
 impl Session {
@@ -394,6 +428,8 @@ impl Session {
             timeline: vec![SessionEvent::SessionStart { timestamp: now }],
             label: None,
             metadata: HashMap::new(),
+            injection_turn: 0,
+            injected: HashMap::new(),
         }
     }
 
@@ -411,6 +447,8 @@ impl Session {
             timeline: vec![SessionEvent::SessionStart { timestamp: now }],
             label: None,
             metadata: HashMap::new(),
+            injection_turn: 0,
+            injected: HashMap::new(),
         }
     }
 
@@ -459,6 +497,138 @@ impl Session {
         self.timeline.push(event);
     }
 
+    /// Record one proactive-injection turn and the memories injected in it
+    pub fn record_injection_turn(&mut self, memory_ids: &[String]) {
+        self.injection_turn += 1;
+        let turn = self.injection_turn;
+        for id in memory_ids {
+            let history = self.injected.entry(id.clone()).or_default();
+            history.streak = if history.total > 0 && history.last_turn + 1 == turn {
+                history.streak + 1
+            } else {
+                1
+            };
+            history.total += 1;
+            history.last_turn = turn;
+        }
+    }
+
+    /// Priority multiplier for re-injecting a memory on the next turn
+    ///
+    /// `decay^streak` while the memory was injected within the last `window`
+    /// turns, so a memory surfaced on several consecutive turns fades out
+    /// instead of being repeated. Returns 1.0 outside the window (or if
+    /// `window` is 0).
+    pub fn injection_priority(&self, memory_id: &str, window: u64, decay: f32) -> f32 {
+        if window == 0 {
+            return 1.0;
+        }
+        match self.injected.get(memory_id) {
+            Some(history) if self.injection_turn - history.last_turn < window => {
+                decay.clamp(0.0, 1.0).powi(history.streak as i32)
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Timeline events not yet covered by a session summary, marking them covered
+    pub fn take_unsummarized_events(&mut self) -> Vec<SessionEvent> {
+        let from = self
+            .metadata
+            .get(SUMMARIZED_UPTO_KEY)
+            .and_then(|v| v.as_u64())
+            .map(|n| (n as usize).min(self.timeline.len()))
+            .unwrap_or(0);
+        self.metadata.insert(
+            SUMMARIZED_UPTO_KEY.to_string(),
+            serde_json::Value::from(self.timeline.len()),
+        );
+        self.timeline[from..].to_vec()
+    }
+
+    /// Remember that `command` failed and was stored as Error memory `memory_id`
+    pub fn record_open_failure(&mut self, command: &str, memory_id: &str) {
+        let failures = self
+            .metadata
+            .entry(OPEN_FAILURES_KEY.to_string())
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        if let Some(map) = failures.as_object_mut() {
+            map.insert(command.to_string(), serde_json::Value::from(memory_id));
+        }
+    }
+
+    /// Error memory ID of an earlier failure of `command`, clearing it
+    pub fn take_open_failure(&mut self, command: &str) -> Option<String> {
+        let memory_id = self
+            .metadata
+            .get_mut(OPEN_FAILURES_KEY)?
+            .as_object_mut()?
+            .remove(command)?
+            .as_str()
+            .map(String::from)?;
+        let resolved = self.metadata_count(RESOLVED_FAILURES_KEY) + 1;
+        self.metadata.insert(
+            RESOLVED_FAILURES_KEY.to_string(),
+            serde_json::Value::from(resolved),
+        );
+        Some(memory_id)
+    }
+
+    fn metadata_count(&self, key: &str) -> u64 {
+        match self.metadata.get(key) {
+            Some(serde_json::Value::Number(n)) => n.as_u64().unwrap_or(0),
+            Some(serde_json::Value::Object(map)) => map.len() as u64,
+            _ => 0,
+        }
+    }
+
+    /// Judge the whole session for retrospective reinforcement of the memories
+    /// surfaced in it: (unique surfaced memory IDs, outcome, strength).
+    ///
+    /// Progress (fixed failures, completed todos) with nothing left broken is
+    /// Helpful; an abandoned session with unresolved errors and no progress is
+    /// Misleading. Mixed or quiet sessions give no signal (`None`).
+    pub fn retrospective(&self) -> Option<(Vec<String>, RetrievalOutcome, f32)> {
+        let mut surfaced: Vec<String> = Vec::new();
+        let mut errors_created = 0u64;
+        for event in &self.timeline {
+            match event {
+                SessionEvent::MemoriesSurfaced { memory_ids, .. } => {
+                    for id in memory_ids {
+                        if !surfaced.contains(id) {
+                            surfaced.push(id.clone());
+                        }
+                    }
+                }
+                SessionEvent::MemoryCreated { memory_type, .. } if memory_type == "Error" => {
+                    errors_created += 1;
+                }
+                _ => {}
+            }
+        }
+        if surfaced.is_empty() {
+            return None;
+        }
+
+        let progress =
+            self.metadata_count(RESOLVED_FAILURES_KEY) + self.stats.todos_completed as u64;
+        let unresolved = self.metadata_count(OPEN_FAILURES_KEY) + errors_created;
+
+        if progress > 0 && unresolved == 0 {
+            let strength =
+                (RETROSPECTIVE_BASE_STRENGTH * progress as f32).min(RETROSPECTIVE_MAX_STRENGTH);
+            Some((surfaced, RetrievalOutcome::Helpful, strength))
+        } else if progress == 0 && unresolved > 0 && self.status == SessionStatus::Abandoned {
+            Some((
+                surfaced,
+                RetrievalOutcome::Misleading,
+                RETROSPECTIVE_BASE_STRENGTH,
+            ))
+        } else {
+            None
+        }
+    }
+
     /// End the session
     pub fn end(&mut self, reason: &str) {
         let now = Utc::now();
@@ -573,19 +743,32 @@ impl SessionStore {
         session_id
     }
 
+    /// Active session of a user, if any
+    pub fn active_session(&self, user_id: &str) -> Option<SessionId> {
+        self.active
+            .read()
+            .iter()
+            .find(|(_, session)| session.user_id == user_id && session.is_active())
+            .map(|(id, _)| id.clone())
+    }
+
     /// Get or create active session for user
+    ///
+    /// Checked and created under one write lock, so concurrent callers share
+    /// a single session.
     pub fn get_or_create_session(&self, user_id: &str) -> SessionId {
-        // Check if user has an active session
+        let mut active = self.active.write();
+        if let Some((id, _)) = active
+            .iter()
+            .find(|(_, session)| session.user_id == user_id && session.is_active())
         {
-            let active = self.active.read();
-            for (id, session) in active.iter() {
-                if session.user_id == user_id && session.is_active() {
-                    return id.clone();
-                }
-            }
+            return id.clone();
         }
         // No active session, create one
-        self.start_session(user_id)
+        let session = Session::new(user_id.to_string());
+        let id = session.id.clone();
+        active.insert(id.clone(), session);
+        id
     }
 
     /// Add event to a session
@@ -611,6 +794,64 @@ impl SessionStore {
         None
     }
 
+    /// Record a proactive-injection turn on a session (see [`Session::record_injection_turn`])
+    pub fn record_injection_turn(&self, session_id: &SessionId, memory_ids: &[String]) -> bool {
+        let mut active = self.active.write();
+        if let Some(session) = active.get_mut(session_id) {
+            session.record_injection_turn(memory_ids);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Temporal label and not-yet-summarized events of an active session
+    pub fn take_unsummarized_events(
+        &self,
+        session_id: &SessionId,
+    ) -> Option<(String, Vec<SessionEvent>)> {
+        let mut active = self.active.write();
+        let session = active.get_mut(session_id)?;
+        let events = session.take_unsummarized_events();
+        Some((session.temporal.label.clone(), events))
+    }
+
+    /// Record a failed command on a session (see [`Session::record_open_failure`])
+    pub fn record_open_failure(&self, session_id: &SessionId, command: &str, memory_id: &str) {
+        if let Some(session) = self.active.write().get_mut(session_id) {
+            session.record_open_failure(command, memory_id);
+        }
+    }
+
+    /// Error memory ID of an unresolved failure of `command` in a session, clearing it
+    pub fn take_open_failure(&self, session_id: &SessionId, command: &str) -> Option<String> {
+        self.active
+            .write()
+            .get_mut(session_id)?
+            .take_open_failure(command)
+    }
+
+    /// Suppression multipliers (< 1.0) for recently injected memories of a session
+    pub fn injection_priorities(
+        &self,
+        session_id: &SessionId,
+        window: u64,
+        decay: f32,
+    ) -> HashMap<String, f32> {
+        let active = self.active.read();
+        let Some(session) = active.get(session_id) else {
+            return HashMap::new();
+        };
+        session
+            .injected
+            .keys()
+            .filter_map(|id| {
+                let priority = session.injection_priority(id, window, decay);
+                (priority < 1.0).then(|| (id.clone(), priority))
+            })
+            .collect()
+    }
+
     /// End a session
     pub fn end_session(&self, session_id: &SessionId, reason: &str) -> Option<Session> {
         let mut active = self.active.write();
@@ -636,6 +877,23 @@ impl SessionStore {
         }
     }
 
+    /// Drop all active and completed sessions for a user, returning how many
+    /// were removed
+    pub fn remove_user(&self, user_id: &str) -> usize {
+        let mut removed = 0;
+        self.active.write().retain(|_, session| {
+            let keep = session.user_id != user_id;
+            if !keep {
+                removed += 1;
+            }
+            keep
+        });
+        if let Some(sessions) = self.completed.write().remove(user_id) {
+            removed += sessions.len();
+        }
+        removed
+    }
+
     /// Get session by ID
     pub fn get_session(&self, session_id: &SessionId) -> Option<Session> {
         // Check active first
@@ -697,8 +955,8 @@ impl SessionStore {
         None
     }
 
-    /// Cleanup stale sessions
-    pub fn cleanup_stale_sessions(&self) -> usize {
+    /// End sessions past the timeout, returning the ended sessions
+    pub fn cleanup_stale_sessions(&self) -> Vec<Session> {
         let now = Utc::now();
         let timeout = Duration::seconds(self.timeout_secs);
 
@@ -711,11 +969,10 @@ impl SessionStore {
                 .collect()
         };
 
-        let count = stale_ids.len();
-        for id in stale_ids {
-            self.end_session(&id, "timeout");
-        }
-        count
+        stale_ids
+            .iter()
+            .filter_map(|id| self.end_session(id, "timeout"))
+            .collect()
     }
 
     /// Get store statistics
@@ -833,6 +1090,82 @@ mod tests {
         assert!((session.stats.memory_hit_rate - 0.3).abs() < 0.01);
     }
 
+    #[test]
+    fn test_open_failures() {
+        let mut session = Session::new("user".to_string());
+        assert!(session.take_open_failure("cargo build").is_none());
+
+        session.record_open_failure("cargo build", "err-1");
+        session.record_open_failure("cargo build", "err-2");
+        assert_eq!(
+            session.take_open_failure("cargo build").as_deref(),
+            Some("err-2")
+        );
+        assert!(session.take_open_failure("cargo build").is_none());
+    }
+
+    #[test]
+    fn test_session_retrospective() {
+        let surfaced = |ids: &[&str]| SessionEvent::MemoriesSurfaced {
+            timestamp: Utc::now(),
+            query_preview: "why does the build fail".to_string(),
+            memory_count: ids.len(),
+            memory_ids: ids.iter().map(|s| s.to_string()).collect(),
+            avg_score: 0.7,
+        };
+
+        let mut session = Session::new("user".to_string());
+        session.record_open_failure("cargo build", "err-1");
+        assert!(session.retrospective().is_none());
+
+        // Surfaced memories, then the failure got fixed
+        session.add_event(surfaced(&["m1", "m2"]));
+        session.add_event(surfaced(&["m2"]));
+        session.take_open_failure("cargo build");
+        let (ids, outcome, strength) = session.retrospective().unwrap();
+        assert_eq!(ids, vec!["m1".to_string(), "m2".to_string()]);
+        assert!(matches!(outcome, RetrievalOutcome::Helpful));
+        assert!((strength - RETROSPECTIVE_BASE_STRENGTH).abs() < f32::EPSILON);
+
+        // Abandoned with the failure still open
+        let mut session = Session::new("user".to_string());
+        session.add_event(surfaced(&["m1"]));
+        session.record_open_failure("cargo test", "err-2");
+        assert!(session.retrospective().is_none());
+        session.end("timeout");
+        let (_, outcome, _) = session.retrospective().unwrap();
+        assert!(matches!(outcome, RetrievalOutcome::Misleading));
+    }
+
+    #[test]
+    fn test_injection_suppression() {
+        let store = SessionStore::new();
+        let session_id = store.start_session("test-user");
+        let ids = vec!["mem-1".to_string()];
+
+        // Injected on three consecutive turns: priority decays each time
+        store.record_injection_turn(&session_id, &ids);
+        store.record_injection_turn(&session_id, &ids);
+        store.record_injection_turn(&session_id, &ids);
+        let priorities = store.injection_priorities(&session_id, 5, 0.5);
+        assert!((priorities["mem-1"] - 0.125).abs() < 1e-6);
+
+        // Once outside the window, the memory is back at full priority
+        for _ in 0..5 {
+            store.record_injection_turn(&session_id, &[]);
+        }
+        assert!(store.injection_priorities(&session_id, 5, 0.5).is_empty());
+
+        // Re-injection after a gap restarts the streak
+        store.record_injection_turn(&session_id, &ids);
+        let session = store.get_session(&session_id).unwrap();
+        assert_eq!(session.injected["mem-1"].streak, 1);
+        assert_eq!(session.injected["mem-1"].total, 4);
+
+        // Window 0 disables suppression
+        assert!(store.injection_priorities(&session_id, 0, 0.5).is_empty());
+    }
+
     #[test]
     fn test_get_or_create() {
         let store = SessionStore::new();
@@ -847,6 +1180,11 @@ mod tests {
         // Different user gets different session
         let id3 = store.get_or_create_session("user-2");
         assert_ne!(id1, id3);
+
+        // Looking up an active session never creates one
+        assert_eq!(store.active_session("user-1"), Some(id1));
+        assert_eq!(store.active_session("user-3"), None);
+        assert_eq!(store.active_session("user-3"), None);
     }
 
     #[test]
@@ -0,0 +1,103 @@
+//! Spaced-Repetition Resurfacing
+//!
+//! Important memories that nobody retrieves fade on the forgetting curve even
+//! when they are still true. This module schedules them for review at
+//! expanding intervals (Pimsleur/Leitner style): each review is a retrieval,
+//! which bumps the access count, which doubles the next interval.
+//!
+//! The schedule is derived from `last_accessed` and `access_count`, so it needs
+//! no extra persisted state:
+//!
+//! ```text
+//! interval = REVIEW_BASE_INTERVAL_DAYS × REVIEW_INTERVAL_MULTIPLIER^access_count
+//! due_at   = last_accessed + min(interval, REVIEW_MAX_INTERVAL_DAYS)
+//! ```
+//!
+//! The queue is rebuilt on heavy maintenance cycles and read by
+//! proactive_context; GET /api/review computes it fresh.
+//!
+//! Reference: Pimsleur (1967) "A Memory Schedule", Leitner (1972)
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::constants::{
+    REVIEW_BASE_INTERVAL_DAYS, REVIEW_INTERVAL_MULTIPLIER, REVIEW_MAX_INTERVAL_DAYS,
+    REVIEW_MIN_IMPORTANCE,
+};
+use crate::memory::types::{Memory, SharedMemory};
+
+/// Days between the last access and the next review
+pub fn review_interval_days(access_count: u32) -> f64 {
+    let exponent = access_count.min(16) as i32;
+    (REVIEW_BASE_INTERVAL_DAYS * REVIEW_INTERVAL_MULTIPLIER.powi(exponent))
+        .min(REVIEW_MAX_INTERVAL_DAYS)
+}
+
+/// When a memory is next due for review
+pub fn next_review_at(memory: &Memory) -> DateTime<Utc> {
+    let interval = review_interval_days(memory.access_count());
+    memory.last_accessed() + Duration::seconds((interval * 86_400.0) as i64)
+}
+
+/// A memory due for review
+#[derive(Debug, Clone)]
+pub struct ReviewItem {
+    pub memory: SharedMemory,
+    pub due_at: DateTime<Utc>,
+    /// Current interval in days (doubles with each review)
+    pub interval_days: f64,
+    /// Retention strength on the forgetting curve (lower = closer to forgotten)
+    pub strength: f32,
+    /// Review order: importance × how far the memory has faded
+    pub priority: f32,
+}
+
+/// Memories due for review at `now`, most urgent first
+///
+/// Only memories of at least `REVIEW_MIN_IMPORTANCE` that are not forgotten
+/// qualify; trivia is allowed to fade.
+pub fn build_review_queue(
+    memories: &[SharedMemory],
+    now: DateTime<Utc>,
+    limit: usize,
+) -> Vec<ReviewItem> {
+    let mut queue: Vec<ReviewItem> = memories
+        .iter()
+        .filter(|m| m.importance() >= REVIEW_MIN_IMPORTANCE && !m.is_forgotten())
+        .filter_map(|m| {
+            let due_at = next_review_at(m);
+            if due_at > now {
+                return None;
+            }
+            let strength = m.retention_strength(now);
+            Some(ReviewItem {
+                memory: m.clone(),
+                due_at,
+                interval_days: review_interval_days(m.access_count()),
+                strength,
+                priority: m.importance() * (1.0 - strength),
+            })
+        })
+        .collect();
+    queue.sort_by(|a, b| {
+        b.priority
+            .total_cmp(&a.priority)
+            .then_with(|| a.due_at.cmp(&b.due_at))
+    });
+    queue.truncate(limit);
+    queue
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intervals_expand_and_cap() {
+        let first = review_interval_days(0);
+        let second = review_interval_days(1);
+        assert!((first - REVIEW_BASE_INTERVAL_DAYS).abs() < f64::EPSILON);
+        assert!((second - first * REVIEW_INTERVAL_MULTIPLIER).abs() < 1e-9);
+        assert_eq!(review_interval_days(1_000), REVIEW_MAX_INTERVAL_DAYS);
+    }
+}
@@ -7,9 +7,12 @@ use rocksdb::{
     ColumnFamily, ColumnFamilyDescriptor, IteratorMode, Options, WriteBatch, WriteOptions, DB,
 };
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use super::blob_compression::{self, Codec};
+use super::encryption::{self, Keyring};
 use super::types::*;
 
 /// Helper trait to safely iterate over RocksDB results with error logging.
@@ -951,6 +954,9 @@ fn crc32_simple(data: &[u8]) -> u32 {
 /// Column family name for secondary indices (tags, types, timestamps, etc.)
 const CF_INDEX: &str = "memory_index";
 
+/// Key prefix for zstd dictionaries in the default CF (`zstd_dict:{id}`)
+const DICTIONARY_PREFIX: &str = "zstd_dict:";
+
 /// Storage engine for long-term memory persistence
 ///
 /// Uses a single RocksDB instance with 2 column families:
@@ -962,6 +968,10 @@ pub struct MemoryStorage {
     storage_path: PathBuf,
     /// Write mode (sync vs async) - affects latency vs durability tradeoff
     write_mode: WriteMode,
+    /// Seals memory records at rest when a key is configured
+    keyring: Option<Arc<Keyring>>,
+    /// Compresses memory records, with the store's trained dictionary
+    codec: Codec,
 }
 
 impl MemoryStorage {
@@ -1045,11 +1055,146 @@ impl MemoryStorage {
             }
         );
 
-        Ok(Self {
+        let storage = Self {
             db,
             storage_path: path.to_path_buf(),
             write_mode,
-        })
+            keyring: encryption::keyring()?,
+            codec: Codec::from_env(),
+        };
+        storage.load_dictionaries()?;
+        Ok(storage)
+    }
+
+    /// Serialize a memory record, compressed and then sealed when encryption is on
+    fn encode_memory(&self, memory: &Memory) -> Result<Vec<u8>> {
+        let value = bincode::serde::encode_to_vec(memory, bincode::config::standard())
+            .context(format!("Failed to serialize memory {}", memory.id.0))?;
+        let value = self.codec.compress(value)?;
+        match &self.keyring {
+            Some(keyring) => keyring.seal(memory.id.0.as_bytes(), &value),
+            None => Ok(value),
+        }
+    }
+
+    /// Open and decompress a stored record into its bincode bytes. The flag
+    /// is true when the record should be re-sealed.
+    fn open_record<'a>(&self, key: &[u8], value: &'a [u8]) -> Result<(Cow<'a, [u8]>, bool)> {
+        let (opened, reseal) = encryption::open(self.keyring.as_deref(), key, value)?;
+        let plain = match opened {
+            Cow::Borrowed(value) => self.codec.decompress(value)?,
+            Cow::Owned(value) if blob_compression::is_compressed(&value) => {
+                Cow::Owned(self.codec.decompress(&value)?.into_owned())
+            }
+            owned => owned,
+        };
+        Ok((plain, reseal))
+    }
+
+    /// Deserialize a memory record. The flag is true when the record should
+    /// be rewritten: legacy format, plaintext while encryption is on, or
+    /// sealed with a previous key.
+    fn decode_memory(&self, key: &[u8], value: &[u8]) -> Result<(Memory, bool)> {
+        let (plaintext, reseal) = self.open_record(key, value)?;
+        let (memory, needs_migration) = deserialize_memory(&plaintext)?;
+        Ok((memory, needs_migration || reseal))
+    }
+
+    /// Register the store's zstd dictionaries with the codec
+    fn load_dictionaries(&self) -> Result<()> {
+        let prefix = DICTIONARY_PREFIX.as_bytes();
+        for (key, value) in self.db.prefix_iterator(prefix).log_errors() {
+            if !key.starts_with(prefix) {
+                break;
+            }
+            let id = std::str::from_utf8(&key[prefix.len()..])
+                .ok()
+                .and_then(|id| id.parse::<u32>().ok())
+                .ok_or_else(|| anyhow!("Invalid dictionary key {:?}", key))?;
+            let (raw, _) = encryption::open(self.keyring.as_deref(), &key, &value)
+                .with_context(|| format!("Failed to open zstd dictionary {id}"))?;
+            self.codec.add_dictionary(id, &raw);
+        }
+        Ok(())
+    }
+
+    /// Train a zstd dictionary on this store's records and recompress them
+    ///
+    /// Does nothing once the store has a dictionary, or until it holds
+    /// enough records to train one. Returns the number of records rewritten.
+    pub fn train_compression(&self) -> Result<usize> {
+        if !self.codec.enabled() || self.codec.current_dictionary() != 0 {
+            return Ok(0);
+        }
+
+        let mut samples = Vec::new();
+        for (key, value) in self.db.iterator(IteratorMode::Start).log_errors() {
+            if key.len() != 16 {
+                continue;
+            }
+            if let Ok((plain, _)) = self.open_record(&key, &value) {
+                samples.push(plain.into_owned());
+            }
+            if samples.len() >= blob_compression::MAX_TRAINING_SAMPLES {
+                break;
+            }
+        }
+        if samples.len() < blob_compression::MIN_TRAINING_SAMPLES {
+            return Ok(0);
+        }
+
+        let raw = blob_compression::train_dictionary(&samples)?;
+        let id = 1;
+        let key = format!("{DICTIONARY_PREFIX}{id}");
+        let value = match &self.keyring {
+            Some(keyring) => keyring.seal(key.as_bytes(), &raw)?,
+            None => raw.clone(),
+        };
+        let mut write_opts = WriteOptions::default();
+        write_opts.set_sync(true);
+        self.db.put_opt(key.as_bytes(), &value, &write_opts)?;
+        self.codec.add_dictionary(id, &raw);
+        tracing::info!(
+            samples = samples.len(),
+            "Trained zstd dictionary for {}",
+            self.storage_path.display()
+        );
+
+        self.recompress()
+    }
+
+    /// Rewrite records that are uncompressed or compressed with an older
+    /// dictionary. Returns the number rewritten.
+    fn recompress(&self) -> Result<usize> {
+        let mut batch = WriteBatch::default();
+        let mut rewritten = 0;
+        for (key, value) in self.db.iterator(IteratorMode::Start).log_errors() {
+            if key.len() != 16 {
+                continue;
+            }
+            let stale = encryption::open(self.keyring.as_deref(), &key, &value)
+                .is_ok_and(|(opened, _)| self.codec.is_stale(&opened));
+            if !stale {
+                continue;
+            }
+            match self.decode_memory(&key, &value) {
+                Ok((memory, _)) => {
+                    batch.put(&key, self.encode_memory(&memory)?);
+                    rewritten += 1;
+                }
+                Err(e) => tracing::warn!("Skipping unreadable record during recompression: {e}"),
+            }
+            if batch.len() >= 1000 {
+                self.db.write(std::mem::take(&mut batch))?;
+            }
+        }
+        if !batch.is_empty() {
+            self.db.write(batch)?;
+        }
+        if rewritten > 0 {
+            self.flush()?;
+        }
+        Ok(rewritten)
     }
 
     /// Open a RocksDB database with column families, automatically repairing if corruption is detected.
@@ -1217,8 +1362,7 @@ impl MemoryStorage {
         let key = memory.id.0.as_bytes();
 
         // Serialize memory
-        let value = bincode::serde::encode_to_vec(memory, bincode::config::standard())
-            .context(format!("Failed to serialize memory {}", memory.id.0))?;
+        let value = self.encode_memory(memory)?;
 
         // Use write mode based on configuration
         let mut write_opts = WriteOptions::default();
@@ -1265,6 +1409,12 @@ impl MemoryStorage {
         );
         batch.put_cf(idx, date_key.as_bytes(), b"1");
 
+        // Index by event date (dates the memory is about), separate from ingest date
+        for date in memory.event_dates() {
+            let event_key = format!("event:{}:{}", date.format("%Y%m%d"), memory.id.0);
+            batch.put_cf(idx, event_key.as_bytes(), b"1");
+        }
+
         // Index by type
         let type_key = format!(
             "type:{:?}:{}",
@@ -1357,6 +1507,12 @@ impl MemoryStorage {
             batch.put_cf(idx, external_key.as_bytes(), memory.id.0.as_bytes());
         }
 
+        // === Content Hash Index ===
+        // Key format: content:{hash}:{memory_id} -> 1
+        // Lets ingest recognize identical content without scanning
+        let content_key = self.content_index_key(memory);
+        batch.put_cf(idx, content_key.as_bytes(), b"1");
+
         // === Hierarchy Index ===
         // Index by parent_id for tree queries (list children, build tree)
         // Key format: parent:{parent_id}:{child_id} -> 1
@@ -1381,13 +1537,14 @@ impl MemoryStorage {
         let key = id.0.as_bytes();
         match self.db.get(key)? {
             Some(value) => {
-                let (memory, needs_migration) = deserialize_memory(&value).with_context(|| {
-                    format!(
-                        "Failed to deserialize memory {} ({} bytes)",
-                        id.0,
-                        value.len()
-                    )
-                })?;
+                let (memory, needs_migration) =
+                    self.decode_memory(key, &value).with_context(|| {
+                        format!(
+                            "Failed to deserialize memory {} ({} bytes)",
+                            id.0,
+                            value.len()
+                        )
+                    })?;
 
                 // Lazy migration: re-write legacy formats in current format
                 if needs_migration {
@@ -1406,7 +1563,8 @@ impl MemoryStorage {
     /// Re-write a memory in current format (lazy migration helper)
     fn migrate_memory_format(&self, memory: &Memory) -> Result<()> {
         let key = memory.id.0.as_bytes();
-        let value = bincode::serde::encode_to_vec(memory, bincode::config::standard())
+        let value = self
+            .encode_memory(memory)
             .context("Failed to serialize for migration")?;
 
         let mut write_opts = WriteOptions::default();
@@ -1446,6 +1604,46 @@ impl MemoryStorage {
         Ok(None)
     }
 
+    fn content_index_key(&self, memory: &Memory) -> String {
+        let hash = encryption::content_index_hash(
+            self.keyring.as_deref(),
+            &super::types::content_hash(&memory.experience.content),
+        );
+        format!("content:{}:{}", hash, memory.id.0)
+    }
+
+    /// Find a live memory whose content has this [`super::types::content_hash`]
+    ///
+    /// Memories stored before the content index existed are not found.
+    pub fn find_by_content_hash(&self, hash: &str) -> Result<Option<Memory>> {
+        // Index key format: content:{hash}:{memory_id}
+        let prefix = format!(
+            "content:{}:",
+            encryption::content_index_hash(self.keyring.as_deref(), hash)
+        );
+
+        let iter = self.db.iterator_cf(
+            self.index_cf(),
+            IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward),
+        );
+
+        for (key, _value) in iter.log_errors() {
+            let key_str = String::from_utf8_lossy(&key);
+            let Some(id_str) = key_str.strip_prefix(&prefix) else {
+                break;
+            };
+            if let Ok(uuid) = uuid::Uuid::parse_str(id_str) {
+                match self.get(&MemoryId(uuid)) {
+                    Ok(memory) if !memory.is_forgotten() => return Ok(Some(memory)),
+                    Ok(_) => {}
+                    Err(e) => tracing::debug!("Stale content index entry {}: {}", id_str, e),
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Update an existing memory
     ///
     /// ALGO-004 FIX: Re-indexes memory after update to handle importance drift.
@@ -1500,6 +1698,12 @@ impl MemoryStorage {
         let date_key = format!("date:{}:{}", memory.created_at.format("%Y%m%d"), id.0);
         batch.delete_cf(idx, date_key.as_bytes());
 
+        // Event date index
+        for date in memory.event_dates() {
+            let event_key = format!("event:{}:{}", date.format("%Y%m%d"), id.0);
+            batch.delete_cf(idx, event_key.as_bytes());
+        }
+
         // Type index
         let type_key = format!("type:{:?}:{}", memory.experience.experience_type, id.0);
         batch.delete_cf(idx, type_key.as_bytes());
@@ -1575,6 +1779,10 @@ impl MemoryStorage {
             batch.delete_cf(idx, external_key.as_bytes());
         }
 
+        // Content hash index
+        let content_key = self.content_index_key(&memory);
+        batch.delete_cf(idx, content_key.as_bytes());
+
         // Parent index (hierarchy)
         if let Some(ref parent_id) = memory.parent_id {
             let parent_key = format!("parent:{}:{}", parent_id.0, id.0);
@@ -1597,6 +1805,9 @@ impl MemoryStorage {
             SearchCriteria::ByDate { start, end } => {
                 memory_ids = self.search_by_date_range(start, end)?;
             }
+            SearchCriteria::ByEventDate { start, end } => {
+                memory_ids = self.search_by_date_index("event", start, end)?;
+            }
             SearchCriteria::ByType(exp_type) => {
                 memory_ids = self.search_by_type(exp_type)?;
             }
@@ -1694,12 +1905,23 @@ impl MemoryStorage {
         &self,
         start: DateTime<Utc>,
         end: DateTime<Utc>,
+    ) -> Result<Vec<MemoryId>> {
+        self.search_by_date_index("date", start, end)
+    }
+
+    /// Scan a `<prefix>:YYYYMMDD:uuid` index (ingest date or event date)
+    fn search_by_date_index(
+        &self,
+        prefix: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
     ) -> Result<Vec<MemoryId>> {
         let mut ids = Vec::new();
-        let start_key = format!("date:{}", start.format("%Y%m%d"));
+        let key_prefix = format!("{prefix}:");
+        let start_key = format!("{key_prefix}{}", start.format("%Y%m%d"));
         // BUG-001 FIX: End key needs ~ suffix to include all UUIDs for that date
         // Keys are: date:YYYYMMDD:uuid, so date:20251207~ comes after all Dec 7 entries
-        let end_key = format!("date:{}~", end.format("%Y%m%d"));
+        let end_key = format!("{key_prefix}{}~", end.format("%Y%m%d"));
 
         let iter = self.db.iterator_cf(
             self.index_cf(),
@@ -1711,10 +1933,10 @@ impl MemoryStorage {
                 break;
             }
             // BUG-001 FIX: Extract memory_id from key (format: date:YYYYMMDD:uuid)
-            if key_str.starts_with("date:") {
+            if key_str.starts_with(&key_prefix) {
                 let parts: Vec<&str> = key_str.split(':').collect();
                 if parts.len() >= 3 {
-                    // parts[0] = "date", parts[1] = "YYYYMMDD", parts[2] = uuid
+                    // parts[0] = prefix, parts[1] = "YYYYMMDD", parts[2] = uuid
                     if let Ok(uuid) = uuid::Uuid::parse_str(parts[2]) {
                         ids.push(MemoryId(uuid));
                     }
@@ -2114,7 +2336,7 @@ impl MemoryStorage {
                 if key.len() != 16 {
                     continue;
                 }
-                if let Ok((memory, _)) = deserialize_memory(&value) {
+                if let Ok((memory, _)) = self.decode_memory(&key, &value) {
                     if memory.parent_id.is_none() {
                         roots.push(memory.id);
                     }
@@ -2230,7 +2452,7 @@ impl MemoryStorage {
                 if key.len() != 16 {
                     continue;
                 }
-                if let Ok((memory, _)) = deserialize_memory(&value) {
+                if let Ok((memory, _)) = self.decode_memory(&key, &value) {
                     if !memory.is_forgotten() {
                         memories.push(memory);
                     }
@@ -2252,7 +2474,7 @@ impl MemoryStorage {
                 if key.len() != 16 {
                     continue;
                 }
-                if let Ok((memory, _)) = deserialize_memory(&value) {
+                if let Ok((memory, _)) = self.decode_memory(&key, &value) {
                     if !memory.compressed && !memory.is_forgotten() && memory.created_at < cutoff {
                         memories.push(memory);
                     }
@@ -2277,7 +2499,7 @@ impl MemoryStorage {
                 if key.len() != 16 {
                     continue;
                 }
-                if let Ok((mut memory, _)) = deserialize_memory(&value) {
+                if let Ok((mut memory, _)) = self.decode_memory(&key, &value) {
                     if memory.is_forgotten() {
                         continue;
                     }
@@ -2292,8 +2514,7 @@ impl MemoryStorage {
                             .metadata
                             .insert("forgotten_at".to_string(), now.clone());
 
-                        let updated_value =
-                            bincode::serde::encode_to_vec(&memory, bincode::config::standard())?;
+                        let updated_value = self.encode_memory(&memory)?;
                         batch.put(&key, updated_value);
                     }
                 }
@@ -2323,7 +2544,7 @@ impl MemoryStorage {
                 if key.len() != 16 {
                     continue;
                 }
-                if let Ok((mut memory, _)) = deserialize_memory(&value) {
+                if let Ok((mut memory, _)) = self.decode_memory(&key, &value) {
                     if memory.is_forgotten() {
                         continue;
                     }
@@ -2338,8 +2559,7 @@ impl MemoryStorage {
                             .metadata
                             .insert("forgotten_at".to_string(), now.clone());
 
-                        let updated_value =
-                            bincode::serde::encode_to_vec(&memory, bincode::config::standard())?;
+                        let updated_value = self.encode_memory(&memory)?;
                         batch.put(&key, updated_value);
                     }
                 }
@@ -2367,7 +2587,7 @@ impl MemoryStorage {
                 if key.len() != 16 {
                     continue;
                 }
-                if let Ok((memory, _)) = deserialize_memory(&value) {
+                if let Ok((memory, _)) = self.decode_memory(&key, &value) {
                     if regex.is_match(&memory.experience.content) {
                         to_delete.push(memory.id);
                         count += 1;
@@ -2424,7 +2644,7 @@ impl MemoryStorage {
                         continue;
                     }
 
-                    match deserialize_memory(&value) {
+                    match self.decode_memory(&key, &value) {
                         Ok((memory, _)) => {
                             if memory.is_forgotten() {
                                 continue;
@@ -2517,6 +2737,11 @@ impl MemoryStorage {
                     continue;
                 }
 
+                // Records can't be read without the compression dictionaries
+                if key.starts_with(DICTIONARY_PREFIX.as_bytes()) {
+                    continue;
+                }
+
                 // Valid memory keys should be exactly 16 bytes (UUID bytes)
                 let is_valid_memory_key = key.len() == 16;
 
@@ -2527,8 +2752,13 @@ impl MemoryStorage {
                         key.len()
                     );
                     to_delete.push(key.to_vec());
-                } else if deserialize_memory(&value).is_err() {
+                } else if !encryption::is_sealed(&value)
+                    && !blob_compression::is_compressed(&value)
+                    && deserialize_memory(&value).is_err()
+                {
                     // Key is valid but value fails all format fallbacks - truly corrupted
+                    // (sealed and compressed records are kept: a missing key or
+                    // dictionary isn't corruption)
                     tracing::debug!(
                         "Marking for deletion: valid key but corrupted value ({} bytes)",
                         value.len()
@@ -2587,9 +2817,14 @@ impl MemoryStorage {
                     continue;
                 }
 
+                let Ok((plaintext, _)) = self.open_record(&key, &value) else {
+                    failed += 1;
+                    continue;
+                };
+
                 // Try current format first (quick check)
                 let is_current = bincode::serde::decode_from_slice::<Memory, _>(
-                    &value,
+                    &plaintext,
                     bincode::config::standard(),
                 )
                 .is_ok();
@@ -2600,7 +2835,7 @@ impl MemoryStorage {
                 }
 
                 // Not current format - try with fallback
-                match deserialize_memory(&value) {
+                match deserialize_memory(&plaintext) {
                     Ok((memory, _)) => {
                         // Successfully deserialized legacy format - queue for migration
                         to_migrate.push((key.to_vec(), memory));
@@ -2623,7 +2858,7 @@ impl MemoryStorage {
             write_opts.set_sync(self.write_mode == WriteMode::Sync);
 
             for (key, memory) in to_migrate {
-                match bincode::serde::encode_to_vec(&memory, bincode::config::standard()) {
+                match self.encode_memory(&memory) {
                     Ok(serialized) => {
                         if let Err(e) = self.db.put_opt(&key, &serialized, &write_opts) {
                             tracing::warn!("Failed to migrate memory: {e}");
@@ -2653,6 +2888,53 @@ impl MemoryStorage {
         Ok((migrated, already_current, failed))
     }
 
+    /// Re-seal memory records with the current encryption key
+    ///
+    /// Rewrites records sealed with a previous key and plaintext records
+    /// written before encryption was enabled, and rebuilds the content index
+    /// under the current key. Returns the number of records rewritten;
+    /// records that can't be opened are left as they are.
+    pub fn reencrypt(&self) -> Result<usize> {
+        if self.keyring.is_none() {
+            return Ok(0);
+        }
+
+        let idx = self.index_cf();
+        let mut batch = WriteBatch::default();
+        batch.delete_range_cf(idx, b"content:", b"content;");
+        let mut resealed = 0;
+        for (key, value) in self.db.iterator(IteratorMode::Start).log_errors() {
+            if key.starts_with(DICTIONARY_PREFIX.as_bytes()) {
+                // Dictionaries must stay readable once the old key is dropped
+                if let Ok((raw, true)) = encryption::open(self.keyring.as_deref(), &key, &value) {
+                    batch.put(&key, self.keyring.as_ref().unwrap().seal(&key, &raw)?);
+                }
+                continue;
+            }
+            if key.len() != 16 {
+                continue;
+            }
+            match self.decode_memory(&key, &value) {
+                Ok((memory, reseal)) => {
+                    if reseal {
+                        batch.put(&key, self.encode_memory(&memory)?);
+                        resealed += 1;
+                    }
+                    batch.put_cf(idx, self.content_index_key(&memory).as_bytes(), b"1");
+                }
+                Err(e) => tracing::warn!("Skipping unreadable record during re-encryption: {e}"),
+            }
+            if batch.len() >= 1000 {
+                self.db.write(std::mem::take(&mut batch))?;
+            }
+        }
+        if !batch.is_empty() {
+            self.db.write(batch)?;
+        }
+        self.flush()?;
+        Ok(resealed)
+    }
+
     /// Flush all column families to ensure data is persisted (critical for graceful shutdown)
     pub fn flush(&self) -> Result<()> {
         use rocksdb::FlushOptions;
@@ -2690,6 +2972,11 @@ pub enum SearchCriteria {
         start: DateTime<Utc>,
         end: DateTime<Utc>,
     },
+    /// Filter by event date (dates mentioned in content) rather than ingest date
+    ByEventDate {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
     ByType(ExperienceType),
     ByImportance {
         min: f32,
@@ -2740,6 +3027,75 @@ pub enum SearchCriteria {
     RootsOnly,
 }
 
+impl SearchCriteria {
+    /// Whether `memory` satisfies these criteria, with the same bucketing as
+    /// the secondary indices. Used by backends that filter records directly.
+    pub fn matches(&self, memory: &Memory) -> bool {
+        let experience = &memory.experience;
+        let episode = experience.context.as_ref().map(|ctx| &ctx.episode);
+        match self {
+            SearchCriteria::ByDate { start, end } => {
+                let day = memory.created_at.date_naive();
+                day >= start.date_naive() && day <= end.date_naive()
+            }
+            SearchCriteria::ByEventDate { start, end } => memory
+                .event_dates()
+                .iter()
+                .any(|day| *day >= start.date_naive() && *day <= end.date_naive()),
+            SearchCriteria::ByType(exp_type) => experience.experience_type == *exp_type,
+            SearchCriteria::ByImportance { min, max } => {
+                let bucket = (memory.importance() * 10.0) as u32;
+                bucket >= (min * 10.0) as u32 && bucket <= (max * 10.0) as u32
+            }
+            SearchCriteria::ByEntity(entity) => experience
+                .entities
+                .iter()
+                .any(|e| e.to_lowercase() == entity.to_lowercase()),
+            SearchCriteria::ByTags(tags) => experience.tags.iter().any(|tag| {
+                tags.iter()
+                    .any(|wanted| tag.to_lowercase() == wanted.to_lowercase())
+            }),
+            SearchCriteria::ByEpisode(episode_id) => {
+                episode.and_then(|e| e.episode_id.as_ref()) == Some(episode_id)
+            }
+            SearchCriteria::ByEpisodeSequence {
+                episode_id,
+                min_sequence,
+                max_sequence,
+            } => match episode {
+                Some(e) if e.episode_id.as_ref() == Some(episode_id) => {
+                    e.sequence_number.is_some_and(|seq| {
+                        min_sequence.map_or(true, |min| seq >= min)
+                            && max_sequence.map_or(true, |max| seq <= max)
+                    })
+                }
+                _ => false,
+            },
+            SearchCriteria::ByRobot(robot_id) => experience.robot_id.as_ref() == Some(robot_id),
+            SearchCriteria::ByMission(mission_id) => {
+                experience.mission_id.as_ref() == Some(mission_id)
+            }
+            SearchCriteria::ByLocation {
+                lat,
+                lon,
+                radius_meters,
+            } => experience.geo_location.is_some_and(|geo| {
+                super::types::GeoFilter::new(*lat, *lon, *radius_meters).contains(geo[0], geo[1])
+            }),
+            SearchCriteria::ByActionType(action_type) => {
+                experience.action_type.as_ref() == Some(action_type)
+            }
+            SearchCriteria::ByReward { min, max } => experience.reward.is_some_and(|reward| {
+                let bucket = |r: f32| ((r.clamp(-1.0, 1.0) + 1.0) * 10.0) as i32;
+                bucket(reward) >= bucket(*min) && bucket(reward) <= bucket(*max)
+            }),
+            SearchCriteria::Combined(criterias) => criterias.iter().all(|c| c.matches(memory)),
+            SearchCriteria::ByParent(parent_id) => memory.parent_id.as_ref() == Some(parent_id),
+            SearchCriteria::RootsOnly => memory.parent_id.is_none(),
+        }
+    }
+}
+
 /// Storage statistics
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct StorageStats {
@@ -2971,8 +3327,7 @@ impl MemoryStorage {
 
         // 1. Serialize memory
         let memory_key = memory.id.0.as_bytes();
-        let memory_value = bincode::serde::encode_to_vec(memory, bincode::config::standard())
-            .context(format!("Failed to serialize memory {}", memory.id.0))?;
+        let memory_value = self.encode_memory(memory)?;
         batch.put(memory_key, &memory_value);
 
         // 2. Serialize vector mapping with modality support
@@ -3164,7 +3519,7 @@ impl MemoryStorage {
                 }
 
                 // Try to deserialize as memory
-                if let Ok((memory, _)) = deserialize_memory(&value) {
+                if let Ok((memory, _)) = self.decode_memory(&key, &value) {
                     // Check if vector mapping exists and has text vectors
                     let has_mapping = match self.get_vector_mapping(&memory.id) {
                         Ok(Some(entry)) => entry.text_vectors().is_some_and(|v| !v.is_empty()),
@@ -3391,6 +3746,141 @@ impl MemoryStorage {
     }
 }
 
+// =========================================================================
+// STORAGE TRAIT (embedded backend)
+// =========================================================================
+
+impl super::backend::Storage for MemoryStorage {
+    fn path(&self) -> &Path {
+        MemoryStorage::path(self)
+    }
+
+    fn store(&self, memory: &Memory) -> Result<()> {
+        MemoryStorage::store(self, memory)
+    }
+
+    fn get(&self, id: &MemoryId) -> Result<Memory> {
+        MemoryStorage::get(self, id)
+    }
+
+    fn update(&self, memory: &Memory) -> Result<()> {
+        MemoryStorage::update(self, memory)
+    }
+
+    fn delete(&self, id: &MemoryId) -> Result<()> {
+        MemoryStorage::delete(self, id)
+    }
+
+    fn get_all_ids(&self) -> Result<Vec<MemoryId>> {
+        MemoryStorage::get_all_ids(self)
+    }
+
+    fn get_all(&self) -> Result<Vec<Memory>> {
+        MemoryStorage::get_all(self)
+    }
+
+    fn search(&self, criteria: SearchCriteria) -> Result<Vec<Memory>> {
+        MemoryStorage::search(self, criteria)
+    }
+
+    fn get_stats(&self) -> Result<StorageStats> {
+        MemoryStorage::get_stats(self)
+    }
+
+    fn flush(&self) -> Result<()> {
+        MemoryStorage::flush(self)
+    }
+
+    fn get_meta(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.db.get(key.as_bytes())?)
+    }
+
+    fn put_meta(&self, key: &str, value: &[u8]) -> Result<()> {
+        let mut write_opts = WriteOptions::default();
+        write_opts.set_sync(self.write_mode == WriteMode::Sync);
+        Ok(self.db.put_opt(key.as_bytes(), value, &write_opts)?)
+    }
+
+    fn delete_meta(&self, key: &str) -> Result<()> {
+        let mut write_opts = WriteOptions::default();
+        write_opts.set_sync(self.write_mode == WriteMode::Sync);
+        Ok(self.db.delete_opt(key.as_bytes(), &write_opts)?)
+    }
+
+    fn scan_meta(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        let iter = self.db.iterator(IteratorMode::From(
+            prefix.as_bytes(),
+            rocksdb::Direction::Forward,
+        ));
+        let mut records = Vec::new();
+        for (key, value) in iter.log_errors() {
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            records.push((String::from_utf8_lossy(&key).into_owned(), value.to_vec()));
+        }
+        Ok(records)
+    }
+
+    fn get_all_vector_mappings(&self) -> Result<Vec<(MemoryId, VectorMappingEntry)>> {
+        MemoryStorage::get_all_vector_mappings(self)
+    }
+
+    fn update_vector_mapping(&self, memory_id: &MemoryId, vector_ids: Vec<u32>) -> Result<()> {
+        MemoryStorage::update_vector_mapping(self, memory_id, vector_ids)
+    }
+
+    fn delete_vector_mapping(&self, memory_id: &MemoryId) -> Result<()> {
+        MemoryStorage::delete_vector_mapping(self, memory_id)
+    }
+
+    // Indexed lookups and batched maintenance instead of the scanning defaults
+
+    fn find_by_external_id(&self, external_id: &str) -> Result<Option<Memory>> {
+        MemoryStorage::find_by_external_id(self, external_id)
+    }
+
+    fn find_by_content_hash(&self, hash: &str) -> Result<Option<Memory>> {
+        MemoryStorage::find_by_content_hash(self, hash)
+    }
+
+    fn get_children(&self, parent_id: &MemoryId) -> Result<Vec<Memory>> {
+        MemoryStorage::get_children(self, parent_id)
+    }
+
+    fn get_uncompressed_older_than(&self, cutoff: DateTime<Utc>) -> Result<Vec<Memory>> {
+        MemoryStorage::get_uncompressed_older_than(self, cutoff)
+    }
+
+    fn mark_forgotten_by_age(&self, cutoff: DateTime<Utc>) -> Result<Vec<MemoryId>> {
+        MemoryStorage::mark_forgotten_by_age(self, cutoff)
+    }
+
+    fn mark_forgotten_by_importance(&self, threshold: f32) -> Result<Vec<MemoryId>> {
+        MemoryStorage::mark_forgotten_by_importance(self, threshold)
+    }
+
+    fn cleanup_corrupted(&self) -> Result<usize> {
+        MemoryStorage::cleanup_corrupted(self)
+    }
+
+    fn migrate_legacy(&self) -> Result<(usize, usize, usize)> {
+        MemoryStorage::migrate_legacy(self)
+    }
+
+    fn reencrypt(&self) -> Result<usize> {
+        MemoryStorage::reencrypt(self)
+    }
+
+    fn train_compression(&self) -> Result<usize> {
+        MemoryStorage::train_compression(self)
+    }
+
+    fn clear_all_interference_records(&self) -> Result<usize> {
+        MemoryStorage::clear_all_interference_records(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3531,6 +4021,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_search_criteria_matches() {
+        let parent = MemoryId(uuid::Uuid::new_v4());
+        let mut memory = Memory::new(
+            MemoryId(uuid::Uuid::new_v4()),
+            Experience {
+                content: "Deployed the gateway".to_string(),
+                experience_type: ExperienceType::Decision,
+                entities: vec!["Gateway".to_string()],
+                tags: vec!["deploy".to_string()],
+                reward: Some(0.8),
+                ..Default::default()
+            },
+            0.7,
+            None,
+            None,
+            None,
+            None,
+        );
+        memory.parent_id = Some(parent.clone());
+
+        assert!(SearchCriteria::ByType(ExperienceType::Decision).matches(&memory));
+        assert!(!SearchCriteria::ByType(ExperienceType::Error).matches(&memory));
+        assert!(SearchCriteria::ByEntity("gateway".to_string()).matches(&memory));
+        assert!(SearchCriteria::ByTags(vec!["other".into(), "DEPLOY".into()]).matches(&memory));
+        assert!(SearchCriteria::ByReward { min: 0.5, max: 1.0 }.matches(&memory));
+        assert!(!SearchCriteria::ByReward {
+            min: -1.0,
+            max: 0.0
+        }
+        .matches(&memory));
+        assert!(SearchCriteria::ByParent(parent).matches(&memory));
+        assert!(!SearchCriteria::RootsOnly.matches(&memory));
+        assert!(!SearchCriteria::Combined(vec![
+            SearchCriteria::ByType(ExperienceType::Decision),
+            SearchCriteria::ByRobot("drone-1".to_string()),
+        ])
+        .matches(&memory));
+    }
+
     #[test]
     fn test_modality_vectors_struct() {
         let mv = ModalityVectors {
@@ -0,0 +1,116 @@
+//! Memory Suppression
+//!
+//! A user can stop a memory from surfacing without deleting it: a rule names
+//! either one memory or a content pattern (a regex, as for
+//! /api/forget/pattern). Recall skips memories any rule matches, so they never
+//! reach recall, search or proactive context again while staying stored,
+//! listed and exportable. Rules are managed through /api/suppress and
+//! persisted by `crate::suppression`.
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::memory::types::{Memory, MemoryId};
+
+/// One user's rule keeping memories out of retrieval
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuppressionRule {
+    pub id: String,
+    /// Suppress this memory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_id: Option<MemoryId>,
+    /// Suppress memories whose content matches this regex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A user's rules, with patterns compiled, as checked during recall
+#[derive(Debug, Clone, Default)]
+pub struct SuppressionList {
+    memory_ids: Vec<MemoryId>,
+    patterns: Vec<Regex>,
+}
+
+impl SuppressionList {
+    /// Compile `rules`; rules with an invalid pattern are skipped
+    pub fn new(rules: &[SuppressionRule]) -> Self {
+        let mut list = Self::default();
+        for rule in rules {
+            if let Some(id) = &rule.memory_id {
+                list.memory_ids.push(id.clone());
+            }
+            if let Some(pattern) = &rule.pattern {
+                match crate::validation::validate_and_compile_pattern(pattern) {
+                    Ok(regex) => list.patterns.push(regex),
+                    Err(e) => tracing::warn!(rule = %rule.id, "Skipping suppression rule: {}", e),
+                }
+            }
+        }
+        list
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.memory_ids.is_empty() && self.patterns.is_empty()
+    }
+
+    /// Whether a rule keeps `memory` out of retrieval
+    pub fn matches(&self, memory: &Memory) -> bool {
+        self.memory_ids.contains(&memory.id)
+            || self
+                .patterns
+                .iter()
+                .any(|p| p.is_match(&memory.experience.content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::types::Experience;
+
+    fn memory(content: &str) -> Memory {
+        Memory::new(
+            MemoryId(uuid::Uuid::new_v4()),
+            Experience {
+                content: content.to_string(),
+                ..Default::default()
+            },
+            0.5,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    fn rule(memory_id: Option<MemoryId>, pattern: Option<&str>) -> SuppressionRule {
+        SuppressionRule {
+            id: uuid::Uuid::new_v4().to_string(),
+            memory_id,
+            pattern: pattern.map(str::to_string),
+            reason: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_rules_match_by_id_and_pattern() {
+        let pinned = memory("The office wifi password is on the fridge");
+        let noisy = memory("Reminder: standup moved to 9:30");
+        let other = memory("Deploys run on Tuesdays");
+
+        let list = SuppressionList::new(&[
+            rule(Some(pinned.id.clone()), None),
+            rule(None, Some("(?i)standup")),
+        ]);
+        assert!(list.matches(&pinned));
+        assert!(list.matches(&noisy));
+        assert!(!list.matches(&other));
+
+        assert!(SuppressionList::new(&[]).is_empty());
+    }
+}
@@ -24,11 +24,9 @@ use super::types::{
     Project, ProjectId, ProjectStatus, Todo, TodoComment, TodoCommentId, TodoCommentType, TodoId,
     TodoStatus,
 };
+use crate::embeddings::provider::{ProviderConfig, LOCAL_DIMENSION};
 use crate::vector_db::{VamanaConfig, VamanaIndex};
 
-/// Embedding dimension (MiniLM-L6-v2)
-const EMBEDDING_DIM: usize = 384;
-
 const CF_TODOS: &str = "todos";
 const CF_PROJECTS: &str = "projects";
 const CF_TODO_INDEX: &str = "todo_index";
@@ -84,6 +82,8 @@ pub struct TodoStore {
     storage_path: std::path::PathBuf,
     /// Mutex for atomic sequence number allocation (prevents TOCTOU race)
     seq_mutex: parking_lot::Mutex<()>,
+    /// Dimension of the configured embedding provider, for the vector indices
+    embedding_dim: usize,
 }
 
 impl TodoStore {
@@ -135,6 +135,9 @@ impl TodoStore {
             vector_indices: RwLock::new(HashMap::new()),
             storage_path: todos_path,
             seq_mutex: parking_lot::Mutex::new(()),
+            embedding_dim: ProviderConfig::from_env()
+                .dimension()
+                .unwrap_or(LOCAL_DIMENSION),
         })
     }
 
@@ -194,7 +197,7 @@ impl TodoStore {
         let mut indices = self.vector_indices.write();
         if !indices.contains_key(user_id) {
             let config = VamanaConfig {
-                dimension: EMBEDDING_DIM,
+                dimension: self.embedding_dim,
                 max_degree: 32,
                 search_list_size: 75,
                 alpha: 1.2,
@@ -314,7 +317,7 @@ impl TodoStore {
 
                 // Create a new index and load from disk
                 let config = VamanaConfig {
-                    dimension: EMBEDDING_DIM,
+                    dimension: self.embedding_dim,
                     ..Default::default()
                 };
                 let mut index = VamanaIndex::new(config)?;
@@ -0,0 +1,369 @@
+//! Memory Type Registry
+//!
+//! The built-in memory types (`ExperienceType`) are fixed; deployments can
+//! register custom types on top of them via `/api/memory_types`. A custom type
+//! is stored as its built-in `base` type with its own name in the memory's
+//! metadata, and carries:
+//!
+//! - a retention rule overriding the base type's (`SHODH_RETENTION_POLICY`)
+//! - a retrieval weight multiplying its memories' ranking scores
+//! - display metadata (label, color, icon) for UIs
+//!
+//! Incoming `memory_type` values are validated against the registry, so a
+//! misspelled type is rejected instead of silently stored as an observation.
+//! Custom types persist in the `memory_types` column family of the shared DB.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use parking_lot::RwLock;
+use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, IteratorMode, Options, DB};
+use serde::{Deserialize, Serialize};
+
+use crate::handlers::crud::parse_experience_type;
+use crate::memory::retention::{RetentionPolicy, RetentionRule};
+use crate::memory::types::{ExperienceType, Memory, SharedMemory, MEMORY_TYPE_KEY};
+
+/// Column family holding custom type definitions (name -> MemoryTypeDef)
+pub const CF_MEMORY_TYPES: &str = "memory_types";
+
+/// Longest custom type name
+const MAX_TYPE_NAME_LEN: usize = 64;
+
+/// Largest retrieval weight a type may carry
+pub const MAX_TYPE_RETRIEVAL_WEIGHT: f32 = 5.0;
+
+/// Built-in types, by their canonical lowercase name
+pub const BUILTIN_TYPES: [(&str, ExperienceType); 14] = [
+    ("observation", ExperienceType::Observation),
+    ("decision", ExperienceType::Decision),
+    ("learning", ExperienceType::Learning),
+    ("error", ExperienceType::Error),
+    ("discovery", ExperienceType::Discovery),
+    ("pattern", ExperienceType::Pattern),
+    ("context", ExperienceType::Context),
+    ("task", ExperienceType::Task),
+    ("code_edit", ExperienceType::CodeEdit),
+    ("file_access", ExperienceType::FileAccess),
+    ("search", ExperienceType::Search),
+    ("command", ExperienceType::Command),
+    ("conversation", ExperienceType::Conversation),
+    ("intention", ExperienceType::Intention),
+];
+
+/// How a type is shown in UIs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TypeDisplay {
+    #[serde(default)]
+    pub label: Option<String>,
+    /// CSS color, e.g. "#4f46e5"
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+}
+
+/// A custom memory type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryTypeDef {
+    pub name: String,
+    /// Built-in type its memories are stored as
+    pub base: ExperienceType,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Overrides the base type's retention rule (`None` inherits it)
+    #[serde(default)]
+    pub retention: Option<RetentionRule>,
+    /// Multiplier on ranking scores (1.0 = neutral)
+    #[serde(default = "default_retrieval_weight")]
+    pub retrieval_weight: f32,
+    #[serde(default)]
+    pub display: TypeDisplay,
+}
+
+fn default_retrieval_weight() -> f32 {
+    1.0
+}
+
+/// A validated `memory_type`: the built-in type to store, plus the custom
+/// type name when it isn't a built-in
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedType {
+    pub base: ExperienceType,
+    pub custom: Option<String>,
+}
+
+impl ResolvedType {
+    /// Record the custom type name (if any) in a memory's metadata
+    pub fn write_to(&self, metadata: &mut HashMap<String, String>) {
+        if let Some(ref custom) = self.custom {
+            metadata.insert(MEMORY_TYPE_KEY.to_string(), custom.clone());
+        }
+    }
+}
+
+/// Check a custom type name: lowercase letters, digits, '_' and '-', and not
+/// a built-in type (or its alias)
+pub fn validate_type_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > MAX_TYPE_NAME_LEN {
+        return Err(anyhow!("must be 1-{MAX_TYPE_NAME_LEN} characters"));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+    {
+        return Err(anyhow!(
+            "may only contain lowercase letters, digits, '_' and '-'"
+        ));
+    }
+    if parse_experience_type(name).is_ok() {
+        return Err(anyhow!("'{name}' is a built-in type"));
+    }
+    Ok(())
+}
+
+/// Retrieval weights of custom types, snapshotted for a ranking pass
+#[derive(Debug, Clone, Default)]
+pub struct TypeWeights(HashMap<String, f32>);
+
+impl TypeWeights {
+    /// Whether every type ranks neutrally
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Weight for a memory's type (1.0 for built-in and unknown types)
+    pub fn weight_for(&self, memory: &Memory) -> f32 {
+        memory
+            .custom_type()
+            .and_then(|name| self.0.get(name))
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    /// Re-rank scored memories by score × type weight (stable for ties)
+    pub fn rank(&self, memories: &mut [SharedMemory]) {
+        if self.is_empty() {
+            return;
+        }
+        memories.sort_by(|a, b| {
+            let weighted = |m: &SharedMemory| m.get_score().unwrap_or(0.0) * self.weight_for(m);
+            weighted(b).total_cmp(&weighted(a))
+        });
+    }
+}
+
+/// Built-in types plus persistent custom types
+pub struct MemoryTypeRegistry {
+    db: Arc<DB>,
+    custom: RwLock<HashMap<String, MemoryTypeDef>>,
+}
+
+impl MemoryTypeRegistry {
+    /// Column family descriptors required by the registry.
+    /// The caller must include these (plus `"default"`) when opening the shared DB.
+    pub fn cf_descriptors() -> Vec<ColumnFamilyDescriptor> {
+        let mut cf_opts = Options::default();
+        cf_opts.create_if_missing(true);
+        vec![ColumnFamilyDescriptor::new(CF_MEMORY_TYPES, cf_opts)]
+    }
+
+    /// Open the registry and load every custom type
+    pub fn new(db: Arc<DB>) -> Result<Self> {
+        let registry = Self {
+            db,
+            custom: RwLock::new(HashMap::new()),
+        };
+        let mut custom = HashMap::new();
+        for item in registry.db.iterator_cf(registry.cf(), IteratorMode::Start) {
+            let (_, value) = item.context("Failed to read memory type")?;
+            match serde_json::from_slice::<MemoryTypeDef>(&value) {
+                Ok(def) => {
+                    custom.insert(def.name.clone(), def);
+                }
+                Err(e) => tracing::warn!("Skipping unreadable memory type: {}", e),
+            }
+        }
+        *registry.custom.write() = custom;
+        Ok(registry)
+    }
+
+    fn cf(&self) -> &ColumnFamily {
+        self.db
+            .cf_handle(CF_MEMORY_TYPES)
+            .expect("memory_types CF must exist")
+    }
+
+    /// Add or replace a custom type
+    pub fn define(&self, def: MemoryTypeDef) -> Result<()> {
+        validate_type_name(&def.name)?;
+        if !(0.0..=MAX_TYPE_RETRIEVAL_WEIGHT).contains(&def.retrieval_weight) {
+            return Err(anyhow!(
+                "retrieval_weight must be between 0.0 and {MAX_TYPE_RETRIEVAL_WEIGHT}"
+            ));
+        }
+        self.db
+            .put_cf(self.cf(), def.name.as_bytes(), serde_json::to_vec(&def)?)?;
+        self.custom.write().insert(def.name.clone(), def);
+        Ok(())
+    }
+
+    /// Remove a custom type; returns whether it existed. Memories already
+    /// stored under it keep their base type.
+    pub fn remove(&self, name: &str) -> Result<bool> {
+        if self.custom.write().remove(name).is_none() {
+            return Ok(false);
+        }
+        self.db.delete_cf(self.cf(), name.as_bytes())?;
+        Ok(true)
+    }
+
+    /// Custom types ordered by name
+    pub fn custom_types(&self) -> Vec<MemoryTypeDef> {
+        let mut types: Vec<MemoryTypeDef> = self.custom.read().values().cloned().collect();
+        types.sort_by(|a, b| a.name.cmp(&b.name));
+        types
+    }
+
+    /// Resolve an incoming `memory_type` (case-insensitive); `None` if unknown
+    pub fn resolve(&self, name: &str) -> Option<ResolvedType> {
+        let name = name.trim().to_lowercase();
+        if let Ok(base) = parse_experience_type(&name) {
+            return Some(ResolvedType { base, custom: None });
+        }
+        self.custom.read().get(&name).map(|def| ResolvedType {
+            base: def.base.clone(),
+            custom: Some(def.name.clone()),
+        })
+    }
+
+    /// Retrieval weights of custom types that don't rank neutrally
+    pub fn weights(&self) -> TypeWeights {
+        TypeWeights(
+            self.custom
+                .read()
+                .values()
+                .filter(|def| def.retrieval_weight != 1.0)
+                .map(|def| (def.name.clone(), def.retrieval_weight))
+                .collect(),
+        )
+    }
+
+    /// `base` plus the retention overrides of custom types
+    pub fn retention_policy(&self, base: &RetentionPolicy) -> RetentionPolicy {
+        let mut policy = base.clone();
+        for def in self.custom.read().values() {
+            if let Some(rule) = def.retention {
+                policy.set_custom(&def.name, rule);
+            }
+        }
+        policy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::types::{Experience, MemoryId};
+
+    fn open_registry(path: &std::path::Path) -> MemoryTypeRegistry {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let mut cfs = vec![ColumnFamilyDescriptor::new("default", Options::default())];
+        cfs.extend(MemoryTypeRegistry::cf_descriptors());
+        let db = DB::open_cf_descriptors(&opts, path, cfs).unwrap();
+        MemoryTypeRegistry::new(Arc::new(db)).unwrap()
+    }
+
+    fn runbook(weight: f32) -> MemoryTypeDef {
+        MemoryTypeDef {
+            name: "runbook".to_string(),
+            base: ExperienceType::Learning,
+            description: Some("Operational procedures".to_string()),
+            retention: Some(RetentionRule::Never),
+            retrieval_weight: weight,
+            display: TypeDisplay::default(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_builtin_and_custom() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let registry = open_registry(dir.path());
+            assert_eq!(
+                registry.resolve("Code_Edit"),
+                Some(ResolvedType {
+                    base: ExperienceType::CodeEdit,
+                    custom: None
+                })
+            );
+            assert_eq!(registry.resolve("runbook"), None);
+            registry.define(runbook(1.5)).unwrap();
+        }
+
+        // Custom types persist
+        let registry = open_registry(dir.path());
+        assert_eq!(
+            registry.resolve("RUNBOOK"),
+            Some(ResolvedType {
+                base: ExperienceType::Learning,
+                custom: Some("runbook".to_string())
+            })
+        );
+        assert!(registry.remove("runbook").unwrap());
+        assert_eq!(registry.resolve("runbook"), None);
+    }
+
+    #[test]
+    fn test_define_validates() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = open_registry(dir.path());
+        let mut def = runbook(1.0);
+        def.name = "decision".to_string();
+        assert!(registry.define(def).is_err());
+        let mut def = runbook(1.0);
+        def.name = "Run Book".to_string();
+        assert!(registry.define(def).is_err());
+        assert!(registry.define(runbook(50.0)).is_err());
+    }
+
+    #[test]
+    fn test_weights_rerank() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = open_registry(dir.path());
+        assert!(registry.weights().is_empty());
+        registry.define(runbook(2.0)).unwrap();
+
+        let scored = |content: &str, score: f32, custom: Option<&str>| {
+            let mut experience = Experience {
+                content: content.to_string(),
+                ..Default::default()
+            };
+            if let Some(custom) = custom {
+                experience
+                    .metadata
+                    .insert(MEMORY_TYPE_KEY.to_string(), custom.to_string());
+            }
+            let mut memory = Memory::new(
+                MemoryId(uuid::Uuid::new_v4()),
+                experience,
+                0.5,
+                None,
+                None,
+                None,
+                None,
+            );
+            memory.set_score(score);
+            Arc::new(memory)
+        };
+        let mut memories = vec![
+            scored("plain", 0.8, None),
+            scored("procedure", 0.5, Some("runbook")),
+        ];
+        registry.weights().rank(&mut memories);
+        assert_eq!(memories[0].experience.content, "procedure");
+    }
+}
@@ -913,6 +913,8 @@ pub enum ChangeType {
     TagsUpdated,
     /// Importance was adjusted
     ImportanceAdjusted,
+    /// Near-duplicate memories were folded into this one
+    Merged,
 }
 
 /// A revision in memory history - tracks what changed and when
@@ -930,6 +932,147 @@ pub struct MemoryRevision {
     pub change_reason: Option<String>,
 }
 
+/// Hex SHA-256 of normalized content (whitespace collapsed, lowercased), used to
+/// recognize identical memories at ingest
+pub fn content_hash(content: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let normalized = content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
+
+/// Metadata key holding a memory's custom type name (see `type_registry`)
+pub const MEMORY_TYPE_KEY: &str = "memory_type";
+
+/// Metadata key marking a memory as pinned ("true"): never evicted by quotas
+pub const PINNED_KEY: &str = "pinned";
+
+/// Metadata key holding when a deleted memory was moved to the trash (RFC 3339).
+/// Trashed memories are also flagged forgotten, so retrieval skips them.
+pub const TRASHED_AT_KEY: &str = "trashed_at";
+
+/// Metadata key holding a memory's `ProvenanceSource`
+pub const PROVENANCE_SOURCE_KEY: &str = "provenance_source";
+/// Metadata key holding the request ID that created a memory
+pub const PROVENANCE_REQUEST_ID_KEY: &str = "provenance_request_id";
+/// Metadata key holding the model that produced a memory
+pub const PROVENANCE_MODEL_KEY: &str = "provenance_model";
+/// Metadata key holding the tools involved in producing a memory (comma-separated)
+pub const PROVENANCE_TOOLS_KEY: &str = "provenance_tools";
+
+/// Where a memory entered the system
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProvenanceSource {
+    /// Explicit remember/upsert call by a client
+    #[default]
+    Manual,
+    /// Captured from Claude Code hooks or the Cortex proxy
+    Cortex,
+    /// Synced from an external integration (Linear, GitHub)
+    Integration,
+    /// Auto-ingested from proactive_context
+    AutoIngest,
+    /// Streaming ingestion
+    Streaming,
+    /// Produced by consolidation (gists, merges)
+    Consolidation,
+}
+
+impl ProvenanceSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Manual => "manual",
+            Self::Cortex => "cortex",
+            Self::Integration => "integration",
+            Self::AutoIngest => "auto_ingest",
+            Self::Streaming => "streaming",
+            Self::Consolidation => "consolidation",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "manual" => Some(Self::Manual),
+            "cortex" | "hook" => Some(Self::Cortex),
+            "integration" => Some(Self::Integration),
+            "auto_ingest" => Some(Self::AutoIngest),
+            "streaming" => Some(Self::Streaming),
+            "consolidation" => Some(Self::Consolidation),
+            _ => None,
+        }
+    }
+}
+
+/// Origin of a memory, kept in `Experience::metadata` under the `provenance_*` keys
+///
+/// Metadata (rather than new fields) keeps the stored format unchanged, so
+/// memories written before provenance existed still decode.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Provenance {
+    pub source: ProvenanceSource,
+    /// Request ID (X-Request-ID) of the call that created the memory
+    pub request_id: Option<String>,
+    /// Model that produced the content
+    pub model: Option<String>,
+    /// Tools involved in producing the content
+    pub tools: Vec<String>,
+}
+
+impl Provenance {
+    pub fn new(source: ProvenanceSource) -> Self {
+        Self {
+            source,
+            ..Default::default()
+        }
+    }
+
+    /// Write into memory metadata, replacing any earlier provenance
+    pub fn write_to(&self, metadata: &mut HashMap<String, String>) {
+        metadata.insert(
+            PROVENANCE_SOURCE_KEY.to_string(),
+            self.source.as_str().to_string(),
+        );
+        for (key, value) in [
+            (PROVENANCE_REQUEST_ID_KEY, self.request_id.clone()),
+            (PROVENANCE_MODEL_KEY, self.model.clone()),
+            (
+                PROVENANCE_TOOLS_KEY,
+                (!self.tools.is_empty()).then(|| self.tools.join(",")),
+            ),
+        ] {
+            match value {
+                Some(value) => metadata.insert(key.to_string(), value),
+                None => metadata.remove(key),
+            };
+        }
+    }
+
+    /// Fresh metadata map holding only this provenance
+    pub fn to_metadata(&self) -> HashMap<String, String> {
+        let mut metadata = HashMap::new();
+        self.write_to(&mut metadata);
+        metadata
+    }
+
+    /// Read from memory metadata; `None` for memories stored before provenance tracking
+    pub fn read_from(metadata: &HashMap<String, String>) -> Option<Self> {
+        let source = ProvenanceSource::parse(metadata.get(PROVENANCE_SOURCE_KEY)?)?;
+        Some(Self {
+            source,
+            request_id: metadata.get(PROVENANCE_REQUEST_ID_KEY).cloned(),
+            model: metadata.get(PROVENANCE_MODEL_KEY).cloned(),
+            tools: metadata
+                .get(PROVENANCE_TOOLS_KEY)
+                .map(|t| t.split(',').map(str::to_string).collect())
+                .unwrap_or_default(),
+        })
+    }
+}
+
 /// Stored memory with metadata
 ///
 /// This is the UNIFIED memory kernel - the single source of truth.
@@ -990,8 +1133,8 @@ pub struct Memory {
     pub version: u32,
 
     /// Audit history - tracks all changes to this memory
-    /// Only populated for memories with external_id (mutable memories)
-    /// Empty for regular immutable memories
+    /// Populated by upserts, API edits and consolidation; empty for memories
+    /// never changed after creation
     pub history: Vec<MemoryRevision>,
 
     /// Related todo IDs for bidirectional linking with todo system
@@ -1159,6 +1302,28 @@ impl Memory {
             .unwrap_or(false)
     }
 
+    /// Event time: dates the memory is about (mentioned in its content),
+    /// as opposed to its ingest time `created_at`
+    pub fn event_dates(&self) -> Vec<chrono::NaiveDate> {
+        self.experience
+            .temporal_refs
+            .iter()
+            .filter_map(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            .collect()
+    }
+
+    /// Whether the memory is about an event within `start..=end` (day precision)
+    pub fn event_in_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.event_dates()
+            .iter()
+            .any(|d| *d >= start.date_naive() && *d <= end.date_naive())
+    }
+
+    /// Whether the memory was ingested, or is about an event, within `start..=end`
+    pub fn in_time_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        (self.created_at >= start && self.created_at <= end) || self.event_in_range(start, end)
+    }
+
     /// Update this memory's content, pushing old content to history
     /// Returns the new version number
     pub fn update_content(
@@ -1169,6 +1334,22 @@ impl Memory {
         change_reason: Option<String>,
     ) -> u32 {
         // Push current content to history
+        self.record_revision(change_type, changed_by, change_reason);
+
+        // Update content
+        self.experience.content = new_content;
+
+        self.version
+    }
+
+    /// Record a change that keeps the content (tags, importance, ...) in history
+    /// Returns the new version number
+    pub fn record_revision(
+        &mut self,
+        change_type: ChangeType,
+        changed_by: Option<String>,
+        change_reason: Option<String>,
+    ) -> u32 {
         self.history.push(MemoryRevision {
             previous_content: self.experience.content.clone(),
             change_type,
@@ -1176,14 +1357,44 @@ impl Memory {
             changed_by,
             change_reason,
         });
-
-        // Update content
-        self.experience.content = new_content;
         self.version += 1;
 
         self.version
     }
 
+    /// Custom type this memory was stored under, if any
+    pub fn custom_type(&self) -> Option<&str> {
+        self.experience
+            .metadata
+            .get(MEMORY_TYPE_KEY)
+            .map(String::as_str)
+    }
+
+    /// When the memory was deleted into the trash, if it is there
+    pub fn trashed_at(&self) -> Option<DateTime<Utc>> {
+        if !self.is_forgotten() {
+            return None;
+        }
+        self.experience
+            .metadata
+            .get(TRASHED_AT_KEY)
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc))
+    }
+
+    /// Pinned memories are exempt from quota eviction
+    pub fn is_pinned(&self) -> bool {
+        self.experience
+            .metadata
+            .get(PINNED_KEY)
+            .is_some_and(|v| v == "true")
+    }
+
+    /// Where this memory came from, if recorded
+    pub fn provenance(&self) -> Option<Provenance> {
+        Provenance::read_from(&self.experience.metadata)
+    }
+
     /// Get the full history of this memory
     pub fn get_history(&self) -> &[MemoryRevision] {
         &self.history
@@ -1372,6 +1583,19 @@ impl Memory {
         meta.importance = (meta.importance * (1.0 - decay)).max(IMPORTANCE_FLOOR);
     }
 
+    /// Take over a merged duplicate's reinforcement history (thread-safe)
+    ///
+    /// Access counts add up; importance, activation and last access keep the
+    /// stronger of the two.
+    pub fn absorb_reinforcement(&self, other: &Memory) {
+        let other = other.metadata_snapshot();
+        let mut meta = self.metadata.lock();
+        meta.access_count = meta.access_count.saturating_add(other.access_count);
+        meta.importance = meta.importance.max(other.importance);
+        meta.activation = meta.activation.max(other.activation);
+        meta.last_accessed = meta.last_accessed.max(other.last_accessed);
+    }
+
     /// Get all metadata snapshot (for debugging/stats)
     pub fn metadata_snapshot(&self) -> MemoryMetadata {
         self.metadata.lock().clone()
@@ -1421,6 +1645,18 @@ impl Memory {
         (weighted_recency * 0.6 + importance * 0.4).clamp(0.0, 1.0)
     }
 
+    /// Current retention strength on the forgetting curve (0.05-1.0)
+    ///
+    /// Decays with time since last access, slowed by access count and importance;
+    /// working-tier memories fade faster, archived ones slower. See
+    /// `decay::memory_strength` and `importance::tier_forget_scale`.
+    pub fn retention_strength(&self, now: DateTime<Utc>) -> f32 {
+        let meta = self.metadata.lock();
+        let days = (now - meta.last_accessed).num_seconds().max(0) as f64 / 86_400.0
+            * crate::memory::importance::tier_forget_scale(self.tier);
+        crate::decay::memory_strength(days, meta.access_count, meta.importance)
+    }
+
     /// Calculate salience score with access-based boost
     ///
     /// Similar to `salience_score()` but also factors in access frequency.
@@ -1980,8 +2216,11 @@ pub struct Query {
 
     // === Scoring Parameters ===
     /// Weight for recency boost in unified scoring (0.0-1.0)
-    /// When None, uses hardcoded default (0.1 = 10% contribution)
+    /// When None, uses the tenant's ranking weight (0.1 = 10% contribution by default)
     pub recency_weight: Option<f32>,
+    /// Age in hours at which the recency boost halves
+    /// When None, uses the default decay (~70 hours)
+    pub recency_half_life_hours: Option<f32>,
 
     // === Result Control ===
     pub max_results: usize,
@@ -2067,6 +2306,7 @@ impl Default for Query {
             prospective_signals: None,
             episode_id: None,
             recency_weight: None,
+            recency_half_life_hours: None,
             max_results: DEFAULT_MAX_RESULTS,
             retrieval_mode: RetrievalMode::Hybrid,
             offset: 0,
@@ -2290,6 +2530,11 @@ impl QueryBuilder {
         self
     }
 
+    pub fn recency_half_life(mut self, hours: f32) -> Self {
+        self.query.recency_half_life_hours = Some(hours);
+        self
+    }
+
     pub fn max_results(mut self, max: usize) -> Self {
         self.query.max_results = max;
         self
@@ -4226,6 +4471,18 @@ pub struct GraphDecayResult {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_content_hash_normalizes() {
+        assert_eq!(
+            content_hash("Deploy uses  Helm charts\n"),
+            content_hash("deploy uses helm charts")
+        );
+        assert_ne!(
+            content_hash("deploy uses helm charts"),
+            content_hash("deploy uses kustomize")
+        );
+    }
+
     #[test]
     fn test_geo_filter_haversine_distance() {
         // San Francisco to Oakland (~13km)
@@ -4315,4 +4572,26 @@ mod tests {
         assert_eq!(query.action_type, Some("landing".to_string()));
         assert_eq!(query.reward_range, Some((0.5, 1.0)));
     }
+
+    #[test]
+    fn test_provenance_metadata_round_trip() {
+        let mut metadata = HashMap::new();
+        assert_eq!(Provenance::read_from(&metadata), None);
+
+        let provenance = Provenance {
+            source: ProvenanceSource::Cortex,
+            request_id: Some("req-42".to_string()),
+            model: Some("claude-sonnet".to_string()),
+            tools: vec!["Edit".to_string(), "Bash".to_string()],
+        };
+        provenance.write_to(&mut metadata);
+        assert_eq!(Provenance::read_from(&metadata), Some(provenance));
+
+        // Rewriting drops fields the new provenance doesn't carry
+        Provenance::new(ProvenanceSource::Manual).write_to(&mut metadata);
+        assert_eq!(
+            Provenance::read_from(&metadata),
+            Some(Provenance::new(ProvenanceSource::Manual))
+        );
+    }
 }
@@ -149,7 +149,7 @@ pub static EMBEDDING_GENERATE_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(||
             "shodh_embedding_generate_total",
             "Total embedding generations",
         ),
-        &["mode", "result"], // mode: "onnx" or "simplified"
+        &["mode", "result"], // mode: "onnx", "simplified", "openai" or "cohere"
     )
     .expect("EMBEDDING_GENERATE_TOTAL metric must be valid at compile time")
 });
@@ -302,6 +302,30 @@ pub static RESOURCE_LIMIT_REJECTIONS: LazyLock<IntCounterVec> = LazyLock::new(||
     .expect("RESOURCE_LIMIT_REJECTIONS metric must be valid at compile time")
 });
 
+/// Requests throttled by the per-key rate limiter (see `crate::rate_limit`)
+pub static RATE_LIMITED_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "shodh_rate_limited_total",
+            "Requests rejected with 429 by the per-key rate limiter",
+        ),
+        &["kind", "subject"],
+    )
+    .expect("RATE_LIMITED_TOTAL metric must be valid at compile time")
+});
+
+/// Outbound webhook deliveries by result (see `crate::event_webhooks`)
+pub static WEBHOOK_DELIVERIES_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "shodh_webhook_deliveries_total",
+            "Outbound webhook delivery attempts by result",
+        ),
+        &["result"],
+    )
+    .expect("WEBHOOK_DELIVERIES_TOTAL metric must be valid at compile time")
+});
+
 // ============================================================================
 // Concurrency Metrics (P0.8)
 // ============================================================================
@@ -454,6 +478,28 @@ pub static EMBEDDING_CACHE_CONTENT_SIZE: LazyLock<IntGauge> = LazyLock::new(|| {
     .expect("EMBEDDING_CACHE_CONTENT_SIZE metric must be valid at compile time")
 });
 
+// ============================================================================
+// Proactive Injection Metrics
+// ============================================================================
+
+/// Tokens of memory content injected via proactive context
+pub static INJECTED_TOKENS_TOTAL: LazyLock<IntCounter> = LazyLock::new(|| {
+    IntCounter::new(
+        "shodh_injected_tokens_total",
+        "Estimated tokens of memory content injected via proactive context",
+    )
+    .expect("INJECTED_TOKENS_TOTAL metric must be valid at compile time")
+});
+
+/// Proactive context requests trimmed or blocked by the daily token budget
+pub static INJECTION_BUDGET_EXHAUSTED_TOTAL: LazyLock<IntCounter> = LazyLock::new(|| {
+    IntCounter::new(
+        "shodh_injection_budget_exhausted_total",
+        "Proactive context requests limited by the per-user daily injection token budget",
+    )
+    .expect("INJECTION_BUDGET_EXHAUSTED_TOTAL metric must be valid at compile time")
+});
+
 /// Register all metrics with the global registry
 ///
 /// # Returns
@@ -521,6 +567,8 @@ fn do_register_metrics() -> Result<(), MetricsError> {
     // Error metrics
     register!(ERRORS_TOTAL, "ERRORS_TOTAL");
     register!(RESOURCE_LIMIT_REJECTIONS, "RESOURCE_LIMIT_REJECTIONS");
+    register!(RATE_LIMITED_TOTAL, "RATE_LIMITED_TOTAL");
+    register!(WEBHOOK_DELIVERIES_TOTAL, "WEBHOOK_DELIVERIES_TOTAL");
 
     // Concurrency metrics
     register!(CONCURRENT_REQUESTS, "CONCURRENT_REQUESTS");
@@ -544,6 +592,13 @@ fn do_register_metrics() -> Result<(), MetricsError> {
     register!(EMBEDDING_CACHE_QUERY_SIZE, "EMBEDDING_CACHE_QUERY_SIZE");
     register!(EMBEDDING_CACHE_CONTENT_SIZE, "EMBEDDING_CACHE_CONTENT_SIZE");
 
+    // Proactive injection metrics
+    register!(INJECTED_TOKENS_TOTAL, "INJECTED_TOKENS_TOTAL");
+    register!(
+        INJECTION_BUDGET_EXHAUSTED_TOTAL,
+        "INJECTION_BUDGET_EXHAUSTED_TOTAL"
+    );
+
     if errors.is_empty() {
         Ok(())
     } else {
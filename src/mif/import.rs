@@ -47,7 +47,8 @@ pub fn build_dedup_set(existing_contents: &[String]) -> HashSet<[u8; 32]> {
     existing_contents.iter().map(|c| content_hash(c)).collect()
 }
 
-fn content_hash(content: &str) -> [u8; 32] {
+/// SHA-256 of memory content, the key used for import dedup
+pub fn content_hash(content: &str) -> [u8; 32] {
     let mut hasher = Sha256::new();
     hasher.update(content.as_bytes());
     hasher.finalize().into()
@@ -789,6 +789,7 @@ impl PyMemorySystem {
             prospective_signals: None,
             episode_id: None,
             recency_weight: None,
+            recency_half_life_hours: None,
             max_results: limit,
             retrieval_mode,
             offset: 0,
@@ -1824,6 +1825,7 @@ impl PyMemorySystem {
             prospective_signals: None,
             episode_id: None,
             recency_weight: None,
+            recency_half_life_hours: None,
             max_results: max_results * 2, // Get more for filtering
             retrieval_mode: RetrievalMode::Hybrid,
             offset: 0,
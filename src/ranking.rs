@@ -0,0 +1,169 @@
+//! Per-Tenant Ranking Weights
+//!
+//! The weights of recall's unified score (see
+//! [`crate::memory::ranking::RankingWeights`]) can be tuned per tenant through
+//! /api/admin/ranking. The default tenant (keys outside SHODH_TENANT_API_KEYS)
+//! is configured under the name "default". Tenants without an entry use the
+//! built-in defaults.
+//!
+//! Entries live in the `ranking` column family of the shared DB, keyed by
+//! tenant name, and are cached in memory. Loaded memory systems pick up a
+//! change immediately (see `MultiUserMemoryManager::apply_ranking`).
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use parking_lot::RwLock;
+use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, IteratorMode, Options, DB};
+
+use crate::memory::ranking::RankingWeights;
+use crate::tenant::{self, DEFAULT_TENANT};
+
+/// Column family holding ranking weights (tenant -> RankingWeights)
+pub const CF_RANKING: &str = "ranking";
+
+fn tenant_key(tenant: Option<&str>) -> &str {
+    tenant.unwrap_or(DEFAULT_TENANT)
+}
+
+/// Persistent ranking weights per tenant
+pub struct RankingStore {
+    db: Arc<DB>,
+    weights: RwLock<HashMap<String, RankingWeights>>,
+}
+
+impl RankingStore {
+    /// Column family descriptors required by the RankingStore.
+    /// The caller must include these (plus `"default"`) when opening the shared DB.
+    pub fn cf_descriptors() -> Vec<ColumnFamilyDescriptor> {
+        let mut cf_opts = Options::default();
+        cf_opts.create_if_missing(true);
+        vec![ColumnFamilyDescriptor::new(CF_RANKING, cf_opts)]
+    }
+
+    /// Open the store and load every tenant's weights
+    pub fn new(db: Arc<DB>) -> Result<Self> {
+        let store = Self {
+            db,
+            weights: RwLock::new(HashMap::new()),
+        };
+        let mut weights = HashMap::new();
+        for item in store.db.iterator_cf(store.cf(), IteratorMode::Start) {
+            let (key, value) = item.context("Failed to read ranking weights")?;
+            match serde_json::from_slice::<RankingWeights>(&value) {
+                Ok(w) => {
+                    weights.insert(String::from_utf8_lossy(&key).into_owned(), w);
+                }
+                Err(e) => tracing::warn!("Skipping unreadable ranking weights: {}", e),
+            }
+        }
+        *store.weights.write() = weights;
+        Ok(store)
+    }
+
+    fn cf(&self) -> &ColumnFamily {
+        self.db
+            .cf_handle(CF_RANKING)
+            .expect("ranking CF must exist")
+    }
+
+    /// Weights in effect for `tenant` (`None` = default tenant)
+    pub fn weights(&self, tenant: Option<&str>) -> RankingWeights {
+        self.weights
+            .read()
+            .get(tenant_key(tenant))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Weights in effect for a stored user id or team namespace
+    pub fn weights_for(&self, user_id: &str) -> RankingWeights {
+        self.weights(tenant::tenant_of(user_id))
+    }
+
+    /// Whether `tenant` has weights of its own
+    pub fn is_configured(&self, tenant: Option<&str>) -> bool {
+        self.weights.read().contains_key(tenant_key(tenant))
+    }
+
+    /// Set `tenant`'s weights
+    pub fn set(&self, tenant: Option<&str>, weights: RankingWeights) -> Result<()> {
+        weights.validate()?;
+        let key = tenant_key(tenant);
+        self.db
+            .put_cf(self.cf(), key.as_bytes(), serde_json::to_vec(&weights)?)?;
+        self.weights.write().insert(key.to_string(), weights);
+        Ok(())
+    }
+
+    /// Drop `tenant`'s weights so it uses the defaults; false if it had none
+    pub fn reset(&self, tenant: Option<&str>) -> Result<bool> {
+        let key = tenant_key(tenant);
+        let mut weights = self.weights.write();
+        if !weights.contains_key(key) {
+            return Ok(false);
+        }
+        self.db.delete_cf(self.cf(), key.as_bytes())?;
+        weights.remove(key);
+        Ok(true)
+    }
+
+    /// Every tenant with weights of its own
+    pub fn list(&self) -> BTreeMap<String, RankingWeights> {
+        self.weights
+            .read()
+            .iter()
+            .map(|(tenant, w)| (tenant.clone(), *w))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_store(path: &std::path::Path) -> RankingStore {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let mut cfs = vec![ColumnFamilyDescriptor::new("default", Options::default())];
+        cfs.extend(RankingStore::cf_descriptors());
+        let db = DB::open_cf_descriptors(&opts, path, cfs).unwrap();
+        RankingStore::new(Arc::new(db)).unwrap()
+    }
+
+    #[test]
+    fn test_weights_per_tenant() {
+        let dir = tempfile::tempdir().unwrap();
+        let tuned = RankingWeights {
+            recency: 0.5,
+            calibrate: true,
+            ..Default::default()
+        };
+        {
+            let store = open_store(dir.path());
+            store.set(Some("acme"), tuned).unwrap();
+            assert!(store
+                .set(
+                    None,
+                    RankingWeights {
+                        importance: 2.0,
+                        ..Default::default()
+                    }
+                )
+                .is_err());
+        }
+
+        let store = open_store(dir.path());
+        assert_eq!(store.weights_for("acme~alice"), tuned);
+        assert_eq!(store.weights_for("teams/acme~eng"), tuned);
+        assert_eq!(store.weights_for("alice"), RankingWeights::default());
+        assert!(store.is_configured(Some("acme")));
+        assert!(!store.is_configured(None));
+
+        assert!(store.reset(Some("acme")).unwrap());
+        assert!(!store.reset(Some("acme")).unwrap());
+        assert_eq!(store.weights_for("acme~alice"), RankingWeights::default());
+    }
+}
@@ -0,0 +1,198 @@
+//! Per-Key Rate Limiting
+//!
+//! The governor layer in main.rs limits requests per client IP, which does
+//! nothing when every cortex and integration calls from the same host. This
+//! limiter runs after authentication and gives each API key its own token
+//! bucket; all keys of a tenant (see [`crate::tenant`]) share one, so a
+//! tenant can't multiply its budget by minting keys.
+//!
+//! ```text
+//! SHODH_KEY_RATE_LIMIT=50    # requests per second per key/tenant (0 = off)
+//! SHODH_KEY_RATE_BURST=100   # bucket size (default: 2x the rate)
+//! ```
+//!
+//! Throttled requests get 429 with `Retry-After` and are counted in
+//! `shodh_rate_limited_total`.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use dashmap::DashMap;
+
+use crate::auth::AuthenticatedKey;
+use crate::config::ServerConfig;
+use crate::errors::ErrorResponse;
+use crate::metrics::RATE_LIMITED_TOTAL;
+
+/// Burst to use for `rate`: `burst` if set, otherwise twice the rate
+pub fn burst_or_default(rate: u64, burst: u32) -> u32 {
+    if burst > 0 {
+        burst
+    } else {
+        rate.saturating_mul(2).min(u32::MAX as u64) as u32
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token buckets per API key or tenant
+pub struct KeyRateLimiter {
+    rate: f64,
+    burst: f64,
+    buckets: DashMap<String, Bucket>,
+}
+
+impl KeyRateLimiter {
+    /// Limiter allowing `rate_per_second` requests per subject (0 = off)
+    pub fn new(rate_per_second: u64, burst: u32) -> Self {
+        Self {
+            rate: rate_per_second as f64,
+            burst: burst_or_default(rate_per_second, burst).max(1) as f64,
+            buckets: DashMap::new(),
+        }
+    }
+
+    pub fn from_config(config: &ServerConfig) -> Self {
+        Self::new(
+            config.key_rate_limit_per_second,
+            config.key_rate_limit_burst,
+        )
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.rate > 0.0
+    }
+
+    /// Take a token for `subject`, or return how long until one is free
+    pub fn check(&self, subject: &str) -> Result<(), Duration> {
+        self.check_at(subject, Instant::now())
+    }
+
+    fn check_at(&self, subject: &str, now: Instant) -> Result<(), Duration> {
+        if !self.enabled() {
+            return Ok(());
+        }
+        let mut bucket = self
+            .buckets
+            .entry(subject.to_string())
+            .or_insert_with(|| Bucket {
+                tokens: self.burst,
+                updated: now,
+            });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate))
+        }
+    }
+}
+
+/// Bucket a request draws from: its tenant, else its key
+fn subject(key: Option<&AuthenticatedKey>) -> (&'static str, String) {
+    match key {
+        Some(AuthenticatedKey {
+            tenant: Some(tenant),
+            ..
+        }) => ("tenant", tenant.clone()),
+        Some(key) => ("key", key.key_id.clone()),
+        None => ("key", "anonymous".to_string()),
+    }
+}
+
+/// Middleware: 429 requests over their key's or tenant's rate
+pub async fn limit_by_key(
+    State(limiter): State<Arc<KeyRateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !limiter.enabled() {
+        return next.run(request).await;
+    }
+    let (kind, name) = subject(request.extensions().get::<AuthenticatedKey>());
+    let Err(wait) = limiter.check(&format!("{kind}:{name}")) else {
+        return next.run(request).await;
+    };
+
+    RATE_LIMITED_TOTAL.with_label_values(&[kind, &name]).inc();
+    // Whole seconds, rounded up: Retry-After has no finer resolution
+    let retry_after = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+    tracing::debug!(%kind, %name, retry_after, "Request rate limited");
+    let body = ErrorResponse {
+        code: "RATE_LIMITED".to_string(),
+        message: format!("Rate limit exceeded for this {kind}; retry in {retry_after}s"),
+        details: None,
+        request_id: None,
+    };
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(header::RETRY_AFTER, retry_after.to_string())],
+        Json(body),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_refill() {
+        let limiter = KeyRateLimiter::new(10, 3);
+        let start = Instant::now();
+        for _ in 0..3 {
+            assert!(limiter.check_at("key:a", start).is_ok());
+        }
+        let wait = limiter.check_at("key:a", start).unwrap_err();
+        assert!(wait <= Duration::from_millis(100) && wait > Duration::ZERO);
+
+        // Other subjects have their own bucket
+        assert!(limiter.check_at("key:b", start).is_ok());
+
+        // 100ms at 10/s buys one more request
+        let later = start + Duration::from_millis(100);
+        assert!(limiter.check_at("key:a", later).is_ok());
+        assert!(limiter.check_at("key:a", later).is_err());
+    }
+
+    #[test]
+    fn test_disabled_and_defaults() {
+        let off = KeyRateLimiter::new(0, 0);
+        assert!(!off.enabled());
+        for _ in 0..1000 {
+            assert!(off.check("key:a").is_ok());
+        }
+        assert_eq!(burst_or_default(50, 0), 100);
+        assert_eq!(burst_or_default(50, 7), 7);
+    }
+
+    #[test]
+    fn test_tenant_keys_share_a_bucket() {
+        let key = |key_id: &str, tenant: Option<&str>| AuthenticatedKey {
+            key_id: key_id.to_string(),
+            admin: false,
+            tenant: tenant.map(str::to_string),
+            scopes: Vec::new(),
+        };
+        assert_eq!(
+            subject(Some(&key("k1", Some("acme")))),
+            subject(Some(&key("k2", Some("acme"))))
+        );
+        assert_ne!(
+            subject(Some(&key("k1", None))),
+            subject(Some(&key("k2", None)))
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//! Sleep-Phase Consolidation Scheduling
+//!
+//! Heavy maintenance (fact extraction, replay, duplicate merging, forgetting
+//! curve, index repair/re-embedding, run summaries) does full storage scans
+//! that hurt request latency. Like the brain, which consolidates during sleep,
+//! the server can defer that work to a "sleep" phase:
+//!
+//! - a nightly window (`SHODH_SLEEP_WINDOW=02:00-05:00`, UTC), and/or
+//! - after a period without API traffic (`SHODH_SLEEP_IDLE_MINUTES=30`).
+//!
+//! When neither is configured, heavy cycles keep running on the fixed cadence
+//! (every 6th maintenance cycle). A starvation guard forces a heavy cycle if
+//! none has run for `SHODH_SLEEP_MAX_GAP_HOURS`, so a busy server still
+//! consolidates eventually.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use chrono::{DateTime, Timelike, Utc};
+use parking_lot::RwLock;
+use serde::Serialize;
+
+/// Minimum seconds between two heavy cycles inside the same sleep phase
+const SLEEP_MIN_HEAVY_GAP_SECS: i64 = 3600;
+
+/// When heavy maintenance may run
+#[derive(Debug, Clone, Default)]
+pub struct SleepConfig {
+    /// Nightly window as minutes after midnight UTC (start, end); may wrap midnight
+    pub window: Option<(u32, u32)>,
+    /// Minutes without API requests after which the server counts as asleep (0 = off)
+    pub idle_minutes: u64,
+    /// Force a heavy cycle when none has run for this many hours (0 = never)
+    pub max_gap_hours: u64,
+}
+
+impl SleepConfig {
+    /// Read SHODH_SLEEP_WINDOW, SHODH_SLEEP_IDLE_MINUTES and SHODH_SLEEP_MAX_GAP_HOURS
+    pub fn from_env() -> Self {
+        let window = std::env::var("SHODH_SLEEP_WINDOW")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .and_then(|v| {
+                let parsed = parse_window(&v);
+                if parsed.is_none() {
+                    tracing::warn!(
+                        "Ignoring invalid SHODH_SLEEP_WINDOW '{}', expected HH:MM-HH:MM",
+                        v
+                    );
+                }
+                parsed
+            });
+        let idle_minutes = std::env::var("SHODH_SLEEP_IDLE_MINUTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+        let max_gap_hours = std::env::var("SHODH_SLEEP_MAX_GAP_HOURS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(24);
+        Self {
+            window,
+            idle_minutes,
+            max_gap_hours,
+        }
+    }
+
+    /// Whether heavy work is restricted to sleep phases
+    pub fn is_enabled(&self) -> bool {
+        self.window.is_some() || self.idle_minutes > 0
+    }
+}
+
+/// Parse `HH:MM-HH:MM` into minutes after midnight
+fn parse_window(value: &str) -> Option<(u32, u32)> {
+    let minutes = |hm: &str| -> Option<u32> {
+        let (h, m) = hm.trim().split_once(':')?;
+        let (h, m): (u32, u32) = (h.parse().ok()?, m.parse().ok()?);
+        (h < 24 && m < 60).then_some(h * 60 + m)
+    };
+    let (start, end) = value.split_once('-')?;
+    let (start, end) = (minutes(start)?, minutes(end)?);
+    (start != end).then_some((start, end))
+}
+
+/// Why the server is (or isn't) in a sleep phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SleepPhase {
+    Awake,
+    /// Inside the nightly window
+    Window,
+    /// No API traffic for `idle_minutes`
+    Idle,
+}
+
+/// Totals of the last completed heavy cycle
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HeavyCycleSummary {
+    pub cycle: u64,
+    pub users: usize,
+    pub memories_processed: usize,
+    pub facts_extracted: usize,
+    pub facts_reinforced: usize,
+    pub edges_strengthened: usize,
+    pub edges_pruned: usize,
+    pub duration_ms: u64,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Progress of background consolidation, served at /api/admin/consolidation
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConsolidationStatus {
+    pub running: bool,
+    /// "heavy" or "light" for the running (or last) cycle
+    pub cycle_kind: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub users_total: usize,
+    pub users_done: usize,
+    pub heavy_cycles_completed: u64,
+    pub last_heavy: Option<HeavyCycleSummary>,
+}
+
+/// Decides when heavy cycles run and tracks their progress
+pub struct SleepScheduler {
+    config: SleepConfig,
+    /// Unix seconds of the last API request
+    last_activity: AtomicI64,
+    /// Unix seconds of the last heavy cycle start (0 = never)
+    last_heavy: AtomicI64,
+    status: RwLock<ConsolidationStatus>,
+}
+
+impl SleepScheduler {
+    pub fn new(config: SleepConfig) -> Self {
+        Self {
+            config,
+            last_activity: AtomicI64::new(Utc::now().timestamp()),
+            last_heavy: AtomicI64::new(0),
+            status: RwLock::new(ConsolidationStatus::default()),
+        }
+    }
+
+    pub fn config(&self) -> &SleepConfig {
+        &self.config
+    }
+
+    /// Note API traffic (postpones the idle sleep phase)
+    pub fn record_activity(&self) {
+        self.last_activity
+            .store(Utc::now().timestamp(), Ordering::Relaxed);
+    }
+
+    /// Current phase at `now`
+    pub fn phase(&self, now: DateTime<Utc>) -> SleepPhase {
+        if let Some((start, end)) = self.config.window {
+            let minute = now.hour() * 60 + now.minute();
+            let inside = if start < end {
+                minute >= start && minute < end
+            } else {
+                minute >= start || minute < end
+            };
+            if inside {
+                return SleepPhase::Window;
+            }
+        }
+        if self.config.idle_minutes > 0 {
+            let idle_secs = now.timestamp() - self.last_activity.load(Ordering::Relaxed);
+            if idle_secs >= (self.config.idle_minutes * 60) as i64 {
+                return SleepPhase::Idle;
+            }
+        }
+        SleepPhase::Awake
+    }
+
+    /// Whether maintenance cycle `cycle` should do heavy work
+    pub fn should_run_heavy(&self, cycle: u64, now: DateTime<Utc>) -> bool {
+        if !self.config.is_enabled() {
+            return cycle % 6 == 0;
+        }
+        let last = self.last_heavy.load(Ordering::Relaxed);
+        let since_last = now.timestamp() - last;
+        if last > 0
+            && self.config.max_gap_hours > 0
+            && since_last >= (self.config.max_gap_hours * 3600) as i64
+        {
+            tracing::info!(
+                "No sleep phase in {}h, forcing heavy maintenance",
+                self.config.max_gap_hours
+            );
+            return true;
+        }
+        self.phase(now) != SleepPhase::Awake
+            && (last == 0 || since_last >= SLEEP_MIN_HEAVY_GAP_SECS)
+    }
+
+    /// Mark the start of a maintenance cycle over `users` users
+    pub fn begin_cycle(&self, heavy: bool, users: usize) {
+        let now = Utc::now();
+        if heavy {
+            self.last_heavy.store(now.timestamp(), Ordering::Relaxed);
+        }
+        let mut status = self.status.write();
+        status.running = true;
+        status.cycle_kind = if heavy { "heavy" } else { "light" }.to_string();
+        status.started_at = Some(now);
+        status.users_total = users;
+        status.users_done = 0;
+    }
+
+    /// One more user finished in the running cycle
+    pub fn user_done(&self) {
+        self.status.write().users_done += 1;
+    }
+
+    /// Mark the running cycle finished; `summary` is set for heavy cycles
+    pub fn finish_cycle(&self, summary: Option<HeavyCycleSummary>) {
+        let mut status = self.status.write();
+        status.running = false;
+        if let Some(summary) = summary {
+            status.heavy_cycles_completed += 1;
+            status.last_heavy = Some(summary);
+        }
+    }
+
+    pub fn status(&self) -> ConsolidationStatus {
+        self.status.read().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_window_and_idle_phases() {
+        assert_eq!(parse_window("23:30-02:00"), Some((1410, 120)));
+        assert_eq!(parse_window("25:00-02:00"), None);
+
+        let scheduler = SleepScheduler::new(SleepConfig {
+            window: parse_window("23:30-02:00"),
+            idle_minutes: 30,
+            max_gap_hours: 24,
+        });
+        let night = Utc.with_ymd_and_hms(2026, 1, 1, 1, 0, 0).unwrap();
+        let day = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(scheduler.phase(night), SleepPhase::Window);
+
+        scheduler
+            .last_activity
+            .store(day.timestamp() - 60, Ordering::Relaxed);
+        assert_eq!(scheduler.phase(day), SleepPhase::Awake);
+        assert!(!scheduler.should_run_heavy(6, day));
+
+        scheduler
+            .last_activity
+            .store(day.timestamp() - 31 * 60, Ordering::Relaxed);
+        assert_eq!(scheduler.phase(day), SleepPhase::Idle);
+        assert!(scheduler.should_run_heavy(1, day));
+    }
+
+    #[test]
+    fn test_fixed_cadence_without_sleep_config() {
+        let scheduler = SleepScheduler::new(SleepConfig::default());
+        let now = Utc::now();
+        assert!(scheduler.should_run_heavy(0, now));
+        assert!(!scheduler.should_run_heavy(1, now));
+        assert!(scheduler.should_run_heavy(12, now));
+    }
+}
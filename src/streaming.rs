@@ -41,7 +41,9 @@ use uuid::Uuid;
 
 use crate::embeddings::{NerEntity, NeuralNer};
 use crate::graph_memory::GraphMemory;
-use crate::memory::{Experience, ExperienceType, MemorySystem, Query as MemoryQuery};
+use crate::memory::{
+    Experience, ExperienceType, MemorySystem, Provenance, ProvenanceSource, Query as MemoryQuery,
+};
 use crate::similarity::cosine_similarity;
 
 /// Case-insensitive substring search without allocation.
@@ -1066,6 +1068,7 @@ impl StreamingMemoryExtractor {
             for (k, v) in msg.metadata {
                 string_metadata.insert(k, v.to_string());
             }
+            Provenance::new(ProvenanceSource::Streaming).write_to(&mut string_metadata);
             // Clone tags before they're consumed (needed for Experience.tags)
             let tags: Vec<String> = msg.tags.clone();
 
@@ -1252,7 +1255,7 @@ impl StreamingMemoryExtractor {
             let guard = memory_for_embed.read();
             guard
                 .compute_embedding(&content_for_embed)
-                .unwrap_or_else(|_| vec![0.0; 384])
+                .unwrap_or_else(|_| vec![0.0; guard.embedding_info().dimension])
         })
         .await
         .ok()?;
@@ -0,0 +1,138 @@
+//! Suppression Rules
+//!
+//! Rules added through /api/suppress keep memories out of a user's retrieval
+//! without deleting them (see [`crate::memory::suppression`]). A rule names
+//! one memory or a content pattern.
+//!
+//! Rules live in the `suppressions` column family of the shared DB, keyed
+//! `{user_id}:{rule_id}` so a user's rules are one prefix scan and are removed
+//! with the rest of their data on purge. A loaded memory system picks up a
+//! change immediately (see `MultiUserMemoryManager::apply_suppressions`).
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, Options, DB};
+
+use crate::memory::suppression::SuppressionRule;
+
+/// Column family holding suppression rules
+pub const CF_SUPPRESSIONS: &str = "suppressions";
+
+fn user_prefix(user_id: &str) -> String {
+    format!("{user_id}:")
+}
+
+fn rule_key(user_id: &str, rule_id: &str) -> String {
+    format!("{user_id}:{rule_id}")
+}
+
+/// Persistent per-user suppression rules
+pub struct SuppressionStore {
+    db: Arc<DB>,
+}
+
+impl SuppressionStore {
+    /// Column family descriptors required by the SuppressionStore.
+    /// The caller must include these (plus `"default"`) when opening the shared DB.
+    pub fn cf_descriptors() -> Vec<ColumnFamilyDescriptor> {
+        let mut cf_opts = Options::default();
+        cf_opts.create_if_missing(true);
+        vec![ColumnFamilyDescriptor::new(CF_SUPPRESSIONS, cf_opts)]
+    }
+
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db }
+    }
+
+    fn cf(&self) -> &ColumnFamily {
+        self.db
+            .cf_handle(CF_SUPPRESSIONS)
+            .expect("suppressions CF must exist")
+    }
+
+    /// Store a rule for `user_id`
+    pub fn add(&self, user_id: &str, rule: &SuppressionRule) -> Result<()> {
+        self.db
+            .put_cf(
+                self.cf(),
+                rule_key(user_id, &rule.id).as_bytes(),
+                serde_json::to_vec(rule)?,
+            )
+            .context("Failed to store suppression rule")
+    }
+
+    /// A user's rules, oldest first
+    pub fn list(&self, user_id: &str) -> Result<Vec<SuppressionRule>> {
+        let prefix = user_prefix(user_id);
+        let mut rules = Vec::new();
+        for item in self.db.prefix_iterator_cf(self.cf(), prefix.as_bytes()) {
+            let (key, value) = item.context("Failed to read suppression rules")?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            match serde_json::from_slice::<SuppressionRule>(&value) {
+                Ok(rule) => rules.push(rule),
+                Err(e) => tracing::warn!("Skipping unreadable suppression rule: {}", e),
+            }
+        }
+        rules.sort_by_key(|r| r.created_at);
+        Ok(rules)
+    }
+
+    /// Delete one of a user's rules; false if it didn't exist
+    pub fn remove(&self, user_id: &str, rule_id: &str) -> Result<bool> {
+        let key = rule_key(user_id, rule_id);
+        if self.db.get_cf(self.cf(), key.as_bytes())?.is_none() {
+            return Ok(false);
+        }
+        self.db.delete_cf(self.cf(), key.as_bytes())?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn open_store(path: &std::path::Path) -> SuppressionStore {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let mut cfs = vec![ColumnFamilyDescriptor::new("default", Options::default())];
+        cfs.extend(SuppressionStore::cf_descriptors());
+        let db = DB::open_cf_descriptors(&opts, path, cfs).unwrap();
+        SuppressionStore::new(Arc::new(db))
+    }
+
+    fn rule(pattern: &str) -> SuppressionRule {
+        SuppressionRule {
+            id: uuid::Uuid::new_v4().to_string(),
+            memory_id: None,
+            pattern: Some(pattern.to_string()),
+            reason: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_rules_are_per_user() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = open_store(dir.path());
+        let standup = rule("standup");
+        store.add("alice", &standup).unwrap();
+        store.add("alice", &rule("wifi")).unwrap();
+        store.add("alice2", &rule("lunch")).unwrap();
+
+        let rules = store.list("alice").unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].pattern.as_deref(), Some("standup"));
+        assert_eq!(store.list("alice2").unwrap().len(), 1);
+
+        assert!(store.remove("alice", &standup.id).unwrap());
+        assert!(!store.remove("alice", &standup.id).unwrap());
+        assert!(!store.remove("alice2", &rules[1].id).unwrap());
+        assert_eq!(store.list("alice").unwrap().len(), 1);
+    }
+}
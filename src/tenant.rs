@@ -0,0 +1,317 @@
+//! Tenants
+//!
+//! API keys listed in SHODH_TENANT_API_KEYS belong to a tenant. Every user
+//! and team a tenant key names is stored under a tenant-qualified id
+//! (`{tenant}~{user_id}`), so two tenants can both have a user "alice"
+//! without sharing memories, todos, files, feedback or graph. The rewrite
+//! happens once per request (see `handlers::tenants::scope_tenant`); handlers
+//! and stores only ever see qualified ids.
+//!
+//! Keys from SHODH_API_KEYS / SHODH_DEV_API_KEY form the default tenant,
+//! whose ids are stored unqualified as before, so existing deployments keep
+//! their data layout.
+//!
+//! The rewrite alone would leave isolation to every field the middleware
+//! recognizes, so each request is also [`confine`]d to its tenant: opening a
+//! user's memory or graph for an id of another tenant fails with
+//! [`TenantMismatch`] (403), whatever path the id took to get there.
+//!
+//! Usage (requests, reads, writes, bytes) is counted per tenant since
+//! startup and reported by GET /api/admin/tenants.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::Serialize;
+
+/// Separates tenant and user in a qualified id; clients can't send it
+pub const SEPARATOR: char = '~';
+
+/// Name usage of keys outside any tenant is reported under
+pub const DEFAULT_TENANT: &str = "default";
+
+const MAX_TENANT_LENGTH: usize = 64;
+
+/// Check a tenant name: 1-64 of alphanumeric, '-', '_'
+pub fn validate_tenant(tenant: &str) -> Result<()> {
+    if tenant.is_empty() || tenant.len() > MAX_TENANT_LENGTH {
+        return Err(anyhow!(
+            "tenant must be 1-{MAX_TENANT_LENGTH} characters, got {}",
+            tenant.len()
+        ));
+    }
+    if !tenant
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(anyhow!(
+            "tenant contains invalid characters (allowed: alphanumeric, -, _)"
+        ));
+    }
+    if tenant == DEFAULT_TENANT {
+        return Err(anyhow!("tenant name '{DEFAULT_TENANT}' is reserved"));
+    }
+    Ok(())
+}
+
+/// Tenant-qualified id for `id` (a user or team id)
+pub fn qualify(tenant: &str, id: &str) -> String {
+    format!("{tenant}{SEPARATOR}{id}")
+}
+
+/// Whether a stored id belongs to `tenant` (`None` = default tenant)
+pub fn owns(tenant: Option<&str>, id: &str) -> bool {
+    match tenant {
+        Some(tenant) => id
+            .strip_prefix(tenant)
+            .is_some_and(|rest| rest.starts_with(SEPARATOR)),
+        None => !id.contains(SEPARATOR),
+    }
+}
+
+/// Tenant owning a stored user id or team namespace (`None` = default tenant)
+pub fn tenant_of(id: &str) -> Option<&str> {
+    let id = id.rsplit('/').next().unwrap_or(id);
+    id.split_once(SEPARATOR).map(|(tenant, _)| tenant)
+}
+
+tokio::task_local! {
+    /// Tenant the request being served is confined to (`None` = default tenant)
+    static REQUEST_TENANT: Option<String>;
+}
+
+/// A confined request named an id of another tenant
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("'{id}' belongs to another tenant")]
+pub struct TenantMismatch {
+    pub id: String,
+}
+
+/// Serve a request confined to `tenant`'s data (`None` = default tenant)
+pub async fn confine<F: Future>(tenant: Option<String>, request: F) -> F::Output {
+    REQUEST_TENANT.scope(tenant, request).await
+}
+
+/// Refuse a stored user id or team namespace outside the tenant the current
+/// request is confined to. Unconfined work (admin keys, deployments without
+/// tenants, background tasks) may use any id.
+pub fn check_access(id: &str) -> Result<(), TenantMismatch> {
+    match REQUEST_TENANT.try_with(|tenant| tenant_of(id) == tenant.as_deref()) {
+        Ok(false) => Err(TenantMismatch { id: id.to_string() }),
+        _ => Ok(()),
+    }
+}
+
+/// Remove `"{tenant}~` prefixes from JSON string values, so responses show
+/// the ids the client sent. Escaped quotes inside strings are left alone.
+pub fn strip_qualifiers(tenant: &str, json: &[u8]) -> Vec<u8> {
+    let mut needle = Vec::with_capacity(tenant.len() + 2);
+    needle.push(b'"');
+    needle.extend_from_slice(tenant.as_bytes());
+    needle.push(SEPARATOR as u8);
+
+    let mut out = Vec::with_capacity(json.len());
+    let mut i = 0;
+    while i < json.len() {
+        if json[i..].starts_with(&needle) && (i == 0 || json[i - 1] != b'\\') {
+            out.push(b'"');
+            i += needle.len();
+        } else {
+            out.push(json[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Request counters for one tenant
+#[derive(Debug, Default)]
+pub struct TenantUsage {
+    requests: AtomicU64,
+    reads: AtomicU64,
+    writes: AtomicU64,
+    errors: AtomicU64,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+    /// Unix millis of the latest request (0 = none)
+    last_request_ms: AtomicI64,
+}
+
+/// Point-in-time copy of a tenant's counters
+#[derive(Debug, Clone, Serialize)]
+pub struct TenantUsageSnapshot {
+    pub tenant: String,
+    pub requests: u64,
+    pub reads: u64,
+    pub writes: u64,
+    /// Responses with a 4xx/5xx status
+    pub errors: u64,
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    pub last_request_at: Option<DateTime<Utc>>,
+}
+
+/// One finished request, as counted against its tenant
+#[derive(Debug, Clone, Copy)]
+pub struct RequestRecord {
+    pub write: bool,
+    pub error: bool,
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+}
+
+/// Per-tenant usage since startup
+#[derive(Debug, Default)]
+pub struct TenantRegistry {
+    usage: DashMap<String, Arc<TenantUsage>>,
+}
+
+impl TenantRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a request against `tenant` (`None` = default tenant)
+    pub fn record(&self, tenant: Option<&str>, request: RequestRecord) {
+        let tenant = tenant.unwrap_or(DEFAULT_TENANT);
+        let usage = match self.usage.get(tenant) {
+            Some(usage) => usage.clone(),
+            None => self.usage.entry(tenant.to_string()).or_default().clone(),
+        };
+        usage.requests.fetch_add(1, Ordering::Relaxed);
+        if request.write {
+            usage.writes.fetch_add(1, Ordering::Relaxed);
+        } else {
+            usage.reads.fetch_add(1, Ordering::Relaxed);
+        }
+        if request.error {
+            usage.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        usage
+            .bytes_in
+            .fetch_add(request.bytes_in, Ordering::Relaxed);
+        usage
+            .bytes_out
+            .fetch_add(request.bytes_out, Ordering::Relaxed);
+        usage
+            .last_request_ms
+            .store(Utc::now().timestamp_millis(), Ordering::Relaxed);
+    }
+
+    /// Counters of every tenant seen since startup, by name
+    pub fn snapshot(&self) -> Vec<TenantUsageSnapshot> {
+        let mut snapshots: Vec<_> = self
+            .usage
+            .iter()
+            .map(|entry| {
+                let usage = entry.value();
+                let last = usage.last_request_ms.load(Ordering::Relaxed);
+                TenantUsageSnapshot {
+                    tenant: entry.key().clone(),
+                    requests: usage.requests.load(Ordering::Relaxed),
+                    reads: usage.reads.load(Ordering::Relaxed),
+                    writes: usage.writes.load(Ordering::Relaxed),
+                    errors: usage.errors.load(Ordering::Relaxed),
+                    bytes_in: usage.bytes_in.load(Ordering::Relaxed),
+                    bytes_out: usage.bytes_out.load(Ordering::Relaxed),
+                    last_request_at: (last > 0)
+                        .then(|| DateTime::from_timestamp_millis(last))
+                        .flatten(),
+                }
+            })
+            .collect();
+        snapshots.sort_by(|a, b| a.tenant.cmp(&b.tenant));
+        snapshots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qualified_ids() {
+        let id = qualify("acme", "alice");
+        assert_eq!(id, "acme~alice");
+        assert!(owns(Some("acme"), &id));
+        assert!(!owns(Some("acm"), &id));
+        assert!(!owns(Some("globex"), &id));
+        assert!(!owns(None, &id));
+        assert!(owns(None, "alice"));
+        assert_eq!(tenant_of(&id), Some("acme"));
+        assert_eq!(tenant_of("teams/acme~eng"), Some("acme"));
+        assert_eq!(tenant_of("alice"), None);
+    }
+
+    #[tokio::test]
+    async fn test_confined_requests_only_reach_their_tenant() {
+        assert!(check_access("globex~alice").is_ok());
+        confine(Some("acme".to_string()), async {
+            assert!(check_access("acme~alice").is_ok());
+            assert!(check_access("teams/acme~eng").is_ok());
+            assert!(check_access("globex~alice").is_err());
+            assert!(check_access("teams/globex~eng").is_err());
+            assert!(check_access("alice").is_err());
+        })
+        .await;
+        confine(None, async {
+            assert!(check_access("alice").is_ok());
+            assert!(check_access("acme~alice").is_err());
+        })
+        .await;
+    }
+
+    #[test]
+    fn test_validate_tenant() {
+        assert!(validate_tenant("acme-corp_1").is_ok());
+        assert!(validate_tenant("").is_err());
+        assert!(validate_tenant("acme~x").is_err());
+        assert!(validate_tenant("a/b").is_err());
+        assert!(validate_tenant(DEFAULT_TENANT).is_err());
+        assert!(validate_tenant(&"a".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn test_strip_qualifiers() {
+        let json = br#"{"user_id":"acme~alice","users":["acme~bob"],"content":"say \"acme~x\""}"#;
+        assert_eq!(
+            strip_qualifiers("acme", json),
+            br#"{"user_id":"alice","users":["bob"],"content":"say \"acme~x\""}"#.to_vec()
+        );
+        assert_eq!(strip_qualifiers("globex", json), json.to_vec());
+    }
+
+    #[test]
+    fn test_usage_counters() {
+        let registry = TenantRegistry::new();
+        let read = RequestRecord {
+            write: false,
+            error: false,
+            bytes_in: 10,
+            bytes_out: 100,
+        };
+        registry.record(Some("acme"), read);
+        registry.record(
+            Some("acme"),
+            RequestRecord {
+                write: true,
+                error: true,
+                ..read
+            },
+        );
+        registry.record(None, read);
+
+        let snapshot = registry.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        let acme = &snapshot[0];
+        assert_eq!(acme.tenant, "acme");
+        assert_eq!((acme.requests, acme.reads, acme.writes), (2, 1, 1));
+        assert_eq!((acme.errors, acme.bytes_in, acme.bytes_out), (1, 20, 200));
+        assert!(acme.last_request_at.is_some());
+        assert_eq!(snapshot[1].tenant, DEFAULT_TENANT);
+    }
+}
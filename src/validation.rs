@@ -6,6 +6,23 @@ use regex::Regex;
 
 /// Maximum lengths for security
 pub const MAX_USER_ID_LENGTH: usize = 128;
+
+/// Directories under the storage root that aren't users' stores; a user with
+/// one of these ids would share (or replace) server data
+pub const RESERVED_USER_IDS: &[&str] = &[
+    "audit_logs",
+    "backups",
+    "feedback",
+    "files",
+    "prospective",
+    "semantic_facts",
+    "shared",
+    "teams",
+    "todos",
+];
+
+/// Suffix of storage directories renamed by column-family migrations
+const MIGRATED_DIR_SUFFIX: &str = ".pre_cf_migration";
 pub const MAX_CONTENT_LENGTH: usize = 50_000; // 50KB
 pub const MAX_PATTERN_LENGTH: usize = 256; // Max regex pattern length
 pub const MAX_ENTITY_LENGTH: usize = 256; // Max entity name length
@@ -20,6 +37,15 @@ pub fn validate_user_id(user_id: &str) -> Result<()> {
         return Err(anyhow!("user_id cannot be empty"));
     }
 
+    // Tenant-qualified id: "{tenant}~{user_id}" (see `crate::tenant`)
+    if let Some((tenant, user_id)) = user_id.split_once(crate::tenant::SEPARATOR) {
+        crate::tenant::validate_tenant(tenant)?;
+        if user_id.contains(crate::tenant::SEPARATOR) {
+            return Err(anyhow!("user_id contains more than one tenant separator"));
+        }
+        return validate_user_id(user_id);
+    }
+
     if user_id.len() > MAX_USER_ID_LENGTH {
         return Err(anyhow!(
             "user_id too long: {} chars (max: {})",
@@ -50,6 +76,15 @@ pub fn validate_user_id(user_id: &str) -> Result<()> {
         return Err(anyhow!("user_id cannot start or end with a dot"));
     }
 
+    // Reject names of server directories under the storage root
+    if RESERVED_USER_IDS
+        .iter()
+        .any(|reserved| user_id.eq_ignore_ascii_case(reserved))
+        || user_id.ends_with(MIGRATED_DIR_SUFFIX)
+    {
+        return Err(anyhow!("user_id '{}' is reserved", user_id));
+    }
+
     // Reject absolute paths — PathBuf::join with an absolute path ignores the base
     if std::path::Path::new(user_id).is_absolute() {
         return Err(anyhow!("user_id cannot be an absolute path"));
@@ -132,8 +167,8 @@ pub fn validate_embeddings(embeddings: &[f32]) -> Result<()> {
         return Err(anyhow!("embeddings cannot be empty"));
     }
 
-    // Common embedding dimensions: 384, 512, 768, 1024, 1536
-    let valid_dims = [128, 256, 384, 512, 768, 1024, 1536, 2048];
+    // Common embedding dimensions: 384, 512, 768, 1024, 1536, 3072
+    let valid_dims = [128, 256, 384, 512, 768, 1024, 1536, 2048, 3072];
     if !valid_dims.contains(&embeddings.len()) {
         return Err(anyhow!(
             "Unusual embedding dimension: {}. Common dimensions: {:?}",
@@ -356,6 +391,20 @@ mod tests {
         assert!(validate_user_id("").is_err()); // empty
         assert!(validate_user_id("user/123").is_err()); // invalid char
         assert!(validate_user_id(&"a".repeat(200)).is_err()); // too long
+        assert!(validate_user_id("teams").is_err()); // team namespaces
+        assert!(validate_user_id("Shared").is_err()); // shared DB
+        assert!(validate_user_id("files.pre_cf_migration").is_err());
+        assert!(validate_user_id("acme~teams").is_err());
+    }
+
+    #[test]
+    fn test_tenant_qualified_user_id() {
+        assert!(validate_user_id("acme~alice").is_ok());
+        assert!(validate_user_id("acme~").is_err());
+        assert!(validate_user_id("~alice").is_err());
+        assert!(validate_user_id("acme~globex~alice").is_err());
+        assert!(validate_user_id("ac/me~alice").is_err());
+        assert!(validate_user_id("acme~..").is_err());
     }
 
     #[test]
@@ -193,7 +193,10 @@ impl VectorIndexBackend {
     /// Load index from file
     pub fn load_from_file(path: &Path, backend_type: BackendType) -> Result<Self> {
         match backend_type {
-            BackendType::Vamana => Ok(Self::Vamana(VamanaIndex::load_from_file(path)?)),
+            BackendType::Vamana => Ok(Self::Vamana(VamanaIndex::load_from_file(
+                path,
+                &VamanaConfig::default(),
+            )?)),
             BackendType::Spann => Ok(Self::Spann(SpannIndex::load_from_file(path)?)),
         }
     }
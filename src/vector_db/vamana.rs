@@ -62,6 +62,10 @@ pub struct VamanaConfig {
     /// Search list size during construction (L in paper)
     pub search_list_size: usize,
 
+    /// Minimum candidate list size at query time (`ef` in HNSW terms).
+    /// Larger values trade latency for recall; 0 searches with a list of `k`.
+    pub search_ef: usize,
+
     /// Alpha parameter for RNG pruning (α in paper, typically 1.2)
     pub alpha: f32,
 
@@ -81,6 +85,7 @@ impl Default for VamanaConfig {
         Self {
             max_degree: 32,                             // R=32 for billion-scale
             search_list_size: 75,                       // L=75 during construction
+            search_ef: 0,                               // List of k at query time
             alpha: 1.2,                                 // Standard α for pruning
             dimension: 384,                             // MiniLM dimension
             use_mmap: true,                             // Disk-based for large datasets
@@ -760,6 +765,7 @@ impl VamanaIndex {
         } else {
             k
         };
+        let search_k = search_k.max(self.config.search_ef);
 
         let candidates = self.greedy_search(query, search_k, entry)?;
 
@@ -1641,6 +1647,33 @@ mod tests {
         assert_eq!(results[0].0, 0); // Closest to [1,0,0,0]
     }
 
+    #[test]
+    fn test_search_ef_widens_candidates_but_returns_k() {
+        let mut index = VamanaIndex::new(VamanaConfig {
+            dimension: 4,
+            max_degree: 3,
+            search_list_size: 10,
+            search_ef: 8,
+            alpha: 1.2,
+            use_mmap: false,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let vectors = vec![
+            vec![1.0, 0.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0, 0.0],
+            vec![0.0, 0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 0.0, 1.0],
+            vec![0.5, 0.5, 0.0, 0.0],
+        ];
+        index.build(vectors).unwrap();
+
+        let results = index.search(&[0.9, 0.1, 0.0, 0.0], 1).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, 0);
+    }
+
     #[test]
     fn test_incremental_repair() {
         let mut index = VamanaIndex::new(VamanaConfig {
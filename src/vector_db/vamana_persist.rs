@@ -286,7 +286,10 @@ impl VamanaIndex {
     ///
     /// Uses mmap for zero-copy access to vectors.
     /// Returns immediately - vectors are demand-paged by OS.
-    pub fn load_from_file(path: &Path) -> Result<Self> {
+    ///
+    /// The graph's shape (dimension, max degree, metric) comes from the file;
+    /// search and build parameters, which aren't persisted, come from `runtime`.
+    pub fn load_from_file(path: &Path, runtime: &VamanaConfig) -> Result<Self> {
         let start = std::time::Instant::now();
 
         if !path.exists() {
@@ -358,8 +361,9 @@ impl VamanaIndex {
 
         let config = VamanaConfig {
             max_degree: header.max_degree as usize,
-            search_list_size: 75, // Default, not persisted
-            alpha: 1.2,           // Default, not persisted
+            search_list_size: runtime.search_list_size,
+            search_ef: runtime.search_ef,
+            alpha: runtime.alpha,
             dimension,
             use_mmap: false, // Loaded into memory
             distance_metric: header.distance_metric_enum(),
@@ -458,8 +462,16 @@ mod tests {
         assert!(VamanaIndex::verify_index_file(&index_path).unwrap());
 
         // Load
-        let loaded = VamanaIndex::load_from_file(&index_path).unwrap();
+        let runtime = VamanaConfig {
+            search_list_size: 120,
+            search_ef: 40,
+            ..Default::default()
+        };
+        let loaded = VamanaIndex::load_from_file(&index_path, &runtime).unwrap();
         assert_eq!(loaded.len(), 5);
+        assert_eq!(loaded.config.search_list_size, 120);
+        assert_eq!(loaded.config.search_ef, 40);
+        assert_eq!(loaded.config.dimension, 4);
 
         // Search should work
         let query = vec![1.0, 0.0, 0.0, 0.0];
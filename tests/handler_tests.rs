@@ -29,6 +29,8 @@ use shodh_memory::{
 // ═══════════════════════════════════════════════════════════════════════
 
 const TEST_KEY: &str = "handler-smoke-test-key";
+const ACME_KEY: &str = "handler-smoke-acme-key";
+const GLOBEX_KEY: &str = "handler-smoke-globex-key";
 static ENV_INIT: Once = Once::new();
 
 fn init_env() {
@@ -36,6 +38,10 @@ fn init_env() {
         // SAFETY: called once before any parallel tests start.
         unsafe {
             std::env::set_var("SHODH_API_KEYS", TEST_KEY);
+            std::env::set_var(
+                "SHODH_TENANT_API_KEYS",
+                format!("acme:{ACME_KEY},globex:{GLOBEX_KEY}"),
+            );
         }
     });
 }
@@ -48,13 +54,19 @@ struct Harness {
 
 impl Harness {
     fn new() -> Self {
+        Self::with_config(|_| {})
+    }
+
+    /// Harness whose server config is adjusted by `configure` before startup
+    fn with_config(configure: impl FnOnce(&mut ServerConfig)) -> Self {
         init_env();
         let dir = TempDir::new().expect("create temp dir");
-        let cfg = ServerConfig {
+        let mut cfg = ServerConfig {
             storage_path: dir.path().to_path_buf(),
             backup_enabled: false,
             ..ServerConfig::default()
         };
+        configure(&mut cfg);
         let mgr = MultiUserMemoryManager::new(dir.path().to_path_buf(), cfg)
             .expect("create MultiUserMemoryManager");
         Self {
@@ -66,9 +78,11 @@ impl Harness {
     fn app(&self) -> Router {
         // Mirror main.rs: auth middleware only wraps protected routes.
         let public = build_public_routes(self.mgr.clone());
-        let protected = build_protected_routes(self.mgr.clone()).layer(axum::middleware::from_fn(
-            shodh_memory::auth::auth_middleware,
-        ));
+        let protected =
+            build_protected_routes(self.mgr.clone()).layer(axum::middleware::from_fn_with_state(
+                self.mgr.api_keys.clone(),
+                shodh_memory::auth::auth_middleware,
+            ));
         Router::new().merge(public).merge(protected)
     }
 }
@@ -95,6 +109,17 @@ fn authed_post(uri: &str, body: serde_json::Value) -> Request<Body> {
         .unwrap()
 }
 
+fn authed_patch(uri: &str, body: serde_json::Value) -> Request<Body> {
+    let bytes = serde_json::to_vec(&body).unwrap();
+    Request::builder()
+        .method(Method::PATCH)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .header("x-api-key", TEST_KEY)
+        .body(Body::from(bytes))
+        .unwrap()
+}
+
 #[allow(dead_code)]
 fn authed_put(uri: &str, body: serde_json::Value) -> Request<Body> {
     let bytes = serde_json::to_vec(&body).unwrap();
@@ -235,8 +260,12 @@ async fn health_live() {
 #[tokio::test]
 async fn health_ready() {
     let h = Harness::new();
-    let (status, _) = json_of(h.app(), authed_get("/health/ready")).await;
+    let (status, body) = json_of(h.app(), authed_get("/health/ready")).await;
     assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ready");
+    assert_eq!(body["checks"]["storage"]["status"], "ok");
+    assert!(body["checks"]["vector_index"]["status"].is_string());
+    assert!(body["checks"]["embedding"]["status"].is_string());
 }
 
 #[tokio::test]
@@ -293,6 +322,324 @@ async fn list_users_empty() {
     assert!(body.is_array());
 }
 
+#[tokio::test]
+async fn tenant_keys_isolate_users() {
+    let h = Harness::new();
+    let with_key = |mut req: Request<Body>, key: &str| {
+        req.headers_mut().insert("x-api-key", key.parse().unwrap());
+        req
+    };
+
+    let (status, body) = json_of(
+        h.app(),
+        with_key(
+            authed_post(
+                "/api/remember",
+                json!({"user_id": "alice", "content": "Acme ships the billing service on Fridays"}),
+            ),
+            ACME_KEY,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "remember failed: {body}");
+
+    // Same user id, other tenant: nothing there
+    let (_, acme) = json_of(
+        h.app(),
+        with_key(authed_get("/api/memories?user_id=alice"), ACME_KEY),
+    )
+    .await;
+    assert_eq!(acme["total"], 1);
+    let (_, globex) = json_of(
+        h.app(),
+        with_key(authed_get("/api/memories?user_id=alice"), GLOBEX_KEY),
+    )
+    .await;
+    assert_eq!(globex["total"], 0);
+    let (_, default) = json_of(h.app(), authed_get("/api/list/alice")).await;
+    assert_eq!(default["total"], 0, "{default}");
+
+    // Tenants see their own users, without the qualifier
+    let (_, users) = json_of(h.app(), with_key(authed_get("/api/users"), ACME_KEY)).await;
+    assert_eq!(users, json!(["alice"]));
+    let (_, users) = json_of(h.app(), authed_get("/api/users")).await;
+    assert!(!users.to_string().contains("acme"), "{users}");
+
+    // Qualified ids can't be named directly, and admin routes are off limits
+    let status = status_of(
+        h.app(),
+        with_key(authed_get("/api/memories?user_id=acme~alice"), GLOBEX_KEY),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let status = status_of(h.app(), authed_get("/api/users/acme~alice/stats")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let status = status_of(
+        h.app(),
+        with_key(authed_get("/api/admin/tenants"), ACME_KEY),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = json_of(h.app(), authed_get("/api/admin/tenants")).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let acme = body["tenants"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["tenant"] == "acme")
+        .expect("acme usage");
+    assert_eq!(acme["users"], 1);
+    assert_eq!(acme["writes"], 1);
+    assert_eq!(acme["requests"], 4);
+
+    // Every body axum parses as JSON is scoped, as are encoded query keys
+    for content_type in ["application/cloudevents+json", "Application/JSON"] {
+        let mut req = with_key(
+            authed_post(
+                "/api/recall/tags",
+                json!({"user_id": "acme~alice", "tags": ["billing"]}),
+            ),
+            GLOBEX_KEY,
+        );
+        req.headers_mut()
+            .insert("content-type", content_type.parse().unwrap());
+        assert_eq!(
+            status_of(h.app(), req).await,
+            StatusCode::BAD_REQUEST,
+            "{content_type}"
+        );
+    }
+    let status = status_of(
+        h.app(),
+        with_key(authed_get("/api/memories?user%5Fid=acme~alice"), GLOBEX_KEY),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn managed_keys_create_rotate_revoke() {
+    let h = Harness::new();
+    let with_key = |mut req: Request<Body>, key: &str| {
+        req.headers_mut().insert("x-api-key", key.parse().unwrap());
+        req
+    };
+
+    let (status, created) = json_of(
+        h.app(),
+        authed_post(
+            "/api/admin/keys",
+            json!({"label": "dashboard", "scopes": ["recall"]}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{created}");
+    let key = created["api_key"].as_str().unwrap().to_string();
+    let key_id = created["key_id"].as_str().unwrap().to_string();
+    assert!(created.get("hash").is_none());
+
+    let status = status_of(h.app(), with_key(authed_get("/api/users"), &key)).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, list) = json_of(h.app(), authed_get("/api/admin/keys")).await;
+    assert_eq!(list["count"], 1);
+    assert_eq!(list["keys"][0]["label"], "dashboard");
+    assert!(list["keys"][0]["last_used_at"].is_string());
+
+    let (status, rotated) = json_of(
+        h.app(),
+        authed_post(&format!("/api/admin/keys/{key_id}/rotate"), json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{rotated}");
+    assert_eq!(rotated["rotated_from"], key_id.as_str());
+    assert_eq!(rotated["scopes"], json!(["recall"]));
+    let new_key = rotated["api_key"].as_str().unwrap().to_string();
+    let status = status_of(h.app(), with_key(authed_get("/api/users"), &key)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let status = status_of(h.app(), with_key(authed_get("/api/users"), &new_key)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let new_id = rotated["key_id"].as_str().unwrap();
+    let mut revoke = authed_get(&format!("/api/admin/keys/{new_id}"));
+    *revoke.method_mut() = Method::DELETE;
+    let (status, revoked) = json_of(h.app(), revoke).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(revoked["revoked_at"].is_string());
+    let status = status_of(h.app(), with_key(authed_get("/api/users"), &new_key)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Without SHODH_ADMIN_API_KEYS no key may mint an admin key
+    let status = status_of(
+        h.app(),
+        authed_post("/api/admin/keys", json!({"scopes": ["admin"]})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn scoped_keys_are_held_to_their_routes() {
+    let h = Harness::new();
+    let with_key = |mut req: Request<Body>, key: &str| {
+        req.headers_mut().insert("x-api-key", key.parse().unwrap());
+        req
+    };
+    let issue = |scopes: serde_json::Value| {
+        let app = h.app();
+        async move {
+            let (status, body) = json_of(
+                app,
+                authed_post("/api/admin/keys", json!({"scopes": scopes})),
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK, "{body}");
+            body["api_key"].as_str().unwrap().to_string()
+        }
+    };
+    let recall_key = issue(json!(["recall"])).await;
+    let ingest_key = issue(json!(["ingest"])).await;
+
+    let remember = || {
+        authed_post(
+            "/api/remember",
+            json!({"user_id": "scoped-user", "content": "Deploys are frozen during the audit"}),
+        )
+    };
+    let status = status_of(h.app(), with_key(remember(), &ingest_key)).await;
+    assert_eq!(status, StatusCode::OK);
+    let status = status_of(h.app(), with_key(remember(), &recall_key)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let list = || authed_get("/api/memories?user_id=scoped-user");
+    let (status, body) = json_of(h.app(), with_key(list(), &recall_key)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+    let status = status_of(h.app(), with_key(list(), &ingest_key)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let status = status_of(
+        h.app(),
+        with_key(authed_get("/api/admin/keys"), &recall_key),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn per_key_rate_limit_returns_429() {
+    let h = Harness::with_config(|c| {
+        c.key_rate_limit_per_second = 1;
+        c.key_rate_limit_burst = 2;
+    });
+    for _ in 0..2 {
+        let status = status_of(h.app(), authed_get("/api/users")).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let resp = h.app().oneshot(authed_get("/api/users")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(resp.headers().contains_key("retry-after"));
+
+    // Tenants draw from their own bucket
+    let mut req = authed_get("/api/users");
+    req.headers_mut()
+        .insert("x-api-key", ACME_KEY.parse().unwrap());
+    let status = status_of(h.app(), req).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn memory_events_stream_creates() {
+    let h = Harness::new();
+    // Following every user takes an admin key
+    let status = status_of(h.app(), authed_get("/api/events?all_users=true")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let resp = h
+        .app()
+        .oneshot(authed_get("/api/events?user_id=events-user&types=CREATE"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let mut events = resp.into_body();
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/remember",
+            json!({"user_id": "events-user", "content": "The staging cluster moved to eu-west"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let frame = tokio::time::timeout(std::time::Duration::from_secs(10), events.frame())
+        .await
+        .expect("event within 10s")
+        .unwrap()
+        .unwrap();
+    let text = String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap();
+    assert!(text.contains("event: CREATE"), "{text}");
+    assert!(text.contains("\"user_id\":\"events-user\""), "{text}");
+}
+
+#[tokio::test]
+async fn webhooks_are_registered_per_tenant() {
+    let h = Harness::new();
+    let with_key = |mut req: Request<Body>, key: &str| {
+        req.headers_mut().insert("x-api-key", key.parse().unwrap());
+        req
+    };
+    let (status, body) = json_of(
+        h.app(),
+        with_key(
+            authed_post(
+                "/api/webhooks",
+                json!({
+                    "url": "https://hooks.example.com/decisions",
+                    "user_id": "alice",
+                    "memory_types": ["Decision"],
+                    "format": "slack"
+                }),
+            ),
+            ACME_KEY,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert!(body["secret"].as_str().unwrap().starts_with("whsec_"));
+    assert_eq!(body["user_id"], "alice");
+    let id = body["id"].as_str().unwrap().to_string();
+
+    let (_, body) = json_of(h.app(), with_key(authed_get("/api/webhooks"), ACME_KEY)).await;
+    assert_eq!(body["count"], 1);
+    assert!(body["webhooks"][0].get("secret").is_none());
+    let (_, body) = json_of(h.app(), with_key(authed_get("/api/webhooks"), GLOBEX_KEY)).await;
+    assert_eq!(body["count"], 0);
+    let (_, body) = json_of(h.app(), authed_get("/api/webhooks")).await;
+    assert_eq!(body["count"], 0);
+
+    let uri = format!("/api/webhooks/{id}");
+    let status = status_of(h.app(), with_key(authed_delete(&uri), GLOBEX_KEY)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, body) = json_of(
+        h.app(),
+        with_key(authed_patch(&uri, json!({"enabled": false})), ACME_KEY),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["enabled"], false);
+    let status = status_of(h.app(), with_key(authed_delete(&uri), ACME_KEY)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let status = status_of(
+        h.app(),
+        authed_post("/api/webhooks", json!({"url": "not a url"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn user_stats_fresh() {
     let h = Harness::new();
@@ -301,6 +648,48 @@ async fn user_stats_fresh() {
     assert!(status.is_success(), "user stats returned {status}");
 }
 
+#[tokio::test]
+async fn purge_user_requires_confirmation_token() {
+    let h = Harness::new();
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/remember",
+            json!({"user_id": "purge-user", "content": "Prefers dark mode in every editor"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "remember failed: {body}");
+
+    let purge = |body: serde_json::Value| authed_post("/api/users/purge-user/purge", body);
+    let (status, body) = json_of(h.app(), purge(json!({}))).await;
+    assert_eq!(status, StatusCode::OK, "token request failed: {body}");
+    assert_eq!(body["purged"], false);
+    let token = body["confirmation_token"].as_str().unwrap().to_string();
+
+    let (status, body) = json_of(h.app(), purge(json!({"confirmation_token": "wrong"}))).await;
+    assert_eq!(
+        status,
+        StatusCode::BAD_REQUEST,
+        "wrong token accepted: {body}"
+    );
+
+    // A wrong guess spends the token
+    let (status, _) = json_of(h.app(), purge(json!({"confirmation_token": token}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (_, body) = json_of(h.app(), purge(json!({}))).await;
+    let token = body["confirmation_token"].as_str().unwrap().to_string();
+
+    let (status, body) = json_of(h.app(), purge(json!({"confirmation_token": token}))).await;
+    assert_eq!(status, StatusCode::OK, "purge failed: {body}");
+    assert_eq!(body["purged"], true);
+    assert_eq!(body["report"]["memories_deleted"], 1);
+
+    // Tokens are single-use
+    let (status, _) = json_of(h.app(), purge(json!({"confirmation_token": token}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn stats_query() {
     let h = Harness::new();
@@ -308,6 +697,100 @@ async fn stats_query() {
     assert!(status.is_success(), "stats query returned {status}");
 }
 
+#[tokio::test]
+async fn admin_stats_breaks_down_memories() {
+    let h = Harness::new();
+    for (content, memory_type, tags) in [
+        (
+            "Decided to use Rust for the memory engine",
+            "Decision",
+            vec!["architecture", "rust"],
+        ),
+        (
+            "The build takes four minutes on CI",
+            "Observation",
+            vec!["rust"],
+        ),
+    ] {
+        let (status, body) = json_of(
+            h.app(),
+            authed_post(
+                "/api/remember",
+                json!({
+                    "user_id": "stats-user",
+                    "content": content,
+                    "memory_type": memory_type,
+                    "tags": tags
+                }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "remember failed: {body}");
+    }
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_get("/api/admin/stats?user_id=stats-user&days=7"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["totals"]["users"], 1);
+    assert_eq!(body["totals"]["memories"], 2);
+    assert_eq!(body["users"][0]["user_id"], "stats-user");
+    assert_eq!(body["memory_types"]["Decision"], 1);
+    assert_eq!(body["memory_types"]["Observation"], 1);
+    assert_eq!(body["sources"]["manual"], 2);
+    assert_eq!(body["tags"][0]["tag"], "rust");
+    assert_eq!(body["tags"][0]["count"], 2);
+    assert_eq!(body["ingestion"]["daily"].as_array().unwrap().len(), 7);
+    assert_eq!(body["ingestion"]["daily"][6]["memories"], 2);
+
+    let status = status_of(h.app(), authed_get("/api/admin/stats?days=0")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn admin_ranking_weights_per_tenant() {
+    let h = Harness::new();
+    let (status, body) = json_of(h.app(), authed_get("/api/admin/ranking")).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["count"], 0);
+    assert_eq!(body["defaults"]["semantic"], 1.0);
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_put(
+            "/api/admin/ranking",
+            json!({"tenant": "acme", "recency": 0.5, "calibrate": true}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["tenant"], "acme");
+    assert_eq!(body["configured"], true);
+    assert_eq!(body["weights"]["recency"], 0.5);
+    assert_eq!(body["weights"]["calibrate"], true);
+    assert_eq!(body["weights"]["semantic"], 1.0);
+
+    let (status, body) = json_of(h.app(), authed_get("/api/admin/ranking?tenant=default")).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["tenants"][0]["configured"], false);
+    assert_eq!(body["tenants"][0]["weights"]["calibrate"], false);
+
+    let status = status_of(
+        h.app(),
+        authed_put("/api/admin/ranking", json!({"importance": 2.0})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = json_of(h.app(), authed_delete("/api/admin/ranking?tenant=acme")).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["reset"], true);
+    assert_eq!(body["configured"], false);
+    assert_eq!(body["weights"]["recency"], 0.1);
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // sessions.rs
 // ═══════════════════════════════════════════════════════════════════════
@@ -376,73 +859,335 @@ async fn remember_with_tags_and_type() {
 }
 
 #[tokio::test]
-async fn batch_remember() {
+async fn remember_idempotency_key_deduplicates() {
     let h = Harness::new();
-    let (status, _) = json_of(
-        h.app(),
+    let request = || {
         authed_post(
-            "/api/remember/batch",
+            "/api/remember",
             json!({
                 "user_id": "test-user",
-                "memories": [
-                    {"content": "First memory item"},
-                    {"content": "Second memory item", "tags": ["batch"]}
-                ]
+                "content": "Retried write that must only be stored once.",
+                "idempotency_key": "conv-42:turn-7"
             }),
+        )
+    };
+
+    let (status, first) = json_of(h.app(), request()).await;
+    assert_eq!(status, StatusCode::OK, "remember failed: {first}");
+    assert_eq!(first["deduplicated"], false);
+
+    let (status, second) = json_of(h.app(), request()).await;
+    assert_eq!(status, StatusCode::OK, "retry failed: {second}");
+    assert_eq!(second["deduplicated"], true);
+    assert_eq!(second["id"], first["id"]);
+}
+
+#[tokio::test]
+async fn remember_identical_content_reinforces_existing() {
+    let h = Harness::new();
+    let request = |content: &str| {
+        authed_post(
+            "/api/remember",
+            json!({"user_id": "test-user", "content": content}),
+        )
+    };
+
+    let (status, first) = json_of(h.app(), request("The CI cache lives in S3.")).await;
+    assert_eq!(status, StatusCode::OK, "remember failed: {first}");
+    assert_eq!(first["deduplicated"], false);
+
+    // Same content modulo case and whitespace
+    let (status, second) = json_of(h.app(), request("the CI cache  lives in s3.\n")).await;
+    assert_eq!(status, StatusCode::OK, "repeat failed: {second}");
+    assert_eq!(second["deduplicated"], true);
+    assert_eq!(second["id"], first["id"]);
+
+    let (status, third) = json_of(h.app(), request("The CI cache lives in GCS.")).await;
+    assert_eq!(status, StatusCode::OK, "remember failed: {third}");
+    assert_eq!(third["deduplicated"], false);
+
+    // A repeat's tags are merged into the existing memory
+    let (status, tagged) = json_of(
+        h.app(),
+        authed_post(
+            "/api/remember",
+            json!({"user_id": "test-user", "content": "The CI cache lives in S3.", "tags": ["infra"]}),
         ),
     )
     .await;
-    assert!(status.is_success(), "batch remember returned {status}");
+    assert_eq!(status, StatusCode::OK, "repeat failed: {tagged}");
+    assert_eq!(tagged["id"], first["id"]);
+    let id = first["id"].as_str().unwrap();
+    let (_, memory) = json_of(
+        h.app(),
+        authed_get(&format!("/api/memory/{id}?user_id=test-user")),
+    )
+    .await;
+    assert!(
+        memory["experience"]["tags"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("infra")),
+        "tags not merged: {memory}"
+    );
 }
 
 #[tokio::test]
-async fn upsert_memory() {
+async fn remember_validates_memory_type_against_registry() {
     let h = Harness::new();
-    let (status, _) = json_of(
-        h.app(),
+    let remember = |memory_type: &str| {
         authed_post(
-            "/api/upsert",
+            "/api/remember",
             json!({
                 "user_id": "test-user",
-                "external_id": "ext-001",
-                "content": "Upserted memory content."
+                "content": format!("Rotate the signing key ({memory_type})"),
+                "memory_type": memory_type
             }),
+        )
+    };
+
+    let (status, body) = json_of(h.app(), remember("runbook")).await;
+    assert_eq!(
+        status,
+        StatusCode::BAD_REQUEST,
+        "unknown type stored: {body}"
+    );
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/memory_types",
+            json!({"name": "runbook", "base": "learning", "retrieval_weight": 1.5}),
         ),
     )
     .await;
-    assert!(status.is_success(), "upsert returned {status}");
-}
+    assert_eq!(status, StatusCode::OK, "define failed: {body}");
 
-// ═══════════════════════════════════════════════════════════════════════
-// recall.rs
-// ═══════════════════════════════════════════════════════════════════════
+    let (status, body) = json_of(h.app(), remember("runbook")).await;
+    assert_eq!(status, StatusCode::OK, "custom type rejected: {body}");
+
+    let (status, body) = json_of(h.app(), authed_get("/api/memory_types")).await;
+    assert_eq!(status, StatusCode::OK);
+    let runbook = body["types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "runbook")
+        .expect("custom type listed");
+    assert_eq!(runbook["base"], "Learning");
+    assert_eq!(runbook["builtin"], false);
+}
 
 #[tokio::test]
-async fn recall_empty_state() {
-    let h = Harness::new();
+async fn remember_redacts_secrets() {
+    let h = Harness::with_config(|cfg| cfg.redact_secrets = true);
     let (status, body) = json_of(
         h.app(),
         authed_post(
-            "/api/recall",
+            "/api/remember",
             json!({
                 "user_id": "test-user",
-                "query": "what do I know?"
+                "content": "Deploy failed until we set DATABASE_URL=postgres://app:s3cretpass@db:5432/prod",
+                "tags": ["deploy"]
             }),
         ),
     )
     .await;
-    assert!(status.is_success(), "recall returned {status}: {body}");
-}
+    assert_eq!(status, StatusCode::OK, "remember failed: {body}");
 
-#[tokio::test]
-async fn context_summary_empty() {
-    let h = Harness::new();
-    let (status, _) = json_of(
+    let id = body["id"].as_str().unwrap();
+    let (status, memory) = json_of(
         h.app(),
-        authed_post("/api/context_summary", json!({"user_id": "test-user"})),
+        authed_get(&format!("/api/memory/{id}?user_id=test-user")),
     )
     .await;
-    assert!(status.is_success());
+    assert_eq!(status, StatusCode::OK, "get failed: {memory}");
+    let content = memory["experience"]["content"].as_str().unwrap();
+    assert!(!content.contains("s3cretpass"), "secret stored: {content}");
+    assert!(memory["experience"]["tags"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|t| t == "redacted:true"));
+}
+
+#[tokio::test]
+async fn remember_respects_encoding_filters() {
+    let rules_dir = TempDir::new().expect("create temp dir");
+    let rules_path = rules_dir.path().join("filters.txt");
+    std::fs::write(&rules_path, "content:/DO NOT STORE/\n").unwrap();
+    let h = Harness::with_config(|cfg| cfg.encoding_filters_path = Some(rules_path.clone()));
+    let request = |content: &str| {
+        authed_post(
+            "/api/remember",
+            json!({
+                "user_id": "test-user",
+                "content": content,
+                "model": "claude-3-5-haiku-20241022"
+            }),
+        )
+    };
+
+    let (status, body) = json_of(h.app(), request("Scratch notes, DO NOT STORE")).await;
+    assert_eq!(status, StatusCode::OK, "filtered write rejected: {body}");
+    assert_eq!(body["filtered"], true, "filtered content stored: {body}");
+    assert_eq!(body["id"], "");
+    let (status, body) = json_of(h.app(), request("Release is on Friday")).await;
+    assert_eq!(status, StatusCode::OK, "remember failed: {body}");
+    assert_eq!(body["filtered"], false);
+
+    // Rules are picked up on change without a restart
+    std::fs::write(&rules_path, "model:claude-3-5-haiku*\n").unwrap();
+    std::fs::File::options()
+        .write(true)
+        .open(&rules_path)
+        .unwrap()
+        .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(5))
+        .unwrap();
+    let (status, body) = json_of(h.app(), request("Release moved to Monday")).await;
+    assert_eq!(status, StatusCode::OK, "filtered write rejected: {body}");
+    assert_eq!(body["filtered"], true, "reloaded filter ignored: {body}");
+}
+
+#[tokio::test]
+async fn run_end_stores_summary_memory() {
+    let h = Harness::new();
+    for (content, memory_type) in [
+        ("Goal: migrate the session store to RocksDB", "Task"),
+        ("Decided to keep one column family per store", "Decision"),
+        ("Migration tests pass", "Observation"),
+    ] {
+        let (status, body) = json_of(
+            h.app(),
+            authed_post(
+                "/api/remember",
+                json!({
+                    "user_id": "test-user",
+                    "content": content,
+                    "memory_type": memory_type,
+                    "run_id": "run-7"
+                }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "remember failed: {body}");
+    }
+
+    let run_end = || {
+        authed_post(
+            "/api/hooks",
+            json!({
+                "hook_event_name": "RunEnd",
+                "user_id": "test-user",
+                "run_id": "run-7"
+            }),
+        )
+    };
+    let (status, body) = json_of(h.app(), run_end()).await;
+    assert_eq!(status, StatusCode::OK, "RunEnd failed: {body}");
+    assert_eq!(body["action"], "run_summarized");
+    assert!(body["memory_id"].is_string());
+
+    // Nothing new since the summary
+    let (status, body) = json_of(h.app(), run_end()).await;
+    assert_eq!(status, StatusCode::OK, "RunEnd failed: {body}");
+    assert_eq!(body["action"], "nothing_to_summarize");
+}
+
+#[tokio::test]
+async fn batch_remember() {
+    let h = Harness::new();
+    let (status, _) = json_of(
+        h.app(),
+        authed_post(
+            "/api/remember/batch",
+            json!({
+                "user_id": "test-user",
+                "memories": [
+                    {"content": "First memory item"},
+                    {"content": "Second memory item", "tags": ["batch"]}
+                ]
+            }),
+        ),
+    )
+    .await;
+    assert!(status.is_success(), "batch remember returned {status}");
+}
+
+#[tokio::test]
+async fn batch_remember_reflush_is_deduplicated() {
+    let h = Harness::new();
+    let request = || {
+        authed_post(
+            "/api/remember_batch",
+            json!({
+                "user_id": "test-user",
+                "memories": [
+                    {"content": "Queued memory one", "idempotency_key": "q-1"},
+                    {"content": "Queued memory two", "idempotency_key": "q-2"}
+                ]
+            }),
+        )
+    };
+    let (status, first) = json_of(h.app(), request()).await;
+    assert_eq!(status, StatusCode::OK, "first flush failed: {first}");
+    assert_eq!(first["created"], 2);
+
+    let (status, second) = json_of(h.app(), request()).await;
+    assert_eq!(status, StatusCode::OK, "re-flush failed: {second}");
+    assert_eq!(second["created"], 0);
+    assert_eq!(second["deduplicated"], 2);
+    assert_eq!(second["memory_ids"], first["memory_ids"]);
+}
+
+#[tokio::test]
+async fn upsert_memory() {
+    let h = Harness::new();
+    let (status, _) = json_of(
+        h.app(),
+        authed_post(
+            "/api/upsert",
+            json!({
+                "user_id": "test-user",
+                "external_id": "ext-001",
+                "content": "Upserted memory content."
+            }),
+        ),
+    )
+    .await;
+    assert!(status.is_success(), "upsert returned {status}");
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// recall.rs
+// ═══════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn recall_empty_state() {
+    let h = Harness::new();
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/recall",
+            json!({
+                "user_id": "test-user",
+                "query": "what do I know?"
+            }),
+        ),
+    )
+    .await;
+    assert!(status.is_success(), "recall returned {status}: {body}");
+}
+
+#[tokio::test]
+async fn context_summary_empty() {
+    let h = Harness::new();
+    let (status, _) = json_of(
+        h.app(),
+        authed_post("/api/context_summary", json!({"user_id": "test-user"})),
+    )
+    .await;
+    assert!(status.is_success());
 }
 
 #[tokio::test]
@@ -462,6 +1207,80 @@ async fn proactive_context() {
     assert!(status.is_success());
 }
 
+#[tokio::test]
+async fn proactive_context_explains_scores() {
+    let h = Harness::new();
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/remember",
+            json!({
+                "user_id": "test-user",
+                "content": "The billing service retries failed webhook deliveries with exponential backoff",
+                "tags": ["billing"]
+            }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "remember failed: {body}");
+
+    let request = json!({
+        "user_id": "test-user",
+        "context": "How does the billing service handle failed webhook deliveries?",
+        "auto_ingest": false
+    });
+    let (status, body) = json_of(
+        h.app(),
+        authed_post("/api/proactive_context?explain=true", request.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "proactive_context failed: {body}");
+    for memory in body["memories"].as_array().unwrap() {
+        let explanation = &memory["explanation"];
+        assert!(explanation.is_object(), "no explanation: {memory}");
+        assert!(explanation["retrieval_score"].is_number());
+        assert!(explanation["matched_terms"].is_array());
+    }
+
+    let (status, body) = json_of(h.app(), authed_post("/api/proactive_context", request)).await;
+    assert_eq!(status, StatusCode::OK, "proactive_context failed: {body}");
+    for memory in body["memories"].as_array().unwrap() {
+        assert!(
+            memory.get("explanation").is_none(),
+            "unrequested explanation: {memory}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn proactive_context_recency_parameters() {
+    let h = Harness::new();
+    for (recency, status) in [
+        (
+            json!({"recency_weight": 0.6, "recency_half_life": 4.0}),
+            StatusCode::OK,
+        ),
+        (
+            json!({"recency_weight": 0.0, "recency_half_life": 8760.0}),
+            StatusCode::OK,
+        ),
+        (json!({"recency_half_life": 0.0}), StatusCode::BAD_REQUEST),
+        (json!({"recency_weight": 1.5}), StatusCode::BAD_REQUEST),
+    ] {
+        let mut request = json!({
+            "user_id": "test-user",
+            "context": "Refactoring the retry logic in the billing service",
+            "auto_ingest": false
+        });
+        request
+            .as_object_mut()
+            .unwrap()
+            .extend(recency.as_object().unwrap().clone());
+        let (got, body) = json_of(h.app(), authed_post("/api/proactive_context", request)).await;
+        assert_eq!(got, status, "{recency}: {body}");
+    }
+}
+
 #[tokio::test]
 async fn recall_by_tags_empty() {
     let h = Harness::new();
@@ -497,6 +1316,37 @@ async fn reinforce_feedback() {
     assert!(status.is_success());
 }
 
+#[tokio::test]
+async fn memory_effectiveness_stats() {
+    let h = Harness::new();
+    let (status, body) = json_of(
+        h.app(),
+        authed_get("/api/admin/memory_stats?user_id=test-user"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "memory_stats failed: {body}");
+    assert_eq!(body["tracked_memories"], 0);
+}
+
+#[tokio::test]
+async fn reinforce_feedback_rejects_out_of_range_strength() {
+    let h = Harness::new();
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/reinforce",
+            json!({
+                "user_id": "test-user",
+                "ids": ["00000000-0000-0000-0000-000000000001"],
+                "outcome": "helpful",
+                "strength": 5.0
+            }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "strength accepted: {body}");
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // crud.rs
 // ═══════════════════════════════════════════════════════════════════════
@@ -519,6 +1369,69 @@ async fn list_memories_post_empty() {
     assert!(status.is_success());
 }
 
+#[tokio::test]
+async fn list_memories_cursor_pagination() {
+    let h = Harness::new();
+    for content in [
+        "First note about the build pipeline",
+        "Second note about the deploy script",
+        "Third note about the release checklist",
+    ] {
+        let (status, body) = json_of(
+            h.app(),
+            authed_post(
+                "/api/remember",
+                json!({"user_id": "test-user", "content": content}),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "remember failed: {body}");
+    }
+
+    let (status, first) = json_of(
+        h.app(),
+        authed_get("/api/memories?user_id=test-user&limit=2&order=asc"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "list failed: {first}");
+    assert_eq!(first["total"], 3);
+    assert_eq!(first["memories"].as_array().unwrap().len(), 2);
+    let cursor = first["next_cursor"]
+        .as_str()
+        .expect("next_cursor")
+        .to_string();
+
+    let (status, second) = json_of(
+        h.app(),
+        authed_get(&format!(
+            "/api/memories?user_id=test-user&limit=2&order=asc&cursor={cursor}"
+        )),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "list failed: {second}");
+    let page = second["memories"].as_array().unwrap();
+    assert_eq!(page.len(), 1);
+    assert!(second.get("next_cursor").is_none());
+    let seen: Vec<&str> = first["memories"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .chain(page)
+        .map(|m| m["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        seen.iter().collect::<std::collections::HashSet<_>>().len(),
+        3
+    );
+
+    let (status, _) = json_of(
+        h.app(),
+        authed_get("/api/memories?user_id=test-user&cursor=garbage"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn get_memory_not_found() {
     let h = Harness::new();
@@ -532,6 +1445,356 @@ async fn get_memory_not_found() {
     );
 }
 
+#[tokio::test]
+async fn memory_access_log_records_retrievals() {
+    let h = Harness::new();
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/remember",
+            json!({
+                "user_id": "test-user",
+                "content": "The staging cluster runs on three nodes",
+                "tags": ["infra"]
+            }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "remember failed: {body}");
+    let id = body["id"].as_str().unwrap().to_string();
+
+    let (status, _) = json_of(
+        h.app(),
+        authed_post(
+            "/api/recall/tags",
+            json!({"user_id": "test-user", "tags": ["infra"]}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Accesses are written in the background
+    let uri = format!("/api/memory/{id}/access_log?user_id=test-user");
+    let mut log = serde_json::Value::Null;
+    for _ in 0..50 {
+        let (status, body) = json_of(h.app(), authed_get(&uri)).await;
+        assert_eq!(status, StatusCode::OK, "access log failed: {body}");
+        log = body;
+        if log["count"] == 1 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert_eq!(log["count"], 1, "{log}");
+    assert_eq!(log["memory_id"], id.as_str());
+    assert_eq!(log["accesses"][0]["endpoint"], "/api/recall/tags");
+    assert_eq!(log["accesses"][0]["rank"], 1);
+    assert!(log["accesses"][0]["key_id"].is_string());
+}
+
+#[tokio::test]
+async fn suppressed_memories_are_not_retrieved() {
+    let h = Harness::new();
+    let mut ids = Vec::new();
+    for content in [
+        "The staging cluster runs on three nodes",
+        "Standup moved to 9:30 this week",
+    ] {
+        let (status, body) = json_of(
+            h.app(),
+            authed_post(
+                "/api/remember",
+                json!({"user_id": "test-user", "content": content, "tags": ["ops"]}),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "remember failed: {body}");
+        ids.push(body["id"].as_str().unwrap().to_string());
+    }
+    let recall_ops = || {
+        authed_post(
+            "/api/recall/tags",
+            json!({"user_id": "test-user", "tags": ["ops"]}),
+        )
+    };
+
+    let (status, rule) = json_of(
+        h.app(),
+        authed_post(
+            "/api/suppress",
+            json!({"user_id": "test-user", "memory_id": ids[0], "reason": "stale"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{rule}");
+    assert_eq!(rule["memory_id"], ids[0].as_str());
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/suppress",
+            json!({"user_id": "test-user", "pattern": "(?i)standup"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let (status, body) = json_of(h.app(), recall_ops()).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["count"], 0, "{body}");
+
+    // Suppressed, not deleted
+    let (status, _) = json_of(
+        h.app(),
+        authed_get(&format!("/api/memory/{}?user_id=test-user", ids[0])),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = json_of(h.app(), authed_get("/api/suppress?user_id=test-user")).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["count"], 2);
+
+    let uri = format!(
+        "/api/suppress/{}?user_id=test-user",
+        rule["id"].as_str().unwrap()
+    );
+    let (status, body) = json_of(h.app(), authed_delete(&uri)).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["removed"], true);
+
+    let (status, body) = json_of(h.app(), recall_ops()).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["count"], 1, "{body}");
+    assert_eq!(body["memories"][0]["id"], ids[0].as_str());
+
+    for invalid in [
+        json!({"user_id": "test-user"}),
+        json!({"user_id": "test-user", "memory_id": ids[1], "pattern": "nodes"}),
+        json!({"user_id": "test-user", "pattern": "(unclosed"}),
+    ] {
+        let status = status_of(h.app(), authed_post("/api/suppress", invalid)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn suppressed_memories_are_not_searched_by_date() {
+    let h = Harness::new();
+    let mut ids = Vec::new();
+    for content in [
+        "Rotated the deploy keys on Monday",
+        "Rebuilt the search index on Tuesday",
+    ] {
+        let (status, body) = json_of(
+            h.app(),
+            authed_post(
+                "/api/remember",
+                json!({"user_id": "test-user", "content": content}),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "remember failed: {body}");
+        ids.push(body["id"].as_str().unwrap().to_string());
+    }
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/suppress",
+            json!({"user_id": "test-user", "memory_id": ids[0]}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    for uri in [
+        "/api/search?user_id=test-user&start_date=2000-01-01T00:00:00Z",
+        "/api/search?user_id=test-user",
+    ] {
+        let (status, body) = json_of(h.app(), authed_get(uri)).await;
+        assert_eq!(status, StatusCode::OK, "search failed: {body}");
+        assert_eq!(body["count"], 1, "{uri}: {body}");
+        assert_eq!(body["memories"][0]["id"], ids[1].as_str());
+    }
+}
+
+#[tokio::test]
+async fn patch_memory_partial_update() {
+    let h = Harness::new();
+    let (status, stored) = json_of(
+        h.app(),
+        authed_post(
+            "/api/remember",
+            json!({"user_id": "test-user", "content": "Deploys run on Tuesdays", "tags": ["ops"]}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "remember failed: {stored}");
+    let uri = format!("/api/memory/{}", stored["id"].as_str().unwrap());
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_patch(
+            &uri,
+            json!({
+                "user_id": "test-user",
+                "content": "Deploys run on Thursdays",
+                "add_tags": ["deploy"],
+                "remove_tags": ["ops"],
+                "memory_type": "Decision",
+                "importance": 0.9
+            }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "patch failed: {body}");
+    assert_eq!(
+        body["updated_fields"],
+        json!(["content", "tags", "type", "importance"])
+    );
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_patch(&uri, json!({"user_id": "test-user", "importance": 1.5})),
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::BAD_REQUEST,
+        "bad importance accepted: {body}"
+    );
+}
+
+#[tokio::test]
+async fn forget_by_query_returns_deleted_ids() {
+    let h = Harness::new();
+    let (status, stored) = json_of(
+        h.app(),
+        authed_post(
+            "/api/remember",
+            json!({"user_id": "test-user", "content": "The staging database lives on port 5433"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "remember failed: {stored}");
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post("/api/forget", json!({"user_id": "test-user"})),
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::BAD_REQUEST,
+        "empty forget accepted: {body}"
+    );
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/forget",
+            json!({"user_id": "test-user", "query": "STAGING DATABASE"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "forget failed: {body}");
+    assert_eq!(body["deleted_count"], 1);
+    assert_eq!(body["deleted_ids"][0], stored["id"]);
+}
+
+#[tokio::test]
+async fn deleted_memories_go_to_trash_and_restore() {
+    let h = Harness::new();
+    let (status, stored) = json_of(
+        h.app(),
+        authed_post(
+            "/api/remember",
+            json!({"user_id": "test-user", "content": "The release train leaves every other Thursday"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "remember failed: {stored}");
+    let id = stored["id"].as_str().unwrap();
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_delete(&format!("/api/memory/{id}?user_id=test-user")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "delete failed: {body}");
+    assert_eq!(body["trashed"], true);
+
+    let (_, trash) = json_of(h.app(), authed_get("/api/trash?user_id=test-user")).await;
+    assert_eq!(trash["count"], 1, "{trash}");
+    assert_eq!(trash["memories"][0]["id"], id);
+
+    let restore = || {
+        authed_post(
+            &format!("/api/memory/{id}/restore"),
+            json!({"user_id": "test-user"}),
+        )
+    };
+    let (status, body) = json_of(h.app(), restore()).await;
+    assert_eq!(status, StatusCode::OK, "restore failed: {body}");
+    let (_, trash) = json_of(h.app(), authed_get("/api/trash?user_id=test-user")).await;
+    assert_eq!(trash["count"], 0);
+    let status = status_of(h.app(), restore()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_delete(&format!(
+            "/api/memory/{id}?user_id=test-user&permanent=true"
+        )),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "delete failed: {body}");
+    assert_eq!(body["trashed"], false);
+    let (_, trash) = json_of(h.app(), authed_get("/api/trash?user_id=test-user")).await;
+    assert_eq!(trash["count"], 0);
+}
+
+#[tokio::test]
+async fn bulk_forgets_go_to_trash() {
+    let h = Harness::new();
+    for content in ["Rotate the staging certs", "Rotate the prod certs"] {
+        let (status, body) = json_of(
+            h.app(),
+            authed_post(
+                "/api/remember",
+                json!({"user_id": "test-user", "content": content, "tags": ["certs"]}),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "remember failed: {body}");
+    }
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/forget/tags",
+            json!({"user_id": "test-user", "tags": ["certs"]}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "forget failed: {body}");
+    assert_eq!(body["deleted_count"], 2);
+    assert_eq!(body["trashed"], true);
+    let (_, trash) = json_of(h.app(), authed_get("/api/trash?user_id=test-user")).await;
+    assert_eq!(trash["count"], 2, "{trash}");
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/memories/clear",
+            json!({"user_id": "test-user", "confirm": "CONFIRM", "permanent": true}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "clear failed: {body}");
+    assert_eq!(body["trashed"], false);
+}
+
 #[tokio::test]
 async fn forget_by_age() {
     let h = Harness::new();
@@ -635,6 +1898,345 @@ async fn advanced_search_empty() {
     );
 }
 
+#[tokio::test]
+async fn search_with_filters() {
+    let h = Harness::new();
+    for (content, run_id) in [
+        ("Chose Postgres for the billing service", "run-a"),
+        ("Chose Redis for the session cache", "run-b"),
+    ] {
+        let (status, body) = json_of(
+            h.app(),
+            authed_post(
+                "/api/remember",
+                json!({
+                    "user_id": "test-user",
+                    "content": content,
+                    "memory_type": "Decision",
+                    "run_id": run_id
+                }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "remember failed: {body}");
+    }
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_get("/api/search?user_id=test-user&memory_type=Decision&run_id=run-b&sort=oldest"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "search failed: {body}");
+    assert_eq!(body["count"], 1);
+
+    // Score filters need a query to score against
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/search",
+            json!({"user_id": "test-user", "min_score": 0.5}),
+        ),
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::BAD_REQUEST,
+        "score filter accepted: {body}"
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// tags.rs
+// ═══════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn tags_rename_merge_delete() {
+    let h = Harness::new();
+    for (content, tags) in [
+        (
+            "Ran cargo test after the refactor",
+            json!(["tool:Bash", "rust"]),
+        ),
+        ("Read the config loader", json!(["tool:read", "rust"])),
+    ] {
+        let (status, body) = json_of(
+            h.app(),
+            authed_post(
+                "/api/remember",
+                json!({"user_id": "test-user", "content": content, "tags": tags}),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "remember failed: {body}");
+    }
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/tags/merge",
+            json!({"user_id": "test-user", "sources": ["tool:Bash", "tool:read"], "target": "tool"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "merge failed: {body}");
+    assert_eq!(body["memories_updated"], 2);
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/tags/rename",
+            json!({"user_id": "test-user", "from": "rust", "to": "lang:rust"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "rename failed: {body}");
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/tags/delete",
+            json!({"user_id": "test-user", "tag": "tool"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "delete failed: {body}");
+
+    let (status, body) = json_of(h.app(), authed_get("/api/tags?user_id=test-user")).await;
+    assert_eq!(status, StatusCode::OK, "list failed: {body}");
+    let tags = body["tags"].as_array().unwrap();
+    assert!(tags
+        .iter()
+        .any(|t| t["tag"] == "lang:rust" && t["count"] == 2));
+    assert!(!tags
+        .iter()
+        .any(|t| t["tag"] == "tool" || t["tag"] == "rust"));
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// export.rs
+// ═══════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn export_jsonl_plain_gzip_and_zstd() {
+    use std::io::Read;
+
+    let h = Harness::new();
+    for content in [
+        "Exported note about the storage layer",
+        "Exported note about the HTTP layer",
+    ] {
+        let (status, body) = json_of(
+            h.app(),
+            authed_post(
+                "/api/remember",
+                json!({"user_id": "test-user", "content": content, "tags": ["export"]}),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "remember failed: {body}");
+    }
+
+    let fetch = |uri: &'static str| {
+        let app = h.app();
+        async move {
+            let resp = app.oneshot(authed_get(uri)).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            resp.into_body().collect().await.unwrap().to_bytes()
+        }
+    };
+
+    let plain = fetch("/api/export?user_id=test-user&format=jsonl").await;
+    let mut gzipped = String::new();
+    flate2::read::GzDecoder::new(&fetch("/api/export?user_id=test-user&gzip=true").await[..])
+        .read_to_string(&mut gzipped)
+        .unwrap();
+    assert_eq!(gzipped.as_bytes(), &plain[..]);
+    let zstd =
+        zstd::decode_all(&fetch("/api/export?user_id=test-user&zstd=true").await[..]).unwrap();
+    assert_eq!(&zstd[..], &plain[..]);
+
+    let records: Vec<serde_json::Value> = std::str::from_utf8(&plain)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records.len(), 2);
+    assert!(records.iter().all(|r| r["tags"] == json!(["export"])
+        && r["created_at"].is_string()
+        && r["access_count"].is_number()));
+
+    let status = status_of(
+        h.app(),
+        authed_get("/api/export?user_id=test-user&format=csv"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let status = status_of(
+        h.app(),
+        authed_get("/api/export?user_id=test-user&gzip=true&zstd=true"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// import.rs
+// ═══════════════════════════════════════════════════════════════════════
+
+fn authed_post_raw(uri: &str, body: &'static str) -> Request<Body> {
+    Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header("x-api-key", TEST_KEY)
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn import_jsonl_dedups_and_supports_dry_run() {
+    let h = Harness::new();
+    let data = concat!(
+        r#"{"content": "Imported decision about caching", "memory_type": "Decision", "tags": ["cache"], "importance": 0.9}"#,
+        "\n",
+        r#"{"memory": "Imported fact from another memory system"}"#,
+        "\n",
+        r#"{"content": "Imported decision about caching"}"#,
+        "\n",
+        "not json\n",
+    );
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post_raw("/api/import?user_id=test-user&dry_run=true", data),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "dry run failed: {body}");
+    assert_eq!(body["total"], 4);
+    assert_eq!(body["imported"], 2);
+    assert_eq!(body["duplicates"], 1);
+    assert_eq!(body["errors"][0]["line"], 4);
+
+    let (status, body) = json_of(h.app(), authed_get("/api/memories?user_id=test-user")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 0, "dry run stored memories: {body}");
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post_raw("/api/import?user_id=test-user", data),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "import failed: {body}");
+    assert_eq!(body["imported_ids"].as_array().unwrap().len(), 2);
+
+    // Re-importing the same file only finds duplicates
+    let (_, body) = json_of(
+        h.app(),
+        authed_post_raw("/api/import?user_id=test-user", data),
+    )
+    .await;
+    assert_eq!(body["imported"], 0);
+    assert_eq!(body["duplicates"], 3);
+}
+
+#[tokio::test]
+async fn import_applies_encoding_filters() {
+    let rules_dir = TempDir::new().expect("create temp dir");
+    let rules_path = rules_dir.path().join("filters.txt");
+    std::fs::write(&rules_path, "content:/DO NOT STORE/\n").unwrap();
+    let h = Harness::with_config(|cfg| cfg.encoding_filters_path = Some(rules_path.clone()));
+    let data = concat!(
+        r#"{"content": "Scratch notes, DO NOT STORE"}"#,
+        "\n",
+        r#"{"content": "Release is on Friday"}"#,
+        "\n",
+    );
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post_raw("/api/import?user_id=test-user", data),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "import failed: {body}");
+    assert_eq!(body["imported"], 1);
+    assert_eq!(body["filtered"], 1);
+
+    let (_, body) = json_of(h.app(), authed_get("/api/memories?user_id=test-user")).await;
+    assert_eq!(body["total"], 1, "filtered record stored: {body}");
+}
+
+#[tokio::test]
+async fn import_restores_exported_feedback_momentum() {
+    let h = Harness::new();
+    let data = concat!(
+        r#"{"content": "Run load tests against staging", "memory_type": "Decision", "feedback": {"memory_id": "00000000-0000-4000-8000-000000000001", "memory_type": "Context", "ema": 0.5, "signal_count": 4, "stability": 0.8, "recent_signals": [], "helpful_contexts": [], "misleading_contexts": [], "positive_count": 4}}"#,
+        "\n",
+    );
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post_raw("/api/import?user_id=test-user", data),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "import failed: {body}");
+    let id = body["imported_ids"][0].as_str().unwrap().to_string();
+
+    let resp = h
+        .app()
+        .oneshot(authed_get("/api/export?user_id=test-user"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    let record: serde_json::Value =
+        serde_json::from_str(std::str::from_utf8(&bytes).unwrap().trim()).unwrap();
+    let feedback = &record["feedback"];
+    assert_eq!(feedback["memory_id"], json!(id), "{record}");
+    assert_eq!(feedback["memory_type"], "Decision");
+    assert_eq!(feedback["ema"], 0.5);
+    assert_eq!(feedback["positive_count"], 4);
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// review.rs
+// ═══════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn review_queue_lists_and_completes_due_memories() {
+    let h = Harness::new();
+    let data = concat!(
+        r#"{"content": "Decided to shard the cache by tenant", "memory_type": "Decision", "importance": 0.9, "created_at": "2024-01-01T00:00:00Z"}"#,
+        "\n",
+        r#"{"content": "Trivia nobody needs to revisit", "importance": 0.1, "created_at": "2024-01-01T00:00:00Z"}"#,
+        "\n",
+    );
+    let (status, body) = json_of(
+        h.app(),
+        authed_post_raw("/api/import?user_id=test-user", data),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "import failed: {body}");
+
+    let (status, body) = json_of(h.app(), authed_get("/api/review?user_id=test-user")).await;
+    assert_eq!(status, StatusCode::OK, "review failed: {body}");
+    assert_eq!(body["count"], 1, "only the important memory is due: {body}");
+    let id = body["items"][0]["id"].as_str().unwrap().to_string();
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/review/complete",
+            json!({"user_id": "test-user", "memory_ids": [id, "00000000-0000-4000-8000-000000000000"]}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "complete failed: {body}");
+    assert_eq!(body["reviewed"], 1, "unknown ids aren't counted: {body}");
+
+    // Reviewing pushes the next review into the future
+    let (_, body) = json_of(h.app(), authed_get("/api/review?user_id=test-user")).await;
+    assert_eq!(body["count"], 0, "reviewed memory still due: {body}");
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // facts.rs
 // ═══════════════════════════════════════════════════════════════════════
@@ -16,7 +16,10 @@ use tempfile::TempDir;
 use uuid::Uuid;
 
 use shodh_memory::embeddings::ner::{NerConfig, NeuralNer};
+use shodh_memory::embeddings::provider::PINNED_PROVIDER_FILE;
+use shodh_memory::embeddings::{ProviderConfig, ProviderKind};
 use shodh_memory::memory::{
+    reembed::reembed,
     retrieval::RetrievalOutcome,
     types::{Experience, ExperienceType, Query},
     MemoryConfig, MemoryId, MemorySystem,
@@ -838,3 +841,47 @@ fn test_stress_reinforcement_cycles() {
         results.len()
     );
 }
+
+// ============================================================================
+// RE-EMBEDDING TESTS
+// ============================================================================
+
+#[test]
+fn test_reembed_keeps_memories_retrievable() {
+    let (system, temp_dir) = create_test_system();
+    assert!(temp_dir.path().join(PINNED_PROVIDER_FILE).exists());
+    for i in 0..5 {
+        system
+            .remember(
+                create_experience(&format!("Deploy runbook step {i}"), vec!["deploy"]),
+                None,
+            )
+            .expect("Failed to record");
+    }
+    let memory = parking_lot::RwLock::new(system);
+    let local = ProviderConfig {
+        kind: ProviderKind::Local,
+        ..Default::default()
+    };
+
+    // Already on the local model
+    let outcome = reembed(&memory, &local, false, |_, _| {}).expect("Failed");
+    assert!(outcome.skipped);
+
+    let mut reported = 0;
+    let outcome = reembed(&memory, &local, true, |done, _| reported = done).expect("Failed");
+    assert!(!outcome.skipped);
+    assert_eq!(outcome.reembedded, 5);
+    assert_eq!(reported, 5);
+
+    let query = Query {
+        query_text: Some("deploy runbook".to_string()),
+        max_results: 10,
+        ..Default::default()
+    };
+    let results = memory.read().recall(&query).expect("Failed");
+    assert!(
+        !results.is_empty(),
+        "Re-embedded memories should be retrievable"
+    );
+}
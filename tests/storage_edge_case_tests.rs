@@ -703,3 +703,59 @@ fn test_data_survives_reopen() {
         );
     }
 }
+
+// ============================================================================
+// RECORD COMPRESSION TESTS
+// ============================================================================
+
+#[test]
+fn test_trained_dictionary_survives_reopen() {
+    use shodh_memory::memory::storage::MemoryStorage;
+    use shodh_memory::memory::types::{Memory, MemoryId};
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let mut ids = Vec::new();
+    {
+        let storage = MemoryStorage::new(temp_dir.path()).expect("Failed to open storage");
+        for i in 0..300 {
+            let exp = Experience {
+                content: format!(
+                    "User: why does build {i} fail?\nTools: cargo build, read_file(Cargo.toml)\n\
+                     Assistant: Build {i} fails because feature {} is missing from the manifest.",
+                    i % 5
+                ),
+                experience_type: ExperienceType::Conversation,
+                ..Default::default()
+            };
+            let memory = Memory::new(
+                MemoryId(uuid::Uuid::new_v4()),
+                exp,
+                0.5,
+                None,
+                None,
+                None,
+                None,
+            );
+            storage.store(&memory).expect("Failed to store");
+            ids.push(memory.id);
+        }
+
+        let rewritten = storage
+            .train_compression()
+            .expect("Failed to train dictionary");
+        assert_eq!(rewritten, 300, "all records should be recompressed");
+        // Only trained once
+        assert_eq!(storage.train_compression().unwrap(), 0);
+        storage.flush().expect("Failed to flush");
+    }
+
+    let storage = MemoryStorage::new(temp_dir.path()).expect("Failed to reopen storage");
+    assert_eq!(storage.cleanup_corrupted().unwrap(), 0);
+    for id in &ids {
+        let memory = storage.get(id).expect("Record unreadable after reopen");
+        assert!(memory
+            .experience
+            .content
+            .starts_with("User: why does build"));
+    }
+}